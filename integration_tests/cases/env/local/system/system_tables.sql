@@ -1,4 +1,3 @@
-
 DROP TABLE IF EXISTS `01_system_table1`;
 
 CREATE TABLE `01_system_table1` (
@@ -9,20 +8,47 @@ CREATE TABLE `01_system_table1` (
     `value` double,
     timestamp KEY (timestamp)) ENGINE=Analytic;
 
+SELECT
+    `timestamp`,
+    `catalog`,
+    `schema`,
+    `table_name`,
+    `engine`
+FROM
+    system.public.tables
+WHERE
+    table_name = '01_system_table1';
+
+INSERT INTO `01_system_table1`
+    (`timestamp`, `arch`, `datacenter`, `hostname`, `value`)
+VALUES
+    (1695348000000, 'x86-64', 'china', 'host1', 1.0);
+
+SELECT
+    `table_name`,
+    `status`
+FROM
+    system.public.memtables
+WHERE
+    table_name = '01_system_table1';
 
--- TODO: when query table in system catalog, it will throw errors now
--- Couldn't find table in table container
--- SELECT
---     `timestamp`,
---     `catalog`,
---     `schema`,
---     `table_name`,
---     `engine`
--- FROM
---     system.public.tables
--- WHERE
---     table_name = '01_system_table1';
+SELECT
+    `table_name`,
+    `level`
+FROM
+    system.public.sst_files
+WHERE
+    table_name = '01_system_table1';
 
+SELECT
+    `table_name`,
+    `status`
+FROM
+    system.public.compactions
+WHERE
+    table_name = '01_system_table1';
 
 -- FIXME
 SHOW TABLES LIKE '01%';
+
+DROP TABLE IF EXISTS `01_system_table1`;
@@ -875,6 +875,7 @@ mod tests {
                     id: 1,
                     row_num: 0,
                     max_seq: 0,
+                    min_seq: 0,
                     storage_format: StorageFormat::default(),
                     associated_files: Vec::new(),
                 };
@@ -896,6 +897,7 @@ mod tests {
                     id: 1,
                     row_num: 0,
                     max_seq,
+                    min_seq: 0,
                     storage_format: StorageFormat::default(),
                     associated_files: Vec::new(),
                 };
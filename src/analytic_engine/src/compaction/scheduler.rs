@@ -107,6 +107,9 @@ pub trait CompactionScheduler {
 
     /// Schedule a compaction job to background workers.
     async fn schedule_table_compaction(&self, request: TableCompactionRequest) -> bool;
+
+    /// Whether compaction of the table is waiting to be scheduled.
+    fn is_compaction_pending(&self, table_id: TableId) -> bool;
 }
 
 // A FIFO queue that remove duplicate values by key.
@@ -279,6 +282,15 @@ impl OngoingTaskLimit {
         result
     }
 
+    #[inline]
+    fn contains_request(&self, table_id: TableId) -> bool {
+        self.request_buf
+            .read()
+            .unwrap()
+            .values
+            .contains_key(&table_id)
+    }
+
     #[inline]
     fn has_pending_requests(&self) -> bool {
         !self.request_buf.read().unwrap().is_empty()
@@ -300,6 +312,7 @@ pub type CompactionSchedulerRef = Arc<dyn CompactionScheduler + Send + Sync>;
 pub struct SchedulerImpl {
     sender: Sender<ScheduleTask>,
     running: Arc<AtomicBool>,
+    limit: Arc<OngoingTaskLimit>,
     handle: Mutex<JoinHandle<()>>,
 }
 
@@ -314,6 +327,11 @@ impl SchedulerImpl {
     ) -> Self {
         let (tx, rx) = mpsc::channel(config.schedule_channel_len);
         let running = Arc::new(AtomicBool::new(true));
+        let limit = Arc::new(OngoingTaskLimit {
+            ongoing_tasks: AtomicUsize::new(0),
+            request_buf: RwLock::new(RequestQueue::default()),
+            max_pending_compaction_tasks: config.max_pending_compaction_tasks,
+        });
 
         let mut worker = ScheduleWorker {
             sender: tx.clone(),
@@ -327,11 +345,7 @@ impl SchedulerImpl {
             write_sst_max_buffer_size,
             min_flush_interval_ms,
             scan_options,
-            limit: Arc::new(OngoingTaskLimit {
-                ongoing_tasks: AtomicUsize::new(0),
-                request_buf: RwLock::new(RequestQueue::default()),
-                max_pending_compaction_tasks: config.max_pending_compaction_tasks,
-            }),
+            limit: limit.clone(),
             running: running.clone(),
             memory_limit: MemoryLimit::new(config.memory_limit.as_byte() as usize),
         };
@@ -343,6 +357,7 @@ impl SchedulerImpl {
         Self {
             sender: tx,
             running,
+            limit,
             handle: Mutex::new(handle),
         }
     }
@@ -377,6 +392,10 @@ impl CompactionScheduler for SchedulerImpl {
             Ok(_) => true,
         }
     }
+
+    fn is_compaction_pending(&self, table_id: TableId) -> bool {
+        self.limit.contains_request(table_id)
+    }
 }
 
 struct OngoingTask {
@@ -721,6 +740,7 @@ fn new_picker_context(table_opts: &TableOptions) -> Option<PickerContext> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::table::data::tests::TableDataMocker;
 
     #[test]
     fn test_memory_usage_limit_apply() {
@@ -821,4 +841,35 @@ mod tests {
         assert!(q.is_empty());
         assert_eq!(0, q.len());
     }
+
+    #[test]
+    fn test_ongoing_task_limit_contains_request() {
+        let limit = OngoingTaskLimit {
+            ongoing_tasks: AtomicUsize::new(0),
+            request_buf: RwLock::new(RequestQueue::default()),
+            max_pending_compaction_tasks: 2,
+        };
+        let build_request = |table_id: u64| {
+            let table_data = TableDataMocker::default()
+                .table_id(TableId::new(table_id))
+                .build();
+            TableCompactionRequest::new(Arc::new(table_data)).0
+        };
+
+        assert!(!limit.contains_request(TableId::new(1)));
+        limit.add_request(build_request(1));
+        limit.add_request(build_request(2));
+        assert!(limit.contains_request(TableId::new(1)));
+        assert!(limit.contains_request(TableId::new(2)));
+
+        // The oldest request is dropped once the buffer is full.
+        limit.add_request(build_request(3));
+        assert!(!limit.contains_request(TableId::new(1)));
+        assert!(limit.contains_request(TableId::new(3)));
+
+        // Drained requests are no longer pending.
+        limit.drain_requests(2);
+        assert!(!limit.contains_request(TableId::new(2)));
+        assert!(!limit.contains_request(TableId::new(3)));
+    }
 }
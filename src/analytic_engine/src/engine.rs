@@ -29,8 +29,8 @@ use table_engine::{
     engine::{
         Close, CloseShardRequest, CloseTableRequest, CreateTableParams, CreateTableRequest,
         DropTableRequest, OpenShard, OpenShardRequest, OpenShardResult, OpenTableNoCause,
-        OpenTableRequest, OpenTableWithCause, Result, ShardStats, TableDef, TableEngine,
-        TableEngineStats, Unexpected,
+        OpenTableRequest, OpenTableWithCause, ReadTableBackup, Result, ShardStats, TableBackupMeta,
+        TableDef, TableEngine, TableEngineStats, Unexpected,
     },
     table::{SchemaId, TableRef},
     ANALYTIC_ENGINE_TYPE,
@@ -249,6 +249,14 @@ impl TableEngine for TableEngineImpl {
 
         Ok(Some(table_engine_stats))
     }
+
+    async fn read_table_backup(&self, path: &str) -> Result<TableBackupMeta> {
+        self.instance
+            .read_table_backup(path)
+            .await
+            .box_err()
+            .context(ReadTableBackup { path })
+    }
 }
 
 /// Collect the table engine stats from the two provided metric.
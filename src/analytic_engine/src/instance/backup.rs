@@ -0,0 +1,577 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Backup and restore logic of instance.
+//!
+//! A backup is a directory in the following layout:
+//! ```text
+//! {backup_root}/{path}/manifest             Snapshot of the table meta and version
+//! {backup_root}/{path}/ssts/{file_id}.sst   Ssts referenced by the snapshot
+//! {backup_root}/{path}/ssts/{file_id}.sst.* Files associated with the ssts
+//! ```
+//!
+//! The `path` of a backup must be relative to the configured `backup_root`,
+//! and can't escape it.
+//!
+//! The manifest is written after all the ssts are copied, so a backup without
+//! the manifest is incomplete.
+//!
+//! The restored ssts keep their sequences, which are recorded in the ssts, so
+//! the sequence of the restored table is advanced past them to make the rows
+//! written later override the restored ones.
+
+use std::{
+    collections::HashMap,
+    path::{Component, Path as StdPath},
+    sync::Arc,
+};
+
+use common_types::{time::TimeRange, SequenceNumber};
+use generic_error::{BoxError, GenericError};
+use horaedbproto::manifest as manifest_pb;
+use logger::info;
+use macros::define_result;
+use object_store::{LocalFileSystem, ObjectStoreError, ObjectStoreRef, Path};
+use prost::Message;
+use snafu::{ensure, Backtrace, OptionExt, ResultExt, Snafu};
+use table_engine::{
+    engine::TableBackupMeta,
+    table::{BackupRequest, BackupStats, FlushRequest, RestoreRequest},
+};
+
+use crate::{
+    instance::{self, Instance},
+    manifest::{
+        meta_edit::{
+            AddTableMeta, MetaEdit, MetaEditRequest, MetaUpdate, Snapshot, VersionEditMeta,
+        },
+        meta_snapshot::MetaSnapshot,
+    },
+    sst::file::{FileMeta, Level},
+    table::{data::TableDataRef, sst_util, version::TableVersionMeta, version_edit::AddFile},
+};
+
+const MANIFEST_NAME: &str = "manifest";
+const SST_DIR: &str = "ssts";
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "Invalid backup path, path:{}, msg:{}.\nBacktrace:\n{}",
+        path,
+        msg,
+        backtrace
+    ))]
+    InvalidBackupPath {
+        path: String,
+        msg: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to create backup dir, path:{}, err:{}", path, source))]
+    CreateBackupDir {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to open backup store, path:{}, err:{}", path, source))]
+    OpenBackupStore {
+        path: String,
+        source: ObjectStoreError,
+    },
+
+    #[snafu(display("Failed to flush table, table:{}, err:{}", table, source))]
+    FlushTable {
+        table: String,
+        source: crate::instance::Error,
+    },
+
+    #[snafu(display("Failed to copy file, from:{}, to:{}, err:{}", from, to, source))]
+    CopyFile {
+        from: String,
+        to: String,
+        source: ObjectStoreError,
+    },
+
+    #[snafu(display("Failed to write manifest of backup, path:{}, err:{}", path, source))]
+    WriteManifest {
+        path: String,
+        source: ObjectStoreError,
+    },
+
+    #[snafu(display("Failed to read manifest of backup, path:{}, err:{}", path, source))]
+    ReadManifest {
+        path: String,
+        source: ObjectStoreError,
+    },
+
+    #[snafu(display("Failed to decode manifest of backup, path:{}, err:{}", path, source))]
+    DecodeManifest {
+        path: String,
+        source: prost::DecodeError,
+    },
+
+    #[snafu(display("Failed to convert manifest of backup, path:{}, err:{}", path, source))]
+    ConvertManifest { path: String, source: GenericError },
+
+    #[snafu(display(
+        "No table in the manifest of backup, path:{}.\nBacktrace:\n{}",
+        path,
+        backtrace
+    ))]
+    EmptyManifest { path: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Only empty table can be restored, table:{}, num_ssts:{}, last_sequence:{}, flushed_sequence:{}.\nBacktrace:\n{}",
+        table,
+        num_ssts,
+        last_sequence,
+        flushed_sequence,
+        backtrace
+    ))]
+    TableNotEmpty {
+        table: String,
+        num_ssts: usize,
+        last_sequence: SequenceNumber,
+        flushed_sequence: SequenceNumber,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to alloc file id, table:{}, err:{}", table, source))]
+    AllocFileId {
+        table: String,
+        source: crate::table::data::Error,
+    },
+
+    #[snafu(display("Failed to store version edit, table:{}, err:{}", table, source))]
+    StoreVersionEdit { table: String, source: GenericError },
+
+    #[snafu(display(
+        "Failed to advance wal sequence, table:{}, sequence:{}, err:{}",
+        table,
+        sequence,
+        source
+    ))]
+    AdvanceWalSequence {
+        table: String,
+        sequence: SequenceNumber,
+        source: wal::manager::Error,
+    },
+}
+
+define_result!(Error);
+
+impl Instance {
+    /// Backup the table to the path in the `request`.
+    ///
+    /// The table is flushed first, then the ssts of the current version are
+    /// copied with a snapshot of the manifest.
+    pub async fn backup_table(
+        &self,
+        table_data: &TableDataRef,
+        request: BackupRequest,
+    ) -> Result<BackupStats> {
+        self.manual_flush_table(table_data, FlushRequest::default())
+            .await
+            .context(FlushTable {
+                table: &table_data.name,
+            })?;
+
+        // Ssts in the read view won't be purged until the view is dropped, so they
+        // are kept during copying even if they are compacted.
+        let version = table_data.current_version();
+        let flushed_sequence = version.flushed_sequence();
+        let read_view = version.pick_read_view(TimeRange::min_to_max());
+        let files: HashMap<_, _> = read_view
+            .leveled_ssts
+            .iter()
+            .enumerate()
+            .flat_map(|(level, ssts)| {
+                ssts.iter().map(move |sst| {
+                    let add_file = AddFile {
+                        level: Level::from(level as u16),
+                        file: sst.meta(),
+                    };
+                    (sst.id(), add_file)
+                })
+            })
+            .collect();
+
+        let table_store = self.space_store.store_picker().default_store();
+        let (backup_store, backup_path) =
+            open_backup_store(table_store, &self.backup_root, &request.path, true)?;
+        let mut stats = BackupStats::default();
+        for add_file in files.values() {
+            let file_meta = &add_file.file;
+            copy_file(
+                table_store,
+                &table_data.set_sst_file_path(file_meta.id),
+                &backup_store,
+                &sst_path_in_backup(&backup_path, file_meta.id),
+            )
+            .await?;
+            for associated_file in &file_meta.associated_files {
+                let from = Path::from(associated_file.as_str());
+                let to = associated_file_path_in_backup(&backup_path, &from);
+                copy_file(table_store, &from, &backup_store, &to).await?;
+            }
+
+            stats.num_ssts += 1;
+            stats.total_size += file_meta.size;
+        }
+
+        let max_file_id = files.keys().copied().max().unwrap_or_default();
+        let snapshot = Snapshot {
+            end_seq: 0,
+            data: Some(MetaSnapshot {
+                table_meta: AddTableMeta {
+                    space_id: table_data.space_id,
+                    table_id: table_data.id,
+                    table_name: table_data.name.clone(),
+                    schema: table_data.schema(),
+                    opts: (*table_data.table_options()).clone(),
+                },
+                version_meta: Some(TableVersionMeta {
+                    flushed_sequence,
+                    files,
+                    max_file_id,
+                }),
+            }),
+        };
+        let manifest_path = backup_path.child(MANIFEST_NAME);
+        let payload = manifest_pb::Snapshot::from(snapshot).encode_to_vec();
+        backup_store
+            .put(&manifest_path, payload.into())
+            .await
+            .context(WriteManifest {
+                path: &request.path,
+            })?;
+
+        info!(
+            "Instance backup table, table:{}, path:{}, stats:{:?}",
+            table_data.name, request.path, stats
+        );
+
+        Ok(stats)
+    }
+
+    /// Read the meta data of the table from the backup at `path`.
+    pub async fn read_table_backup(&self, path: &str) -> Result<TableBackupMeta> {
+        let table_store = self.space_store.store_picker().default_store();
+        let (backup_store, backup_path) =
+            open_backup_store(table_store, &self.backup_root, path, false)?;
+        let snapshot = read_backup_manifest(&backup_store, &backup_path, path).await?;
+
+        let table_meta = snapshot.table_meta;
+        Ok(TableBackupMeta {
+            table_name: table_meta.table_name,
+            table_schema: table_meta.schema,
+            table_options: table_meta.opts.to_raw_map(),
+        })
+    }
+
+    /// Restore the ssts in the backup to the table, the table must be empty.
+    ///
+    /// The ssts are copied with new file ids allocated by the table.
+    pub async fn restore_table(
+        &self,
+        table_data: &TableDataRef,
+        request: RestoreRequest,
+    ) -> Result<BackupStats> {
+        let table_store = self.space_store.store_picker().default_store();
+        let (backup_store, backup_path) =
+            open_backup_store(table_store, &self.backup_root, &request.path, false)?;
+        let snapshot = read_backup_manifest(&backup_store, &backup_path, &request.path).await?;
+
+        // Hold the serial executor to avoid flush during restoring.
+        let _serial_exec = table_data.serial_exec.lock().await;
+        let version = table_data.current_version();
+        let num_ssts = version.snapshot().files.len();
+        let (last_sequence, flushed_sequence) =
+            (table_data.last_sequence(), version.flushed_sequence());
+        // The rows in the memtables are not flushed yet.
+        ensure!(
+            num_ssts == 0 && last_sequence <= flushed_sequence,
+            TableNotEmpty {
+                table: &table_data.name,
+                num_ssts,
+                last_sequence,
+                flushed_sequence,
+            }
+        );
+
+        let version_meta = snapshot.version_meta.unwrap_or_default();
+        let mut stats = BackupStats::default();
+        let mut files_to_add = Vec::with_capacity(version_meta.files.len());
+        let mut max_sequence = 0;
+        for add_file in version_meta.ordered_files() {
+            let file_meta = add_file.file;
+            max_sequence = max_sequence.max(file_meta.max_seq);
+            let file_id = table_data
+                .alloc_file_id(&self.space_store.manifest)
+                .await
+                .context(AllocFileId {
+                    table: &table_data.name,
+                })?;
+            let sst_path = table_data.set_sst_file_path(file_id);
+            copy_file(
+                &backup_store,
+                &sst_path_in_backup(&backup_path, file_meta.id),
+                table_store,
+                &sst_path,
+            )
+            .await?;
+
+            // Associated files are named after the sst, e.g. `{file_id}.sst.metadata`, so
+            // they are renamed with the new sst.
+            let old_sst_name = sst_util::sst_file_name(file_meta.id);
+            let mut associated_files = Vec::with_capacity(file_meta.associated_files.len());
+            for associated_file in &file_meta.associated_files {
+                let from = associated_file_path_in_backup(
+                    &backup_path,
+                    &Path::from(associated_file.as_str()),
+                );
+                let file_name = from.filename().unwrap_or_default();
+                let suffix = file_name.strip_prefix(&old_sst_name).unwrap_or(file_name);
+                let to = Path::from(format!("{sst_path}{suffix}"));
+                copy_file(&backup_store, &from, table_store, &to).await?;
+                associated_files.push(to.to_string());
+            }
+
+            stats.num_ssts += 1;
+            stats.total_size += file_meta.size;
+            files_to_add.push(AddFile {
+                level: add_file.level,
+                file: FileMeta {
+                    id: file_id,
+                    associated_files,
+                    ..file_meta
+                },
+            });
+        }
+
+        // Advance the sequence of the table past the restored ssts, otherwise the rows
+        // written later may be shadowed by the restored rows with the same key.
+        let table_location = table_data.table_location();
+        let wal_location =
+            instance::create_wal_location(table_location.id, table_location.shard_info);
+        self.space_store
+            .wal_manager
+            .advance_sequence_num(wal_location, max_sequence)
+            .await
+            .context(AdvanceWalSequence {
+                table: &table_data.name,
+                sequence: max_sequence,
+            })?;
+
+        let edit_req = {
+            let edit_meta = VersionEditMeta {
+                space_id: table_data.space_id,
+                table_id: table_data.id,
+                flushed_sequence: max_sequence,
+                files_to_add,
+                files_to_delete: vec![],
+                mems_to_remove: vec![],
+                max_file_id: 0,
+            };
+            MetaEditRequest {
+                shard_info: table_data.shard_info,
+                meta_edit: MetaEdit::Update(MetaUpdate::VersionEdit(edit_meta)),
+                table_catalog_info: table_data.table_catalog_info.clone(),
+            }
+        };
+        // The edit is also applied to the version of the table by the manifest.
+        self.space_store
+            .manifest
+            .apply_edit(edit_req)
+            .await
+            .context(StoreVersionEdit {
+                table: &table_data.name,
+            })?;
+        if table_data.last_sequence() < max_sequence {
+            table_data.set_last_sequence(max_sequence);
+        }
+
+        info!(
+            "Instance restore table, table:{}, path:{}, stats:{:?}",
+            table_data.name, request.path, stats
+        );
+
+        Ok(stats)
+    }
+}
+
+/// Open the store of the backup at `path` under the `backup_root`, returns the
+/// store and the path of the backup in it.
+///
+/// An absolute `backup_root` is treated as a directory of the local file
+/// system, otherwise it is a directory in the `table_store`.
+fn open_backup_store(
+    table_store: &ObjectStoreRef,
+    backup_root: &str,
+    path: &str,
+    create_dir: bool,
+) -> Result<(ObjectStoreRef, Path)> {
+    ensure!(
+        !path.is_empty() && is_normal_path(path),
+        InvalidBackupPath {
+            path,
+            msg: "path must be relative to the backup root without `.` or `..`",
+        }
+    );
+
+    let root = StdPath::new(backup_root);
+    if !root.is_absolute() {
+        // The ssts of the tables are stored under `{space_id}/{table_id}`, the backups
+        // must not be mixed with them.
+        let is_table_dir = root
+            .components()
+            .next()
+            .map(|c| c.as_os_str().to_string_lossy().parse::<u64>().is_ok())
+            .unwrap_or(true);
+        ensure!(
+            is_normal_path(backup_root) && !is_table_dir,
+            InvalidBackupPath {
+                path: backup_root,
+                msg: "backup root must not be a dir of the table data",
+            }
+        );
+
+        return Ok((
+            table_store.clone(),
+            Path::from(format!("{backup_root}/{path}")),
+        ));
+    }
+
+    let dir = root.join(path);
+    if create_dir {
+        std::fs::create_dir_all(&dir).context(CreateBackupDir { path })?;
+    }
+    let store = LocalFileSystem::new_with_prefix(dir).context(OpenBackupStore { path })?;
+
+    Ok((Arc::new(store), Path::default()))
+}
+
+/// Whether the `path` is relative and only consists of normal components.
+fn is_normal_path(path: &str) -> bool {
+    StdPath::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+}
+
+async fn read_backup_manifest(
+    store: &ObjectStoreRef,
+    backup_path: &Path,
+    path: &str,
+) -> Result<MetaSnapshot> {
+    let payload = store
+        .get(&backup_path.child(MANIFEST_NAME))
+        .await
+        .context(ReadManifest { path })?
+        .bytes()
+        .await
+        .context(ReadManifest { path })?;
+    let snapshot_pb = manifest_pb::Snapshot::decode(payload).context(DecodeManifest { path })?;
+    let snapshot = Snapshot::try_from(snapshot_pb)
+        .box_err()
+        .context(ConvertManifest { path })?;
+
+    snapshot.data.context(EmptyManifest { path })
+}
+
+#[inline]
+fn sst_path_in_backup(backup_path: &Path, file_id: u64) -> Path {
+    backup_path
+        .child(SST_DIR)
+        .child(sst_util::sst_file_name(file_id))
+}
+
+#[inline]
+fn associated_file_path_in_backup(backup_path: &Path, file_path: &Path) -> Path {
+    backup_path
+        .child(SST_DIR)
+        .child(file_path.filename().unwrap_or_default())
+}
+
+async fn copy_file(
+    from_store: &ObjectStoreRef,
+    from: &Path,
+    to_store: &ObjectStoreRef,
+    to: &Path,
+) -> Result<()> {
+    let copy_err = || CopyFile {
+        from: from.to_string(),
+        to: to.to_string(),
+    };
+    let bytes = from_store
+        .get(from)
+        .await
+        .with_context(copy_err)?
+        .bytes()
+        .await
+        .with_context(copy_err)?;
+
+    to_store.put(to, bytes).await.with_context(copy_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_paths() {
+        let backup_path = Path::from("backups/t1");
+        assert_eq!(
+            "backups/t1/ssts/100.sst",
+            sst_path_in_backup(&backup_path, 100).to_string()
+        );
+
+        let meta_path = Path::from(sst_util::new_metadata_path("1/2/100.sst"));
+        assert_eq!(
+            "backups/t1/ssts/100.sst.metadata",
+            associated_file_path_in_backup(&backup_path, &meta_path).to_string()
+        );
+    }
+
+    #[test]
+    fn test_open_backup_store() {
+        let table_store: ObjectStoreRef = Arc::new(LocalFileSystem::new());
+        let (_, path) = open_backup_store(&table_store, "backups", "t1", false).unwrap();
+        assert_eq!("backups/t1", path.to_string());
+
+        let dir = tempfile::tempdir().unwrap();
+        let backup_root = dir.path().to_str().unwrap();
+        let (_, path) = open_backup_store(&table_store, backup_root, "t1/v1", true).unwrap();
+        assert_eq!(Path::default(), path);
+        assert!(dir.path().join("t1/v1").exists());
+    }
+
+    #[test]
+    fn test_open_backup_store_invalid_path() {
+        let table_store: ObjectStoreRef = Arc::new(LocalFileSystem::new());
+        let dir = tempfile::tempdir().unwrap();
+        let backup_root = dir.path().to_str().unwrap();
+        for path in ["", "/tmp/t1", "../t1", "t1/../../t2", "./t1"] {
+            assert!(open_backup_store(&table_store, "backups", path, false).is_err());
+            assert!(open_backup_store(&table_store, backup_root, path, true).is_err());
+        }
+
+        for backup_root in ["0", "1/2", "../backups", ""] {
+            assert!(open_backup_store(&table_store, backup_root, "t1", false).is_err());
+        }
+    }
+}
@@ -28,15 +28,16 @@ use table_engine::{
 use crate::{
     instance::{
         engine::{
-            CreateOpenFailedTable, InvalidOptions, Result, TableNotExist,
-            TryCreateRandomPartitionTableInOverwriteMode, WriteManifest,
+            AggregationInNonAggregateMode, CreateOpenFailedTable, InvalidOptions, Result,
+            TableNotExist, TryCreateRandomPartitionTableInOverwriteMode, WriteManifest,
         },
         Instance,
     },
     manifest::meta_edit::{AddTableMeta, MetaEdit, MetaEditRequest, MetaUpdate},
     space::SpaceRef,
     table::data::{TableCatalogInfo, TableDataRef, TableShardInfo},
-    table_options, TableOptions,
+    table_options::{self, UpdateMode},
+    TableOptions,
 };
 
 impl Instance {
@@ -61,6 +62,21 @@ impl Instance {
             );
         }
 
+        if table_opts.update_mode != UpdateMode::Aggregate {
+            if let Some(column) = params
+                .table_schema
+                .columns()
+                .iter()
+                .find(|column| column.aggregation.is_some())
+            {
+                return AggregationInNonAggregateMode {
+                    table: &params.table_name,
+                    column: &column.name,
+                }
+                .fail();
+            }
+        }
+
         Ok(table_opts)
     }
 
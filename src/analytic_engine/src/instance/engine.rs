@@ -245,6 +245,15 @@ pub enum Error {
     ))]
     TryCreateRandomPartitionTableInOverwriteMode { table: String, backtrace: Backtrace },
 
+    #[snafu(display(
+        "Column aggregation is only supported in aggregate mode, table:{table}, column:{column}.\nBacktrace:\n{backtrace}",
+    ))]
+    AggregationInNonAggregateMode {
+        table: String,
+        column: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display(
         "Failed to purge wal, wal_location:{:?}, sequence:{}",
         wal_location,
@@ -264,12 +273,11 @@ impl From<Error> for table_engine::engine::Error {
         match &err {
             Error::InvalidOptions { table, .. }
             | Error::SpaceNotExist { table, .. }
-            | Error::TryCreateRandomPartitionTableInOverwriteMode { table, .. } => {
-                Self::InvalidArguments {
-                    table: table.clone(),
-                    source: Box::new(err),
-                }
-            }
+            | Error::TryCreateRandomPartitionTableInOverwriteMode { table, .. }
+            | Error::AggregationInNonAggregateMode { table, .. } => Self::InvalidArguments {
+                table: table.clone(),
+                source: Box::new(err),
+            },
             Error::WriteManifest { .. } => Self::WriteMeta {
                 source: Box::new(err),
             },
@@ -579,6 +579,8 @@ impl FlushTask {
         };
 
         let max_sequence = sampling_mem.mem.last_sequence();
+        // The rows not flushed yet are all written after the flushed sequence.
+        let min_sequence = self.table_data.current_version().flushed_sequence() + 1;
         let time_ranges = sampling_mem.sampler.ranges();
 
         info!("Flush sampling memtable, table_id:{:?}, table_name:{:?}, request_id:{}, sampling memtable time_ranges:{:?}",
@@ -719,6 +721,7 @@ impl FlushTask {
                     row_num: sst_info.row_num as u64,
                     time_range: sst_info.time_range,
                     max_seq: sst_meta.max_sequence,
+                    min_seq: min_sequence,
                     storage_format: sst_info.storage_format,
                     associated_files: vec![sst_info.meta_path],
                 },
@@ -744,6 +747,8 @@ impl FlushTask {
             }
         };
         let max_sequence = memtable_state.last_sequence();
+        // The rows not flushed yet are all written after the flushed sequence.
+        let min_sequence = self.table_data.current_version().flushed_sequence() + 1;
         let sst_meta = MetaData {
             min_key,
             max_key,
@@ -819,6 +824,7 @@ impl FlushTask {
             size: sst_info.file_size as u64,
             time_range: sst_info.time_range,
             max_seq: memtable_state.last_sequence(),
+            min_seq: min_sequence,
             storage_format: sst_info.storage_format,
             associated_files: vec![sst_info.meta_path],
         }))
@@ -999,6 +1005,7 @@ impl SpaceStore {
                 store_picker: self.store_picker(),
                 merge_iter_options: iter_options.clone(),
                 need_dedup: table_options.need_dedup(),
+                merge_duplicates: table_options.merge_duplicates(),
                 reverse: false,
             });
             // Add all ssts in compaction input to builder.
@@ -1011,11 +1018,10 @@ impl SpaceStore {
         };
 
         let record_batch_stream = if table_options.need_dedup() {
-            row_iter::record_batch_with_key_iter_to_stream(DedupIterator::new(
-                request_id.clone(),
-                merge_iter,
-                iter_options,
-            ))
+            row_iter::record_batch_with_key_iter_to_stream(
+                DedupIterator::new(request_id.clone(), merge_iter, iter_options)
+                    .with_merge_duplicates(table_options.merge_duplicates()),
+            )
         } else {
             row_iter::record_batch_with_key_iter_to_stream(merge_iter)
         };
@@ -1116,6 +1122,12 @@ impl SpaceStore {
                 size: sst_file_size,
                 row_num: sst_row_num,
                 max_seq: sst_meta.max_sequence,
+                min_seq: input
+                    .files
+                    .iter()
+                    .map(|file| file.min_sequence())
+                    .min()
+                    .unwrap_or_default(),
                 time_range: sst_meta.time_range,
                 storage_format: sst_info.storage_format,
                 associated_files: vec![sst_info.meta_path],
@@ -1251,6 +1263,7 @@ fn build_mem_table_iter(
         sequence: common_types::MAX_SEQUENCE_NUMBER,
         row_projector_builder,
         need_dedup: table_data.dedup(),
+        merge_duplicates: table_data.merge_duplicates(),
         reverse: false,
         metrics_collector: None,
         time_range: TimeRange::min_to_max(),
@@ -21,6 +21,7 @@
 //! divided into the sub crates
 
 pub(crate) mod alter;
+mod backup;
 mod close;
 mod create;
 mod drop;
@@ -31,6 +32,7 @@ pub mod open;
 mod read;
 mod reorder_memtable;
 pub(crate) mod serial_executor;
+mod verify;
 pub mod wal_replayer;
 pub(crate) mod write;
 
@@ -193,6 +195,8 @@ pub struct Instance {
     pub(crate) recover_mode: RecoverMode,
     pub(crate) wal_encode: WalEncodeConfig,
     pub(crate) disable_wal: bool,
+    /// Root dir of the table backups
+    pub(crate) backup_root: String,
 }
 
 impl Instance {
@@ -208,6 +212,11 @@ impl Instance {
             .context(StopScheduler)
     }
 
+    /// Whether compaction of the table is waiting to be scheduled.
+    pub fn is_compaction_pending(&self, table_id: TableId) -> bool {
+        self.compaction_scheduler.is_compaction_pending(table_id)
+    }
+
     pub async fn manual_flush_table(
         &self,
         table_data: &TableDataRef,
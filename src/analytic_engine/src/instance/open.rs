@@ -153,6 +153,7 @@ impl Instance {
             recover_mode: ctx.config.recover_mode,
             wal_encode: ctx.config.wal_encode,
             disable_wal: ctx.config.wal.disable_data,
+            backup_root: ctx.config.backup_root.clone(),
         });
 
         Ok(instance)
@@ -28,24 +28,26 @@ use common_types::{
     projected_schema::ProjectedSchema,
     record_batch::{FetchedRecordBatch, RecordBatch},
     schema::RecordSchema,
-    time::TimeRange,
+    time::{TimeRange, Timestamp},
+    SequenceNumber,
 };
 use futures::stream::Stream;
 use generic_error::BoxError;
 use logger::debug;
 use macros::define_result;
-use snafu::{ResultExt, Snafu};
+use snafu::{ensure, Backtrace, OptionExt, ResultExt, Snafu};
 use table_engine::{
     stream::{
         self, ErrWithSource, PartitionedStreams, RecordBatchStream, SendableRecordBatchStream,
     },
-    table::ReadRequest,
+    table::{ReadAsOf, ReadRequest},
 };
 use time_ext::current_time_millis;
 use trace_metric::Metric;
 
 use crate::{
     instance::{Instance, ScanType, SstReadOptionsBuilder},
+    memtable::MemtableType,
     row_iter::{
         chain,
         chain::{ChainConfig, ChainIterator},
@@ -79,6 +81,42 @@ pub enum Error {
         table: String,
         source: crate::row_iter::chain::Error,
     },
+
+    #[snafu(display(
+        "Time travel is not supported by table, table:{}, msg:{}.\nBacktrace:\n{}",
+        table,
+        msg,
+        backtrace
+    ))]
+    TimeTravelNotSupported {
+        table: String,
+        msg: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Timestamp is out of the time travel history, table:{}, timestamp:{:?}.\nBacktrace:\n{}",
+        table,
+        timestamp,
+        backtrace
+    ))]
+    TimeTravelOutOfRange {
+        table: String,
+        timestamp: Timestamp,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Sequence is out of the time travel history, table:{}, sequence:{}.\nBacktrace:\n{}",
+        table,
+        sequence,
+        backtrace
+    ))]
+    SequenceOutOfRange {
+        table: String,
+        sequence: SequenceNumber,
+        backtrace: Backtrace,
+    },
 }
 
 define_result!(Error);
@@ -196,11 +234,18 @@ impl Instance {
         table_options: &TableOptions,
         sst_read_options_builder: SstReadOptionsBuilder,
     ) -> Result<Vec<DedupIterator<MergeIterator>>> {
+        let as_of_sequence = as_of_sequence(table_data, table_options, request)?;
         // Current visible sequence
-        let sequence = table_data.last_sequence();
+        let sequence = as_of_sequence.unwrap_or_else(|| table_data.last_sequence());
         let time_range = request.predicate.time_range();
         let version = table_data.current_version();
-        let read_views = self.partition_ssts_and_memtables(time_range, version, table_options);
+        let read_views = self.partition_ssts_and_memtables(
+            table_data,
+            time_range,
+            version,
+            table_options,
+            as_of_sequence,
+        )?;
         let iter_options = self.make_iter_options(table_options.num_rows_per_row_group);
 
         let mut iters = Vec::with_capacity(read_views.len());
@@ -222,6 +267,7 @@ impl Instance {
                 store_picker: self.space_store.store_picker(),
                 merge_iter_options: iter_options.clone(),
                 need_dedup: table_options.need_dedup(),
+                merge_duplicates: table_options.merge_duplicates(),
                 reverse: false,
             };
 
@@ -235,7 +281,8 @@ impl Instance {
                     table: &table_data.name,
                 })?;
             let dedup_iter =
-                DedupIterator::new(request.request_id.clone(), merge_iter, iter_options.clone());
+                DedupIterator::new(request.request_id.clone(), merge_iter, iter_options.clone())
+                    .with_merge_duplicates(table_options.merge_duplicates());
 
             iters.push(dedup_iter);
         }
@@ -258,9 +305,17 @@ impl Instance {
     ) -> Result<Vec<ChainIterator>> {
         let projected_schema = request.projected_schema.clone();
 
+        let as_of_sequence = as_of_sequence(table_data, table_options, request)?;
+        let sequence = as_of_sequence.unwrap_or_else(|| table_data.last_sequence());
         let time_range = request.predicate.time_range();
         let version = table_data.current_version();
-        let read_views = self.partition_ssts_and_memtables(time_range, version, table_options);
+        let read_views = self.partition_ssts_and_memtables(
+            table_data,
+            time_range,
+            version,
+            table_options,
+            as_of_sequence,
+        )?;
 
         let mut iters = Vec::with_capacity(read_views.len());
         for (idx, read_view) in read_views.into_iter().enumerate() {
@@ -274,6 +329,7 @@ impl Instance {
                 num_streams_to_prefetch: self.scan_options.num_streams_to_prefetch,
                 space_id: table_data.space_id,
                 table_id: table_data.id,
+                sequence,
                 projected_schema: projected_schema.clone(),
                 predicate: request.predicate.clone(),
                 sst_read_options_builder: sst_read_options_builder.clone(),
@@ -299,11 +355,21 @@ impl Instance {
 
     fn partition_ssts_and_memtables(
         &self,
+        table_data: &TableData,
         time_range: TimeRange,
         version: &TableVersion,
         table_options: &TableOptions,
-    ) -> Vec<ReadView> {
-        let read_view = version.pick_read_view(time_range);
+        as_of_sequence: Option<SequenceNumber>,
+    ) -> Result<Vec<ReadView>> {
+        let read_view = match as_of_sequence {
+            Some(sequence) => version
+                .pick_read_view_at_sequence(time_range, sequence)
+                .context(SequenceOutOfRange {
+                    table: &table_data.name,
+                    sequence,
+                })?,
+            None => version.pick_read_view(time_range),
+        };
 
         let segment_duration = match table_options.segment_duration {
             Some(v) => v.0,
@@ -311,12 +377,12 @@ impl Instance {
                 // Segment duration is unknown, the table maybe still in sampling phase
                 // or the segment duration is still not applied to the table options,
                 // just return one partition.
-                return vec![read_view];
+                return Ok(vec![read_view]);
             }
         };
         if read_view.contains_sampling() {
             // The table contains sampling memtable, just return one partition.
-            return vec![read_view];
+            return Ok(vec![read_view]);
         }
 
         // Collect the aligned ssts and memtables into the map.
@@ -346,7 +412,7 @@ impl Instance {
             entry.memtables.push(memtable);
         }
 
-        read_view_by_time.into_values().collect()
+        Ok(read_view_by_time.into_values().collect())
     }
 
     fn make_iter_options(&self, num_rows_per_row_group: usize) -> IterOptions {
@@ -356,6 +422,50 @@ impl Instance {
     }
 }
 
+/// Resolve the sequence of the time-travel read, returns `None` if the
+/// `request` reads the latest data.
+fn as_of_sequence(
+    table_data: &TableData,
+    table_options: &TableOptions,
+    request: &ReadRequest,
+) -> Result<Option<SequenceNumber>> {
+    let as_of = match request.opts.as_of {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    ensure!(
+        table_options.time_travel_retention().is_some(),
+        TimeTravelNotSupported {
+            table: &table_data.name,
+            msg: "time_travel_retention is not set",
+        }
+    );
+    // Only the skiplist memtable keeps the sequence of each row.
+    ensure!(
+        table_options.memtable_type == MemtableType::SkipList
+            && !table_data.enable_layered_memtable,
+        TimeTravelNotSupported {
+            table: &table_data.name,
+            msg: "only skiplist memtable is supported",
+        }
+    );
+
+    let sequence = match as_of {
+        ReadAsOf::Sequence(sequence) => sequence,
+        ReadAsOf::Timestamp(timestamp) => {
+            table_data
+                .sequence_at(timestamp)
+                .context(TimeTravelOutOfRange {
+                    table: &table_data.name,
+                    timestamp,
+                })?
+        }
+    };
+
+    Ok(Some(sequence.min(table_data.last_sequence())))
+}
+
 struct StreamStateOnMultiIters<I> {
     iters: Vec<I>,
     curr_iter_idx: usize,
@@ -0,0 +1,368 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Online verification and repair of the ssts of a table.
+//!
+//! Every sst referenced by the current version is checked against the
+//! [FileMeta] recorded by the table, including the existence and size of the
+//! object, the parquet footer, the custom meta data, the row number and the
+//! time range. The objects in the table directory which are neither referenced
+//! by the table nor held by any reader are reported as orphans.
+//!
+//! The repair removes the orphans and rebuilds the custom meta data file of
+//! the ssts whose meta data file is missing or broken. The rebuilt meta data
+//! only contains the fields recorded in the [FileMeta] and the current schema
+//! of the table, so it is only rebuilt if the columns of the sst match the
+//! schema.
+
+use std::{collections::HashSet, time::Duration};
+
+use bytes_ext::Bytes;
+use common_types::time::{TimeRange, Timestamp};
+use futures::TryStreamExt;
+use logger::{info, warn};
+use macros::define_result;
+use object_store::{ObjectStoreError, ObjectStoreRef, Path};
+use parquet_ext::{meta_data::fetch_parquet_metadata, ParquetMetaData as ParquetFileMetaData};
+use snafu::{ResultExt, Snafu};
+use table_engine::table::{SstIssue, SstIssueKind, VerifyReport, VerifyRequest};
+
+use crate::{
+    instance::Instance,
+    sst::{
+        file::{FileHandle, FileMeta},
+        manager::FileId,
+        meta_data::cache::MetaData,
+        parquet::{
+            async_reader::ChunkReaderAdapter,
+            encoding::{self, META_VERSION_CURRENT, META_VERSION_KEY},
+            meta_data::ParquetMetaData,
+        },
+    },
+    table::{data::TableDataRef, sst_util},
+};
+
+/// Objects modified within the period may be written by an ongoing flush or
+/// compaction, so they are never treated as orphans.
+const ORPHAN_GRACE_PERIOD: Duration = Duration::from_secs(3600);
+const SST_FILE_SUFFIX: &str = ".sst";
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to list objects, path:{}, err:{}", path, source))]
+    ListObjects {
+        path: String,
+        source: ObjectStoreError,
+    },
+}
+
+define_result!(Error);
+
+impl Instance {
+    /// Verify the ssts of the table, and repair the issues found if
+    /// `request.repair` is set.
+    pub async fn verify_table(
+        &self,
+        table_data: &TableDataRef,
+        request: VerifyRequest,
+    ) -> Result<VerifyReport> {
+        let store = self.space_store.store_picker().default_store();
+        let version = table_data.current_version();
+        // Ssts in the read view won't be purged until the view is dropped.
+        let read_view = version.pick_read_view(TimeRange::min_to_max());
+        let obsolete_ssts = version.obsolete_ssts();
+
+        let mut report = VerifyReport::default();
+        let mut referenced = HashSet::new();
+        for sst in read_view.leveled_ssts.iter().flatten() {
+            let sst_path = table_data.set_sst_file_path(sst.id());
+            verify_sst(
+                store,
+                table_data,
+                sst,
+                &sst_path,
+                request.repair,
+                &mut report.issues,
+            )
+            .await;
+            report.num_ssts += 1;
+
+            referenced.insert(sst_path.to_string());
+            referenced.insert(sst_util::new_metadata_path(sst_path.as_ref()));
+        }
+        // Ssts retained for time-travel reads are not verified, but they are not
+        // orphans either.
+        for sst in &obsolete_ssts {
+            let sst_path = table_data.set_sst_file_path(sst.id());
+            referenced.insert(sst_util::new_metadata_path(sst_path.as_ref()));
+            referenced.insert(sst_path.to_string());
+        }
+
+        let table_dir =
+            Path::from_iter([table_data.space_id.to_string(), table_data.id.to_string()]);
+        let objects: Vec<_> = store
+            .list(Some(&table_dir))
+            .await
+            .context(ListObjects {
+                path: table_dir.to_string(),
+            })?
+            .try_collect()
+            .await
+            .context(ListObjects {
+                path: table_dir.to_string(),
+            })?;
+        let grace_deadline = Timestamp::now()
+            .sub_duration_or_min(ORPHAN_GRACE_PERIOD)
+            .as_i64();
+        for object in objects {
+            let path = object.location;
+            let file_id = match path.filename().and_then(parse_sst_file_id) {
+                Some(v) => v,
+                None => continue,
+            };
+            // The ssts removed from the version may still be read by in-flight queries
+            // or waiting to be deleted by the purger, so the handles of the ssts are
+            // checked as the listing may be stale.
+            if referenced.contains(path.as_ref())
+                || object.last_modified.timestamp_millis() > grace_deadline
+                || version.is_file_alive(file_id)
+            {
+                continue;
+            }
+
+            let mut issue = SstIssue {
+                kind: SstIssueKind::Orphan,
+                path: path.to_string(),
+                msg: "object is not referenced by the table".to_string(),
+                repaired: false,
+            };
+            if request.repair {
+                match store.delete(&path).await {
+                    Ok(()) | Err(ObjectStoreError::NotFound { .. }) => issue.repaired = true,
+                    Err(e) => issue.msg = format!("{}, failed to remove it, err:{e}", issue.msg),
+                }
+            }
+            report.issues.push(issue);
+        }
+
+        info!(
+            "Instance verify table, table:{}, repair:{}, num_ssts:{}, num_issues:{}",
+            table_data.name,
+            request.repair,
+            report.num_ssts,
+            report.issues.len()
+        );
+
+        Ok(report)
+    }
+}
+
+async fn verify_sst(
+    store: &ObjectStoreRef,
+    table_data: &TableDataRef,
+    sst: &FileHandle,
+    sst_path: &Path,
+    repair: bool,
+    issues: &mut Vec<SstIssue>,
+) {
+    let file_meta = sst.meta();
+    let new_issue = |kind, msg: String| SstIssue {
+        kind,
+        path: sst_path.to_string(),
+        msg,
+        repaired: false,
+    };
+
+    let object = match store.head(sst_path).await {
+        Ok(v) => v,
+        Err(ObjectStoreError::NotFound { .. }) => {
+            issues.push(new_issue(
+                SstIssueKind::Missing,
+                "sst is not found".to_string(),
+            ));
+            return;
+        }
+        Err(e) => {
+            issues.push(new_issue(
+                SstIssueKind::Corrupted,
+                format!("failed to head sst, err:{e}"),
+            ));
+            return;
+        }
+    };
+    if object.size as u64 != file_meta.size {
+        issues.push(new_issue(
+            SstIssueKind::Mismatched,
+            format!(
+                "size mismatch, expect:{}, actual:{}",
+                file_meta.size, object.size
+            ),
+        ));
+    }
+
+    let reader = ChunkReaderAdapter::new(sst_path, store);
+    let parquet_meta = match fetch_parquet_metadata(object.size, &reader).await {
+        Ok((v, _)) => v,
+        Err(e) => {
+            issues.push(new_issue(
+                SstIssueKind::Corrupted,
+                format!("invalid parquet footer, err:{e}"),
+            ));
+            return;
+        }
+    };
+    let num_rows = parquet_meta.file_metadata().num_rows() as u64;
+    if num_rows != file_meta.row_num {
+        issues.push(new_issue(
+            SstIssueKind::Mismatched,
+            format!(
+                "row number mismatch, expect:{}, actual:{num_rows}",
+                file_meta.row_num
+            ),
+        ));
+    }
+
+    match MetaData::try_new(&parquet_meta, true, sst_path, store.clone()).await {
+        Ok(meta_data) => {
+            let custom = meta_data.custom();
+            if custom.time_range != file_meta.time_range {
+                issues.push(new_issue(
+                    SstIssueKind::Mismatched,
+                    format!(
+                        "time range mismatch, expect:{:?}, actual:{:?}",
+                        file_meta.time_range, custom.time_range
+                    ),
+                ));
+            }
+            if custom.max_sequence != file_meta.max_seq {
+                issues.push(new_issue(
+                    SstIssueKind::Mismatched,
+                    format!(
+                        "max sequence mismatch, expect:{}, actual:{}",
+                        file_meta.max_seq, custom.max_sequence
+                    ),
+                ));
+            }
+        }
+        Err(e) => {
+            let mut issue = new_issue(
+                SstIssueKind::Corrupted,
+                format!("invalid custom meta data, err:{e}"),
+            );
+            if repair {
+                match rebuild_meta_data(store, table_data, sst_path, &file_meta, &parquet_meta)
+                    .await
+                {
+                    Ok(()) => issue.repaired = true,
+                    Err(msg) => issue.msg = format!("{}, failed to rebuild it, {msg}", issue.msg),
+                }
+            }
+            issues.push(issue);
+        }
+    }
+}
+
+/// Rebuild the custom meta data file of the sst, returns the reason if it
+/// can't be rebuilt.
+async fn rebuild_meta_data(
+    store: &ObjectStoreRef,
+    table_data: &TableDataRef,
+    sst_path: &Path,
+    file_meta: &FileMeta,
+    parquet_meta: &ParquetFileMetaData,
+) -> std::result::Result<(), String> {
+    let file_metadata = parquet_meta.file_metadata();
+    let meta_version = file_metadata.key_value_metadata().and_then(|kvs| {
+        kvs.iter()
+            .find(|kv| kv.key == META_VERSION_KEY)
+            .and_then(|kv| kv.value.as_deref())
+    });
+    // The meta data of other versions is embedded in the sst, which can't be
+    // rebuilt without rewriting the sst.
+    if meta_version != Some(META_VERSION_CURRENT) {
+        return Err(format!(
+            "meta data is not stored in a separate file, version:{meta_version:?}"
+        ));
+    }
+
+    let schema = table_data.schema();
+    let sst_columns: Vec<_> = file_metadata
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| column.name())
+        .collect();
+    let table_columns: Vec<_> = schema
+        .columns()
+        .iter()
+        .map(|column| column.name.as_str())
+        .collect();
+    if sst_columns != table_columns {
+        return Err(format!(
+            "columns of sst don't match the table schema, sst:{sst_columns:?}, table:{table_columns:?}"
+        ));
+    }
+
+    let meta_data = ParquetMetaData {
+        min_key: Bytes::new(),
+        max_key: Bytes::new(),
+        time_range: file_meta.time_range,
+        max_sequence: file_meta.max_seq,
+        schema,
+        parquet_filter: None,
+        column_values: None,
+    };
+    let bytes = encoding::encode_sst_meta_data(meta_data).map_err(|e| e.to_string())?;
+    let meta_path = Path::from(sst_util::new_metadata_path(sst_path.as_ref()));
+    store
+        .put(&meta_path, bytes)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    warn!(
+        "Rebuild meta data of sst, table:{}, path:{}",
+        table_data.name, meta_path
+    );
+
+    Ok(())
+}
+
+/// Parse the id of the sst from the name of the sst or its associated files,
+/// e.g. `{file_id}.sst` or `{file_id}.sst.metadata`.
+fn parse_sst_file_id(file_name: &str) -> Option<FileId> {
+    let end = file_name.find(SST_FILE_SUFFIX)?;
+    let suffix = &file_name[end + SST_FILE_SUFFIX.len()..];
+    if !suffix.is_empty() && !suffix.starts_with('.') {
+        return None;
+    }
+
+    file_name[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sst_file_id() {
+        assert_eq!(Some(100), parse_sst_file_id("100.sst"));
+        assert_eq!(Some(100), parse_sst_file_id("100.sst.metadata"));
+        assert_eq!(None, parse_sst_file_id("100.sstx"));
+        assert_eq!(None, parse_sst_file_id("a.sst"));
+        assert_eq!(None, parse_sst_file_id("manifest"));
+    }
+}
@@ -200,6 +200,7 @@ impl EncodeContext {
                 num_nulls: None,
                 num_datums: Some(self.row_group.num_rows()),
                 datum_kind: col_schema.data_type,
+                value_encoding: None,
             };
             let sz = enc.estimated_encoded_size(col_iter.clone(), &mut hint);
             let mut buf = Vec::with_capacity(sz);
@@ -552,6 +553,7 @@ impl<'a> Writer<'a> {
         );
 
         table_data.set_last_sequence(sequence);
+        table_data.record_sequence_history(sequence);
 
         // Collect metrics.
         let num_columns = row_group.schema().num_columns();
@@ -138,6 +138,12 @@ pub struct Config {
     pub remote_engine_client: remote_engine_client::config::Config,
 
     pub metrics: MetricsOptions,
+
+    /// Root dir of the table backups, the paths of backups are relative to it.
+    ///
+    /// An absolute path is a dir of the local file system, otherwise it is a
+    /// dir in the object store of the tables.
+    pub backup_root: String,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
@@ -209,6 +215,7 @@ impl Default for Config {
             recover_mode: RecoverMode::TableBased,
             metrics: MetricsOptions::default(),
             mutable_segment_switch_threshold: ReadableSize::mb(3),
+            backup_root: "backups".to_string(),
         }
     }
 }
@@ -42,11 +42,14 @@ use parquet::data_type::AsBytes;
 use skiplist::{ArenaSlice, BytewiseComparator, IterRef, Skiplist};
 use snafu::{OptionExt, ResultExt};
 
-use crate::memtable::{
-    key,
-    key::{KeySequence, SequenceCodec},
-    AppendRow, BuildRecordBatch, DecodeInternalKey, Internal, InternalNoCause, IterTimeout,
-    ProjectSchema, Result, ScanContext, ScanRequest,
+use crate::{
+    memtable::{
+        key,
+        key::{KeySequence, SequenceCodec},
+        AppendRow, BuildRecordBatch, DecodeInternalKey, Internal, InternalNoCause, IterTimeout,
+        ProjectSchema, Result, ScanContext, ScanRequest,
+    },
+    row_iter::aggregate,
 };
 
 /// Iterator state
@@ -85,6 +88,8 @@ pub struct ColumnarIterImpl<A: Arena<Stats = BasicStats> + Clone + Sync + Send>
 
     /// Dedup rows with key
     need_dedup: bool,
+    /// Merge the duplicate rows instead of keeping the latest one
+    merge_duplicates: bool,
 
     skiplist: Skiplist<BytewiseComparator, A>,
     /// The internal skiplist iter
@@ -119,6 +124,7 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
             end_user_key: request.end_user_key,
             state: State::Uninitialized,
             need_dedup: request.need_dedup,
+            merge_duplicates: request.need_dedup && request.merge_duplicates,
             iter: skiplist.iter(),
             skiplist,
             last_internal_key: None,
@@ -296,12 +302,15 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
     fn fetch_next_record_batch_rows(&mut self) -> Result<Vec<Row>> {
         let mut num_rows = 0;
         let mut row_idxs = Vec::with_capacity(self.batch_size);
+        // Indexes of the older rows with the same key for each row in `row_idxs`,
+        // only used if `merge_duplicates` is enabled.
+        let mut duplicate_row_idxs = Vec::new();
         while self.iter.valid() && num_rows < self.batch_size {
             if let Some(row) = self.fetch_next_row()? {
-                let mut buf = [0u8; 4];
-                buf.copy_from_slice(&row);
-                let idx = u32::from_le_bytes(buf);
-                row_idxs.push(idx);
+                row_idxs.push(decode_row_idx(&row));
+                if self.merge_duplicates {
+                    duplicate_row_idxs.push(self.fetch_duplicate_row_idxs()?);
+                }
                 num_rows += 1;
             } else {
                 // There is no more row to fetch.
@@ -325,7 +334,21 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
                 let column_schema = self.memtable_schema.column(*column_schema_idx);
                 if let Some(column) = memtable.get(&column_schema.id) {
                     for (i, row_idx) in row_idxs.iter().enumerate() {
-                        let datum = column.get_datum(*row_idx as usize);
+                        let mut datum = column.get_datum(*row_idx as usize);
+                        if self.merge_duplicates {
+                            // Merge the duplicate rows from the latest to the oldest.
+                            for duplicate_row_idx in &duplicate_row_idxs[i] {
+                                aggregate::merge_datum(
+                                    column_schema.aggregation,
+                                    &mut datum,
+                                    column.get_datum(*duplicate_row_idx as usize),
+                                )
+                                .box_err()
+                                .context(Internal {
+                                    msg: "merge duplicate rows",
+                                })?;
+                            }
+                        }
                         rows[i][col_idx] = datum;
                     }
                 }
@@ -335,6 +358,30 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
         Ok(rows)
     }
 
+    /// Fetch the row indexes of the older rows with the same key as the last
+    /// returned row, and move the iter to the next user key.
+    fn fetch_duplicate_row_idxs(&mut self) -> Result<Vec<u32>> {
+        let mut row_idxs = Vec::new();
+        let last_internal_key = match &self.last_internal_key {
+            Some(v) => v,
+            None => return Ok(row_idxs),
+        };
+        let (last_user_key, _) =
+            key::user_key_from_internal_key(last_internal_key).context(DecodeInternalKey)?;
+
+        while self.iter.valid() {
+            let (user_key, _) =
+                key::user_key_from_internal_key(self.iter.key()).context(DecodeInternalKey)?;
+            if user_key != last_user_key {
+                break;
+            }
+            row_idxs.push(decode_row_idx(self.iter.value()));
+            self.iter.next();
+        }
+
+        Ok(row_idxs)
+    }
+
     /// Fetch next record batch
     fn fetch_next_record_batch_rows_no_dedup(&mut self) -> Result<Vec<Row>> {
         let mut num_rows = 0;
@@ -394,6 +441,12 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
     }
 }
 
+fn decode_row_idx(value: &[u8]) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(value);
+    u32::from_le_bytes(buf)
+}
+
 impl Iterator for ColumnarIterImpl<MonoIncArena> {
     type Item = Result<FetchedRecordBatch>;
 
@@ -227,6 +227,7 @@ impl Inner {
             end_user_key: Bound::Unbounded,
             sequence: common_types::MAX_SEQUENCE_NUMBER,
             need_dedup: false,
+            merge_duplicates: false,
             reverse: false,
             metrics_collector: None,
             time_range: TimeRange::min_to_max(),
@@ -684,6 +685,7 @@ mod tests {
             sequence: SequenceNumber::MAX,
             row_projector_builder,
             need_dedup: false,
+            merge_duplicates: false,
             reverse: false,
             metrics_collector: None,
             time_range,
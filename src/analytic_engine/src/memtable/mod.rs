@@ -264,6 +264,10 @@ pub struct ScanRequest {
     /// Schema and projection to read.
     pub row_projector_builder: RowProjectorBuilder,
     pub need_dedup: bool,
+    /// Merge the duplicate rows by choosing the latest non-null value of each
+    /// column, or aggregating the values if the column has an aggregation,
+    /// only takes effect if `need_dedup` is true.
+    pub merge_duplicates: bool,
     pub reverse: bool,
     /// Collector for scan metrics.
     pub metrics_collector: Option<MetricsCollector>,
@@ -25,19 +25,26 @@ use codec::row;
 use common_types::{
     projected_schema::RowProjector,
     record_batch::{FetchedRecordBatch, FetchedRecordBatchBuilder},
-    row::contiguous::{ContiguousRowReader, ProjectedContiguousRow},
+    row::{
+        contiguous::{ContiguousRowReader, ProjectedContiguousRow},
+        Row,
+    },
     schema::Schema,
     SequenceNumber,
 };
+use generic_error::BoxError;
 use logger::trace;
 use skiplist::{ArenaSlice, BytewiseComparator, IterRef, Skiplist};
 use snafu::ResultExt;
 
-use crate::memtable::{
-    key::{self, KeySequence},
-    skiplist::SkiplistMemTable,
-    AppendRow, BuildRecordBatch, DecodeContinuousRow, DecodeInternalKey, EncodeInternalKey,
-    IterTimeout, ProjectSchema, Result, ScanContext, ScanRequest,
+use crate::{
+    memtable::{
+        key::{self, KeySequence},
+        skiplist::SkiplistMemTable,
+        AppendRow, BuildRecordBatch, DecodeContinuousRow, DecodeInternalKey, EncodeInternalKey,
+        Internal, IterTimeout, ProjectSchema, Result, ScanContext, ScanRequest,
+    },
+    row_iter::aggregate,
 };
 
 /// Iterator state
@@ -78,6 +85,8 @@ pub struct ColumnarIterImpl<A: Arena<Stats = BasicStats> + Clone + Sync + Send>
 
     /// Dedup rows with key
     need_dedup: bool,
+    /// Merge the duplicate rows instead of keeping the latest one
+    merge_duplicates: bool,
 }
 
 impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
@@ -106,6 +115,7 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
             state: State::Uninitialized,
             last_internal_key: None,
             need_dedup: request.need_dedup,
+            merge_duplicates: request.need_dedup && request.merge_duplicates,
         };
 
         columnar_iter.init()?;
@@ -172,9 +182,19 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
 
                 trace!("Column iterator fetch next row, row:{:?}", projected_row);
 
-                builder
-                    .append_projected_contiguous_row(&projected_row)
-                    .context(AppendRow)?;
+                if self.merge_duplicates {
+                    let mut merged_row = Row::from_datums(
+                        (0..projected_row.num_datum_views())
+                            .map(|i| projected_row.datum_view_at(i).to_datum())
+                            .collect(),
+                    );
+                    self.merge_duplicate_rows(&mut merged_row)?;
+                    builder.append_row(merged_row).context(AppendRow)?;
+                } else {
+                    builder
+                        .append_projected_contiguous_row(&projected_row)
+                        .context(AppendRow)?;
+                }
                 num_rows += 1;
             } else {
                 // There is no more row to fetch
@@ -271,6 +291,50 @@ impl<A: Arena<Stats = BasicStats> + Clone + Sync + Send> ColumnarIterImpl<A> {
         Ok(None)
     }
 
+    /// Merge the older visible rows with the same key as the last returned row
+    /// into `merged_row`, see [aggregate::merge_datum].
+    ///
+    /// The iter will be moved to the next user key.
+    fn merge_duplicate_rows(&mut self, merged_row: &mut Row) -> Result<()> {
+        let last_internal_key = match &self.last_internal_key {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        let (last_user_key, _) =
+            key::user_key_from_internal_key(last_internal_key).context(DecodeInternalKey)?;
+
+        while self.iter.valid() {
+            let (user_key, sequence) =
+                key::user_key_from_internal_key(self.iter.key()).context(DecodeInternalKey)?;
+            if user_key != last_user_key {
+                break;
+            }
+
+            if sequence.sequence() <= self.sequence {
+                let row = self.iter.value_with_arena();
+                let row_reader = ContiguousRowReader::try_new(&row, &self.memtable_schema)
+                    .context(DecodeContinuousRow)?;
+                let projected_row = ProjectedContiguousRow::new(row_reader, &self.row_projector);
+                let fetched_schema = self.row_projector.fetched_schema();
+                for i in 0..projected_row.num_datum_views() {
+                    aggregate::merge_datum(
+                        fetched_schema.column(i).aggregation,
+                        &mut merged_row[i],
+                        projected_row.datum_view_at(i).to_datum(),
+                    )
+                    .box_err()
+                    .context(Internal {
+                        msg: "merge duplicate rows",
+                    })?;
+                }
+            }
+
+            self.iter.next();
+        }
+
+        Ok(())
+    }
+
     /// Return true if the sequence is visible
     #[inline]
     fn is_visible(&self, sequence: KeySequence) -> bool {
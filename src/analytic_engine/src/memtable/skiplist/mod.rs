@@ -281,7 +281,7 @@ mod tests {
         record_batch::FetchedRecordBatch,
         row::Row,
         schema::IndexInWriterSchema,
-        tests::{build_row, build_schema},
+        tests::{build_row, build_row_opt, build_schema},
         time::Timestamp,
     };
 
@@ -337,6 +337,7 @@ mod tests {
                     sequence: 2,
                     row_projector_builder: row_projector_builder.clone(),
                     need_dedup: true,
+                    merge_duplicates: false,
                     reverse: false,
                     metrics_collector: None,
                     time_range: TimeRange::min_to_max(),
@@ -358,6 +359,7 @@ mod tests {
                     sequence: 2,
                     row_projector_builder: row_projector_builder.clone(),
                     need_dedup: true,
+                    merge_duplicates: false,
                     reverse: false,
                     metrics_collector: None,
                     time_range: TimeRange::min_to_max(),
@@ -378,6 +380,7 @@ mod tests {
                     sequence: 1,
                     row_projector_builder,
                     need_dedup: true,
+                    merge_duplicates: false,
                     reverse: false,
                     metrics_collector: None,
                     time_range: TimeRange::min_to_max(),
@@ -414,6 +417,7 @@ mod tests {
                 sequence: 2,
                 row_projector_builder,
                 need_dedup: true,
+                merge_duplicates: false,
                 reverse: false,
                 metrics_collector: None,
                 time_range: TimeRange::min_to_max(),
@@ -445,6 +449,69 @@ mod tests {
         test_memtable_scan_for_projection(schema, memtable);
     }
 
+    #[test]
+    fn test_memtable_scan_merge_duplicates() {
+        let data = vec![
+            (
+                KeySequence::new(1, 1),
+                build_row_opt(b"a", 1, Some(10.0), Some("v1"), None, None),
+            ),
+            (
+                KeySequence::new(2, 1),
+                build_row_opt(b"a", 1, None, Some("v2"), Some(1000), None),
+            ),
+            (
+                KeySequence::new(3, 1),
+                build_row_opt(b"a", 1, None, None, None, Some(1_000_000)),
+            ),
+            (
+                KeySequence::new(1, 2),
+                build_row_opt(b"b", 2, Some(20.0), None, None, None),
+            ),
+        ];
+        let test_util = TestUtil::new(TestMemtableBuilderImpl, data);
+        let memtable = test_util.memtable();
+        let schema = memtable.schema().clone();
+
+        let projected_schema = ProjectedSchema::no_projection(schema);
+        let fetched_schema = projected_schema.to_record_schema();
+        let table_schema = projected_schema.table_schema();
+        let row_projector_builder =
+            RowProjectorBuilder::new(fetched_schema, table_schema.clone(), None);
+        let testcases = vec![
+            (
+                3,
+                vec![
+                    build_row_opt(b"a", 1, Some(10.0), Some("v2"), Some(1000), Some(1_000_000)),
+                    build_row_opt(b"b", 2, Some(20.0), None, None, None),
+                ],
+            ),
+            (
+                2,
+                vec![
+                    build_row_opt(b"a", 1, Some(10.0), Some("v2"), Some(1000), None),
+                    build_row_opt(b"b", 2, Some(20.0), None, None, None),
+                ],
+            ),
+        ];
+
+        for (sequence, expected) in testcases {
+            let req = ScanRequest {
+                start_user_key: Bound::Unbounded,
+                end_user_key: Bound::Unbounded,
+                sequence,
+                row_projector_builder: row_projector_builder.clone(),
+                need_dedup: true,
+                merge_duplicates: true,
+                reverse: false,
+                metrics_collector: None,
+                time_range: TimeRange::min_to_max(),
+            };
+            let iter = memtable.scan(ScanContext::default(), req).unwrap();
+            check_iterator(iter, expected);
+        }
+    }
+
     fn check_iterator<T: Iterator<Item = Result<FetchedRecordBatch>>>(
         iter: T,
         expected_rows: Vec<Row>,
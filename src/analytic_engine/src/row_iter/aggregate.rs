@@ -0,0 +1,364 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Merge the values of the rows with the same primary key.
+
+use bytes_ext::Bytes;
+use common_types::{
+    column_schema::ColumnAggregation,
+    datum::{Datum, DatumKind},
+    decimal::{self, Decimal},
+};
+use hyperloglog::HyperLogLog;
+use macros::define_result;
+use serde::Deserialize;
+use snafu::{ensure, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to decode hll, err:{}", source))]
+    DecodeHll { source: bincode::Error },
+
+    #[snafu(display("Invalid hll, num_registers:{}, actual:{}", num_registers, actual))]
+    InvalidHll { num_registers: usize, actual: usize },
+
+    #[snafu(display(
+        "Failed to union the incompatible hlls, merged:{:?}, older:{:?}",
+        merged,
+        older
+    ))]
+    IncompatibleHll { merged: HllLayout, older: HllLayout },
+
+    #[snafu(display("Failed to encode hll, err:{}", source))]
+    EncodeHll { source: bincode::Error },
+
+    #[snafu(display("Failed to sum decimals, err:{}", source))]
+    SumDecimal { source: decimal::Error },
+
+    #[snafu(display(
+        "Mismatched datum kinds to aggregate, aggregation:{}, merged:{}, older:{}",
+        aggregation,
+        merged,
+        older
+    ))]
+    MismatchedKind {
+        aggregation: ColumnAggregation,
+        merged: DatumKind,
+        older: DatumKind,
+    },
+}
+
+define_result!(Error);
+
+/// Merge the datum of an older duplicate row into the `merged` datum.
+///
+/// Null values are ignored, so a column without aggregation (or with the
+/// `REPLACE` aggregation) keeps the latest non-null value.
+pub fn merge_datum(
+    aggregation: Option<ColumnAggregation>,
+    merged: &mut Datum,
+    older: Datum,
+) -> Result<()> {
+    if older.is_null() {
+        return Ok(());
+    }
+    if merged.is_null() {
+        *merged = older;
+        return Ok(());
+    }
+
+    match aggregation {
+        None | Some(ColumnAggregation::Replace) => (),
+        Some(ColumnAggregation::Sum) => sum_datum(merged, &older)?,
+        Some(ColumnAggregation::Max) => {
+            if older > *merged {
+                *merged = older;
+            }
+        }
+        Some(ColumnAggregation::Min) => {
+            if older < *merged {
+                *merged = older;
+            }
+        }
+        Some(ColumnAggregation::HllUnion) => union_hll(merged, &older)?,
+    }
+
+    Ok(())
+}
+
+/// Integers are summed with saturation to avoid panic on overflow, while the
+/// decimal overflowing its precision is an error.
+fn sum_datum(merged: &mut Datum, older: &Datum) -> Result<()> {
+    let (merged_kind, older_kind) = (merged.kind(), older.kind());
+    match (merged, older) {
+        (Datum::Double(v), Datum::Double(o)) => *v += o,
+        (Datum::Float(v), Datum::Float(o)) => *v += o,
+        (Datum::UInt64(v), Datum::UInt64(o)) => *v = v.saturating_add(*o),
+        (Datum::UInt32(v), Datum::UInt32(o)) => *v = v.saturating_add(*o),
+        (Datum::UInt16(v), Datum::UInt16(o)) => *v = v.saturating_add(*o),
+        (Datum::UInt8(v), Datum::UInt8(o)) => *v = v.saturating_add(*o),
+        (Datum::Int64(v), Datum::Int64(o)) => *v = v.saturating_add(*o),
+        (Datum::Int32(v), Datum::Int32(o)) => *v = v.saturating_add(*o),
+        (Datum::Int16(v), Datum::Int16(o)) => *v = v.saturating_add(*o),
+        (Datum::Int8(v), Datum::Int8(o)) => *v = v.saturating_add(*o),
+        // The decimals of a column share the same decimal type.
+        (Datum::Decimal(v), Datum::Decimal(o)) => {
+            *v = Decimal::try_new(v.value().saturating_add(o.value()), v.decimal_type())
+                .context(SumDecimal)?
+        }
+        _ => {
+            return MismatchedKind {
+                aggregation: ColumnAggregation::Sum,
+                merged: merged_kind,
+                older: older_kind,
+            }
+            .fail()
+        }
+    }
+
+    Ok(())
+}
+
+/// The leading fields of a [HyperLogLog] serialized by bincode, the sketches
+/// to union must share them, otherwise [HyperLogLog::merge] panics.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HllLayout {
+    precision: u8,
+    num_registers: usize,
+    /// Keys of the sip hasher, which is serialized right after the registers.
+    sip_keys: (u64, u64),
+}
+
+impl HllLayout {
+    fn decode(buf: &[u8]) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Prefix<'a> {
+            _alpha: f64,
+            precision: u8,
+            num_registers: usize,
+            registers: &'a [u8],
+            sip_keys: (u64, u64),
+        }
+
+        let prefix: Prefix = bincode::deserialize(buf).context(DecodeHll)?;
+        ensure!(
+            prefix.registers.len() == prefix.num_registers,
+            InvalidHll {
+                num_registers: prefix.num_registers,
+                actual: prefix.registers.len(),
+            }
+        );
+
+        Ok(Self {
+            precision: prefix.precision,
+            num_registers: prefix.num_registers,
+            sip_keys: prefix.sip_keys,
+        })
+    }
+}
+
+/// The sketches are serialized [HyperLogLog] by bincode, and must be created
+/// with the same precision and hash keys, e.g. the template used by
+/// `thetasketch_distinct`.
+fn union_hll(merged: &mut Datum, older: &Datum) -> Result<()> {
+    let (merged_bytes, older_bytes) = match (&*merged, older) {
+        (Datum::Varbinary(v), Datum::Varbinary(o)) => (v, o),
+        _ => {
+            return MismatchedKind {
+                aggregation: ColumnAggregation::HllUnion,
+                merged: merged.kind(),
+                older: older.kind(),
+            }
+            .fail()
+        }
+    };
+
+    let (merged_layout, older_layout) = (
+        HllLayout::decode(merged_bytes)?,
+        HllLayout::decode(older_bytes)?,
+    );
+    ensure!(
+        merged_layout == older_layout,
+        IncompatibleHll {
+            merged: merged_layout,
+            older: older_layout,
+        }
+    );
+
+    let mut hll: HyperLogLog = bincode::deserialize(merged_bytes).context(DecodeHll)?;
+    let older_hll: HyperLogLog = bincode::deserialize(older_bytes).context(DecodeHll)?;
+    hll.merge(&older_hll);
+    let buf = bincode::serialize(&hll).context(EncodeHll)?;
+    *merged = Datum::Varbinary(Bytes::from(buf));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use common_types::decimal::DecimalType;
+
+    use super::*;
+
+    #[test]
+    fn test_merge_datum() {
+        let mut merged = Datum::Null;
+        merge_datum(Some(ColumnAggregation::Sum), &mut merged, Datum::Int64(2)).unwrap();
+        merge_datum(Some(ColumnAggregation::Sum), &mut merged, Datum::Int64(3)).unwrap();
+        merge_datum(Some(ColumnAggregation::Sum), &mut merged, Datum::Null).unwrap();
+        assert_eq!(Datum::Int64(5), merged);
+
+        let mut merged = Datum::Double(1.0);
+        merge_datum(
+            Some(ColumnAggregation::Max),
+            &mut merged,
+            Datum::Double(3.0),
+        )
+        .unwrap();
+        merge_datum(
+            Some(ColumnAggregation::Max),
+            &mut merged,
+            Datum::Double(2.0),
+        )
+        .unwrap();
+        assert_eq!(Datum::Double(3.0), merged);
+
+        let mut merged = Datum::UInt32(2);
+        merge_datum(Some(ColumnAggregation::Min), &mut merged, Datum::UInt32(1)).unwrap();
+        merge_datum(Some(ColumnAggregation::Min), &mut merged, Datum::UInt32(3)).unwrap();
+        assert_eq!(Datum::UInt32(1), merged);
+
+        let mut merged = Datum::Null;
+        merge_datum(
+            Some(ColumnAggregation::Replace),
+            &mut merged,
+            Datum::Int8(1),
+        )
+        .unwrap();
+        merge_datum(
+            Some(ColumnAggregation::Replace),
+            &mut merged,
+            Datum::Int8(2),
+        )
+        .unwrap();
+        assert_eq!(Datum::Int8(1), merged);
+
+        let decimal_type = DecimalType::try_new(10, 2).unwrap();
+        let mut merged = Datum::Decimal(Decimal::parse("1.25", decimal_type).unwrap());
+        merge_datum(
+            Some(ColumnAggregation::Sum),
+            &mut merged,
+            Datum::Decimal(Decimal::parse("2.5", decimal_type).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(
+            Datum::Decimal(Decimal::parse("3.75", decimal_type).unwrap()),
+            merged
+        );
+    }
+
+    #[test]
+    fn test_sum_datum_overflow() {
+        let mut merged = Datum::Int64(i64::MAX - 1);
+        merge_datum(Some(ColumnAggregation::Sum), &mut merged, Datum::Int64(2)).unwrap();
+        assert_eq!(Datum::Int64(i64::MAX), merged);
+
+        let mut merged = Datum::Int8(i8::MIN + 1);
+        merge_datum(Some(ColumnAggregation::Sum), &mut merged, Datum::Int8(-2)).unwrap();
+        assert_eq!(Datum::Int8(i8::MIN), merged);
+
+        let mut merged = Datum::UInt32(u32::MAX);
+        merge_datum(Some(ColumnAggregation::Sum), &mut merged, Datum::UInt32(1)).unwrap();
+        assert_eq!(Datum::UInt32(u32::MAX), merged);
+
+        let decimal_type = DecimalType::try_new(3, 1).unwrap();
+        let mut merged = Datum::Decimal(Decimal::parse("60.5", decimal_type).unwrap());
+        let older = Datum::Decimal(Decimal::parse("50.5", decimal_type).unwrap());
+        assert!(merge_datum(Some(ColumnAggregation::Sum), &mut merged, older).is_err());
+    }
+
+    #[test]
+    fn test_merge_mismatched_kind() {
+        let mut merged = Datum::Int64(1);
+        assert!(merge_datum(Some(ColumnAggregation::Sum), &mut merged, Datum::Int32(1)).is_err());
+
+        let mut merged = Datum::Varbinary(Bytes::from_static(b"hll"));
+        assert!(merge_datum(
+            Some(ColumnAggregation::HllUnion),
+            &mut merged,
+            Datum::Int32(1)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_union_hll() {
+        let template = HyperLogLog::new_deterministic(0.01, 0);
+        let mut hll1 = HyperLogLog::new_from_template(&template);
+        let mut hll2 = HyperLogLog::new_from_template(&template);
+        for i in 0..100 {
+            hll1.insert(&i);
+        }
+        for i in 50..200 {
+            hll2.insert(&i);
+        }
+
+        let mut merged = Datum::Varbinary(Bytes::from(bincode::serialize(&hll1).unwrap()));
+        let older = Datum::Varbinary(Bytes::from(bincode::serialize(&hll2).unwrap()));
+        merge_datum(Some(ColumnAggregation::HllUnion), &mut merged, older).unwrap();
+
+        let hll: HyperLogLog = match &merged {
+            Datum::Varbinary(v) => bincode::deserialize(v).unwrap(),
+            _ => panic!("unexpected datum:{merged:?}"),
+        };
+        let len = hll.len();
+        assert!((190.0..210.0).contains(&len), "len:{len}");
+    }
+
+    #[test]
+    fn test_union_incompatible_hll() {
+        let hll1 = HyperLogLog::new_deterministic(0.01, 0);
+        let hll2 = HyperLogLog::new_deterministic(0.05, 0);
+        let hll3 = HyperLogLog::new_deterministic(0.01, 1);
+
+        let encode =
+            |hll: &HyperLogLog| Datum::Varbinary(Bytes::from(bincode::serialize(hll).unwrap()));
+        // Mismatched precisions.
+        let mut merged = encode(&hll1);
+        assert!(merge_datum(
+            Some(ColumnAggregation::HllUnion),
+            &mut merged,
+            encode(&hll2)
+        )
+        .is_err());
+        // Mismatched hash keys.
+        let mut merged = encode(&hll1);
+        assert!(merge_datum(
+            Some(ColumnAggregation::HllUnion),
+            &mut merged,
+            encode(&hll3)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_hll() {
+        let mut merged = Datum::Varbinary(Bytes::from_static(b"a"));
+        let older = Datum::Varbinary(Bytes::from_static(b"b"));
+        assert!(merge_datum(Some(ColumnAggregation::HllUnion), &mut merged, older).is_err());
+    }
+}
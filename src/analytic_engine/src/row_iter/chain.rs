@@ -26,6 +26,7 @@ use common_types::{
     record_batch::FetchedRecordBatch,
     request_id::RequestId,
     schema::RecordSchemaWithKey,
+    SequenceNumber,
 };
 use generic_error::GenericError;
 use logger::debug;
@@ -77,6 +78,8 @@ pub struct ChainConfig<'a> {
     pub space_id: SpaceId,
     pub table_id: TableId,
     /// The projected schema to read.
+    /// Max visible sequence (inclusive)
+    pub sequence: SequenceNumber,
     pub projected_schema: ProjectedSchema,
     /// Predicate of the query.
     pub predicate: PredicateRef,
@@ -137,10 +140,12 @@ impl<'a> Builder<'a> {
             .build(row_projector_builder.clone());
 
         let memtable_stream_ctx = MemtableStreamContext {
+            sequence: self.config.sequence,
             row_projector_builder,
             fetched_schema: fetched_schema.clone(),
             predicate: self.config.predicate,
             need_dedup: false,
+            merge_duplicates: false,
             reverse: false,
             deadline: self.config.deadline,
         };
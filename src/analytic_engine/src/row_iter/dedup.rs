@@ -29,7 +29,7 @@ use logger::{info, trace};
 use macros::define_result;
 use snafu::{ResultExt, Snafu};
 
-use crate::row_iter::{FetchedRecordBatchIterator, IterOptions};
+use crate::row_iter::{aggregate, FetchedRecordBatchIterator, IterOptions};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -48,12 +48,19 @@ pub enum Error {
 
     #[snafu(display("Failed to read data from the sub iterator, err:{:?}", source))]
     ReadFromSubIter { source: GenericError },
+
+    #[snafu(display("Failed to merge duplicate rows, err:{:?}", source))]
+    MergeRow { source: aggregate::Error },
 }
 
 define_result!(Error);
 
 /// Dedup the elements from the `iter` by choosing the first one in the
 /// duplicate rows.
+///
+/// If `merge_duplicates` is enabled, the duplicate rows are merged into one row
+/// instead, by choosing the first non-null value of each column or aggregating
+/// the values if the column has an aggregation.
 pub struct DedupIterator<I> {
     request_id: RequestId,
     schema: RecordSchemaWithKey,
@@ -64,6 +71,12 @@ pub struct DedupIterator<I> {
     /// Store which row in record batch is keep, use Vec<bool> is a bit faster
     /// than a bitmap.
     selected_rows: Vec<bool>,
+    /// Whether to merge the duplicate rows.
+    merge_duplicates: bool,
+    /// The merged row not returned yet, only used if `merge_duplicates` is
+    /// enabled, because the following rows in next batch may still have the
+    /// same key.
+    pending_row: Option<Row>,
 
     // Metrics:
     total_duplications: usize,
@@ -87,11 +100,86 @@ impl<I: FetchedRecordBatchIterator> DedupIterator<I> {
             iter,
             prev_row: None,
             selected_rows: Vec::new(),
+            merge_duplicates: false,
+            pending_row: None,
             total_duplications: 0,
             total_selected_rows: 0,
         }
     }
 
+    pub fn with_merge_duplicates(mut self, merge_duplicates: bool) -> Self {
+        self.merge_duplicates = merge_duplicates;
+        self
+    }
+
+    fn merge_batch(&mut self, record_batch: FetchedRecordBatch) -> Result<FetchedRecordBatch> {
+        self.record_batch_builder.clear();
+        for row_idx in 0..record_batch.num_rows() {
+            let is_equal = match &self.pending_row {
+                Some(pending_row) => {
+                    let pending_row_view = RowWithMeta {
+                        row: pending_row,
+                        schema: &self.schema,
+                    };
+                    let curr_row_view = RowViewOnBatch {
+                        record_batch: &record_batch,
+                        row_idx,
+                    };
+                    matches!(
+                        self.schema.compare_row(&pending_row_view, &curr_row_view),
+                        Ordering::Equal
+                    )
+                }
+                None => false,
+            };
+
+            if is_equal {
+                // The pending row is newer, merge the older values into it.
+                let pending_row = self.pending_row.as_mut().unwrap();
+                for col_idx in 0..record_batch.num_columns() {
+                    aggregate::merge_datum(
+                        self.schema.columns()[col_idx].aggregation,
+                        &mut pending_row[col_idx],
+                        record_batch.column(col_idx).datum(row_idx),
+                    )
+                    .context(MergeRow)?;
+                }
+                self.total_duplications += 1;
+                continue;
+            }
+
+            if let Some(pending_row) = self.pending_row.replace(record_batch.clone_row_at(row_idx))
+            {
+                self.record_batch_builder
+                    .append_row(pending_row)
+                    .context(AppendRow)?;
+                self.total_selected_rows += 1;
+            }
+        }
+
+        self.record_batch_builder.build().context(BuildRecordBatch)
+    }
+
+    /// Build a batch with the pending row, returns None if there is no pending
+    /// row.
+    fn take_pending_batch(&mut self) -> Result<Option<FetchedRecordBatch>> {
+        match self.pending_row.take() {
+            Some(pending_row) => {
+                self.record_batch_builder.clear();
+                self.record_batch_builder
+                    .append_row(pending_row)
+                    .context(AppendRow)?;
+                self.total_selected_rows += 1;
+
+                self.record_batch_builder
+                    .build()
+                    .context(BuildRecordBatch)
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
     fn dedup_batch(&mut self, record_batch: FetchedRecordBatch) -> Result<FetchedRecordBatch> {
         self.selected_rows.clear();
         // Ignore all rows by default.
@@ -198,9 +286,17 @@ impl<I: FetchedRecordBatchIterator> FetchedRecordBatchIterator for DedupIterator
                     record_batch
                 );
 
-                self.dedup_batch(record_batch).map(Some)
+                if self.merge_duplicates {
+                    self.merge_batch(record_batch).map(Some)
+                } else {
+                    self.dedup_batch(record_batch).map(Some)
+                }
             }
             None => {
+                if let Some(record_batch) = self.take_pending_batch()? {
+                    return Ok(Some(record_batch));
+                }
+
                 info!(
                     "DedupIterator received none record batch, request_id:{}, total_duplications:{}, total_selected_rows:{}",
                     self.request_id, self.total_duplications, self.total_selected_rows,
@@ -214,7 +310,9 @@ impl<I: FetchedRecordBatchIterator> FetchedRecordBatchIterator for DedupIterator
 
 #[cfg(test)]
 mod tests {
-    use common_types::tests::{build_row, build_schema};
+    use common_types::tests::{
+        build_row, build_row_opt, build_schema, build_schema_with_aggregation,
+    };
 
     use super::*;
     use crate::row_iter::tests::{
@@ -261,4 +359,86 @@ mod tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn test_dedup_iterator_merge_duplicates() {
+        // first two columns are key columns
+        let schema = build_schema();
+        let iter = VectorIterator::new(
+            schema.to_record_schema_with_key(),
+            vec![
+                build_fetched_record_batch_with_key(
+                    schema.clone(),
+                    vec![
+                        build_row_opt(b"a", 1, Some(10.0), None, None, Some(1_000_000)),
+                        build_row_opt(b"a", 1, Some(11.0), Some("v1"), None, None),
+                        build_row_opt(b"a", 2, None, Some("v2"), None, None),
+                    ],
+                ),
+                build_fetched_record_batch_with_key(
+                    schema,
+                    vec![
+                        // Duplicate with the last row of previous batch.
+                        build_row_opt(b"a", 2, Some(20.0), Some("v"), Some(2000), None),
+                        build_row_opt(b"a", 3, Some(30.0), Some("v3"), Some(3000), None),
+                    ],
+                ),
+            ],
+        );
+
+        let mut iter =
+            DedupIterator::new(RequestId::next_id(), iter, IterOptions { batch_size: 500 })
+                .with_merge_duplicates(true);
+        check_iterator(
+            &mut iter,
+            vec![
+                build_row_opt(b"a", 1, Some(10.0), Some("v1"), None, Some(1_000_000)),
+                build_row_opt(b"a", 2, Some(20.0), Some("v2"), Some(2000), None),
+                build_row_opt(b"a", 3, Some(30.0), Some("v3"), Some(3000), None),
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_dedup_iterator_aggregate_duplicates() {
+        // field1 is summed, field3 takes the max value and field4 takes the min
+        // value.
+        let schema = build_schema_with_aggregation();
+        let iter = VectorIterator::new(
+            schema.to_record_schema_with_key(),
+            vec![
+                build_fetched_record_batch_with_key(
+                    schema.clone(),
+                    vec![
+                        build_row_opt(b"a", 1, Some(1.0), None, Some(10), Some(5)),
+                        build_row_opt(b"a", 1, Some(2.0), Some("v1"), Some(30), Some(3)),
+                        build_row_opt(b"a", 2, None, Some("v2"), None, None),
+                    ],
+                ),
+                build_fetched_record_batch_with_key(
+                    schema,
+                    vec![
+                        // Duplicate with the last row of previous batch.
+                        build_row_opt(b"a", 2, Some(3.0), Some("v"), Some(20), Some(7)),
+                        build_row_opt(b"a", 2, Some(4.0), None, Some(10), Some(9)),
+                        build_row_opt(b"a", 3, Some(5.0), Some("v3"), None, None),
+                    ],
+                ),
+            ],
+        );
+
+        let mut iter =
+            DedupIterator::new(RequestId::next_id(), iter, IterOptions { batch_size: 500 })
+                .with_merge_duplicates(true);
+        check_iterator(
+            &mut iter,
+            vec![
+                build_row_opt(b"a", 1, Some(3.0), Some("v1"), Some(30), Some(3)),
+                build_row_opt(b"a", 2, Some(7.0), Some("v2"), Some(20), Some(7)),
+                build_row_opt(b"a", 3, Some(5.0), Some("v3"), None, None),
+            ],
+        )
+        .await;
+    }
 }
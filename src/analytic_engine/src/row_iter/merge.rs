@@ -123,6 +123,9 @@ pub struct MergeConfig<'a> {
     pub merge_iter_options: IterOptions,
 
     pub need_dedup: bool,
+    /// Merge the duplicate rows of the memtables, see
+    /// [ScanRequest](crate::memtable::ScanRequest) for details.
+    pub merge_duplicates: bool,
     // TODO: Currently, the read the sst in a reverse order is not supported yet, that is to say,
     // the output won't be expected if it is set.
     pub reverse: bool,
@@ -193,10 +196,12 @@ impl<'a> MergeBuilder<'a> {
             .build(row_projector_builder.clone());
 
         let memtable_stream_ctx = MemtableStreamContext {
+            sequence: self.config.sequence,
             row_projector_builder,
             fetched_schema: fetched_schema.clone(),
             predicate: self.config.predicate,
             need_dedup: self.config.need_dedup,
+            merge_duplicates: self.config.merge_duplicates,
             reverse: self.config.reverse,
             deadline: self.config.deadline,
         };
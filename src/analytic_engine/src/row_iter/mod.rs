@@ -24,6 +24,7 @@ use generic_error::BoxError;
 
 use crate::sst::writer::RecordBatchStream;
 
+pub mod aggregate;
 pub mod chain;
 pub mod dedup;
 pub mod merge;
@@ -243,7 +243,8 @@ pub fn stream_from_memtable(
         deadline: ctx.deadline,
         ..Default::default()
     };
-    let max_seq = memtable.last_sequence();
+    // Rows written after the visible sequence are skipped by the memtable scan.
+    let max_seq = memtable.last_sequence().min(ctx.sequence);
     let fetched_cols = ctx
         .fetched_schema
         .columns()
@@ -257,6 +258,7 @@ pub fn stream_from_memtable(
         sequence: max_seq,
         row_projector_builder: ctx.row_projector_builder.clone(),
         need_dedup: ctx.need_dedup,
+        merge_duplicates: ctx.merge_duplicates,
         reverse: ctx.reverse,
         metrics_collector,
         time_range: ctx.predicate.time_range(),
@@ -275,10 +277,13 @@ pub fn stream_from_memtable(
 }
 
 pub struct MemtableStreamContext {
+    /// Max visible sequence (inclusive)
+    pub sequence: SequenceNumber,
     pub row_projector_builder: RowProjectorBuilder,
     pub fetched_schema: RecordSchema,
     pub predicate: PredicateRef,
     pub need_dedup: bool,
+    pub merge_duplicates: bool,
     pub reverse: bool,
     pub deadline: Option<Instant>,
 }
@@ -168,6 +168,7 @@ impl From<&ColumnStats> for ColumnEncoding {
     fn from(value: &ColumnStats) -> Self {
         ColumnEncoding {
             enable_dict: value.low_cardinality,
+            ..Default::default()
         }
     }
 }
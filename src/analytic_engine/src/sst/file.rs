@@ -25,7 +25,7 @@ use std::{
     hash::{Hash, Hasher},
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex as StdMutex,
     },
     time::Duration,
 };
@@ -132,8 +132,8 @@ impl LevelHandler {
     }
 
     #[inline]
-    pub fn remove_ssts(&mut self, file_ids: &[FileId]) {
-        self.files.remove_by_ids(file_ids);
+    pub fn remove_ssts(&mut self, file_ids: &[FileId]) -> Vec<FileHandle> {
+        self.files.remove_by_ids(file_ids)
     }
 
     pub fn iter_ssts(&self) -> Iter {
@@ -187,6 +187,8 @@ impl Hash for FileHandle {
 
 impl FileHandle {
     pub fn new(meta: FileMeta, purge_queue: FilePurgeQueue) -> Self {
+        purge_queue.track_file(meta.id);
+
         Self {
             inner: Arc::new(FileHandleInner {
                 meta,
@@ -237,6 +239,11 @@ impl FileHandle {
         self.inner.meta.max_seq
     }
 
+    #[inline]
+    pub fn min_sequence(&self) -> SequenceNumber {
+        self.inner.meta.min_seq
+    }
+
     #[inline]
     pub fn being_compacted(&self) -> bool {
         self.inner.being_compacted.load(Ordering::Relaxed)
@@ -403,13 +410,17 @@ impl FileHandleSet {
         self.id_to_files.insert(FileHandleHash(file));
     }
 
-    fn remove_by_ids(&mut self, file_ids: &[FileId]) {
+    fn remove_by_ids(&mut self, file_ids: &[FileId]) -> Vec<FileHandle> {
+        let mut removed = Vec::with_capacity(file_ids.len());
         for file_id in file_ids {
             if let Some(file) = self.id_to_files.take(file_id) {
                 let key = FileOrdKey::key_of(&file.0);
                 self.file_map.remove(&key);
+                removed.push(file.0);
             }
         }
+
+        removed
     }
 
     /// Collect ssts with time range is expired.
@@ -448,6 +459,9 @@ pub struct FileMeta {
     pub time_range: TimeRange,
     /// The max sequence number of the file.
     pub max_seq: u64,
+    /// The lower bound of the min sequence number of the file, zero for the
+    /// files created before it is recorded.
+    pub min_seq: u64,
     /// The format of the file.
     pub storage_format: StorageFormat,
     /// Associated files, such as: meta_path
@@ -475,10 +489,21 @@ impl FilePurgeQueue {
                 table_id,
                 sender,
                 closed: AtomicBool::new(false),
+                alive_files: Arc::new(StdMutex::new(HashSet::new())),
             }),
         }
     }
 
+    /// Whether the file is still held by a [FileHandle] or waiting to be
+    /// deleted by the purger.
+    pub fn is_file_alive(&self, file_id: FileId) -> bool {
+        self.inner.alive_files.lock().unwrap().contains(&file_id)
+    }
+
+    fn track_file(&self, file_id: FileId) {
+        self.inner.alive_files.lock().unwrap().insert(file_id);
+    }
+
     /// Close the purge queue, then all request pushed to this queue will be
     /// ignored. This is mainly used to avoid files being deleted after the
     /// db is closed.
@@ -499,6 +524,7 @@ impl FilePurgeQueue {
             table_id: self.inner.table_id,
             file_id: file_meta.id,
             associated_files: file_meta.associated_files.clone(),
+            alive_files: self.inner.alive_files.clone(),
         };
 
         if let Err(send_res) = self.inner.sender.send(Request::Purge(request)) {
@@ -515,14 +541,28 @@ struct FilePurgeQueueInner {
     table_id: TableId,
     closed: AtomicBool,
     sender: UnboundedSender<Request>,
+    /// Ids of the files held by handles or waiting to be purged, the id is
+    /// removed after the file is deleted.
+    alive_files: Arc<StdMutex<HashSet<FileId>>>,
 }
 
-#[derive(Debug)]
 pub struct FilePurgeRequest {
     space_id: SpaceId,
     table_id: TableId,
     file_id: FileId,
     associated_files: Vec<String>,
+    alive_files: Arc<StdMutex<HashSet<FileId>>>,
+}
+
+impl fmt::Debug for FilePurgeRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilePurgeRequest")
+            .field("space_id", &self.space_id)
+            .field("table_id", &self.table_id)
+            .field("file_id", &self.file_id)
+            .field("associated_files", &self.associated_files)
+            .finish()
+    }
 }
 
 #[derive(Debug)]
@@ -610,6 +650,11 @@ impl FilePurger {
                     }
 
                     Self::delete_file(&store, &sst_file_path).await;
+                    purge_request
+                        .alive_files
+                        .lock()
+                        .unwrap()
+                        .remove(&purge_request.file_id);
                 }
                 Request::Exit => break,
             }
@@ -637,4 +682,46 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_file_alive_until_purged() {
+        let runtime = Arc::new(
+            runtime::Builder::default()
+                .worker_threads(1)
+                .enable_all()
+                .build()
+                .unwrap(),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let store: ObjectStoreRef =
+            Arc::new(object_store::LocalFileSystem::new_with_prefix(dir.path()).unwrap());
+        let purger = FilePurger::start(&runtime, store);
+        let queue = purger.create_purge_queue(1, TableId::from(2));
+
+        let file = FileHandle::new(
+            FileMeta {
+                id: 100,
+                size: 0,
+                row_num: 0,
+                time_range: TimeRange::min_to_max(),
+                max_seq: 0,
+                min_seq: 0,
+                storage_format: StorageFormat::Columnar,
+                associated_files: Vec::new(),
+            },
+            queue.clone(),
+        );
+        let reader = file.clone();
+        assert!(queue.is_file_alive(100));
+        assert!(!queue.is_file_alive(101));
+
+        // The file is still read by the reader.
+        drop(file);
+        assert!(queue.is_file_alive(100));
+
+        // The purge request is handled before the purger exits.
+        drop(reader);
+        runtime.block_on(purger.stop()).unwrap();
+        assert!(!queue.is_file_alive(100));
+    }
 }
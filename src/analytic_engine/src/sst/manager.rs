@@ -17,7 +17,12 @@
 
 //! Multi-level SST management
 
-use common_types::time::{TimeRange, Timestamp};
+use std::time::Duration;
+
+use common_types::{
+    time::{TimeRange, Timestamp},
+    SequenceNumber,
+};
 
 use crate::{
     compaction::ExpiredFiles,
@@ -27,14 +32,35 @@ use crate::{
 /// Id for a sst file
 pub type FileId = u64;
 
+/// A sst file removed from the levels but still retained for time-travel
+/// reads.
+struct ObsoleteFile {
+    level: Level,
+    file: FileHandle,
+    /// The file is replaced by the data of this sequence, so it is only visible
+    /// to the reads before this sequence.
+    superseded_seq: SequenceNumber,
+    /// The files added to replace this file, e.g. the output of compaction.
+    superseded_by: Vec<FileId>,
+    /// When the file became obsolete.
+    obsolete_time: Timestamp,
+}
+
 /// A table level manager that manages all the sst files of the table
 pub struct LevelsController {
     levels: Vec<LevelHandler>,
     purge_queue: FilePurgeQueue,
+    /// How long the removed files are retained, `None` means they are purged
+    /// at once.
+    obsolete_retention: Option<Duration>,
+    obsolete_files: Vec<ObsoleteFile>,
 }
 
 impl Drop for LevelsController {
     fn drop(&mut self) {
+        // The obsolete files are no longer referenced by the manifest, so release
+        // them before closing the purge queue to have them deleted.
+        self.obsolete_files.clear();
         // Close the purge queue to avoid files being deleted.
         self.purge_queue.close();
     }
@@ -48,9 +74,17 @@ impl LevelsController {
                 .map(|v| LevelHandler::new(v.into()))
                 .collect::<Vec<_>>(),
             purge_queue,
+            obsolete_retention: None,
+            obsolete_files: Vec::new(),
         }
     }
 
+    /// Set how long the removed sst files are retained for time-travel reads.
+    pub fn set_obsolete_retention(&mut self, retention: Option<Duration>, now: Timestamp) {
+        self.obsolete_retention = retention;
+        self.purge_expired_obsolete_ssts(now);
+    }
+
     /// Add sst file to level
     ///
     /// Panic: If the level is greater than the max level
@@ -77,6 +111,63 @@ impl LevelsController {
         }
     }
 
+    /// Pick the ssts visible to the read at `sequence` and collect it by
+    /// `append_sst`, including the retained obsolete ssts.
+    ///
+    /// The files replaced by the retained obsolete ones are read through the
+    /// obsolete ones instead. Ssts have no per-row sequence, so false is
+    /// returned if any sst to read has rows both before and after `sequence`,
+    /// as the data at `sequence` is no longer retained.
+    pub fn pick_ssts_at_sequence(
+        &self,
+        time_range: TimeRange,
+        sequence: SequenceNumber,
+        mut append_sst: impl FnMut(Level, &[FileHandle]),
+    ) -> bool {
+        let retained: Vec<_> = self
+            .obsolete_files
+            .iter()
+            .filter(|obsolete| sequence < obsolete.superseded_seq)
+            .collect();
+        let is_superseded = |file: &FileHandle| {
+            retained
+                .iter()
+                .any(|obsolete| obsolete.superseded_by.contains(&file.id()))
+        };
+
+        let mut leveled_ssts = Vec::with_capacity(self.levels.len());
+        for level_handler in self.levels.iter() {
+            let mut ssts = level_handler.pick_ssts(time_range);
+            ssts.extend(
+                retained
+                    .iter()
+                    .filter(|obsolete| {
+                        obsolete.level == level_handler.level
+                            && obsolete.file.time_range().intersect_with(time_range)
+                    })
+                    .map(|obsolete| obsolete.file.clone()),
+            );
+
+            let mut visible_ssts = Vec::with_capacity(ssts.len());
+            for file in ssts {
+                if is_superseded(&file) || file.min_sequence() > sequence {
+                    continue;
+                }
+                if file.max_sequence() > sequence {
+                    return false;
+                }
+                visible_ssts.push(file);
+            }
+            leveled_ssts.push((level_handler.level, visible_ssts));
+        }
+
+        for (level, ssts) in leveled_ssts {
+            append_sst(level, &ssts);
+        }
+
+        true
+    }
+
     /// Remove sst files from level.
     ///
     /// Panic: If the level is greater than the max level
@@ -85,6 +176,60 @@ impl LevelsController {
         level_handler.remove_ssts(file_ids);
     }
 
+    /// Remove sst files from level, the removed files are retained for
+    /// time-travel reads before `superseded_seq` if the retention is set, and
+    /// the files `superseded_by` are read through them for these reads.
+    ///
+    /// Panic: If the level is greater than the max level
+    pub fn retire_ssts_from_level(
+        &mut self,
+        level: Level,
+        file_ids: &[FileId],
+        superseded_seq: SequenceNumber,
+        superseded_by: &[FileId],
+        now: Timestamp,
+    ) {
+        let level_handler = &mut self.levels[level.as_usize()];
+        let removed = level_handler.remove_ssts(file_ids);
+        if self.obsolete_retention.is_some() {
+            self.obsolete_files
+                .extend(removed.into_iter().map(|file| ObsoleteFile {
+                    level,
+                    file,
+                    superseded_seq,
+                    superseded_by: superseded_by.to_vec(),
+                    obsolete_time: now,
+                }));
+        }
+    }
+
+    /// Whether the sst is still held by a [FileHandle] or waiting to be
+    /// purged.
+    pub fn is_file_alive(&self, file_id: FileId) -> bool {
+        self.purge_queue.is_file_alive(file_id)
+    }
+
+    /// The removed ssts retained for time-travel reads.
+    pub fn obsolete_ssts(&self) -> Vec<FileHandle> {
+        self.obsolete_files
+            .iter()
+            .map(|obsolete| obsolete.file.clone())
+            .collect()
+    }
+
+    /// Release the obsolete files out of the retention, which will be purged
+    /// once no reader holds them.
+    pub fn purge_expired_obsolete_ssts(&mut self, now: Timestamp) {
+        match self.obsolete_retention {
+            Some(retention) => {
+                let expire_time = now.sub_duration_or_min(retention);
+                self.obsolete_files
+                    .retain(|obsolete| obsolete.obsolete_time > expire_time);
+            }
+            None => self.obsolete_files.clear(),
+        }
+    }
+
     pub fn levels(&self) -> impl Iterator<Item = Level> + '_ {
         self.levels.iter().map(|v| v.level)
     }
@@ -127,6 +272,9 @@ impl LevelsController {
 
 #[cfg(test)]
 pub mod tests {
+    use std::time::Duration;
+
+    use common_types::time::{TimeRange, Timestamp};
     use table_engine::table::TableId;
     use tokio::sync::mpsc;
 
@@ -164,6 +312,7 @@ pub mod tests {
                         row_num: 0,
                         time_range: sst_meta.time_range(),
                         max_seq: sst_meta.max_sequence(),
+                        min_seq: 0,
                         storage_format: StorageFormat::Columnar,
                         associated_files: Vec::new(),
                     },
@@ -172,4 +321,60 @@ pub mod tests {
             levels_controller
         }
     }
+
+    fn new_file_meta(id: FileId, min_seq: u64, max_seq: u64) -> FileMeta {
+        FileMeta {
+            id,
+            size: 0,
+            row_num: 0,
+            time_range: TimeRange::new_unchecked_for_test(0, 100),
+            max_seq,
+            min_seq,
+            storage_format: StorageFormat::Columnar,
+            associated_files: Vec::new(),
+        }
+    }
+
+    fn picked_ids_at(controller: &LevelsController, sequence: u64) -> Option<Vec<FileId>> {
+        let mut ids = Vec::new();
+        let retained =
+            controller.pick_ssts_at_sequence(TimeRange::min_to_max(), sequence, |_, ssts| {
+                ids.extend(ssts.iter().map(|f| f.id()))
+            });
+        ids.sort_unstable();
+        retained.then_some(ids)
+    }
+
+    #[test]
+    fn test_pick_ssts_at_sequence() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let file_purge_queue = FilePurgeQueue::new(100, TableId::from(101), tx);
+        let mut controller = LevelsController::new(file_purge_queue);
+        let now = Timestamp::new(10_000);
+        controller.set_obsolete_retention(Some(Duration::from_secs(5)), now);
+
+        controller.add_sst_to_level(Level::MIN, new_file_meta(1, 1, 10));
+        controller.add_sst_to_level(Level::MIN, new_file_meta(2, 11, 20));
+        // The rows of file 1 and 2 before the sequence are not retained.
+        assert_eq!(None, picked_ids_at(&controller, 5));
+        assert_eq!(None, picked_ids_at(&controller, 15));
+        assert_eq!(Some(vec![]), picked_ids_at(&controller, 0));
+        assert_eq!(Some(vec![1]), picked_ids_at(&controller, 10));
+        assert_eq!(Some(vec![1, 2]), picked_ids_at(&controller, 20));
+
+        // Compact file 1 and 2 into file 3.
+        controller.add_sst_to_level(Level::MAX, new_file_meta(3, 1, 20));
+        controller.retire_ssts_from_level(Level::MIN, &[1, 2], 20, &[3], now);
+
+        // File 3 is read through file 1 and 2 before it's fully visible.
+        assert_eq!(None, picked_ids_at(&controller, 5));
+        assert_eq!(Some(vec![1]), picked_ids_at(&controller, 10));
+        assert_eq!(None, picked_ids_at(&controller, 15));
+        assert_eq!(Some(vec![3]), picked_ids_at(&controller, 20));
+        assert_eq!(Some(vec![3]), picked_ids_at(&controller, 30));
+
+        controller.purge_expired_obsolete_ssts(Timestamp::new(20_000));
+        assert_eq!(None, picked_ids_at(&controller, 10));
+        assert_eq!(Some(vec![3]), picked_ids_at(&controller, 20));
+    }
 }
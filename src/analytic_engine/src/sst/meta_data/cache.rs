@@ -25,13 +25,16 @@ use object_store::{ObjectStoreRef, Path};
 use parquet::{file::metadata::FileMetaData, format::KeyValue};
 use snafu::{ensure, OptionExt};
 
-use crate::sst::{
-    meta_data::{
-        metadata_reader::parse_metadata, KvMetaDataNotFound, KvMetaVersionEmpty,
-        ParquetMetaDataRef, Result,
+use crate::{
+    sst::{
+        meta_data::{
+            metadata_reader::parse_metadata, KvMetaDataNotFound, KvMetaVersionEmpty,
+            ParquetMetaDataRef, Result,
+        },
+        metrics::{META_DATA_CACHE_HIT_COUNTER, META_DATA_CACHE_MISS_COUNTER},
+        parquet::encoding,
     },
-    metrics::{META_DATA_CACHE_HIT_COUNTER, META_DATA_CACHE_MISS_COUNTER},
-    parquet::encoding,
+    table::sst_util::new_metadata_path,
 };
 
 pub type MetaCacheRef = Arc<MetaCache>;
@@ -51,11 +54,15 @@ impl MetaData {
     ///
     /// After the building, a new parquet meta data will be generated which
     /// contains no extended custom information.
+    ///
+    /// The `sst_path` is the path of the sst file to which the metadata
+    /// belongs.
     // TODO: remove it and use the suggested api.
     #[allow(deprecated)]
     pub async fn try_new(
         parquet_meta_data: &parquet_ext::ParquetMetaData,
         ignore_sst_filter: bool,
+        sst_path: &Path,
         store: ObjectStoreRef,
     ) -> Result<Self> {
         let file_meta_data = parquet_meta_data.file_metadata();
@@ -74,7 +81,13 @@ impl MetaData {
             if kv_meta.key == encoding::META_KEY {
                 custom_kv_meta = Some(kv_meta);
             } else if kv_meta.key == encoding::META_PATH_KEY {
-                meta_path = kv_meta.value.as_ref().map(|path| Path::from(path.as_str()))
+                // The meta file is always placed next to the sst, so derive its path from the
+                // sst path instead of the recorded one, which becomes stale after the sst is
+                // moved, e.g. restored from a backup.
+                meta_path = kv_meta
+                    .value
+                    .as_ref()
+                    .map(|_| Path::from(new_metadata_path(sst_path.as_ref())))
             } else if kv_meta.key == encoding::META_VERSION_KEY {
                 meta_version = kv_meta.value.as_ref().context(KvMetaVersionEmpty)?;
             } else {
@@ -333,7 +346,8 @@ mod tests {
 
         let parquet_file = File::open(parquet_file_path.as_path()).unwrap();
         let parquet_meta_data = footer::parse_metadata(&parquet_file).unwrap();
-        let meta_data = MetaData::try_new(&parquet_meta_data, false, store)
+        let sst_path = object_store::Path::from(parquet_file_path.to_str().unwrap());
+        let meta_data = MetaData::try_new(&parquet_meta_data, false, &sst_path, store)
             .await
             .unwrap();
 
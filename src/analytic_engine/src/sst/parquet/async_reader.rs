@@ -423,10 +423,15 @@ impl<'a> Reader<'a> {
             parquet_meta_data = meta_data;
         }
 
-        MetaData::try_new(&parquet_meta_data, ignore_sst_filter, self.store.clone())
-            .await
-            .box_err()
-            .context(DecodeSstMeta)
+        MetaData::try_new(
+            &parquet_meta_data,
+            ignore_sst_filter,
+            self.path,
+            self.store.clone(),
+        )
+        .await
+        .box_err()
+        .context(DecodeSstMeta)
     }
 
     fn need_update_cache(&self) -> bool {
@@ -27,7 +27,7 @@ use horaedbproto::sst as sst_pb;
 use macros::define_result;
 use parquet::{
     arrow::AsyncArrowWriter,
-    basic::Compression,
+    basic::{Compression, Encoding},
     file::{metadata::KeyValue, properties::WriterProperties},
     schema::types::ColumnPath,
 };
@@ -241,9 +241,14 @@ struct ColumnarRecordEncoder<W> {
     arrow_schema: ArrowSchemaRef,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ColumnEncoding {
     pub enable_dict: bool,
+    /// Encoding of the values, used as the fallback encoding if dictionary is
+    /// enabled.
+    pub encoding: Option<Encoding>,
+    /// Compression of the column, overrides the compression of the table.
+    pub compression: Option<Compression>,
 }
 
 #[derive(Debug, Clone)]
@@ -265,7 +270,14 @@ impl<W: AsyncWrite + Send + Unpin> ColumnarRecordEncoder<W> {
 
             for (col_name, encoding) in &options.column_encodings {
                 let col_path = ColumnPath::new(vec![col_name.to_string()]);
-                builder = builder.set_column_dictionary_enabled(col_path, encoding.enable_dict);
+                builder =
+                    builder.set_column_dictionary_enabled(col_path.clone(), encoding.enable_dict);
+                if let Some(value_encoding) = encoding.encoding {
+                    builder = builder.set_column_encoding(col_path.clone(), value_encoding);
+                }
+                if let Some(compression) = encoding.compression {
+                    builder = builder.set_column_compression(col_path, compression);
+                }
             }
 
             builder.build()
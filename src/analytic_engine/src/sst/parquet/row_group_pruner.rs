@@ -24,9 +24,13 @@ use std::{
 };
 
 use arrow::datatypes::SchemaRef;
-use common_types::datum::Datum;
+use common_types::{
+    column_schema::ColumnSchema,
+    datum::{Datum, DatumKind},
+    json::{self, JsonPath},
+};
 use datafusion::{
-    logical_expr::Operator,
+    logical_expr::{expr::ScalarUDF, BinaryExpr, Operator},
     prelude::{lit, Expr},
     scalar::ScalarValue,
 };
@@ -44,6 +48,10 @@ use crate::sst::{
     reader::error::{OtherNoCause, Result},
 };
 
+/// Name of the udf to extract the json value as plain string, which is
+/// registered in the `df_operator`.
+const JSON_EXTRACT_STRING: &str = "json_extract_string";
+
 #[derive(Default, Debug, Clone, TraceMetricWhenDrop)]
 struct Metrics {
     #[metric(boolean)]
@@ -176,6 +184,65 @@ fn rewrite_not_expr(expr: Expr, column_values: &HashMap<String, Option<ColumnVal
 
     expr
 }
+
+/// The leaves of the json column are indexed in the parquet filter, so the path
+/// equality of the json column can be rewritten into the equality of the
+/// column and the leaf key, e.g. `json_extract_string(col, '$.a') = 'x'` into
+/// `col = '$.a=x'`.
+///
+/// NOTE: The rewritten expr is only valid for the parquet filter.
+fn rewrite_json_path_expr(expr: Expr, json_columns: &HashSet<&str>) -> Expr {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => match op {
+            Operator::And | Operator::Or => Expr::BinaryExpr(BinaryExpr::new(
+                Box::new(rewrite_json_path_expr(*left, json_columns)),
+                op,
+                Box::new(rewrite_json_path_expr(*right, json_columns)),
+            )),
+            Operator::Eq => json_path_equality(&left, &right, json_columns)
+                .or_else(|| json_path_equality(&right, &left, json_columns))
+                .unwrap_or(Expr::BinaryExpr(BinaryExpr { left, op, right })),
+            _ => Expr::BinaryExpr(BinaryExpr { left, op, right }),
+        },
+        _ => expr,
+    }
+}
+
+/// Returns `col = leaf_key` if `extracted` is `json_extract_string(col, path)`
+/// over the json column and `value` is a string literal.
+fn json_path_equality(
+    extracted: &Expr,
+    value: &Expr,
+    json_columns: &HashSet<&str>,
+) -> Option<Expr> {
+    let (column, path) = match extracted {
+        Expr::ScalarUDF(ScalarUDF { fun, args })
+            if fun.name == JSON_EXTRACT_STRING && args.len() == 2 =>
+        {
+            match (&args[0], &args[1]) {
+                (Expr::Column(column), Expr::Literal(ScalarValue::Utf8(Some(path))))
+                    if json_columns.contains(column.name.as_str()) =>
+                {
+                    (column, path)
+                }
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let value = match value {
+        Expr::Literal(ScalarValue::Utf8(Some(v))) => v,
+        _ => return None,
+    };
+    // The extracted object and array are not indexed.
+    if value.starts_with('{') || value.starts_with('[') {
+        return None;
+    }
+
+    let path = JsonPath::parse(path).ok()?;
+    Some(Expr::Column(column.clone()).eq(lit(json::leaf_key(&path, value))))
+}
+
 impl<'a> RowGroupPruner<'a> {
     // TODO: DataFusion already change predicates to PhyscialExpr, we should keep up
     // with upstream.
@@ -301,14 +368,39 @@ impl<'a> RowGroupPruner<'a> {
                 }
             };
 
+        let predicates = self.rewrite_json_path_predicates();
         equal::prune_row_groups(
             self.schema.clone(),
-            &self.predicates,
+            &predicates,
             self.row_groups.len(),
             is_equal,
         )
     }
 
+    fn rewrite_json_path_predicates(&self) -> Cow<'_, [Expr]> {
+        let json_columns: HashSet<_> = self
+            .schema
+            .fields()
+            .iter()
+            .filter(|field| {
+                ColumnSchema::try_from(*field)
+                    .map(|column| column.data_type == DatumKind::Json)
+                    .unwrap_or(false)
+            })
+            .map(|field| field.name().as_str())
+            .collect();
+        if json_columns.is_empty() {
+            return Cow::Borrowed(&self.predicates);
+        }
+
+        let predicates = self
+            .predicates
+            .iter()
+            .map(|expr| rewrite_json_path_expr(expr.clone(), &json_columns))
+            .collect();
+        Cow::Owned(predicates)
+    }
+
     /// Compute the intersection of the two row groups which are in increasing
     /// order.
     fn intersect_pruned_row_groups(row_groups0: &[usize], row_groups1: &[usize]) -> Vec<usize> {
@@ -412,4 +504,63 @@ mod tests {
             assert_eq!(expected, rewrite_not_expr(input, &column_values));
         }
     }
+    #[test]
+    fn test_rewrite_json_path_expr() {
+        use std::sync::Arc;
+
+        use arrow::datatypes::DataType;
+        use datafusion::logical_expr::{create_udf, Volatility};
+
+        let json_extract_string = create_udf(
+            JSON_EXTRACT_STRING,
+            vec![DataType::Utf8, DataType::Utf8],
+            Arc::new(DataType::Utf8),
+            Volatility::Immutable,
+            Arc::new(|_| unimplemented!()),
+        );
+        let extract =
+            |column: &str, path: &str| json_extract_string.call(vec![col(column), lit(path)]);
+        let json_columns: HashSet<_> = ["attrs"].into_iter().collect();
+
+        let testcases = vec![
+            (
+                extract("attrs", "$.a").eq(lit("x")),
+                col("attrs").eq(lit("$.a=x")),
+            ),
+            (
+                lit("1").eq(extract("attrs", r#"$."b"[0]"#)),
+                col("attrs").eq(lit("$.b[0]=1")),
+            ),
+            (
+                extract("attrs", "$.a")
+                    .eq(lit("x"))
+                    .and(col("host").eq(lit("web1"))),
+                col("attrs")
+                    .eq(lit("$.a=x"))
+                    .and(col("host").eq(lit("web1"))),
+            ),
+            // Can't rewrite since host is not a json column.
+            (
+                extract("host", "$.a").eq(lit("x")),
+                extract("host", "$.a").eq(lit("x")),
+            ),
+            // Can't rewrite since the object is not indexed.
+            (
+                extract("attrs", "$.a").eq(lit("{}")),
+                extract("attrs", "$.a").eq(lit("{}")),
+            ),
+            // Can't rewrite since the path is invalid.
+            (
+                extract("attrs", "a").eq(lit("x")),
+                extract("attrs", "a").eq(lit("x")),
+            ),
+            (
+                extract("attrs", "$.a").not_eq(lit("x")),
+                extract("attrs", "$.a").not_eq(lit("x")),
+            ),
+        ];
+        for (input, expected) in testcases {
+            assert_eq!(expected, rewrite_json_path_expr(input, &json_columns));
+        }
+    }
 }
@@ -21,10 +21,15 @@ use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
 use common_types::{
-    datum::DatumKind, record_batch::FetchedRecordBatch, request_id::RequestId, schema::Schema,
+    column_schema::{CompressionHint, EncodingHint},
+    datum::{DatumKind, DatumView},
+    json,
+    record_batch::FetchedRecordBatch,
+    request_id::RequestId,
+    schema::Schema,
     time::TimeRange,
 };
-use datafusion::parquet::basic::Compression;
+use datafusion::parquet::basic::{Compression, Encoding};
 use futures::StreamExt;
 use generic_error::BoxError;
 use logger::{debug, error};
@@ -231,7 +236,10 @@ impl<'a> RecordBatchGroupWriter<'a> {
             max_unique_value_ratio: MAX_UNIQUE_VALUE_RATIO_DICT_ENCODING,
             column_encodings,
         };
-        sampler.sample()
+        sampler.sample()?;
+        decide_column_value_encodings(&self.meta_data.schema, column_encodings);
+
+        Ok(())
     }
 
     /// Build the parquet filter for the given `row_group`.
@@ -249,6 +257,16 @@ impl<'a> RecordBatchGroupWriter<'a> {
                     datum_view.do_with_bytes(|bytes| {
                         builder.add_key(col_idx, bytes);
                     });
+
+                    // Index the leaves of the json document too, so the row groups can be
+                    // pruned by `json_extract_string(column, path) = value`.
+                    if let DatumView::Json(v) = datum_view {
+                        if let Ok(doc) = json::parse(v) {
+                            json::for_each_leaf(&doc, |path, value| {
+                                builder.add_key(col_idx, json::leaf_key(path, value).as_bytes());
+                            });
+                        }
+                    }
                 }
             }
         }
@@ -561,7 +579,10 @@ impl<'a> ColumnEncodingSampler<'a> {
             if !Self::is_dictionary_type(col_schema.data_type) {
                 self.column_encodings.insert(
                     col_schema.name.clone(),
-                    ColumnEncoding { enable_dict: false },
+                    ColumnEncoding {
+                        enable_dict: false,
+                        ..Default::default()
+                    },
                 );
                 continue;
             }
@@ -588,8 +609,13 @@ impl<'a> ColumnEncodingSampler<'a> {
             // small.
             let enable_dict = column_hashes.len() < max_unique_values;
             column_hashes.clear();
-            self.column_encodings
-                .insert(col_schema.name.clone(), ColumnEncoding { enable_dict });
+            self.column_encodings.insert(
+                col_schema.name.clone(),
+                ColumnEncoding {
+                    enable_dict,
+                    ..Default::default()
+                },
+            );
         }
 
         Ok(())
@@ -600,7 +626,10 @@ impl<'a> ColumnEncodingSampler<'a> {
             if !Self::is_dictionary_type(col_schema.data_type) {
                 self.column_encodings.insert(
                     col_schema.name.clone(),
-                    ColumnEncoding { enable_dict: false },
+                    ColumnEncoding {
+                        enable_dict: false,
+                        ..Default::default()
+                    },
                 );
             }
         }
@@ -613,6 +642,82 @@ impl<'a> ColumnEncodingSampler<'a> {
     }
 }
 
+/// Decide the value encoding and compression of the columns by the hints in
+/// the schema, the value encoding is chosen by the data type if the column has
+/// no hint and the dictionary is not enabled.
+fn decide_column_value_encodings(
+    schema: &Schema,
+    column_encodings: &mut HashMap<String, ColumnEncoding>,
+) {
+    for col_schema in schema.columns() {
+        let column_encoding = column_encodings
+            .entry(col_schema.name.clone())
+            // The dictionary is enabled by default in parquet.
+            .or_insert(ColumnEncoding {
+                enable_dict: true,
+                ..Default::default()
+            });
+        match col_schema.encoding {
+            Some(EncodingHint::Dictionary) => column_encoding.enable_dict = true,
+            Some(hint) => {
+                column_encoding.enable_dict = false;
+                column_encoding.encoding = Some(parquet_encoding(hint));
+            }
+            None => {
+                if !column_encoding.enable_dict {
+                    column_encoding.encoding = default_value_encoding(col_schema.data_type);
+                }
+            }
+        }
+        column_encoding.compression = col_schema.compression.map(parquet_compression);
+    }
+}
+
+fn default_value_encoding(data_type: DatumKind) -> Option<Encoding> {
+    match data_type {
+        DatumKind::Timestamp
+        | DatumKind::UInt64
+        | DatumKind::UInt32
+        | DatumKind::UInt16
+        | DatumKind::UInt8
+        | DatumKind::Int64
+        | DatumKind::Int32
+        | DatumKind::Int16
+        | DatumKind::Int8
+        | DatumKind::Date
+        | DatumKind::Time => Some(Encoding::DELTA_BINARY_PACKED),
+        DatumKind::Double | DatumKind::Float => Some(Encoding::BYTE_STREAM_SPLIT),
+        DatumKind::Null
+        | DatumKind::Varbinary
+        | DatumKind::String
+        | DatumKind::Boolean
+        | DatumKind::Decimal
+        | DatumKind::Json
+        | DatumKind::Array => None,
+    }
+}
+
+/// The dictionary hint is handled by `enable_dict`, so it should not be passed
+/// here.
+fn parquet_encoding(hint: EncodingHint) -> Encoding {
+    match hint {
+        EncodingHint::Plain | EncodingHint::Dictionary => Encoding::PLAIN,
+        EncodingHint::DeltaBinaryPacked => Encoding::DELTA_BINARY_PACKED,
+        EncodingHint::DeltaLengthByteArray => Encoding::DELTA_LENGTH_BYTE_ARRAY,
+        EncodingHint::DeltaByteArray => Encoding::DELTA_BYTE_ARRAY,
+        EncodingHint::ByteStreamSplit => Encoding::BYTE_STREAM_SPLIT,
+    }
+}
+
+fn parquet_compression(hint: CompressionHint) -> Compression {
+    match hint {
+        CompressionHint::Uncompressed => Compression::UNCOMPRESSED,
+        CompressionHint::Lz4 => Compression::LZ4,
+        CompressionHint::Snappy => Compression::SNAPPY,
+        CompressionHint::Zstd => Compression::ZSTD(Default::default()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -620,6 +725,7 @@ mod tests {
 
     use bytes_ext::Bytes;
     use common_types::{
+        column_schema,
         projected_schema::{ProjectedSchema, RowProjectorBuilder},
         tests::{build_row, build_row_for_dictionary, build_schema, build_schema_with_dictionary},
         time::{TimeRange, Timestamp},
@@ -946,17 +1052,92 @@ mod tests {
     ) {
         sampler.sample().unwrap();
         for (col_idx, col_schema) in sampler.meta_data.schema.columns().iter().enumerate() {
-            let expect_enable_dict =
-                expect_enable_dicts[col_idx].map(|v| ColumnEncoding { enable_dict: v });
-            let column_encoding = sampler.column_encodings.get(&col_schema.name).cloned();
+            let enable_dict = sampler
+                .column_encodings
+                .get(&col_schema.name)
+                .map(|v| v.enable_dict);
             assert_eq!(
-                expect_enable_dict, column_encoding,
+                expect_enable_dicts[col_idx], enable_dict,
                 "column:{}",
                 col_schema.name
             );
         }
     }
 
+    #[test]
+    fn test_decide_column_value_encodings() {
+        let hints = [
+            (
+                "field1",
+                DatumKind::Double,
+                None,
+                Some(CompressionHint::Zstd),
+            ),
+            ("field2", DatumKind::String, Some(EncodingHint::Plain), None),
+            ("field3", DatumKind::Int64, None, None),
+            (
+                "field4",
+                DatumKind::String,
+                Some(EncodingHint::Dictionary),
+                Some(CompressionHint::Uncompressed),
+            ),
+        ];
+        let mut builder = common_types::schema::Builder::new()
+            .auto_increment_column_id(true)
+            .add_key_column(
+                column_schema::Builder::new("key".to_string(), DatumKind::Timestamp)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap()
+            .primary_key_indexes(vec![0]);
+        for (name, kind, encoding, compression) in hints {
+            builder = builder
+                .add_normal_column(
+                    column_schema::Builder::new(name.to_string(), kind)
+                        .encoding(encoding)
+                        .compression(compression)
+                        .build()
+                        .unwrap(),
+                )
+                .unwrap();
+        }
+        let schema = builder.build().unwrap();
+
+        let mut column_encodings = HashMap::new();
+        for name in ["field1", "field3"] {
+            column_encodings.insert(
+                name.to_string(),
+                ColumnEncoding {
+                    enable_dict: false,
+                    ..Default::default()
+                },
+            );
+        }
+        decide_column_value_encodings(&schema, &mut column_encodings);
+
+        let expects = [
+            ("key", true, None, None),
+            (
+                "field1",
+                false,
+                Some(Encoding::BYTE_STREAM_SPLIT),
+                Some(Compression::ZSTD(Default::default())),
+            ),
+            ("field2", false, Some(Encoding::PLAIN), None),
+            ("field3", false, Some(Encoding::DELTA_BINARY_PACKED), None),
+            ("field4", true, None, Some(Compression::UNCOMPRESSED)),
+        ];
+        for (name, enable_dict, encoding, compression) in expects {
+            let expect = ColumnEncoding {
+                enable_dict,
+                encoding,
+                compression,
+            };
+            assert_eq!(Some(&expect), column_encodings.get(name), "column:{name}");
+        }
+    }
+
     #[test]
     fn test_column_encoding_option_sample() {
         let schema = build_schema();
@@ -1046,8 +1227,12 @@ mod tests {
         // `field1` is double type, it will still be changed to false even if it is set
         // as true.
         // `field2` is string type, it will be kept as the pre-set.
-        column_encodings.insert("field1".to_string(), ColumnEncoding { enable_dict: true });
-        column_encodings.insert("field2".to_string(), ColumnEncoding { enable_dict: true });
+        let dict_encoding = ColumnEncoding {
+            enable_dict: true,
+            ..Default::default()
+        };
+        column_encodings.insert("field1".to_string(), dict_encoding.clone());
+        column_encodings.insert("field2".to_string(), dict_encoding);
         let sampler = ColumnEncodingSampler {
             sample_row_groups: &record_batches_with_key,
             meta_data: &meta_data,
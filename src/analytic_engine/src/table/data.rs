@@ -65,6 +65,7 @@ use crate::{
     sst::{file::FilePurger, manager::FileId},
     table::{
         metrics::{Metrics, MetricsContext},
+        sequence_history::SequenceHistory,
         sst_util,
         version::{MemTableForWrite, MemTableState, SamplingMemTable, TableVersion},
     },
@@ -208,6 +209,8 @@ pub struct TableData {
     /// single writer, but reads are allowed to be done concurrently without
     /// mutex protected
     last_sequence: AtomicU64,
+    /// History of the last sequence, only recorded if time-travel is enabled
+    sequence_history: SequenceHistory,
 
     /// Auto incremented id to track memtable, reset on engine open
     ///
@@ -341,6 +344,7 @@ impl TableData {
         let purge_queue = purger.create_purge_queue(space_id, id);
         let current_version =
             TableVersion::new(mem_size_options.size_sampling_interval, purge_queue);
+        current_version.set_obsolete_retention(opts.time_travel_retention());
         let metrics_ctx = MetricsContext::new(&name, shard_id, metrics_opt);
         let metrics = Metrics::new(metrics_ctx);
         let mutable_limit = AtomicU32::new(compute_mutable_limit(
@@ -365,6 +369,7 @@ impl TableData {
             mem_usage_collector: mem_size_options.collector,
             current_version,
             last_sequence: AtomicU64::new(0),
+            sequence_history: SequenceHistory::default(),
             last_memtable_id: AtomicU64::new(0),
             allocator: IdAllocator::new(0, 0, DEFAULT_ALLOC_STEP),
             last_flush_time_ms: AtomicU64::new(0),
@@ -421,6 +426,7 @@ impl TableData {
         let purge_queue = purger.create_purge_queue(add_meta.space_id, add_meta.table_id);
         let current_version =
             TableVersion::new(mem_size_options.size_sampling_interval, purge_queue);
+        current_version.set_obsolete_retention(add_meta.opts.time_travel_retention());
         let metrics_ctx = MetricsContext::new(&add_meta.table_name, shard_id, metrics_opt);
         let metrics = Metrics::new(metrics_ctx);
         let mutable_limit = AtomicU32::new(compute_mutable_limit(
@@ -441,6 +447,7 @@ impl TableData {
             mem_usage_collector: mem_size_options.collector,
             current_version,
             last_sequence: AtomicU64::new(0),
+            sequence_history: SequenceHistory::default(),
             last_memtable_id: AtomicU64::new(0),
             allocator,
             last_flush_time_ms: AtomicU64::new(0),
@@ -493,6 +500,20 @@ impl TableData {
         self.last_sequence.fetch_add(1, Ordering::Relaxed) + 1
     }
 
+    /// Record the last sequence at current time for time-travel reads.
+    pub fn record_sequence_history(&self, sequence: SequenceNumber) {
+        if let Some(retention) = self.table_options().time_travel_retention() {
+            self.sequence_history
+                .record(Timestamp::now(), sequence, retention);
+        }
+    }
+
+    /// Find the last sequence visible at `timestamp`, returns `None` if the
+    /// `timestamp` is out of the recorded history.
+    pub fn sequence_at(&self, timestamp: Timestamp) -> Option<SequenceNumber> {
+        self.sequence_history.sequence_at(timestamp)
+    }
+
     /// Get last flush time
     #[inline]
     pub fn last_flush_time(&self) -> u64 {
@@ -518,6 +539,11 @@ impl TableData {
             self.mutable_limit_write_buffer_ratio,
         );
         self.mutable_limit.store(mutable_limit, Ordering::Relaxed);
+        let retention = opts.time_travel_retention();
+        if retention.is_none() {
+            self.sequence_history.clear();
+        }
+        self.current_version.set_obsolete_retention(retention);
         self.opts.store(Arc::new(opts))
     }
 
@@ -737,6 +763,10 @@ impl TableData {
         self.table_options().need_dedup()
     }
 
+    pub fn merge_duplicates(&self) -> bool {
+        self.table_options().merge_duplicates()
+    }
+
     pub fn is_expired(&self, timestamp: Timestamp) -> bool {
         self.table_options().is_expired(timestamp)
     }
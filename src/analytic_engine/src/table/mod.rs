@@ -40,10 +40,11 @@ use table_engine::{
     predicate::PredicateBuilder,
     stream::{PartitionedStreams, SendableRecordBatchStream},
     table::{
-        AlterOptions, AlterSchema, AlterSchemaRequest, Compact, Flush, FlushRequest, Get,
-        GetInvalidPrimaryKey, GetNullPrimaryKey, GetRequest, MergeWrite, ReadOptions, ReadRequest,
-        Result, Scan, Table, TableId, TableStats, TooManyPendingWrites, WaitForPendingWrites,
-        Write, WriteRequest,
+        AlterOptions, AlterSchema, AlterSchemaRequest, Backup, BackupRequest, BackupStats, Compact,
+        Flush, FlushRequest, Get, GetInvalidPrimaryKey, GetNullPrimaryKey, GetRequest, MergeWrite,
+        ReadOptions, ReadRequest, Restore, RestoreRequest, Result, Scan, Table, TableId,
+        TableStats, TableStorageInfo, TooManyPendingWrites, Verify, VerifyReport, VerifyRequest,
+        WaitForPendingWrites, Write, WriteRequest,
     },
     ANALYTIC_ENGINE_TYPE,
 };
@@ -58,6 +59,7 @@ use crate::{
 
 pub mod data;
 pub mod metrics;
+pub mod sequence_history;
 pub mod sst_util;
 pub mod version;
 pub mod version_edit;
@@ -465,6 +467,20 @@ impl Table for TableImpl {
         self.table_data.metrics.table_stats()
     }
 
+    fn storage_info(&self) -> Option<TableStorageInfo> {
+        let version = self.table_data.current_version();
+        let (memtables, ssts) = version.memtable_and_sst_infos();
+
+        Some(TableStorageInfo {
+            ssts,
+            memtables,
+            last_sequence: self.table_data.last_sequence(),
+            flushed_sequence: version.flushed_sequence(),
+            last_flush_time_ms: self.table_data.last_flush_time(),
+            compaction_pending: self.instance.is_compaction_pending(self.table_data.id),
+        })
+    }
+
     fn support_pushdown(&self, read_schema: &Schema, col_names: &[String]) -> bool {
         let need_dedup = self.table_data.table_options().need_dedup();
 
@@ -646,6 +662,30 @@ impl Table for TableImpl {
             .context(Compact { table: self.name() })?;
         Ok(())
     }
+
+    async fn backup(&self, request: BackupRequest) -> Result<BackupStats> {
+        self.instance
+            .backup_table(&self.table_data, request)
+            .await
+            .box_err()
+            .context(Backup { table: self.name() })
+    }
+
+    async fn restore(&self, request: RestoreRequest) -> Result<BackupStats> {
+        self.instance
+            .restore_table(&self.table_data, request)
+            .await
+            .box_err()
+            .context(Restore { table: self.name() })
+    }
+
+    async fn verify(&self, request: VerifyRequest) -> Result<VerifyReport> {
+        self.instance
+            .verify_table(&self.table_data, request)
+            .await
+            .box_err()
+            .context(Verify { table: self.name() })
+    }
 }
 
 #[cfg(test)]
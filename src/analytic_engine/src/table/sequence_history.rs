@@ -0,0 +1,101 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! History of the table sequence, used to resolve the time-travel reads by
+//! timestamp.
+
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+use common_types::{time::Timestamp, SequenceNumber};
+
+/// Writes in the same interval share one sample.
+const SAMPLE_INTERVAL_MS: i64 = 1000;
+
+/// Samples of `(time, last sequence)` ordered by time, at most one sample per
+/// second.
+///
+/// The history is kept in memory only, so it is lost after the table is
+/// reopened.
+#[derive(Debug, Default)]
+pub struct SequenceHistory {
+    samples: Mutex<VecDeque<(Timestamp, SequenceNumber)>>,
+}
+
+impl SequenceHistory {
+    /// Record the last sequence at `now` and discard the samples out of the
+    /// `retention`.
+    pub fn record(&self, now: Timestamp, sequence: SequenceNumber, retention: Duration) {
+        let mut samples = self.samples.lock().unwrap();
+        match samples.back_mut() {
+            Some((time, last_seq))
+                if now.as_i64() / SAMPLE_INTERVAL_MS == time.as_i64() / SAMPLE_INTERVAL_MS =>
+            {
+                *time = now.max(*time);
+                *last_seq = sequence.max(*last_seq);
+            }
+            _ => samples.push_back((now, sequence)),
+        }
+
+        let expire_time = now.sub_duration_or_min(retention);
+        while samples.len() > 1 && samples[0].0 < expire_time {
+            samples.pop_front();
+        }
+    }
+
+    /// Find the last sequence visible at `timestamp`, returns `None` if the
+    /// `timestamp` is before the history.
+    pub fn sequence_at(&self, timestamp: Timestamp) -> Option<SequenceNumber> {
+        let samples = self.samples.lock().unwrap();
+        let idx = samples.partition_point(|(time, _)| *time <= timestamp);
+        if idx == 0 {
+            None
+        } else {
+            Some(samples[idx - 1].1)
+        }
+    }
+
+    pub fn clear(&self) {
+        self.samples.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_history() {
+        let history = SequenceHistory::default();
+        let retention = Duration::from_secs(10);
+        assert_eq!(None, history.sequence_at(Timestamp::new(1000)));
+
+        history.record(Timestamp::new(1000), 1, retention);
+        history.record(Timestamp::new(1500), 2, retention);
+        history.record(Timestamp::new(3000), 5, retention);
+
+        assert_eq!(None, history.sequence_at(Timestamp::new(999)));
+        assert_eq!(Some(2), history.sequence_at(Timestamp::new(2000)));
+        assert_eq!(Some(5), history.sequence_at(Timestamp::new(3000)));
+        assert_eq!(Some(5), history.sequence_at(Timestamp::new(10000)));
+
+        // Samples before 12000 - 10s are discarded.
+        history.record(Timestamp::new(12000), 8, retention);
+        assert_eq!(None, history.sequence_at(Timestamp::new(2000)));
+        assert_eq!(Some(5), history.sequence_at(Timestamp::new(3000)));
+        assert_eq!(Some(8), history.sequence_at(Timestamp::new(12000)));
+    }
+}
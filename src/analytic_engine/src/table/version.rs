@@ -35,6 +35,7 @@ use common_types::{
 use macros::define_result;
 use sampling_cache::SamplingCachedUsize;
 use snafu::{ensure, Backtrace, ResultExt, Snafu};
+use table_engine::table::{MemTableInfo, MemTableStatus, SstInfo};
 use time_ext::ReadableDuration;
 
 use crate::{
@@ -767,6 +768,20 @@ impl TableVersion {
 
         inner.max_file_id = cmp::max(inner.max_file_id, edit.max_file_id);
 
+        // The removed ssts are superseded by the added ones, or by the flushed data if
+        // nothing is added, e.g. the ssts are expired.
+        let superseded_seq = edit
+            .files_to_add
+            .iter()
+            .map(|add_file| add_file.file.max_seq)
+            .max()
+            .unwrap_or(inner.flushed_sequence);
+        let superseded_by: Vec<_> = edit
+            .files_to_add
+            .iter()
+            .map(|add_file| add_file.file.id)
+            .collect();
+
         // Add sst files to level first.
         for add_file in edit.files_to_add {
             inner
@@ -775,11 +790,17 @@ impl TableVersion {
         }
 
         // Remove ssts from level.
+        let now = Timestamp::now();
         for delete_file in edit.files_to_delete {
-            inner
-                .levels_controller
-                .remove_ssts_from_level(delete_file.level, &[delete_file.file_id]);
+            inner.levels_controller.retire_ssts_from_level(
+                delete_file.level,
+                &[delete_file.file_id],
+                superseded_seq,
+                &superseded_by,
+                now,
+            );
         }
+        inner.levels_controller.purge_expired_obsolete_ssts(now);
 
         // Remove immutable memtables.
         for mem_id in edit.mems_to_remove {
@@ -830,6 +851,67 @@ impl TableVersion {
         }
     }
 
+    /// Pick the memtables and ssts visible to the read at `sequence`, returns
+    /// `None` if the data at `sequence` is no longer retained.
+    ///
+    /// The rows of memtables are filtered by sequence during scan, so all the
+    /// memtables are picked.
+    pub fn pick_read_view_at_sequence(
+        &self,
+        time_range: TimeRange,
+        sequence: SequenceNumber,
+    ) -> Option<ReadView> {
+        let mut sampling_mem = None;
+        let mut memtables = MemTableVec::new();
+        let mut leveled_ssts = vec![Vec::new(); SST_LEVEL_NUM];
+
+        {
+            let inner = self.inner.read().unwrap();
+
+            inner
+                .memtable_view
+                .memtables_for_read(time_range, &mut memtables, &mut sampling_mem);
+
+            let retained = inner.levels_controller.pick_ssts_at_sequence(
+                time_range,
+                sequence,
+                |level, ssts| leveled_ssts[level.as_usize()].extend_from_slice(ssts),
+            );
+            if !retained {
+                return None;
+            }
+        }
+
+        Some(ReadView {
+            sampling_mem,
+            memtables,
+            leveled_ssts,
+        })
+    }
+
+    /// Set how long the removed ssts are retained for time-travel reads.
+    pub fn set_obsolete_retention(&self, retention: Option<Duration>) {
+        let mut inner = self.inner.write().unwrap();
+
+        inner
+            .levels_controller
+            .set_obsolete_retention(retention, Timestamp::now());
+    }
+
+    /// Whether the sst is still held by a reader or waiting to be purged.
+    pub fn is_file_alive(&self, file_id: FileId) -> bool {
+        let inner = self.inner.read().unwrap();
+
+        inner.levels_controller.is_file_alive(file_id)
+    }
+
+    /// The removed ssts retained for time-travel reads.
+    pub fn obsolete_ssts(&self) -> Vec<FileHandle> {
+        let inner = self.inner.read().unwrap();
+
+        inner.levels_controller.obsolete_ssts()
+    }
+
     /// Pick ssts for compaction using given `picker`.
     pub fn pick_for_compaction(
         &self,
@@ -859,6 +941,62 @@ impl TableVersion {
         inner.flushed_sequence
     }
 
+    /// Collect meta data of all the memtables and ssts.
+    pub fn memtable_and_sst_infos(&self) -> (Vec<MemTableInfo>, Vec<SstInfo>) {
+        let inner = self.inner.read().unwrap();
+
+        let memtable_view = &inner.memtable_view;
+        let mut memtables = Vec::new();
+        if let Some(v) = &memtable_view.sampling_mem {
+            memtables.push(MemTableInfo {
+                id: v.id,
+                status: MemTableStatus::Sampling,
+                memory_usage: v.memory_usage() as u64,
+                time_range: v.mem.time_range().unwrap_or_else(TimeRange::empty),
+                last_sequence: v.last_sequence(),
+            });
+        }
+        let mutables = memtable_view
+            .mutables
+            .0
+            .values()
+            .map(|v| (MemTableStatus::Mutable, v));
+        let immutables = memtable_view
+            .immutables
+            .0
+            .values()
+            .map(|v| (MemTableStatus::Immutable, v));
+        for (status, v) in mutables.chain(immutables) {
+            memtables.push(MemTableInfo {
+                id: v.id,
+                status,
+                memory_usage: v.mem.approximate_memory_usage() as u64,
+                time_range: v.real_time_range(),
+                last_sequence: v.last_sequence(),
+            });
+        }
+
+        let controller = &inner.levels_controller;
+        let ssts = controller
+            .levels()
+            .flat_map(|level| {
+                controller
+                    .iter_ssts_at_level(level)
+                    .map(move |file| SstInfo {
+                        level: level.as_u16(),
+                        file_id: file.id(),
+                        size: file.size(),
+                        row_num: file.row_num(),
+                        time_range: file.time_range(),
+                        max_sequence: file.max_sequence(),
+                        being_compacted: file.being_compacted(),
+                    })
+            })
+            .collect();
+
+        (memtables, ssts)
+    }
+
     pub fn snapshot(&self) -> TableVersionSnapshot {
         let inner = self.inner.read().unwrap();
         let controller = &inner.levels_controller;
@@ -982,6 +1120,10 @@ mod tests {
             assert!(ssts.is_empty());
         }
 
+        let (memtables, ssts) = version.memtable_and_sst_infos();
+        assert!(memtables.is_empty());
+        assert!(ssts.is_empty());
+
         let now = Timestamp::now();
         let mutable = version.memtable_for_write(now, 1).unwrap();
         assert!(mutable.is_none());
@@ -1042,6 +1184,12 @@ mod tests {
         assert!(read_view.contains_sampling());
         assert_eq!(memtable_id, read_view.sampling_mem.unwrap().id);
 
+        let (memtables, ssts) = version.memtable_and_sst_infos();
+        assert_eq!(1, memtables.len());
+        assert_eq!(memtable_id, memtables[0].id);
+        assert_eq!(MemTableStatus::Sampling, memtables[0].status);
+        assert!(ssts.is_empty());
+
         let last_sequence = 1000;
         let flushable_mems = version.pick_memtables_to_flush(last_sequence);
         check_flushable_mem_with_sampling(&flushable_mems, memtable_id);
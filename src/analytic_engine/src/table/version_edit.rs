@@ -77,6 +77,7 @@ impl From<AddFile> for manifest_pb::AddFileMeta {
             file_id: v.file.id,
             time_range: Some(v.file.time_range.into()),
             max_seq: v.file.max_seq,
+            min_seq: v.file.min_seq,
             size: v.file.size,
             row_num: v.file.row_num,
             storage_format: manifest_pb::StorageFormat::from(v.file.storage_format) as i32,
@@ -103,6 +104,7 @@ impl TryFrom<manifest_pb::AddFileMeta> for AddFile {
                 row_num: src.row_num,
                 time_range,
                 max_seq: src.max_seq,
+                min_seq: src.min_seq,
                 storage_format: StorageFormat::try_from(storage_format)
                     .context(ConvertStorageFormat)?,
                 associated_files: src.associated_files,
@@ -199,6 +201,7 @@ pub mod tests {
                     row_num: 0,
                     time_range: self.time_range,
                     max_seq: self.max_seq,
+                    min_seq: 0,
                     storage_format: StorageFormat::default(),
                     associated_files: Vec::new(),
                 },
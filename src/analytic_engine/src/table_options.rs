@@ -21,8 +21,8 @@ use std::{collections::HashMap, string::ToString, time::Duration};
 
 use common_types::{
     time::Timestamp, ARENA_BLOCK_SIZE, COMPACTION_STRATEGY, COMPRESSION, ENABLE_TTL, MEMTABLE_TYPE,
-    NUM_ROWS_PER_ROW_GROUP, OPTION_KEY_ENABLE_TTL, SEGMENT_DURATION, STORAGE_FORMAT, TTL,
-    UPDATE_MODE, WRITE_BUFFER_SIZE,
+    NUM_ROWS_PER_ROW_GROUP, OPTION_KEY_ENABLE_TTL, SEGMENT_DURATION, STORAGE_FORMAT,
+    TIME_TRAVEL_RETENTION, TTL, UPDATE_MODE, WRITE_BUFFER_SIZE,
 };
 use datafusion::parquet::basic::Compression as ParquetCompression;
 use horaedbproto::manifest as manifest_pb;
@@ -41,6 +41,8 @@ use crate::{
 
 const UPDATE_MODE_OVERWRITE: &str = "OVERWRITE";
 const UPDATE_MODE_APPEND: &str = "APPEND";
+const UPDATE_MODE_MERGE: &str = "MERGE";
+const UPDATE_MODE_AGGREGATE: &str = "AGGREGATE";
 const COMPRESSION_UNCOMPRESSED: &str = "UNCOMPRESSED";
 const COMPRESSION_LZ4: &str = "LZ4";
 const COMPRESSION_SNAPPY: &str = "SNAPPY";
@@ -157,6 +159,12 @@ define_result!(Error);
 pub enum UpdateMode {
     Overwrite,
     Append,
+    /// Like `Overwrite`, but the rows with the same primary key are merged by
+    /// choosing the latest non-null value of each column.
+    Merge,
+    /// Like `Merge`, but the values of the field columns with aggregation are
+    /// aggregated, e.g. summed, instead.
+    Aggregate,
 }
 
 impl UpdateMode {
@@ -165,6 +173,10 @@ impl UpdateMode {
             Ok(UpdateMode::Overwrite)
         } else if s.eq_ignore_ascii_case(UPDATE_MODE_APPEND) {
             Ok(UpdateMode::Append)
+        } else if s.eq_ignore_ascii_case(UPDATE_MODE_MERGE) {
+            Ok(UpdateMode::Merge)
+        } else if s.eq_ignore_ascii_case(UPDATE_MODE_AGGREGATE) {
+            Ok(UpdateMode::Aggregate)
         } else {
             ParseUpdateMode { s }.fail()
         }
@@ -176,6 +188,8 @@ impl ToString for UpdateMode {
         match self {
             UpdateMode::Append => UPDATE_MODE_APPEND.to_string(),
             UpdateMode::Overwrite => UPDATE_MODE_OVERWRITE.to_string(),
+            UpdateMode::Merge => UPDATE_MODE_MERGE.to_string(),
+            UpdateMode::Aggregate => UPDATE_MODE_AGGREGATE.to_string(),
         }
     }
 }
@@ -391,7 +405,8 @@ pub struct TableOptions {
     /// `None` means the table is doing the segment duration sampling and
     /// the actual duration is still unknown.
     pub segment_duration: Option<ReadableDuration>,
-    /// Table update mode, now support Overwrite(Default) and Append
+    /// Table update mode, now support Overwrite(Default), Append, Merge and
+    /// Aggregate
     pub update_mode: UpdateMode,
     /// Hint for storage format.
     pub storage_format_hint: StorageFormatHint,
@@ -416,6 +431,9 @@ pub struct TableOptions {
     pub memtable_type: MemtableType,
     /// Layered memtable options
     pub layered_memtable_opts: LayeredMemtableOptions,
+    /// How long the ssts removed by compaction are retained for time-travel
+    /// reads, zero means they are deleted at once.
+    pub time_travel_retention: ReadableDuration,
 }
 
 impl TableOptions {
@@ -438,6 +456,14 @@ impl TableOptions {
         }
     }
 
+    /// Retention of the ssts removed by compaction, `None` means time-travel
+    /// read is disabled.
+    #[inline]
+    pub fn time_travel_retention(&self) -> Option<Duration> {
+        let retention = self.time_travel_retention.0;
+        (!retention.is_zero()).then_some(retention)
+    }
+
     // for show create table
     pub fn to_raw_map(&self) -> HashMap<String, String> {
         let mut m = [
@@ -472,6 +498,13 @@ impl TableOptions {
         .into_iter()
         .collect();
         self.compaction_strategy.fill_raw_map(&mut m);
+        // Only show the retention if time-travel is enabled.
+        if !self.time_travel_retention.0.is_zero() {
+            m.insert(
+                TIME_TRAVEL_RETENTION.to_string(),
+                self.time_travel_retention.to_string(),
+            );
+        }
 
         m
     }
@@ -512,15 +545,21 @@ impl TableOptions {
 
     pub fn need_dedup(&self) -> bool {
         match self.update_mode {
-            UpdateMode::Overwrite => true,
+            UpdateMode::Overwrite | UpdateMode::Merge | UpdateMode::Aggregate => true,
             UpdateMode::Append => false,
         }
     }
 
+    /// Whether to merge the duplicate rows instead of keeping the latest one
+    /// during dedup.
+    pub fn merge_duplicates(&self) -> bool {
+        matches!(self.update_mode, UpdateMode::Merge | UpdateMode::Aggregate)
+    }
+
     // Only support sample primary key for APPEND.
     pub fn support_sample_pk(&self) -> bool {
         match self.update_mode {
-            UpdateMode::Overwrite => false,
+            UpdateMode::Overwrite | UpdateMode::Merge | UpdateMode::Aggregate => false,
             UpdateMode::Append => true,
         }
     }
@@ -619,6 +658,7 @@ impl From<TableOptions> for manifest_pb::TableOptions {
                 opts.storage_format_hint,
             )),
             layered_memtable_options: Some(layered_memtable_opts),
+            time_travel_retention: opts.time_travel_retention.0.as_millis_u64(),
             // TODO: persist `memtable_type` in PB.
         }
     }
@@ -629,6 +669,8 @@ impl From<UpdateMode> for manifest_pb::UpdateMode {
         match v {
             UpdateMode::Overwrite => manifest_pb::UpdateMode::Overwrite,
             UpdateMode::Append => manifest_pb::UpdateMode::Append,
+            UpdateMode::Merge => manifest_pb::UpdateMode::Merge,
+            UpdateMode::Aggregate => manifest_pb::UpdateMode::Aggregate,
         }
     }
 }
@@ -638,6 +680,8 @@ impl From<manifest_pb::UpdateMode> for UpdateMode {
         match v {
             manifest_pb::UpdateMode::Overwrite => UpdateMode::Overwrite,
             manifest_pb::UpdateMode::Append => UpdateMode::Append,
+            manifest_pb::UpdateMode::Merge => UpdateMode::Merge,
+            manifest_pb::UpdateMode::Aggregate => UpdateMode::Aggregate,
         }
     }
 }
@@ -702,6 +746,7 @@ impl TryFrom<manifest_pb::TableOptions> for TableOptions {
             storage_format_hint: StorageFormatHint::try_from(storage_format_hint)?,
             memtable_type: MemtableType::SkipList,
             layered_memtable_opts,
+            time_travel_retention: Duration::from_millis(opts.time_travel_retention).into(),
         };
 
         Ok(table_opts)
@@ -723,6 +768,7 @@ impl Default for TableOptions {
             storage_format_hint: StorageFormatHint::default(),
             memtable_type: MemtableType::SkipList,
             layered_memtable_opts: LayeredMemtableOptions::default(),
+            time_travel_retention: ReadableDuration::default(),
         }
     }
 }
@@ -791,6 +837,9 @@ fn merge_table_options(
     if let Some(v) = options.get(MEMTABLE_TYPE) {
         base_table_opts.memtable_type = MemtableType::parse_from(v);
     }
+    if let Some(v) = options.get(TIME_TRAVEL_RETENTION) {
+        base_table_opts.time_travel_retention = parse_duration(v).context(ParseDuration)?;
+    }
 
     let layered_memtable_opts =
         LayeredMemtableOptions::parse_from(options).context(ParseLayeredMemtableOptions)?;
@@ -805,3 +854,39 @@ fn parse_size(v: &str) -> Result<ReadableSize> {
         backtrace: Backtrace::generate(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_mode_pb_round_trip() {
+        for update_mode in [
+            UpdateMode::Overwrite,
+            UpdateMode::Append,
+            UpdateMode::Merge,
+            UpdateMode::Aggregate,
+        ] {
+            let opts = TableOptions {
+                update_mode: update_mode.clone(),
+                ..Default::default()
+            };
+            let opts_pb = manifest_pb::TableOptions::from(opts);
+            let decoded = TableOptions::try_from(opts_pb).unwrap();
+            assert_eq!(update_mode, decoded.update_mode);
+        }
+    }
+
+    #[test]
+    fn test_time_travel_retention_pb_round_trip() {
+        for retention in [Duration::ZERO, Duration::from_secs(3600)] {
+            let opts = TableOptions {
+                time_travel_retention: retention.into(),
+                ..Default::default()
+            };
+            let opts_pb = manifest_pb::TableOptions::from(opts);
+            let decoded = TableOptions::try_from(opts_pb).unwrap();
+            assert_eq!(retention, decoded.time_travel_retention.0);
+        }
+    }
+}
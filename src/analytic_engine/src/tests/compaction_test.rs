@@ -116,3 +116,81 @@ fn test_table_compact_current_segment<T: EngineBuildContext>(engine_context: T)
         .await;
     });
 }
+
+#[test]
+fn test_table_storage_info_rocks() {
+    let rocksdb_ctx = RocksDBEngineBuildContext::default();
+    test_table_storage_info(rocksdb_ctx);
+}
+
+#[test]
+fn test_table_storage_info_mem_wal() {
+    let memory_ctx = MemoryEngineBuildContext::default();
+    test_table_storage_info(memory_ctx);
+}
+
+fn test_table_storage_info<T: EngineBuildContext>(engine_context: T) {
+    let env = TestEnv::builder().build();
+    let mut test_ctx = env.new_context(engine_context);
+
+    env.block_on(async {
+        test_ctx.open().await;
+
+        let storage_info_table1 = "storage_info_table1";
+        let fixed_schema_table = test_ctx
+            .create_fixed_schema_table(storage_info_table1)
+            .await;
+
+        let storage_info = test_ctx.table(storage_info_table1).storage_info().unwrap();
+        assert!(storage_info.ssts.is_empty());
+        assert!(storage_info.memtables.is_empty());
+        assert!(!storage_info.compaction_pending);
+
+        let start_ms = test_ctx.start_ms();
+        for offset in 0..2 {
+            let rows = [(
+                "key1",
+                Timestamp::new(start_ms + offset),
+                "tag1-1",
+                11.0,
+                110.0,
+                "tag2-1",
+            )];
+            let row_group = fixed_schema_table.rows_to_row_group(&rows);
+            test_ctx
+                .write_to_table(storage_info_table1, row_group)
+                .await;
+
+            // Data not flushed yet stays in the memtables.
+            let storage_info = test_ctx.table(storage_info_table1).storage_info().unwrap();
+            assert!(!storage_info.memtables.is_empty());
+            assert!(storage_info.flushed_sequence < storage_info.last_sequence);
+
+            test_ctx
+                .flush_table_with_request(storage_info_table1, FlushRequest { sync: true })
+                .await;
+        }
+
+        // Every flush generates one sst.
+        let storage_info = test_ctx.table(storage_info_table1).storage_info().unwrap();
+        assert!(storage_info.memtables.is_empty());
+        assert_eq!(2, storage_info.ssts.len());
+        assert_eq!(storage_info.last_sequence, storage_info.flushed_sequence);
+        assert!(storage_info.last_flush_time_ms > 0);
+        for sst in &storage_info.ssts {
+            assert_eq!(0, sst.level);
+            assert_eq!(1, sst.row_num);
+            assert!(!sst.being_compacted);
+        }
+
+        // No sst is held by the compaction once it finishes.
+        test_ctx.compact_table(storage_info_table1).await;
+        let storage_info = test_ctx.table(storage_info_table1).storage_info().unwrap();
+        assert!(!storage_info.compaction_pending);
+        assert_eq!(
+            2,
+            storage_info.ssts.iter().map(|sst| sst.row_num).sum::<u64>()
+        );
+        assert!(storage_info.ssts.iter().all(|sst| !sst.being_compacted));
+    });
+}
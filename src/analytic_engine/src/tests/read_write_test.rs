@@ -17,10 +17,11 @@
 
 //! Read write test.
 
-use std::{thread, time};
+use std::{collections::HashMap, thread, time};
 
-use common_types::time::Timestamp;
+use common_types::{time::Timestamp, TIME_TRAVEL_RETENTION};
 use logger::info;
+use table_engine::table::{BackupRequest, ReadAsOf, ReadOptions, RestoreRequest};
 use wal::manager::WalsOpener;
 
 use crate::{
@@ -267,6 +268,177 @@ fn test_table_write_read<T: EngineBuildContext>(engine_context: T) {
     });
 }
 
+#[test]
+fn test_time_travel_read_after_flush_rocks() {
+    let rocksdb_ctxs = rocksdb_ctxs();
+    for ctx in rocksdb_ctxs {
+        test_time_travel_read_after_flush(ctx);
+    }
+}
+
+#[test]
+fn test_time_travel_read_after_flush_mem_wal() {
+    let memory_ctxs = memory_ctxs();
+    for ctx in memory_ctxs {
+        test_time_travel_read_after_flush(ctx);
+    }
+}
+
+fn test_time_travel_read_after_flush<T: EngineBuildContext>(engine_context: T) {
+    let env = TestEnv::builder().build();
+    let mut test_ctx = env.new_context(engine_context);
+
+    env.block_on(async {
+        test_ctx.open().await;
+
+        let test_table1 = "test_table1";
+        let fixed_schema_table = test_ctx.create_fixed_schema_table(test_table1).await;
+        let opts = HashMap::from([(TIME_TRAVEL_RETENTION.to_string(), "1h".to_string())]);
+        test_ctx.try_alter_options(test_table1, opts).await.unwrap();
+
+        let start_ms = test_ctx.start_ms();
+        let rows1 = [(
+            "key1",
+            Timestamp::new(start_ms),
+            "tag1-1",
+            11.0,
+            110.0,
+            "tag2-1",
+        )];
+        let rows2 = [(
+            "key2",
+            Timestamp::new(start_ms),
+            "tag1-2",
+            12.0,
+            110.0,
+            "tag2-2",
+        )];
+        // The two writes get sequence 1 and 2.
+        test_ctx
+            .write_to_table(test_table1, fixed_schema_table.rows_to_row_group(&rows1))
+            .await;
+        test_ctx
+            .write_to_table(test_table1, fixed_schema_table.rows_to_row_group(&rows2))
+            .await;
+
+        let read_opts_at = |sequence| ReadOptions {
+            as_of: Some(ReadAsOf::Sequence(sequence)),
+            ..Default::default()
+        };
+        let record_batches = test_ctx
+            .read_table(
+                test_table1,
+                fixed_schema_table.new_read_all_request(read_opts_at(1)),
+            )
+            .await;
+        fixed_schema_table.assert_batch_eq_to_rows(&record_batches, &rows1);
+
+        // Both writes are flushed into one sst, and the data at the sequence in the
+        // middle of the sst is no longer retained.
+        test_ctx.flush_table(test_table1).await;
+        let result = test_ctx
+            .table(test_table1)
+            .read(fixed_schema_table.new_read_all_request(read_opts_at(1)))
+            .await;
+        assert!(result.is_err());
+
+        let record_batches = test_ctx
+            .read_table(
+                test_table1,
+                fixed_schema_table.new_read_all_request(read_opts_at(2)),
+            )
+            .await;
+        fixed_schema_table.assert_batch_eq_to_rows(&record_batches, &[rows1[0], rows2[0]]);
+    });
+}
+
+#[test]
+fn test_overwrite_restored_rows_rocks() {
+    let rocksdb_ctxs = rocksdb_ctxs();
+    for ctx in rocksdb_ctxs {
+        test_overwrite_restored_rows(ctx);
+    }
+}
+
+#[test]
+fn test_overwrite_restored_rows_mem_wal() {
+    let memory_ctxs = memory_ctxs();
+    for ctx in memory_ctxs {
+        test_overwrite_restored_rows(ctx);
+    }
+}
+
+fn test_overwrite_restored_rows<T: EngineBuildContext>(engine_context: T) {
+    let env = TestEnv::builder().build();
+    let mut test_ctx = env.new_context(engine_context);
+
+    env.block_on(async {
+        test_ctx.open().await;
+
+        let test_table1 = "test_table1";
+        let test_table2 = "test_table2";
+        let fixed_schema_table = test_ctx.create_fixed_schema_table(test_table1).await;
+        test_ctx.create_fixed_schema_table(test_table2).await;
+
+        let start_ms = test_ctx.start_ms();
+        let old_rows = [(
+            "key1",
+            Timestamp::new(start_ms),
+            "tag1-1",
+            11.0,
+            110.0,
+            "tag2-1",
+        )];
+        // Several writes make the sequences of the backup greater than the ones of
+        // the restored table.
+        for _ in 0..3 {
+            test_ctx
+                .write_to_table(test_table1, fixed_schema_table.rows_to_row_group(&old_rows))
+                .await;
+        }
+        let path = "test_backup".to_string();
+        test_ctx
+            .table(test_table1)
+            .backup(BackupRequest { path: path.clone() })
+            .await
+            .unwrap();
+        test_ctx
+            .table(test_table2)
+            .restore(RestoreRequest { path })
+            .await
+            .unwrap();
+
+        let new_rows = [(
+            "key1",
+            Timestamp::new(start_ms),
+            "tag1-2",
+            12.0,
+            120.0,
+            "tag2-2",
+        )];
+        test_ctx
+            .write_to_table(test_table2, fixed_schema_table.rows_to_row_group(&new_rows))
+            .await;
+        let record_batches = test_ctx
+            .read_table(
+                test_table2,
+                fixed_schema_table.new_read_all_request(ReadOptions::default()),
+            )
+            .await;
+        fixed_schema_table.assert_batch_eq_to_rows(&record_batches, &new_rows);
+
+        // The new rows still override the restored ones after flush.
+        test_ctx.flush_table(test_table2).await;
+        let record_batches = test_ctx
+            .read_table(
+                test_table2,
+                fixed_schema_table.new_read_all_request(ReadOptions::default()),
+            )
+            .await;
+        fixed_schema_table.assert_batch_eq_to_rows(&record_batches, &new_rows);
+    });
+}
+
 #[test]
 fn test_table_write_get_rocks() {
     let rocksdb_ctxs = rocksdb_ctxs();
@@ -169,21 +169,25 @@ pub fn read_opts_list() -> Vec<ReadOptions> {
             batch_size: 1,
             read_parallelism: 1,
             deadline: None,
+            as_of: None,
         },
         ReadOptions {
             batch_size: 1,
             read_parallelism: 4,
             deadline: None,
+            as_of: None,
         },
         ReadOptions {
             batch_size: 100,
             read_parallelism: 1,
             deadline: None,
+            as_of: None,
         },
         ReadOptions {
             batch_size: 100,
             read_parallelism: 4,
             deadline: None,
+            as_of: None,
         },
     ]
 }
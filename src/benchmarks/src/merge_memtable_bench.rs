@@ -172,6 +172,7 @@ impl MergeMemTableBench {
             store_picker: &store_picker,
             merge_iter_options: iter_options.clone(),
             need_dedup: true,
+            merge_duplicates: false,
             reverse: false,
         });
 
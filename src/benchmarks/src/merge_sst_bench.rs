@@ -163,6 +163,7 @@ impl MergeSstBench {
             store_picker: &store_picker,
             merge_iter_options: iter_options.clone(),
             need_dedup: true,
+            merge_duplicates: false,
             reverse: false,
         });
 
@@ -208,6 +209,7 @@ impl MergeSstBench {
             deadline: None,
             space_id,
             table_id,
+            sequence: u64::MAX,
             projected_schema,
             predicate: Arc::new(Predicate::empty()),
             sst_factory: &sst_factory,
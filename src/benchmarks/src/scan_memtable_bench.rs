@@ -106,6 +106,7 @@ impl ScanMemTableBench {
             end_user_key: Bound::Unbounded,
             sequence: common_types::MAX_SEQUENCE_NUMBER,
             need_dedup: true,
+            merge_duplicates: false,
             reverse: false,
             metrics_collector: None,
             row_projector_builder,
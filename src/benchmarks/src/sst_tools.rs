@@ -120,7 +120,7 @@ pub async fn rebuild_sst(config: RebuildSstConfig, runtime: Arc<Runtime>) {
     let input_path = Path::from(config.input_file_name);
 
     let parquet_metadata = util::parquet_metadata(&store, &input_path).await;
-    let sst_meta = util::meta_from_sst(&parquet_metadata, &store, &None).await;
+    let sst_meta = util::meta_from_sst(&parquet_metadata, &input_path, &store, &None).await;
 
     let projected_schema = ProjectedSchema::no_projection(sst_meta.schema.clone());
     let scan_options = ScanOptions {
@@ -282,6 +282,7 @@ pub async fn merge_sst(config: MergeSstConfig, runtime: Arc<Runtime>) {
             store_picker: &store_picker,
             merge_iter_options: iter_options.clone(),
             need_dedup: true,
+            merge_duplicates: false,
             reverse: false,
             sst_read_options_builder: sst_read_options_builder.clone(),
         });
@@ -80,10 +80,11 @@ pub async fn parquet_metadata(
 
 pub async fn meta_from_sst(
     metadata: &parquet_ext::ParquetMetaData,
+    sst_path: &Path,
     store: &ObjectStoreRef,
     _meta_cache: &Option<MetaCacheRef>,
 ) -> MetaData {
-    let md = cache::MetaData::try_new(metadata, false, store.clone())
+    let md = cache::MetaData::try_new(metadata, false, sst_path, store.clone())
         .await
         .unwrap();
 
@@ -96,7 +97,7 @@ pub async fn schema_from_sst(
     meta_cache: &Option<MetaCacheRef>,
 ) -> Schema {
     let parquet_metadata = parquet_metadata(store, sst_path).await;
-    let sst_meta = meta_from_sst(&parquet_metadata, store, meta_cache).await;
+    let sst_meta = meta_from_sst(&parquet_metadata, sst_path, store, meta_cache).await;
     sst_meta.schema
 }
 
@@ -188,7 +189,7 @@ pub async fn file_handles_from_ssts(
     for file_id in sst_file_ids.iter() {
         let path = sst_util::new_sst_file_path(space_id, table_id, *file_id);
         let parquet_metadata = parquet_metadata(store, &path).await;
-        let sst_meta = meta_from_sst(&parquet_metadata, store, meta_cache).await;
+        let sst_meta = meta_from_sst(&parquet_metadata, &path, store, meta_cache).await;
 
         let file_meta = FileMeta {
             id: *file_id,
@@ -196,6 +197,7 @@ pub async fn file_handles_from_ssts(
             row_num: 0,
             time_range: sst_meta.time_range,
             max_seq: sst_meta.max_sequence,
+            min_seq: 0,
             storage_format: StorageFormat::Columnar,
             associated_files: Vec::new(),
         };
@@ -23,7 +23,11 @@ use catalog::{
     schema::NameRef,
     CatalogRef,
 };
-use system_catalog::{tables::Tables, SystemTableAdapter};
+use cluster::ClusterRef;
+use system_catalog::{
+    compactions::Compactions, memtables::MemTables, shards::Shards, sst_files::SstFiles,
+    tables::Tables, SystemTableAdapter,
+};
 
 use crate::system_tables::{SystemTables, SystemTablesBuilder};
 
@@ -40,10 +44,16 @@ pub struct CatalogManagerImpl {
 }
 
 impl CatalogManagerImpl {
-    pub fn new(manager: ManagerRef) -> Self {
+    /// Create the catalog manager, and the `cluster` is only provided in the
+    /// cluster mode.
+    pub fn new(manager: ManagerRef, cluster: Option<ClusterRef>) -> Self {
         let mut system_tables_builder = SystemTablesBuilder::new();
         system_tables_builder = system_tables_builder
-            .insert_table(SystemTableAdapter::new(Tables::new(manager.clone())));
+            .insert_table(SystemTableAdapter::new(Tables::new(manager.clone())))
+            .insert_table(SystemTableAdapter::new(SstFiles::new(manager.clone())))
+            .insert_table(SystemTableAdapter::new(MemTables::new(manager.clone())))
+            .insert_table(SystemTableAdapter::new(Compactions::new(manager.clone())))
+            .insert_table(SystemTableAdapter::new(Shards::new(cluster)));
         Self {
             system_tables: system_tables_builder.build(),
             user_catalog_manager: manager,
@@ -102,6 +102,11 @@ impl Shard {
         data.find_table(schema_name, table_name)
     }
 
+    pub fn tables(&self) -> Vec<TableInfo> {
+        let data = self.data.read().unwrap();
+        data.tables.clone()
+    }
+
     pub async fn open(&self, ctx: OpenContext) -> Result<()> {
         let operator = self
             .operator
@@ -0,0 +1,510 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Array type, which holds a list of values of the same kind
+
+use std::{cmp::Ordering, fmt, sync::Arc};
+
+use arrow::datatypes::{DataType, Field, FieldRef};
+use bytes_ext::Bytes;
+use prost::encoding::{decode_varint, encode_varint, encoded_len_varint};
+use snafu::{ensure, Backtrace, OptionExt, Snafu};
+
+use crate::{
+    datum::{Datum, DatumKind},
+    string::StringBytes,
+    time::Timestamp,
+};
+
+/// Used if the element kind of the array is unknown.
+pub const DEFAULT_ELEMENT_KIND: DatumKind = DatumKind::String;
+/// Name of the element field of the arrow list type, same as arrow.
+const ELEMENT_FIELD_NAME: &str = "item";
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid element kind of array, kind:{kind}.\nBacktrace:\n{backtrace}"))]
+    InvalidElementKind {
+        kind: DatumKind,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Element kind of array mismatch, expect:{expect}, given:{given}.\nBacktrace:\n{backtrace}"
+    ))]
+    ElementKindMismatch {
+        expect: DatumKind,
+        given: DatumKind,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid encoded array, msg:{msg}.\nBacktrace:\n{backtrace}"))]
+    InvalidEncodedArray { msg: String, backtrace: Backtrace },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Whether the datum of `kind` can be the element of the array.
+///
+/// The decimal is excluded as its precision and scale are not tracked by the
+/// array, and the nested array is not supported.
+pub fn is_valid_element_kind(kind: DatumKind) -> bool {
+    matches!(
+        kind,
+        DatumKind::Timestamp
+            | DatumKind::Double
+            | DatumKind::Float
+            | DatumKind::Varbinary
+            | DatumKind::String
+            | DatumKind::UInt64
+            | DatumKind::UInt32
+            | DatumKind::UInt16
+            | DatumKind::UInt8
+            | DatumKind::Int64
+            | DatumKind::Int32
+            | DatumKind::Int16
+            | DatumKind::Int8
+            | DatumKind::Boolean
+            | DatumKind::Date
+            | DatumKind::Time
+    )
+}
+
+/// The element field of the arrow list type.
+pub fn element_field(element_kind: DatumKind) -> FieldRef {
+    Arc::new(Field::new(ELEMENT_FIELD_NAME, element_kind.into(), true))
+}
+
+/// The arrow list type of the array with elements of `element_kind`.
+pub fn to_arrow_data_type(element_kind: DatumKind) -> DataType {
+    DataType::List(element_field(element_kind))
+}
+
+/// The element kind of the arrow list type, returns None if the type is not a
+/// list or the element is not supported.
+pub fn element_kind_of_arrow_type(data_type: &DataType) -> Option<DatumKind> {
+    match data_type {
+        DataType::List(field) => {
+            DatumKind::from_data_type(field.data_type()).filter(|kind| is_valid_element_kind(*kind))
+        }
+        _ => None,
+    }
+}
+
+/// Value of the array datum, the elements are nullable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayValue {
+    element_kind: DatumKind,
+    elements: Vec<Datum>,
+}
+
+impl ArrayValue {
+    /// Create the array, all the non-null elements must be of `element_kind`.
+    pub fn try_new(element_kind: DatumKind, elements: Vec<Datum>) -> Result<Self> {
+        ensure!(
+            is_valid_element_kind(element_kind),
+            InvalidElementKind { kind: element_kind }
+        );
+        for element in &elements {
+            ensure!(
+                element.is_null() || element.kind() == element_kind,
+                ElementKindMismatch {
+                    expect: element_kind,
+                    given: element.kind(),
+                }
+            );
+        }
+
+        Ok(Self {
+            element_kind,
+            elements,
+        })
+    }
+
+    /// Create the array without checking the kinds of the elements, the caller
+    /// should ensure they are of the valid `element_kind`.
+    pub(crate) fn new_unchecked(element_kind: DatumKind, elements: Vec<Datum>) -> Self {
+        Self {
+            element_kind,
+            elements,
+        }
+    }
+
+    /// Create an empty array.
+    pub fn empty(element_kind: DatumKind) -> Self {
+        Self {
+            element_kind,
+            elements: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn element_kind(&self) -> DatumKind {
+        self.element_kind
+    }
+
+    #[inline]
+    pub fn elements(&self) -> &[Datum] {
+        &self.elements
+    }
+
+    #[inline]
+    pub fn into_elements(self) -> Vec<Datum> {
+        self.elements
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Whether the array contains the non-null `value`, the numbers are
+    /// compared by value even if their kinds are different.
+    pub fn contains(&self, value: &Datum) -> bool {
+        if value.is_null() {
+            return false;
+        }
+
+        self.elements.iter().any(|element| {
+            if element.kind() == value.kind() {
+                return element == value;
+            }
+            match (element.as_f64(), value.as_f64()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            }
+        })
+    }
+
+    /// Returns the array with the elements converted into `element_kind`, only
+    /// the conversion between the numbers is supported, which is used to cast
+    /// the literal array into the type of the column.
+    pub fn cast_to(self, element_kind: DatumKind) -> Result<Self> {
+        if self.element_kind == element_kind {
+            return Ok(self);
+        }
+
+        let elements = self
+            .elements
+            .into_iter()
+            .map(|element| cast_number(element, element_kind))
+            .collect::<Result<Vec<_>>>()?;
+        Self::try_new(element_kind, elements)
+    }
+}
+
+/// Cast the number into the number of `kind` without checking overflow.
+fn cast_number(datum: Datum, kind: DatumKind) -> Result<Datum> {
+    if datum.is_null() || datum.kind() == kind {
+        return Ok(datum);
+    }
+
+    let mismatch = || {
+        ElementKindMismatch {
+            expect: kind,
+            given: datum.kind(),
+        }
+        .fail()
+    };
+    let casted = match (kind, datum.as_i64(), datum.as_f64()) {
+        (DatumKind::Double, _, Some(v)) => Datum::Double(v),
+        (DatumKind::Float, _, Some(v)) => Datum::Float(v as f32),
+        (DatumKind::UInt64, Some(v), _) => Datum::UInt64(v as u64),
+        (DatumKind::UInt32, Some(v), _) => Datum::UInt32(v as u32),
+        (DatumKind::UInt16, Some(v), _) => Datum::UInt16(v as u16),
+        (DatumKind::UInt8, Some(v), _) => Datum::UInt8(v as u8),
+        (DatumKind::Int64, Some(v), _) => Datum::Int64(v),
+        (DatumKind::Int32, Some(v), _) => Datum::Int32(v as i32),
+        (DatumKind::Int16, Some(v), _) => Datum::Int16(v as i16),
+        (DatumKind::Int8, Some(v), _) => Datum::Int8(v as i8),
+        (DatumKind::Timestamp, Some(v), _) => Datum::Timestamp(Timestamp::new(v)),
+        _ => return mismatch(),
+    };
+
+    Ok(casted)
+}
+
+/// Here is the layout of the encoded array:
+/// ```plaintext
+/// +-------------------+--------------------------+------------------------------+
+/// | element_kind(u8)  | num_elements(varint)     | (is_null(u8), payload)...    |
+/// +-------------------+--------------------------+------------------------------+
+/// ```
+/// The payload of the null element is omitted, the payload of the fixed size
+/// element is its little-endian bytes, and the payload of the string or
+/// varbinary is its length in varint followed by its bytes.
+impl ArrayValue {
+    /// The length of the encoded array.
+    pub fn encoded_len(&self) -> usize {
+        let payload_len: usize = self
+            .elements
+            .iter()
+            .map(|element| {
+                let payload_len = match element {
+                    Datum::Null => 0,
+                    Datum::Varbinary(v) => encoded_len_varint(v.len() as u64) + v.len(),
+                    Datum::String(v) => encoded_len_varint(v.len() as u64) + v.len(),
+                    v => v.size(),
+                };
+                1 + payload_len
+            })
+            .sum();
+
+        1 + encoded_len_varint(self.elements.len() as u64) + payload_len
+    }
+
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        buf.reserve(self.encoded_len());
+        buf.push(self.element_kind.into_u8());
+        encode_varint(self.elements.len() as u64, buf);
+        for element in &self.elements {
+            buf.push(element.is_null() as u8);
+            match element {
+                Datum::Null => (),
+                Datum::Varbinary(v) => {
+                    encode_varint(v.len() as u64, buf);
+                    buf.extend_from_slice(v);
+                }
+                Datum::String(v) => {
+                    encode_varint(v.len() as u64, buf);
+                    buf.extend_from_slice(v.as_bytes());
+                }
+                v => v.do_with_bytes(|bytes| buf.extend_from_slice(bytes)),
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_len());
+        self.encode(&mut buf);
+        buf
+    }
+
+    /// Decode the array encoded by [ArrayValue::encode].
+    pub fn decode(mut buf: &[u8]) -> Result<Self> {
+        let buf = &mut buf;
+        let kind_byte = take(buf, 1)?[0];
+        let element_kind = DatumKind::try_from(kind_byte)
+            .ok()
+            .filter(|kind| is_valid_element_kind(*kind))
+            .with_context(|| InvalidEncodedArray {
+                msg: format!("invalid element kind:{kind_byte}"),
+            })?;
+        let num_elements = decode_len(buf)?;
+
+        let mut elements = Vec::with_capacity(num_elements.min(buf.len()));
+        for _ in 0..num_elements {
+            let is_null = take(buf, 1)?[0] != 0;
+            let element = if is_null {
+                Datum::Null
+            } else {
+                decode_element(element_kind, buf)?
+            };
+            elements.push(element);
+        }
+
+        Ok(Self {
+            element_kind,
+            elements,
+        })
+    }
+}
+
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    ensure!(
+        buf.len() >= len,
+        InvalidEncodedArray {
+            msg: format!("expect {len} bytes, but only {} left", buf.len()),
+        }
+    );
+    let (taken, rest) = buf.split_at(len);
+    *buf = rest;
+
+    Ok(taken)
+}
+
+fn decode_len(buf: &mut &[u8]) -> Result<usize> {
+    decode_varint(buf)
+        .ok()
+        .map(|v| v as usize)
+        .with_context(|| InvalidEncodedArray {
+            msg: "invalid varint".to_string(),
+        })
+}
+
+macro_rules! decode_le {
+    ($buf: expr, $Type: ty) => {{
+        let bytes = take($buf, std::mem::size_of::<$Type>())?;
+        <$Type>::from_le_bytes(bytes.try_into().unwrap())
+    }};
+}
+
+fn decode_element(kind: DatumKind, buf: &mut &[u8]) -> Result<Datum> {
+    let datum = match kind {
+        DatumKind::Timestamp => Datum::Timestamp(Timestamp::new(decode_le!(buf, i64))),
+        DatumKind::Double => Datum::Double(decode_le!(buf, f64)),
+        DatumKind::Float => Datum::Float(decode_le!(buf, f32)),
+        DatumKind::UInt64 => Datum::UInt64(decode_le!(buf, u64)),
+        DatumKind::UInt32 => Datum::UInt32(decode_le!(buf, u32)),
+        DatumKind::UInt16 => Datum::UInt16(decode_le!(buf, u16)),
+        DatumKind::UInt8 => Datum::UInt8(decode_le!(buf, u8)),
+        DatumKind::Int64 => Datum::Int64(decode_le!(buf, i64)),
+        DatumKind::Int32 => Datum::Int32(decode_le!(buf, i32)),
+        DatumKind::Int16 => Datum::Int16(decode_le!(buf, i16)),
+        DatumKind::Int8 => Datum::Int8(decode_le!(buf, i8)),
+        DatumKind::Boolean => Datum::Boolean(take(buf, 1)?[0] != 0),
+        DatumKind::Date => Datum::Date(decode_le!(buf, i32)),
+        DatumKind::Time => Datum::Time(decode_le!(buf, i64)),
+        DatumKind::Varbinary => {
+            let len = decode_len(buf)?;
+            Datum::Varbinary(Bytes::copy_from_slice(take(buf, len)?))
+        }
+        DatumKind::String => {
+            let len = decode_len(buf)?;
+            let s =
+                std::str::from_utf8(take(buf, len)?)
+                    .ok()
+                    .with_context(|| InvalidEncodedArray {
+                        msg: "invalid utf8 string".to_string(),
+                    })?;
+            Datum::String(StringBytes::copy_from_str(s))
+        }
+        DatumKind::Null | DatumKind::Decimal | DatumKind::Json | DatumKind::Array => {
+            return InvalidElementKind { kind }.fail()
+        }
+    };
+
+    Ok(datum)
+}
+
+impl PartialOrd for ArrayValue {
+    /// Arrays of different element kinds are not comparable.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.element_kind != other.element_kind {
+            return None;
+        }
+
+        self.elements.partial_cmp(&other.elements)
+    }
+}
+
+impl fmt::Display for ArrayValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, element) in self.elements.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(&element.display_string())?;
+        }
+        f.write_str("]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_array() {
+        let array = ArrayValue::try_new(
+            DatumKind::String,
+            vec![Datum::from("a"), Datum::Null, Datum::from("b")],
+        )
+        .unwrap();
+        assert_eq!(3, array.len());
+        assert_eq!("[a, null, b]", array.to_string());
+
+        assert!(ArrayValue::try_new(DatumKind::String, vec![Datum::Int32(1)]).is_err());
+        assert!(ArrayValue::try_new(DatumKind::Json, vec![]).is_err());
+        assert!(ArrayValue::try_new(DatumKind::Array, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let arrays = vec![
+            ArrayValue::empty(DatumKind::Int64),
+            ArrayValue::try_new(
+                DatumKind::String,
+                vec![Datum::from("admin"), Datum::Null, Datum::from("")],
+            )
+            .unwrap(),
+            ArrayValue::try_new(
+                DatumKind::Double,
+                vec![Datum::Double(1.5), Datum::Double(-0.25)],
+            )
+            .unwrap(),
+            ArrayValue::try_new(
+                DatumKind::Varbinary,
+                vec![Datum::Varbinary(Bytes::from_static(b"\x00\x01"))],
+            )
+            .unwrap(),
+            ArrayValue::try_new(
+                DatumKind::Timestamp,
+                vec![Datum::Null, Datum::Timestamp(Timestamp::new(1000))],
+            )
+            .unwrap(),
+            ArrayValue::try_new(DatumKind::Boolean, vec![Datum::Boolean(true)]).unwrap(),
+        ];
+
+        for array in arrays {
+            let buf = array.to_bytes();
+            assert_eq!(array.encoded_len(), buf.len());
+            assert_eq!(array, ArrayValue::decode(&buf).unwrap());
+            assert!(ArrayValue::decode(&buf[..buf.len() - 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_contains_and_cast() {
+        let array = ArrayValue::try_new(
+            DatumKind::Int32,
+            vec![Datum::Int32(1), Datum::Null, Datum::Int32(3)],
+        )
+        .unwrap();
+        assert!(array.contains(&Datum::Int32(3)));
+        assert!(array.contains(&Datum::Int64(1)));
+        assert!(!array.contains(&Datum::Int32(2)));
+        assert!(!array.contains(&Datum::Null));
+        assert!(!array.contains(&Datum::from("1")));
+
+        let literal = ArrayValue::try_new(DatumKind::Int64, vec![Datum::Int64(1), Datum::Null])
+            .unwrap()
+            .cast_to(DatumKind::Double)
+            .unwrap();
+        assert_eq!(
+            ArrayValue::try_new(DatumKind::Double, vec![Datum::Double(1.0), Datum::Null]).unwrap(),
+            literal
+        );
+        assert!(literal.cast_to(DatumKind::String).is_err());
+    }
+
+    #[test]
+    fn test_arrow_data_type() {
+        let data_type = to_arrow_data_type(DatumKind::Int32);
+        assert_eq!(
+            Some(DatumKind::Int32),
+            element_kind_of_arrow_type(&data_type)
+        );
+        assert_eq!(None, element_kind_of_arrow_type(&DataType::Int32));
+    }
+}
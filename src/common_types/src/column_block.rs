@@ -16,20 +16,22 @@
 // under the License.
 
 //! Column
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use arrow::{
     array::{
-        Array, ArrayAccessor, ArrayBuilder, ArrayRef, BinaryArray, BinaryBuilder, BooleanArray,
-        BooleanBuilder, Date32Array as DateArray, Date32Builder as DateBuilder, DictionaryArray,
+        Array, ArrayAccessor, ArrayBuilder, ArrayData, ArrayRef, BinaryArray, BinaryBuilder,
+        BooleanArray, BooleanBufferBuilder, BooleanBuilder, Date32Array as DateArray,
+        Date32Builder as DateBuilder, Decimal128Array, Decimal128Builder, DictionaryArray,
         Float32Array as FloatArray, Float32Builder as FloatBuilder, Float64Array as DoubleArray,
         Float64Builder as DoubleBuilder, Int16Array, Int16Builder, Int32Array, Int32Builder,
-        Int64Array, Int64Builder, Int8Array, Int8Builder, NullArray, StringArray, StringBuilder,
-        StringDictionaryBuilder, Time64NanosecondArray as TimeArray,
+        Int64Array, Int64Builder, Int8Array, Int8Builder, ListArray, NullArray, StringArray,
+        StringBuilder, StringDictionaryBuilder, Time64NanosecondArray as TimeArray,
         Time64NanosecondBuilder as TimeBuilder, TimestampMillisecondArray,
         TimestampMillisecondBuilder, UInt16Array, UInt16Builder, UInt32Array, UInt32Builder,
         UInt64Array, UInt64Builder, UInt8Array, UInt8Builder,
     },
+    buffer::Buffer,
     datatypes::{DataType, Int32Type, TimeUnit},
     error::ArrowError,
 };
@@ -39,7 +41,10 @@ use paste::paste;
 use snafu::{Backtrace, OptionExt, ResultExt, Snafu};
 
 use crate::{
+    array::{self as array_type, ArrayValue},
+    column_schema::ColumnSchema,
     datum::{Datum, DatumKind, DatumView},
+    decimal::{self, Decimal, DecimalType},
     string::StringBytes,
     time::{TimeRange, Timestamp},
 };
@@ -98,6 +103,12 @@ pub enum Error {
 
     #[snafu(display("Operation not yet implemented."))]
     NotImplemented,
+
+    #[snafu(display("Failed to convert decimal, err:{}", source))]
+    ConvertDecimal { source: decimal::Error },
+
+    #[snafu(display("Failed to convert array, err:{}", source))]
+    ConvertArray { source: array_type::Error },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -187,6 +198,104 @@ pub struct DateColumn(DateArray);
 #[derive(Debug, Clone)]
 pub struct TimeColumn(TimeArray);
 
+#[derive(Debug, Clone)]
+pub struct DecimalColumn(Decimal128Array);
+
+/// The json column is stored as string array.
+#[derive(Debug, Clone)]
+pub struct JsonColumn(StringArray);
+
+/// The array column is stored as list array, whose element type is one of the
+/// valid element kinds.
+#[derive(Debug, Clone)]
+pub struct ArrayColumn(ListArray);
+
+impl DecimalColumn {
+    /// Create a column that all values are null.
+    fn new_null(num_rows: usize, decimal_type: DecimalType) -> Self {
+        let mut builder = new_decimal_builder(num_rows, decimal_type);
+        for _ in 0..num_rows {
+            builder.append_null();
+        }
+        let array = builder.finish();
+
+        Self(array)
+    }
+
+    pub fn decimal_type(&self) -> DecimalType {
+        DecimalType::new_unchecked(self.0.precision(), self.0.scale())
+    }
+}
+
+fn new_decimal_builder(capacity: usize, decimal_type: DecimalType) -> Decimal128Builder {
+    // The precision and scale are validated by the `DecimalType`.
+    Decimal128Builder::with_capacity(capacity)
+        .with_precision_and_scale(decimal_type.precision(), decimal_type.scale())
+        .unwrap()
+}
+
+impl ArrayColumn {
+    /// Create a column that all values are null.
+    fn new_null(num_rows: usize, element_kind: DatumKind) -> Self {
+        let mut builder = ArrayColumnBuilder::with_capacity(element_kind, num_rows);
+        for _ in 0..num_rows {
+            builder.append_null();
+        }
+        let array = builder.finish();
+
+        Self(array)
+    }
+
+    /// Create the column from the list array, returns None if the element type
+    /// is not supported.
+    fn try_from_list_array(array: &ListArray) -> Option<Self> {
+        array_type::element_kind_of_arrow_type(array.data_type())?;
+
+        Some(Self::from(array))
+    }
+
+    pub fn element_kind(&self) -> DatumKind {
+        list_element_kind(&self.0)
+    }
+
+    pub fn value(&self, index: usize) -> Option<ArrayValue> {
+        if self.0.is_valid(index) {
+            Some(get_array_value(&self.0, index))
+        } else {
+            None
+        }
+    }
+
+    pub fn dedup(&self, selected: &mut [bool]) {
+        if self.0.is_empty() {
+            return;
+        }
+
+        selected[0] = true;
+        for i in 1..self.0.len() {
+            if self.datum_view(i) != self.datum_view(i - 1) {
+                selected[i] = true;
+            }
+        }
+    }
+}
+
+fn list_element_kind(array: &ListArray) -> DatumKind {
+    // The element type is checked while creating the column.
+    array_type::element_kind_of_arrow_type(array.data_type())
+        .unwrap_or(array_type::DEFAULT_ELEMENT_KIND)
+}
+
+fn get_array_value(array: &ListArray, index: usize) -> ArrayValue {
+    let element_kind = list_element_kind(array);
+    let values = array.value(index);
+    let block = ColumnBlock::try_from_arrow_array_ref(&element_kind, &values)
+        .expect("element type of the array column should be valid");
+    let elements = (0..block.num_rows()).map(|i| block.datum(i)).collect();
+
+    ArrayValue::new_unchecked(element_kind, elements)
+}
+
 #[inline]
 fn get_null_datum_view(_array: &NullArray, _index: usize) -> DatumView {
     DatumView::Null
@@ -222,6 +331,18 @@ fn get_time_datum_view(array: &TimeArray, index: usize) -> DatumView {
     DatumView::Time(value)
 }
 
+#[inline]
+fn get_decimal_datum_view(array: &Decimal128Array, index: usize) -> DatumView {
+    let decimal_type = DecimalType::new_unchecked(array.precision(), array.scale());
+    DatumView::Decimal(Decimal::new_unchecked(array.value(index), decimal_type))
+}
+
+#[inline]
+fn get_json_datum_view(array: &StringArray, index: usize) -> DatumView {
+    let value = array.value(index);
+    DatumView::Json(value)
+}
+
 #[inline]
 fn get_null_datum(_array: &NullArray, _index: usize) -> Datum {
     Datum::Null
@@ -261,6 +382,28 @@ fn get_time_datum(array: &TimeArray, index: usize) -> Datum {
     Datum::Time(value)
 }
 
+#[inline]
+fn get_decimal_datum(array: &Decimal128Array, index: usize) -> Datum {
+    let decimal_type = DecimalType::new_unchecked(array.precision(), array.scale());
+    Datum::Decimal(Decimal::new_unchecked(array.value(index), decimal_type))
+}
+
+#[inline]
+fn get_json_datum(array: &StringArray, index: usize) -> Datum {
+    let value = array.value(index);
+    Datum::Json(StringBytes::copy_from_str(value))
+}
+
+#[inline]
+fn get_array_datum_view(array: &ListArray, index: usize) -> DatumView {
+    DatumView::Array(Cow::Owned(get_array_value(array, index)))
+}
+
+#[inline]
+fn get_array_datum(array: &ListArray, index: usize) -> Datum {
+    Datum::Array(get_array_value(array, index))
+}
+
 macro_rules! impl_column {
     ($Column: ident, $get_datum: expr, $get_datum_view: expr) => {
         impl $Column {
@@ -325,6 +468,9 @@ impl_column!(
     get_varbinary_datum_view
 );
 impl_column!(StringColumn, get_string_datum, get_string_datum_view);
+impl_column!(DecimalColumn, get_decimal_datum, get_decimal_datum_view);
+impl_column!(JsonColumn, get_json_datum, get_json_datum_view);
+impl_column!(ArrayColumn, get_array_datum, get_array_datum_view);
 
 impl StringDictionaryColumn {
     /// Get datum by index
@@ -408,6 +554,8 @@ macro_rules! impl_dedup {
 impl_dedup!(TimestampColumn);
 impl_dedup!(VarbinaryColumn);
 impl_dedup!(StringColumn);
+impl_dedup!(DecimalColumn);
+impl_dedup!(JsonColumn);
 
 impl StringDictionaryColumn {
     pub fn dedup(&self, selected: &mut [bool]) {
@@ -492,6 +640,9 @@ impl_from_array_and_slice!(NullColumn, NullArray);
 impl_from_array_and_slice!(TimestampColumn, TimestampMillisecondArray);
 impl_from_array_and_slice!(VarbinaryColumn, BinaryArray);
 impl_from_array_and_slice!(StringColumn, StringArray);
+impl_from_array_and_slice!(DecimalColumn, Decimal128Array);
+impl_from_array_and_slice!(JsonColumn, StringArray);
+impl_from_array_and_slice!(ArrayColumn, ListArray);
 
 impl From<DictionaryArray<Int32Type>> for StringDictionaryColumn {
     fn from(array: DictionaryArray<Int32Type>) -> Self {
@@ -544,6 +695,7 @@ macro_rules! impl_iter_map {
 }
 
 impl_iter_map!(TimestampColumn, Timestamp);
+impl_iter!(DecimalColumn, i128);
 
 impl VarbinaryColumn {
     fn new_null(num_rows: usize) -> Self {
@@ -570,6 +722,19 @@ impl StringColumn {
     }
 }
 
+impl JsonColumn {
+    /// Create a column that all values are null.
+    fn new_null(num_rows: usize) -> Self {
+        let mut builder = StringBuilder::with_capacity(num_rows, 0usize);
+        for _ in 0..num_rows {
+            builder.append_null();
+        }
+        let array = builder.finish();
+
+        Self(array)
+    }
+}
+
 impl StringDictionaryColumn {
     /// Create a column that all values are null.
     fn new_null(num_rows: usize) -> Self {
@@ -652,7 +817,8 @@ batch_impl_numeric_value!(
     (Int8, i8),
     (Boolean, bool),
     (Date, i32),
-    (Time, i64)
+    (Time, i64),
+    (Decimal, i128)
 );
 
 impl VarbinaryColumn {
@@ -683,6 +849,20 @@ impl StringColumn {
     }
 }
 
+impl JsonColumn {
+    pub fn iter(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.0.iter()
+    }
+
+    pub fn value(&self, index: usize) -> Option<&str> {
+        if self.0.is_valid(index) {
+            unsafe { Some(self.0.value_unchecked(index)) }
+        } else {
+            None
+        }
+    }
+}
+
 macro_rules! impl_column_block {
     ($($Kind: ident), *) => {
         impl ColumnBlock {
@@ -777,7 +957,7 @@ macro_rules! impl_column_block {
 
 impl_column_block!(
     Null, Timestamp, Double, Float, Varbinary, String, UInt64, UInt32, UInt16, UInt8, Int64, Int32,
-    Int16, Int8, Boolean, Date, Time
+    Int16, Int8, Boolean, Date, Time, Decimal, Json, Array
 );
 
 // TODO(yingwen): We can add a unsafe function that don't do bound check.
@@ -790,6 +970,9 @@ macro_rules! define_column_block {
                 Null(NullColumn),
                 StringDictionary(StringDictionaryColumn),
                 String(StringColumn),
+                Decimal(DecimalColumn),
+                Json(JsonColumn),
+                Array(ArrayColumn),
                 $(
                     $Kind([<$Kind Column>]),
                 )*
@@ -814,6 +997,21 @@ macro_rules! define_column_block {
                                 ColumnBlock::String(StringColumn::from(cast_column))
                             }
                         },
+                        DatumKind::Decimal => {
+                            let cast_column: &Decimal128Array = cast_array(datum_kind, array)?;
+                            ColumnBlock::Decimal(DecimalColumn::from(cast_column))
+                        },
+                        DatumKind::Json => {
+                            let cast_column: &StringArray = cast_array(datum_kind, array)?;
+                            ColumnBlock::Json(JsonColumn::from(cast_column))
+                        },
+                        DatumKind::Array => {
+                            let cast_column: &ListArray = cast_array(datum_kind, array)?;
+                            let column = ArrayColumn::try_from_list_array(cast_column).with_context(|| UnsupportedArray {
+                                data_type: array.data_type().clone(),
+                            })?;
+                            ColumnBlock::Array(column)
+                        },
                         $(
                             DatumKind::$Kind => {
                                 let mills_array;
@@ -844,6 +1042,9 @@ macro_rules! define_column_block {
                                 ColumnBlock::String(StringColumn::new_null(rows))
                             }
                         },
+                        DatumKind::Decimal => ColumnBlock::Decimal(DecimalColumn::new_null(rows, DecimalType::default())),
+                        DatumKind::Json => ColumnBlock::Json(JsonColumn::new_null(rows)),
+                        DatumKind::Array => ColumnBlock::Array(ArrayColumn::new_null(rows, array_type::DEFAULT_ELEMENT_KIND)),
                         $(
                             DatumKind::$Kind => ColumnBlock::$Kind([<$Kind Column>]::new_null(rows)),
                         )*
@@ -875,6 +1076,21 @@ impl ColumnBlock {
         Self::Null(NullColumn::new_null(rows))
     }
 
+    /// Create a block that all values are null with the type of the column.
+    pub fn new_null_with_column_schema(column_schema: &ColumnSchema, rows: usize) -> Result<Self> {
+        match (column_schema.decimal_type, column_schema.element_type) {
+            (Some(decimal_type), _) => {
+                Ok(Self::Decimal(DecimalColumn::new_null(rows, decimal_type)))
+            }
+            (_, Some(element_type)) => Ok(Self::Array(ArrayColumn::new_null(rows, element_type))),
+            _ => Self::new_null_with_type(
+                &column_schema.data_type,
+                rows,
+                column_schema.is_dictionary,
+            ),
+        }
+    }
+
     pub fn as_timestamp(&self) -> Option<&TimestampColumn> {
         match self {
             ColumnBlock::Timestamp(c) => Some(c),
@@ -988,6 +1204,9 @@ macro_rules! define_column_block_builder {
                 String(StringBuilder),
                 Date(DateBuilder),
                 Time(TimeBuilder),
+                Decimal(Decimal128Builder, DecimalType),
+                Json(StringBuilder),
+                Array(ArrayColumnBuilder),
                 Dictionary(StringDictionaryBuilder::<Int32Type>),
                 $(
                     $Kind($Builder),
@@ -1011,6 +1230,12 @@ macro_rules! define_column_block_builder {
                         }
                         DatumKind::Date => Self::Date(DateBuilder::with_capacity(item_capacity)),
                         DatumKind::Time => Self::Time(TimeBuilder::with_capacity(item_capacity)),
+                        DatumKind::Decimal => {
+                            let decimal_type = DecimalType::default();
+                            Self::Decimal(new_decimal_builder(item_capacity, decimal_type), decimal_type)
+                        }
+                        DatumKind::Json => Self::Json(StringBuilder::with_capacity(item_capacity, 1024)),
+                        DatumKind::Array => Self::Array(ArrayColumnBuilder::with_capacity(array_type::DEFAULT_ELEMENT_KIND, item_capacity)),
                         $(
                             DatumKind::$Kind => Self::$Kind($Builder::with_capacity(item_capacity)),
                         )*
@@ -1038,6 +1263,28 @@ macro_rules! define_column_block_builder {
                         Self::String(builder) => append_datum!(String, builder, Datum, datum),
                         Self::Date(builder) => append_datum!(Date, builder, Datum, datum),
                         Self::Time(builder) => append_datum!(Time, builder, Datum, datum),
+                        Self::Json(builder) => append_datum!(Json, builder, Datum, datum),
+                        Self::Array(builder) => match datum {
+                            Datum::Null => {
+                                builder.append_null();
+                                Ok(())
+                            }
+                            Datum::Array(v) => builder.append_value(v),
+                            _ => ConflictType {
+                                expect: DatumKind::Array,
+                                given,
+                            }
+                            .fail(),
+                        },
+                        Self::Decimal(builder, decimal_type) => match datum {
+                            Datum::Null => Ok(builder.append_null()),
+                            Datum::Decimal(v) => append_decimal(builder, *decimal_type, v),
+                            _ => ConflictType {
+                                expect: DatumKind::Decimal,
+                                given,
+                            }
+                            .fail(),
+                        },
                         Self::Dictionary(builder) => {
                             match datum {
                                 Datum::Null => Ok(builder.append_null()),
@@ -1076,6 +1323,28 @@ macro_rules! define_column_block_builder {
                         Self::String(builder) => append_datum!(String, builder, DatumView, datum),
                         Self::Date(builder) => append_datum!(Date, builder, DatumView, datum),
                         Self::Time(builder) => append_datum!(Time, builder, DatumView, datum),
+                        Self::Json(builder) => append_datum!(Json, builder, DatumView, datum),
+                        Self::Array(builder) => match datum {
+                            DatumView::Null => {
+                                builder.append_null();
+                                Ok(())
+                            }
+                            DatumView::Array(v) => builder.append_value(v.into_owned()),
+                            _ => ConflictType {
+                                expect: DatumKind::Array,
+                                given,
+                            }
+                            .fail(),
+                        },
+                        Self::Decimal(builder, decimal_type) => match datum {
+                            DatumView::Null => Ok(builder.append_null()),
+                            DatumView::Decimal(v) => append_decimal(builder, *decimal_type, v),
+                            _ => ConflictType {
+                                expect: DatumKind::Decimal,
+                                given,
+                            }
+                            .fail(),
+                        },
                         Self::Dictionary(builder) => {
                             match datum {
                                 DatumView::Null => Ok(builder.append_null()),
@@ -1111,6 +1380,37 @@ macro_rules! define_column_block_builder {
                         Self::String(builder) => append_block!(String, builder, ColumnBlock, block, start, len),
                         Self::Date(builder) => append_block!(Date, builder, ColumnBlock, block, start, len),
                         Self::Time(builder) => append_block!(Time, builder, ColumnBlock, block, start, len),
+                        Self::Json(builder) => append_block!(Json, builder, ColumnBlock, block, start, len),
+                        Self::Array(builder) => {
+                            let end = std::cmp::min(start + len, block.num_rows());
+                            for i in start..end {
+                                match block.datum_view(i) {
+                                    DatumView::Null => builder.append_null(),
+                                    DatumView::Array(v) => builder.append_value(v.into_owned())?,
+                                    _ => return ConflictType {
+                                        expect: DatumKind::Array,
+                                        given: block.datum_kind(),
+                                    }
+                                    .fail(),
+                                }
+                            }
+                            Ok(())
+                        },
+                        Self::Decimal(builder, decimal_type) => {
+                            let end = std::cmp::min(start + len, block.num_rows());
+                            for i in start..end {
+                                match block.datum_view(i) {
+                                    DatumView::Null => builder.append_null(),
+                                    DatumView::Decimal(v) => append_decimal(builder, *decimal_type, v)?,
+                                    _ => return ConflictType {
+                                        expect: DatumKind::Decimal,
+                                        given: block.datum_kind(),
+                                    }
+                                    .fail(),
+                                }
+                            }
+                            Ok(())
+                        },
                         Self::Dictionary(builder) => {
                                 match block {
                                     ColumnBlock::Null(v) => {
@@ -1153,6 +1453,9 @@ macro_rules! define_column_block_builder {
                         Self::String(builder) => builder.len(),
                         Self::Date(builder) => builder.len(),
                         Self::Time(builder) => builder.len(),
+                        Self::Decimal(builder, _) => builder.len(),
+                        Self::Json(builder) => builder.len(),
+                        Self::Array(builder) => builder.len(),
                         Self::Dictionary(builder) => builder.len(),
                         $(
                             Self::$Kind(builder) =>  builder.len(),
@@ -1173,6 +1476,9 @@ macro_rules! define_column_block_builder {
                         Self::String(builder) => StringColumn::from(builder.finish()).into(),
                         Self::Date(builder) => DateColumn::from(builder.finish()).into(),
                         Self::Time(builder) => TimeColumn::from(builder.finish()).into(),
+                        Self::Decimal(builder, _) => DecimalColumn::from(builder.finish()).into(),
+                        Self::Json(builder) => JsonColumn::from(builder.finish()).into(),
+                        Self::Array(builder) => ArrayColumn::from(builder.finish()).into(),
                         Self::Dictionary(builder) => {
                             StringDictionaryColumn::from(builder.finish()).into()
                         },
@@ -1202,12 +1508,111 @@ define_column_block_builder!(
     (Boolean, BooleanBuilder)
 );
 
+/// Append the decimal after converting it into the type of the builder.
+fn append_decimal(
+    builder: &mut Decimal128Builder,
+    decimal_type: DecimalType,
+    decimal: Decimal,
+) -> Result<()> {
+    let decimal = decimal.rescale(decimal_type).context(ConvertDecimal)?;
+    builder.append_value(decimal.value());
+    Ok(())
+}
+
+/// Builder of the [ArrayColumn], the elements of all the arrays are appended
+/// into the builder of the element kind.
+pub struct ArrayColumnBuilder {
+    element_kind: DatumKind,
+    values: Box<ColumnBlockBuilder>,
+    /// Offsets of the arrays in the values, starts with 0.
+    offsets: Vec<i32>,
+    validity: BooleanBufferBuilder,
+}
+
+impl ArrayColumnBuilder {
+    fn with_capacity(element_kind: DatumKind, item_capacity: usize) -> Self {
+        let mut offsets = Vec::with_capacity(item_capacity + 1);
+        offsets.push(0);
+
+        Self {
+            element_kind,
+            values: Box::new(ColumnBlockBuilder::with_capacity(
+                &element_kind,
+                item_capacity,
+                false,
+            )),
+            offsets,
+            validity: BooleanBufferBuilder::new(item_capacity),
+        }
+    }
+
+    fn append_null(&mut self) {
+        self.offsets.push(self.values.len() as i32);
+        self.validity.append(false);
+    }
+
+    /// Append the array after converting it into the element kind of the
+    /// builder.
+    fn append_value(&mut self, value: ArrayValue) -> Result<()> {
+        let value = value.cast_to(self.element_kind).context(ConvertArray)?;
+        for element in value.into_elements() {
+            self.values.append(element)?;
+        }
+        self.offsets.push(self.values.len() as i32);
+        self.validity.append(true);
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Build the list array and reset the builder.
+    fn finish(&mut self) -> ListArray {
+        let values = self.values.build().to_arrow_array_ref();
+        let offsets = std::mem::replace(&mut self.offsets, vec![0]);
+        let validity = self.validity.finish();
+
+        let array_data = ArrayData::builder(array_type::to_arrow_data_type(self.element_kind))
+            .len(offsets.len() - 1)
+            .add_buffer(Buffer::from_slice_ref(&offsets))
+            .add_child_data(values.to_data())
+            .null_bit_buffer(Some(validity.into_inner()))
+            .build()
+            // The offsets and values are built by the builder, so they must be valid.
+            .expect("array data built by the builder should be valid");
+
+        ListArray::from(array_data)
+    }
+}
+
 impl ColumnBlockBuilder {
     /// Create by data type
     pub fn new(data_type: &DatumKind, is_dictionry: bool) -> Self {
         Self::with_capacity(data_type, 0, is_dictionry)
     }
 
+    /// Create by the column schema with initial capacity, the decimal type of
+    /// the column is respected.
+    pub fn with_column_schema(column_schema: &ColumnSchema, item_capacity: usize) -> Self {
+        match (column_schema.decimal_type, column_schema.element_type) {
+            (Some(decimal_type), _) => Self::Decimal(
+                new_decimal_builder(item_capacity, decimal_type),
+                decimal_type,
+            ),
+            (_, Some(element_type)) => Self::Array(ArrayColumnBuilder::with_capacity(
+                element_type,
+                item_capacity,
+            )),
+            _ => Self::with_capacity(
+                &column_schema.data_type,
+                item_capacity,
+                column_schema.is_dictionary,
+            ),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -1221,8 +1626,12 @@ impl ColumnBlockBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::{
-        build_row_for_dictionary, build_rows, build_schema, build_schema_with_dictionary,
+    use crate::{
+        column_schema,
+        tests::{
+            build_row_for_dictionary, build_rows, build_schema, build_schema_with_decimal,
+            build_schema_with_dictionary,
+        },
     };
 
     #[test]
@@ -1367,4 +1776,116 @@ mod tests {
         );
         assert_eq!(column_block.datum(5), Datum::Null);
     }
+
+    #[test]
+    fn test_column_block_decimal_builder() {
+        let schema = build_schema_with_decimal();
+        let column = schema.column(2);
+        let mut builder = ColumnBlockBuilder::with_column_schema(column, 2);
+        let decimal_type = column.decimal_type.unwrap();
+
+        let decimal = Decimal::parse("1.005", DecimalType::try_new(10, 3).unwrap()).unwrap();
+        builder.append(Datum::Decimal(decimal)).unwrap();
+        builder.append_view(DatumView::Null).unwrap();
+        assert!(builder.append(Datum::Double(1.0)).is_err());
+
+        let column_block = builder.build();
+        assert_eq!(column_block.num_rows(), 2);
+        assert_eq!(
+            column_block.datum(0),
+            Datum::Decimal(Decimal::parse("1.01", decimal_type).unwrap())
+        );
+        assert_eq!(column_block.datum(1), Datum::Null);
+        assert_eq!(
+            column_block.to_arrow_array_ref().data_type(),
+            &DataType::Decimal128(10, 2)
+        );
+
+        let null_block = ColumnBlock::new_null_with_column_schema(column, 3).unwrap();
+        assert_eq!(null_block.num_rows(), 3);
+        assert_eq!(
+            null_block.to_arrow_array_ref().data_type(),
+            &DataType::Decimal128(10, 2)
+        );
+    }
+
+    #[test]
+    fn test_column_block_json_builder() {
+        let mut builder = ColumnBlockBuilder::with_capacity(&DatumKind::Json, 2, false);
+        builder
+            .append(Datum::Json(StringBytes::from(r#"{"a":1}"#)))
+            .unwrap();
+        builder.append_view(DatumView::Null).unwrap();
+        assert!(builder
+            .append(Datum::String(StringBytes::from("a")))
+            .is_err());
+
+        let column_block = builder.build();
+        assert_eq!(column_block.datum_kind(), DatumKind::Json);
+        assert_eq!(
+            column_block.datum(0),
+            Datum::Json(StringBytes::from(r#"{"a":1}"#))
+        );
+        assert_eq!(column_block.datum(1), Datum::Null);
+
+        let array = column_block.to_arrow_array_ref();
+        assert_eq!(array.data_type(), &DataType::Utf8);
+        let column_block = ColumnBlock::try_from_arrow_array_ref(&DatumKind::Json, &array).unwrap();
+        assert_eq!(column_block.datum_view(0), DatumView::Json(r#"{"a":1}"#));
+    }
+
+    #[test]
+    fn test_column_block_array_builder() {
+        let column = column_schema::Builder::new("arr".to_string(), DatumKind::Array)
+            .element_type(Some(DatumKind::Int64))
+            .build()
+            .unwrap();
+        let mut builder = ColumnBlockBuilder::with_column_schema(&column, 2);
+
+        let value = ArrayValue::try_new(
+            DatumKind::Int32,
+            vec![Datum::Int32(1), Datum::Null, Datum::Int32(3)],
+        )
+        .unwrap();
+        builder.append(Datum::Array(value)).unwrap();
+        builder.append_view(DatumView::Null).unwrap();
+        builder
+            .append(Datum::Array(ArrayValue::empty(DatumKind::Int64)))
+            .unwrap();
+        assert!(builder.append(Datum::Int64(1)).is_err());
+
+        let column_block = builder.build();
+        assert_eq!(column_block.num_rows(), 3);
+        let expect = ArrayValue::try_new(
+            DatumKind::Int64,
+            vec![Datum::Int64(1), Datum::Null, Datum::Int64(3)],
+        )
+        .unwrap();
+        assert_eq!(column_block.datum(0), Datum::Array(expect.clone()));
+        assert_eq!(column_block.datum(1), Datum::Null);
+        assert_eq!(
+            column_block.datum(2),
+            Datum::Array(ArrayValue::empty(DatumKind::Int64))
+        );
+
+        let array = column_block.to_arrow_array_ref();
+        assert_eq!(array.data_type(), &column.to_arrow_data_type());
+        let column_block =
+            ColumnBlock::try_from_arrow_array_ref(&DatumKind::Array, &array).unwrap();
+        assert_eq!(column_block.datum(0), Datum::Array(expect));
+
+        // Append the range of the block.
+        let mut builder = ColumnBlockBuilder::with_column_schema(&column, 2);
+        builder.append_block_range(&column_block, 1, 2).unwrap();
+        let sliced = builder.build();
+        assert_eq!(sliced.num_rows(), 2);
+        assert_eq!(sliced.datum(0), Datum::Null);
+
+        let null_block = ColumnBlock::new_null_with_column_schema(&column, 3).unwrap();
+        assert_eq!(null_block.num_rows(), 3);
+        assert_eq!(
+            null_block.to_arrow_array_ref().data_type(),
+            &column.to_arrow_data_type()
+        );
+    }
 }
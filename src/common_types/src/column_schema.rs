@@ -17,14 +17,18 @@
 
 //! Schema of column
 
-use std::{collections::HashMap, convert::TryFrom, str::FromStr, sync::Arc};
+use std::{collections::HashMap, convert::TryFrom, fmt, str::FromStr, sync::Arc};
 
 use arrow::datatypes::{DataType, Field};
 use horaedbproto::{remote_engine::ColumnDesc, schema as schema_pb};
 use snafu::{ensure, Backtrace, OptionExt, ResultExt, Snafu};
 use sqlparser::ast::Expr;
 
-use crate::datum::DatumKind;
+use crate::{
+    array::{self, ArrayValue},
+    datum::{Datum, DatumKind},
+    decimal::{self, Decimal, DecimalType},
+};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -95,6 +99,74 @@ pub enum Error {
         source: serde_json::error::Error,
         backtrace: Backtrace,
     },
+
+    #[snafu(display("Unknown column aggregation:{}.\nBacktrace:\n{}", s, backtrace))]
+    UnknownAggregation { s: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Invalid aggregation of column, aggregation:{}, data_type:{}, is_tag:{}.\nBacktrace:\n{}",
+        aggregation,
+        data_type,
+        is_tag,
+        backtrace
+    ))]
+    InvalidAggregation {
+        aggregation: ColumnAggregation,
+        data_type: DatumKind,
+        is_tag: bool,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Unknown column encoding:{}.\nBacktrace:\n{}", s, backtrace))]
+    UnknownEncoding { s: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Invalid encoding of column, encoding:{}, data_type:{}.\nBacktrace:\n{}",
+        encoding,
+        data_type,
+        backtrace
+    ))]
+    InvalidEncoding {
+        encoding: EncodingHint,
+        data_type: DatumKind,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Unknown column compression:{}.\nBacktrace:\n{}", s, backtrace))]
+    UnknownCompression { s: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Decimal type is set on a non-decimal column, decimal_type:{}, data_type:{}.\nBacktrace:\n{}",
+        decimal_type,
+        data_type,
+        backtrace
+    ))]
+    InvalidDecimalType {
+        decimal_type: DecimalType,
+        data_type: DatumKind,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Decimal type of the decimal column is missing.\nBacktrace:\n{}",
+        backtrace
+    ))]
+    MissingDecimalType { backtrace: Backtrace },
+
+    #[snafu(display("Failed to decode decimal type, err:{}", source))]
+    DecodeDecimalType { source: decimal::Error },
+
+    #[snafu(display(
+        "Invalid element type of array column, element_type:{}, data_type:{}.\nBacktrace:\n{}",
+        element_type,
+        data_type,
+        backtrace
+    ))]
+    InvalidElementType {
+        element_type: DatumKind,
+        data_type: DatumKind,
+        backtrace: Backtrace,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -135,6 +207,266 @@ pub enum ReadOp {
     FillNull,
 }
 
+/// Aggregation of a field column, used to combine the values of the rows
+/// sharing the same primary key if the table is in the aggregate update mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAggregation {
+    Sum,
+    Max,
+    Min,
+    /// Keep the latest value.
+    Replace,
+    /// Union of the HyperLogLog sketches serialized in a varbinary column.
+    HllUnion,
+}
+
+impl ColumnAggregation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnAggregation::Sum => "SUM",
+            ColumnAggregation::Max => "MAX",
+            ColumnAggregation::Min => "MIN",
+            ColumnAggregation::Replace => "REPLACE",
+            ColumnAggregation::HllUnion => "HLL_UNION",
+        }
+    }
+
+    /// Check whether the aggregation can be applied to the column of `typ`.
+    pub fn is_valid_type(&self, typ: DatumKind) -> bool {
+        match self {
+            ColumnAggregation::Sum => typ.is_f64_castable() || typ == DatumKind::Decimal,
+            ColumnAggregation::Max | ColumnAggregation::Min => {
+                typ.is_f64_castable() || typ.is_key_kind()
+            }
+            ColumnAggregation::Replace => true,
+            ColumnAggregation::HllUnion => matches!(typ, DatumKind::Varbinary),
+        }
+    }
+}
+
+impl FromStr for ColumnAggregation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let aggregation = match s.to_uppercase().as_str() {
+            "SUM" => ColumnAggregation::Sum,
+            "MAX" => ColumnAggregation::Max,
+            "MIN" => ColumnAggregation::Min,
+            "REPLACE" => ColumnAggregation::Replace,
+            "HLL_UNION" => ColumnAggregation::HllUnion,
+            _ => return UnknownAggregation { s }.fail(),
+        };
+
+        Ok(aggregation)
+    }
+}
+
+impl fmt::Display for ColumnAggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn aggregation_to_pb(aggregation: Option<ColumnAggregation>) -> schema_pb::ColumnAggregation {
+    match aggregation {
+        None => schema_pb::ColumnAggregation::None,
+        Some(ColumnAggregation::Sum) => schema_pb::ColumnAggregation::Sum,
+        Some(ColumnAggregation::Max) => schema_pb::ColumnAggregation::Max,
+        Some(ColumnAggregation::Min) => schema_pb::ColumnAggregation::Min,
+        Some(ColumnAggregation::Replace) => schema_pb::ColumnAggregation::Replace,
+        Some(ColumnAggregation::HllUnion) => schema_pb::ColumnAggregation::HllUnion,
+    }
+}
+
+fn aggregation_from_pb(v: i32) -> Result<Option<ColumnAggregation>> {
+    let aggregation = schema_pb::ColumnAggregation::from_i32(v)
+        .with_context(|| UnknownAggregation { s: v.to_string() })?;
+    let aggregation = match aggregation {
+        schema_pb::ColumnAggregation::None => None,
+        schema_pb::ColumnAggregation::Sum => Some(ColumnAggregation::Sum),
+        schema_pb::ColumnAggregation::Max => Some(ColumnAggregation::Max),
+        schema_pb::ColumnAggregation::Min => Some(ColumnAggregation::Min),
+        schema_pb::ColumnAggregation::Replace => Some(ColumnAggregation::Replace),
+        schema_pb::ColumnAggregation::HllUnion => Some(ColumnAggregation::HllUnion),
+    };
+
+    Ok(aggregation)
+}
+
+/// Hint of the encoding used to store the values of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodingHint {
+    Plain,
+    Dictionary,
+    /// Suitable for timestamps and monotonic counters.
+    DeltaBinaryPacked,
+    DeltaLengthByteArray,
+    DeltaByteArray,
+    /// Suitable for floating point values.
+    ByteStreamSplit,
+}
+
+impl EncodingHint {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EncodingHint::Plain => "PLAIN",
+            EncodingHint::Dictionary => "DICTIONARY",
+            EncodingHint::DeltaBinaryPacked => "DELTA_BINARY_PACKED",
+            EncodingHint::DeltaLengthByteArray => "DELTA_LENGTH_BYTE_ARRAY",
+            EncodingHint::DeltaByteArray => "DELTA_BYTE_ARRAY",
+            EncodingHint::ByteStreamSplit => "BYTE_STREAM_SPLIT",
+        }
+    }
+
+    /// Check whether the encoding can be applied to the column of `typ`.
+    pub fn is_valid_type(&self, typ: DatumKind) -> bool {
+        match self {
+            EncodingHint::Plain => true,
+            EncodingHint::Dictionary => !matches!(typ, DatumKind::Boolean),
+            EncodingHint::DeltaBinaryPacked => matches!(
+                typ,
+                DatumKind::Timestamp
+                    | DatumKind::UInt64
+                    | DatumKind::UInt32
+                    | DatumKind::UInt16
+                    | DatumKind::UInt8
+                    | DatumKind::Int64
+                    | DatumKind::Int32
+                    | DatumKind::Int16
+                    | DatumKind::Int8
+                    | DatumKind::Date
+                    | DatumKind::Time
+            ),
+            EncodingHint::DeltaLengthByteArray | EncodingHint::DeltaByteArray => {
+                matches!(
+                    typ,
+                    DatumKind::String | DatumKind::Varbinary | DatumKind::Json
+                )
+            }
+            EncodingHint::ByteStreamSplit => matches!(typ, DatumKind::Double | DatumKind::Float),
+        }
+    }
+}
+
+impl FromStr for EncodingHint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let encoding = match s.to_uppercase().as_str() {
+            "PLAIN" => EncodingHint::Plain,
+            "DICTIONARY" => EncodingHint::Dictionary,
+            "DELTA_BINARY_PACKED" => EncodingHint::DeltaBinaryPacked,
+            "DELTA_LENGTH_BYTE_ARRAY" => EncodingHint::DeltaLengthByteArray,
+            "DELTA_BYTE_ARRAY" => EncodingHint::DeltaByteArray,
+            "BYTE_STREAM_SPLIT" => EncodingHint::ByteStreamSplit,
+            _ => return UnknownEncoding { s }.fail(),
+        };
+
+        Ok(encoding)
+    }
+}
+
+impl fmt::Display for EncodingHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn encoding_to_pb(encoding: Option<EncodingHint>) -> schema_pb::ColumnEncoding {
+    match encoding {
+        None => schema_pb::ColumnEncoding::None,
+        Some(EncodingHint::Plain) => schema_pb::ColumnEncoding::Plain,
+        Some(EncodingHint::Dictionary) => schema_pb::ColumnEncoding::Dictionary,
+        Some(EncodingHint::DeltaBinaryPacked) => schema_pb::ColumnEncoding::DeltaBinaryPacked,
+        Some(EncodingHint::DeltaLengthByteArray) => schema_pb::ColumnEncoding::DeltaLengthByteArray,
+        Some(EncodingHint::DeltaByteArray) => schema_pb::ColumnEncoding::DeltaByteArray,
+        Some(EncodingHint::ByteStreamSplit) => schema_pb::ColumnEncoding::ByteStreamSplit,
+    }
+}
+
+fn encoding_from_pb(v: i32) -> Result<Option<EncodingHint>> {
+    let encoding = schema_pb::ColumnEncoding::from_i32(v)
+        .with_context(|| UnknownEncoding { s: v.to_string() })?;
+    let encoding = match encoding {
+        schema_pb::ColumnEncoding::None => None,
+        schema_pb::ColumnEncoding::Plain => Some(EncodingHint::Plain),
+        schema_pb::ColumnEncoding::Dictionary => Some(EncodingHint::Dictionary),
+        schema_pb::ColumnEncoding::DeltaBinaryPacked => Some(EncodingHint::DeltaBinaryPacked),
+        schema_pb::ColumnEncoding::DeltaLengthByteArray => Some(EncodingHint::DeltaLengthByteArray),
+        schema_pb::ColumnEncoding::DeltaByteArray => Some(EncodingHint::DeltaByteArray),
+        schema_pb::ColumnEncoding::ByteStreamSplit => Some(EncodingHint::ByteStreamSplit),
+    };
+
+    Ok(encoding)
+}
+
+/// Hint of the compression codec used to store a column, overrides the
+/// compression of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionHint {
+    Uncompressed,
+    Lz4,
+    Snappy,
+    Zstd,
+}
+
+impl CompressionHint {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionHint::Uncompressed => "UNCOMPRESSED",
+            CompressionHint::Lz4 => "LZ4",
+            CompressionHint::Snappy => "SNAPPY",
+            CompressionHint::Zstd => "ZSTD",
+        }
+    }
+}
+
+impl FromStr for CompressionHint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let compression = match s.to_uppercase().as_str() {
+            "UNCOMPRESSED" => CompressionHint::Uncompressed,
+            "LZ4" => CompressionHint::Lz4,
+            "SNAPPY" => CompressionHint::Snappy,
+            "ZSTD" => CompressionHint::Zstd,
+            _ => return UnknownCompression { s }.fail(),
+        };
+
+        Ok(compression)
+    }
+}
+
+impl fmt::Display for CompressionHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn compression_to_pb(compression: Option<CompressionHint>) -> schema_pb::ColumnCompression {
+    match compression {
+        None => schema_pb::ColumnCompression::None,
+        Some(CompressionHint::Uncompressed) => schema_pb::ColumnCompression::Uncompressed,
+        Some(CompressionHint::Lz4) => schema_pb::ColumnCompression::Lz4,
+        Some(CompressionHint::Snappy) => schema_pb::ColumnCompression::Snappy,
+        Some(CompressionHint::Zstd) => schema_pb::ColumnCompression::Zstd,
+    }
+}
+
+fn compression_from_pb(v: i32) -> Result<Option<CompressionHint>> {
+    let compression = schema_pb::ColumnCompression::from_i32(v)
+        .with_context(|| UnknownCompression { s: v.to_string() })?;
+    let compression = match compression {
+        schema_pb::ColumnCompression::None => None,
+        schema_pb::ColumnCompression::Uncompressed => Some(CompressionHint::Uncompressed),
+        schema_pb::ColumnCompression::Lz4 => Some(CompressionHint::Lz4),
+        schema_pb::ColumnCompression::Snappy => Some(CompressionHint::Snappy),
+        schema_pb::ColumnCompression::Zstd => Some(CompressionHint::Zstd),
+    };
+
+    Ok(compression)
+}
+
 /// Meta data of the arrow field.
 #[derive(Clone, Debug, Default, PartialEq)]
 struct ArrowFieldMeta {
@@ -142,6 +474,10 @@ struct ArrowFieldMeta {
     is_tag: bool,
     comment: String,
     is_dictionary: bool,
+    aggregation: Option<ColumnAggregation>,
+    encoding: Option<EncodingHint>,
+    compression: Option<CompressionHint>,
+    is_json: bool,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -150,6 +486,10 @@ pub enum ArrowFieldMetaKey {
     IsTag,
     IsDictionary,
     Comment,
+    Aggregation,
+    Encoding,
+    Compression,
+    IsJson,
 }
 
 impl ArrowFieldMetaKey {
@@ -159,6 +499,10 @@ impl ArrowFieldMetaKey {
             ArrowFieldMetaKey::IsTag => "field::is_tag",
             ArrowFieldMetaKey::Comment => "field::comment",
             ArrowFieldMetaKey::IsDictionary => "field::is_dictionary",
+            ArrowFieldMetaKey::Aggregation => "field::aggregation",
+            ArrowFieldMetaKey::Encoding => "field::encoding",
+            ArrowFieldMetaKey::Compression => "field::compression",
+            ArrowFieldMetaKey::IsJson => "field::is_json",
         }
     }
 
@@ -197,6 +541,18 @@ pub struct ColumnSchema {
     pub escaped_name: String,
     /// Default value expr
     pub default_value: Option<Expr>,
+    /// Aggregation of the column, only takes effect in the aggregate update
+    /// mode
+    pub aggregation: Option<ColumnAggregation>,
+    /// Encoding hint of the column, chosen automatically if not set
+    pub encoding: Option<EncodingHint>,
+    /// Compression hint of the column, the compression of the table is used
+    /// if not set
+    pub compression: Option<CompressionHint>,
+    /// Precision and scale of the column, only set for the decimal column
+    pub decimal_type: Option<DecimalType>,
+    /// Type of the elements, only set for the array column
+    pub element_type: Option<DatumKind>,
 }
 
 impl ColumnSchema {
@@ -220,6 +576,9 @@ impl ColumnSchema {
             DatumKind::Boolean => true,
             DatumKind::Date => true,
             DatumKind::Time => true,
+            DatumKind::Decimal => true,
+            DatumKind::Json => false,
+            DatumKind::Array => false,
         }
     }
 
@@ -233,6 +592,29 @@ impl ColumnSchema {
         From::from(self)
     }
 
+    /// Arrow data type of the column, the decimal column is mapped to the
+    /// `Decimal128` with its own precision and scale, and the array column is
+    /// mapped to the `List` of its element type.
+    pub fn to_arrow_data_type(&self) -> DataType {
+        match (self.decimal_type, self.element_type) {
+            (Some(decimal_type), _) => {
+                DataType::Decimal128(decimal_type.precision(), decimal_type.scale())
+            }
+            (_, Some(element_type)) => array::to_arrow_data_type(element_type),
+            _ => self.data_type.into(),
+        }
+    }
+
+    /// Creates an empty datum of the column, used as the template to decode
+    /// the datum of the column.
+    pub fn empty_datum(&self) -> Datum {
+        match (self.decimal_type, self.element_type) {
+            (Some(decimal_type), _) => Datum::Decimal(Decimal::new_unchecked(0, decimal_type)),
+            (_, Some(element_type)) => Datum::Array(ArrayValue::empty(element_type)),
+            _ => Datum::empty(&self.data_type),
+        }
+    }
+
     /// Returns Ok if column with `writer_schema` can write to column with the
     /// same schema as `self`.
     pub fn compatible_for_write(
@@ -240,7 +622,9 @@ impl ColumnSchema {
         writer_schema: &ColumnSchema,
     ) -> std::result::Result<(), CompatError> {
         ensure!(
-            self.data_type == writer_schema.data_type,
+            self.data_type == writer_schema.data_type
+                && self.decimal_type == writer_schema.decimal_type
+                && self.element_type == writer_schema.element_type,
             IncompatDataType {
                 name: &self.name,
                 expect: writer_schema.data_type,
@@ -312,7 +696,9 @@ impl TryFrom<schema_pb::ColumnSchema> for ColumnSchema {
 
     fn try_from(column_schema: schema_pb::ColumnSchema) -> Result<Self> {
         let escaped_name = column_schema.name.escape_debug().to_string();
-        let data_type = column_schema.data_type();
+        let data_type = DatumKind::from(column_schema.data_type());
+        let decimal_type = decimal_type_from_pb(data_type, column_schema.decimal_type)?;
+        let element_type = element_type_from_pb(data_type, column_schema.element_type)?;
         let default_value = column_schema
             .default_value
             .map(|v| match v {
@@ -322,17 +708,25 @@ impl TryFrom<schema_pb::ColumnSchema> for ColumnSchema {
                 }
             })
             .transpose()?;
+        let aggregation = aggregation_from_pb(column_schema.aggregation)?;
+        let encoding = encoding_from_pb(column_schema.encoding)?;
+        let compression = compression_from_pb(column_schema.compression)?;
 
         Ok(Self {
             id: column_schema.id,
             name: column_schema.name,
-            data_type: DatumKind::from(data_type),
+            data_type,
             is_nullable: column_schema.is_nullable,
             is_tag: column_schema.is_tag,
             is_dictionary: column_schema.is_dictionary,
             comment: column_schema.comment,
             escaped_name,
             default_value,
+            aggregation,
+            encoding,
+            compression,
+            decimal_type,
+            element_type,
         })
     }
 }
@@ -346,21 +740,42 @@ impl TryFrom<&Arc<Field>> for ColumnSchema {
             is_tag,
             is_dictionary,
             comment,
+            aggregation,
+            encoding,
+            compression,
+            is_json,
         } = decode_arrow_field_meta_data(field.metadata())?;
+        let decimal_type = match field.data_type() {
+            DataType::Decimal128(precision, scale) => {
+                Some(DecimalType::new_unchecked(*precision, *scale))
+            }
+            _ => None,
+        };
+        let element_type = array::element_kind_of_arrow_type(field.data_type());
+        // The json column is mapped to the string type of arrow, so it's recognized
+        // by the meta data.
+        let data_type = if is_json {
+            DatumKind::Json
+        } else {
+            DatumKind::from_data_type(field.data_type()).context(UnsupportedDataType {
+                data_type: field.data_type().clone(),
+            })?
+        };
         Ok(Self {
             id,
             name: field.name().clone(),
-            data_type: DatumKind::from_data_type(field.data_type()).context(
-                UnsupportedDataType {
-                    data_type: field.data_type().clone(),
-                },
-            )?,
+            data_type,
             is_nullable: field.is_nullable(),
             is_tag,
             is_dictionary,
             comment,
             escaped_name: field.name().escape_debug().to_string(),
             default_value: None,
+            aggregation,
+            encoding,
+            compression,
+            decimal_type,
+            element_type,
         })
     }
 }
@@ -381,7 +796,7 @@ impl From<&ColumnSchema> for Field {
         } else {
             Field::new(
                 &col_schema.name,
-                col_schema.data_type.into(),
+                col_schema.to_arrow_data_type(),
                 col_schema.is_nullable,
             )
         };
@@ -425,10 +840,37 @@ fn decode_arrow_field_meta_data(meta: &HashMap<String, String>) -> Result<ArrowF
             is_tag: parse_arrow_field_meta_value(meta, ArrowFieldMetaKey::IsTag)?,
             comment: parse_arrow_field_meta_value(meta, ArrowFieldMetaKey::Comment)?,
             is_dictionary: parse_arrow_field_meta_value(meta, ArrowFieldMetaKey::IsDictionary)?,
+            aggregation: parse_optional_arrow_field_meta_value(
+                meta,
+                ArrowFieldMetaKey::Aggregation,
+            )?,
+            encoding: parse_optional_arrow_field_meta_value(meta, ArrowFieldMetaKey::Encoding)?,
+            compression: parse_optional_arrow_field_meta_value(
+                meta,
+                ArrowFieldMetaKey::Compression,
+            )?,
+            is_json: parse_arrow_field_meta_value(meta, ArrowFieldMetaKey::IsJson)?,
         })
     }
 }
 
+fn parse_optional_arrow_field_meta_value<T>(
+    meta: &HashMap<String, String>,
+    key: ArrowFieldMetaKey,
+) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    meta.get(key.as_str())
+        .map(|raw_value| {
+            T::from_str(raw_value)
+                .map_err(|e| Box::new(e) as _)
+                .context(InvalidArrowFieldMetaValue { key, raw_value })
+        })
+        .transpose()
+}
+
 fn encode_arrow_field_meta_data(col_schema: &ColumnSchema) -> HashMap<String, String> {
     let mut meta = HashMap::new();
 
@@ -445,6 +887,27 @@ fn encode_arrow_field_meta_data(col_schema: &ColumnSchema) -> HashMap<String, St
         ArrowFieldMetaKey::Comment.to_string(),
         col_schema.comment.clone(),
     );
+    if let Some(aggregation) = col_schema.aggregation {
+        meta.insert(
+            ArrowFieldMetaKey::Aggregation.to_string(),
+            aggregation.to_string(),
+        );
+    }
+    if let Some(encoding) = col_schema.encoding {
+        meta.insert(
+            ArrowFieldMetaKey::Encoding.to_string(),
+            encoding.to_string(),
+        );
+    }
+    if let Some(compression) = col_schema.compression {
+        meta.insert(
+            ArrowFieldMetaKey::Compression.to_string(),
+            compression.to_string(),
+        );
+    }
+    if col_schema.data_type == DatumKind::Json {
+        meta.insert(ArrowFieldMetaKey::IsJson.to_string(), true.to_string());
+    }
 
     meta
 }
@@ -460,6 +923,11 @@ pub struct Builder {
     is_dictionary: bool,
     comment: String,
     default_value: Option<Expr>,
+    aggregation: Option<ColumnAggregation>,
+    encoding: Option<EncodingHint>,
+    compression: Option<CompressionHint>,
+    decimal_type: Option<DecimalType>,
+    element_type: Option<DatumKind>,
 }
 
 impl Builder {
@@ -474,6 +942,11 @@ impl Builder {
             is_dictionary: false,
             comment: String::new(),
             default_value: None,
+            aggregation: None,
+            encoding: None,
+            compression: None,
+            decimal_type: None,
+            element_type: None,
         }
     }
 
@@ -510,6 +983,38 @@ impl Builder {
         self
     }
 
+    /// Set the aggregation of this column, default is None.
+    pub fn aggregation(mut self, aggregation: Option<ColumnAggregation>) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    /// Set the encoding hint of this column, default is None.
+    pub fn encoding(mut self, encoding: Option<EncodingHint>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set the compression hint of this column, default is None.
+    pub fn compression(mut self, compression: Option<CompressionHint>) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Set the precision and scale of the decimal column, the default
+    /// precision and scale are used if not set.
+    pub fn decimal_type(mut self, decimal_type: Option<DecimalType>) -> Self {
+        self.decimal_type = decimal_type;
+        self
+    }
+
+    /// Set the element type of the array column, the array of string is used
+    /// if not set.
+    pub fn element_type(mut self, element_type: Option<DatumKind>) -> Self {
+        self.element_type = element_type;
+        self
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.is_tag {
             ensure!(
@@ -529,12 +1034,61 @@ impl Builder {
             );
         }
 
+        if let Some(aggregation) = self.aggregation {
+            ensure!(
+                !self.is_tag && aggregation.is_valid_type(self.data_type),
+                InvalidAggregation {
+                    aggregation,
+                    data_type: self.data_type,
+                    is_tag: self.is_tag,
+                }
+            );
+        }
+
+        if let Some(encoding) = self.encoding {
+            ensure!(
+                encoding.is_valid_type(self.data_type),
+                InvalidEncoding {
+                    encoding,
+                    data_type: self.data_type,
+                }
+            );
+        }
+
+        if let Some(decimal_type) = self.decimal_type {
+            ensure!(
+                self.data_type == DatumKind::Decimal,
+                InvalidDecimalType {
+                    decimal_type,
+                    data_type: self.data_type,
+                }
+            );
+        }
+
+        if let Some(element_type) = self.element_type {
+            ensure!(
+                self.data_type == DatumKind::Array && array::is_valid_element_kind(element_type),
+                InvalidElementType {
+                    element_type,
+                    data_type: self.data_type,
+                }
+            );
+        }
+
         Ok(())
     }
 
     pub fn build(self) -> Result<ColumnSchema> {
         self.validate()?;
         let escaped_name = self.name.escape_debug().to_string();
+        let decimal_type = match self.data_type {
+            DatumKind::Decimal => Some(self.decimal_type.unwrap_or_default()),
+            _ => None,
+        };
+        let element_type = match self.data_type {
+            DatumKind::Array => Some(self.element_type.unwrap_or(array::DEFAULT_ELEMENT_KIND)),
+            _ => None,
+        };
         Ok(ColumnSchema {
             id: self.id,
             name: self.name,
@@ -545,6 +1099,11 @@ impl Builder {
             comment: self.comment,
             escaped_name,
             default_value: self.default_value,
+            aggregation: self.aggregation,
+            encoding: self.encoding,
+            compression: self.compression,
+            decimal_type,
+            element_type,
         })
     }
 }
@@ -566,13 +1125,58 @@ impl From<ColumnSchema> for schema_pb::ColumnSchema {
             is_dictionary: src.is_dictionary,
             comment: src.comment,
             default_value,
+            aggregation: aggregation_to_pb(src.aggregation) as i32,
+            encoding: encoding_to_pb(src.encoding) as i32,
+            compression: compression_to_pb(src.compression) as i32,
+            decimal_type: src.decimal_type.map(|v| schema_pb::DecimalType {
+                precision: v.precision() as u32,
+                scale: v.scale() as u32,
+            }),
+            element_type: schema_pb::DataType::from(src.element_type.unwrap_or(DatumKind::Null))
+                as i32,
         }
     }
 }
 
+fn decimal_type_from_pb(
+    data_type: DatumKind,
+    decimal_type: Option<schema_pb::DecimalType>,
+) -> Result<Option<DecimalType>> {
+    if data_type != DatumKind::Decimal {
+        return Ok(None);
+    }
+
+    let decimal_type = decimal_type.context(MissingDecimalType)?;
+    let decimal_type =
+        DecimalType::try_new(decimal_type.precision as u64, decimal_type.scale as u64)
+            .context(DecodeDecimalType)?;
+    Ok(Some(decimal_type))
+}
+
+fn element_type_from_pb(data_type: DatumKind, element_type: i32) -> Result<Option<DatumKind>> {
+    if data_type != DatumKind::Array {
+        return Ok(None);
+    }
+
+    // The unknown value is treated as null, which is an invalid element type.
+    let element_type = schema_pb::DataType::from_i32(element_type)
+        .map(DatumKind::from)
+        .unwrap_or(DatumKind::Null);
+    ensure!(
+        array::is_valid_element_kind(element_type),
+        InvalidElementType {
+            element_type,
+            data_type,
+        }
+    );
+
+    Ok(Some(element_type))
+}
+
 #[cfg(test)]
 mod tests {
     use macros::hash_map;
+    use prost::Message;
     use sqlparser::ast::Value;
 
     use super::*;
@@ -604,6 +1208,11 @@ mod tests {
             comment: "Comment of this column".to_string(),
             escaped_name: "test_column_schema".escape_debug().to_string(),
             default_value: Some(Expr::Value(Value::Boolean(true))),
+            aggregation: None,
+            encoding: None,
+            compression: None,
+            decimal_type: None,
+            element_type: None,
         };
 
         assert_eq!(&lhs, &rhs);
@@ -660,6 +1269,10 @@ mod tests {
                     is_tag: true,
                     comment: "".to_string(),
                     is_dictionary: false,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    is_json: false,
                 },
             ),
             (
@@ -674,6 +1287,28 @@ mod tests {
                     is_tag: false,
                     comment: "abc".to_string(),
                     is_dictionary: true,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    is_json: false,
+                },
+            ),
+            (
+                hash_map! {
+                    "field::id".to_string() => "2".to_string(),
+                    "field::is_tag".to_string() => "false".to_string(),
+                    "field::comment".to_string() => "".to_string(),
+                    "field::aggregation".to_string() => "SUM".to_string()
+                },
+                ArrowFieldMeta {
+                    id: 2,
+                    is_tag: false,
+                    comment: "".to_string(),
+                    is_dictionary: false,
+                    aggregation: Some(ColumnAggregation::Sum),
+                    encoding: None,
+                    compression: None,
+                    is_json: false,
                 },
             ),
         ];
@@ -687,4 +1322,292 @@ mod tests {
         };
         assert!(decode_arrow_field_meta_data(&meta).is_err());
     }
+
+    #[test]
+    fn test_column_aggregation() {
+        for aggregation in [
+            ColumnAggregation::Sum,
+            ColumnAggregation::Max,
+            ColumnAggregation::Min,
+            ColumnAggregation::Replace,
+            ColumnAggregation::HllUnion,
+        ] {
+            let parsed = ColumnAggregation::from_str(&aggregation.to_string()).unwrap();
+            assert_eq!(aggregation, parsed);
+        }
+        assert_eq!(
+            ColumnAggregation::Sum,
+            ColumnAggregation::from_str("sum").unwrap()
+        );
+        assert!(ColumnAggregation::from_str("avg").is_err());
+
+        let column_schema = Builder::new("value".to_string(), DatumKind::Double)
+            .aggregation(Some(ColumnAggregation::Sum))
+            .build()
+            .unwrap();
+        let field = Arc::new(column_schema.to_arrow_field());
+        assert_eq!(column_schema, ColumnSchema::try_from(&field).unwrap());
+
+        assert!(Builder::new("value".to_string(), DatumKind::String)
+            .aggregation(Some(ColumnAggregation::Sum))
+            .build()
+            .is_err());
+        assert!(Builder::new("value".to_string(), DatumKind::Double)
+            .aggregation(Some(ColumnAggregation::HllUnion))
+            .build()
+            .is_err());
+        assert!(Builder::new("host".to_string(), DatumKind::String)
+            .is_tag(true)
+            .aggregation(Some(ColumnAggregation::Max))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_column_aggregation_pb_convert() {
+        for (data_type, aggregation) in [
+            (DatumKind::Double, None),
+            (DatumKind::Double, Some(ColumnAggregation::Sum)),
+            (DatumKind::Int64, Some(ColumnAggregation::Max)),
+            (DatumKind::Timestamp, Some(ColumnAggregation::Min)),
+            (DatumKind::String, Some(ColumnAggregation::Replace)),
+            (DatumKind::Varbinary, Some(ColumnAggregation::HllUnion)),
+        ] {
+            let column_schema = Builder::new("value".to_string(), data_type)
+                .aggregation(aggregation)
+                .build()
+                .unwrap();
+            let encoded = schema_pb::ColumnSchema::from(column_schema.clone()).encode_to_vec();
+            let pb_schema = schema_pb::ColumnSchema::decode(encoded.as_slice()).unwrap();
+            let schema_from_pb = ColumnSchema::try_from(pb_schema).unwrap();
+            assert_eq!(column_schema, schema_from_pb);
+            assert_eq!(aggregation, schema_from_pb.aggregation);
+        }
+
+        let mut pb_schema = schema_pb::ColumnSchema::from(new_test_column_schema());
+        pb_schema.aggregation = 100;
+        assert!(ColumnSchema::try_from(pb_schema).is_err());
+    }
+
+    #[test]
+    fn test_column_encoding_and_compression() {
+        for encoding in [
+            EncodingHint::Plain,
+            EncodingHint::Dictionary,
+            EncodingHint::DeltaBinaryPacked,
+            EncodingHint::DeltaLengthByteArray,
+            EncodingHint::DeltaByteArray,
+            EncodingHint::ByteStreamSplit,
+        ] {
+            let parsed = EncodingHint::from_str(&encoding.to_string()).unwrap();
+            assert_eq!(encoding, parsed);
+        }
+        assert!(EncodingHint::from_str("gorilla").is_err());
+
+        for compression in [
+            CompressionHint::Uncompressed,
+            CompressionHint::Lz4,
+            CompressionHint::Snappy,
+            CompressionHint::Zstd,
+        ] {
+            let parsed = CompressionHint::from_str(&compression.to_string()).unwrap();
+            assert_eq!(compression, parsed);
+        }
+        assert!(CompressionHint::from_str("gzip").is_err());
+
+        let column_schema = Builder::new("ts".to_string(), DatumKind::Timestamp)
+            .encoding(Some(EncodingHint::DeltaBinaryPacked))
+            .compression(Some(CompressionHint::Zstd))
+            .build()
+            .unwrap();
+        let field = Arc::new(column_schema.to_arrow_field());
+        assert_eq!(column_schema, ColumnSchema::try_from(&field).unwrap());
+
+        assert!(Builder::new("value".to_string(), DatumKind::Double)
+            .encoding(Some(EncodingHint::DeltaBinaryPacked))
+            .build()
+            .is_err());
+        assert!(Builder::new("value".to_string(), DatumKind::Int64)
+            .encoding(Some(EncodingHint::ByteStreamSplit))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_column_encoding_and_compression_pb_convert() {
+        for (data_type, encoding, compression) in [
+            (DatumKind::Timestamp, None, None),
+            (
+                DatumKind::Timestamp,
+                Some(EncodingHint::DeltaBinaryPacked),
+                Some(CompressionHint::Zstd),
+            ),
+            (
+                DatumKind::Double,
+                Some(EncodingHint::ByteStreamSplit),
+                Some(CompressionHint::Lz4),
+            ),
+            (
+                DatumKind::String,
+                Some(EncodingHint::DeltaLengthByteArray),
+                Some(CompressionHint::Snappy),
+            ),
+            (
+                DatumKind::String,
+                Some(EncodingHint::DeltaByteArray),
+                Some(CompressionHint::Uncompressed),
+            ),
+            (DatumKind::String, Some(EncodingHint::Dictionary), None),
+            (DatumKind::Int64, Some(EncodingHint::Plain), None),
+        ] {
+            let column_schema = Builder::new("value".to_string(), data_type)
+                .encoding(encoding)
+                .compression(compression)
+                .build()
+                .unwrap();
+            let encoded = schema_pb::ColumnSchema::from(column_schema.clone()).encode_to_vec();
+            let pb_schema = schema_pb::ColumnSchema::decode(encoded.as_slice()).unwrap();
+            let schema_from_pb = ColumnSchema::try_from(pb_schema).unwrap();
+            assert_eq!(column_schema, schema_from_pb);
+            assert_eq!(encoding, schema_from_pb.encoding);
+            assert_eq!(compression, schema_from_pb.compression);
+        }
+
+        let mut pb_schema = schema_pb::ColumnSchema::from(new_test_column_schema());
+        pb_schema.encoding = 100;
+        assert!(ColumnSchema::try_from(pb_schema).is_err());
+        let mut pb_schema = schema_pb::ColumnSchema::from(new_test_column_schema());
+        pb_schema.compression = 100;
+        assert!(ColumnSchema::try_from(pb_schema).is_err());
+    }
+
+    #[test]
+    fn test_decimal_column() {
+        let decimal_type = DecimalType::try_new(20, 4).unwrap();
+        let column_schema = Builder::new("amount".to_string(), DatumKind::Decimal)
+            .id(3)
+            .decimal_type(Some(decimal_type))
+            .build()
+            .unwrap();
+        assert_eq!(
+            column_schema.to_arrow_data_type(),
+            DataType::Decimal128(20, 4)
+        );
+        assert_eq!(
+            column_schema.empty_datum(),
+            Datum::Decimal(Decimal::new_unchecked(0, decimal_type))
+        );
+
+        let field = Arc::new(column_schema.to_arrow_field());
+        assert_eq!(column_schema, ColumnSchema::try_from(&field).unwrap());
+        let pb_schema = schema_pb::ColumnSchema::from(column_schema.clone());
+        assert_eq!(schema_pb::DataType::Decimal as i32, pb_schema.data_type);
+        assert_eq!(
+            Some(schema_pb::DecimalType {
+                precision: 20,
+                scale: 4
+            }),
+            pb_schema.decimal_type
+        );
+        let encoded = pb_schema.encode_to_vec();
+        let pb_schema = schema_pb::ColumnSchema::decode(encoded.as_slice()).unwrap();
+        assert_eq!(column_schema, ColumnSchema::try_from(pb_schema).unwrap());
+
+        // The decimal type of the decimal column must be valid.
+        let mut pb_schema = schema_pb::ColumnSchema::from(column_schema.clone());
+        pb_schema.decimal_type = None;
+        assert!(ColumnSchema::try_from(pb_schema).is_err());
+        let mut pb_schema = schema_pb::ColumnSchema::from(column_schema);
+        pb_schema.decimal_type = Some(schema_pb::DecimalType {
+            precision: 100,
+            scale: 4,
+        });
+        assert!(ColumnSchema::try_from(pb_schema).is_err());
+
+        let column_schema = Builder::new("amount".to_string(), DatumKind::Decimal)
+            .build()
+            .unwrap();
+        assert_eq!(column_schema.decimal_type, Some(DecimalType::default()));
+        assert!(Builder::new("value".to_string(), DatumKind::Double)
+            .decimal_type(Some(decimal_type))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_json_column() {
+        let column_schema = Builder::new("attrs".to_string(), DatumKind::Json)
+            .id(3)
+            .build()
+            .unwrap();
+        let field = Arc::new(column_schema.to_arrow_field());
+        assert_eq!(&DataType::Utf8, field.data_type());
+        assert_eq!(column_schema, ColumnSchema::try_from(&field).unwrap());
+        let pb_schema = schema_pb::ColumnSchema::from(column_schema.clone());
+        assert_eq!(schema_pb::DataType::Json as i32, pb_schema.data_type);
+        let encoded = pb_schema.encode_to_vec();
+        let pb_schema = schema_pb::ColumnSchema::decode(encoded.as_slice()).unwrap();
+        assert_eq!(column_schema, ColumnSchema::try_from(pb_schema).unwrap());
+
+        assert!(Builder::new("attrs".to_string(), DatumKind::Json)
+            .is_tag(true)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_array_column() {
+        let column_schema = Builder::new("roles".to_string(), DatumKind::Array)
+            .id(3)
+            .is_nullable(true)
+            .element_type(Some(DatumKind::Int32))
+            .build()
+            .unwrap();
+        assert_eq!(
+            column_schema.to_arrow_data_type(),
+            array::to_arrow_data_type(DatumKind::Int32)
+        );
+        assert_eq!(
+            column_schema.empty_datum(),
+            Datum::Array(ArrayValue::empty(DatumKind::Int32))
+        );
+
+        let field = Arc::new(column_schema.to_arrow_field());
+        assert_eq!(column_schema, ColumnSchema::try_from(&field).unwrap());
+        let pb_schema = schema_pb::ColumnSchema::from(column_schema.clone());
+        assert_eq!(schema_pb::DataType::Array as i32, pb_schema.data_type);
+        assert_eq!(schema_pb::DataType::Int32 as i32, pb_schema.element_type);
+        let encoded = pb_schema.encode_to_vec();
+        let pb_schema = schema_pb::ColumnSchema::decode(encoded.as_slice()).unwrap();
+        assert_eq!(column_schema, ColumnSchema::try_from(pb_schema).unwrap());
+
+        // The element type of the array column must be valid.
+        for invalid_element_type in [
+            schema_pb::DataType::Null as i32,
+            schema_pb::DataType::Json as i32,
+            schema_pb::DataType::Array as i32,
+            100,
+        ] {
+            let mut pb_schema = schema_pb::ColumnSchema::from(column_schema.clone());
+            pb_schema.element_type = invalid_element_type;
+            assert!(ColumnSchema::try_from(pb_schema).is_err());
+        }
+
+        let column_schema = Builder::new("roles".to_string(), DatumKind::Array)
+            .build()
+            .unwrap();
+        assert_eq!(column_schema.element_type, Some(DatumKind::String));
+        assert!(Builder::new("roles".to_string(), DatumKind::Array)
+            .is_tag(true)
+            .build()
+            .is_err());
+        assert!(Builder::new("roles".to_string(), DatumKind::Array)
+            .element_type(Some(DatumKind::Json))
+            .build()
+            .is_err());
+        assert!(Builder::new("value".to_string(), DatumKind::Double)
+            .element_type(Some(DatumKind::Double))
+            .build()
+            .is_err());
+    }
 }
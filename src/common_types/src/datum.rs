@@ -17,7 +17,7 @@
 
 //! Datum holds different kind of data
 
-use std::{convert::TryFrom, fmt, str};
+use std::{borrow::Cow, convert::TryFrom, fmt, str};
 
 use arrow::{
     datatypes::{DataType, TimeUnit},
@@ -28,11 +28,17 @@ use chrono::{Datelike, Local, NaiveDate, NaiveTime, TimeZone, Timelike};
 use datafusion::scalar::ScalarValue;
 use hash_ext::hash64;
 use horaedbproto::schema::DataType as DataTypePb;
-use serde::ser::{Serialize, Serializer};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
 use snafu::{Backtrace, OptionExt, ResultExt, Snafu};
 use sqlparser::ast::{DataType as SqlDataType, Value};
 
-use crate::{hex, string::StringBytes, time::Timestamp};
+use crate::{
+    array::{self, ArrayValue},
+    decimal::{self, Decimal, DecimalType},
+    hex, json,
+    string::StringBytes,
+    time::Timestamp,
+};
 
 const DATE_FORMAT: &str = "%Y-%m-%d";
 const TIME_FORMAT: &str = "%H:%M:%S%.3f";
@@ -94,6 +100,12 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Invalid decimal, err:{source}"))]
+    InvalidDecimal { source: decimal::Error },
+
+    #[snafu(display("Invalid json, err:{source}"))]
+    InvalidJson { source: json::Error },
+
     #[snafu(display("Invalid datum byte, byte:{value}.\nBacktrace:\n{backtrace}"))]
     InvalidDatumByte { value: u8, backtrace: Backtrace },
 
@@ -147,10 +159,13 @@ pub enum DatumKind {
     Boolean,
     Date,
     Time,
+    Decimal,
+    Json,
+    Array,
 }
 
 impl DatumKind {
-    pub const VALUES: [Self; 17] = [
+    pub const VALUES: [Self; 20] = [
         Self::Null,
         Self::Timestamp,
         Self::Double,
@@ -168,6 +183,9 @@ impl DatumKind {
         Self::Boolean,
         Self::Date,
         Self::Time,
+        Self::Decimal,
+        Self::Json,
+        Self::Array,
     ];
 
     /// Return true if this is DatumKind::Timestamp
@@ -209,6 +227,7 @@ impl DatumKind {
                 | DatumKind::Boolean
                 | DatumKind::Date
                 | DatumKind::Time
+                | DatumKind::Decimal
         )
     }
 
@@ -247,6 +266,9 @@ impl DatumKind {
             DatumKind::Boolean => "boolean",
             DatumKind::Date => "date",
             DatumKind::Time => "time",
+            DatumKind::Decimal => "decimal",
+            DatumKind::Json => "json",
+            DatumKind::Array => "array",
         }
     }
 
@@ -276,6 +298,9 @@ impl DatumKind {
             DatumKind::Boolean => 1,
             DatumKind::Date => 4,
             DatumKind::Time => 8,
+            DatumKind::Decimal => 16,
+            DatumKind::Json => return None,
+            DatumKind::Array => return None,
         };
         Some(size)
     }
@@ -298,6 +323,9 @@ impl TryFrom<&SqlDataType> for DatumKind {
             SqlDataType::Varbinary(_) => Ok(Self::Varbinary),
             SqlDataType::Date => Ok(Self::Date),
             SqlDataType::Time(_, _) => Ok(Self::Time),
+            SqlDataType::Decimal(_) | SqlDataType::Numeric(_) => Ok(Self::Decimal),
+            SqlDataType::JSON => Ok(Self::Json),
+            SqlDataType::Array(_) => Ok(Self::Array),
             SqlDataType::Custom(objects, _) if objects.0.len() == 1 => {
                 match objects.0[0].value.as_str() {
                     "UINT64" | "uint64" => Ok(Self::UInt64),
@@ -315,7 +343,6 @@ impl TryFrom<&SqlDataType> for DatumKind {
                 }
             }
 
-            // Unlike datafusion, Decimal is not supported now
             _ => UnsupportedDataType {
                 sql_type: sql_type.clone(),
             }
@@ -346,6 +373,9 @@ impl TryFrom<u8> for DatumKind {
             v if DatumKind::Boolean.into_u8() == v => Ok(DatumKind::Boolean),
             v if DatumKind::Date.into_u8() == v => Ok(DatumKind::Date),
             v if DatumKind::Time.into_u8() == v => Ok(DatumKind::Time),
+            v if DatumKind::Decimal.into_u8() == v => Ok(DatumKind::Decimal),
+            v if DatumKind::Json.into_u8() == v => Ok(DatumKind::Json),
+            v if DatumKind::Array.into_u8() == v => Ok(DatumKind::Array),
             _ => InvalidDatumByte { value: v }.fail(),
         }
     }
@@ -371,6 +401,9 @@ impl From<DatumKind> for DataTypePb {
             DatumKind::Boolean => Self::Bool,
             DatumKind::Date => Self::Date,
             DatumKind::Time => Self::Time,
+            DatumKind::Decimal => Self::Decimal,
+            DatumKind::Json => Self::Json,
+            DatumKind::Array => Self::Array,
         }
     }
 }
@@ -395,6 +428,9 @@ impl From<DataTypePb> for DatumKind {
             DataTypePb::Bool => DatumKind::Boolean,
             DataTypePb::Date => DatumKind::Date,
             DataTypePb::Time => DatumKind::Time,
+            DataTypePb::Decimal => DatumKind::Decimal,
+            DataTypePb::Json => DatumKind::Json,
+            DataTypePb::Array => DatumKind::Array,
         }
     }
 }
@@ -445,6 +481,15 @@ pub enum Datum {
     /// It is mapped to [`arrow::datatypes::DataType::Time64`].
     /// The supported time range is '-838:59:59.000000' to '838:59:59.000000'.
     Time(i64),
+    /// Fixed-point decimal with the precision and scale.
+    /// It is mapped to [`arrow::datatypes::DataType::Decimal128`].
+    Decimal(Decimal),
+    /// Json document, which is validated on write and stored as string.
+    /// It is mapped to [`arrow::datatypes::DataType::Utf8`].
+    Json(StringBytes),
+    /// Array of the values of the same kind.
+    /// It is mapped to [`arrow::datatypes::DataType::List`].
+    Array(ArrayValue),
 }
 
 impl Datum {
@@ -468,6 +513,9 @@ impl Datum {
             DatumKind::Boolean => Self::Boolean(false),
             DatumKind::Date => Self::Date(0),
             DatumKind::Time => Self::Time(0),
+            DatumKind::Decimal => Self::Decimal(Decimal::default()),
+            DatumKind::Json => Self::Json(StringBytes::new()),
+            DatumKind::Array => Self::Array(ArrayValue::empty(array::DEFAULT_ELEMENT_KIND)),
         }
     }
 
@@ -491,6 +539,9 @@ impl Datum {
             Datum::Boolean(_) => DatumKind::Boolean,
             Datum::Date(_) => DatumKind::Date,
             Datum::Time(_) => DatumKind::Time,
+            Datum::Decimal(_) => DatumKind::Decimal,
+            Datum::Json(_) => DatumKind::Json,
+            Datum::Array(_) => DatumKind::Array,
         }
     }
 
@@ -514,6 +565,9 @@ impl Datum {
             Datum::Boolean(v) => *v as u64,
             Datum::Date(v) => *v as u64,
             Datum::Time(v) => *v as u64,
+            Datum::Decimal(v) => v.value() as u64,
+            Datum::Json(v) => hash64(v.as_bytes()),
+            Datum::Array(v) => hash64(&v.to_bytes()),
         }
     }
 
@@ -530,10 +584,10 @@ impl Datum {
         }
     }
 
-    /// Cast datum to &str.
+    /// Cast datum to &str, the json datum is also treated as string.
     pub fn as_str(&self) -> Option<&str> {
         match self {
-            Datum::String(v) => Some(v),
+            Datum::String(v) | Datum::Json(v) => Some(v),
             _ => None,
         }
     }
@@ -601,11 +655,22 @@ impl Datum {
             Datum::Time(v) => Some(*v as f64),
             Datum::Int16(v) => Some(*v as f64),
             Datum::Int8(v) => Some(*v as f64),
+            Datum::Decimal(v) => Some(v.to_f64()),
             Datum::Boolean(_)
             | Datum::Null
             | Datum::Timestamp(_)
             | Datum::Varbinary(_)
-            | Datum::String(_) => None,
+            | Datum::String(_)
+            | Datum::Json(_)
+            | Datum::Array(_) => None,
+        }
+    }
+
+    /// Cast datum to array.
+    pub fn as_array(&self) -> Option<&ArrayValue> {
+        match self {
+            Datum::Array(v) => Some(v),
+            _ => None,
         }
     }
 
@@ -667,7 +732,7 @@ impl Datum {
                 f(arr.as_slice())
             }
             Datum::Varbinary(v) => f(v.as_ref()),
-            Datum::String(v) => f(v.as_bytes()),
+            Datum::String(v) | Datum::Json(v) => f(v.as_bytes()),
             Datum::Date(v) => {
                 let arr = v.to_le_bytes();
                 f(arr.as_slice())
@@ -676,6 +741,11 @@ impl Datum {
                 let arr = v.to_le_bytes();
                 f(arr.as_slice())
             }
+            Datum::Decimal(v) => {
+                let arr = v.value().to_le_bytes();
+                f(arr.as_slice())
+            }
+            Datum::Array(v) => f(&v.to_bytes()),
         }
     }
 
@@ -701,9 +771,11 @@ impl Datum {
             Datum::Null => vec![0],
             Datum::Timestamp(ts) => ts.as_i64().to_le_bytes().to_vec(),
             Datum::Varbinary(b) => b.to_vec(),
-            Datum::String(string) => string.as_bytes().to_vec(),
+            Datum::String(string) | Datum::Json(string) => string.as_bytes().to_vec(),
             Datum::Date(v) => v.to_le_bytes().to_vec(),
             Datum::Time(v) => v.to_le_bytes().to_vec(),
+            Datum::Decimal(v) => v.value().to_le_bytes().to_vec(),
+            Datum::Array(v) => v.to_bytes(),
         }
     }
 
@@ -731,6 +803,11 @@ impl Datum {
             Datum::Boolean(v) => Some(Datum::Boolean(!v)),
             Datum::Date(_) => None,
             Datum::Time(_) => None,
+            Datum::Decimal(v) => 0i128
+                .checked_sub(v.value())
+                .map(|value| Datum::Decimal(Decimal::new_unchecked(value, v.decimal_type()))),
+            Datum::Json(_) => None,
+            Datum::Array(_) => None,
         }
     }
 
@@ -760,6 +837,9 @@ impl Datum {
                 .to_string(),
 
             Datum::Time(v) => Datum::format_datum_time(v),
+            Datum::Decimal(v) => v.to_string(),
+            Datum::Json(v) => v.to_string(),
+            Datum::Array(v) => v.to_string(),
         }
     }
 
@@ -836,6 +916,18 @@ impl Datum {
                 Ok(Datum::Int8(n))
             }
             (DatumKind::Boolean, Value::Boolean(b)) => Ok(Datum::Boolean(b)),
+            // The decimal is parsed with the default type, and should be rescaled to the
+            // type of the column later.
+            (DatumKind::Decimal, Value::Number(n, _long))
+            | (DatumKind::Decimal, Value::SingleQuotedString(n)) => {
+                let decimal = Decimal::parse(&n, DecimalType::default()).context(InvalidDecimal)?;
+                Ok(Datum::Decimal(decimal))
+            }
+            (DatumKind::Json, Value::SingleQuotedString(s))
+            | (DatumKind::Json, Value::DoubleQuotedString(s)) => {
+                json::validate(&s).context(InvalidJson)?;
+                Ok(Datum::Json(StringBytes::from(s)))
+            }
             (_, value) => InvalidValueType { kind: *kind, value }.fail(),
         }
     }
@@ -914,8 +1006,9 @@ impl Datum {
             | Datum::Int8(_)
             | Datum::Boolean(_)
             | Datum::Date(_)
-            | Datum::Time(_) => true,
-            Datum::Varbinary(_) | Datum::String(_) => false,
+            | Datum::Time(_)
+            | Datum::Decimal(_) => true,
+            Datum::Varbinary(_) | Datum::String(_) | Datum::Json(_) | Datum::Array(_) => false,
         }
     }
 
@@ -938,6 +1031,10 @@ impl Datum {
             Datum::Boolean(_) => 1,
             Datum::Date(_) => 4,
             Datum::Time(_) => 8,
+            Datum::Decimal(_) => 16,
+            Datum::Json(v) => v.len(),
+            // The size of the encoded array.
+            Datum::Array(v) => v.encoded_len(),
         }
     }
 
@@ -960,6 +1057,9 @@ impl Datum {
             Datum::Int16(v) => DatumView::Int16(*v),
             Datum::Int8(v) => DatumView::Int8(*v),
             Datum::Boolean(v) => DatumView::Boolean(*v),
+            Datum::Decimal(v) => DatumView::Decimal(*v),
+            Datum::Json(v) => DatumView::Json(v),
+            Datum::Array(v) => DatumView::Array(Cow::Borrowed(v)),
         }
     }
 }
@@ -1052,13 +1152,24 @@ impl Serialize for Datum {
             Datum::Boolean(v) => serializer.serialize_bool(*v),
             Datum::Date(v) => serializer.serialize_str(Self::format_datum_date(v).as_ref()),
             Datum::Time(v) => serializer.serialize_str(Datum::format_datum_time(v).as_ref()),
+            Datum::Decimal(v) => serializer.serialize_str(&v.to_string()),
+            Datum::Json(v) => serializer.serialize_str(v),
+            Datum::Array(v) => {
+                let mut seq = serializer.serialize_seq(Some(v.len()))?;
+                for element in v.elements() {
+                    seq.serialize_element(element)?;
+                }
+                seq.end()
+            }
         }
     }
 }
 
 /// A view to a datum.
 ///
-/// Holds copy of integer like datum and reference of string like datum.
+/// Holds copy of integer like datum and reference of string like datum. The
+/// array is borrowed if it comes from a datum, otherwise it's decoded into an
+/// owned value.
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum DatumView<'a> {
     Null,
@@ -1078,6 +1189,9 @@ pub enum DatumView<'a> {
     Boolean(bool),
     Date(i32),
     Time(i64),
+    Decimal(Decimal),
+    Json(&'a str),
+    Array(Cow<'a, ArrayValue>),
 }
 
 impl<'a> DatumView<'a> {
@@ -1106,6 +1220,9 @@ impl<'a> DatumView<'a> {
             DatumView::Boolean(_) => DatumKind::Boolean,
             DatumView::Date(_) => DatumKind::Date,
             DatumView::Time(_) => DatumKind::Time,
+            DatumView::Decimal(_) => DatumKind::Decimal,
+            DatumView::Json(_) => DatumKind::Json,
+            DatumView::Array(_) => DatumKind::Array,
         }
     }
 
@@ -1167,7 +1284,7 @@ impl<'a> DatumView<'a> {
                 f(arr.as_slice())
             }
             DatumView::Varbinary(v) => f(v),
-            DatumView::String(v) => f(v.as_bytes()),
+            DatumView::String(v) | DatumView::Json(v) => f(v.as_bytes()),
             DatumView::Date(v) => {
                 let arr = v.to_le_bytes();
                 f(arr.as_slice())
@@ -1176,6 +1293,11 @@ impl<'a> DatumView<'a> {
                 let arr = v.to_le_bytes();
                 f(arr.as_slice())
             }
+            DatumView::Decimal(v) => {
+                let arr = v.value().to_le_bytes();
+                f(arr.as_slice())
+            }
+            DatumView::Array(v) => f(&v.to_bytes()),
         }
     }
 
@@ -1198,6 +1320,9 @@ impl<'a> DatumView<'a> {
             DatumView::Boolean(v) => Datum::Boolean(*v),
             DatumView::Date(v) => Datum::Date(*v),
             DatumView::Time(v) => Datum::Time(*v),
+            DatumView::Decimal(v) => Datum::Decimal(*v),
+            DatumView::Json(v) => Datum::Json(StringBytes::copy_from_str(v)),
+            DatumView::Array(v) => Datum::Array(v.as_ref().clone()),
         }
     }
 
@@ -1293,9 +1418,24 @@ impl<'a> DatumView<'a> {
         }
     }
 
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            DatumView::Decimal(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&ArrayValue> {
+        match self {
+            DatumView::Array(v) => Some(v.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// The json datum is also treated as string.
     pub fn into_str(self) -> Option<&'a str> {
         match self {
-            DatumView::String(v) => Some(v),
+            DatumView::String(v) | DatumView::Json(v) => Some(v),
             _ => None,
         }
     }
@@ -1328,6 +1468,14 @@ impl<'a> std::hash::Hash for DatumView<'a> {
             DatumView::Boolean(v) => v.hash(state),
             DatumView::Date(v) => v.hash(state),
             DatumView::Time(v) => v.hash(state),
+            DatumView::Decimal(v) => v.hash(state),
+            DatumView::Json(v) => v.hash(state),
+            DatumView::Array(v) => {
+                v.element_kind().into_u8().hash(state);
+                for element in v.elements() {
+                    element.as_view().hash(state);
+                }
+            }
         }
     }
 }
@@ -1356,13 +1504,14 @@ impl DatumKind {
             DataType::Date32 => Some(Self::Date),
             DataType::Time64(TimeUnit::Nanosecond) => Some(Self::Time),
             DataType::Dictionary(_, _) => Some(Self::String),
+            DataType::Decimal128(_, _) => Some(Self::Decimal),
+            DataType::List(_) => array::element_kind_of_arrow_type(data_type).map(|_| Self::Array),
             DataType::Float16
             | DataType::LargeUtf8
             | DataType::LargeBinary
             | DataType::FixedSizeBinary(_)
             | DataType::Struct(_)
             | DataType::Union(_, _)
-            | DataType::List(_)
             | DataType::LargeList(_)
             | DataType::FixedSizeList(_, _)
             | DataType::Time32(_)
@@ -1371,7 +1520,6 @@ impl DatumKind {
             | DataType::Date64
             | DataType::Interval(_)
             | DataType::Duration(_)
-            | DataType::Decimal128(_, _)
             | DataType::Decimal256(_, _)
             | DataType::RunEndEncoded(_, _)
             | DataType::Map(_, _) => None,
@@ -1397,6 +1545,12 @@ impl DatumKind {
             DatumKind::Boolean => DataType::Boolean,
             DatumKind::Date => DataType::Date32,
             DatumKind::Time => DataType::Time64(TimeUnit::Nanosecond),
+            DatumKind::Decimal => {
+                let decimal_type = DecimalType::default();
+                DataType::Decimal128(decimal_type.precision(), decimal_type.scale())
+            }
+            DatumKind::Json => DataType::Utf8,
+            DatumKind::Array => array::to_arrow_data_type(array::DEFAULT_ELEMENT_KIND),
         }
     }
 }
@@ -1423,6 +1577,28 @@ impl Datum {
             Datum::Boolean(v) => Some(ScalarValue::Boolean(Some(*v))),
             Datum::Date(v) => Some(ScalarValue::Date32(Some(*v))),
             Datum::Time(v) => Some(ScalarValue::Time64Nanosecond(Some(*v))),
+            Datum::Decimal(v) => Some(ScalarValue::Decimal128(
+                Some(v.value()),
+                v.decimal_type().precision(),
+                v.decimal_type().scale(),
+            )),
+            Datum::Json(v) => Some(ScalarValue::Utf8(Some(v.to_string()))),
+            Datum::Array(v) => {
+                let element_type = DataType::from(v.element_kind());
+                let elements = v
+                    .elements()
+                    .iter()
+                    .map(|element| {
+                        element.as_scalar_value().unwrap_or_else(|| {
+                            ScalarValue::try_from(&element_type).unwrap_or(ScalarValue::Null)
+                        })
+                    })
+                    .collect();
+                Some(ScalarValue::List(
+                    Some(elements),
+                    array::element_field(v.element_kind()),
+                ))
+            }
         }
     }
 
@@ -1452,9 +1628,23 @@ impl Datum {
             }
             ScalarValue::Date32(v) => v.map(Datum::Date),
             ScalarValue::Time64Nanosecond(v) => v.map(Datum::Time),
+            ScalarValue::Decimal128(v, precision, scale) => v.map(|v| {
+                let decimal_type = DecimalType::new_unchecked(*precision, *scale);
+                Datum::Decimal(Decimal::new_unchecked(v, decimal_type))
+            }),
             ScalarValue::Dictionary(_, literal) => Datum::from_scalar_value(literal),
-            ScalarValue::List(_, _)
-            | ScalarValue::Date64(_)
+            ScalarValue::List(v, field) => {
+                let element_kind = DatumKind::from_data_type(field.data_type())?;
+                let elements = v
+                    .as_ref()?
+                    .iter()
+                    .map(|v| Datum::from_scalar_value(v).unwrap_or(Datum::Null))
+                    .collect();
+                ArrayValue::try_new(element_kind, elements)
+                    .ok()
+                    .map(Datum::Array)
+            }
+            ScalarValue::Date64(_)
             | ScalarValue::Time32Second(_)
             | ScalarValue::Time32Millisecond(_)
             | ScalarValue::Time64Microsecond(_)
@@ -1464,7 +1654,6 @@ impl Datum {
             | ScalarValue::IntervalYearMonth(_)
             | ScalarValue::IntervalDayTime(_)
             | ScalarValue::Struct(_, _)
-            | ScalarValue::Decimal128(_, _, _)
             | ScalarValue::Null
             | ScalarValue::IntervalMonthDayNano(_)
             | ScalarValue::Fixedsizelist(_, _, _)
@@ -1492,6 +1681,10 @@ impl<'a> DatumView<'a> {
             ScalarValue::UInt64(v) => v.map(DatumView::UInt64),
             ScalarValue::Date32(v) => v.map(DatumView::Date),
             ScalarValue::Time64Nanosecond(v) => v.map(DatumView::Time),
+            ScalarValue::Decimal128(v, precision, scale) => v.map(|v| {
+                let decimal_type = DecimalType::new_unchecked(*precision, *scale);
+                DatumView::Decimal(Decimal::new_unchecked(v, decimal_type))
+            }),
             ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => {
                 v.as_ref().map(|v| DatumView::String(v.as_str()))
             }
@@ -1502,8 +1695,11 @@ impl<'a> DatumView<'a> {
                 v.map(|v| DatumView::Timestamp(Timestamp::new(v)))
             }
             ScalarValue::Dictionary(_, literal) => DatumView::from_scalar_value(literal),
-            ScalarValue::List(_, _)
-            | ScalarValue::Date64(_)
+            ScalarValue::List(_, _) => match Datum::from_scalar_value(val)? {
+                Datum::Array(v) => Some(DatumView::Array(Cow::Owned(v))),
+                _ => None,
+            },
+            ScalarValue::Date64(_)
             | ScalarValue::Time32Second(_)
             | ScalarValue::Time32Millisecond(_)
             | ScalarValue::Time64Microsecond(_)
@@ -1513,7 +1709,6 @@ impl<'a> DatumView<'a> {
             | ScalarValue::IntervalYearMonth(_)
             | ScalarValue::IntervalDayTime(_)
             | ScalarValue::Struct(_, _)
-            | ScalarValue::Decimal128(_, _, _)
             | ScalarValue::Null
             | ScalarValue::IntervalMonthDayNano(_)
             | ScalarValue::Fixedsizelist(_, _, _)
@@ -1545,6 +1740,12 @@ impl From<DatumKind> for DataType {
             DatumKind::Boolean => DataType::Boolean,
             DatumKind::Date => DataType::Date32,
             DatumKind::Time => DataType::Time64(TimeUnit::Nanosecond),
+            DatumKind::Decimal => {
+                let decimal_type = DecimalType::default();
+                DataType::Decimal128(decimal_type.precision(), decimal_type.scale())
+            }
+            DatumKind::Json => DataType::Utf8,
+            DatumKind::Array => array::to_arrow_data_type(array::DEFAULT_ELEMENT_KIND),
         }
     }
 }
@@ -1577,6 +1778,9 @@ mod tests {
         assert!(DatumKind::Boolean.is_key_kind());
         assert!(DatumKind::Date.is_key_kind());
         assert!(DatumKind::Time.is_key_kind());
+        assert!(DatumKind::Decimal.is_key_kind());
+        assert!(!DatumKind::Json.is_key_kind());
+        assert!(!DatumKind::Array.is_key_kind());
     }
 
     #[test]
@@ -1618,6 +1822,16 @@ mod tests {
         assert_eq!(14, DatumKind::Boolean.into_u8());
         assert_eq!(15, DatumKind::Date.into_u8());
         assert_eq!(16, DatumKind::Time.into_u8());
+        assert_eq!(17, DatumKind::Decimal.into_u8());
+        assert_eq!(18, DatumKind::Json.into_u8());
+        assert_eq!(19, DatumKind::Array.into_u8());
+    }
+
+    #[test]
+    fn test_pb_convert() {
+        for kind in DatumKind::VALUES {
+            assert_eq!(kind, DatumKind::from(DataTypePb::from(kind)));
+        }
     }
 
     #[test]
@@ -1761,6 +1975,32 @@ mod tests {
                 true,
                 Some(Datum::Varbinary(Bytes::from(vec![199, 10, 11]))),
             ),
+            (
+                Value::Number("-12.5".to_string(), false),
+                DatumKind::Decimal,
+                true,
+                Some(Datum::Decimal(
+                    Decimal::parse("-12.5", DecimalType::default()).unwrap(),
+                )),
+            ),
+            (
+                Value::Number("1.2.3".to_string(), false),
+                DatumKind::Decimal,
+                false,
+                None,
+            ),
+            (
+                Value::SingleQuotedString(r#"{"a": [1, 2]}"#.to_string()),
+                DatumKind::Json,
+                true,
+                Some(Datum::Json(StringBytes::from_static(r#"{"a": [1, 2]}"#))),
+            ),
+            (
+                Value::SingleQuotedString(r#"{"a": "#.to_string()),
+                DatumKind::Json,
+                false,
+                None,
+            ),
             (
                 Value::EscapedStringLiteral("string_literal".to_string()),
                 DatumKind::String,
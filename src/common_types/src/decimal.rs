@@ -0,0 +1,456 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Fixed-point decimal type
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use arrow::datatypes::DECIMAL128_MAX_PRECISION;
+use snafu::{ensure, Backtrace, OptionExt, Snafu};
+
+/// Used if the precision and scale are not specified, same as datafusion.
+pub const DEFAULT_PRECISION: u8 = 38;
+pub const DEFAULT_SCALE: i8 = 10;
+pub const MAX_PRECISION: u8 = DECIMAL128_MAX_PRECISION;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "Invalid decimal type, precision:{precision}, scale:{scale}.\nBacktrace:\n{backtrace}"
+    ))]
+    InvalidDecimalType {
+        precision: u64,
+        scale: u64,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid decimal value, value:{value}.\nBacktrace:\n{backtrace}"))]
+    InvalidDecimal { value: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Decimal value overflows the type, value:{value}, type:{decimal_type}.\nBacktrace:\n{backtrace}"
+    ))]
+    DecimalOverflow {
+        value: String,
+        decimal_type: DecimalType,
+        backtrace: Backtrace,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The precision and scale of the decimal.
+///
+/// The precision is the max number of the digits, and the scale is the number
+/// of the digits after the decimal point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecimalType {
+    precision: u8,
+    scale: i8,
+}
+
+impl DecimalType {
+    pub fn try_new(precision: u64, scale: u64) -> Result<Self> {
+        ensure!(
+            precision > 0 && precision <= MAX_PRECISION as u64 && scale <= precision,
+            InvalidDecimalType { precision, scale }
+        );
+
+        Ok(Self {
+            precision: precision as u8,
+            scale: scale as i8,
+        })
+    }
+
+    /// Create the decimal type without validation, the caller should ensure
+    /// the precision and scale are valid, e.g. they are from the arrow type.
+    pub const fn new_unchecked(precision: u8, scale: i8) -> Self {
+        Self { precision, scale }
+    }
+
+    #[inline]
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    #[inline]
+    pub fn scale(&self) -> i8 {
+        self.scale
+    }
+
+    /// The max unscaled value of the type, exclusive.
+    fn max_value(&self) -> i128 {
+        10i128.pow(self.precision as u32)
+    }
+}
+
+impl Default for DecimalType {
+    fn default() -> Self {
+        Self::new_unchecked(DEFAULT_PRECISION, DEFAULT_SCALE)
+    }
+}
+
+impl fmt::Display for DecimalType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "decimal({}, {})", self.precision, self.scale)
+    }
+}
+
+/// A fixed-point decimal, stored as the unscaled value in i128.
+///
+/// For example, `123.45` in `decimal(10, 2)` is stored as `12345`.
+///
+/// The decimals are compared by their values regardless of the types, e.g.
+/// `1.50` in `decimal(10, 2)` equals to `1.5` in `decimal(5, 1)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal {
+    value: i128,
+    decimal_type: DecimalType,
+}
+
+impl Decimal {
+    /// Create the decimal from the unscaled value without validation.
+    pub const fn new_unchecked(value: i128, decimal_type: DecimalType) -> Self {
+        Self {
+            value,
+            decimal_type,
+        }
+    }
+
+    /// Create the decimal from the unscaled value.
+    pub fn try_new(value: i128, decimal_type: DecimalType) -> Result<Self> {
+        ensure!(
+            value.unsigned_abs() < decimal_type.max_value() as u128,
+            DecimalOverflow {
+                value: value.to_string(),
+                decimal_type,
+            }
+        );
+
+        Ok(Self::new_unchecked(value, decimal_type))
+    }
+
+    /// Parse the decimal from string like `-123.45`, the extra fractional
+    /// digits are rounded half away from zero.
+    pub fn parse(s: &str, decimal_type: DecimalType) -> Result<Self> {
+        let invalid = || InvalidDecimal { value: s };
+        let (negative, digits) = match s.trim().as_bytes() {
+            [b'-', rest @ ..] => (true, rest),
+            [b'+', rest @ ..] => (false, rest),
+            rest => (false, rest),
+        };
+        let (int_part, frac_part) = match digits.iter().position(|c| *c == b'.') {
+            Some(idx) => (&digits[..idx], &digits[idx + 1..]),
+            None => (digits, &[][..]),
+        };
+        ensure!(
+            !(int_part.is_empty() && frac_part.is_empty())
+                && int_part.iter().chain(frac_part).all(|c| c.is_ascii_digit()),
+            invalid()
+        );
+
+        let scale = decimal_type.scale as usize;
+        let overflow = || DecimalOverflow {
+            value: s,
+            decimal_type,
+        };
+        let mut value: i128 = 0;
+        let kept_digits = int_part
+            .iter()
+            .chain(frac_part)
+            .take(int_part.len() + scale);
+        for c in kept_digits {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((c - b'0') as i128))
+                .with_context(overflow)?;
+        }
+        // Pad the missing fractional digits.
+        for _ in frac_part.len()..scale {
+            value = value.checked_mul(10).with_context(overflow)?;
+        }
+        // Round by the first truncated digit.
+        if frac_part.get(scale).map(|c| *c >= b'5').unwrap_or(false) {
+            value = value.checked_add(1).with_context(overflow)?;
+        }
+
+        let value = if negative { -value } else { value };
+        Self::try_new(value, decimal_type)
+    }
+
+    pub fn from_i64(v: i64, decimal_type: DecimalType) -> Result<Self> {
+        let value = (v as i128)
+            .checked_mul(10i128.pow(decimal_type.scale as u32))
+            .with_context(|| DecimalOverflow {
+                value: v.to_string(),
+                decimal_type,
+            })?;
+        Self::try_new(value, decimal_type)
+    }
+
+    pub fn from_f64(v: f64, decimal_type: DecimalType) -> Result<Self> {
+        ensure!(
+            v.is_finite(),
+            InvalidDecimal {
+                value: v.to_string()
+            }
+        );
+        let value = (v * 10f64.powi(decimal_type.scale as i32)).round();
+        ensure!(
+            value.abs() < decimal_type.max_value() as f64,
+            DecimalOverflow {
+                value: v.to_string(),
+                decimal_type,
+            }
+        );
+
+        Self::try_new(value as i128, decimal_type)
+    }
+
+    /// The unscaled value.
+    #[inline]
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+
+    #[inline]
+    pub fn decimal_type(&self) -> DecimalType {
+        self.decimal_type
+    }
+
+    /// Convert the decimal into another type, the extra fractional digits are
+    /// rounded half away from zero.
+    pub fn rescale(&self, decimal_type: DecimalType) -> Result<Self> {
+        if self.decimal_type == decimal_type {
+            return Ok(*self);
+        }
+
+        let overflow = || DecimalOverflow {
+            value: self.to_string(),
+            decimal_type,
+        };
+        let diff = decimal_type.scale as i32 - self.decimal_type.scale as i32;
+        let value = if diff >= 0 {
+            self.value
+                .checked_mul(10i128.pow(diff as u32))
+                .with_context(overflow)?
+        } else {
+            let divisor = 10i128.pow((-diff) as u32);
+            let (quotient, remainder) = (self.value / divisor, self.value % divisor);
+            if remainder.abs() * 2 >= divisor {
+                quotient + self.value.signum()
+            } else {
+                quotient
+            }
+        };
+
+        Self::try_new(value, decimal_type)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.decimal_type.scale as i32)
+    }
+
+    /// The unscaled value and scale without the trailing zeros of the
+    /// fractional part, which are the same for the equal decimals.
+    fn normalize(&self) -> (i128, i32) {
+        if self.value == 0 {
+            return (0, 0);
+        }
+
+        let (mut value, mut scale) = (self.value, self.decimal_type.scale as i32);
+        while value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+        (value, scale)
+    }
+}
+
+/// Compare `value * 10^exp` with `other`.
+fn cmp_rescaled(value: i128, exp: u32, other: i128) -> Ordering {
+    if value == 0 {
+        return 0.cmp(&other);
+    }
+
+    match 10i128.checked_pow(exp).and_then(|v| value.checked_mul(v)) {
+        Some(rescaled) => rescaled.cmp(&other),
+        // The rescaled value overflowing i128 is out of the range of `other`.
+        None => value.cmp(&0),
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (scale, other_scale) = (
+            self.decimal_type.scale as i32,
+            other.decimal_type.scale as i32,
+        );
+        match scale.cmp(&other_scale) {
+            Ordering::Equal => self.value.cmp(&other.value),
+            Ordering::Less => cmp_rescaled(self.value, other_scale.abs_diff(scale), other.value),
+            Ordering::Greater => {
+                cmp_rescaled(other.value, scale.abs_diff(other_scale), self.value).reverse()
+            }
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalize().hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = self.decimal_type.scale as usize;
+        let sign = if self.value < 0 { "-" } else { "" };
+        let digits = self.value.unsigned_abs().to_string();
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        write!(f, "{sign}{int_part}.{frac_part}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_type() {
+        assert!(DecimalType::try_new(10, 2).is_ok());
+        assert!(DecimalType::try_new(38, 38).is_ok());
+        assert!(DecimalType::try_new(0, 0).is_err());
+        assert!(DecimalType::try_new(39, 2).is_err());
+        assert!(DecimalType::try_new(5, 6).is_err());
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let decimal_type = DecimalType::try_new(10, 2).unwrap();
+        let cases = [
+            ("123.45", "123.45", 12345),
+            ("-123.45", "-123.45", -12345),
+            ("0.5", "0.50", 50),
+            ("-.5", "-0.50", -50),
+            ("7", "7.00", 700),
+            ("+1.005", "1.01", 101),
+            ("-1.004", "-1.00", -100),
+            ("12345678.99", "12345678.99", 1234567899),
+        ];
+        for (input, output, value) in cases {
+            let decimal = Decimal::parse(input, decimal_type).unwrap();
+            assert_eq!(decimal.value(), value, "input:{input}");
+            assert_eq!(decimal.to_string(), output, "input:{input}");
+        }
+
+        for input in ["", ".", "1.2.3", "abc", "1e3", "123456789.1"] {
+            assert!(
+                Decimal::parse(input, decimal_type).is_err(),
+                "input:{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rescale_and_compare() {
+        let decimal = Decimal::parse("-12.345", DecimalType::try_new(10, 3).unwrap()).unwrap();
+        let rescaled = decimal
+            .rescale(DecimalType::try_new(10, 1).unwrap())
+            .unwrap();
+        assert_eq!(rescaled.to_string(), "-12.3");
+        let rescaled = decimal
+            .rescale(DecimalType::try_new(10, 5).unwrap())
+            .unwrap();
+        assert_eq!(rescaled.to_string(), "-12.34500");
+        assert!(decimal
+            .rescale(DecimalType::try_new(4, 3).unwrap())
+            .is_err());
+
+        let larger = Decimal::parse("-12.3", DecimalType::try_new(10, 1).unwrap()).unwrap();
+        assert!(decimal < larger);
+        assert_eq!(
+            Decimal::from_f64(2.5, DecimalType::try_new(5, 1).unwrap())
+                .unwrap()
+                .to_string(),
+            "2.5"
+        );
+        assert_eq!(
+            Decimal::from_i64(-3, DecimalType::try_new(5, 2).unwrap())
+                .unwrap()
+                .to_string(),
+            "-3.00"
+        );
+    }
+
+    #[test]
+    fn test_compare_different_scales() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |decimal: &Decimal| {
+            let mut hasher = DefaultHasher::new();
+            decimal.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let a = Decimal::parse("1.50", DecimalType::try_new(10, 2).unwrap()).unwrap();
+        let b = Decimal::parse("1.5", DecimalType::try_new(5, 1).unwrap()).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        let zero = Decimal::parse("0", DecimalType::try_new(5, 0).unwrap()).unwrap();
+        assert_eq!(zero, Decimal::default());
+        assert_eq!(hash(&zero), hash(&Decimal::default()));
+
+        // The values differ in the digits beyond the precision of f64.
+        let a = Decimal::new_unchecked(10i128.pow(37) + 1, DecimalType::try_new(38, 37).unwrap());
+        let b = Decimal::new_unchecked(10, DecimalType::try_new(38, 1).unwrap());
+        assert!(a > b);
+        assert!(b < a);
+        assert_ne!(a, b);
+
+        // Rescaling the one with the smaller scale overflows i128.
+        let large =
+            Decimal::new_unchecked(10i128.pow(38) - 1, DecimalType::try_new(38, 0).unwrap());
+        let small = Decimal::new_unchecked(1, DecimalType::try_new(38, 38).unwrap());
+        assert!(large > small);
+        let negative_large = Decimal::new_unchecked(-large.value(), large.decimal_type());
+        assert!(negative_large < small);
+    }
+}
@@ -0,0 +1,341 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Json type and path utilities
+
+use std::fmt;
+
+use serde::de::IgnoredAny;
+use serde_json::Value;
+use snafu::{Backtrace, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid json, err:{source}"))]
+    InvalidJson { source: serde_json::Error },
+
+    #[snafu(display("Invalid json path, path:{path}, msg:{msg}.\nBacktrace:\n{backtrace}"))]
+    InvalidPath {
+        path: String,
+        msg: String,
+        backtrace: Backtrace,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Ensure the `s` is a valid json document.
+pub fn validate(s: &str) -> Result<()> {
+    serde_json::from_str::<IgnoredAny>(s).context(InvalidJson)?;
+
+    Ok(())
+}
+
+pub fn parse(s: &str) -> Result<Value> {
+    serde_json::from_str(s).context(InvalidJson)
+}
+
+/// Format the scalar json value as a plain string, e.g. the string is returned
+/// without quotes. Returns None for the json null.
+pub fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(v) => Some(v.clone()),
+        v => Some(v.to_string()),
+    }
+}
+
+/// Whether the `candidate` is contained in the `target`, following the
+/// semantics of `JSON_CONTAINS` in MySQL:
+/// - A scalar is contained in a scalar if they are equal;
+/// - A value is contained in an array if it is contained in some element of the
+///   array, and an array is contained in an array if all its elements are
+///   contained;
+/// - An object is contained in an object if every key of the candidate exists
+///   in the target and the value is contained in the value of the target.
+pub fn contains(target: &Value, candidate: &Value) -> bool {
+    match (target, candidate) {
+        (Value::Object(target), Value::Object(candidate)) => candidate.iter().all(|(k, v)| {
+            target
+                .get(k)
+                .map(|target_v| contains(target_v, v))
+                .unwrap_or(false)
+        }),
+        (Value::Array(target), Value::Array(candidate)) => candidate
+            .iter()
+            .all(|v| target.iter().any(|target_v| contains(target_v, v))),
+        (Value::Array(target), candidate) => target.iter().any(|v| contains(v, candidate)),
+        (target, candidate) => target == candidate,
+    }
+}
+
+/// The filter key of a scalar leaf in the json document, which is used to
+/// index the `json_extract_string(column, path) = value` predicate.
+pub fn leaf_key(path: &JsonPath, value: &str) -> String {
+    format!("{path}={value}")
+}
+
+/// Visit all the non-null scalar leaves of the json document with their paths
+/// and values formatted by [value_to_string].
+pub fn for_each_leaf<F>(value: &Value, mut f: F)
+where
+    F: FnMut(&JsonPath, &str),
+{
+    let mut path = JsonPath::root();
+    visit_leaves(value, &mut path, &mut f);
+}
+
+fn visit_leaves<F>(value: &Value, path: &mut JsonPath, f: &mut F)
+where
+    F: FnMut(&JsonPath, &str),
+{
+    match value {
+        Value::Null => (),
+        Value::Object(obj) => {
+            for (k, v) in obj {
+                path.legs.push(PathLeg::Key(k.clone()));
+                visit_leaves(v, path, f);
+                path.legs.pop();
+            }
+        }
+        Value::Array(arr) => {
+            for (i, v) in arr.iter().enumerate() {
+                path.legs.push(PathLeg::Index(i));
+                visit_leaves(v, path, f);
+                path.legs.pop();
+            }
+        }
+        v => {
+            if let Some(s) = value_to_string(v) {
+                f(path, &s);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathLeg {
+    Key(String),
+    Index(usize),
+}
+
+/// Path to a value in the json document, in the MySQL style, e.g.
+/// `$.a."b c"[0]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    legs: Vec<PathLeg>,
+}
+
+impl JsonPath {
+    pub fn root() -> Self {
+        Self { legs: Vec::new() }
+    }
+
+    pub fn parse(path: &str) -> Result<Self> {
+        let invalid = |msg: &str| {
+            InvalidPath {
+                path,
+                msg: msg.to_string(),
+            }
+            .fail()
+        };
+
+        let rest = match path.trim().strip_prefix('$') {
+            Some(v) => v,
+            None => return invalid("path must start with $"),
+        };
+
+        let mut legs = Vec::new();
+        let mut chars = rest.char_indices().peekable();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '.' => match chars.peek() {
+                    Some((start, '"')) => {
+                        let start = *start;
+                        let mut end = None;
+                        let mut escaped = false;
+                        chars.next();
+                        for (i, c) in chars.by_ref() {
+                            match c {
+                                '\\' if !escaped => escaped = true,
+                                '"' if !escaped => {
+                                    end = Some(i);
+                                    break;
+                                }
+                                _ => escaped = false,
+                            }
+                        }
+                        let end = match end {
+                            Some(v) => v,
+                            None => return invalid("unterminated quoted key"),
+                        };
+                        let key: String = match serde_json::from_str(&rest[start..=end]) {
+                            Ok(v) => v,
+                            Err(_) => return invalid("invalid quoted key"),
+                        };
+                        legs.push(PathLeg::Key(key));
+                    }
+                    _ => {
+                        let start = idx + 1;
+                        let mut end = rest.len();
+                        while let Some((i, c)) = chars.peek() {
+                            if *c == '.' || *c == '[' {
+                                end = *i;
+                                break;
+                            }
+                            chars.next();
+                        }
+                        let key = &rest[start..end];
+                        if key.is_empty() || !key.chars().all(is_ident_char) {
+                            return invalid("invalid key");
+                        }
+                        legs.push(PathLeg::Key(key.to_string()));
+                    }
+                },
+                '[' => {
+                    let start = idx + 1;
+                    let mut end = None;
+                    for (i, c) in chars.by_ref() {
+                        if c == ']' {
+                            end = Some(i);
+                            break;
+                        }
+                    }
+                    let index = end.and_then(|end| rest[start..end].parse::<usize>().ok());
+                    match index {
+                        Some(v) => legs.push(PathLeg::Index(v)),
+                        None => return invalid("invalid array index"),
+                    }
+                }
+                _ => return invalid("unexpected character"),
+            }
+        }
+
+        Ok(Self { legs })
+    }
+
+    /// Returns the value at the path, None if the path doesn't exist.
+    pub fn extract<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.legs.iter().try_fold(value, |v, leg| match leg {
+            PathLeg::Key(k) => v.as_object()?.get(k),
+            PathLeg::Index(i) => v.as_array()?.get(*i),
+        })
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("$")?;
+        for leg in &self.legs {
+            match leg {
+                PathLeg::Key(k) if !k.is_empty() && k.chars().all(is_ident_char) => {
+                    write!(f, ".{k}")?
+                }
+                PathLeg::Key(k) => write!(f, ".{}", Value::String(k.clone()))?,
+                PathLeg::Index(i) => write!(f, "[{i}]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(r#"{"a": [1, 2, {"b": null}]}"#).is_ok());
+        assert!(validate("1.5").is_ok());
+        assert!(validate(r#"{"a": }"#).is_err());
+        assert!(validate("").is_err());
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let cases = [
+            ("$", "$"),
+            ("$.a", "$.a"),
+            ("$.a.b[1]", "$.a.b[1]"),
+            (r#"$."a b"[0].c"#, r#"$."a b"[0].c"#),
+            (r#"$."a""#, "$.a"),
+        ];
+        for (path, expect) in cases {
+            let parsed = JsonPath::parse(path).unwrap();
+            assert_eq!(expect, parsed.to_string());
+            assert_eq!(parsed, JsonPath::parse(expect).unwrap());
+        }
+
+        for path in ["", "a", "$.", "$[a]", "$[1", r#"$."a"#, "$a", "$.a b"] {
+            assert!(JsonPath::parse(path).is_err(), "path:{path}");
+        }
+    }
+
+    #[test]
+    fn test_extract() {
+        let doc = json!({"a": {"b": [1, "x", {"c": true}]}, "d e": null});
+        let cases = [
+            ("$", Some(doc.clone())),
+            ("$.a.b[0]", Some(json!(1))),
+            ("$.a.b[1]", Some(json!("x"))),
+            ("$.a.b[2].c", Some(json!(true))),
+            (r#"$."d e""#, Some(Value::Null)),
+            ("$.a.b[3]", None),
+            ("$.a.c", None),
+            ("$.a.b.c", None),
+        ];
+        for (path, expect) in cases {
+            let path = JsonPath::parse(path).unwrap();
+            assert_eq!(expect.as_ref(), path.extract(&doc), "path:{path}");
+        }
+
+        assert_eq!(Some("x".to_string()), value_to_string(&json!("x")));
+        assert_eq!(Some("1.5".to_string()), value_to_string(&json!(1.5)));
+        assert_eq!(Some("[1]".to_string()), value_to_string(&json!([1])));
+        assert_eq!(None, value_to_string(&Value::Null));
+    }
+
+    #[test]
+    fn test_contains() {
+        let target = json!({"a": 1, "b": [1, 2, {"c": "x"}]});
+        assert!(contains(&target, &json!({"a": 1})));
+        assert!(contains(&target, &json!({"b": [2, 1]})));
+        assert!(contains(&target, &json!({"b": 2})));
+        assert!(contains(&target, &json!({"b": [{"c": "x"}]})));
+        assert!(!contains(&target, &json!({"a": 2})));
+        assert!(!contains(&target, &json!({"c": 1})));
+        assert!(!contains(&target, &json!(1)));
+        assert!(contains(&json!([1, [2, 3]]), &json!(3)));
+    }
+
+    #[test]
+    fn test_for_each_leaf() {
+        let doc = json!({"a": {"b": [1, "x"]}, "c d": true, "e": null});
+        let mut keys = Vec::new();
+        for_each_leaf(&doc, |path, value| keys.push(leaf_key(path, value)));
+        keys.sort();
+
+        assert_eq!(vec![r#"$."c d"=true"#, "$.a.b[0]=1", "$.a.b[1]=x"], keys);
+    }
+}
@@ -17,12 +17,15 @@
 
 //! Contains common types
 
+pub mod array;
 pub mod bitset;
 pub mod column;
 pub mod column_block;
 pub mod column_schema;
 pub mod datum;
+pub mod decimal;
 pub(crate) mod hex;
+pub mod json;
 pub mod projected_schema;
 pub mod record_batch;
 pub mod request_id;
@@ -54,6 +57,7 @@ pub const COMPRESSION: &str = "compression";
 pub const STORAGE_FORMAT: &str = "storage_format";
 pub const MEMTABLE_TYPE: &str = "memtable_type";
 pub const MUTABLE_SEGMENT_SWITCH_THRESHOLD: &str = "mutable_segment_switch_threshold";
+pub const TIME_TRAVEL_RETENTION: &str = "time_travel_retention";
 
 #[cfg(any(test, feature = "test"))]
 pub mod tests;
@@ -408,12 +408,8 @@ impl FetchedRecordBatch {
                 }
                 None => {
                     // Need to push row with specific type.
-                    let null_block = ColumnBlock::new_null_with_type(
-                        &col_schema.data_type,
-                        num_rows,
-                        col_schema.is_dictionary,
-                    )
-                    .context(CreateColumnBlock)?;
+                    let null_block = ColumnBlock::new_null_with_column_schema(col_schema, num_rows)
+                        .context(CreateColumnBlock)?;
                     column_blocks.push(null_block);
                 }
             }
@@ -589,13 +585,7 @@ impl FetchedRecordBatchBuilder {
         let builders = fetched_schema
             .columns()
             .iter()
-            .map(|column_schema| {
-                ColumnBlockBuilder::with_capacity(
-                    &column_schema.data_type,
-                    0,
-                    column_schema.is_dictionary,
-                )
-            })
+            .map(|column_schema| ColumnBlockBuilder::with_column_schema(column_schema, 0))
             .collect();
         Self {
             fetched_schema,
@@ -612,13 +602,7 @@ impl FetchedRecordBatchBuilder {
         let builders = record_schema
             .columns()
             .iter()
-            .map(|column_schema| {
-                ColumnBlockBuilder::with_capacity(
-                    &column_schema.data_type,
-                    capacity,
-                    column_schema.is_dictionary,
-                )
-            })
+            .map(|column_schema| ColumnBlockBuilder::with_column_schema(column_schema, capacity))
             .collect();
         Self {
             fetched_schema: record_schema,
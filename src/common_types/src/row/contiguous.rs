@@ -18,6 +18,7 @@
 //! Contiguous row.
 
 use std::{
+    borrow::Cow,
     convert::TryInto,
     debug_assert_eq, fmt, mem,
     ops::{Deref, DerefMut},
@@ -28,7 +29,9 @@ use prost::encoding::{decode_varint, encode_varint, encoded_len_varint};
 use snafu::{ensure, Backtrace, Snafu};
 
 use crate::{
+    array::ArrayValue,
     datum::{Datum, DatumKind, DatumView},
+    decimal::{Decimal, DecimalType},
     projected_schema::RowProjector,
     row::{
         bitset::{BitSet, RoBitSet},
@@ -360,7 +363,7 @@ impl<'a, T: RowBuffer + 'a> ContiguousRowWriter<'a, T> {
                 Self::write_slice_to_offset(inner, next_string_offset, value_buf);
                 Self::write_slice_to_offset(inner, next_string_offset, v);
             }
-            Datum::String(v) => {
+            Datum::String(v) | Datum::Json(v) => {
                 ensure!(
                     *next_string_offset <= MAX_ROW_LEN,
                     StringTooLong {
@@ -378,6 +381,28 @@ impl<'a, T: RowBuffer + 'a> ContiguousRowWriter<'a, T> {
                 Self::write_slice_to_offset(inner, next_string_offset, value_buf);
                 Self::write_slice_to_offset(inner, next_string_offset, v.as_bytes());
             }
+            Datum::Array(v) => {
+                ensure!(
+                    *next_string_offset <= MAX_ROW_LEN,
+                    StringTooLong {
+                        len: *next_string_offset
+                    }
+                );
+                // Encode the offset of the encoded array as a u32.
+                let value_buf = (*next_string_offset as u32).to_ne_bytes();
+                Self::write_slice_to_offset(inner, offset, &value_buf);
+
+                // The array is encoded as bytes, whose length is encoded as a varint.
+                let bytes = v.to_bytes();
+                ensure!(
+                    bytes.len() <= MAX_STRING_LEN,
+                    StringTooLong { len: bytes.len() }
+                );
+                let mut buf = [0; 4];
+                let value_buf = Self::encode_varint(bytes.len() as u32, &mut buf);
+                Self::write_slice_to_offset(inner, next_string_offset, value_buf);
+                Self::write_slice_to_offset(inner, next_string_offset, &bytes);
+            }
             Datum::UInt64(v) => {
                 let value_buf = v.to_ne_bytes();
                 Self::write_slice_to_offset(inner, offset, &value_buf);
@@ -419,6 +444,16 @@ impl<'a, T: RowBuffer + 'a> ContiguousRowWriter<'a, T> {
                 let value_buf = v.to_ne_bytes();
                 Self::write_slice_to_offset(inner, offset, &value_buf);
             }
+            Datum::Decimal(v) => {
+                let value_buf = v.value().to_ne_bytes();
+                Self::write_slice_to_offset(inner, offset, &value_buf);
+                let decimal_type = v.decimal_type();
+                Self::write_slice_to_offset(
+                    inner,
+                    offset,
+                    &[decimal_type.precision(), decimal_type.scale() as u8],
+                );
+            }
         }
 
         Ok(())
@@ -587,7 +622,9 @@ pub(crate) fn byte_size_of_datum(kind: &DatumKind) -> usize {
         DatumKind::Double => mem::size_of::<f64>(),
         DatumKind::Float => mem::size_of::<f32>(),
         // The size of offset.
-        DatumKind::Varbinary | DatumKind::String => Encoding::size_of_offset(),
+        DatumKind::Varbinary | DatumKind::String | DatumKind::Json | DatumKind::Array => {
+            Encoding::size_of_offset()
+        }
         DatumKind::UInt64 => mem::size_of::<u64>(),
         DatumKind::UInt32 => mem::size_of::<u32>(),
         DatumKind::UInt16 => mem::size_of::<u16>(),
@@ -599,6 +636,8 @@ pub(crate) fn byte_size_of_datum(kind: &DatumKind) -> usize {
         DatumKind::Boolean => mem::size_of::<bool>(),
         DatumKind::Date => mem::size_of::<i32>(),
         DatumKind::Time => mem::size_of::<i64>(),
+        // The value, precision and scale.
+        DatumKind::Decimal => mem::size_of::<i128>() + 2,
     }
 }
 
@@ -640,6 +679,17 @@ fn must_read_view<'a>(
             let v = unsafe { str::from_utf8_unchecked(bytes) };
             DatumView::String(v)
         }
+        DatumKind::Json => {
+            let bytes = must_read_bytes(datum_buf, string_buf);
+            let v = unsafe { str::from_utf8_unchecked(bytes) };
+            DatumView::Json(v)
+        }
+        DatumKind::Array => {
+            let bytes = must_read_bytes(datum_buf, string_buf);
+            // The array is encoded by the writer, so it must be valid.
+            let v = ArrayValue::decode(bytes).expect("encoded array should be valid");
+            DatumView::Array(Cow::Owned(v))
+        }
         DatumKind::UInt64 => {
             let value_buf = datum_buf[..mem::size_of::<u64>()].try_into().unwrap();
             let v = u64::from_ne_bytes(value_buf);
@@ -683,6 +733,14 @@ fn must_read_view<'a>(
             let v = i64::from_ne_bytes(value_buf);
             DatumView::Time(v)
         }
+        DatumKind::Decimal => {
+            let value_size = mem::size_of::<i128>();
+            let value_buf = datum_buf[..value_size].try_into().unwrap();
+            let v = i128::from_ne_bytes(value_buf);
+            let decimal_type =
+                DecimalType::new_unchecked(datum_buf[value_size], datum_buf[value_size + 1] as i8);
+            DatumView::Decimal(Decimal::new_unchecked(v, decimal_type))
+        }
     }
 }
 
@@ -719,7 +777,8 @@ mod tests {
     use super::*;
     use crate::{
         projected_schema::ProjectedSchema,
-        tests::{build_rows, build_schema},
+        row::RowBuilder,
+        tests::{build_rows, build_schema, build_schema_with_array, build_schema_with_decimal},
     };
 
     #[test]
@@ -751,6 +810,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_contiguous_read_write_decimal() {
+        let schema = build_schema_with_decimal();
+        let index_in_writer = IndexInWriterSchema::for_same_schema(schema.num_columns());
+        let decimal_type = DecimalType::try_new(38, 3).unwrap();
+        let rows = [Some("-123.456"), None, Some("99999999.99")]
+            .into_iter()
+            .map(|value| {
+                let datum = value
+                    .map(|v| Datum::Decimal(Decimal::parse(v, decimal_type).unwrap()))
+                    .unwrap_or(Datum::Null);
+                RowBuilder::new(&schema)
+                    .append_datum(Datum::Varbinary(b"key".to_vec().into()))
+                    .unwrap()
+                    .append_datum(Datum::Timestamp(Timestamp::new(1000)))
+                    .unwrap()
+                    .append_datum(datum)
+                    .unwrap()
+                    .finish()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let expects = [Some("-123.46"), None, Some("99999999.99")];
+
+        let mut buf = Vec::new();
+        for (row, expect) in rows.iter().zip(expects) {
+            let mut writer = ContiguousRowWriter::new(&mut buf, &schema, &index_in_writer);
+            writer.write_row(row).unwrap();
+
+            let reader = ContiguousRowReader::try_new(&buf, &schema).unwrap();
+            let view = reader.datum_view_at(2, &DatumKind::Decimal);
+            assert_eq!(row[2].as_view(), view);
+            assert_eq!(expect, view.as_decimal().map(|v| v.to_string()).as_deref());
+        }
+    }
+
+    #[test]
+    fn test_contiguous_read_write_array() {
+        let schema = build_schema_with_array();
+        let index_in_writer = IndexInWriterSchema::for_same_schema(schema.num_columns());
+        let values = [
+            Some(vec![Datum::Int32(1), Datum::Null, Datum::Int32(-3)]),
+            None,
+            Some(vec![]),
+        ];
+        let rows = values
+            .into_iter()
+            .map(|value| {
+                let datum = value
+                    .map(|v| Datum::Array(ArrayValue::try_new(DatumKind::Int32, v).unwrap()))
+                    .unwrap_or(Datum::Null);
+                RowBuilder::new(&schema)
+                    .append_datum(Datum::Varbinary(b"key".to_vec().into()))
+                    .unwrap()
+                    .append_datum(Datum::Timestamp(Timestamp::new(1000)))
+                    .unwrap()
+                    .append_datum(datum)
+                    .unwrap()
+                    .finish()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let mut buf = Vec::new();
+        for row in &rows {
+            let mut writer = ContiguousRowWriter::new(&mut buf, &schema, &index_in_writer);
+            writer.write_row(row).unwrap();
+
+            let reader = ContiguousRowReader::try_new(&buf, &schema).unwrap();
+            let view = reader.datum_view_at(2, &DatumKind::Array);
+            assert_eq!(row[2].as_view(), view);
+            if let Some(v) = view.as_array() {
+                assert_eq!(DatumKind::Int64, v.element_kind());
+            }
+        }
+    }
+
     #[test]
     fn test_contiguous_read_write_with_different_write_schema() {
         let schema = build_schema();
@@ -22,11 +22,13 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use snafu::{ensure, Backtrace, OptionExt, Snafu};
+use snafu::{ensure, Backtrace, OptionExt, ResultExt, Snafu};
 
 use crate::{
+    array,
     column_schema::{ColumnId, ColumnSchema},
     datum::{Datum, DatumKind, DatumView},
+    decimal::{self, DecimalType},
     record_batch::FetchedRecordBatch,
     schema::{RecordSchemaWithKey, Schema},
     time::Timestamp,
@@ -93,6 +95,46 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display(
+        "Column decimal type mismatch, name:{}, expect:{}, given:{}.\nBacktrace:\n{}",
+        column,
+        expect,
+        given,
+        backtrace
+    ))]
+    DecimalTypeMismatch {
+        column: String,
+        expect: DecimalType,
+        given: DecimalType,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to convert decimal, column:{}, err:{}", column, source))]
+    ConvertDecimal {
+        column: String,
+        source: decimal::Error,
+    },
+
+    #[snafu(display(
+        "Column element type mismatch, name:{}, expect:{}, given:{}.\nBacktrace:\n{}",
+        column,
+        expect,
+        given,
+        backtrace
+    ))]
+    ElementTypeMismatch {
+        column: String,
+        expect: DatumKind,
+        given: DatumKind,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to convert array, column:{}, err:{}", column, source))]
+    ConvertArray {
+        column: String,
+        source: array::Error,
+    },
+
     #[snafu(display("Missing columns to build row.\nBacktrace:\n{}", backtrace))]
     MissingColumns { backtrace: Backtrace },
 
@@ -505,6 +547,28 @@ pub fn check_datum_type(datum: &Datum, column_schema: &ColumnSchema) -> Result<(
                 given: datum.kind(),
             }
         );
+
+        if let (Datum::Decimal(v), Some(expect)) = (datum, column_schema.decimal_type) {
+            ensure!(
+                v.decimal_type() == expect,
+                DecimalTypeMismatch {
+                    column: &column_schema.name,
+                    expect,
+                    given: v.decimal_type(),
+                }
+            );
+        }
+
+        if let (Datum::Array(v), Some(expect)) = (datum, column_schema.element_type) {
+            ensure!(
+                v.element_kind() == expect,
+                ElementTypeMismatch {
+                    column: &column_schema.name,
+                    expect,
+                    given: v.element_kind(),
+                }
+            );
+        }
     }
 
     Ok(())
@@ -525,8 +589,11 @@ impl<'a> RowBuilder<'a> {
         }
     }
 
-    /// Append a datum into the row
+    /// Append a datum into the row, the decimal datum is converted into the
+    /// decimal type of the column, and the array datum is converted into the
+    /// element type of the column.
     pub fn append_datum(mut self, datum: Datum) -> Result<Self> {
+        let datum = self.cast_to_column_type(datum)?;
         self.check_datum(&datum)?;
 
         self.cols.push(datum);
@@ -534,6 +601,30 @@ impl<'a> RowBuilder<'a> {
         Ok(self)
     }
 
+    fn cast_to_column_type(&self, datum: Datum) -> Result<Datum> {
+        // The out of bound column is checked later by `check_datum`.
+        let column = match self.schema.columns().get(self.cols.len()) {
+            Some(column) => column,
+            None => return Ok(datum),
+        };
+
+        match (datum, column.decimal_type, column.element_type) {
+            (Datum::Decimal(v), Some(decimal_type), _) => v
+                .rescale(decimal_type)
+                .map(Datum::Decimal)
+                .context(ConvertDecimal {
+                    column: &column.name,
+                }),
+            (Datum::Array(v), _, Some(element_type)) => v
+                .cast_to(element_type)
+                .map(Datum::Array)
+                .context(ConvertArray {
+                    column: &column.name,
+                }),
+            (datum, ..) => Ok(datum),
+        }
+    }
+
     /// Check whether the datum is valid
     fn check_datum(&self, datum: &Datum) -> Result<()> {
         let index = self.cols.len();
@@ -112,6 +112,13 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display(
+        "Key column should not have aggregation, name:{}.\nBacktrace:\n{}",
+        name,
+        backtrace
+    ))]
+    KeyColumnAggregation { name: String, backtrace: Backtrace },
+
     #[snafu(display(
         "Timestamp key column already exists, timestamp_column:{}, given:{}.\nBacktrace:\n{}",
         timestamp_column,
@@ -1157,6 +1164,10 @@ impl Builder {
                     kind: column.data_type,
                 }
             );
+            ensure!(
+                column.aggregation.is_none(),
+                KeyColumnAggregation { name: &column.name }
+            );
         }
 
         ensure!(
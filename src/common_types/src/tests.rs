@@ -19,8 +19,9 @@ use bytes_ext::Bytes;
 use sqlparser::ast::{BinaryOperator, Expr, Value};
 
 use crate::{
-    column_schema,
+    column_schema::{self, ColumnAggregation},
     datum::{Datum, DatumKind},
+    decimal::DecimalType,
     projected_schema::{ProjectedSchema, RowProjector},
     record_batch::{FetchedRecordBatch, FetchedRecordBatchBuilder},
     row::{
@@ -156,6 +157,106 @@ pub fn build_schema() -> Schema {
     base_schema_builder().build().unwrap()
 }
 
+/// Build a schema for testing:
+/// key1(varbinary), key2(timestamp), field1(double sum), field2(string),
+/// field3(date max), field4(time min)
+pub fn build_schema_with_aggregation() -> Schema {
+    let aggregations = [
+        ("field1", DatumKind::Double, Some(ColumnAggregation::Sum)),
+        ("field2", DatumKind::String, None),
+        ("field3", DatumKind::Date, Some(ColumnAggregation::Max)),
+        ("field4", DatumKind::Time, Some(ColumnAggregation::Min)),
+    ];
+    let mut builder = schema::Builder::new()
+        .auto_increment_column_id(true)
+        .add_key_column(
+            column_schema::Builder::new("key1".to_string(), DatumKind::Varbinary)
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .add_key_column(
+            column_schema::Builder::new("key2".to_string(), DatumKind::Timestamp)
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .primary_key_indexes(vec![0, 1]);
+    for (name, kind, aggregation) in aggregations {
+        builder = builder
+            .add_normal_column(
+                column_schema::Builder::new(name.to_string(), kind)
+                    .is_nullable(true)
+                    .aggregation(aggregation)
+                    .build()
+                    .expect("should succeed build column schema"),
+            )
+            .unwrap();
+    }
+
+    builder.build().unwrap()
+}
+
+/// Build a schema for testing:
+/// key1(varbinary), key2(timestamp), field1(decimal(10, 2))
+pub fn build_schema_with_decimal() -> Schema {
+    schema::Builder::new()
+        .auto_increment_column_id(true)
+        .add_key_column(
+            column_schema::Builder::new("key1".to_string(), DatumKind::Varbinary)
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .add_key_column(
+            column_schema::Builder::new("key2".to_string(), DatumKind::Timestamp)
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .add_normal_column(
+            column_schema::Builder::new("field1".to_string(), DatumKind::Decimal)
+                .is_nullable(true)
+                .decimal_type(Some(DecimalType::try_new(10, 2).unwrap()))
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .primary_key_indexes(vec![0, 1])
+        .build()
+        .unwrap()
+}
+
+/// Build a schema for testing:
+/// key1(varbinary), key2(timestamp), field1(array<int64>)
+pub fn build_schema_with_array() -> Schema {
+    schema::Builder::new()
+        .auto_increment_column_id(true)
+        .add_key_column(
+            column_schema::Builder::new("key1".to_string(), DatumKind::Varbinary)
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .add_key_column(
+            column_schema::Builder::new("key2".to_string(), DatumKind::Timestamp)
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .add_normal_column(
+            column_schema::Builder::new("field1".to_string(), DatumKind::Array)
+                .is_nullable(true)
+                .element_type(Some(DatumKind::Int64))
+                .build()
+                .expect("should succeed build column schema"),
+        )
+        .unwrap()
+        .primary_key_indexes(vec![0, 1])
+        .build()
+        .unwrap()
+}
+
 /// Build a schema for testing:
 /// key1(varbinary), key2(timestamp),
 /// field1(int64, default 10),
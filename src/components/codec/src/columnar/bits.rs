@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Bit level writer and reader used by the compact value encodings.
+
+use snafu::ensure;
+
+use crate::columnar::{NotEnoughBytes, Result};
+
+/// Write bits into bytes from the most significant bit.
+#[derive(Debug, Default)]
+pub(crate) struct BitWriter {
+    buf: Vec<u8>,
+    /// The number of unused bits in the last byte.
+    free_bits: u32,
+}
+
+impl BitWriter {
+    #[inline]
+    pub fn write_bit(&mut self, bit: bool) {
+        self.write_bits(bit as u64, 1);
+    }
+
+    /// Write the lowest `num_bits` bits of the `value`.
+    pub fn write_bits(&mut self, value: u64, mut num_bits: u32) {
+        debug_assert!(num_bits <= 64);
+
+        while num_bits > 0 {
+            if self.free_bits == 0 {
+                self.buf.push(0);
+                self.free_bits = 8;
+            }
+
+            let n = num_bits.min(self.free_bits);
+            let bits = ((value >> (num_bits - n)) & ((1 << n) - 1)) as u8;
+            let last = self.buf.last_mut().unwrap();
+            *last |= bits << (self.free_bits - n);
+            self.free_bits -= n;
+            num_bits -= n;
+        }
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+/// Read bits written by [`BitWriter`].
+pub(crate) struct BitReader<'a> {
+    buf: &'a [u8],
+    /// The position of the next bit to read.
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    #[inline]
+    pub fn read_bit(&mut self) -> Result<bool> {
+        self.read_bits(1).map(|v| v == 1)
+    }
+
+    pub fn read_bits(&mut self, mut num_bits: u32) -> Result<u64> {
+        debug_assert!(num_bits <= 64);
+        ensure!(
+            self.pos + num_bits as usize <= self.buf.len() * 8,
+            NotEnoughBytes {
+                len: self.buf.len()
+            }
+        );
+
+        let mut value = 0;
+        while num_bits > 0 {
+            let offset = (self.pos % 8) as u32;
+            let avail = 8 - offset;
+            let n = num_bits.min(avail);
+            let bits = (self.buf[self.pos / 8] as u64 >> (avail - n)) & ((1 << n) - 1);
+            value = (value << n) | bits;
+            self.pos += n as usize;
+            num_bits -= n;
+        }
+
+        Ok(value)
+    }
+
+    /// The number of the bytes consumed so far, including the padding bits of
+    /// the last byte.
+    #[inline]
+    pub fn num_bytes_read(&self) -> usize {
+        (self.pos + 7) / 8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_read_bits() {
+        let cases = [
+            (1, 1),
+            (0, 1),
+            (0b101, 3),
+            (u64::MAX, 64),
+            (0x1234, 13),
+            (7, 8),
+        ];
+
+        let mut writer = BitWriter::default();
+        for (value, num_bits) in cases {
+            writer.write_bits(value, num_bits);
+        }
+        assert_eq!(writer.as_bytes().len(), 12);
+
+        let mut reader = BitReader::new(writer.as_bytes());
+        for (value, num_bits) in cases {
+            assert_eq!(value, reader.read_bits(num_bits).unwrap());
+        }
+        assert_eq!(reader.num_bytes_read(), 12);
+        assert!(reader.read_bits(8).is_err());
+    }
+}
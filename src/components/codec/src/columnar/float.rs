@@ -0,0 +1,239 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Gorilla XOR encoding for the float values.
+
+use bytes_ext::{Buf, BufMut};
+use snafu::{ensure, OptionExt};
+
+use crate::columnar::{
+    bits::{BitReader, BitWriter},
+    InvalidEncodedValues, NotEnoughBytes, Result,
+};
+
+/// The layout for the Gorilla XOR encoded float values:
+/// ```plaintext
+/// +-----------------+----------+
+/// | num_values(u32) | bit data |
+/// +-----------------+----------+
+/// ```
+///
+/// The first value is stored in full, and every following value is stored as
+/// the XOR with the previous one:
+/// - `0`: the value equals the previous one;
+/// - `10` + meaningful bits: the meaningful bits of the XOR fall in the window
+///   of the last stored leading and trailing zeros;
+/// - `11` + leading zeros(5 bits) + meaningful length - 1(6 bits) + meaningful
+///   bits.
+///
+/// Refer to: https://www.vldb.org/pvldb/vol8/p1816-teller.pdf
+struct Encoding;
+
+impl Encoding {
+    const LEADING_ZEROS_BITS: u32 = 5;
+    const MAX_LEADING_ZEROS: u32 = (1 << Self::LEADING_ZEROS_BITS) - 1;
+    const MEANINGFUL_LEN_BITS: u32 = 6;
+    const NUM_VALUES_SIZE: usize = 4;
+
+    /// Encode the bits of the float values whose width is `width`.
+    fn encode<B, I>(buf: &mut B, values: I, width: u32)
+    where
+        B: BufMut,
+        I: Iterator<Item = u64>,
+    {
+        let mut writer = BitWriter::default();
+        let mut num_values: u32 = 0;
+        let mut prev = 0;
+        // The leading and trailing zeros of the last stored window.
+        let mut window: Option<(u32, u32)> = None;
+        for value in values {
+            if num_values == 0 {
+                writer.write_bits(value, width);
+            } else {
+                let xor = value ^ prev;
+                if xor == 0 {
+                    writer.write_bit(false);
+                } else {
+                    writer.write_bit(true);
+                    let leading = (xor.leading_zeros() - (64 - width)).min(Self::MAX_LEADING_ZEROS);
+                    let trailing = xor.trailing_zeros();
+                    match window {
+                        Some((prev_leading, prev_trailing))
+                            if leading >= prev_leading && trailing >= prev_trailing =>
+                        {
+                            writer.write_bit(false);
+                            let len = width - prev_leading - prev_trailing;
+                            writer.write_bits(xor >> prev_trailing, len);
+                        }
+                        _ => {
+                            writer.write_bit(true);
+                            let len = width - leading - trailing;
+                            writer.write_bits(leading as u64, Self::LEADING_ZEROS_BITS);
+                            writer.write_bits((len - 1) as u64, Self::MEANINGFUL_LEN_BITS);
+                            writer.write_bits(xor >> trailing, len);
+                            window = Some((leading, trailing));
+                        }
+                    }
+                }
+            }
+
+            prev = value;
+            num_values += 1;
+        }
+
+        buf.put_u32(num_values);
+        buf.put_slice(writer.as_bytes());
+    }
+
+    fn decode<B, F>(buf: &mut B, width: u32, mut f: F) -> Result<()>
+    where
+        B: Buf,
+        F: FnMut(u64) -> Result<()>,
+    {
+        ensure!(
+            buf.remaining() >= Self::NUM_VALUES_SIZE,
+            NotEnoughBytes {
+                len: buf.remaining()
+            }
+        );
+        let num_values = buf.get_u32();
+        if num_values == 0 {
+            return Ok(());
+        }
+
+        let mut reader = BitReader::new(buf.chunk());
+        let mut prev = reader.read_bits(width)?;
+        f(prev)?;
+
+        let (mut leading, mut trailing) = (0, 0);
+        for _ in 1..num_values {
+            if reader.read_bit()? {
+                if reader.read_bit()? {
+                    leading = reader.read_bits(Self::LEADING_ZEROS_BITS)? as u32;
+                    let len = reader.read_bits(Self::MEANINGFUL_LEN_BITS)? as u32 + 1;
+                    trailing =
+                        width
+                            .checked_sub(leading + len)
+                            .with_context(|| InvalidEncodedValues {
+                                msg: format!("leading zeros:{leading}, meaningful length:{len}"),
+                            })?;
+                }
+                let len = width - leading - trailing;
+                prev ^= reader.read_bits(len)? << trailing;
+            }
+            f(prev)?;
+        }
+
+        buf.advance(reader.num_bytes_read());
+
+        Ok(())
+    }
+}
+
+/// Encode the f64 values in Gorilla XOR encoding.
+pub(crate) fn encode_f64<B, I>(buf: &mut B, values: I)
+where
+    B: BufMut,
+    I: Iterator<Item = f64>,
+{
+    Encoding::encode(buf, values.map(|v| v.to_bits()), 64)
+}
+
+pub(crate) fn decode_f64<B, F>(buf: &mut B, mut f: F) -> Result<()>
+where
+    B: Buf,
+    F: FnMut(f64) -> Result<()>,
+{
+    Encoding::decode(buf, 64, |v| f(f64::from_bits(v)))
+}
+
+/// Encode the f32 values in Gorilla XOR encoding.
+pub(crate) fn encode_f32<B, I>(buf: &mut B, values: I)
+where
+    B: BufMut,
+    I: Iterator<Item = f32>,
+{
+    Encoding::encode(buf, values.map(|v| v.to_bits() as u64), 32)
+}
+
+pub(crate) fn decode_f32<B, F>(buf: &mut B, mut f: F) -> Result<()>
+where
+    B: Buf,
+    F: FnMut(f32) -> Result<()>,
+{
+    Encoding::decode(buf, 32, |v| f(f32::from_bits(v as u32)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_and_decode_f64() {
+        let values = vec![
+            12.0,
+            12.0,
+            24.0,
+            15.5,
+            15.25,
+            -0.0,
+            0.0,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            1.0 / 3.0,
+        ];
+        let mut buf = Vec::new();
+        encode_f64(&mut buf, values.iter().copied());
+
+        let mut decoded = Vec::new();
+        decode_f64(&mut buf.as_slice(), |v| {
+            decoded.push(v);
+            Ok(())
+        })
+        .unwrap();
+        let bits = |vs: &[f64]| vs.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&values), bits(&decoded));
+    }
+
+    #[test]
+    fn test_encode_and_decode_f32() {
+        let values = vec![1.5f32, 1.5, 1.75, f32::NAN, -3.0, f32::MIN, 0.1];
+        let mut buf = Vec::new();
+        encode_f32(&mut buf, values.iter().copied());
+
+        let mut decoded = Vec::new();
+        decode_f32(&mut buf.as_slice(), |v| {
+            decoded.push(v);
+            Ok(())
+        })
+        .unwrap();
+        let bits = |vs: &[f32]| vs.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&values), bits(&decoded));
+    }
+
+    #[test]
+    fn test_compress_repeated_values() {
+        let values = vec![100.0f64; 64];
+        let mut buf = Vec::new();
+        encode_f64(&mut buf, values.into_iter());
+
+        // num_values(4B) + the first value(8B) + 63 bits for the same values.
+        assert_eq!(buf.len(), 4 + 8 + 8);
+    }
+}
@@ -22,8 +22,10 @@
 
 use bytes_ext::{Buf, BufMut, Bytes};
 use common_types::{
+    array::ArrayValue,
     column_schema::ColumnId,
     datum::{Datum, DatumKind, DatumView},
+    decimal::Decimal,
     row::bitset::{BitSet, RoBitSet},
     string::StringBytes,
     time::Timestamp,
@@ -33,8 +35,10 @@ use snafu::{self, ensure, Backtrace, OptionExt, ResultExt, Snafu};
 
 use crate::varint;
 
+mod bits;
 mod bool;
 mod bytes;
+mod float;
 mod number;
 mod timestamp;
 
@@ -94,6 +98,24 @@ pub enum Error {
 
     #[snafu(display("Number operation overflowed, msg:{msg}.\nBacktrace:\n{backtrace}"))]
     Overflow { msg: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid value encoding flag:{flag}.\nBacktrace:\n{backtrace}"))]
+    InvalidValueEncoding { flag: u8, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Value encoding is not supported by the datum kind, value_encoding:{value_encoding:?}, datum_kind:{datum_kind:?}.\nBacktrace:\n{backtrace}"
+    ))]
+    UnsupportedValueEncoding {
+        value_encoding: ValueEncoding,
+        datum_kind: DatumKind,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid encoded values, msg:{msg}.\nBacktrace:\n{backtrace}"))]
+    InvalidEncodedValues { msg: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to decode array, err:{source}"))]
+    DecodeArray { source: common_types::array::Error },
 }
 
 define_result!(Error);
@@ -149,14 +171,50 @@ pub struct ColumnarEncoder {
     bytes_compress_threshold: usize,
 }
 
+/// The encoding of the non-null values in a column.
+///
+/// It is not allowed to be modified and only allowed to be appended with a new
+/// variant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ValueEncoding {
+    /// The default encoding of the datum kind.
+    #[default]
+    Plain = 0,
+    /// Gorilla XOR encoding, only for `Double` and `Float`.
+    Gorilla = 1,
+    /// Delta-of-delta encoding, only for `Timestamp`.
+    DeltaOfDelta = 2,
+}
+
+impl ValueEncoding {
+    fn decode(flag: u8) -> Result<Self> {
+        let value_encoding = match flag {
+            0 => ValueEncoding::Plain,
+            1 => ValueEncoding::Gorilla,
+            2 => ValueEncoding::DeltaOfDelta,
+            _ => InvalidValueEncoding { flag }.fail()?,
+        };
+
+        Ok(value_encoding)
+    }
+}
+
 /// A hint helps column encoding.
 pub struct EncodeHint {
     pub num_nulls: Option<usize>,
     pub num_datums: Option<usize>,
     pub datum_kind: DatumKind,
+    /// The encoding of the non-null values, and the most compact one will be
+    /// chosen if not set.
+    pub value_encoding: Option<ValueEncoding>,
 }
 
 impl EncodeHint {
+    /// The number of the leading non-null values to choose the value encoding
+    /// from.
+    const VALUE_ENCODING_SAMPLE_SIZE: usize = 1024;
+
     fn compute_num_nulls<'a, I>(&mut self, datums: &I) -> usize
     where
         I: Iterator<Item = DatumView<'a>> + Clone,
@@ -182,10 +240,78 @@ impl EncodeHint {
             num_datums
         }
     }
+
+    /// The compact encodings are chosen only if they really shrink the first
+    /// [`VALUE_ENCODING_SAMPLE_SIZE`](Self::VALUE_ENCODING_SAMPLE_SIZE) values,
+    /// so at most the sample is encoded twice here.
+    fn compute_value_encoding<'a, I>(&mut self, datums: &I) -> ValueEncoding
+    where
+        I: Iterator<Item = DatumView<'a>> + Clone,
+    {
+        if let Some(v) = self.value_encoding {
+            return v;
+        }
+
+        let sample = datums.clone().filter(|v| !v.is_null());
+        let sample = sample.take(Self::VALUE_ENCODING_SAMPLE_SIZE);
+        let value_encoding = match self.datum_kind {
+            DatumKind::Double => {
+                let values = sample.filter_map(|v| v.as_f64());
+                let plain_size = values.clone().count() * std::mem::size_of::<f64>();
+                let mut buf = Vec::with_capacity(plain_size);
+                float::encode_f64(&mut buf, values);
+                Self::choose_compact(buf.len(), Some(plain_size), ValueEncoding::Gorilla)
+            }
+            DatumKind::Float => {
+                let values = sample.filter_map(|v| v.as_f32());
+                let plain_size = values.clone().count() * std::mem::size_of::<f32>();
+                let mut buf = Vec::with_capacity(plain_size);
+                float::encode_f32(&mut buf, values);
+                Self::choose_compact(buf.len(), Some(plain_size), ValueEncoding::Gorilla)
+            }
+            DatumKind::Timestamp => {
+                let values = sample.filter_map(|v| v.as_timestamp());
+                let mut buf = Vec::new();
+                // The plain encoding fails if the delta of any value overflows, which is
+                // cheaper to check on all the values than to encode them.
+                let all_values = datums.clone().filter_map(|v| v.as_timestamp());
+                let plain_size = if timestamp::deltas_fit(all_values) {
+                    ValuesEncoderImpl::default()
+                        .encode(&mut buf, values.clone())
+                        .ok()
+                        .map(|_| buf.len())
+                } else {
+                    None
+                };
+                buf.clear();
+                timestamp::encode_delta_of_delta(&mut buf, values);
+                Self::choose_compact(buf.len(), plain_size, ValueEncoding::DeltaOfDelta)
+            }
+            _ => ValueEncoding::Plain,
+        };
+        self.value_encoding = Some(value_encoding);
+
+        value_encoding
+    }
+
+    fn choose_compact(
+        compact_size: usize,
+        plain_size: Option<usize>,
+        compact: ValueEncoding,
+    ) -> ValueEncoding {
+        match plain_size {
+            Some(plain_size) if plain_size <= compact_size => ValueEncoding::Plain,
+            _ => compact,
+        }
+    }
 }
 
 impl ColumnarEncoder {
-    const VERSION: u8 = 0;
+    /// The version appends the `value_encoding` to the header.
+    const VERSION: u8 = 1;
+    /// The version without the `value_encoding`, whose values are always in
+    /// the plain encoding.
+    const VERSION_WITHOUT_VALUE_ENCODING: u8 = 0;
 
     pub fn new(column_id: ColumnId, bytes_compress_threshold: usize) -> Self {
         Self {
@@ -194,24 +320,24 @@ impl ColumnarEncoder {
         }
     }
 
-    /// The header includes `version`, `datum_kind`, `column_id`, `num_datums`
-    /// and `num_nulls`.
+    /// The header includes `version`, `datum_kind`, `column_id`, `num_datums`,
+    /// `num_nulls` and `value_encoding`.
     ///
     /// Refer to the [encode](ColumnarEncoder::encode) method.
     #[inline]
     const fn header_size() -> usize {
-        1 + 1 + 4 + 4 + 4
+        1 + 1 + 4 + 4 + 4 + 1
     }
 
     /// The layout of the final serialized bytes:
     /// ```plaintext
-    /// +-------------+----------------+-----------------+-----------------+----------------+---------------+---------------------+
-    /// | version(u8) | datum_kind(u8) | column_id(u32) | num_datums(u32) | num_nulls(u32) | nulls_bit_set | non-null data block |
-    /// +-------------+----------------+-----------------+-----------------+----------------+---------------+---------------------+
+    /// +-------------+----------------+-----------------+-----------------+----------------+--------------------+---------------+---------------------+
+    /// | version(u8) | datum_kind(u8) | column_id(u32) | num_datums(u32) | num_nulls(u32) | value_encoding(u8) | nulls_bit_set | non-null data block |
+    /// +-------------+----------------+-----------------+-----------------+----------------+--------------------+---------------+---------------------+
     /// ```
     /// Note:
-    /// 1. The `num_nulls`, `nulls_bit_set` and `non-null data block` will not
-    /// exist if the kind of datum is null;
+    /// 1. The `num_nulls`, `value_encoding`, `nulls_bit_set` and `non-null data
+    /// block` will not exist if the kind of datum is null;
     /// 2. The `nulls_bit_set` will not exist if the `num_nulls` is zero;
     /// 3. The `nulls_bit_set` and `non-null data block` will not exist if the
     /// `num_nulls` equals the `num_datums`;
@@ -236,6 +362,8 @@ impl ColumnarEncoder {
         assert!(num_nulls < u32::MAX as usize);
 
         buf.put_u32(num_nulls as u32);
+        let value_encoding = hint.compute_value_encoding(&datums);
+        buf.put_u8(value_encoding as u8);
         if num_nulls > 0 {
             let mut bit_set = BitSet::all_set(num_datums);
             for (idx, value) in datums.clone().enumerate() {
@@ -247,7 +375,7 @@ impl ColumnarEncoder {
             buf.put_slice(bit_set.as_bytes());
         }
 
-        self.encode_datums(buf, datums, hint.datum_kind)
+        self.encode_datums(buf, datums, hint.datum_kind, value_encoding)
     }
 
     pub fn estimated_encoded_size<'a, I>(&self, datums: I, hint: &mut EncodeHint) -> usize
@@ -276,7 +404,7 @@ impl ColumnarEncoder {
             DatumKind::Varbinary => {
                 enc.estimated_encoded_size(datums.clone().filter_map(|v| v.into_bytes()))
             }
-            DatumKind::String => enc.estimated_encoded_size(
+            DatumKind::String | DatumKind::Json => enc.estimated_encoded_size(
                 datums
                     .clone()
                     .filter_map(|v| v.into_str().map(|v| v.as_bytes())),
@@ -312,12 +440,57 @@ impl ColumnarEncoder {
             DatumKind::Time => {
                 enc.estimated_encoded_size(datums.clone().filter_map(|v| v.as_timestamp()))
             }
+            DatumKind::Decimal => {
+                enc.estimated_encoded_size(datums.clone().filter_map(|v| v.as_decimal()))
+            }
+            DatumKind::Array => {
+                let encoded = encode_arrays(datums.clone());
+                enc.estimated_encoded_size(encoded.iter().map(|v| v.as_slice()))
+            }
         };
 
         Self::header_size() + bit_set_size + data_size
     }
 
-    fn encode_datums<'a, I, B>(&self, buf: &mut B, datums: I, datum_kind: DatumKind) -> Result<()>
+    fn encode_datums<'a, I, B>(
+        &self,
+        buf: &mut B,
+        datums: I,
+        datum_kind: DatumKind,
+        value_encoding: ValueEncoding,
+    ) -> Result<()>
+    where
+        I: Iterator<Item = DatumView<'a>> + Clone,
+        B: BufMut,
+    {
+        match (value_encoding, datum_kind) {
+            (ValueEncoding::Plain, _) => self.encode_plain_datums(buf, datums, datum_kind),
+            (ValueEncoding::Gorilla, DatumKind::Double) => {
+                float::encode_f64(buf, datums.filter_map(|v| v.as_f64()));
+                Ok(())
+            }
+            (ValueEncoding::Gorilla, DatumKind::Float) => {
+                float::encode_f32(buf, datums.filter_map(|v| v.as_f32()));
+                Ok(())
+            }
+            (ValueEncoding::DeltaOfDelta, DatumKind::Timestamp) => {
+                timestamp::encode_delta_of_delta(buf, datums.filter_map(|v| v.as_timestamp()));
+                Ok(())
+            }
+            _ => UnsupportedValueEncoding {
+                value_encoding,
+                datum_kind,
+            }
+            .fail(),
+        }
+    }
+
+    fn encode_plain_datums<'a, I, B>(
+        &self,
+        buf: &mut B,
+        datums: I,
+        datum_kind: DatumKind,
+    ) -> Result<()>
     where
         I: Iterator<Item = DatumView<'a>> + Clone,
         B: BufMut,
@@ -331,7 +504,7 @@ impl ColumnarEncoder {
             DatumKind::Double => enc.encode(buf, datums.filter_map(|v| v.as_f64())),
             DatumKind::Float => enc.encode(buf, datums.filter_map(|v| v.as_f32())),
             DatumKind::Varbinary => enc.encode(buf, datums.filter_map(|v| v.into_bytes())),
-            DatumKind::String => enc.encode(
+            DatumKind::String | DatumKind::Json => enc.encode(
                 buf,
                 datums.filter_map(|v| v.into_str().map(|v| v.as_bytes())),
             ),
@@ -346,10 +519,25 @@ impl ColumnarEncoder {
             DatumKind::Boolean => enc.encode(buf, datums.filter_map(|v| v.as_bool())),
             DatumKind::Date => enc.encode(buf, datums.filter_map(|v| v.as_date_i32())),
             DatumKind::Time => enc.encode(buf, datums.filter_map(|v| v.as_timestamp())),
+            DatumKind::Decimal => enc.encode(buf, datums.filter_map(|v| v.as_decimal())),
+            DatumKind::Array => {
+                let encoded = encode_arrays(datums);
+                enc.encode(buf, encoded.iter().map(|v| v.as_slice()))
+            }
         }
     }
 }
 
+/// The arrays are self-described, so they are encoded as bytes.
+fn encode_arrays<'a, I>(datums: I) -> Vec<Vec<u8>>
+where
+    I: Iterator<Item = DatumView<'a>>,
+{
+    datums
+        .filter_map(|v| v.as_array().map(|v| v.to_bytes()))
+        .collect()
+}
+
 /// The decoder for [`ColumnarEncoder`].
 #[derive(Debug, Clone)]
 pub struct ColumnarDecoder;
@@ -364,7 +552,8 @@ impl ColumnarDecoder {
     pub fn decode<B: Buf>(&self, ctx: DecodeContext<'_>, buf: &mut B) -> Result<DecodeResult> {
         let version = buf.get_u8();
         ensure!(
-            version == ColumnarEncoder::VERSION,
+            version == ColumnarEncoder::VERSION
+                || version == ColumnarEncoder::VERSION_WITHOUT_VALUE_ENCODING,
             InvalidVersion { version }
         );
 
@@ -380,12 +569,17 @@ impl ColumnarDecoder {
         }
 
         let num_nulls = buf.get_u32() as usize;
+        let value_encoding = if version == ColumnarEncoder::VERSION_WITHOUT_VALUE_ENCODING {
+            ValueEncoding::Plain
+        } else {
+            ValueEncoding::decode(buf.get_u8())?
+        };
         let datums = if num_nulls == num_datums {
             vec![Datum::Null; num_datums]
         } else if num_nulls > 0 {
-            Self::decode_with_nulls(ctx, buf, num_datums, datum_kind)?
+            Self::decode_with_nulls(ctx, buf, num_datums, datum_kind, value_encoding)?
         } else {
-            Self::decode_without_nulls(ctx, buf, num_datums, datum_kind)?
+            Self::decode_without_nulls(ctx, buf, num_datums, datum_kind, value_encoding)?
         };
 
         Ok(DecodeResult { column_id, datums })
@@ -398,6 +592,7 @@ impl ColumnarDecoder {
         buf: &B,
         num_datums: usize,
         datum_kind: DatumKind,
+        value_encoding: ValueEncoding,
     ) -> Result<Vec<Datum>> {
         let chunk = buf.chunk();
         let bit_set = RoBitSet::try_new(chunk, num_datums).context(InvalidBitSetBuf)?;
@@ -415,7 +610,7 @@ impl ColumnarDecoder {
         };
 
         let mut data_block = &chunk[BitSet::num_bytes(num_datums)..];
-        Self::decode_datums(ctx, &mut data_block, datum_kind, with_datum)?;
+        Self::decode_datums(ctx, &mut data_block, datum_kind, value_encoding, with_datum)?;
 
         Ok(datums)
     }
@@ -425,17 +620,48 @@ impl ColumnarDecoder {
         buf: &mut B,
         num_datums: usize,
         datum_kind: DatumKind,
+        value_encoding: ValueEncoding,
     ) -> Result<Vec<Datum>> {
         let mut datums = Vec::with_capacity(num_datums);
         let with_datum = |datum: Datum| {
             datums.push(datum);
             Ok(())
         };
-        Self::decode_datums(ctx, buf, datum_kind, with_datum)?;
+        Self::decode_datums(ctx, buf, datum_kind, value_encoding, with_datum)?;
         Ok(datums)
     }
 
     fn decode_datums<B, F>(
+        ctx: DecodeContext<'_>,
+        buf: &mut B,
+        datum_kind: DatumKind,
+        value_encoding: ValueEncoding,
+        mut f: F,
+    ) -> Result<()>
+    where
+        B: Buf,
+        F: FnMut(Datum) -> Result<()>,
+    {
+        match (value_encoding, datum_kind) {
+            (ValueEncoding::Plain, _) => Self::decode_plain_datums(ctx, buf, datum_kind, f),
+            (ValueEncoding::Gorilla, DatumKind::Double) => {
+                float::decode_f64(buf, |v| f(Datum::from(v)))
+            }
+            (ValueEncoding::Gorilla, DatumKind::Float) => {
+                float::decode_f32(buf, |v| f(Datum::from(v)))
+            }
+            (ValueEncoding::DeltaOfDelta, DatumKind::Timestamp) => {
+                timestamp::decode_delta_of_delta(buf, |v| f(Datum::from(v)))
+            }
+            _ => UnsupportedValueEncoding {
+                value_encoding,
+                datum_kind,
+            }
+            .fail(),
+        }
+    }
+
+    fn decode_plain_datums<B, F>(
         ctx: DecodeContext<'_>,
         buf: &mut B,
         datum_kind: DatumKind,
@@ -470,6 +696,13 @@ impl ColumnarDecoder {
                 };
                 ValuesDecoderImpl.decode(ctx, buf, with_str)
             }
+            DatumKind::Json => {
+                let with_json = |value| {
+                    let datum = unsafe { Datum::Json(StringBytes::from_bytes_unchecked(value)) };
+                    f(datum)
+                };
+                ValuesDecoderImpl.decode(ctx, buf, with_json)
+            }
             DatumKind::UInt64 => {
                 let with_u64 = |value: u64| {
                     let datum = Datum::from(value);
@@ -538,28 +771,54 @@ impl ColumnarDecoder {
                 let with_timestamp = |v: Timestamp| f(Datum::Time(v.as_i64()));
                 ValuesDecoderImpl.decode(ctx, buf, with_timestamp)
             }
+            DatumKind::Decimal => {
+                let with_decimal = |v: Decimal| f(Datum::Decimal(v));
+                ValuesDecoderImpl.decode(ctx, buf, with_decimal)
+            }
+            DatumKind::Array => {
+                let with_array = |v: Bytes| {
+                    let array = ArrayValue::decode(&v).context(DecodeArray)?;
+                    f(Datum::Array(array))
+                };
+                ValuesDecoderImpl.decode(ctx, buf, with_array)
+            }
         }
     }
 }
 #[cfg(test)]
 mod tests {
+    use common_types::decimal::DecimalType;
+
     use super::*;
 
     fn check_encode_end_decode(column_id: ColumnId, datums: Vec<Datum>, datum_kind: DatumKind) {
+        check_encode_end_decode_with_encoding(column_id, datums, datum_kind, None);
+    }
+
+    fn check_encode_end_decode_with_encoding(
+        column_id: ColumnId,
+        datums: Vec<Datum>,
+        datum_kind: DatumKind,
+        value_encoding: Option<ValueEncoding>,
+    ) -> Vec<u8> {
         let encoder = ColumnarEncoder::new(column_id, 256);
         let views = datums.iter().map(|v| v.as_view());
         let mut hint = EncodeHint {
             num_nulls: None,
             num_datums: None,
             datum_kind,
+            value_encoding,
         };
 
         let buf_len = encoder.estimated_encoded_size(views.clone(), &mut hint);
         let mut buf = Vec::with_capacity(buf_len);
         encoder.encode(&mut buf, views, &mut hint).unwrap();
 
-        // Ensure no growth over the capacity.
-        assert!(buf.capacity() <= buf_len);
+        // Ensure no growth over the capacity, and the forced compact encodings
+        // may be larger than the plain one.
+        if value_encoding.is_none() {
+            assert!(buf.capacity() <= buf_len);
+        }
 
         let mut reused_buf = Vec::new();
         let ctx = DecodeContext {
@@ -572,6 +831,8 @@ mod tests {
         } = decoder.decode(ctx, &mut buf.as_slice()).unwrap();
         assert_eq!(column_id, decoded_column_id);
         assert_eq!(datums, decoded_datums);
+
+        buf
     }
 
     #[test]
@@ -749,6 +1010,47 @@ mod tests {
         check_encode_end_decode(10, datums, DatumKind::Time);
     }
 
+    #[test]
+    fn test_decimal() {
+        let decimal_type = DecimalType::try_new(10, 2).unwrap();
+        let datums = vec![
+            Datum::Decimal(Decimal::parse("-10.5", decimal_type).unwrap()),
+            Datum::Null,
+            Datum::Decimal(Decimal::parse("0.01", decimal_type).unwrap()),
+            Datum::Decimal(Decimal::parse("12345678.99", decimal_type).unwrap()),
+        ];
+
+        check_encode_end_decode(10, datums, DatumKind::Decimal);
+    }
+
+    #[test]
+    fn test_json() {
+        let datums = vec![
+            Datum::Json(StringBytes::from(r#"{"a":1}"#)),
+            Datum::Null,
+            Datum::Json(StringBytes::from("[1,2]")),
+        ];
+
+        check_encode_end_decode(10, datums, DatumKind::Json);
+    }
+
+    #[test]
+    fn test_array() {
+        let datums = vec![
+            Datum::Array(
+                ArrayValue::try_new(
+                    DatumKind::String,
+                    vec![Datum::from("a"), Datum::Null, Datum::from("bc")],
+                )
+                .unwrap(),
+            ),
+            Datum::Null,
+            Datum::Array(ArrayValue::empty(DatumKind::String)),
+        ];
+
+        check_encode_end_decode(10, datums, DatumKind::Array);
+    }
+
     #[test]
     fn test_overflow_timestamp() {
         let datums = vec![
@@ -765,11 +1067,122 @@ mod tests {
             num_nulls: None,
             num_datums: None,
             datum_kind: DatumKind::Timestamp,
+            value_encoding: Some(ValueEncoding::Plain),
         };
 
         let mut buf = Vec::new();
         let enc_res = encoder.encode(&mut buf, views, &mut hint);
         assert!(enc_res.is_err());
+
+        // The delta-of-delta encoding is chosen as the plain one overflows.
+        check_encode_end_decode(10, datums, DatumKind::Timestamp);
+    }
+
+    #[test]
+    fn test_compact_value_encodings() {
+        let datums: Vec<_> = (0..100)
+            .map(|i| Datum::from(Timestamp::new(1_700_000_000_000 + i * 1000)))
+            .collect();
+        let plain = check_encode_end_decode_with_encoding(
+            10,
+            datums.clone(),
+            DatumKind::Timestamp,
+            Some(ValueEncoding::Plain),
+        );
+        let compact = check_encode_end_decode_with_encoding(10, datums, DatumKind::Timestamp, None);
+        assert_eq!(compact[14], ValueEncoding::DeltaOfDelta as u8);
+        assert!(compact.len() < plain.len());
+
+        let mut datums: Vec<_> = (0..100)
+            .map(|i| Datum::from(20.0 + (i % 3) as f64))
+            .collect();
+        datums[10] = Datum::Null;
+        let plain = check_encode_end_decode_with_encoding(
+            10,
+            datums.clone(),
+            DatumKind::Double,
+            Some(ValueEncoding::Plain),
+        );
+        let compact = check_encode_end_decode_with_encoding(10, datums, DatumKind::Double, None);
+        assert_eq!(compact[14], ValueEncoding::Gorilla as u8);
+        assert!(compact.len() < plain.len());
+
+        let datums: Vec<_> = (0..100).map(|i| Datum::from(0.5f32 * i as f32)).collect();
+        check_encode_end_decode_with_encoding(
+            10,
+            datums,
+            DatumKind::Float,
+            Some(ValueEncoding::Gorilla),
+        );
+
+        // The random values can't be shrunk.
+        let datums = vec![Datum::from(0.1f64), Datum::from(-1e300f64)];
+        let buf = check_encode_end_decode_with_encoding(10, datums, DatumKind::Double, None);
+        assert_eq!(buf[14], ValueEncoding::Plain as u8);
+    }
+
+    #[test]
+    fn test_value_encoding_from_sample() {
+        // Only the leading values are sampled to choose the encoding.
+        let num_values = EncodeHint::VALUE_ENCODING_SAMPLE_SIZE as u64 * 2;
+        let datums: Vec<_> = (0..num_values)
+            .map(|i| {
+                if i < num_values / 2 {
+                    Datum::from(20.0)
+                } else {
+                    Datum::from((i as f64).sin() * 1e6)
+                }
+            })
+            .collect();
+        let buf = check_encode_end_decode_with_encoding(10, datums, DatumKind::Double, None);
+        assert_eq!(buf[14], ValueEncoding::Gorilla as u8);
+
+        // The values out of the sample overflowing the plain encoding are still
+        // encoded.
+        let mut datums: Vec<_> = (0..num_values as i64)
+            .map(|i| Datum::from(Timestamp::new(i)))
+            .collect();
+        datums.push(Datum::from(Timestamp::new(i64::MIN)));
+        let buf = check_encode_end_decode_with_encoding(10, datums, DatumKind::Timestamp, None);
+        assert_eq!(buf[14], ValueEncoding::DeltaOfDelta as u8);
+    }
+
+    #[test]
+    fn test_unsupported_value_encoding() {
+        let datums = [Datum::from(1i32)];
+        let mut hint = EncodeHint {
+            num_nulls: None,
+            num_datums: None,
+            datum_kind: DatumKind::Int32,
+            value_encoding: Some(ValueEncoding::Gorilla),
+        };
+        let mut buf = Vec::new();
+        let res = ColumnarEncoder::new(0, 256).encode(
+            &mut buf,
+            datums.iter().map(|v| v.as_view()),
+            &mut hint,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_decode_without_value_encoding() {
+        // The payload encoded by the version without value encoding.
+        let mut buf = vec![ColumnarEncoder::VERSION_WITHOUT_VALUE_ENCODING];
+        buf.put_u8(DatumKind::Double.into_u8());
+        buf.put_u32(10);
+        buf.put_u32(2);
+        buf.put_u32(0);
+        buf.put_f64(1.5);
+        buf.put_f64(-2.0);
+
+        let mut reused_buf = Vec::new();
+        let ctx = DecodeContext {
+            buf: &mut reused_buf,
+        };
+        let res = ColumnarDecoder.decode(ctx, &mut buf.as_slice()).unwrap();
+        assert_eq!(res.column_id, 10);
+        assert_eq!(res.datums, vec![Datum::from(1.5f64), Datum::from(-2.0f64)]);
     }
 
     #[test]
@@ -16,12 +16,13 @@
 // under the License.
 
 use bytes_ext::{Buf, BufMut};
+use common_types::decimal::{Decimal, DecimalType};
 use snafu::{ensure, ResultExt};
 
 use crate::{
     columnar::{
-        DecodeContext, InvalidVersion, Result, ValuesDecoder, ValuesDecoderImpl, ValuesEncoder,
-        ValuesEncoderImpl, Varint,
+        DecodeContext, InvalidEncodedValues, InvalidVersion, Result, ValuesDecoder,
+        ValuesDecoderImpl, ValuesEncoder, ValuesEncoderImpl, Varint,
     },
     varint,
 };
@@ -30,6 +31,8 @@ use crate::{
 const MAX_NUM_BYTES_OF_64VARINT: usize = 10;
 const VERSION: u8 = 0;
 const VERSION_SIZE: usize = 1;
+/// The size of the precision and scale of the decimal values.
+const DECIMAL_TYPE_SIZE: usize = 2;
 
 macro_rules! impl_number_encoding {
     ($num_type: ty, $write_method: ident, $read_method: ident) => {
@@ -157,3 +160,66 @@ impl ValuesDecoder<u64> for ValuesDecoderImpl {
         Ok(())
     }
 }
+
+/// The decimal values of a column share the same decimal type, so the data
+/// block is: version | precision(u8) | scale(i8) | values(i128)...
+impl ValuesEncoder<Decimal> for ValuesEncoderImpl {
+    fn encode<B, I>(&self, buf: &mut B, values: I) -> Result<()>
+    where
+        B: BufMut,
+        I: Iterator<Item = Decimal> + Clone,
+    {
+        let decimal_type = values
+            .clone()
+            .next()
+            .map(|v| v.decimal_type())
+            .unwrap_or_default();
+
+        buf.put_u8(VERSION);
+        buf.put_u8(decimal_type.precision());
+        buf.put_i8(decimal_type.scale());
+        for v in values {
+            buf.put_i128(v.value());
+        }
+
+        Ok(())
+    }
+
+    fn estimated_encoded_size<I>(&self, values: I) -> usize
+    where
+        I: Iterator<Item = Decimal>,
+    {
+        let (lower, higher) = values.size_hint();
+        let num = lower.max(higher.unwrap_or_default());
+        num * std::mem::size_of::<i128>() + VERSION_SIZE + DECIMAL_TYPE_SIZE
+    }
+}
+
+impl ValuesDecoder<Decimal> for ValuesDecoderImpl {
+    fn decode<B, F>(&self, _ctx: DecodeContext<'_>, buf: &mut B, mut f: F) -> Result<()>
+    where
+        B: Buf,
+        F: FnMut(Decimal) -> Result<()>,
+    {
+        let version = buf.get_u8();
+        ensure!(version == VERSION, InvalidVersion { version });
+
+        let precision = buf.get_u8();
+        let scale = buf.get_i8();
+        let decimal_type = match DecimalType::try_new(u64::from(precision), scale as u64) {
+            Ok(v) => v,
+            Err(e) => {
+                return InvalidEncodedValues {
+                    msg: format!("invalid decimal type, err:{e}"),
+                }
+                .fail()
+            }
+        };
+        while buf.remaining() > 0 {
+            let v = buf.get_i128();
+            f(Decimal::new_unchecked(v, decimal_type))?;
+        }
+
+        Ok(())
+    }
+}
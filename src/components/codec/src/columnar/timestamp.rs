@@ -15,13 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use bytes_ext::{Buf, BufMut};
 use common_types::time::Timestamp;
 use snafu::{ensure, OptionExt, ResultExt};
 
 use crate::{
     columnar::{
-        DecodeContext, InvalidVersion, Overflow, Result, ValuesDecoder, ValuesDecoderImpl,
-        ValuesEncoder, ValuesEncoderImpl, Varint,
+        bits::{BitReader, BitWriter},
+        DecodeContext, InvalidVersion, NotEnoughBytes, Overflow, Result, ValuesDecoder,
+        ValuesDecoderImpl, ValuesEncoder, ValuesEncoderImpl, Varint,
     },
     consts::MAX_VARINT_BYTES,
     varint,
@@ -107,3 +109,167 @@ impl ValuesDecoder<Timestamp> for ValuesDecoderImpl {
         Ok(())
     }
 }
+
+/// The layout for the delta-of-delta encoded timestamp values:
+/// ```plaintext
+/// +-----------------+----------------------+----------+
+/// | num_values(u32) | first_timestamp(i64) | bit data |
+/// +-----------------+----------------------+----------+
+/// ```
+///
+/// The bit data stores the zigzag encoded differences between the adjacent
+/// deltas, and the delta before the first timestamp is treated as zero:
+/// - `0`: the delta equals the previous one;
+/// - `10` + 7 bits, `110` + 12 bits, `1110` + 20 bits or `1111` + 64 bits for
+///   the others.
+///
+/// The timestamps written periodically have almost constant deltas, so most
+/// of them take only one bit.
+struct DeltaOfDeltaEncoding;
+
+impl DeltaOfDeltaEncoding {
+    /// The number of the value bits for the buckets, and the bucket `i` is
+    /// marked by `i` ones and a zero.
+    const BUCKET_VALUE_BITS: [u32; 4] = [0, 7, 12, 20];
+    const HEADER_SIZE: usize = 4 + 8;
+
+    fn write_delta_of_delta(writer: &mut BitWriter, delta_of_delta: i64) {
+        let zigzag = ((delta_of_delta << 1) ^ (delta_of_delta >> 63)) as u64;
+        for (idx, num_value_bits) in Self::BUCKET_VALUE_BITS.into_iter().enumerate() {
+            if zigzag < (1 << num_value_bits) {
+                let num_control_bits = idx as u32 + 1;
+                writer.write_bits((1 << num_control_bits) - 2, num_control_bits);
+                writer.write_bits(zigzag, num_value_bits);
+                return;
+            }
+        }
+
+        writer.write_bits(0b1111, 4);
+        writer.write_bits(zigzag, 64);
+    }
+
+    fn read_delta_of_delta(reader: &mut BitReader<'_>) -> Result<i64> {
+        let mut num_value_bits = 64;
+        for bits in Self::BUCKET_VALUE_BITS {
+            if !reader.read_bit()? {
+                num_value_bits = bits;
+                break;
+            }
+        }
+
+        let zigzag = reader.read_bits(num_value_bits)?;
+        Ok((zigzag >> 1) as i64 ^ -((zigzag & 1) as i64))
+    }
+}
+
+/// Whether the deltas of all the `values` from the first one fit in i64, that
+/// is to say, the plain encoding of the `values` won't overflow.
+pub(crate) fn deltas_fit<I>(mut values: I) -> bool
+where
+    I: Iterator<Item = Timestamp>,
+{
+    let first_ts = match values.next() {
+        Some(v) => v.as_i64(),
+        None => return true,
+    };
+
+    values.all(|v| v.as_i64().checked_sub(first_ts).is_some())
+}
+
+/// Encode the timestamps in delta-of-delta encoding.
+///
+/// The deltas are computed with wrapping arithmetic, so any timestamps can be
+/// encoded without overflow.
+pub(crate) fn encode_delta_of_delta<B, I>(buf: &mut B, values: I)
+where
+    B: BufMut,
+    I: Iterator<Item = Timestamp>,
+{
+    let mut writer = BitWriter::default();
+    let mut num_values: u32 = 0;
+    let mut first_ts = 0;
+    let (mut prev_ts, mut prev_delta) = (0i64, 0i64);
+    for value in values {
+        let ts = value.as_i64();
+        if num_values == 0 {
+            first_ts = ts;
+        } else {
+            let delta = ts.wrapping_sub(prev_ts);
+            DeltaOfDeltaEncoding::write_delta_of_delta(&mut writer, delta.wrapping_sub(prev_delta));
+            prev_delta = delta;
+        }
+
+        prev_ts = ts;
+        num_values += 1;
+    }
+
+    buf.put_u32(num_values);
+    buf.put_i64(first_ts);
+    buf.put_slice(writer.as_bytes());
+}
+
+pub(crate) fn decode_delta_of_delta<B, F>(buf: &mut B, mut f: F) -> Result<()>
+where
+    B: Buf,
+    F: FnMut(Timestamp) -> Result<()>,
+{
+    ensure!(
+        buf.remaining() >= DeltaOfDeltaEncoding::HEADER_SIZE,
+        NotEnoughBytes {
+            len: buf.remaining()
+        }
+    );
+    let num_values = buf.get_u32();
+    let first_ts = buf.get_i64();
+    if num_values == 0 {
+        return Ok(());
+    }
+    f(Timestamp::new(first_ts))?;
+
+    let mut reader = BitReader::new(buf.chunk());
+    let (mut prev_ts, mut prev_delta) = (first_ts, 0i64);
+    for _ in 1..num_values {
+        let delta_of_delta = DeltaOfDeltaEncoding::read_delta_of_delta(&mut reader)?;
+        prev_delta = prev_delta.wrapping_add(delta_of_delta);
+        prev_ts = prev_ts.wrapping_add(prev_delta);
+        f(Timestamp::new(prev_ts))?;
+    }
+
+    buf.advance(reader.num_bytes_read());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_delta_of_delta(timestamps: Vec<i64>) -> usize {
+        let mut buf = Vec::new();
+        encode_delta_of_delta(&mut buf, timestamps.iter().map(|v| Timestamp::new(*v)));
+
+        let mut decoded = Vec::new();
+        decode_delta_of_delta(&mut buf.as_slice(), |v| {
+            decoded.push(v.as_i64());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(timestamps, decoded);
+
+        buf.len()
+    }
+
+    #[test]
+    fn test_delta_of_delta() {
+        check_delta_of_delta(vec![]);
+        check_delta_of_delta(vec![1000]);
+        check_delta_of_delta(vec![-10, 10, 1024, 1024, 1025, 900, 1 << 20, 1 << 40]);
+        check_delta_of_delta(vec![i64::MIN, 10, i64::MAX, 0, i64::MIN]);
+
+        // Periodic timestamps take one bit for each.
+        let timestamps = (0..80).map(|i| 1_700_000_000_000 + i * 10_000).collect();
+        let encoded_len = check_delta_of_delta(timestamps);
+        // header(12B) + the first delta(4 + 20 bits) + 78 bits.
+        assert_eq!(encoded_len, 12 + 13);
+    }
+}
@@ -18,11 +18,13 @@
 //! Datum compact codec
 
 use bytes_ext::{Buf, BufMut, BytesMut, SafeBufMut};
-use common_types::{datum::Datum, string::StringBytes, time::Timestamp};
+use common_types::{
+    array::ArrayValue, datum::Datum, decimal::Decimal, string::StringBytes, time::Timestamp,
+};
 use snafu::ResultExt;
 
 use crate::{
-    compact::{EncodeKey, Error, MemCompactDecoder, MemCompactEncoder, Result},
+    compact::{DecodeArray, EncodeKey, Error, MemCompactDecoder, MemCompactEncoder, Result},
     consts, DecodeTo, Encoder,
 };
 
@@ -50,8 +52,8 @@ impl Encoder<Datum> for MemCompactEncoder {
                     .context(EncodeKey)?;
                 self.encode(buf, v)
             }
-            // For string, just encode/decode like bytes.
-            Datum::String(v) => {
+            // For string and json, just encode/decode like bytes.
+            Datum::String(v) | Datum::Json(v) => {
                 buf.try_put_u8(consts::COMPACT_BYTES_FLAG)
                     .context(EncodeKey)?;
                 self.encode(buf, v.as_bytes())
@@ -100,6 +102,18 @@ impl Encoder<Datum> for MemCompactEncoder {
                 buf.try_put_u8(consts::VARINT_FLAG).context(EncodeKey)?;
                 self.encode(buf, v)
             }
+            // The precision and scale is stored in the schema, so only the
+            // unscaled value is encoded.
+            Datum::Decimal(v) => {
+                buf.try_put_u8(consts::DECIMAL_FLAG).context(EncodeKey)?;
+                self.encode(buf, &v.value())
+            }
+            // The array is self-described, so just encode/decode it like bytes.
+            Datum::Array(v) => {
+                buf.try_put_u8(consts::COMPACT_BYTES_FLAG)
+                    .context(EncodeKey)?;
+                self.encode(buf, v.to_bytes().as_slice())
+            }
         }
     }
 
@@ -111,7 +125,7 @@ impl Encoder<Datum> for MemCompactEncoder {
             Datum::Double(v) => self.estimate_encoded_size(v),
             Datum::Float(v) => self.estimate_encoded_size(v),
             Datum::Varbinary(v) => self.estimate_encoded_size(v),
-            Datum::String(v) => self.estimate_encoded_size(v.as_bytes()),
+            Datum::String(v) | Datum::Json(v) => self.estimate_encoded_size(v.as_bytes()),
             Datum::UInt64(v) => self.estimate_encoded_size(v),
             Datum::UInt32(v) => self.estimate_encoded_size(&(u64::from(*v))),
             Datum::UInt16(v) => self.estimate_encoded_size(&(u64::from(*v))),
@@ -123,6 +137,8 @@ impl Encoder<Datum> for MemCompactEncoder {
             Datum::Boolean(v) => self.estimate_encoded_size(&(u64::from(*v))),
             Datum::Date(v) => self.estimate_encoded_size(&(i64::from(*v))),
             Datum::Time(v) => self.estimate_encoded_size(v),
+            Datum::Decimal(v) => self.estimate_encoded_size(&v.value()),
+            Datum::Array(v) => consts::MAX_VARINT_BYTES + v.encoded_len(),
         }
     }
 }
@@ -193,7 +209,7 @@ impl DecodeTo<Datum> for MemCompactDecoder {
                 self.decode_to(buf, &mut data)?;
                 *v = data.freeze();
             }
-            Datum::String(v) => {
+            Datum::String(v) | Datum::Json(v) => {
                 Self::ensure_flag(consts::COMPACT_BYTES_FLAG, actual)?;
                 let mut data = BytesMut::new();
                 self.decode_to(buf, &mut data)?;
@@ -224,6 +240,19 @@ impl DecodeTo<Datum> for MemCompactDecoder {
                 Self::ensure_flag(consts::VARINT_FLAG, actual)?;
                 self.decode_to(buf, v)?;
             }
+            // The decimal type of the template datum is kept.
+            Datum::Decimal(v) => {
+                Self::ensure_flag(consts::DECIMAL_FLAG, actual)?;
+                let mut data = 0i128;
+                self.decode_to(buf, &mut data)?;
+                *v = Decimal::new_unchecked(data, v.decimal_type());
+            }
+            Datum::Array(v) => {
+                Self::ensure_flag(consts::COMPACT_BYTES_FLAG, actual)?;
+                let mut data = BytesMut::new();
+                self.decode_to(buf, &mut data)?;
+                *v = ArrayValue::decode(&data).context(DecodeArray)?;
+            }
         }
         Ok(())
     }
@@ -232,9 +261,14 @@ impl DecodeTo<Datum> for MemCompactDecoder {
 #[cfg(test)]
 mod tests {
     use bytes_ext::Bytes;
+    use common_types::{datum::DatumKind, decimal::DecimalType};
 
     use super::*;
 
+    fn decimal_type() -> DecimalType {
+        DecimalType::try_new(10, 2).unwrap()
+    }
+
     // TODO(yingwen): Test nullable.
     #[test]
     fn test_datum_codec() {
@@ -258,6 +292,21 @@ mod tests {
             (Datum::Boolean(false), 10),
             (Datum::Date(1000), 10),
             (Datum::Time(1_000_000_000), 10),
+            (
+                Datum::Decimal(Decimal::parse("-12.34", decimal_type()).unwrap()),
+                16,
+            ),
+            (Datum::Json(StringBytes::from_static(r#"{"a":1}"#)), 17),
+            (
+                Datum::Array(
+                    ArrayValue::try_new(
+                        DatumKind::Int32,
+                        vec![Datum::Int32(1), Datum::Null, Datum::Int32(2)],
+                    )
+                    .unwrap(),
+                ),
+                23,
+            ),
         ];
         let mut decoded = vec![
             Datum::Null,
@@ -278,6 +327,9 @@ mod tests {
             Datum::Boolean(false),
             Datum::Date(0),
             Datum::Time(0),
+            Datum::Decimal(Decimal::new_unchecked(0, decimal_type())),
+            Datum::Json(StringBytes::new()),
+            Datum::Array(ArrayValue::empty(DatumKind::Int32)),
         ];
         let encoder = MemCompactEncoder;
         let decoder = MemCompactDecoder;
@@ -77,6 +77,9 @@ pub enum Error {
     #[snafu(display("Failed to decode string, err:{}", source))]
     DecodeString { source: common_types::string::Error },
 
+    #[snafu(display("Failed to decode array, err:{}", source))]
+    DecodeArray { source: common_types::array::Error },
+
     #[snafu(display("Datum cannot be null.\nBacktrace:\n{}", backtrace))]
     NullDatum { backtrace: Backtrace },
 }
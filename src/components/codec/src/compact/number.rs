@@ -17,11 +17,16 @@
 
 //! Number format
 
-use bytes_ext::{Buf, SafeBufMut};
+use std::mem;
+
+use bytes_ext::{Buf, SafeBuf, SafeBufMut};
 use snafu::ResultExt;
 
 use crate::{
-    compact::{DecodeVarint, EncodeVarint, Error, MemCompactDecoder, MemCompactEncoder, Result},
+    compact::{
+        DecodeValue, DecodeVarint, EncodeValue, EncodeVarint, Error, MemCompactDecoder,
+        MemCompactEncoder, Result,
+    },
     consts, varint, DecodeTo, Encoder,
 };
 
@@ -69,6 +74,31 @@ impl DecodeTo<u64> for MemCompactDecoder {
     }
 }
 
+/// i128 is encoded as fixed 16 bytes in big-endian order.
+impl Encoder<i128> for MemCompactEncoder {
+    type Error = Error;
+
+    fn encode<B: SafeBufMut>(&self, buf: &mut B, value: &i128) -> Result<()> {
+        buf.try_put(&value.to_be_bytes()).context(EncodeValue)?;
+        Ok(())
+    }
+
+    fn estimate_encoded_size(&self, _value: &i128) -> usize {
+        mem::size_of::<i128>()
+    }
+}
+
+impl DecodeTo<i128> for MemCompactDecoder {
+    type Error = Error;
+
+    fn decode_to<B: Buf>(&self, buf: &mut B, value: &mut i128) -> Result<()> {
+        let mut bytes = [0; mem::size_of::<i128>()];
+        buf.try_copy_to_slice(&mut bytes).context(DecodeValue)?;
+        *value = i128::from_be_bytes(bytes);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -172,4 +202,24 @@ mod test {
             assert_eq!(d, x.data);
         }
     }
+
+    #[test]
+    fn test_compact_i128_codec() {
+        let data = [0i128, 1, -1, i128::MIN, i128::MAX, 123456789012345678901234];
+
+        let encoder = MemCompactEncoder;
+        let mut buf = vec![];
+        for x in &data {
+            encoder.encode(&mut buf, x).unwrap();
+            assert_eq!(16, encoder.estimate_encoded_size(x));
+        }
+
+        let decoder = MemCompactDecoder;
+        let mut buf = &buf[..];
+        for x in &data {
+            let mut d = 0i128;
+            decoder.decode_to(&mut buf, &mut d).unwrap();
+            assert_eq!(d, *x);
+        }
+    }
 }
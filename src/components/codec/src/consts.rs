@@ -25,6 +25,7 @@ pub const COMPACT_BYTES_FLAG: u8 = 2;
 pub const INT_FLAG: u8 = 3;
 pub const UINT_FLAG: u8 = 4;
 pub const FLOAT_FLAG: u8 = 5;
+pub const DECIMAL_FLAG: u8 = 6;
 pub const VARINT_FLAG: u8 = 8;
 pub const UVARINT_FLAG: u8 = 9;
 
@@ -22,6 +22,7 @@ use std::i64;
 use bytes_ext::{Buf, BufMut, BytesMut, SafeBufMut};
 use common_types::{
     datum::{Datum, DatumKind},
+    decimal::Decimal,
     string::StringBytes,
     time::Timestamp,
 };
@@ -97,6 +98,12 @@ impl Encoder<Datum> for MemComparable {
                 buf.try_put_u8(consts::INT_FLAG).context(EncodeKey)?;
                 self.encode(buf, v)
             }
+            // Only the value is encoded, the precision and scale are the same as the
+            // column.
+            Datum::Decimal(v) => {
+                buf.try_put_u8(consts::DECIMAL_FLAG).context(EncodeKey)?;
+                self.encode(buf, &v.value())
+            }
             Datum::Double(_) => UnsupportedKind {
                 kind: DatumKind::Double,
             }
@@ -105,6 +112,14 @@ impl Encoder<Datum> for MemComparable {
                 kind: DatumKind::Float,
             }
             .fail(),
+            Datum::Json(_) => UnsupportedKind {
+                kind: DatumKind::Json,
+            }
+            .fail(),
+            Datum::Array(_) => UnsupportedKind {
+                kind: DatumKind::Array,
+            }
+            .fail(),
         }
     }
 
@@ -123,11 +138,12 @@ impl Encoder<Datum> for MemComparable {
             Datum::Int32(v) => self.estimate_encoded_size(&(i64::from(*v))),
             Datum::Date(v) => self.estimate_encoded_size(&(i64::from(*v))),
             Datum::Time(v) => self.estimate_encoded_size(v),
+            Datum::Decimal(v) => self.estimate_encoded_size(&v.value()),
             Datum::Int16(v) => self.estimate_encoded_size(&(i64::from(*v))),
             Datum::Int8(v) => self.estimate_encoded_size(&(i64::from(*v))),
             Datum::Boolean(v) => self.estimate_encoded_size(&(u64::from(*v))),
             // Unsupported kind, but we return 1
-            Datum::Double(_) | Datum::Float(_) => 1,
+            Datum::Double(_) | Datum::Float(_) | Datum::Json(_) | Datum::Array(_) => 1,
         }
     }
 }
@@ -210,6 +226,12 @@ impl DecodeTo<Datum> for MemComparable {
                 Self::ensure_flag(buf, consts::INT_FLAG)?;
                 self.decode_to(buf, v)?;
             }
+            Datum::Decimal(v) => {
+                Self::ensure_flag(buf, consts::DECIMAL_FLAG)?;
+                let mut data = 0i128;
+                self.decode_to(buf, &mut data)?;
+                *v = Decimal::new_unchecked(data, v.decimal_type());
+            }
             Datum::Int16(v) => decode_i64_into!(self, v, buf, i16),
             Datum::Int8(v) => decode_i64_into!(self, v, buf, i8),
             Datum::Boolean(v) => decode_u64_into_bool!(self, v, buf),
@@ -225,6 +247,18 @@ impl DecodeTo<Datum> for MemComparable {
                 }
                 .fail();
             }
+            Datum::Json(_) => {
+                return UnsupportedKind {
+                    kind: DatumKind::Json,
+                }
+                .fail();
+            }
+            Datum::Array(_) => {
+                return UnsupportedKind {
+                    kind: DatumKind::Array,
+                }
+                .fail();
+            }
         }
         Ok(())
     }
@@ -235,9 +269,14 @@ mod tests {
     use core::cmp::Ordering;
 
     use bytes_ext::Bytes;
+    use common_types::decimal::DecimalType;
 
     use super::*;
 
+    fn decimal_type() -> DecimalType {
+        DecimalType::try_new(10, 2).unwrap()
+    }
+
     #[test]
     fn test_datum_codec() {
         let data = vec![
@@ -258,6 +297,10 @@ mod tests {
             (Datum::Boolean(false), 9),
             (Datum::Date(1000), 9),
             (Datum::Time(100000000), 9),
+            (
+                Datum::Decimal(Decimal::parse("-12.34", decimal_type()).unwrap()),
+                17,
+            ),
         ];
         let mut decoded = vec![
             Datum::Null,
@@ -276,6 +319,7 @@ mod tests {
             Datum::Boolean(false),
             Datum::Date(0),
             Datum::Time(0),
+            Datum::Decimal(Decimal::new_unchecked(0, decimal_type())),
         ];
         let c = MemComparable;
         for (index, x) in data.iter().enumerate() {
@@ -319,6 +363,11 @@ mod tests {
                 Datum::Time(1000000000),
                 Ordering::Greater,
             ),
+            (
+                Datum::Decimal(Decimal::parse("-0.01", decimal_type()).unwrap()),
+                Datum::Decimal(Decimal::parse("0.01", decimal_type()).unwrap()),
+                Ordering::Less,
+            ),
         ];
         let c = MemComparable;
         for x in &data {
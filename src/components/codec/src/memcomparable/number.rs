@@ -83,6 +83,34 @@ impl DecodeTo<u64> for MemComparable {
     }
 }
 
+/// The i128 is encoded as the comparable high 64 bits followed by the low 64
+/// bits.
+impl Encoder<i128> for MemComparable {
+    type Error = Error;
+
+    fn encode<B: SafeBufMut>(&self, buf: &mut B, value: &i128) -> Result<()> {
+        self.encode(buf, &((*value >> 64) as i64))?;
+        self.encode(buf, &(*value as u64))
+    }
+
+    fn estimate_encoded_size(&self, _value: &i128) -> usize {
+        // flag + u64 + u64
+        17
+    }
+}
+
+impl DecodeTo<i128> for MemComparable {
+    type Error = Error;
+
+    fn decode_to<B: SafeBuf>(&self, buf: &mut B, value: &mut i128) -> Result<()> {
+        let (mut high, mut low) = (0i64, 0u64);
+        self.decode_to(buf, &mut high)?;
+        self.decode_to(buf, &mut low)?;
+        *value = ((high as i128) << 64) | low as i128;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use core::cmp::Ordering;
@@ -186,6 +214,40 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_i128_codec_and_order() {
+        let data = [
+            i128::MIN,
+            i128::MIN + 1,
+            -(1 << 64) - 1,
+            -(1 << 64),
+            -1,
+            0,
+            1,
+            u64::MAX as i128,
+            1 << 64,
+            i128::MAX,
+        ];
+        let c = MemComparable;
+        let encoded = data
+            .iter()
+            .map(|v| {
+                let mut buf = vec![];
+                c.encode(&mut buf, v).unwrap();
+                buf
+            })
+            .collect::<Vec<_>>();
+
+        for (v, buf) in data.iter().zip(&encoded) {
+            let mut d = 0i128;
+            c.decode_to(&mut &buf[..], &mut d).unwrap();
+            assert_eq!(*v, d);
+        }
+        for pair in encoded.windows(2) {
+            assert_eq!(Ordering::Less, pair[0].cmp(&pair[1]));
+        }
+    }
+
     struct TblI64 {
         arg1: i64,
         arg2: i64,
@@ -123,11 +123,10 @@ impl<'a> Decoder<Row> for WalRowDecoder<'a> {
 
         for idx in 0..num_columns {
             let column_schema = &self.schema.column(idx);
-            let datum_kind = &column_schema.data_type;
             let decoder = MemCompactDecoder;
 
             // Decode each column
-            let mut datum = Datum::empty(datum_kind);
+            let mut datum = column_schema.empty_datum();
             decoder.decode_to(buf, &mut datum).context(DecodeRowDatum)?;
 
             datums.push(datum);
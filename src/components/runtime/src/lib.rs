@@ -152,6 +152,27 @@ impl<T> Drop for AbortOnDropMany<T> {
     }
 }
 
+/// Helper that aborts the given join handle on drop.
+///
+/// The handle can be awaited through the helper, so the task is killed once
+/// the awaiting future is dropped.
+#[derive(Debug)]
+pub struct AbortOnDrop<T>(pub JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.inner.abort();
+    }
+}
+
+impl<T> Future for AbortOnDrop<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(ctx)
+    }
+}
+
 /// Runtime statistics
 pub struct RuntimeStats {
     pub alive_thread_num: i64,
@@ -324,4 +345,21 @@ mod tests {
 
         assert_eq!(2, rt.block_on(handle).unwrap());
     }
+
+    #[test]
+    fn test_abort_on_drop() {
+        let rt = rt();
+        let handle = AbortOnDrop(rt.spawn(async { 1 + 1 }));
+        assert_eq!(2, rt.block_on(handle).unwrap());
+
+        let (tx, rx) = oneshot::channel::<()>();
+        let handle = AbortOnDrop(rt.spawn(async move {
+            // Hold the sender until the task is aborted.
+            let _tx = tx;
+            std::future::pending::<()>().await
+        }));
+        drop(handle);
+        // The sender is dropped along with the aborted task.
+        assert!(rt.block_on(rx).is_err());
+    }
 }
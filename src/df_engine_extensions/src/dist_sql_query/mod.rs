@@ -15,10 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::{
-    fmt,
-    sync::{Arc, Mutex},
-};
+use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
 use common_types::projected_schema::ProjectedSchema;
@@ -30,7 +27,11 @@ use datafusion::{
 use futures::future::BoxFuture;
 use generic_error::BoxError;
 use runtime::Priority;
-use table_engine::{predicate::PredicateRef, remote::model::TableIdentifier, table::TableRef};
+use table_engine::{
+    predicate::PredicateRef,
+    remote::model::{RemoteMetricsRef, TableIdentifier},
+    table::TableRef,
+};
 
 pub mod codec;
 pub mod physical_plan;
@@ -68,14 +69,14 @@ type ExecutableScanBuilderRef = Box<dyn ExecutableScanBuilder>;
 
 pub struct RemoteTaskContext {
     pub task_ctx: Arc<TaskContext>,
-    pub remote_metrics: Arc<Mutex<Option<String>>>,
+    pub remote_metrics: RemoteMetricsRef,
     pub is_analyze: bool,
 }
 
 impl RemoteTaskContext {
     pub fn new(
         task_ctx: Arc<TaskContext>,
-        remote_metrics: Arc<Mutex<Option<String>>>,
+        remote_metrics: RemoteMetricsRef,
         is_analyze: bool,
     ) -> Self {
         Self {
@@ -47,7 +47,10 @@ use datafusion::{
 };
 use futures::{future::BoxFuture, FutureExt, Stream, StreamExt};
 use runtime::Priority;
-use table_engine::{remote::model::TableIdentifier, table::ReadRequest};
+use table_engine::{
+    remote::model::{RemoteMetricsRef, TableIdentifier},
+    table::ReadRequest,
+};
 use trace_metric::{collector::FormatCollectorVisitor, MetricsCollector, TraceMetricWhenDrop};
 
 use crate::dist_sql_query::{RemotePhysicalPlanExecutor, RemoteTaskContext, TableScanContext};
@@ -259,6 +262,12 @@ impl ResolvedPartitionedScan {
     }
 }
 
+/// Whether the plan is the executable scan of the partitioned table, whose
+/// children are executed remotely and can't be replaced.
+pub fn is_resolved_partitioned_scan(plan: &dyn ExecutionPlan) -> bool {
+    plan.as_any().is::<ResolvedPartitionedScan>()
+}
+
 #[derive(Debug)]
 pub struct RemoteExecContext {
     executor: Arc<dyn RemotePhysicalPlanExecutor>,
@@ -270,7 +279,7 @@ pub(crate) struct SubTablePlanContext {
     table: TableIdentifier,
     plan: Arc<dyn ExecutionPlan>,
     metrics_collector: MetricsCollector,
-    remote_metrics: Arc<Mutex<Option<String>>>,
+    remote_metrics: RemoteMetricsRef,
 }
 
 impl SubTablePlanContext {
@@ -380,10 +389,10 @@ impl ExecutionPlan for ResolvedPartitionedScan {
 
         // collect metrics from remote
         for sub_table_ctx in &self.remote_exec_ctx.plan_ctxs {
-            if let Some(remote_metrics) = sub_table_ctx.remote_metrics.lock().unwrap().take() {
+            if let Some(remote_metrics) = &*sub_table_ctx.remote_metrics.lock().unwrap() {
                 metrics_desc.push_str(&format!(
-                    "\n{}:\n{}",
-                    sub_table_ctx.table.table, remote_metrics
+                    "\n{}, endpoint:{}, metrics:\n{}",
+                    sub_table_ctx.table.table, remote_metrics.endpoint, remote_metrics.metrics
                 ));
             }
         }
@@ -656,7 +665,9 @@ pub enum PushDownEvent {
 }
 
 impl PushDownEvent {
-    // Those aggregate functions can't be pushed down.
+    // Those aggregate functions can't be pushed down, and
+    // `approx_percentile_sketch` whose partial states are mergeable can be used
+    // instead.
     // https://github.com/apache/incubator-horaedb/issues/1405
     fn blacklist_expr(expr: &dyn Any) -> bool {
         expr.is::<ApproxPercentileCont>() || expr.is::<ApproxPercentileContWithWeight>()
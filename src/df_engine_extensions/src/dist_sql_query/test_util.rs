@@ -436,6 +436,7 @@ impl ExecutableScanBuilder for MockScanBuilder {
                 batch_size: ctx.batch_size,
                 read_parallelism: ctx.read_parallelism,
                 deadline: None,
+                as_of: None,
             },
             projected_schema: ctx.projected_schema.clone(),
             predicate: ctx.predicate.clone(),
@@ -38,6 +38,9 @@ pub enum Error {
     #[snafu(display("Failed to get state, err:{}", source))]
     GetState { source: GenericError },
 
+    #[snafu(display("Failed to update state, err:{}", source))]
+    UpdateState { source: GenericError },
+
     #[snafu(display("Failed to merge state, err:{}", source))]
     MergeState { source: GenericError },
 }
@@ -23,7 +23,10 @@ use std::{
 };
 
 use arrow::datatypes::DataType;
-use common_types::{column_block::ColumnBlock, datum::DatumKind};
+use common_types::{
+    column_block::ColumnBlock,
+    datum::{Datum, DatumKind},
+};
 use datafusion::{
     error::DataFusionError,
     logical_expr::{
@@ -80,6 +83,11 @@ impl ScalarValue {
             _ => None,
         }
     }
+
+    /// Convert into the datum, returns None if the type is not supported.
+    pub fn to_datum(&self) -> Option<Datum> {
+        Datum::from_scalar_value(&self.0)
+    }
 }
 
 impl From<String> for ScalarValue {
@@ -94,6 +102,12 @@ impl From<u64> for ScalarValue {
     }
 }
 
+impl From<Option<f64>> for ScalarValue {
+    fn from(value: Option<f64>) -> Self {
+        Self(DfScalarValue::Float64(value))
+    }
+}
+
 pub struct ScalarValueRef<'a>(&'a DfScalarValue);
 
 impl<'a> ScalarValueRef<'a> {
@@ -165,6 +179,9 @@ pub enum TypeSignature {
     Uniform(usize, Vec<DatumKind>),
     /// One of a list of signatures
     OneOf(Vec<TypeSignature>),
+    /// fixed number of arguments of arbitrary types, which is used if the type
+    /// can't be represented by the [DatumKind], e.g. the array of any element
+    Any(usize),
 }
 
 impl TypeSignature {
@@ -189,6 +206,7 @@ impl TypeSignature {
                     .collect();
                 DfTypeSignature::OneOf(df_sigs)
             }
+            TypeSignature::Any(num) => DfTypeSignature::Any(*num),
         }
     }
 }
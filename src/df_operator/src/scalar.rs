@@ -41,6 +41,14 @@ impl ScalarUdf {
         Self { df_udf }
     }
 
+    /// Create from the datafusion udf directly, used when the return type
+    /// depends on the types of the arguments.
+    pub fn from_datafusion_udf(df_udf: ScalarUDF) -> Self {
+        Self {
+            df_udf: Arc::new(df_udf),
+        }
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         &self.df_udf.name
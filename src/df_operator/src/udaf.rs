@@ -48,6 +48,14 @@ impl AggregateUdf {
         Self { df_udaf }
     }
 
+    /// Create from the datafusion udaf directly, used when the return type
+    /// depends on the types of the arguments.
+    pub fn from_datafusion_udaf(df_udaf: AggregateUDF) -> Self {
+        Self {
+            df_udaf: Arc::new(df_udaf),
+        }
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         &self.df_udaf.name
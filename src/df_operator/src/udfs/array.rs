@@ -0,0 +1,194 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Array UDFs, including array_contains.
+
+use common_types::{
+    column_block::{ColumnBlock, ColumnBlockBuilder},
+    datum::{Datum, DatumKind},
+};
+use generic_error::BoxError;
+use macros::define_result;
+use snafu::{ensure, ResultExt, Snafu};
+
+use crate::{
+    functions::{CallFunction, ColumnarValue, InvalidArguments, ScalarFunction, TypeSignature},
+    registry::{self, FunctionRegistry},
+    scalar::ScalarUdf,
+};
+
+/// Check whether the array contains the value.
+pub const ARRAY_CONTAINS: &str = "array_contains";
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid argument number, expect:{}, given:{}.", expect, given))]
+    InvalidArgNum { expect: usize, given: usize },
+
+    #[snafu(display("Invalid arguments, require an array, given:{}.", given))]
+    NotArray { given: DatumKind },
+
+    #[snafu(display("Failed to build result column, err:{}", source))]
+    BuildColumn {
+        source: common_types::column_block::Error,
+    },
+}
+
+define_result!(Error);
+
+pub fn register_to_registry(registry: &mut dyn FunctionRegistry) -> registry::Result<()> {
+    registry.register_udf(new_array_contains_udf())
+}
+
+fn new_array_contains_udf() -> ScalarUdf {
+    // args:
+    // - array.
+    // - value to find.
+    let func = |args: &[ColumnarValue]| {
+        let contains = ArrayContains::parse_args(args)
+            .box_err()
+            .context(InvalidArguments)?;
+
+        let result_column = contains.call().box_err().context(CallFunction)?;
+
+        Ok(ColumnarValue::Array(result_column))
+    };
+
+    // The element type of the array is arbitrary, which can't be represented by the
+    // datum kind.
+    let signature = TypeSignature::Any(2);
+    let scalar_function = ScalarFunction::make_by_fn(signature, DatumKind::Boolean, func);
+
+    ScalarUdf::create(ARRAY_CONTAINS, scalar_function)
+}
+
+/// Get the datum of the argument at the `row`, the scalar argument is shared by
+/// all rows.
+fn datum_at(arg: &ColumnarValue, row: usize) -> Datum {
+    match arg {
+        ColumnarValue::Array(block) => block.datum(row),
+        ColumnarValue::Scalar(value) => value.to_datum().unwrap_or(Datum::Null),
+    }
+}
+
+struct ArrayContains<'a> {
+    arrays: &'a ColumnarValue,
+    values: &'a ColumnarValue,
+    num_rows: usize,
+}
+
+impl<'a> ArrayContains<'a> {
+    fn parse_args(args: &[ColumnarValue]) -> Result<ArrayContains> {
+        ensure!(
+            args.len() == 2,
+            InvalidArgNum {
+                expect: 2usize,
+                given: args.len(),
+            }
+        );
+
+        if let ColumnarValue::Array(block) = &args[0] {
+            let given = block.datum_kind();
+            ensure!(
+                matches!(given, DatumKind::Array | DatumKind::Null),
+                NotArray { given }
+            );
+        }
+
+        // The number of rows to compute, it's one if all arguments are scalar.
+        let num_rows = args
+            .iter()
+            .find_map(|arg| match arg {
+                ColumnarValue::Array(block) => Some(block.num_rows()),
+                ColumnarValue::Scalar(_) => None,
+            })
+            .unwrap_or(1);
+
+        Ok(ArrayContains {
+            arrays: &args[0],
+            values: &args[1],
+            num_rows,
+        })
+    }
+
+    fn call(&self) -> Result<ColumnBlock> {
+        let mut builder =
+            ColumnBlockBuilder::with_capacity(&DatumKind::Boolean, self.num_rows, false);
+        for row in 0..self.num_rows {
+            let array = datum_at(self.arrays, row);
+            let value = datum_at(self.values, row);
+            let datum = match (array, value) {
+                (Datum::Null, _) | (_, Datum::Null) => Datum::Null,
+                (Datum::Array(array), value) => Datum::Boolean(array.contains(&value)),
+                (array, _) => {
+                    return NotArray {
+                        given: array.kind(),
+                    }
+                    .fail()
+                }
+            };
+            builder.append(datum).context(BuildColumn)?;
+        }
+
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_types::array::ArrayValue;
+
+    use super::*;
+    use crate::functions::ScalarValue;
+
+    fn build_arrays(arrays: Vec<Option<Vec<Datum>>>) -> ColumnarValue {
+        let mut builder = ColumnBlockBuilder::with_capacity(&DatumKind::Array, 0, false);
+        for array in arrays {
+            let datum = array
+                .map(|v| Datum::Array(ArrayValue::try_new(DatumKind::String, v).unwrap()))
+                .unwrap_or(Datum::Null);
+            builder.append(datum).unwrap();
+        }
+
+        ColumnarValue::Array(builder.build())
+    }
+
+    #[test]
+    fn test_array_contains() {
+        let args = [
+            build_arrays(vec![
+                Some(vec![Datum::from("admin"), Datum::from("dev")]),
+                Some(vec![Datum::from("dev"), Datum::Null]),
+                Some(vec![]),
+                None,
+            ]),
+            ColumnarValue::Scalar(ScalarValue::from("admin".to_string())),
+        ];
+        let block = ArrayContains::parse_args(&args).unwrap().call().unwrap();
+        assert_eq!(Datum::Boolean(true), block.datum(0));
+        assert_eq!(Datum::Boolean(false), block.datum(1));
+        assert_eq!(Datum::Boolean(false), block.datum(2));
+        assert_eq!(Datum::Null, block.datum(3));
+
+        let args = [
+            ColumnarValue::Scalar(ScalarValue::from("admin".to_string())),
+            ColumnarValue::Scalar(ScalarValue::from("admin".to_string())),
+        ];
+        let contains = ArrayContains::parse_args(&args).unwrap();
+        assert!(contains.call().is_err());
+    }
+}
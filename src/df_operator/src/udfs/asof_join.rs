@@ -0,0 +1,54 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Marker UDF of the `ASOF JOIN` conditions.
+//!
+//! The sql parser wraps the condition of an `ASOF JOIN` into
+//! `asof_join_condition(condition)`, and the join is planned into the asof join
+//! plan extension according to it.
+
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnTypeFunction, ScalarFunctionImplementation, ScalarUDF, Signature,
+    Volatility,
+};
+
+use crate::{
+    registry::{self, FunctionRegistry},
+    scalar::ScalarUdf,
+};
+
+pub const ASOF_JOIN_CONDITION: &str = "asof_join_condition";
+
+pub fn register_to_registry(registry: &mut dyn FunctionRegistry) -> registry::Result<()> {
+    registry.register_udf(new_udf())
+}
+
+fn new_udf() -> ScalarUdf {
+    let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Boolean)));
+    let func: ScalarFunctionImplementation = Arc::new(|args: &[ColumnarValue]| Ok(args[0].clone()));
+    let signature = Signature::exact(vec![DataType::Boolean], Volatility::Immutable);
+
+    ScalarUdf::from_datafusion_udf(ScalarUDF::new(
+        ASOF_JOIN_CONDITION,
+        &signature,
+        &return_type,
+        &func,
+    ))
+}
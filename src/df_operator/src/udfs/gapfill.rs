@@ -0,0 +1,150 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Gap filling UDFs.
+//!
+//! - `time_bucket_gapfill(timestamp, period)`: same as `time_bucket`, and marks
+//!   the aggregation grouped by it to be gap filled.
+//! - `locf(value)`: fills the gaps with the last observed value.
+//! - `interpolate(value)`: fills the gaps by linear interpolation.
+//!
+//! These functions are only markers and return their input as is, the gaps
+//! are filled by the gap fill plan extension.
+
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+use common_types::time::Timestamp;
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnTypeFunction, ScalarFunctionImplementation, ScalarUDF, Signature,
+    Volatility,
+};
+use macros::define_result;
+use snafu::{Backtrace, Snafu};
+
+use crate::{
+    registry::{self, FunctionRegistry},
+    scalar::ScalarUdf,
+    udfs::time_bucket::{self, Period},
+};
+
+pub const TIME_BUCKET_GAPFILL: &str = "time_bucket_gapfill";
+pub const LOCF: &str = "locf";
+pub const INTERPOLATE: &str = "interpolate";
+
+const DAY_MILLIS: i64 = 24 * 3600 * 1000;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid gap fill period, period:{period}, msg:{msg}"))]
+    InvalidPeriod { period: String, msg: String },
+
+    #[snafu(display(
+        "Unsupported gap fill period, only fixed periods are supported, period:{period}.\nBacktrace:\n{backtrace}"
+    ))]
+    UnsupportedPeriod {
+        period: String,
+        backtrace: Backtrace,
+    },
+}
+
+define_result!(Error);
+
+pub fn register_to_registry(registry: &mut dyn FunctionRegistry) -> registry::Result<()> {
+    registry.register_udf(time_bucket::new_udf_with_name(TIME_BUCKET_GAPFILL))?;
+    registry.register_udf(new_fill_udf(LOCF))?;
+    registry.register_udf(new_fill_udf(INTERPOLATE))
+}
+
+fn new_fill_udf(name: &str) -> ScalarUdf {
+    let return_type: ReturnTypeFunction =
+        Arc::new(|arg_types: &[DataType]| Ok(Arc::new(arg_types[0].clone())));
+    let func: ScalarFunctionImplementation = Arc::new(|args: &[ColumnarValue]| Ok(args[0].clone()));
+    let signature = Signature::any(1, Volatility::Immutable);
+
+    ScalarUdf::from_datafusion_udf(ScalarUDF::new(name, &signature, &return_type, &func))
+}
+
+/// Period of the gap filling buckets, whose stride must be fixed.
+#[derive(Debug, Clone, Copy)]
+pub struct GapFillPeriod {
+    period: Period,
+    stride_ms: i64,
+}
+
+impl GapFillPeriod {
+    pub fn parse(period: &str) -> Result<Self> {
+        const SECOND_MILLIS: i64 = 1000;
+
+        let parsed = Period::parse(period).map_err(|e| Error::InvalidPeriod {
+            period: period.to_string(),
+            msg: e.to_string(),
+        })?;
+        let stride_ms = match parsed {
+            Period::Second(n) => i64::from(n) * SECOND_MILLIS,
+            Period::Minute(n) => i64::from(n) * 60 * SECOND_MILLIS,
+            Period::Hour(n) => i64::from(n) * 3600 * SECOND_MILLIS,
+            Period::Day(1) => DAY_MILLIS,
+            Period::Week => 7 * DAY_MILLIS,
+            Period::Day(_) | Period::Month | Period::Year => {
+                return UnsupportedPeriod { period }.fail()
+            }
+        };
+        if stride_ms <= 0 {
+            return UnsupportedPeriod { period }.fail();
+        }
+
+        Ok(Self {
+            period: parsed,
+            stride_ms,
+        })
+    }
+
+    #[inline]
+    pub fn stride_ms(&self) -> i64 {
+        self.stride_ms
+    }
+
+    /// Truncate the timestamp to the start of its bucket, same as
+    /// `time_bucket`.
+    pub fn truncate(&self, ts: i64) -> i64 {
+        self.period
+            .truncate(Timestamp::new(ts))
+            .map(|ts| ts.as_i64())
+            .unwrap_or(ts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gapfill_period() {
+        let period = GapFillPeriod::parse("PT5M").unwrap();
+        assert_eq!(300_000, period.stride_ms());
+        assert_eq!(1_689_999_900_000, period.truncate(1_690_000_000_000));
+
+        let period = GapFillPeriod::parse("P1D").unwrap();
+        assert_eq!(DAY_MILLIS, period.stride_ms());
+
+        assert!(GapFillPeriod::parse("P1M").is_err());
+        assert!(GapFillPeriod::parse("P2D").is_err());
+        assert!(GapFillPeriod::parse("PT0S").is_err());
+        assert!(GapFillPeriod::parse("1m").is_err());
+    }
+}
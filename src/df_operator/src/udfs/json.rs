@@ -0,0 +1,326 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Json UDFs, including json_extract, json_extract_string and json_contains.
+
+use common_types::{
+    column_block::{ColumnBlock, ColumnBlockBuilder},
+    datum::{Datum, DatumKind},
+    json::{self, JsonPath},
+    string::StringBytes,
+};
+use generic_error::BoxError;
+use macros::define_result;
+use serde_json::Value;
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
+
+use crate::{
+    functions::{CallFunction, ColumnarValue, InvalidArguments, ScalarFunction, TypeSignature},
+    registry::{self, FunctionRegistry},
+    scalar::ScalarUdf,
+};
+
+/// Extract the value at the path as json, e.g. the string is quoted.
+pub const JSON_EXTRACT: &str = "json_extract";
+/// Extract the value at the path as plain string, e.g. the string is unquoted.
+pub const JSON_EXTRACT_STRING: &str = "json_extract_string";
+/// Check whether the candidate is contained in the json document.
+pub const JSON_CONTAINS: &str = "json_contains";
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid argument number, expect:{}, given:{}.", expect, given))]
+    InvalidArgNum { expect: &'static str, given: usize },
+
+    #[snafu(display("Invalid arguments, require a constant json path."))]
+    NotPath,
+
+    #[snafu(display("Invalid json path, err:{}", source))]
+    InvalidPath { source: json::Error },
+
+    #[snafu(display("Invalid json document, err:{}", source))]
+    InvalidJson { source: json::Error },
+
+    #[snafu(display("Failed to build result column, err:{}", source))]
+    BuildColumn {
+        source: common_types::column_block::Error,
+    },
+}
+
+define_result!(Error);
+
+pub fn register_to_registry(registry: &mut dyn FunctionRegistry) -> registry::Result<()> {
+    registry.register_udf(new_json_extract_udf(JSON_EXTRACT, false))?;
+    registry.register_udf(new_json_extract_udf(JSON_EXTRACT_STRING, true))?;
+    registry.register_udf(new_json_contains_udf())
+}
+
+fn new_json_extract_udf(name: &str, unquote: bool) -> ScalarUdf {
+    // args:
+    // - json document.
+    // - json path.
+    let func = move |args: &[ColumnarValue]| {
+        let extract = JsonExtract::parse_args(args, unquote)
+            .box_err()
+            .context(InvalidArguments)?;
+
+        let result_column = extract.call().box_err().context(CallFunction)?;
+
+        Ok(ColumnarValue::Array(result_column))
+    };
+
+    // The json column is mapped to string in the datafusion.
+    let signature = TypeSignature::Exact(vec![DatumKind::String, DatumKind::String]);
+    let scalar_function = ScalarFunction::make_by_fn(signature, DatumKind::String, func);
+
+    ScalarUdf::create(name, scalar_function)
+}
+
+fn new_json_contains_udf() -> ScalarUdf {
+    // args:
+    // - json document.
+    // - candidate json document.
+    // - json path (optional).
+    let func = |args: &[ColumnarValue]| {
+        let contains = JsonContains::parse_args(args)
+            .box_err()
+            .context(InvalidArguments)?;
+
+        let result_column = contains.call().box_err().context(CallFunction)?;
+
+        Ok(ColumnarValue::Array(result_column))
+    };
+
+    let signature = TypeSignature::OneOf(vec![
+        TypeSignature::Exact(vec![DatumKind::String, DatumKind::String]),
+        TypeSignature::Exact(vec![
+            DatumKind::String,
+            DatumKind::String,
+            DatumKind::String,
+        ]),
+    ]);
+    let scalar_function = ScalarFunction::make_by_fn(signature, DatumKind::Boolean, func);
+
+    ScalarUdf::create(JSON_CONTAINS, scalar_function)
+}
+
+/// Get the string value of the argument at the `row`, the scalar argument is
+/// shared by all rows.
+fn str_at(arg: &ColumnarValue, row: usize) -> Option<&str> {
+    match arg {
+        ColumnarValue::Array(block) => block.datum_view(row).into_str(),
+        ColumnarValue::Scalar(value) => value.as_str(),
+    }
+}
+
+/// The number of rows to compute, it's one if all arguments are scalar.
+fn num_rows(args: &[ColumnarValue]) -> usize {
+    args.iter()
+        .find_map(|arg| match arg {
+            ColumnarValue::Array(block) => Some(block.num_rows()),
+            ColumnarValue::Scalar(_) => None,
+        })
+        .unwrap_or(1)
+}
+
+fn parse_path_arg(arg: &ColumnarValue) -> Result<JsonPath> {
+    match arg {
+        ColumnarValue::Scalar(value) => {
+            let path = value.as_str().context(NotPath)?;
+            JsonPath::parse(path).context(InvalidPath)
+        }
+        ColumnarValue::Array(_) => NotPath.fail(),
+    }
+}
+
+struct JsonExtract<'a> {
+    docs: &'a ColumnarValue,
+    path: JsonPath,
+    /// Whether to return the scalar value without quotes.
+    unquote: bool,
+    num_rows: usize,
+}
+
+impl<'a> JsonExtract<'a> {
+    fn parse_args(args: &[ColumnarValue], unquote: bool) -> Result<JsonExtract> {
+        ensure!(
+            args.len() == 2,
+            InvalidArgNum {
+                expect: "2",
+                given: args.len(),
+            }
+        );
+
+        Ok(JsonExtract {
+            docs: &args[0],
+            path: parse_path_arg(&args[1])?,
+            unquote,
+            num_rows: num_rows(args),
+        })
+    }
+
+    fn call(&self) -> Result<ColumnBlock> {
+        let mut builder =
+            ColumnBlockBuilder::with_capacity(&DatumKind::String, self.num_rows, false);
+        for row in 0..self.num_rows {
+            let extracted = match str_at(self.docs, row) {
+                Some(doc) => {
+                    let doc = json::parse(doc).context(InvalidJson)?;
+                    self.path.extract(&doc).and_then(|v| {
+                        if self.unquote {
+                            json::value_to_string(v)
+                        } else {
+                            Some(v.to_string())
+                        }
+                    })
+                }
+                None => None,
+            };
+
+            let datum = extracted
+                .map(|v| Datum::String(StringBytes::from(v)))
+                .unwrap_or(Datum::Null);
+            builder.append(datum).context(BuildColumn)?;
+        }
+
+        Ok(builder.build())
+    }
+}
+
+struct JsonContains<'a> {
+    docs: &'a ColumnarValue,
+    candidates: &'a ColumnarValue,
+    path: Option<JsonPath>,
+    num_rows: usize,
+}
+
+impl<'a> JsonContains<'a> {
+    fn parse_args(args: &[ColumnarValue]) -> Result<JsonContains> {
+        ensure!(
+            args.len() == 2 || args.len() == 3,
+            InvalidArgNum {
+                expect: "2 or 3",
+                given: args.len(),
+            }
+        );
+
+        let path = args.get(2).map(parse_path_arg).transpose()?;
+        Ok(JsonContains {
+            docs: &args[0],
+            candidates: &args[1],
+            path,
+            num_rows: num_rows(args),
+        })
+    }
+
+    fn call(&self) -> Result<ColumnBlock> {
+        let mut builder =
+            ColumnBlockBuilder::with_capacity(&DatumKind::Boolean, self.num_rows, false);
+        for row in 0..self.num_rows {
+            let contained = match (str_at(self.docs, row), str_at(self.candidates, row)) {
+                (Some(doc), Some(candidate)) => {
+                    let doc = json::parse(doc).context(InvalidJson)?;
+                    let candidate = json::parse(candidate).context(InvalidJson)?;
+                    self.contains(&doc, &candidate)
+                }
+                _ => None,
+            };
+
+            let datum = contained.map(Datum::Boolean).unwrap_or(Datum::Null);
+            builder.append(datum).context(BuildColumn)?;
+        }
+
+        Ok(builder.build())
+    }
+
+    /// Returns None if the path doesn't exist in the document.
+    fn contains(&self, doc: &Value, candidate: &Value) -> Option<bool> {
+        let target = match &self.path {
+            Some(path) => path.extract(doc)?,
+            None => doc,
+        };
+
+        Some(json::contains(target, candidate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_types::column_block::StringColumn;
+
+    use super::*;
+    use crate::functions::ScalarValue;
+
+    fn build_docs(docs: &[Option<&str>]) -> ColumnarValue {
+        let array = arrow::array::StringArray::from(docs.to_vec());
+        ColumnarValue::Array(StringColumn::from(array).into())
+    }
+
+    #[test]
+    fn test_json_extract() {
+        let args = [
+            build_docs(&[
+                Some(r#"{"a": {"b": "x"}}"#),
+                Some(r#"{"a": {"b": [1, 2]}}"#),
+                Some(r#"{"a": 1}"#),
+                None,
+            ]),
+            ColumnarValue::Scalar(ScalarValue::from("$.a.b".to_string())),
+        ];
+
+        let extract = JsonExtract::parse_args(&args, false).unwrap();
+        let block = extract.call().unwrap();
+        let expect = [Some(r#""x""#), Some("[1,2]"), None, None];
+        for (i, v) in expect.iter().enumerate() {
+            assert_eq!(*v, block.datum_view(i).into_str());
+        }
+
+        let extract = JsonExtract::parse_args(&args, true).unwrap();
+        let block = extract.call().unwrap();
+        let expect = [Some("x"), Some("[1,2]"), None, None];
+        for (i, v) in expect.iter().enumerate() {
+            assert_eq!(*v, block.datum_view(i).into_str());
+        }
+
+        let args = [
+            build_docs(&[Some("{}")]),
+            ColumnarValue::Scalar(ScalarValue::from("a.b".to_string())),
+        ];
+        assert!(JsonExtract::parse_args(&args, true).is_err());
+    }
+
+    #[test]
+    fn test_json_contains() {
+        let args = [
+            build_docs(&[Some(r#"{"a": 1, "b": [1, 2]}"#), Some(r#"{"a": 2}"#), None]),
+            ColumnarValue::Scalar(ScalarValue::from(r#"{"a": 1}"#.to_string())),
+        ];
+        let block = JsonContains::parse_args(&args).unwrap().call().unwrap();
+        assert_eq!(Datum::Boolean(true), block.datum(0));
+        assert_eq!(Datum::Boolean(false), block.datum(1));
+        assert_eq!(Datum::Null, block.datum(2));
+
+        let args = [
+            build_docs(&[Some(r#"{"a": 1, "b": [1, 2]}"#), Some(r#"{"a": 2}"#)]),
+            ColumnarValue::Scalar(ScalarValue::from("2".to_string())),
+            ColumnarValue::Scalar(ScalarValue::from("$.b".to_string())),
+        ];
+        let block = JsonContains::parse_args(&args).unwrap().call().unwrap();
+        assert_eq!(Datum::Boolean(true), block.datum(0));
+        assert_eq!(Datum::Null, block.datum(1));
+    }
+}
@@ -19,13 +19,25 @@
 
 use crate::registry::{FunctionRegistry, Result};
 
+pub mod array;
+pub mod asof_join;
+pub mod gapfill;
+pub mod json;
+mod percentile_sketch;
 mod thetasketch_distinct;
 mod time_bucket;
+pub mod time_series;
 
 pub fn register_all_udfs(registry: &mut dyn FunctionRegistry) -> Result<()> {
     // Register all udfs
     time_bucket::register_to_registry(registry)?;
+    gapfill::register_to_registry(registry)?;
+    asof_join::register_to_registry(registry)?;
     thetasketch_distinct::register_to_registry(registry)?;
+    percentile_sketch::register_to_registry(registry)?;
+    json::register_to_registry(registry)?;
+    array::register_to_registry(registry)?;
+    time_series::register_to_registry(registry)?;
 
     Ok(())
 }
@@ -0,0 +1,391 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! approx_percentile_sketch(value, percentile) udaf.
+//!
+//! Unlike `approx_percentile_cont`, the partial states of it are serialized
+//! sketches which can be merged, so the partial aggregation can be pushed down
+//! to the sub tables.
+
+use std::collections::BTreeMap;
+
+use arrow::datatypes::DataType;
+use common_types::datum::DatumKind;
+use generic_error::BoxError;
+use macros::define_result;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
+
+use crate::{
+    aggregate::{self, Accumulator, GetState, Input, MergeState, State, StateRef, UpdateState},
+    functions::{AggregateFunction, ScalarValue, TypeSignature},
+    registry::{self, FunctionRegistry},
+    udaf::AggregateUdf,
+};
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid argument number."))]
+    InvalidArgNum,
+
+    #[snafu(display("Invalid percentile, it must be a double in [0, 1]."))]
+    InvalidPercentile,
+
+    #[snafu(display("Inconsistent percentiles, expect:{expect}, given:{given}."))]
+    InconsistentPercentile { expect: f64, given: f64 },
+
+    #[snafu(display("Invalid state len."))]
+    InvalidStateLen,
+
+    #[snafu(display("Invalid state, state is not string."))]
+    StateNotString,
+
+    #[snafu(display("Failed to decode base64 of sketch, err:{}.", source))]
+    DecodeBase64 { source: base64::DecodeError },
+
+    #[snafu(display("Invalid state, failed to decode sketch, err:{}.", source))]
+    DecodeSketch { source: bincode::Error },
+
+    #[snafu(display("Invalid state, failed to encode sketch, err:{}.", source))]
+    EncodeSketch { source: bincode::Error },
+}
+
+define_result!(Error);
+
+pub const APPROX_PERCENTILE_SKETCH: &str = "approx_percentile_sketch";
+
+/// Relative error of the estimated percentiles.
+const RELATIVE_ACCURACY: f64 = 0.01;
+/// Values whose absolute value is less than it are treated as zero.
+const MIN_INDEXABLE_VALUE: f64 = 1e-9;
+
+pub fn register_to_registry(registry: &mut dyn FunctionRegistry) -> registry::Result<()> {
+    registry.register_udaf(new_udaf())
+}
+
+fn new_udaf() -> AggregateUdf {
+    let aggregate_function = new_function();
+
+    AggregateUdf::create(APPROX_PERCENTILE_SKETCH, aggregate_function)
+}
+
+pub(crate) fn new_function() -> AggregateFunction {
+    let accumulator_fn = |_: &DataType| Ok(PercentileSketch::default());
+    let type_signature = TypeSignature::Exact(vec![DatumKind::Double, DatumKind::Double]);
+    let state_type = vec![DatumKind::String];
+
+    AggregateFunction::make_by_fn(
+        type_signature,
+        DatumKind::Double,
+        state_type,
+        accumulator_fn,
+    )
+}
+
+/// Sketch based on [DDSketch](https://arxiv.org/abs/1908.10693).
+///
+/// The values are counted in logarithmic bins, so the estimated percentile has
+/// a relative error bounded by [RELATIVE_ACCURACY], and the sketches can be
+/// merged by adding the counts of the bins.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct DdSketch {
+    /// Bins of the positive values.
+    positive_bins: BTreeMap<i32, u64>,
+    /// Bins of the negative values, keyed by their absolute values.
+    negative_bins: BTreeMap<i32, u64>,
+    zero_count: u64,
+    count: u64,
+}
+
+impl DdSketch {
+    #[inline]
+    fn gamma() -> f64 {
+        (1.0 + RELATIVE_ACCURACY) / (1.0 - RELATIVE_ACCURACY)
+    }
+
+    #[inline]
+    fn key(value: f64) -> i32 {
+        (value.ln() / Self::gamma().ln()).ceil() as i32
+    }
+
+    /// The estimated value of the bin, whose relative error to any value in
+    /// the bin is at most [RELATIVE_ACCURACY].
+    #[inline]
+    fn value(key: i32) -> f64 {
+        let gamma = Self::gamma();
+        2.0 * gamma.powi(key) / (gamma + 1.0)
+    }
+
+    fn insert(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+
+        if value > MIN_INDEXABLE_VALUE {
+            *self.positive_bins.entry(Self::key(value)).or_default() += 1;
+        } else if value < -MIN_INDEXABLE_VALUE {
+            *self.negative_bins.entry(Self::key(-value)).or_default() += 1;
+        } else {
+            self.zero_count += 1;
+        }
+        self.count += 1;
+    }
+
+    fn merge(&mut self, other: &DdSketch) {
+        for (key, count) in &other.positive_bins {
+            *self.positive_bins.entry(*key).or_default() += count;
+        }
+        for (key, count) in &other.negative_bins {
+            *self.negative_bins.entry(*key).or_default() += count;
+        }
+        self.zero_count += other.zero_count;
+        self.count += other.count;
+    }
+
+    fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = q * (self.count - 1) as f64;
+        let mut seen = 0;
+        // The negative values are visited from the smallest one, whose absolute value
+        // is the largest.
+        for (key, count) in self.negative_bins.iter().rev() {
+            seen += count;
+            if seen as f64 > rank {
+                return Some(-Self::value(*key));
+            }
+        }
+        seen += self.zero_count;
+        if seen as f64 > rank {
+            return Some(0.0);
+        }
+        for (key, count) in &self.positive_bins {
+            seen += count;
+            if seen as f64 > rank {
+                return Some(Self::value(*key));
+            }
+        }
+
+        // Unreachable as the rank is less than the count.
+        None
+    }
+}
+
+/// The serialized state of [PercentileSketch].
+#[derive(Debug, Serialize, Deserialize)]
+struct SketchState {
+    /// It is none if no value is inserted into the sketch.
+    percentile: Option<f64>,
+    sketch: DdSketch,
+}
+
+/// Percentile estimator based on [DdSketch].
+///
+/// The percentile is carried in the state, because the final accumulator is
+/// only fed with the merged states.
+#[derive(Debug, Default)]
+struct PercentileSketch {
+    percentile: Option<f64>,
+    sketch: DdSketch,
+}
+
+impl PercentileSketch {
+    fn set_percentile(&mut self, percentile: f64) -> Result<()> {
+        ensure!((0.0..=1.0).contains(&percentile), InvalidPercentile);
+
+        match self.percentile {
+            Some(expect) => ensure!(
+                expect == percentile,
+                InconsistentPercentile {
+                    expect,
+                    given: percentile
+                }
+            ),
+            None => self.percentile = Some(percentile),
+        }
+
+        Ok(())
+    }
+
+    fn update_impl(&mut self, input: Input) -> Result<()> {
+        if input.is_empty() {
+            return Ok(());
+        }
+        ensure!(input.num_columns() == 2, InvalidArgNum);
+
+        let value_col = input.column(0).unwrap();
+        let percentile_col = input.column(1).unwrap();
+        let num_rows = value_col.num_rows();
+        if num_rows == 0 {
+            return Ok(());
+        }
+
+        // The percentile should be a constant, so only the first one is checked.
+        let percentile = percentile_col
+            .datum_view(0)
+            .as_f64()
+            .context(InvalidPercentile)?;
+        self.set_percentile(percentile)?;
+
+        for row_idx in 0..num_rows {
+            if let Some(value) = value_col.datum_view(row_idx).as_f64() {
+                self.sketch.insert(value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn merge_impl(&mut self, states: StateRef) -> Result<()> {
+        ensure!(states.num_columns() == 1, InvalidStateLen);
+        let merged_col = states.column(0).unwrap();
+
+        for row_idx in 0..merged_col.num_rows() {
+            let datum = merged_col.datum_view(row_idx);
+            let state_string = datum.into_str().context(StateNotString)?;
+            let state_bytes = base64::decode(state_string).context(DecodeBase64)?;
+            let state: SketchState = bincode::deserialize(&state_bytes).context(DecodeSketch)?;
+
+            if let Some(percentile) = state.percentile {
+                self.set_percentile(percentile)?;
+            }
+            self.sketch.merge(&state.sketch);
+        }
+
+        Ok(())
+    }
+}
+
+impl Accumulator for PercentileSketch {
+    fn state(&self) -> aggregate::Result<State> {
+        let state = SketchState {
+            percentile: self.percentile,
+            sketch: self.sketch.clone(),
+        };
+        let buf = bincode::serialize(&state)
+            .context(EncodeSketch)
+            .box_err()
+            .context(GetState)?;
+        // Same as the `thetasketch_distinct`, the binary is encoded into string by
+        // base64.
+        let state_string = base64::encode(buf);
+
+        Ok(State::from(ScalarValue::from(state_string)))
+    }
+
+    fn update(&mut self, input: Input) -> aggregate::Result<()> {
+        self.update_impl(input).box_err().context(UpdateState)
+    }
+
+    fn merge(&mut self, states: StateRef) -> aggregate::Result<()> {
+        self.merge_impl(states).box_err().context(MergeState)
+    }
+
+    fn evaluate(&self) -> aggregate::Result<ScalarValue> {
+        let value = self
+            .percentile
+            .and_then(|percentile| self.sketch.quantile(percentile));
+
+        Ok(ScalarValue::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Float64Array};
+    use datafusion::{
+        physical_plan::Accumulator as DfAccumulator, scalar::ScalarValue as DfScalarValue,
+    };
+
+    use super::*;
+    use crate::aggregate::ToDfAccumulator;
+
+    fn build_input(values: Vec<f64>, percentile: f64) -> Vec<ArrayRef> {
+        let percentiles = vec![percentile; values.len()];
+        vec![
+            Arc::new(Float64Array::from(values)),
+            Arc::new(Float64Array::from(percentiles)),
+        ]
+    }
+
+    fn assert_relative_eq(expect: f64, actual: f64) {
+        let relative_error = ((actual - expect) / expect).abs();
+        assert!(
+            relative_error <= RELATIVE_ACCURACY,
+            "expect:{expect}, actual:{actual}"
+        );
+    }
+
+    #[test]
+    fn test_dd_sketch() {
+        let mut sketch = DdSketch::default();
+        assert!(sketch.quantile(0.5).is_none());
+
+        for i in -100..=100 {
+            sketch.insert(i as f64 * 10.0);
+        }
+        assert_eq!(201, sketch.count);
+        assert_eq!(1, sketch.zero_count);
+        assert_relative_eq(-1000.0, sketch.quantile(0.0).unwrap());
+        assert_eq!(0.0, sketch.quantile(0.5).unwrap());
+        assert_relative_eq(500.0, sketch.quantile(0.75).unwrap());
+        assert_relative_eq(1000.0, sketch.quantile(1.0).unwrap());
+    }
+
+    #[test]
+    fn test_merge_partial_states() {
+        let mut states = Vec::new();
+        for partition in 0..4 {
+            let values = (1..=1000)
+                .filter(|v| v % 4 == partition)
+                .map(|v| v as f64)
+                .collect();
+            let mut partial = ToDfAccumulator::new(PercentileSketch::default());
+            partial.update_batch(&build_input(values, 0.99)).unwrap();
+            states.extend(partial.state().unwrap());
+        }
+        // Partial accumulator without any input.
+        let empty = ToDfAccumulator::new(PercentileSketch::default());
+        states.extend(empty.state().unwrap());
+
+        let state_array = DfScalarValue::iter_to_array(states).unwrap();
+        let mut accumulator = ToDfAccumulator::new(PercentileSketch::default());
+        accumulator.merge_batch(&[state_array]).unwrap();
+        match accumulator.evaluate().unwrap() {
+            DfScalarValue::Float64(Some(v)) => assert_relative_eq(990.0, v),
+            v => panic!("unexpected result:{v:?}"),
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_percentile() {
+        let mut accumulator = ToDfAccumulator::new(PercentileSketch::default());
+        accumulator
+            .update_batch(&build_input(vec![1.0], 0.5))
+            .unwrap();
+        assert!(accumulator
+            .update_batch(&build_input(vec![1.0], 0.9))
+            .is_err());
+        assert!(accumulator
+            .update_batch(&build_input(vec![1.0], 1.5))
+            .is_err());
+    }
+}
@@ -83,10 +83,10 @@ define_result!(Error);
 const DEFAULT_TIMEZONE_OFFSET_SECS: i32 = 8 * 3600;
 
 pub fn register_to_registry(registry: &mut dyn FunctionRegistry) -> registry::Result<()> {
-    registry.register_udf(new_udf())
+    registry.register_udf(new_udf_with_name("time_bucket"))
 }
 
-fn new_udf() -> ScalarUdf {
+pub(crate) fn new_udf_with_name(name: &str) -> ScalarUdf {
     // args:
     // - timestamp column.
     // - period.
@@ -106,7 +106,7 @@ fn new_udf() -> ScalarUdf {
     let signature = make_signature();
     let scalar_function = ScalarFunction::make_by_fn(signature, DatumKind::Timestamp, func);
 
-    ScalarUdf::create("time_bucket", scalar_function)
+    ScalarUdf::create(name, scalar_function)
 }
 
 fn make_signature() -> TypeSignature {
@@ -206,7 +206,7 @@ pub enum Period {
 }
 
 impl Period {
-    fn parse(period: &str) -> Result<Period> {
+    pub(crate) fn parse(period: &str) -> Result<Period> {
         ensure!(period.len() >= 3, InvalidPeriod { period });
         let is_pt = if period.starts_with("PT") {
             true
@@ -254,7 +254,7 @@ impl Period {
         Ok(parsed)
     }
 
-    fn truncate(&self, ts: Timestamp) -> Option<Timestamp> {
+    pub(crate) fn truncate(&self, ts: Timestamp) -> Option<Timestamp> {
         const MINUTE_SECONDS: u64 = 60;
         const HOUR_SECONDS: u64 = 60 * MINUTE_SECONDS;
 
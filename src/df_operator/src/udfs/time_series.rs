@@ -0,0 +1,607 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Time series UDAFs.
+//!
+//! - `first(value, timestamp)`: value of the earliest point.
+//! - `last(value, timestamp)`: value of the latest point.
+//! - `time_weighted_avg(value, timestamp)`: average of the values weighted by
+//!   the durations between the points, using linear interpolation.
+//! - `rate(value, timestamp)`: per-second change between the earliest and the
+//!   latest points.
+//! - `derivative(value, timestamp)`: per-second change between the last two
+//!   points.
+//!
+//! The timestamps are in milliseconds, and the points whose value or timestamp
+//! is null are ignored.
+//!
+//! All the states are mergeable, so the partial aggregations of these
+//! functions can be pushed down to the sub tables by `dist_sql_query`.
+
+use std::{mem, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, Float64Array, Int64Array, ListArray},
+    compute::cast,
+    datatypes::{DataType, Field},
+};
+use datafusion::{
+    error::{DataFusionError, Result as DfResult},
+    logical_expr::{
+        AccumulatorFactoryFunction, ReturnTypeFunction, Signature, StateTypeFunction, Volatility,
+    },
+    physical_plan::{udaf::AggregateUDF, Accumulator},
+    scalar::ScalarValue,
+};
+
+use crate::{
+    registry::{self, FunctionRegistry},
+    udaf::AggregateUdf,
+};
+
+pub const FIRST: &str = "first";
+pub const LAST: &str = "last";
+pub const TIME_WEIGHTED_AVG: &str = "time_weighted_avg";
+pub const RATE: &str = "rate";
+pub const DERIVATIVE: &str = "derivative";
+
+const SECOND_MILLIS: f64 = 1000.0;
+
+pub fn register_to_registry(registry: &mut dyn FunctionRegistry) -> registry::Result<()> {
+    registry.register_udaf(new_selector_udaf(FIRST, false))?;
+    registry.register_udaf(new_selector_udaf(LAST, true))?;
+    registry.register_udaf(new_time_weighted_avg_udaf())?;
+    registry.register_udaf(new_delta_udaf(RATE, DeltaKind::Rate))?;
+    registry.register_udaf(new_delta_udaf(DERIVATIVE, DeltaKind::Derivative))
+}
+
+/// The return type of `first` and `last` is the type of the value, so they
+/// are built on the datafusion udaf directly.
+fn new_selector_udaf(name: &str, latest: bool) -> AggregateUdf {
+    let return_type: ReturnTypeFunction =
+        Arc::new(|arg_types: &[DataType]| Ok(Arc::new(arg_types[0].clone())));
+    let accumulator: AccumulatorFactoryFunction = Arc::new(move |data_type: &DataType| {
+        Ok(Box::new(SelectorAccumulator::new(data_type.clone(), latest)) as Box<dyn Accumulator>)
+    });
+    let state_type: StateTypeFunction =
+        Arc::new(|data_type: &DataType| Ok(Arc::new(vec![data_type.clone(), DataType::Int64])));
+
+    new_udaf(name, return_type, accumulator, state_type)
+}
+
+fn new_time_weighted_avg_udaf() -> AggregateUdf {
+    let accumulator: AccumulatorFactoryFunction =
+        Arc::new(|_| Ok(Box::<TimeWeightedAvgAccumulator>::default() as Box<dyn Accumulator>));
+    let state_type: StateTypeFunction = Arc::new(|_| {
+        Ok(Arc::new(vec![
+            list_type(DataType::Int64),
+            list_type(DataType::Float64),
+        ]))
+    });
+
+    new_udaf(
+        TIME_WEIGHTED_AVG,
+        float64_return_type(),
+        accumulator,
+        state_type,
+    )
+}
+
+fn new_delta_udaf(name: &str, kind: DeltaKind) -> AggregateUdf {
+    let accumulator: AccumulatorFactoryFunction =
+        Arc::new(move |_| Ok(Box::new(DeltaAccumulator::new(kind)) as Box<dyn Accumulator>));
+    let state_type: StateTypeFunction = Arc::new(|_| {
+        Ok(Arc::new(vec![
+            DataType::Int64,
+            DataType::Float64,
+            DataType::Int64,
+            DataType::Float64,
+        ]))
+    });
+
+    new_udaf(name, float64_return_type(), accumulator, state_type)
+}
+
+fn new_udaf(
+    name: &str,
+    return_type: ReturnTypeFunction,
+    accumulator: AccumulatorFactoryFunction,
+    state_type: StateTypeFunction,
+) -> AggregateUdf {
+    let signature = Signature::any(2, Volatility::Immutable);
+
+    AggregateUdf::from_datafusion_udaf(AggregateUDF::new(
+        name,
+        &signature,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+}
+
+fn float64_return_type() -> ReturnTypeFunction {
+    Arc::new(|_| Ok(Arc::new(DataType::Float64)))
+}
+
+fn list_type(item_type: DataType) -> DataType {
+    DataType::List(Arc::new(Field::new("item", item_type, true)))
+}
+
+fn cast_to_int64(array: &ArrayRef) -> DfResult<Int64Array> {
+    let array = cast(array, &DataType::Int64)?;
+    Ok(array
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .expect("casted to int64 array")
+        .clone())
+}
+
+fn cast_to_float64(array: &ArrayRef) -> DfResult<Float64Array> {
+    let array = cast(array, &DataType::Float64)?;
+    Ok(array
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .expect("casted to float64 array")
+        .clone())
+}
+
+fn as_list_array(array: &ArrayRef) -> DfResult<&ListArray> {
+    array.as_any().downcast_ref::<ListArray>().ok_or_else(|| {
+        DataFusionError::Internal(format!(
+            "Invalid state, expect list array, actual:{}",
+            array.data_type()
+        ))
+    })
+}
+
+/// Point of a time series, whose value and timestamp are both valid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+    ts: i64,
+    value: f64,
+}
+
+/// Iterate over the valid points of the timestamp and value columns.
+fn valid_points<'a>(
+    timestamps: &'a Int64Array,
+    values: &'a Float64Array,
+) -> impl Iterator<Item = Point> + 'a {
+    (0..timestamps.len()).filter_map(move |idx| {
+        if timestamps.is_null(idx) || values.is_null(idx) {
+            None
+        } else {
+            Some(Point {
+                ts: timestamps.value(idx),
+                value: values.value(idx),
+            })
+        }
+    })
+}
+
+/// Accumulator of `first` and `last`, which selects the value of the earliest
+/// or latest point.
+#[derive(Debug)]
+struct SelectorAccumulator {
+    data_type: DataType,
+    /// Select the latest point if true, otherwise the earliest one.
+    latest: bool,
+    selected: Option<(i64, ScalarValue)>,
+}
+
+impl SelectorAccumulator {
+    fn new(data_type: DataType, latest: bool) -> Self {
+        Self {
+            data_type,
+            latest,
+            selected: None,
+        }
+    }
+
+    #[inline]
+    fn should_replace(&self, selected_ts: i64, ts: i64) -> bool {
+        if self.latest {
+            ts >= selected_ts
+        } else {
+            ts < selected_ts
+        }
+    }
+
+    fn update_arrays(&mut self, values: &ArrayRef, timestamps: &Int64Array) -> DfResult<()> {
+        // Find the selected row of the batch first to avoid converting every row into
+        // scalar value. The rows of a series are mostly sorted by the timestamp in the
+        // primary key order, so the earliest one is usually the first valid row.
+        let mut selected_row: Option<(i64, usize)> = None;
+        for idx in 0..timestamps.len() {
+            if timestamps.is_null(idx) || values.is_null(idx) {
+                continue;
+            }
+            let ts = timestamps.value(idx);
+            match selected_row {
+                Some((selected_ts, _)) if !self.should_replace(selected_ts, ts) => {}
+                _ => selected_row = Some((ts, idx)),
+            }
+        }
+
+        if let Some((ts, idx)) = selected_row {
+            let replace = match &self.selected {
+                Some((selected_ts, _)) => self.should_replace(*selected_ts, ts),
+                None => true,
+            };
+            if replace {
+                self.selected = Some((ts, ScalarValue::try_from_array(values, idx)?));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Accumulator for SelectorAccumulator {
+    fn state(&self) -> DfResult<Vec<ScalarValue>> {
+        match &self.selected {
+            Some((ts, value)) => Ok(vec![value.clone(), ScalarValue::Int64(Some(*ts))]),
+            None => Ok(vec![
+                ScalarValue::try_from(&self.data_type)?,
+                ScalarValue::Int64(None),
+            ]),
+        }
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DfResult<()> {
+        let timestamps = cast_to_int64(&values[1])?;
+        self.update_arrays(&values[0], &timestamps)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DfResult<()> {
+        let timestamps = cast_to_int64(&states[1])?;
+        self.update_arrays(&states[0], &timestamps)
+    }
+
+    fn evaluate(&self) -> DfResult<ScalarValue> {
+        match &self.selected {
+            Some((_, value)) => Ok(value.clone()),
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn size(&self) -> usize {
+        mem::size_of_val(self)
+            + self
+                .selected
+                .as_ref()
+                .map(|(_, value)| value.size() - mem::size_of_val(value))
+                .unwrap_or_default()
+    }
+}
+
+/// Accumulator of `time_weighted_avg`.
+///
+/// All the points are kept in the state, because the points of a series may
+/// be split into multiple partial aggregations whose time ranges overlap.
+#[derive(Debug, Default)]
+struct TimeWeightedAvgAccumulator {
+    points: Vec<Point>,
+}
+
+impl TimeWeightedAvgAccumulator {
+    fn is_sorted(&self) -> bool {
+        self.points.windows(2).all(|w| w[0].ts <= w[1].ts)
+    }
+}
+
+impl Accumulator for TimeWeightedAvgAccumulator {
+    fn state(&self) -> DfResult<Vec<ScalarValue>> {
+        let timestamps = self
+            .points
+            .iter()
+            .map(|p| ScalarValue::Int64(Some(p.ts)))
+            .collect();
+        let values = self
+            .points
+            .iter()
+            .map(|p| ScalarValue::Float64(Some(p.value)))
+            .collect();
+
+        Ok(vec![
+            ScalarValue::new_list(Some(timestamps), DataType::Int64),
+            ScalarValue::new_list(Some(values), DataType::Float64),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DfResult<()> {
+        let timestamps = cast_to_int64(&values[1])?;
+        let values = cast_to_float64(&values[0])?;
+        self.points.extend(valid_points(&timestamps, &values));
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DfResult<()> {
+        let timestamp_lists = as_list_array(&states[0])?;
+        let value_lists = as_list_array(&states[1])?;
+        for idx in 0..timestamp_lists.len() {
+            if timestamp_lists.is_null(idx) || value_lists.is_null(idx) {
+                continue;
+            }
+            let timestamps = cast_to_int64(&timestamp_lists.value(idx))?;
+            let values = cast_to_float64(&value_lists.value(idx))?;
+            self.points.extend(valid_points(&timestamps, &values));
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DfResult<ScalarValue> {
+        // The points are usually appended in the primary key order, so sorting is
+        // skipped in most cases.
+        let mut sorted_points;
+        let points = if self.is_sorted() {
+            &self.points
+        } else {
+            sorted_points = self.points.clone();
+            sorted_points.sort_unstable_by_key(|p| p.ts);
+            &sorted_points
+        };
+
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Ok(ScalarValue::Float64(None)),
+        };
+        let duration = last.ts - first.ts;
+        if duration == 0 {
+            // All the points are at the same time, fallback to the plain average.
+            let sum: f64 = points.iter().map(|p| p.value).sum();
+            return Ok(ScalarValue::Float64(Some(sum / points.len() as f64)));
+        }
+
+        let weighted_sum: f64 = points
+            .windows(2)
+            .map(|w| (w[0].value + w[1].value) / 2.0 * (w[1].ts - w[0].ts) as f64)
+            .sum();
+        Ok(ScalarValue::Float64(Some(weighted_sum / duration as f64)))
+    }
+
+    fn size(&self) -> usize {
+        mem::size_of_val(self) + self.points.capacity() * mem::size_of::<Point>()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DeltaKind {
+    /// Change between the earliest and the latest points.
+    Rate,
+    /// Change between the last two points.
+    Derivative,
+}
+
+/// Accumulator of `rate` and `derivative`, which only keeps two points of the
+/// series.
+#[derive(Debug)]
+struct DeltaAccumulator {
+    kind: DeltaKind,
+    /// The earliest point for `rate`, the second latest point for
+    /// `derivative`.
+    start: Option<Point>,
+    /// The latest point.
+    end: Option<Point>,
+}
+
+impl DeltaAccumulator {
+    fn new(kind: DeltaKind) -> Self {
+        Self {
+            kind,
+            start: None,
+            end: None,
+        }
+    }
+
+    fn update(&mut self, point: Point) {
+        match self.kind {
+            DeltaKind::Rate => {
+                if self.start.map(|p| point.ts < p.ts).unwrap_or(true) {
+                    self.start = Some(point);
+                }
+                if self.end.map(|p| point.ts >= p.ts).unwrap_or(true) {
+                    self.end = Some(point);
+                }
+            }
+            DeltaKind::Derivative => match self.end {
+                Some(end) if point.ts >= end.ts => {
+                    self.start = Some(end);
+                    self.end = Some(point);
+                }
+                Some(_) => {
+                    if self.start.map(|p| point.ts > p.ts).unwrap_or(true) {
+                        self.start = Some(point);
+                    }
+                }
+                None => self.end = Some(point),
+            },
+        }
+    }
+}
+
+impl Accumulator for DeltaAccumulator {
+    fn state(&self) -> DfResult<Vec<ScalarValue>> {
+        let mut state = Vec::with_capacity(4);
+        for point in [self.start, self.end] {
+            state.push(ScalarValue::Int64(point.map(|p| p.ts)));
+            state.push(ScalarValue::Float64(point.map(|p| p.value)));
+        }
+
+        Ok(state)
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DfResult<()> {
+        let timestamps = cast_to_int64(&values[1])?;
+        let values = cast_to_float64(&values[0])?;
+        for point in valid_points(&timestamps, &values) {
+            self.update(point);
+        }
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DfResult<()> {
+        for (ts_idx, value_idx) in [(0, 1), (2, 3)] {
+            let timestamps = cast_to_int64(&states[ts_idx])?;
+            let values = cast_to_float64(&states[value_idx])?;
+            for point in valid_points(&timestamps, &values) {
+                self.update(point);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DfResult<ScalarValue> {
+        let delta = match (self.start, self.end) {
+            (Some(start), Some(end)) if end.ts > start.ts => {
+                let seconds = (end.ts - start.ts) as f64 / SECOND_MILLIS;
+                Some((end.value - start.value) / seconds)
+            }
+            _ => None,
+        };
+
+        Ok(ScalarValue::Float64(delta))
+    }
+
+    fn size(&self) -> usize {
+        mem::size_of_val(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_input(values: Vec<Option<f64>>, timestamps: Vec<Option<i64>>) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(Float64Array::from(values)),
+            Arc::new(Int64Array::from(timestamps)),
+        ]
+    }
+
+    /// Aggregate the inputs by partial accumulators, and merge their states
+    /// into the final accumulator.
+    fn partial_aggregate<F>(new_accumulator: F, inputs: Vec<Vec<ArrayRef>>) -> ScalarValue
+    where
+        F: Fn() -> Box<dyn Accumulator>,
+    {
+        let mut states: Vec<Vec<ScalarValue>> = Vec::new();
+        for input in inputs {
+            let mut partial = new_accumulator();
+            partial.update_batch(&input).unwrap();
+            states.push(partial.state().unwrap());
+        }
+
+        let num_states = states[0].len();
+        let state_arrays = (0..num_states)
+            .map(|i| ScalarValue::iter_to_array(states.iter().map(|state| state[i].clone())))
+            .collect::<DfResult<Vec<_>>>()
+            .unwrap();
+        let mut accumulator = new_accumulator();
+        accumulator.merge_batch(&state_arrays).unwrap();
+        accumulator.evaluate().unwrap()
+    }
+
+    #[test]
+    fn test_first_last() {
+        let inputs = || {
+            vec![
+                build_input(
+                    vec![Some(2.0), Some(3.0), None],
+                    vec![Some(2000), Some(3000), Some(500)],
+                ),
+                build_input(vec![Some(1.0), Some(4.0)], vec![Some(1000), Some(4000)]),
+                build_input(vec![], vec![]),
+            ]
+        };
+
+        let first = partial_aggregate(
+            || Box::new(SelectorAccumulator::new(DataType::Float64, false)),
+            inputs(),
+        );
+        assert_eq!(ScalarValue::Float64(Some(1.0)), first);
+
+        let last = partial_aggregate(
+            || Box::new(SelectorAccumulator::new(DataType::Float64, true)),
+            inputs(),
+        );
+        assert_eq!(ScalarValue::Float64(Some(4.0)), last);
+
+        let empty = partial_aggregate(
+            || Box::new(SelectorAccumulator::new(DataType::Float64, true)),
+            vec![build_input(vec![None], vec![Some(1000)])],
+        );
+        assert_eq!(ScalarValue::Float64(None), empty);
+    }
+
+    #[test]
+    fn test_time_weighted_avg() {
+        // The points are (0s, 0), (1s, 2), (3s, 2), (4s, 10), and the time weighted
+        // average is (1 + 4 + 6) / 4.
+        let avg = partial_aggregate(
+            || Box::<TimeWeightedAvgAccumulator>::default(),
+            vec![
+                build_input(vec![Some(2.0), Some(10.0)], vec![Some(3000), Some(4000)]),
+                build_input(
+                    vec![Some(0.0), Some(2.0), None],
+                    vec![Some(0), Some(1000), Some(2000)],
+                ),
+            ],
+        );
+        assert_eq!(ScalarValue::Float64(Some(2.75)), avg);
+
+        let avg = partial_aggregate(
+            || Box::<TimeWeightedAvgAccumulator>::default(),
+            vec![build_input(
+                vec![Some(1.0), Some(3.0)],
+                vec![Some(0), Some(0)],
+            )],
+        );
+        assert_eq!(ScalarValue::Float64(Some(2.0)), avg);
+    }
+
+    #[test]
+    fn test_rate_derivative() {
+        let inputs = || {
+            vec![
+                build_input(vec![Some(30.0), Some(10.0)], vec![Some(3000), Some(1000)]),
+                build_input(vec![Some(50.0), Some(20.0)], vec![Some(4000), Some(2000)]),
+            ]
+        };
+
+        let rate = partial_aggregate(
+            || Box::new(DeltaAccumulator::new(DeltaKind::Rate)),
+            inputs(),
+        );
+        assert_eq!(ScalarValue::Float64(Some(40.0 / 3.0)), rate);
+
+        let derivative = partial_aggregate(
+            || Box::new(DeltaAccumulator::new(DeltaKind::Derivative)),
+            inputs(),
+        );
+        assert_eq!(ScalarValue::Float64(Some(20.0)), derivative);
+
+        let single = partial_aggregate(
+            || Box::new(DeltaAccumulator::new(DeltaKind::Rate)),
+            vec![build_input(vec![Some(1.0)], vec![Some(1000)])],
+        );
+        assert_eq!(ScalarValue::Float64(None), single);
+    }
+}
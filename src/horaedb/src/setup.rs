@@ -344,7 +344,10 @@ async fn build_with_meta<T: WalsOpener>(
     ));
 
     // Build catalog manager.
-    let catalog_manager = Arc::new(CatalogManagerImpl::new(meta_based_manager_ref));
+    let catalog_manager = Arc::new(CatalogManagerImpl::new(
+        meta_based_manager_ref,
+        Some(cluster.clone()),
+    ));
 
     let table_manipulator = Arc::new(meta_based::TableManipulatorImpl::new(meta_client));
 
@@ -389,7 +392,7 @@ async fn build_without_meta<T: WalsOpener>(
         .await
         .expect("Failed to fetch table infos for opening");
 
-    let catalog_manager = Arc::new(CatalogManagerImpl::new(Arc::new(table_based_manager)));
+    let catalog_manager = Arc::new(CatalogManagerImpl::new(Arc::new(table_based_manager), None));
     let table_operator = TableOperator::new(catalog_manager.clone());
     let table_manipulator = Arc::new(catalog_based::TableManipulatorImpl::new(
         table_operator.clone(),
@@ -0,0 +1,244 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Interpreter for backup and restore table statements
+
+use async_trait::async_trait;
+use catalog::manager::ManagerRef;
+use generic_error::BoxError;
+use logger::{info, warn};
+use macros::define_result;
+use query_frontend::plan::{BackupTablePlan, CreateTablePlan, DropTablePlan, RestoreTablePlan};
+use snafu::{OptionExt, ResultExt, Snafu};
+use table_engine::{
+    engine::TableEngineRef,
+    table::{BackupRequest, RestoreRequest, TableRef},
+    ANALYTIC_ENGINE_TYPE,
+};
+
+use crate::{
+    context::Context,
+    interpreter::{
+        BackupTable, Interpreter, InterpreterPtr, Output, RestoreTable, Result as InterpreterResult,
+    },
+    table_manipulator::{self, TableManipulatorRef},
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum Error {
+    #[snafu(display("Failed to backup table, table:{}, err:{}", table, source))]
+    Backup {
+        table: String,
+        source: table_engine::table::Error,
+    },
+
+    #[snafu(display("Failed to read table backup, path:{}, err:{}", path, source))]
+    ReadBackup {
+        path: String,
+        source: table_engine::engine::Error,
+    },
+
+    #[snafu(display("Failed to create table for restore, err:{}", source))]
+    CreateTable { source: table_manipulator::Error },
+
+    #[snafu(display("Failed to find restored table, table:{}, err:{}", table, source))]
+    FindTable {
+        table: String,
+        source: generic_error::GenericError,
+    },
+
+    #[snafu(display("Restored table not found after creation, table:{}", table))]
+    TableNotFound { table: String },
+
+    #[snafu(display("Failed to restore table, table:{}, err:{}", table, source))]
+    Restore {
+        table: String,
+        source: table_engine::table::Error,
+    },
+}
+
+define_result!(Error);
+
+/// Backup table interpreter
+pub struct BackupTableInterpreter {
+    plan: BackupTablePlan,
+}
+
+impl BackupTableInterpreter {
+    pub fn create(plan: BackupTablePlan) -> InterpreterPtr {
+        Box::new(Self { plan })
+    }
+
+    async fn execute_backup(self: Box<Self>) -> Result<Output> {
+        let table = &self.plan.table;
+        let stats = table
+            .backup(BackupRequest {
+                path: self.plan.path.clone(),
+            })
+            .await
+            .context(Backup {
+                table: table.name(),
+            })?;
+
+        info!(
+            "Backup table finished, table:{}, path:{}, stats:{:?}",
+            table.name(),
+            self.plan.path,
+            stats
+        );
+
+        Ok(Output::AffectedRows(stats.num_ssts))
+    }
+}
+
+#[async_trait]
+impl Interpreter for BackupTableInterpreter {
+    async fn execute(self: Box<Self>) -> InterpreterResult<Output> {
+        self.execute_backup().await.context(BackupTable)
+    }
+}
+
+/// Restore table interpreter
+///
+/// The table is created from the schema and options recorded in the backup,
+/// and then the backed up ssts are loaded into it. The created table is
+/// dropped again if loading fails.
+pub struct RestoreTableInterpreter {
+    ctx: Context,
+    plan: RestoreTablePlan,
+    catalog_manager: ManagerRef,
+    table_engine: TableEngineRef,
+    table_manipulator: TableManipulatorRef,
+}
+
+impl RestoreTableInterpreter {
+    pub fn create(
+        ctx: Context,
+        plan: RestoreTablePlan,
+        catalog_manager: ManagerRef,
+        table_engine: TableEngineRef,
+        table_manipulator: TableManipulatorRef,
+    ) -> InterpreterPtr {
+        Box::new(Self {
+            ctx,
+            plan,
+            catalog_manager,
+            table_engine,
+            table_manipulator,
+        })
+    }
+
+    async fn execute_restore(self: Box<Self>) -> Result<Output> {
+        let backup_meta = self
+            .table_engine
+            .read_table_backup(&self.plan.path)
+            .await
+            .context(ReadBackup {
+                path: &self.plan.path,
+            })?;
+
+        let create_plan = CreateTablePlan {
+            engine: ANALYTIC_ENGINE_TYPE.to_string(),
+            if_not_exists: false,
+            table: self.plan.table.clone(),
+            table_schema: backup_meta.table_schema,
+            options: backup_meta.table_options,
+            partition_info: None,
+        };
+        self.table_manipulator
+            .create_table(self.ctx.clone(), create_plan, self.table_engine.clone())
+            .await
+            .context(CreateTable)?;
+
+        match self.restore_created_table().await {
+            Ok(output) => Ok(output),
+            Err(e) => {
+                self.drop_created_table().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn restore_created_table(&self) -> Result<Output> {
+        let table = self.find_table()?;
+        let stats = table
+            .restore(RestoreRequest {
+                path: self.plan.path.clone(),
+            })
+            .await
+            .context(Restore {
+                table: &self.plan.table,
+            })?;
+
+        info!(
+            "Restore table finished, table:{}, path:{}, stats:{:?}",
+            self.plan.table, self.plan.path, stats
+        );
+
+        Ok(Output::AffectedRows(stats.num_ssts))
+    }
+
+    fn find_table(&self) -> Result<TableRef> {
+        let table = &self.plan.table;
+        let catalog = self
+            .catalog_manager
+            .catalog_by_name(self.ctx.default_catalog())
+            .box_err()
+            .context(FindTable { table })?
+            .context(TableNotFound { table })?;
+        let schema = catalog
+            .schema_by_name(self.ctx.default_schema())
+            .box_err()
+            .context(FindTable { table })?
+            .context(TableNotFound { table })?;
+
+        schema
+            .table_by_name(table)
+            .box_err()
+            .context(FindTable { table })?
+            .context(TableNotFound { table })
+    }
+
+    /// Drop the table created for restoring, errors are only logged as the
+    /// restore error is more meaningful to the caller.
+    async fn drop_created_table(&self) {
+        let drop_plan = DropTablePlan {
+            engine: ANALYTIC_ENGINE_TYPE.to_string(),
+            if_exists: true,
+            table: self.plan.table.clone(),
+            partition_info: None,
+        };
+        if let Err(e) = self
+            .table_manipulator
+            .drop_table(self.ctx.clone(), drop_plan, self.table_engine.clone())
+            .await
+        {
+            warn!(
+                "Failed to drop table after restore failure, table:{}, err:{}",
+                self.plan.table, e
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Interpreter for RestoreTableInterpreter {
+    async fn execute(self: Box<Self>) -> InterpreterResult<Output> {
+        self.execute_restore().await.context(RestoreTable)
+    }
+}
@@ -21,10 +21,15 @@ use std::{sync::Arc, time::Instant};
 
 use common_types::request_id::RequestId;
 use macros::define_result;
-use query_engine::context::{Context as QueryContext, ContextRef as QueryContextRef};
+use query_engine::{
+    context::{Context as QueryContext, ContextRef as QueryContextRef},
+    limits::QueryLimits,
+};
 use runtime::Priority;
 use snafu::Snafu;
 
+use crate::process_list::ProcessHandle;
+
 #[derive(Debug, Snafu)]
 pub enum Error {}
 
@@ -43,6 +48,10 @@ pub struct Context {
     /// If time range exceeds this threshold, the query will be marked as
     /// expensive
     expensive_query_threshold: u64,
+    /// Handle of the query in the process list, if it is registered
+    process: Option<ProcessHandle>,
+    /// Limits of the query
+    query_limits: QueryLimits,
 }
 
 impl Context {
@@ -54,6 +63,8 @@ impl Context {
             default_schema: String::new(),
             enable_partition_table_access: false,
             expensive_query_threshold: 24 * 3600 * 1000, // default 24 hours
+            process: None,
+            query_limits: QueryLimits::default(),
         }
     }
 
@@ -65,6 +76,7 @@ impl Context {
             default_catalog: self.default_catalog.clone(),
             default_schema: self.default_schema.clone(),
             priority,
+            limits: self.query_limits,
         };
         Ok(Arc::new(ctx))
     }
@@ -93,6 +105,11 @@ impl Context {
     pub fn expensive_query_threshold(&self) -> u64 {
         self.expensive_query_threshold
     }
+
+    #[inline]
+    pub fn process(&self) -> Option<&ProcessHandle> {
+        self.process.as_ref()
+    }
 }
 
 #[must_use]
@@ -103,6 +120,8 @@ pub struct Builder {
     default_schema: String,
     enable_partition_table_access: bool,
     expensive_query_threshold: u64,
+    process: Option<ProcessHandle>,
+    query_limits: QueryLimits,
 }
 
 impl Builder {
@@ -122,6 +141,16 @@ impl Builder {
         self
     }
 
+    pub fn process(mut self, process: ProcessHandle) -> Self {
+        self.process = Some(process);
+        self
+    }
+
+    pub fn query_limits(mut self, query_limits: QueryLimits) -> Self {
+        self.query_limits = query_limits;
+        self
+    }
+
     pub fn build(self) -> Context {
         Context {
             request_id: self.request_id,
@@ -130,6 +159,8 @@ impl Builder {
             default_schema: self.default_schema,
             enable_partition_table_access: self.enable_partition_table_access,
             expensive_query_threshold: self.expensive_query_threshold,
+            process: self.process,
+            query_limits: self.query_limits,
         }
     }
 }
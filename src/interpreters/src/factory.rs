@@ -25,6 +25,7 @@ use table_engine::engine::TableEngineRef;
 
 use crate::{
     alter_table::AlterTableInterpreter,
+    backup::{BackupTableInterpreter, RestoreTableInterpreter},
     context::Context,
     create::CreateInterpreter,
     describe::DescribeInterpreter,
@@ -32,10 +33,13 @@ use crate::{
     exists::ExistsInterpreter,
     insert::InsertInterpreter,
     interpreter::{InterpreterPtr, Result},
+    kill::KillQueryInterpreter,
+    process_list::ProcessListRef,
     select::SelectInterpreter,
     show::ShowInterpreter,
     table_manipulator::TableManipulatorRef,
     validator::{ValidateContext, Validator},
+    verify::VerifyTableInterpreter,
 };
 
 /// A factory to create interpreters
@@ -46,6 +50,7 @@ pub struct Factory {
     catalog_manager: ManagerRef,
     table_engine: TableEngineRef,
     table_manipulator: TableManipulatorRef,
+    process_list: ProcessListRef,
 }
 
 impl Factory {
@@ -56,6 +61,7 @@ impl Factory {
         table_engine: TableEngineRef,
         table_manipulator: TableManipulatorRef,
         query_runtime: PriorityRuntime,
+        process_list: ProcessListRef,
     ) -> Self {
         Self {
             query_executor,
@@ -64,6 +70,7 @@ impl Factory {
             catalog_manager,
             table_engine,
             table_manipulator,
+            process_list,
         }
     }
 
@@ -91,8 +98,20 @@ impl Factory {
             }
             Plan::Describe(p) => DescribeInterpreter::create(p),
             Plan::AlterTable(p) => AlterTableInterpreter::create(p),
-            Plan::Show(p) => ShowInterpreter::create(ctx, p, self.catalog_manager),
+            Plan::Show(p) => {
+                ShowInterpreter::create(ctx, p, self.catalog_manager, self.process_list)
+            }
             Plan::Exists(p) => ExistsInterpreter::create(p),
+            Plan::KillQuery(p) => KillQueryInterpreter::create(p, self.process_list),
+            Plan::BackupTable(p) => BackupTableInterpreter::create(p),
+            Plan::RestoreTable(p) => RestoreTableInterpreter::create(
+                ctx,
+                p,
+                self.catalog_manager,
+                self.table_engine,
+                self.table_manipulator,
+            ),
+            Plan::VerifyTable(p) => VerifyTableInterpreter::create(p),
         };
 
         Ok(interpreter)
@@ -272,10 +272,10 @@ fn fill_default_values(
         let from_type = physical_expr
             .data_type(&input_arrow_schema)
             .context(DatafusionDataType)?;
-        let to_type = row_groups.schema().column(*column_idx).data_type;
+        let to_type = row_groups.schema().column(*column_idx).to_arrow_data_type();
 
-        let casted_physical_expr = if from_type != to_type.into() {
-            Arc::new(TryCastExpr::new(physical_expr, to_type.into()))
+        let casted_physical_expr = if from_type != to_type {
+            Arc::new(TryCastExpr::new(physical_expr, to_type))
         } else {
             physical_expr
         };
@@ -357,13 +357,10 @@ fn get_or_extract_column_from_row_groups(
         .get(&column_idx)
         .map(|c| Ok(c.clone()))
         .unwrap_or_else(|| {
-            let data_type = row_groups.schema().column(column_idx).data_type;
+            let column_schema = row_groups.schema().column(column_idx);
             let iter = row_groups.iter_column(column_idx);
-            let mut builder = ColumnBlockBuilder::with_capacity(
-                &data_type,
-                iter.size_hint().0,
-                row_groups.schema().column(column_idx).is_dictionary,
-            );
+            let mut builder =
+                ColumnBlockBuilder::with_column_schema(column_schema, iter.size_hint().0);
 
             for datum in iter {
                 builder.append(datum.clone()).context(BuildColumnBlock)?;
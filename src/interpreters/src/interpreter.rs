@@ -54,9 +54,24 @@ pub enum Error {
     #[snafu(display("Failed to execute show database, err:{}", source))]
     ShowDatabases { source: crate::show::Error },
 
+    #[snafu(display("Failed to execute show processlist, err:{}", source))]
+    ShowProcessList { source: crate::show::Error },
+
     #[snafu(display("Failed to execute exists, err:{}", source))]
     Exists { source: crate::exists::Error },
 
+    #[snafu(display("Failed to execute kill query, err:{}", source))]
+    KillQuery { source: crate::kill::Error },
+
+    #[snafu(display("Failed to execute backup table, err:{}", source))]
+    BackupTable { source: crate::backup::Error },
+
+    #[snafu(display("Failed to execute restore table, err:{}", source))]
+    RestoreTable { source: crate::backup::Error },
+
+    #[snafu(display("Failed to execute verify table, err:{}", source))]
+    VerifyTable { source: crate::verify::Error },
+
     #[snafu(display("Failed to transfer output to records"))]
     TryIntoRecords,
 
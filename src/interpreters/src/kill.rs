@@ -0,0 +1,107 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Interpreter for kill query statement
+
+use async_trait::async_trait;
+use common_types::request_id::RequestId;
+use macros::define_result;
+use query_frontend::plan::KillQueryPlan;
+use snafu::{ensure, Backtrace, ResultExt, Snafu};
+
+use crate::{
+    interpreter::{Interpreter, InterpreterPtr, KillQuery, Output, Result as InterpreterResult},
+    process_list::ProcessListRef,
+};
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Query not found, id:{}.\nBacktrace:\n{}", query_id, backtrace))]
+    QueryNotFound {
+        query_id: String,
+        backtrace: Backtrace,
+    },
+}
+
+define_result!(Error);
+
+pub struct KillQueryInterpreter {
+    plan: KillQueryPlan,
+    process_list: ProcessListRef,
+}
+
+impl KillQueryInterpreter {
+    pub fn create(plan: KillQueryPlan, process_list: ProcessListRef) -> InterpreterPtr {
+        Box::new(Self { plan, process_list })
+    }
+
+    fn execute_kill(self: Box<Self>) -> Result<Output> {
+        let KillQueryPlan { query_id } = self.plan;
+        let request_id = RequestId::from(query_id.as_str());
+        ensure!(
+            self.process_list.kill(&request_id),
+            QueryNotFound { query_id }
+        );
+
+        Ok(Output::AffectedRows(1))
+    }
+}
+
+#[async_trait]
+impl Interpreter for KillQueryInterpreter {
+    async fn execute(self: Box<Self>) -> InterpreterResult<Output> {
+        self.execute_kill().context(KillQuery)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::process_list::{ProcessDesc, ProcessList};
+
+    #[tokio::test]
+    async fn test_kill_query() {
+        let process_list = Arc::new(ProcessList::default());
+        let (_guard, _registration) = process_list.register(ProcessDesc {
+            request_id: RequestId::from("query-1"),
+            catalog: "horaedb".to_string(),
+            schema: "public".to_string(),
+            client: None,
+            sql: "select 1".to_string(),
+        });
+
+        let interpreter = KillQueryInterpreter::create(
+            KillQueryPlan {
+                query_id: "query-1".to_string(),
+            },
+            process_list.clone(),
+        );
+        let output = interpreter.execute().await.unwrap();
+        assert!(matches!(output, Output::AffectedRows(1)));
+        assert!(process_list.list()[0].killed);
+
+        let interpreter = KillQueryInterpreter::create(
+            KillQueryPlan {
+                query_id: "query-2".to_string(),
+            },
+            process_list,
+        );
+        assert!(interpreter.execute().await.is_err());
+    }
+}
@@ -24,6 +24,7 @@
 use common_types::record_batch::RecordBatch;
 
 pub mod alter_table;
+pub mod backup;
 pub mod context;
 pub mod create;
 pub mod describe;
@@ -32,12 +33,15 @@ pub mod exists;
 pub mod factory;
 pub mod insert;
 pub mod interpreter;
+pub mod kill;
 mod metrics;
+pub mod process_list;
 pub mod select;
 pub mod show;
 mod show_create;
 pub mod table_manipulator;
 pub mod validator;
+pub mod verify;
 
 #[cfg(test)]
 mod tests;
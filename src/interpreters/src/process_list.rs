@@ -0,0 +1,240 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Registry of the in-flight queries on this node.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+use common_types::{request_id::RequestId, time::Timestamp};
+use futures::future::{AbortHandle, AbortRegistration};
+use query_engine::physical_planner::PhysicalPlanRef;
+use time_ext::InstantExt;
+
+/// Description of a query to register.
+#[derive(Debug, Clone)]
+pub struct ProcessDesc {
+    pub request_id: RequestId,
+    pub catalog: String,
+    pub schema: String,
+    /// Who issues the query, e.g. the connection info of the session.
+    pub client: Option<String>,
+    pub sql: String,
+}
+
+/// Snapshot of a registered query.
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub desc: ProcessDesc,
+    pub start_time: Timestamp,
+    pub elapsed: Duration,
+    pub killed: bool,
+    /// Metrics collected by the physical plan so far, empty if the query has
+    /// no physical plan yet.
+    pub progress: String,
+}
+
+struct Process {
+    desc: ProcessDesc,
+    start_time: Timestamp,
+    start_instant: Instant,
+    abort_handle: AbortHandle,
+    killed: AtomicBool,
+    physical_plan: Mutex<Option<PhysicalPlanRef>>,
+}
+
+impl Process {
+    fn info(&self) -> ProcessInfo {
+        let progress = self
+            .physical_plan
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|plan| plan.metrics_to_string())
+            .unwrap_or_default();
+
+        ProcessInfo {
+            desc: self.desc.clone(),
+            start_time: self.start_time,
+            elapsed: self.start_instant.saturating_elapsed(),
+            killed: self.killed.load(Ordering::Relaxed),
+            progress,
+        }
+    }
+}
+
+/// Handle of a registered query, used by the interpreters to attach the
+/// execution state to the query.
+#[derive(Clone)]
+pub struct ProcessHandle {
+    process: Arc<Process>,
+}
+
+impl ProcessHandle {
+    pub fn set_physical_plan(&self, plan: PhysicalPlanRef) {
+        *self.process.physical_plan.lock().unwrap() = Some(plan);
+    }
+}
+
+impl fmt::Debug for ProcessHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessHandle")
+            .field("request_id", &self.process.desc.request_id)
+            .finish()
+    }
+}
+
+/// Guard of a registered query, the query is removed from the registry when
+/// the guard is dropped.
+pub struct ProcessGuard {
+    process_list: ProcessListRef,
+    handle: ProcessHandle,
+}
+
+impl ProcessGuard {
+    pub fn handle(&self) -> ProcessHandle {
+        self.handle.clone()
+    }
+}
+
+impl Drop for ProcessGuard {
+    fn drop(&mut self) {
+        self.process_list
+            .remove(&self.handle.process.desc.request_id);
+    }
+}
+
+/// Registry of the in-flight queries.
+///
+/// The execution of a registered query should be wrapped by
+/// [futures::future::Abortable] with the returned [AbortRegistration], so that
+/// killing the query drops the whole execution, including the record batch
+/// streams of the remote sub plans.
+#[derive(Default)]
+pub struct ProcessList {
+    processes: RwLock<HashMap<RequestId, Arc<Process>>>,
+}
+
+pub type ProcessListRef = Arc<ProcessList>;
+
+impl ProcessList {
+    pub fn register(self: &Arc<Self>, desc: ProcessDesc) -> (ProcessGuard, AbortRegistration) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let request_id = desc.request_id.clone();
+        let process = Arc::new(Process {
+            desc,
+            start_time: Timestamp::now(),
+            start_instant: Instant::now(),
+            abort_handle,
+            killed: AtomicBool::new(false),
+            physical_plan: Mutex::new(None),
+        });
+        self.processes
+            .write()
+            .unwrap()
+            .insert(request_id, process.clone());
+
+        let guard = ProcessGuard {
+            process_list: self.clone(),
+            handle: ProcessHandle { process },
+        };
+        (guard, abort_registration)
+    }
+
+    /// Kill the query with given id, returns false if the query is not found.
+    pub fn kill(&self, request_id: &RequestId) -> bool {
+        let processes = self.processes.read().unwrap();
+        match processes.get(request_id) {
+            Some(process) => {
+                process.killed.store(true, Ordering::Relaxed);
+                process.abort_handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Snapshot of all the in-flight queries, ordered by start time.
+    pub fn list(&self) -> Vec<ProcessInfo> {
+        let mut infos = self
+            .processes
+            .read()
+            .unwrap()
+            .values()
+            .map(|process| process.info())
+            .collect::<Vec<_>>();
+        infos.sort_by_key(|info| info.start_time);
+
+        infos
+    }
+
+    fn remove(&self, request_id: &RequestId) {
+        self.processes.write().unwrap().remove(request_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::{Abortable, Aborted};
+
+    use super::*;
+
+    fn new_desc(request_id: &str) -> ProcessDesc {
+        ProcessDesc {
+            request_id: RequestId::from(request_id),
+            catalog: "horaedb".to_string(),
+            schema: "public".to_string(),
+            client: None,
+            sql: "select 1".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_and_kill() {
+        let process_list = Arc::new(ProcessList::default());
+        let (guard, registration) = process_list.register(new_desc("a"));
+        let (_guard_b, _) = process_list.register(new_desc("b"));
+
+        let infos = process_list.list();
+        assert_eq!(infos.len(), 2);
+        assert!(infos.iter().all(|info| !info.killed));
+
+        assert!(process_list.kill(&RequestId::from("a")));
+        assert!(!process_list.kill(&RequestId::from("c")));
+        let killed = process_list
+            .list()
+            .into_iter()
+            .find(|info| info.desc.request_id.as_str() == "a")
+            .unwrap();
+        assert!(killed.killed);
+
+        let res = Abortable::new(futures::future::pending::<()>(), registration).await;
+        assert_eq!(res, Err(Aborted));
+
+        drop(guard);
+        let infos = process_list.list();
+        assert_eq!(infos.len(), 1);
+        assert_eq!(infos[0].desc.request_id.as_str(), "b");
+    }
+}
@@ -28,7 +28,7 @@ use query_engine::{
     physical_planner::{PhysicalPlanRef, PhysicalPlannerRef},
 };
 use query_frontend::plan::{PriorityContext, QueryPlan};
-use runtime::{Priority, PriorityRuntime};
+use runtime::{AbortOnDrop, Priority, PriorityRuntime};
 use snafu::{ResultExt, Snafu};
 
 use crate::{
@@ -127,21 +127,23 @@ impl Interpreter for SelectInterpreter {
             })
             .context(Select)?;
 
+        if let Some(process) = self.ctx.process() {
+            process.set_physical_plan(physical_plan.clone());
+        }
+
         if matches!(priority, Priority::Low) {
             let executor = self.executor;
-            return self
-                .query_runtime
-                .spawn_with_priority(
-                    async move {
-                        execute_and_collect(query_ctx, executor, physical_plan)
-                            .await
-                            .context(Select)
-                    },
-                    Priority::Low,
-                )
-                .await
-                .context(Spawn)
-                .context(Select)?;
+            let handle = self.query_runtime.spawn_with_priority(
+                async move {
+                    execute_and_collect(query_ctx, executor, physical_plan)
+                        .await
+                        .context(Select)
+                },
+                Priority::Low,
+            );
+            // Abort the spawned task if this future is dropped, e.g. the query is
+            // killed or timeout.
+            return AbortOnDrop(handle).await.context(Spawn).context(Select)?;
         }
 
         execute_and_collect(query_ctx, self.executor, physical_plan)
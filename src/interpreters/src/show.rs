@@ -18,8 +18,8 @@
 use std::{convert::TryInto, sync::Arc};
 
 use arrow::{
-    array::StringArray,
-    datatypes::{DataType, Field, Schema as DataSchema},
+    array::{StringArray, TimestampMillisecondArray, UInt64Array},
+    datatypes::{DataType, Field, Schema as DataSchema, TimeUnit},
     record_batch::RecordBatch,
 };
 use async_trait::async_trait;
@@ -36,13 +36,16 @@ use crate::{
     context::Context,
     interpreter::{
         Interpreter, InterpreterPtr, Output, Result as InterpreterResult, ShowCreateTable,
-        ShowDatabases, ShowTables,
+        ShowDatabases, ShowProcessList, ShowTables,
     },
+    process_list::ProcessListRef,
     show_create::ShowCreateInterpreter,
 };
 
 const SHOW_TABLES_COLUMN_SCHEMA: &str = "Tables";
 const SHOW_DATABASES_COLUMN_SCHEMA: &str = "Schemas";
+const PROCESS_STATE_RUNNING: &str = "running";
+const PROCESS_STATE_KILLED: &str = "killed";
 
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)))]
@@ -99,14 +102,21 @@ pub struct ShowInterpreter {
     ctx: Context,
     plan: ShowPlan,
     catalog_manager: ManagerRef,
+    process_list: ProcessListRef,
 }
 
 impl ShowInterpreter {
-    pub fn create(ctx: Context, plan: ShowPlan, catalog_manager: ManagerRef) -> InterpreterPtr {
+    pub fn create(
+        ctx: Context,
+        plan: ShowPlan,
+        catalog_manager: ManagerRef,
+        process_list: ProcessListRef,
+    ) -> InterpreterPtr {
         Box::new(Self {
             ctx,
             plan,
             catalog_manager,
+            process_list,
         })
     }
 }
@@ -203,6 +213,89 @@ impl ShowInterpreter {
 
         Ok(Output::Records(vec![record_batch]))
     }
+
+    fn show_process_list(process_list: ProcessListRef) -> Result<Output> {
+        let processes = process_list.list();
+
+        let schema = DataSchema::new(vec![
+            Field::new("Id", DataType::Utf8, false),
+            Field::new("Catalog", DataType::Utf8, false),
+            Field::new("Schema", DataType::Utf8, false),
+            Field::new("Client", DataType::Utf8, true),
+            Field::new("Query", DataType::Utf8, false),
+            Field::new(
+                "StartTime",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new("ElapsedMs", DataType::UInt64, false),
+            Field::new("State", DataType::Utf8, false),
+            Field::new("Progress", DataType::Utf8, false),
+        ]);
+
+        let ids = processes
+            .iter()
+            .map(|p| p.desc.request_id.as_str())
+            .collect::<Vec<_>>();
+        let catalogs = processes
+            .iter()
+            .map(|p| p.desc.catalog.as_str())
+            .collect::<Vec<_>>();
+        let schemas = processes
+            .iter()
+            .map(|p| p.desc.schema.as_str())
+            .collect::<Vec<_>>();
+        let clients = processes
+            .iter()
+            .map(|p| p.desc.client.as_deref())
+            .collect::<Vec<_>>();
+        let queries = processes
+            .iter()
+            .map(|p| p.desc.sql.as_str())
+            .collect::<Vec<_>>();
+        let start_times = processes
+            .iter()
+            .map(|p| p.start_time.as_i64())
+            .collect::<Vec<_>>();
+        let elapsed = processes
+            .iter()
+            .map(|p| p.elapsed.as_millis() as u64)
+            .collect::<Vec<_>>();
+        let states = processes
+            .iter()
+            .map(|p| {
+                if p.killed {
+                    PROCESS_STATE_KILLED
+                } else {
+                    PROCESS_STATE_RUNNING
+                }
+            })
+            .collect::<Vec<_>>();
+        let progresses = processes
+            .iter()
+            .map(|p| p.progress.as_str())
+            .collect::<Vec<_>>();
+
+        let record_batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(ids)),
+                Arc::new(StringArray::from(catalogs)),
+                Arc::new(StringArray::from(schemas)),
+                Arc::new(StringArray::from(clients)),
+                Arc::new(StringArray::from(queries)),
+                Arc::new(TimestampMillisecondArray::from(start_times)),
+                Arc::new(UInt64Array::from(elapsed)),
+                Arc::new(StringArray::from(states)),
+                Arc::new(StringArray::from(progresses)),
+            ],
+        )
+        .context(CreateRecordBatch)?;
+
+        let record_batch = record_batch.try_into().context(ToCommonRecordType)?;
+
+        Ok(Output::Records(vec![record_batch]))
+    }
 }
 
 fn to_pattern_re(pattern: &str) -> Result<Regex> {
@@ -227,6 +320,9 @@ impl Interpreter for ShowInterpreter {
             ShowPlan::ShowDatabase => {
                 Self::show_databases(self.ctx, self.catalog_manager).context(ShowDatabases)
             }
+            ShowPlan::ShowProcessList => {
+                Self::show_process_list(self.process_list).context(ShowProcessList)
+            }
         }
     }
 }
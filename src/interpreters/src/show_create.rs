@@ -97,7 +97,13 @@ impl ShowCreateInterpreter {
 
         let mut res = String::new();
         for col in table_schema.columns() {
-            res += format!("`{}` {}", col.name, col.data_type).as_str();
+            match (col.decimal_type, col.element_type) {
+                (Some(decimal_type), _) => res += format!("`{}` {decimal_type}", col.name).as_str(),
+                (_, Some(element_type)) => {
+                    res += format!("`{}` {}<{element_type}>", col.name, col.data_type).as_str()
+                }
+                _ => res += format!("`{}` {}", col.name, col.data_type).as_str(),
+            }
             if col.is_tag {
                 res += " TAG";
             }
@@ -106,6 +112,18 @@ impl ShowCreateInterpreter {
                 res += " DICTIONARY";
             }
 
+            if let Some(aggregation) = col.aggregation {
+                res += format!(" {aggregation}").as_str();
+            }
+
+            if let Some(encoding) = col.encoding {
+                res += format!(" ENCODING {encoding}").as_str();
+            }
+
+            if let Some(compression) = col.compression {
+                res += format!(" COMPRESSION {compression}").as_str();
+            }
+
             if !col.is_nullable {
                 res += " NOT NULL";
             }
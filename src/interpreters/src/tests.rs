@@ -39,6 +39,7 @@ use crate::{
     context::Context,
     factory::Factory,
     interpreter::{Output, Result},
+    process_list::ProcessList,
     table_manipulator::{catalog_based::TableManipulatorImpl, TableManipulatorRef},
 };
 
@@ -90,6 +91,7 @@ where
             self.engine(),
             self.table_manipulator.clone(),
             self.read_runtime.clone(),
+            Arc::new(ProcessList::default()),
         )
     }
 
@@ -243,6 +245,7 @@ where
             self.engine(),
             table_manipulator.clone(),
             self.read_runtime.clone(),
+            Arc::new(ProcessList::default()),
         );
         let insert_sql = "INSERT INTO test_missing_columns_table(key1, key2, field4) VALUES('tagk', 1638428434000, 1), ('tagk2', 1638428434000, 10);";
 
@@ -264,6 +267,7 @@ where
             self.engine(),
             table_manipulator,
             self.read_runtime.clone(),
+            Arc::new(ProcessList::default()),
         );
         let ctx = Context::builder(RequestId::next_id(), None)
             .default_catalog_and_schema(DEFAULT_CATALOG.to_string(), DEFAULT_SCHEMA.to_string())
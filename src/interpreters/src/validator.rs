@@ -100,7 +100,19 @@ impl Validator {
                 }
             }
 
-            Plan::Exists(_) => false,
+            Plan::BackupTable(plan) => {
+                is_sub_table!(plan.table.name())
+            }
+
+            Plan::RestoreTable(plan) => {
+                is_sub_table!(&plan.table)
+            }
+
+            Plan::VerifyTable(plan) => {
+                is_sub_table!(plan.table.name())
+            }
+
+            Plan::Exists(_) | Plan::KillQuery(_) => false,
         }
     }
 }
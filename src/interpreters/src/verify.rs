@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Interpreter for verify table statement
+
+use std::{convert::TryInto, sync::Arc};
+
+use arrow::{
+    array::{BooleanArray, StringArray},
+    datatypes::{DataType, Field, Schema as DataSchema},
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use logger::info;
+use macros::define_result;
+use query_frontend::plan::VerifyTablePlan;
+use snafu::{ResultExt, Snafu};
+use table_engine::table::{VerifyReport, VerifyRequest};
+
+use crate::interpreter::{
+    Interpreter, InterpreterPtr, Output, Result as InterpreterResult, VerifyTable,
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum Error {
+    #[snafu(display("Failed to verify table, table:{}, err:{}", table, source))]
+    Verify {
+        table: String,
+        source: table_engine::table::Error,
+    },
+
+    #[snafu(display("Failed to create record batch, err:{}", source))]
+    CreateRecordBatch { source: arrow::error::ArrowError },
+
+    #[snafu(display(
+        "Failed to convert arrow::RecordBatch to common_types::RecordBatch, err:{}",
+        source
+    ))]
+    ToCommonRecordType {
+        source: common_types::record_batch::Error,
+    },
+}
+
+define_result!(Error);
+
+/// Verify table interpreter
+///
+/// Outputs one row for each issue found.
+pub struct VerifyTableInterpreter {
+    plan: VerifyTablePlan,
+}
+
+impl VerifyTableInterpreter {
+    pub fn create(plan: VerifyTablePlan) -> InterpreterPtr {
+        Box::new(Self { plan })
+    }
+
+    async fn execute_verify(self: Box<Self>) -> Result<Output> {
+        let table = &self.plan.table;
+        let report = table
+            .verify(VerifyRequest {
+                repair: self.plan.repair,
+            })
+            .await
+            .context(Verify {
+                table: table.name(),
+            })?;
+
+        info!(
+            "Verify table finished, table:{}, repair:{}, num_ssts:{}, num_issues:{}",
+            table.name(),
+            self.plan.repair,
+            report.num_ssts,
+            report.issues.len()
+        );
+
+        report_to_output(report)
+    }
+}
+
+fn report_to_output(report: VerifyReport) -> Result<Output> {
+    let schema = DataSchema::new(vec![
+        Field::new("Kind", DataType::Utf8, false),
+        Field::new("Path", DataType::Utf8, false),
+        Field::new("Message", DataType::Utf8, false),
+        Field::new("Repaired", DataType::Boolean, false),
+    ]);
+
+    let issues = &report.issues;
+    let kinds = issues
+        .iter()
+        .map(|issue| issue.kind.as_str())
+        .collect::<Vec<_>>();
+    let paths = issues
+        .iter()
+        .map(|issue| issue.path.as_str())
+        .collect::<Vec<_>>();
+    let msgs = issues
+        .iter()
+        .map(|issue| issue.msg.as_str())
+        .collect::<Vec<_>>();
+    let repaired = issues
+        .iter()
+        .map(|issue| issue.repaired)
+        .collect::<Vec<_>>();
+
+    let record_batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(StringArray::from(kinds)),
+            Arc::new(StringArray::from(paths)),
+            Arc::new(StringArray::from(msgs)),
+            Arc::new(BooleanArray::from(repaired)),
+        ],
+    )
+    .context(CreateRecordBatch)?;
+
+    let record_batch = record_batch.try_into().context(ToCommonRecordType)?;
+
+    Ok(Output::Records(vec![record_batch]))
+}
+
+#[async_trait]
+impl Interpreter for VerifyTableInterpreter {
+    async fn execute(self: Box<Self>) -> InterpreterResult<Output> {
+        self.execute_verify().await.context(VerifyTable)
+    }
+}
@@ -21,6 +21,7 @@ use std::time::Duration;
 
 use common_types::request_id::RequestId;
 use macros::define_result;
+use query_engine::limits::QueryLimits;
 use snafu::{ensure, Backtrace, Snafu};
 
 #[allow(clippy::enum_variant_names)]
@@ -56,6 +57,16 @@ pub struct RequestContext {
     pub timeout: Option<Duration>,
     /// Request id
     pub request_id: RequestId,
+    /// Client of the request, e.g. the connection info of the session
+    pub client: Option<String>,
+    /// Whether to skip the query cache
+    pub bypass_query_cache: bool,
+    /// Whether the request comes from the admin api, only which can execute the
+    /// admin statements, e.g. backup and restore
+    pub is_admin: bool,
+    /// Limits of the query set by the request, which can only tighten the
+    /// configured limits
+    pub query_limits: QueryLimits,
 }
 
 impl RequestContext {
@@ -69,6 +80,9 @@ pub struct Builder {
     catalog: String,
     schema: String,
     timeout: Option<Duration>,
+    client: Option<String>,
+    bypass_query_cache: bool,
+    query_limits: QueryLimits,
 }
 
 impl Builder {
@@ -87,6 +101,21 @@ impl Builder {
         self
     }
 
+    pub fn client(mut self, client: String) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn bypass_query_cache(mut self, bypass_query_cache: bool) -> Self {
+        self.bypass_query_cache = bypass_query_cache;
+        self
+    }
+
+    pub fn query_limits(mut self, query_limits: QueryLimits) -> Self {
+        self.query_limits = query_limits;
+        self
+    }
+
     pub fn build(self) -> Result<RequestContext> {
         ensure!(!self.catalog.is_empty(), MissingCatalog);
         ensure!(!self.schema.is_empty(), MissingSchema);
@@ -96,6 +125,10 @@ impl Builder {
             schema: self.schema,
             timeout: self.timeout,
             request_id: RequestId::next_id(),
+            client: self.client,
+            bypass_query_cache: self.bypass_query_cache,
+            is_admin: false,
+            query_limits: self.query_limits,
         })
     }
 }
@@ -15,16 +15,63 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::error::Error as StdError;
+
+use datafusion::error::DataFusionError;
 use generic_error::GenericError;
 use horaedbproto::common::ResponseHeader;
 use http::StatusCode;
 use macros::define_result;
+use query_engine::limits::Error as QueryLimitError;
 use snafu::{Backtrace, Snafu};
 
 use crate::error_util;
 
 define_result!(Error);
 
+/// Status code of the query killed by `KILL QUERY`, borrowed from the
+/// non-standard `Client Closed Request` of nginx.
+const QUERY_KILLED_CODE: u16 = 499;
+
+#[inline]
+pub fn query_killed_code() -> StatusCode {
+    StatusCode::from_u16(QUERY_KILLED_CODE).unwrap()
+}
+
+/// Whether the error is caused by a query exceeding its memory limit.
+pub fn is_memory_limit_exceeded(err: &(dyn StdError + 'static)) -> bool {
+    any_source(err, |e| {
+        matches!(
+            e.downcast_ref::<DataFusionError>(),
+            Some(DataFusionError::ResourcesExhausted(_))
+        )
+    })
+}
+
+/// Whether the error is caused by a query exceeding its [QueryLimits].
+///
+/// [QueryLimits]: query_engine::limits::QueryLimits
+pub fn is_query_limit_exceeded(err: &(dyn StdError + 'static)) -> bool {
+    any_source(err, |e| e.is::<QueryLimitError>())
+}
+
+/// Whether any error in the source chain of `err`, including itself, matches
+/// the predicate.
+fn any_source(
+    err: &(dyn StdError + 'static),
+    predicate: impl Fn(&(dyn StdError + 'static)) -> bool,
+) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if predicate(err) {
+            return true;
+        }
+        source = err.source();
+    }
+
+    false
+}
+
 #[derive(Snafu, Debug)]
 #[snafu(visibility(pub))]
 pub enum Error {
@@ -107,3 +154,40 @@ impl From<router::Error> for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::error::ArrowError;
+
+    use super::*;
+
+    #[test]
+    fn test_is_memory_limit_exceeded() {
+        let exhausted = DataFusionError::ResourcesExhausted("memory".to_string());
+        let err = Error::Internal {
+            msg: "Failed to execute interpreter".to_string(),
+            source: Box::new(DataFusionError::ArrowError(ArrowError::ExternalError(
+                Box::new(exhausted),
+            ))),
+        };
+        assert!(is_memory_limit_exceeded(&err));
+
+        let err = Error::Internal {
+            msg: "Failed to execute interpreter".to_string(),
+            source: Box::new(DataFusionError::Execution("failed".to_string())),
+        };
+        assert!(!is_memory_limit_exceeded(&err));
+        assert!(!is_query_limit_exceeded(&err));
+    }
+
+    #[test]
+    fn test_is_query_limit_exceeded() {
+        let exceeded = QueryLimitError::ResultRowsExceeded { limit: 10 };
+        let err = Error::Internal {
+            msg: "Failed to execute interpreter".to_string(),
+            source: Box::new(DataFusionError::External(Box::new(exceeded))),
+        };
+        assert!(is_query_limit_exceeded(&err));
+        assert!(!is_memory_limit_exceeded(&err));
+    }
+}
@@ -126,6 +126,7 @@ impl Proxy {
                 code: StatusCode::FORBIDDEN,
                 msg: "Query is blocked",
             })?;
+        self.try_acquire_rate_limit(&schema, &plan)?;
 
         let output = self
             .execute_plan(request_id.clone(), catalog, &schema, plan, deadline)
@@ -15,9 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use crate::{handlers::prelude::*, limiter::BlockRule};
+use crate::{
+    handlers::prelude::*,
+    http::sql::Request as SqlRequest,
+    limiter::{BlockRule, RateLimit},
+};
 
 #[derive(Debug, Deserialize)]
 pub enum Operation {
@@ -77,3 +81,174 @@ pub async fn handle_block(
         block_rules: limiter.get_block_rules().into_iter().collect(),
     })
 }
+
+/// The keys of the tables are `schema.table`, and only the keys are used for
+/// the remove operation.
+#[derive(Debug, Deserialize)]
+pub struct RateLimitRequest {
+    operation: Operation,
+    #[serde(default)]
+    schema_rate_limits: HashMap<String, RateLimit>,
+    #[serde(default)]
+    table_rate_limits: HashMap<String, RateLimit>,
+}
+
+#[derive(Serialize)]
+pub struct RateLimitResponse {
+    schema_rate_limits: BTreeMap<String, RateLimit>,
+    table_rate_limits: BTreeMap<String, RateLimit>,
+}
+
+pub async fn handle_rate_limit(
+    _ctx: RequestContext,
+    instance: InstanceRef,
+    request: RateLimitRequest,
+) -> Result<RateLimitResponse> {
+    let limiter = &instance.limiter;
+    match request.operation {
+        Operation::Add => {
+            limiter.add_schema_rate_limits(request.schema_rate_limits);
+            limiter.add_table_rate_limits(request.table_rate_limits);
+        }
+        Operation::Set => {
+            limiter.set_schema_rate_limits(request.schema_rate_limits);
+            limiter.set_table_rate_limits(request.table_rate_limits);
+        }
+        Operation::Remove => {
+            limiter.remove_schema_rate_limits(request.schema_rate_limits.into_keys().collect());
+            limiter.remove_table_rate_limits(request.table_rate_limits.into_keys().collect());
+        }
+    }
+
+    Ok(RateLimitResponse {
+        schema_rate_limits: limiter.get_schema_rate_limits().into_iter().collect(),
+        table_rate_limits: limiter.get_table_rate_limits().into_iter().collect(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TableBackupRequest {
+    table: String,
+    path: String,
+}
+
+impl TableBackupRequest {
+    /// Build the `BACKUP TABLE` statement for this request.
+    pub fn to_backup_sql(&self) -> SqlRequest {
+        SqlRequest {
+            query: format!(
+                "BACKUP TABLE {} TO {}",
+                quote_ident(&self.table),
+                quote_literal(&self.path)
+            ),
+        }
+    }
+
+    /// Build the `RESTORE TABLE` statement for this request.
+    pub fn to_restore_sql(&self) -> SqlRequest {
+        SqlRequest {
+            query: format!(
+                "RESTORE TABLE {} FROM {}",
+                quote_ident(&self.table),
+                quote_literal(&self.path)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TableVerifyRequest {
+    table: String,
+    #[serde(default)]
+    repair: bool,
+}
+
+impl TableVerifyRequest {
+    /// Build the `VERIFY TABLE` statement for this request.
+    pub fn to_sql(&self) -> SqlRequest {
+        let repair = if self.repair { " REPAIR" } else { "" };
+        SqlRequest {
+            query: format!("VERIFY TABLE {}{repair}", quote_ident(&self.table)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KillQueryRequest {
+    query_id: String,
+}
+
+impl KillQueryRequest {
+    /// Build the `KILL QUERY` statement for this request.
+    pub fn to_sql(&self) -> SqlRequest {
+        SqlRequest {
+            query: format!("KILL QUERY {}", quote_literal(&self.query_id)),
+        }
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("`{}`", ident.replace('`', "``"))
+}
+
+fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use query_frontend::{ast::Statement, parser::Parser};
+
+    use super::*;
+
+    #[test]
+    fn test_table_backup_request_to_sql() {
+        let req = TableBackupRequest {
+            table: "demo".to_string(),
+            path: "demo/it's\\".to_string(),
+        };
+        assert_eq!(
+            "BACKUP TABLE `demo` TO 'demo/it''s\\\\'",
+            req.to_backup_sql().query
+        );
+        assert_eq!(
+            "RESTORE TABLE `demo` FROM 'demo/it''s\\\\'",
+            req.to_restore_sql().query
+        );
+
+        let stmts = Parser::parse_sql(&req.to_backup_sql().query).unwrap();
+        match &stmts[0] {
+            Statement::BackupTable(s) => assert_eq!(req.path, s.path),
+            s => panic!("unexpected statement:{s:?}"),
+        }
+    }
+
+    #[test]
+    fn test_table_verify_request_to_sql() {
+        let req = TableVerifyRequest {
+            table: "demo".to_string(),
+            repair: false,
+        };
+        assert_eq!("VERIFY TABLE `demo`", req.to_sql().query);
+
+        let req = TableVerifyRequest {
+            table: "de`mo".to_string(),
+            repair: true,
+        };
+        assert_eq!("VERIFY TABLE `de``mo` REPAIR", req.to_sql().query);
+    }
+
+    #[test]
+    fn test_kill_query_request_to_sql() {
+        let req = KillQueryRequest {
+            query_id: "a1'b2".to_string(),
+        };
+        assert_eq!("KILL QUERY 'a1''b2'", req.to_sql().query);
+
+        let stmts = Parser::parse_sql(&req.to_sql().query).unwrap();
+        match &stmts[0] {
+            Statement::KillQuery(s) => assert_eq!(req.query_id, s.query_id),
+            s => panic!("unexpected statement:{s:?}"),
+        }
+    }
+}
@@ -158,6 +158,7 @@ impl Proxy {
                 code: StatusCode::INTERNAL_SERVER_ERROR,
                 msg: "Query is blocked",
             })?;
+        self.try_acquire_rate_limit(&ctx.schema, &plan)?;
         let output = self
             .execute_plan(
                 request_id.clone(),
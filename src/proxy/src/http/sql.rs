@@ -50,7 +50,11 @@ impl Proxy {
         req: Request,
     ) -> Result<Output> {
         let schema = &ctx.schema;
-        let ctx = Context::new(ctx.timeout, None);
+        let ctx = Context::new(ctx.timeout, None)
+            .with_client(ctx.client.clone())
+            .with_bypass_query_cache(ctx.bypass_query_cache)
+            .with_is_admin(ctx.is_admin)
+            .with_query_limits(ctx.query_limits);
 
         let query_res = self
             .handle_sql(
@@ -190,7 +194,9 @@ fn convert_records(records: RecordBatchVec) -> Response {
     })
 }
 
-fn convert_sql_response_to_output(sql_query_response: SqlQueryResponse) -> Result<Output> {
+pub(crate) fn convert_sql_response_to_output(
+    sql_query_response: SqlQueryResponse,
+) -> Result<Output> {
     if let Some(header) = sql_query_response.header {
         if header.code as u16 != StatusCode::OK.as_u16() {
             return ErrNoCause {
@@ -15,9 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! This module implements [write][1] and [query][2] for InfluxDB.
+//! This module implements [write][1] and [query][2] for InfluxDB, and also
+//! the [write][3] and influxql [query][4] of InfluxDB 2.x.
 //! [1]: https://docs.influxdata.com/influxdb/v1.8/tools/api/#write-http-endpoint
 //! [2]: https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+//! [3]: https://docs.influxdata.com/influxdb/v2.7/api/#operation/PostWrite
+//! [4]: https://docs.influxdata.com/influxdb/v2.7/api/#operation/PostQuery
 
 pub mod types;
 
@@ -58,6 +61,26 @@ impl Proxy {
         convert_influxql_output(output)
     }
 
+    /// Query of influxdb 2.x, and the bucket is used as the schema.
+    pub async fn handle_influxdb_v2_query(
+        &self,
+        mut ctx: RequestContext,
+        req: InfluxqlRequest,
+    ) -> Result<InfluxqlResponse> {
+        ctx.schema = req.db.clone();
+        self.handle_influxdb_query(ctx, req).await
+    }
+
+    /// Write of influxdb 2.x, and the bucket is used as the schema.
+    pub async fn handle_influxdb_v2_write(
+        &self,
+        mut ctx: RequestContext,
+        req: WriteRequest,
+    ) -> Result<WriteResponse> {
+        ctx.schema = req.db.clone();
+        self.handle_influxdb_write(ctx, req).await
+    }
+
     pub async fn handle_influxdb_write(
         &self,
         ctx: RequestContext,
@@ -182,6 +205,7 @@ impl Proxy {
                 code: StatusCode::INTERNAL_SERVER_ERROR,
                 msg: "Query is blocked",
             })?;
+        self.try_acquire_rate_limit(&ctx.schema, &plan)?;
         let output = self
             .execute_plan(
                 request_id.clone(),
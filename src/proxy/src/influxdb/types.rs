@@ -28,14 +28,14 @@ use generic_error::BoxError;
 use horaedbproto::storage::{
     value, Field, FieldGroup, Tag, Value, WriteSeriesEntry, WriteTableRequest,
 };
-use http::Method;
+use http::{Method, StatusCode};
 use influxdb_line_protocol::FieldValue;
 use interpreters::interpreter::Output;
 use query_frontend::influxql::planner::HORAEDB_MEASUREMENT_COLUMN_NAME;
 use serde::{Deserialize, Serialize};
 use snafu::{ensure, OptionExt, ResultExt};
 
-use crate::error::{Internal, InternalNoCause, Result};
+use crate::error::{ErrNoCause, ErrWithCause, Internal, InternalNoCause, Result};
 
 /// Influxql write request compatible with influxdb 1.8
 ///
@@ -93,6 +93,62 @@ impl Default for WriteParams {
     }
 }
 
+/// Query string parameters for write api of influxdb 2.x
+///
+/// It's derived from query string parameters of write described in
+/// doc of influxdb 2.7:
+///     https://docs.influxdata.com/influxdb/v2.7/api/#operation/PostWrite
+///
+/// NOTE:
+///     - `org` is ignored in HoraeDB.
+///     - `bucket` is mapped to schema, and the retention policy part in
+///       DBRP-style bucket(`db/rp`) is ignored.
+///     - `precision`'s default value is `ns` like influxdb 2.x, because the v2
+///       clients(e.g. telegraf) don't set it by default.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct WriteV2Params {
+    pub org: Option<String>,
+    pub bucket: String,
+    pub precision: String,
+}
+
+impl Default for WriteV2Params {
+    fn default() -> Self {
+        Self {
+            org: None,
+            bucket: String::new(),
+            precision: "ns".to_string(),
+        }
+    }
+}
+
+impl WriteRequest {
+    pub fn try_new_v2(lines: Bytes, params: WriteV2Params) -> Result<Self> {
+        let lines = String::from_utf8_lossy(&lines).to_string();
+
+        Ok(WriteRequest {
+            lines,
+            db: bucket_to_schema(&params.bucket)?,
+            precision: Precision::try_from_v2(&params.precision)?,
+        })
+    }
+}
+
+/// Convert the bucket of influxdb 2.x to the schema.
+fn bucket_to_schema(bucket: &str) -> Result<String> {
+    let schema = bucket.split('/').next().unwrap_or_default();
+    ensure!(
+        !schema.is_empty(),
+        ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: "bucket is required",
+        }
+    );
+
+    Ok(schema.to_string())
+}
+
 /// Influxql query request compatible with influxdb 1.8
 ///
 /// It's derived from 1.x query api described in doc of influxdb 1.8:
@@ -157,6 +213,80 @@ impl InfluxqlRequest {
     }
 }
 
+/// Query string parameters for query api of influxdb 2.x
+///
+/// Only influxql is supported, and it's derived from the query described in
+/// doc of influxdb 2.7:
+///     https://docs.influxdata.com/influxdb/v2.7/api/#operation/PostQuery
+///
+/// NOTE:
+///     - `org` is ignored in HoraeDB.
+///     - `bucket` in the json body takes precedence over this one.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryV2Params {
+    pub org: Option<String>,
+    pub bucket: Option<String>,
+}
+
+/// Json body of query api of influxdb 2.x.
+#[derive(Debug, Deserialize)]
+struct QueryV2Body {
+    query: String,
+    #[serde(rename = "type")]
+    query_type: Option<String>,
+    bucket: Option<String>,
+}
+
+/// Content type of raw influxql body in influxdb 2.x.
+pub const INFLUXQL_CONTENT_TYPE: &str = "application/vnd.influxql";
+
+impl InfluxqlRequest {
+    /// Build request from the query api of influxdb 2.x, the body can be the
+    /// raw influxql(with content type `application/vnd.influxql`) or json with
+    /// `type` set to `influxql`.
+    pub fn try_new_v2(
+        content_type: Option<String>,
+        body: Bytes,
+        params: QueryV2Params,
+    ) -> Result<Self> {
+        let (query, bucket) = if content_type.as_deref() == Some(INFLUXQL_CONTENT_TYPE) {
+            (String::from_utf8_lossy(&body).to_string(), params.bucket)
+        } else {
+            let body: QueryV2Body =
+                serde_json::from_slice(&body)
+                    .box_err()
+                    .with_context(|| ErrWithCause {
+                        code: StatusCode::BAD_REQUEST,
+                        msg: "Json parse error",
+                    })?;
+            ensure!(
+                body.query_type.as_deref() == Some("influxql"),
+                ErrNoCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!(
+                        "only influxql is supported, query type:{:?}",
+                        body.query_type
+                    ),
+                }
+            );
+            (body.query, body.bucket.or(params.bucket))
+        };
+
+        let bucket = bucket.with_context(|| ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: "bucket is required",
+        })?;
+
+        Ok(InfluxqlRequest {
+            query,
+            db: bucket_to_schema(&bucket)?,
+            epoch: Precision::default(),
+            pretty: false,
+        })
+    }
+}
+
 #[derive(Debug, Default)]
 pub enum Precision {
     #[default]
@@ -179,6 +309,22 @@ impl Precision {
             Self::Hour => ts.checked_mul(1000 * 60 * 60),
         }
     }
+
+    /// Parse the precision of influxdb 2.x, which only supports `ns`, `us`,
+    /// `ms` and `s`.
+    fn try_from_v2(value: &str) -> Result<Self> {
+        match value {
+            "ns" => Ok(Precision::Nanosecond),
+            "us" => Ok(Precision::Microsecond),
+            "ms" => Ok(Precision::Millisecond),
+            "s" => Ok(Precision::Second),
+            _ => ErrNoCause {
+                code: StatusCode::BAD_REQUEST,
+                msg: format!("invalid precision:{value}, expected one of ns, us, ms and s"),
+            }
+            .fail(),
+        }
+    }
 }
 
 impl From<&str> for Precision {
@@ -900,4 +1046,65 @@ mod tests {
             field_builder2.build(),
         ]
     }
+
+    #[test]
+    fn test_build_v2_write_req() {
+        let params = WriteV2Params {
+            org: Some("my-org".to_string()),
+            bucket: "telegraf/autogen".to_string(),
+            ..Default::default()
+        };
+        let req = WriteRequest::try_new_v2(Bytes::from("m1,t=a f=1 1000000"), params).unwrap();
+        assert_eq!("telegraf", req.db);
+        assert!(matches!(req.precision, Precision::Nanosecond));
+
+        let params = WriteV2Params {
+            bucket: "telegraf".to_string(),
+            precision: "us".to_string(),
+            ..Default::default()
+        };
+        let req = WriteRequest::try_new_v2(Bytes::new(), params).unwrap();
+        assert!(matches!(req.precision, Precision::Microsecond));
+
+        let params = WriteV2Params {
+            bucket: "telegraf".to_string(),
+            precision: "h".to_string(),
+            ..Default::default()
+        };
+        assert!(WriteRequest::try_new_v2(Bytes::new(), params).is_err());
+
+        let params = WriteV2Params::default();
+        assert!(WriteRequest::try_new_v2(Bytes::new(), params).is_err());
+    }
+
+    #[test]
+    fn test_build_v2_influxql_req() {
+        let req = InfluxqlRequest::try_new_v2(
+            Some(INFLUXQL_CONTENT_TYPE.to_string()),
+            Bytes::from("select * from m1"),
+            QueryV2Params {
+                org: None,
+                bucket: Some("db1/rp1".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!("select * from m1", req.query);
+        assert_eq!("db1", req.db);
+
+        let body = r#"{"query":"select * from m1","type":"influxql","bucket":"db2"}"#;
+        let req = InfluxqlRequest::try_new_v2(
+            Some("application/json".to_string()),
+            Bytes::from(body),
+            QueryV2Params {
+                org: None,
+                bucket: Some("db1".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!("db2", req.db);
+
+        let body = r#"{"query":"from(bucket: \"db1\")","type":"flux"}"#;
+        let res = InfluxqlRequest::try_new_v2(None, Bytes::from(body), QueryV2Params::default());
+        assert!(res.is_err());
+    }
 }
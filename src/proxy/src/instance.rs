@@ -21,7 +21,7 @@ use std::sync::{atomic::AtomicU64, Arc};
 
 use catalog::manager::ManagerRef;
 use df_operator::registry::FunctionRegistryRef;
-use interpreters::table_manipulator::TableManipulatorRef;
+use interpreters::{process_list::ProcessListRef, table_manipulator::TableManipulatorRef};
 use query_engine::QueryEngineRef;
 use query_frontend::config::DynamicConfig as FrontendDynamicConfig;
 use runtime::PriorityRuntime;
@@ -43,6 +43,8 @@ pub struct Instance {
     pub table_manipulator: TableManipulatorRef,
     pub remote_engine_ref: RemoteEngineRef,
     pub dyn_config: DynamicConfig,
+    /// In-flight queries on this node
+    pub process_list: ProcessListRef,
 }
 
 /// A reference counted instance pointer
@@ -34,12 +34,17 @@ pub mod instance;
 pub mod limiter;
 mod metrics;
 pub mod opentsdb;
+pub mod otlp;
+pub mod query_cache;
 mod read;
 pub mod schema_config_provider;
 mod util;
 mod write;
 
 pub const FORWARDED_FROM: &str = "forwarded-from";
+/// Marks the forwarded request is sent by the admin api, only trusted along
+/// with [FORWARDED_FROM].
+pub const FORWARDED_AS_ADMIN: &str = "forwarded-as-admin";
 
 use std::{
     sync::Arc,
@@ -64,8 +69,10 @@ use interpreters::{
     context::Context as InterpreterContext,
     factory::Factory,
     interpreter::{InterpreterPtr, Output},
+    process_list::ProcessHandle,
 };
 use logger::{error, info, warn};
+use query_engine::limits::QueryLimits;
 use query_frontend::plan::Plan;
 use router::{endpoint::Endpoint, RouteRequest, Router};
 use serde::{Deserialize, Serialize};
@@ -80,10 +87,11 @@ use table_engine::{
 use tonic::{transport::Channel, IntoRequest};
 
 use crate::{
-    error::{ErrNoCause, ErrWithCause, Error, Internal, Result},
+    error::{self, ErrNoCause, ErrWithCause, Error, Internal, Result},
     forward::{ForwardRequest, ForwardResult, Forwarder, ForwarderRef},
     hotspot::HotspotRecorder,
     instance::InstanceRef,
+    query_cache::QueryCacheRef,
     read::ReadRequestNotifiers,
     schema_config_provider::SchemaConfigProviderRef,
 };
@@ -117,6 +125,7 @@ pub struct Proxy {
     sub_table_access_perm: SubTableAccessPerm,
     request_notifiers: Option<ReadRequestNotifiers>,
     expensive_query_threshold: u64,
+    query_cache: Option<QueryCacheRef>,
 }
 
 impl Proxy {
@@ -135,6 +144,7 @@ impl Proxy {
         sub_table_access_perm: SubTableAccessPerm,
         request_notifiers: Option<ReadRequestNotifiers>,
         expensive_query_threshold: u64,
+        query_cache: Option<QueryCacheRef>,
     ) -> Self {
         let forwarder = Arc::new(Forwarder::new(
             forward_config,
@@ -155,6 +165,7 @@ impl Proxy {
             sub_table_access_perm,
             request_notifiers,
             expensive_query_threshold,
+            query_cache,
         }
     }
 
@@ -447,20 +458,21 @@ impl Proxy {
             })
     }
 
-    async fn execute_plan(
-        &self,
-        request_id: RequestId,
-        catalog: &str,
-        schema: &str,
-        plan: Plan,
-        deadline: Option<Instant>,
-    ) -> Result<Output> {
-        let interpreter =
-            self.build_interpreter(request_id, catalog, schema, plan, deadline, false)?;
-        Self::interpreter_execute_plan(interpreter, deadline).await
+    /// Throttle the request if the rate limits of the schemas or tables
+    /// accessed by the plan are exceeded, and the client is expected to retry
+    /// later.
+    fn try_acquire_rate_limit(&self, schema: &str, plan: &Plan) -> Result<()> {
+        Self::convert_throttled_error(self.instance.limiter.try_acquire(schema, plan))
     }
 
-    async fn execute_plan_involving_partition_table(
+    fn convert_throttled_error(result: limiter::Result<()>) -> Result<()> {
+        result.box_err().context(ErrWithCause {
+            code: StatusCode::TOO_MANY_REQUESTS,
+            msg: "Request is throttled",
+        })
+    }
+
+    async fn execute_plan(
         &self,
         request_id: RequestId,
         catalog: &str,
@@ -468,11 +480,21 @@ impl Proxy {
         plan: Plan,
         deadline: Option<Instant>,
     ) -> Result<Output> {
-        let interpreter =
-            self.build_interpreter(request_id, catalog, schema, plan, deadline, true)?;
+        let query_limits = self.instance.limiter.query_limits(schema);
+        let interpreter = self.build_interpreter(
+            request_id,
+            catalog,
+            schema,
+            plan,
+            deadline,
+            false,
+            None,
+            query_limits,
+        )?;
         Self::interpreter_execute_plan(interpreter, deadline).await
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn build_interpreter(
         &self,
         request_id: RequestId,
@@ -481,13 +503,19 @@ impl Proxy {
         plan: Plan,
         deadline: Option<Instant>,
         enable_partition_table_access: bool,
+        process: Option<ProcessHandle>,
+        query_limits: QueryLimits,
     ) -> Result<InterpreterPtr> {
-        let interpreter_ctx = InterpreterContext::builder(request_id, deadline)
+        let mut builder = InterpreterContext::builder(request_id, deadline)
             // Use current ctx's catalog and schema as default catalog and schema
             .default_catalog_and_schema(catalog.to_string(), schema.to_string())
             .enable_partition_table_access(enable_partition_table_access)
             .expensive_query_threshold(self.expensive_query_threshold)
-            .build();
+            .query_limits(query_limits);
+        if let Some(process) = process {
+            builder = builder.process(process);
+        }
+        let interpreter_ctx = builder.build();
         let interpreter_factory = Factory::new(
             self.instance.query_engine.executor(),
             self.instance.query_engine.physical_planner(),
@@ -495,6 +523,7 @@ impl Proxy {
             self.instance.table_engine.clone(),
             self.instance.table_manipulator.clone(),
             self.instance.query_runtime.clone(),
+            self.instance.process_list.clone(),
         );
         interpreter_factory
             .create(interpreter_ctx, plan)
@@ -508,7 +537,7 @@ impl Proxy {
         interpreter: InterpreterPtr,
         deadline: Option<Instant>,
     ) -> Result<Output> {
-        if let Some(deadline) = deadline {
+        let output = if let Some(deadline) = deadline {
             tokio::time::timeout_at(
                 tokio::time::Instant::from_std(deadline),
                 interpreter.execute(),
@@ -517,16 +546,30 @@ impl Proxy {
             .box_err()
             .context(Internal {
                 msg: "Plan execution timeout",
-            })
-            .and_then(|v| {
-                v.box_err().context(Internal {
-                    msg: "Failed to execute interpreter",
-                })
-            })
+            })?
         } else {
-            interpreter.execute().await.box_err().context(Internal {
+            interpreter.execute().await
+        };
+
+        match output {
+            Ok(v) => Ok(v),
+            // The query exceeding its limits is rejected as a client error as retrying
+            // it won't help.
+            Err(e) if error::is_memory_limit_exceeded(&e) => {
+                Err(e).box_err().context(ErrWithCause {
+                    code: StatusCode::PAYLOAD_TOO_LARGE,
+                    msg: "Query memory limit exceeded",
+                })
+            }
+            Err(e) if error::is_query_limit_exceeded(&e) => {
+                Err(e).box_err().context(ErrWithCause {
+                    code: StatusCode::UNPROCESSABLE_ENTITY,
+                    msg: "Query limit exceeded",
+                })
+            }
+            Err(e) => Err(e).box_err().context(Internal {
                 msg: "Failed to execute interpreter",
-            })
+            }),
         }
     }
 }
@@ -536,6 +579,13 @@ pub struct Context {
     request_id: RequestId,
     timeout: Option<Duration>,
     forwarded_from: Option<String>,
+    client: Option<String>,
+    /// Skip the query cache and execute the query anyway
+    bypass_query_cache: bool,
+    /// Whether the request comes from the admin api
+    is_admin: bool,
+    /// Limits of the query set by the request
+    query_limits: QueryLimits,
 }
 
 impl Context {
@@ -544,6 +594,30 @@ impl Context {
             request_id: RequestId::next_id(),
             timeout,
             forwarded_from,
+            client: None,
+            bypass_query_cache: false,
+            is_admin: false,
+            query_limits: QueryLimits::default(),
         }
     }
+
+    pub fn with_client(mut self, client: Option<String>) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_bypass_query_cache(mut self, bypass_query_cache: bool) -> Self {
+        self.bypass_query_cache = bypass_query_cache;
+        self
+    }
+
+    pub fn with_is_admin(mut self, is_admin: bool) -> Self {
+        self.is_admin = is_admin;
+        self
+    }
+
+    pub fn with_query_limits(mut self, query_limits: QueryLimits) -> Self {
+        self.query_limits = query_limits;
+        self
+    }
 }
@@ -15,17 +15,27 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::{collections::HashSet, str::FromStr, sync::RwLock};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Add,
+    str::FromStr,
+    sync::{Mutex, RwLock},
+    time::Instant,
+};
 
 use datafusion::logical_expr::logical_plan::LogicalPlan;
+use horaedbproto::storage::WriteTableRequest;
 use logger::error;
 use macros::define_result;
-use query_frontend::plan::Plan;
+use prost::Message;
+use query_engine::limits::QueryLimits;
+use query_frontend::plan::{InsertPlan, Plan};
 use serde::{Deserialize, Serialize};
+use size_ext::ReadableSize;
 use snafu::Snafu;
 use time_ext::ReadableDuration;
 
-use crate::metrics::BLOCKED_REQUEST_COUNTER_VEC_GLOBAL;
+use crate::metrics::{BLOCKED_REQUEST_COUNTER_VEC_GLOBAL, THROTTLED_REQUEST_COUNTER_VEC_GLOBAL};
 
 #[derive(Snafu, Debug)]
 #[snafu(visibility(pub))]
@@ -35,6 +45,15 @@ pub enum Error {
 
     #[snafu(display("Query is blocked by rule:{:?}", rule))]
     BlockedByRule { rule: BlockRule },
+
+    #[snafu(display("Invalid query limit hint, hint:{}, msg:{}", hint, msg))]
+    InvalidQueryLimitHint { hint: String, msg: String },
+
+    #[snafu(display("Request is throttled, target:{}, resource:{}", target, resource))]
+    Throttled {
+        target: String,
+        resource: &'static str,
+    },
 }
 
 define_result!(Error);
@@ -66,6 +85,170 @@ pub struct LimiterConfig {
     pub write_block_list: Vec<String>,
     pub read_block_list: Vec<String>,
     pub rules: Vec<BlockRule>,
+    /// Default limits of the queries
+    pub query_limits: QueryLimits,
+    /// Limits of the queries on the specific schemas, the limits not set
+    /// fallback to the default ones
+    pub schema_query_limits: HashMap<String, QueryLimits>,
+    /// Rate limits of the requests on the specific schemas
+    pub schema_rate_limits: HashMap<String, RateLimit>,
+    /// Rate limits of the requests on the specific tables, keyed by
+    /// `schema.table`
+    pub table_rate_limits: HashMap<String, RateLimit>,
+}
+
+/// Rate limits of the requests, and the limits not set are unlimited.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RateLimit {
+    /// Max rows written per second
+    pub write_rows_per_second: Option<u64>,
+    /// Max bytes written per second
+    pub write_bytes_per_second: Option<ReadableSize>,
+    /// Max queries per second
+    pub read_queries_per_second: Option<u64>,
+}
+
+/// Token bucket refilled at `rate` tokens per second, and the burst is up to
+/// the tokens of one second.
+///
+/// The request is only throttled when the bucket is exhausted, so the
+/// requests larger than the burst can still pass and the excess is paid back
+/// later.
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            rate: rate as f64,
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    fn is_exhausted(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        self.tokens <= 0.0
+    }
+
+    fn consume(&mut self, tokens: u64) {
+        self.tokens -= tokens as f64;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct RequestCost {
+    write_rows: u64,
+    write_bytes: u64,
+    read_queries: u64,
+}
+
+impl Add for RequestCost {
+    type Output = RequestCost;
+
+    fn add(self, other: RequestCost) -> RequestCost {
+        RequestCost {
+            write_rows: self.write_rows + other.write_rows,
+            write_bytes: self.write_bytes + other.write_bytes,
+            read_queries: self.read_queries + other.read_queries,
+        }
+    }
+}
+
+struct RateLimiter {
+    limit: RateLimit,
+    write_rows: Option<TokenBucket>,
+    write_bytes: Option<TokenBucket>,
+    read_queries: Option<TokenBucket>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            write_rows: limit
+                .write_rows_per_second
+                .map(|v| TokenBucket::new(v, now)),
+            write_bytes: limit
+                .write_bytes_per_second
+                .map(|v| TokenBucket::new(v.as_byte(), now)),
+            read_queries: limit
+                .read_queries_per_second
+                .map(|v| TokenBucket::new(v, now)),
+        }
+    }
+
+    fn buckets(&mut self, cost: RequestCost) -> [(&'static str, Option<&mut TokenBucket>, u64); 3] {
+        [
+            ("write_rows", self.write_rows.as_mut(), cost.write_rows),
+            ("write_bytes", self.write_bytes.as_mut(), cost.write_bytes),
+            (
+                "read_queries",
+                self.read_queries.as_mut(),
+                cost.read_queries,
+            ),
+        ]
+    }
+
+    /// Returns the exhausted resource the request needs, if any.
+    fn find_exhausted(&mut self, cost: RequestCost, now: Instant) -> Option<&'static str> {
+        self.buckets(cost)
+            .into_iter()
+            .find_map(|(resource, bucket, tokens)| match bucket {
+                Some(bucket) if tokens > 0 && bucket.is_exhausted(now) => Some(resource),
+                _ => None,
+            })
+    }
+
+    fn consume(&mut self, cost: RequestCost) {
+        for (_, bucket, tokens) in self.buckets(cost) {
+            if let Some(bucket) = bucket {
+                bucket.consume(tokens);
+            }
+        }
+    }
+}
+
+type RateLimiters = RwLock<HashMap<String, Mutex<RateLimiter>>>;
+
+fn get_rate_limits(limiters: &RateLimiters) -> HashMap<String, RateLimit> {
+    limiters
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(key, limiter)| (key.clone(), limiter.lock().unwrap().limit))
+        .collect()
+}
+
+fn add_rate_limits(limiters: &RateLimiters, limits: HashMap<String, RateLimit>) {
+    let now = Instant::now();
+    limiters.write().unwrap().extend(
+        limits
+            .into_iter()
+            .map(|(key, limit)| (key, Mutex::new(RateLimiter::new(limit, now)))),
+    );
+}
+
+fn set_rate_limits(limiters: &RateLimiters, limits: HashMap<String, RateLimit>) {
+    let now = Instant::now();
+    *limiters.write().unwrap() = limits
+        .into_iter()
+        .map(|(key, limit)| (key, Mutex::new(RateLimiter::new(limit, now))))
+        .collect();
+}
+
+fn remove_rate_limits(limiters: &RateLimiters, keys: Vec<String>) {
+    let mut limiters = limiters.write().unwrap();
+    for key in keys {
+        limiters.remove(&key);
+    }
 }
 
 impl BlockRule {
@@ -122,6 +305,10 @@ pub struct Limiter {
     write_block_list: RwLock<HashSet<String>>,
     read_block_list: RwLock<HashSet<String>>,
     rules: RwLock<HashSet<BlockRule>>,
+    query_limits: QueryLimits,
+    schema_query_limits: HashMap<String, QueryLimits>,
+    schema_rate_limiters: RateLimiters,
+    table_rate_limiters: RateLimiters,
 }
 
 impl Default for Limiter {
@@ -130,17 +317,31 @@ impl Default for Limiter {
             write_block_list: RwLock::new(HashSet::new()),
             read_block_list: RwLock::new(HashSet::new()),
             rules: RwLock::new(HashSet::new()),
+            query_limits: QueryLimits::default(),
+            schema_query_limits: HashMap::new(),
+            schema_rate_limiters: RwLock::new(HashMap::new()),
+            table_rate_limiters: RwLock::new(HashMap::new()),
         }
     }
 }
 
 impl Limiter {
     pub fn new(limit_config: LimiterConfig) -> Self {
-        Self {
+        let limiter = Self {
             write_block_list: RwLock::new(limit_config.write_block_list.into_iter().collect()),
             read_block_list: RwLock::new(limit_config.read_block_list.into_iter().collect()),
             rules: RwLock::new(limit_config.rules.into_iter().collect()),
-        }
+            query_limits: limit_config.query_limits,
+            schema_query_limits: limit_config.schema_query_limits,
+            ..Default::default()
+        };
+        add_rate_limits(
+            &limiter.schema_rate_limiters,
+            limit_config.schema_rate_limits,
+        );
+        add_rate_limits(&limiter.table_rate_limiters, limit_config.table_rate_limits);
+
+        limiter
     }
 
     fn try_limit_by_block_list(&self, plan: &Plan) -> Result<()> {
@@ -270,18 +471,297 @@ impl Limiter {
         let new_rule_set: HashSet<_> = new_rules.into_iter().collect();
         *self.rules.write().unwrap() = new_rule_set;
     }
+
+    /// Try to acquire the quota of the plan from the rate limits of the schemas
+    /// and tables it accesses, and the default schema of the plan is
+    /// `schema`.
+    ///
+    /// Error will throws if any rate limit is exceeded, and the request is
+    /// expected to be retried later.
+    pub fn try_acquire(&self, schema: &str, plan: &Plan) -> Result<()> {
+        match plan {
+            Plan::Query(query) => {
+                let cost = RequestCost {
+                    read_queries: 1,
+                    ..Default::default()
+                };
+                let mut schemas = BTreeMap::new();
+                let mut tables = BTreeMap::new();
+                let _ = query.tables.visit::<_, ()>(|table_ref, _| {
+                    schemas.insert(table_ref.schema.to_string(), cost);
+                    tables.insert(format!("{}.{}", table_ref.schema, table_ref.table), cost);
+                    Ok(())
+                });
+                self.try_acquire_with_metrics(&schemas, &tables, plan.plan_type())
+            }
+            Plan::Insert(insert) => self.try_acquire_inserts(schema, std::slice::from_ref(insert)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Try to acquire the quota of all the insert plans on the `schema` at
+    /// once, so either all or none of them are admitted.
+    ///
+    /// The schema is charged the total cost of the plans, while each table is
+    /// only charged the cost of its own rows.
+    pub fn try_acquire_inserts(&self, schema: &str, inserts: &[InsertPlan]) -> Result<()> {
+        let mut total = RequestCost::default();
+        let mut tables = BTreeMap::new();
+        for insert in inserts {
+            let cost = RequestCost {
+                write_rows: insert.rows.num_rows() as u64,
+                write_bytes: insert.rows.iter().map(|row| row.size() as u64).sum(),
+                ..Default::default()
+            };
+            total = total + cost;
+            let table_cost = tables
+                .entry(format!("{schema}.{}", insert.table.name()))
+                .or_insert_with(RequestCost::default);
+            *table_cost = *table_cost + cost;
+        }
+        let schemas = BTreeMap::from([(schema.to_string(), total)]);
+        self.try_acquire_with_metrics(&schemas, &tables, "insert")
+    }
+
+    /// Check whether the write requests on the `schema` would be throttled,
+    /// without consuming any quota.
+    ///
+    /// It rejects the throttled requests before their side effects like
+    /// creating tables, and the quota is acquired by the insert plans later.
+    /// The size of the encoded requests is taken as the written bytes.
+    pub fn check_write_requests(&self, schema: &str, requests: &[WriteTableRequest]) -> Result<()> {
+        let mut total = RequestCost::default();
+        let mut tables = BTreeMap::new();
+        for request in requests {
+            let cost = RequestCost {
+                write_rows: request
+                    .entries
+                    .iter()
+                    .map(|entry| entry.field_groups.len() as u64)
+                    .sum(),
+                write_bytes: request.encoded_len() as u64,
+                ..Default::default()
+            };
+            total = total + cost;
+            let table_cost = tables
+                .entry(format!("{schema}.{}", request.table))
+                .or_insert_with(RequestCost::default);
+            *table_cost = *table_cost + cost;
+        }
+        let schemas = BTreeMap::from([(schema.to_string(), total)]);
+        let result = self.acquire_at(&schemas, &tables, Instant::now(), false);
+        if result.is_err() {
+            THROTTLED_REQUEST_COUNTER_VEC_GLOBAL
+                .with_label_values(&["insert"])
+                .inc();
+        }
+
+        result
+    }
+
+    fn try_acquire_with_metrics(
+        &self,
+        schemas: &BTreeMap<String, RequestCost>,
+        tables: &BTreeMap<String, RequestCost>,
+        plan_type: &str,
+    ) -> Result<()> {
+        let result = self.try_acquire_at(schemas, tables, Instant::now());
+        if result.is_err() {
+            THROTTLED_REQUEST_COUNTER_VEC_GLOBAL
+                .with_label_values(&[plan_type])
+                .inc();
+        }
+
+        result
+    }
+
+    /// Try to acquire the cost of every schema and table from its rate limits.
+    fn try_acquire_at(
+        &self,
+        schemas: &BTreeMap<String, RequestCost>,
+        tables: &BTreeMap<String, RequestCost>,
+        now: Instant,
+    ) -> Result<()> {
+        self.acquire_at(schemas, tables, now, true)
+    }
+
+    /// Check the rate limits of every schema and table, and consume the cost
+    /// from them if `consume` is true and none of them is exhausted.
+    fn acquire_at(
+        &self,
+        schemas: &BTreeMap<String, RequestCost>,
+        tables: &BTreeMap<String, RequestCost>,
+        now: Instant,
+        consume: bool,
+    ) -> Result<()> {
+        let schema_rate_limiters = self.schema_rate_limiters.read().unwrap();
+        let table_rate_limiters = self.table_rate_limiters.read().unwrap();
+        // The limiters are always locked in the same order, schemas before tables and
+        // both sorted, to avoid deadlock.
+        let mut limiters = Vec::new();
+        for (schema, cost) in schemas {
+            if let Some(limiter) = schema_rate_limiters.get(schema) {
+                limiters.push((schema, *cost, limiter.lock().unwrap()));
+            }
+        }
+        for (table, cost) in tables {
+            if let Some(limiter) = table_rate_limiters.get(table) {
+                limiters.push((table, *cost, limiter.lock().unwrap()));
+            }
+        }
+
+        // Nothing is consumed unless all the rate limits are satisfied.
+        for (target, cost, limiter) in &mut limiters {
+            if let Some(resource) = limiter.find_exhausted(*cost, now) {
+                return Throttled {
+                    target: target.as_str(),
+                    resource,
+                }
+                .fail();
+            }
+        }
+        if !consume {
+            return Ok(());
+        }
+        for (_, cost, limiter) in &mut limiters {
+            limiter.consume(*cost);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_schema_rate_limits(&self) -> HashMap<String, RateLimit> {
+        get_rate_limits(&self.schema_rate_limiters)
+    }
+
+    pub fn get_table_rate_limits(&self) -> HashMap<String, RateLimit> {
+        get_rate_limits(&self.table_rate_limiters)
+    }
+
+    /// Add the rate limits of the schemas, the existing limits of the same
+    /// schemas are replaced.
+    pub fn add_schema_rate_limits(&self, limits: HashMap<String, RateLimit>) {
+        add_rate_limits(&self.schema_rate_limiters, limits)
+    }
+
+    /// Add the rate limits of the tables, the existing limits of the same
+    /// tables are replaced.
+    pub fn add_table_rate_limits(&self, limits: HashMap<String, RateLimit>) {
+        add_rate_limits(&self.table_rate_limiters, limits)
+    }
+
+    pub fn set_schema_rate_limits(&self, limits: HashMap<String, RateLimit>) {
+        set_rate_limits(&self.schema_rate_limiters, limits)
+    }
+
+    pub fn set_table_rate_limits(&self, limits: HashMap<String, RateLimit>) {
+        set_rate_limits(&self.table_rate_limiters, limits)
+    }
+
+    pub fn remove_schema_rate_limits(&self, schemas: Vec<String>) {
+        remove_rate_limits(&self.schema_rate_limiters, schemas)
+    }
+
+    pub fn remove_table_rate_limits(&self, tables: Vec<String>) {
+        remove_rate_limits(&self.table_rate_limiters, tables)
+    }
+
+    /// Limits of the queries on the schema.
+    pub fn query_limits(&self, schema: &str) -> QueryLimits {
+        match self.schema_query_limits.get(schema) {
+            Some(limits) => limits.or(self.query_limits),
+            None => self.query_limits,
+        }
+    }
+}
+
+const QUERY_LIMIT_HINT_START: &str = "/*+";
+const QUERY_LIMIT_HINT_END: &str = "*/";
+
+/// Parse the limits of the query from the hint in the sql, e.g.
+/// `SELECT /*+ MAX_EXECUTION_TIME(10s) MAX_RESULT_ROWS(1000) */ * FROM t`.
+///
+/// Supported hints are `MAX_EXECUTION_TIME`, `MAX_SCANNED_ROWS`,
+/// `MAX_SCANNED_BYTES` and `MAX_RESULT_ROWS`, and other hints are ignored.
+pub fn parse_query_limit_hints(sql: &str) -> Result<QueryLimits> {
+    let hints = match sql.find(QUERY_LIMIT_HINT_START) {
+        Some(start) => {
+            let hints = &sql[start + QUERY_LIMIT_HINT_START.len()..];
+            match hints.find(QUERY_LIMIT_HINT_END) {
+                Some(end) => &hints[..end],
+                None => return Ok(QueryLimits::default()),
+            }
+        }
+        None => return Ok(QueryLimits::default()),
+    };
+
+    parse_query_limits(hints)
+}
+
+/// Parse the limits of the query in the form of the hints, e.g.
+/// `MAX_EXECUTION_TIME(10s) MAX_RESULT_ROWS(1000)`.
+pub fn parse_query_limits(hints: &str) -> Result<QueryLimits> {
+    let mut limits = QueryLimits::default();
+    let mut rest = hints.trim_start();
+    while !rest.is_empty() {
+        let (name, value, remaining) = match rest.find('(').zip(rest.find(')')) {
+            Some((open, close)) if open < close => (
+                rest[..open].trim(),
+                rest[open + 1..close].trim(),
+                &rest[close + 1..],
+            ),
+            _ => {
+                return InvalidQueryLimitHint {
+                    hint: rest,
+                    msg: "hint should be in the form of `NAME(value)`",
+                }
+                .fail()
+            }
+        };
+        rest = remaining.trim_start();
+
+        let invalid_value = |msg: String| Error::InvalidQueryLimitHint {
+            hint: format!("{name}({value})"),
+            msg,
+        };
+        match name.to_ascii_uppercase().as_str() {
+            "MAX_EXECUTION_TIME" => {
+                let v = ReadableDuration::from_str(value).map_err(invalid_value)?;
+                limits.max_execution_time = Some(v);
+            }
+            "MAX_SCANNED_ROWS" => {
+                let v = value
+                    .parse::<u64>()
+                    .map_err(|e| invalid_value(e.to_string()))?;
+                limits.max_scanned_rows = Some(v);
+            }
+            "MAX_SCANNED_BYTES" => {
+                let v = ReadableSize::from_str(value).map_err(invalid_value)?;
+                limits.max_scanned_bytes = Some(v);
+            }
+            "MAX_RESULT_ROWS" => {
+                let v = value
+                    .parse::<u64>()
+                    .map_err(|e| invalid_value(e.to_string()))?;
+                limits.max_result_rows = Some(v);
+            }
+            _ => (),
+        }
+    }
+
+    Ok(limits)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use common_types::request_id::RequestId;
     use query_frontend::{
-        config::DynamicConfig, parser::Parser, plan::Plan, planner::Planner,
-        tests::MockMetaProvider,
+        config::DynamicConfig, parser::Parser, planner::Planner, tests::MockMetaProvider,
     };
 
-    use super::{BlockRule, LimiterConfig};
-    use crate::limiter::Limiter;
+    use super::*;
 
     fn sql_to_plan(meta_provider: &MockMetaProvider, sql: &str) -> Plan {
         let dyn_config = DynamicConfig::default();
@@ -307,6 +787,7 @@ mod tests {
             write_block_list: vec![],
             read_block_list: vec![],
             rules,
+            ..Default::default()
         };
 
         let limiter = Limiter::new(config);
@@ -428,4 +909,258 @@ mod tests {
         limiter.set_block_rules(vec![BlockRule::QueryWithoutPredicate]);
         assert!(limiter.try_limit(&query_plan).is_err());
     }
+
+    #[test]
+    fn test_schema_query_limits() {
+        let config = LimiterConfig {
+            query_limits: QueryLimits {
+                max_execution_time: Some(ReadableDuration::secs(60)),
+                max_result_rows: Some(10000),
+                ..Default::default()
+            },
+            schema_query_limits: [(
+                "analysis".to_string(),
+                QueryLimits {
+                    max_result_rows: Some(100),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let limiter = Limiter::new(config);
+
+        let limits = limiter.query_limits("public");
+        assert_eq!(Some(ReadableDuration::secs(60)), limits.max_execution_time);
+        assert_eq!(Some(10000), limits.max_result_rows);
+
+        let limits = limiter.query_limits("analysis");
+        assert_eq!(Some(ReadableDuration::secs(60)), limits.max_execution_time);
+        assert_eq!(Some(100), limits.max_result_rows);
+    }
+
+    #[test]
+    fn test_parse_query_limit_hints() {
+        let limits = parse_query_limit_hints(
+            "SELECT /*+ MAX_EXECUTION_TIME(10s) max_scanned_rows(1000) MAX_SCANNED_BYTES(1MB) MAX_RESULT_ROWS(10) UNKNOWN(1) */ * FROM t",
+        )
+        .unwrap();
+        assert_eq!(
+            QueryLimits {
+                max_execution_time: Some(ReadableDuration::secs(10)),
+                max_scanned_rows: Some(1000),
+                max_scanned_bytes: Some(ReadableSize::mb(1)),
+                max_result_rows: Some(10),
+            },
+            limits
+        );
+
+        let sqls = [
+            "SELECT * FROM t",
+            "SELECT /* MAX_RESULT_ROWS(10) */ * FROM t",
+            "SELECT /*+ MAX_RESULT_ROWS(10) * FROM t",
+        ];
+        for sql in sqls {
+            assert_eq!(
+                QueryLimits::default(),
+                parse_query_limit_hints(sql).unwrap()
+            );
+        }
+
+        let invalid_sqls = [
+            "SELECT /*+ MAX_RESULT_ROWS(abc) */ * FROM t",
+            "SELECT /*+ MAX_EXECUTION_TIME(10) */ * FROM t",
+            "SELECT /*+ MAX_RESULT_ROWS */ * FROM t",
+        ];
+        for sql in invalid_sqls {
+            assert!(parse_query_limit_hints(sql).is_err(), "sql:{sql}");
+        }
+
+        let limits = parse_query_limits("MAX_RESULT_ROWS(10)").unwrap();
+        assert_eq!(Some(10), limits.max_result_rows);
+        assert_eq!(QueryLimits::default(), parse_query_limits("").unwrap());
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let config = LimiterConfig {
+            schema_rate_limits: [(
+                "public".to_string(),
+                RateLimit {
+                    write_rows_per_second: Some(10),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+            table_rate_limits: [(
+                "public.test_table".to_string(),
+                RateLimit {
+                    read_queries_per_second: Some(2),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let limiter = Limiter::new(config);
+        let try_acquire_at = |cost, now| {
+            let schemas = BTreeMap::from([("public".to_string(), cost)]);
+            let tables = BTreeMap::from([("public.test_table".to_string(), cost)]);
+            limiter.try_acquire_at(&schemas, &tables, now)
+        };
+        let write = RequestCost {
+            write_rows: 8,
+            ..Default::default()
+        };
+        let read = RequestCost {
+            read_queries: 1,
+            ..Default::default()
+        };
+
+        let now = Instant::now();
+        // The request larger than the remaining tokens passes until the bucket is
+        // exhausted.
+        assert!(try_acquire_at(write, now).is_ok());
+        assert!(try_acquire_at(write, now).is_ok());
+        assert!(try_acquire_at(write, now).is_err());
+        // Reads are not limited by the write rate.
+        assert!(try_acquire_at(read, now).is_ok());
+        assert!(try_acquire_at(read, now).is_ok());
+        assert!(matches!(
+            try_acquire_at(read, now),
+            Err(Error::Throttled { target, resource: "read_queries" }) if target == "public.test_table"
+        ));
+
+        // The tokens are refilled as time goes by.
+        let now = now + Duration::from_secs(1);
+        assert!(try_acquire_at(write, now).is_ok());
+        assert!(try_acquire_at(read, now).is_ok());
+
+        limiter.remove_schema_rate_limits(vec!["public".to_string()]);
+        limiter.add_table_rate_limits(
+            [(
+                "public.test_table".to_string(),
+                RateLimit {
+                    write_rows_per_second: Some(1),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+        );
+        assert!(limiter.get_schema_rate_limits().is_empty());
+        assert_eq!(
+            Some(1),
+            limiter.get_table_rate_limits()["public.test_table"].write_rows_per_second
+        );
+        assert!(try_acquire_at(read, now).is_ok());
+        assert!(try_acquire_at(write, now).is_ok());
+        assert!(try_acquire_at(write, now).is_err());
+
+        limiter.set_table_rate_limits(HashMap::new());
+        assert!(try_acquire_at(write, now).is_ok());
+    }
+
+    #[test]
+    fn test_try_acquire_by_plan() {
+        let mock = MockMetaProvider::default();
+        let limiter = Limiter::default();
+        limiter.set_table_rate_limits(
+            [(
+                "public.test_table".to_string(),
+                RateLimit {
+                    read_queries_per_second: Some(1),
+                    write_bytes_per_second: Some(ReadableSize(1)),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+        );
+
+        let query_plan = sql_to_plan(&mock, "select * from test_table");
+        assert!(limiter.try_acquire("public", &query_plan).is_ok());
+        assert!(limiter.try_acquire("public", &query_plan).is_err());
+
+        let insert = "INSERT INTO test_table(key1, key2, field1, field2) VALUES('tagk', 1638428434000, 100, 'hello3')";
+        let insert_plan = sql_to_plan(&mock, insert);
+        assert!(limiter.try_acquire("public", &insert_plan).is_ok());
+        assert!(limiter.try_acquire("public", &insert_plan).is_err());
+        // The table of other schemas is not limited.
+        assert!(limiter.try_acquire("other", &insert_plan).is_ok());
+    }
+
+    #[test]
+    fn test_try_acquire_inserts() {
+        let mock = MockMetaProvider::default();
+        let limiter = Limiter::default();
+        let rate_limit = |rows| RateLimit {
+            write_rows_per_second: Some(rows),
+            ..Default::default()
+        };
+        limiter.set_schema_rate_limits([("public".to_string(), rate_limit(3))].into());
+        limiter.set_table_rate_limits([("public.test_table".to_string(), rate_limit(1))].into());
+
+        let insert = "INSERT INTO test_table(key1, key2, field1, field2) VALUES('tagk', 1638428434000, 100, 'hello3')";
+        let insert_plan = || match sql_to_plan(&mock, insert) {
+            Plan::Insert(plan) => plan,
+            plan => panic!("Unexpected plan:{plan:?}"),
+        };
+        let inserts = vec![insert_plan(), insert_plan()];
+        assert!(limiter.try_acquire_inserts("public", &inserts).is_ok());
+        // The schema is not charged if the table is throttled.
+        assert!(limiter.try_acquire_inserts("public", &inserts).is_err());
+
+        limiter.set_table_rate_limits(HashMap::new());
+        assert!(limiter.try_acquire_inserts("public", &inserts).is_ok());
+        assert!(limiter
+            .try_acquire_inserts("public", &inserts[..1])
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_write_requests() {
+        use horaedbproto::storage::{FieldGroup, WriteSeriesEntry};
+
+        let limiter = Limiter::default();
+        limiter.set_table_rate_limits(
+            [(
+                "public.test_table".to_string(),
+                RateLimit {
+                    write_rows_per_second: Some(2),
+                    ..Default::default()
+                },
+            )]
+            .into(),
+        );
+        let requests = vec![WriteTableRequest {
+            table: "test_table".to_string(),
+            entries: vec![WriteSeriesEntry {
+                field_groups: vec![FieldGroup::default(), FieldGroup::default()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+
+        // Checking the requests consumes no quota.
+        assert!(limiter.check_write_requests("public", &requests).is_ok());
+        assert!(limiter.check_write_requests("public", &requests).is_ok());
+
+        // Overdraw the bucket so it stays exhausted for a while.
+        let write = RequestCost {
+            write_rows: 3,
+            ..Default::default()
+        };
+        let tables = BTreeMap::from([("public.test_table".to_string(), write)]);
+        assert!(limiter
+            .try_acquire_at(&BTreeMap::new(), &tables, Instant::now())
+            .is_ok());
+        assert!(matches!(
+            limiter.check_write_requests("public", &requests),
+            Err(Error::Throttled { target, resource: "write_rows" }) if target == "public.test_table"
+        ));
+    }
 }
@@ -70,6 +70,12 @@ lazy_static! {
         &["type"]
     )
     .unwrap();
+    pub static ref THROTTLED_REQUEST_COUNTER_VEC_GLOBAL: IntCounterVec = register_int_counter_vec!(
+        "throttled_request_counter",
+        "Throttled request counter",
+        &["type"]
+    )
+    .unwrap();
 }
 
 lazy_static! {
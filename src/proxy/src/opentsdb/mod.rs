@@ -15,20 +15,34 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! This module implements [put][1] for OpenTSDB
+//! This module implements [put][1], [query][2] and [suggest][3] for OpenTSDB
 //! [1]: http://opentsdb.net/docs/build/html/api_http/put.html
+//! [2]: http://opentsdb.net/docs/build/html/api_http/query/index.html
+//! [3]: http://opentsdb.net/docs/build/html/api_http/suggest.html
 
+use std::collections::BTreeSet;
+
+use generic_error::BoxError;
 use horaedbproto::storage::{
     RequestContext as GrpcRequestContext, WriteRequest as GrpcWriteRequest,
 };
 use http::StatusCode;
-use logger::debug;
+use interpreters::interpreter::Output;
+use logger::{debug, error};
+use snafu::ResultExt;
 
 use crate::{
     context::RequestContext,
-    error::{ErrNoCause, Result},
+    error::{ErrNoCause, ErrWithCause, Result},
+    http::sql::convert_sql_response_to_output,
     metrics::HTTP_HANDLER_COUNTER_VEC,
-    opentsdb::types::{convert_put_request, PutRequest, PutResponse},
+    opentsdb::types::{
+        build_describe_sql, build_tag_values_sql, convert_describe_output, convert_put_request,
+        convert_query_results_to_seconds, convert_query_time_range, convert_tag_values_output,
+        PutRequest, PutResponse, QueryRequest, QueryResponse, SubQueryPlan, SuggestParams,
+        SuggestResponse,
+    },
+    read::SqlResponse,
     Context, Proxy,
 };
 
@@ -93,4 +107,116 @@ impl Proxy {
             }
         }
     }
+
+    pub async fn handle_opentsdb_query(
+        &self,
+        ctx: RequestContext,
+        req: QueryRequest,
+    ) -> Result<QueryResponse> {
+        let (start, end) = convert_query_time_range(&req)?;
+
+        let mut results = Vec::new();
+        for query in &req.queries {
+            let describe_output = self
+                .execute_opentsdb_sql(&ctx, &build_describe_sql(&query.metric))
+                .await?;
+            let columns = convert_describe_output(&query.metric, describe_output)?;
+            let plan = SubQueryPlan::try_new(query, &columns, start, end)?;
+
+            debug!(
+                "OpenTSDB query begin, catalog:{}, schema:{}, sql:{}",
+                ctx.catalog, ctx.schema, plan.sql
+            );
+            let output = self.execute_opentsdb_sql(&ctx, &plan.sql).await?;
+            results.extend(plan.convert_output(output)?);
+        }
+        convert_query_results_to_seconds(&mut results);
+
+        Ok(results)
+    }
+
+    pub async fn handle_opentsdb_suggest(
+        &self,
+        ctx: RequestContext,
+        params: SuggestParams,
+    ) -> Result<SuggestResponse> {
+        let catalog = self.get_catalog(&ctx.catalog)?;
+        let schema = self.get_schema(&catalog, &ctx.schema)?;
+        let tables = schema
+            .all_tables()
+            .box_err()
+            .with_context(|| ErrWithCause {
+                code: StatusCode::INTERNAL_SERVER_ERROR,
+                msg: format!("Failed to list tables, schema:{}", ctx.schema),
+            })?;
+
+        let mut suggestions = BTreeSet::new();
+        match params.suggest_type.as_str() {
+            "metrics" => {
+                for table in &tables {
+                    if table.name().starts_with(&params.q) {
+                        suggestions.insert(table.name().to_string());
+                    }
+                }
+            }
+            "tagk" => {
+                for table in &tables {
+                    for column in table.schema().columns() {
+                        if column.is_tag && column.name.starts_with(&params.q) {
+                            suggestions.insert(column.name.clone());
+                        }
+                    }
+                }
+            }
+            "tagv" => {
+                for table in &tables {
+                    for column in table.schema().columns() {
+                        if !column.is_tag || suggestions.len() >= params.max {
+                            continue;
+                        }
+                        let sql = build_tag_values_sql(
+                            table.name(),
+                            &column.name,
+                            &params.q,
+                            params.max - suggestions.len(),
+                        );
+                        let output = self.execute_opentsdb_sql(&ctx, &sql).await?;
+                        suggestions.extend(convert_tag_values_output(&column.name, output)?);
+                    }
+                }
+            }
+            other => {
+                return ErrNoCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!("Invalid suggest type:{other}"),
+                }
+                .fail()
+            }
+        }
+
+        Ok(suggestions.into_iter().take(params.max).collect())
+    }
+
+    async fn execute_opentsdb_sql(&self, ctx: &RequestContext, sql: &str) -> Result<Output> {
+        let schema = &ctx.schema;
+        let proxy_context = Context::new(ctx.timeout, None);
+
+        match self
+            .handle_sql(
+                &proxy_context,
+                schema,
+                sql,
+                self.sub_table_access_perm.enable_http,
+                false,
+            )
+            .await
+        {
+            Err(e) => {
+                error!("OpenTSDB query failed, schema:{schema}, sql:{sql}, err:{e}");
+                Err(e)
+            }
+            Ok(SqlResponse::Forwarded(resp)) => convert_sql_response_to_output(resp),
+            Ok(SqlResponse::Local(output)) => Ok(output),
+        }
+    }
 }
@@ -16,22 +16,24 @@
 // under the License.
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Debug,
 };
 
 use bytes::Bytes;
+use common_types::time::Timestamp;
 use generic_error::BoxError;
 use horaedbproto::storage::{
     value, Field, FieldGroup, Tag, Value as ProtoValue, WriteSeriesEntry, WriteTableRequest,
 };
 use http::StatusCode;
-use serde::Deserialize;
+use interpreters::interpreter::Output;
+use serde::{Deserialize, Serialize};
 use serde_json::from_slice;
-use snafu::{OptionExt, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use time_ext::try_to_millis;
 
-use crate::error::{ErrNoCause, ErrWithCause, Result};
+use crate::error::{ErrNoCause, ErrWithCause, InternalNoCause, Result};
 
 const OPENTSDB_DEFAULT_FIELD: &str = "value";
 
@@ -213,3 +215,1086 @@ pub(crate) fn validate(points: &[Point]) -> Result<()> {
 
     Ok(())
 }
+
+/// Query string parameters for query api
+///
+/// It's derived from query string parameters of query described in
+/// doc of OpenTSDB 2.4:
+///     http://opentsdb.net/docs/build/html/api_http/query/index.html#requests
+///
+/// NOTE:
+///     - only one sub query(`m`) is supported now.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryParams {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub m: Option<String>,
+}
+
+/// Query request of OpenTSDB 2.4, which can be either json body of the POST
+/// method or built from [QueryParams] of the GET method.
+#[derive(Debug, Deserialize)]
+pub struct QueryRequest {
+    pub start: TimeValue,
+    #[serde(default)]
+    pub end: Option<TimeValue>,
+    pub queries: Vec<SubQuery>,
+}
+
+impl QueryRequest {
+    pub fn try_new(body: Bytes, params: QueryParams) -> Result<Self> {
+        if !body.is_empty() {
+            return from_slice::<QueryRequest>(&body)
+                .box_err()
+                .with_context(|| ErrWithCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: "Json parse error".to_string(),
+                });
+        }
+
+        let start = params.start.with_context(|| ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: "Missing start time",
+        })?;
+        let m = params.m.with_context(|| ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: "Missing sub query",
+        })?;
+
+        Ok(QueryRequest {
+            start: TimeValue::Str(start),
+            end: params.end.map(TimeValue::Str),
+            queries: vec![SubQuery::parse_metric_query(&m)?],
+        })
+    }
+}
+
+/// Absolute or relative time in OpenTSDB query.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum TimeValue {
+    Int(i64),
+    Str(String),
+}
+
+impl TimeValue {
+    /// Convert the time to milliseconds, the relative time is based on `now`.
+    ///
+    /// Supported formats:
+    ///     - unix timestamp in seconds or milliseconds, e.g. `1356998400`.
+    ///     - relative time, e.g. `1h-ago`.
+    fn to_millis(&self, now: i64) -> Result<i64> {
+        match self {
+            TimeValue::Int(v) => normalize_timestamp(*v),
+            TimeValue::Str(v) => {
+                if let Some(relative) = v.strip_suffix("-ago") {
+                    let duration = parse_duration_millis(relative)?;
+                    return now.checked_sub(duration).with_context(|| ErrNoCause {
+                        code: StatusCode::BAD_REQUEST,
+                        msg: format!("Time out of range:{v}"),
+                    });
+                }
+                let ts = v.parse::<i64>().box_err().with_context(|| ErrWithCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!("Invalid time:{v}"),
+                })?;
+                normalize_timestamp(ts)
+            }
+        }
+    }
+}
+
+/// Timestamp in OpenTSDB can be seconds or milliseconds, and the one with more
+/// than 10 digits is considered as milliseconds.
+fn normalize_timestamp(ts: i64) -> Result<i64> {
+    const MAX_SECONDS: i64 = 9_999_999_999;
+    if ts > MAX_SECONDS {
+        return Ok(ts);
+    }
+
+    ts.checked_mul(1000).with_context(|| ErrNoCause {
+        code: StatusCode::BAD_REQUEST,
+        msg: format!("Timestamp out of range:{ts}"),
+    })
+}
+
+/// Parse the duration like `1h`, `30s` into milliseconds.
+fn parse_duration_millis(duration: &str) -> Result<i64> {
+    let split_idx = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .with_context(|| ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: format!("Invalid duration:{duration}"),
+        })?;
+    let (number, unit) = duration.split_at(split_idx);
+    let number = number
+        .parse::<i64>()
+        .box_err()
+        .with_context(|| ErrWithCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: format!("Invalid duration:{duration}"),
+        })?;
+    let unit_millis = match unit {
+        "ms" => 1,
+        "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 3600 * 1000,
+        "d" => 24 * 3600 * 1000,
+        "w" => 7 * 24 * 3600 * 1000,
+        "n" => 30 * 24 * 3600 * 1000,
+        "y" => 365 * 24 * 3600 * 1000,
+        _ => {
+            return ErrNoCause {
+                code: StatusCode::BAD_REQUEST,
+                msg: format!("Invalid duration unit:{duration}"),
+            }
+            .fail()
+        }
+    };
+
+    number.checked_mul(unit_millis).with_context(|| ErrNoCause {
+        code: StatusCode::BAD_REQUEST,
+        msg: format!("Duration out of range:{duration}"),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubQuery {
+    pub aggregator: String,
+    pub metric: String,
+    #[serde(default)]
+    pub rate: bool,
+    #[serde(default)]
+    pub downsample: Option<String>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    #[serde(default)]
+    pub filters: Vec<Filter>,
+}
+
+impl SubQuery {
+    /// Parse the sub query in the format of
+    /// `<aggregator>:[<down_sampler>:][rate:
+    /// ]<metric_name>[{<tag_name1>=<grouping filter>}]`
+    fn parse_metric_query(m: &str) -> Result<Self> {
+        let invalid_query = || ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: format!("Invalid sub query:{m}"),
+        };
+
+        // Tags are always at the end, and the `rate` may also have options in
+        // braces, e.g. `rate{counter}`.
+        let (prefix, tags) = match m.strip_suffix('}') {
+            Some(rest) => {
+                let idx = rest.rfind('{').with_context(invalid_query)?;
+                (&rest[..idx], &rest[idx + 1..])
+            }
+            None => (m, ""),
+        };
+
+        let mut parts: Vec<_> = prefix.split(':').collect();
+        ensure!(parts.len() >= 2, invalid_query());
+        let aggregator = parts.remove(0).to_string();
+        let metric = parts.pop().unwrap().to_string();
+        let mut rate = false;
+        let mut downsample = None;
+        for part in parts {
+            if part.starts_with("rate") {
+                rate = true;
+            } else {
+                downsample = Some(part.to_string());
+            }
+        }
+
+        let mut tag_map = HashMap::new();
+        for tag in tags.split(',').filter(|v| !v.is_empty()) {
+            let (k, v) = tag.split_once('=').with_context(invalid_query)?;
+            tag_map.insert(k.to_string(), v.to_string());
+        }
+
+        Ok(SubQuery {
+            aggregator,
+            metric,
+            rate,
+            downsample,
+            tags: tag_map,
+            filters: Vec::new(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+    #[serde(rename = "type")]
+    pub filter_type: String,
+    pub tagk: String,
+    pub filter: String,
+    #[serde(default)]
+    pub group_by: bool,
+}
+
+/// One series in the query response.
+///
+/// The basic example:
+/// ```json
+/// [{"metric":"sys.cpu.nice","tags":{"host":"web01"},"aggregateTags":[],
+///   "dps":{"1346846400":18,"1346846460":9}}]
+/// ```
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    pub metric: String,
+    pub tags: BTreeMap<String, String>,
+    pub aggregate_tags: Vec<String>,
+    pub dps: BTreeMap<i64, f64>,
+}
+
+pub type QueryResponse = Vec<QueryResult>;
+
+/// Query string parameters for suggest api
+///
+/// It's derived from query string parameters of suggest described in
+/// doc of OpenTSDB 2.4:
+///     http://opentsdb.net/docs/build/html/api_http/suggest.html
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct SuggestParams {
+    #[serde(rename = "type")]
+    pub suggest_type: String,
+    pub q: String,
+    pub max: usize,
+}
+
+impl Default for SuggestParams {
+    fn default() -> Self {
+        Self {
+            suggest_type: String::new(),
+            q: String::new(),
+            max: 25,
+        }
+    }
+}
+
+pub type SuggestResponse = Vec<String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Aggregator {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Count,
+    Dev,
+    None,
+}
+
+impl Aggregator {
+    fn try_from_str(name: &str) -> Result<Self> {
+        let aggregator = match name {
+            "sum" | "zimsum" => Aggregator::Sum,
+            "avg" => Aggregator::Avg,
+            "min" | "mimmin" => Aggregator::Min,
+            "max" | "mimmax" => Aggregator::Max,
+            "count" => Aggregator::Count,
+            "dev" => Aggregator::Dev,
+            "none" => Aggregator::None,
+            _ => {
+                return ErrNoCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!("Unsupported aggregator:{name}"),
+                }
+                .fail()
+            }
+        };
+
+        Ok(aggregator)
+    }
+
+    fn sql_function(&self) -> Option<&'static str> {
+        match self {
+            Aggregator::Sum => Some("SUM"),
+            Aggregator::Avg => Some("AVG"),
+            Aggregator::Min => Some("MIN"),
+            Aggregator::Max => Some("MAX"),
+            Aggregator::Count => Some("COUNT"),
+            Aggregator::Dev => Some("STDDEV_POP"),
+            Aggregator::None => None,
+        }
+    }
+
+    /// Aggregate the values of multiple series at the same timestamp.
+    fn aggregate(&self, values: &[f64]) -> f64 {
+        let n = values.len() as f64;
+        match self {
+            Aggregator::Sum => values.iter().sum(),
+            Aggregator::Avg => values.iter().sum::<f64>() / n,
+            Aggregator::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregator::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Aggregator::Count => n,
+            Aggregator::Dev => {
+                let mean = values.iter().sum::<f64>() / n;
+                (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt()
+            }
+            // Values of different series are never aggregated together.
+            Aggregator::None => values[0],
+        }
+    }
+}
+
+/// Downsample of the sub query, `None` interval means downsampling all the
+/// points in the time range into one point.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Downsample {
+    interval: Option<i64>,
+    aggregator: Aggregator,
+}
+
+impl Downsample {
+    /// Parse the downsample like `1m-avg`, `0all-sum`, the fill policy is
+    /// ignored now.
+    fn try_from_str(downsample: &str) -> Result<Self> {
+        let mut parts = downsample.split('-');
+        let (interval, aggregator) = match (parts.next(), parts.next()) {
+            (Some(interval), Some(aggregator)) => (interval, aggregator),
+            _ => {
+                return ErrNoCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!("Invalid downsample:{downsample}"),
+                }
+                .fail()
+            }
+        };
+        let aggregator = Aggregator::try_from_str(aggregator)?;
+        ensure!(
+            aggregator != Aggregator::None,
+            ErrNoCause {
+                code: StatusCode::BAD_REQUEST,
+                msg: format!("Aggregator of downsample must not be none:{downsample}"),
+            }
+        );
+
+        let interval = if interval.ends_with("all") {
+            None
+        } else {
+            Some(parse_duration_millis(interval)?)
+        };
+
+        Ok(Self {
+            interval,
+            aggregator,
+        })
+    }
+
+    /// Convert the interval to the period of `time_bucket`, which only
+    /// accepts the number less than [u16::MAX].
+    fn time_bucket_period(interval: i64) -> Result<String> {
+        const MAX_NUMBER: i64 = u16::MAX as i64;
+
+        let period = if interval % (3600 * 1000) == 0 && interval / (3600 * 1000) <= MAX_NUMBER {
+            format!("PT{}H", interval / (3600 * 1000))
+        } else if interval % (60 * 1000) == 0 && interval / (60 * 1000) <= MAX_NUMBER {
+            format!("PT{}M", interval / (60 * 1000))
+        } else if interval % 1000 == 0 && interval > 0 && interval / 1000 <= MAX_NUMBER {
+            format!("PT{}S", interval / 1000)
+        } else {
+            return ErrNoCause {
+                code: StatusCode::BAD_REQUEST,
+                msg: format!("Unsupported downsample interval:{interval}ms"),
+            }
+            .fail();
+        };
+
+        Ok(period)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TagFilterKind {
+    LiteralOr {
+        values: Vec<String>,
+        case_insensitive: bool,
+        negate: bool,
+    },
+    Wildcard {
+        pattern: String,
+        case_insensitive: bool,
+    },
+    Regexp {
+        pattern: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TagFilter {
+    tagk: String,
+    kind: TagFilterKind,
+    group_by: bool,
+}
+
+impl TagFilter {
+    /// Build filter from the `tags` of sub query, which are always grouped by.
+    fn from_tag(tagk: String, value: &str) -> Self {
+        let kind = if value.contains('*') {
+            TagFilterKind::Wildcard {
+                pattern: value.to_string(),
+                case_insensitive: false,
+            }
+        } else {
+            TagFilterKind::LiteralOr {
+                values: value.split('|').map(|v| v.to_string()).collect(),
+                case_insensitive: false,
+                negate: false,
+            }
+        };
+
+        Self {
+            tagk,
+            kind,
+            group_by: true,
+        }
+    }
+
+    fn try_from_filter(filter: &Filter) -> Result<Self> {
+        let split_values = || filter.filter.split('|').map(|v| v.to_string()).collect();
+        let kind = match filter.filter_type.as_str() {
+            "literal_or" => TagFilterKind::LiteralOr {
+                values: split_values(),
+                case_insensitive: false,
+                negate: false,
+            },
+            "iliteral_or" => TagFilterKind::LiteralOr {
+                values: split_values(),
+                case_insensitive: true,
+                negate: false,
+            },
+            "not_literal_or" => TagFilterKind::LiteralOr {
+                values: split_values(),
+                case_insensitive: false,
+                negate: true,
+            },
+            "not_iliteral_or" => TagFilterKind::LiteralOr {
+                values: split_values(),
+                case_insensitive: true,
+                negate: true,
+            },
+            "wildcard" => TagFilterKind::Wildcard {
+                pattern: filter.filter.clone(),
+                case_insensitive: false,
+            },
+            "iwildcard" => TagFilterKind::Wildcard {
+                pattern: filter.filter.clone(),
+                case_insensitive: true,
+            },
+            "regexp" => TagFilterKind::Regexp {
+                pattern: filter.filter.clone(),
+            },
+            other => {
+                return ErrNoCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!("Unsupported filter type:{other}"),
+                }
+                .fail()
+            }
+        };
+
+        Ok(Self {
+            tagk: filter.tagk.clone(),
+            kind,
+            group_by: filter.group_by,
+        })
+    }
+
+    /// The single literal value which will be returned in the tags of result.
+    fn literal_value(&self) -> Option<&str> {
+        match &self.kind {
+            TagFilterKind::LiteralOr {
+                values,
+                case_insensitive: false,
+                negate: false,
+            } if values.len() == 1 => Some(&values[0]),
+            _ => None,
+        }
+    }
+
+    fn to_sql(&self) -> String {
+        let column = quote_ident(&self.tagk);
+        match &self.kind {
+            TagFilterKind::LiteralOr {
+                values,
+                case_insensitive,
+                negate,
+            } => {
+                let (column, values): (_, Vec<_>) = if *case_insensitive {
+                    (
+                        format!("lower({column})"),
+                        values
+                            .iter()
+                            .map(|v| quote_literal(&v.to_lowercase()))
+                            .collect(),
+                    )
+                } else {
+                    (column, values.iter().map(|v| quote_literal(v)).collect())
+                };
+                let op = if *negate { "NOT IN" } else { "IN" };
+                format!("{column} {op} ({})", values.join(", "))
+            }
+            TagFilterKind::Wildcard {
+                pattern,
+                case_insensitive,
+            } => {
+                if pattern == "*" {
+                    return format!("{column} IS NOT NULL");
+                }
+                let pattern = pattern
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+                    .replace('*', "%");
+                if *case_insensitive {
+                    format!(
+                        "lower({column}) LIKE {}",
+                        quote_literal(&pattern.to_lowercase())
+                    )
+                } else {
+                    format!("{column} LIKE {}", quote_literal(&pattern))
+                }
+            }
+            TagFilterKind::Regexp { pattern } => {
+                format!("{column} ~ {}", quote_literal(pattern))
+            }
+        }
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("`{}`", ident.replace('`', "``"))
+}
+
+/// The backslash is an escape character in the string literal of the mysql
+/// dialect used by the sql parser, so it's escaped as well.
+fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+/// Tag and timestamp columns of the queried table.
+#[derive(Debug)]
+pub(crate) struct TableColumns {
+    pub tags: Vec<String>,
+    pub timestamp: String,
+}
+
+/// Build the sql for `DESCRIBE`, whose output is used to build
+/// [TableColumns].
+pub(crate) fn build_describe_sql(metric: &str) -> String {
+    format!("DESCRIBE TABLE {}", quote_ident(metric))
+}
+
+pub(crate) fn convert_describe_output(metric: &str, output: Output) -> Result<TableColumns> {
+    let records = match output {
+        Output::Records(records) => records,
+        Output::AffectedRows(_) => {
+            return InternalNoCause {
+                msg: "output of describe should not be affected rows",
+            }
+            .fail()
+        }
+    };
+
+    let mut tags = Vec::new();
+    let mut timestamp = None;
+    for record in records {
+        let schema = record.schema();
+        let column_index = |name: &str| {
+            schema.index_of(name).with_context(|| InternalNoCause {
+                msg: format!("column {name} not found in output of describe"),
+            })
+        };
+        let name_idx = column_index("name")?;
+        let type_idx = column_index("type")?;
+        let is_primary_idx = column_index("is_primary")?;
+        let is_tag_idx = column_index("is_tag")?;
+
+        for row_idx in 0..record.num_rows() {
+            let name = record.column(name_idx).datum(row_idx);
+            let name = name.as_str().unwrap_or_default().to_string();
+            let is_tag = record.column(is_tag_idx).datum(row_idx).as_u64() == Some(1);
+            let is_primary = record.column(is_primary_idx).datum(row_idx).as_u64() == Some(1);
+            let data_type = record.column(type_idx).datum(row_idx);
+            if is_tag {
+                tags.push(name);
+            } else if is_primary && data_type.as_str() == Some("timestamp") {
+                timestamp = Some(name);
+            }
+        }
+    }
+
+    let timestamp = timestamp.with_context(|| ErrNoCause {
+        code: StatusCode::BAD_REQUEST,
+        msg: format!("Timestamp column not found, metric:{metric}"),
+    })?;
+
+    Ok(TableColumns { tags, timestamp })
+}
+
+/// Plan of one sub query.
+///
+/// Filtering and downsampling of every series are done by the sql, and then
+/// series are grouped and aggregated by the group by tags.
+#[derive(Debug)]
+pub(crate) struct SubQueryPlan {
+    pub metric: String,
+    pub sql: String,
+    aggregator: Aggregator,
+    rate: bool,
+    start: i64,
+    timestamp: String,
+    downsample_all: bool,
+    group_by_tags: Vec<String>,
+    aggregate_tags: Vec<String>,
+    literal_tags: BTreeMap<String, String>,
+}
+
+impl SubQueryPlan {
+    pub fn try_new(query: &SubQuery, columns: &TableColumns, start: i64, end: i64) -> Result<Self> {
+        let aggregator = Aggregator::try_from_str(&query.aggregator)?;
+        let downsample = query
+            .downsample
+            .as_deref()
+            .map(Downsample::try_from_str)
+            .transpose()?;
+
+        let mut filters = Vec::with_capacity(query.tags.len() + query.filters.len());
+        for (tagk, value) in &query.tags {
+            filters.push(TagFilter::from_tag(tagk.clone(), value));
+        }
+        for filter in &query.filters {
+            filters.push(TagFilter::try_from_filter(filter)?);
+        }
+        for filter in &filters {
+            ensure!(
+                columns.tags.contains(&filter.tagk),
+                ErrNoCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!(
+                        "Tag not found, metric:{}, tag:{}",
+                        query.metric, filter.tagk
+                    ),
+                }
+            );
+        }
+
+        let (group_by_tags, aggregate_tags) = if aggregator == Aggregator::None {
+            (columns.tags.clone(), Vec::new())
+        } else {
+            columns
+                .tags
+                .iter()
+                .cloned()
+                .partition(|tag| filters.iter().any(|f| f.group_by && &f.tagk == tag))
+        };
+        let literal_tags = filters
+            .iter()
+            .filter_map(|f| f.literal_value().map(|v| (f.tagk.clone(), v.to_string())))
+            .collect();
+
+        // Build the sql, and every series is identified by all the tags.
+        let timestamp = quote_ident(&columns.timestamp);
+        let tags: Vec<_> = columns.tags.iter().map(|t| quote_ident(t)).collect();
+        let mut conditions = vec![
+            format!("{timestamp} >= {start}"),
+            format!("{timestamp} <= {end}"),
+        ];
+        conditions.extend(filters.iter().map(|f| f.to_sql()));
+        let where_clause = conditions.join(" AND ");
+        let value = quote_ident(OPENTSDB_DEFAULT_FIELD);
+        let table = quote_ident(&query.metric);
+
+        let sql = match &downsample {
+            Some(Downsample {
+                interval,
+                aggregator: downsample_aggregator,
+            }) => {
+                let agg = downsample_aggregator.sql_function().unwrap();
+                let mut group_by = tags.clone();
+                let time_expr = match interval {
+                    Some(interval) => {
+                        let period = Downsample::time_bucket_period(*interval)?;
+                        let time_expr = format!("time_bucket({timestamp}, '{period}')");
+                        group_by.push(time_expr.clone());
+                        time_expr
+                    }
+                    None => format!("MIN({timestamp})"),
+                };
+                let mut select_list = tags.clone();
+                select_list.push(format!("{time_expr} AS {timestamp}"));
+                select_list.push(format!("{agg}({value}) AS {value}"));
+                let group_by = if group_by.is_empty() {
+                    String::new()
+                } else {
+                    format!(" GROUP BY {}", group_by.join(", "))
+                };
+                format!(
+                    "SELECT {} FROM {table} WHERE {where_clause}{group_by}",
+                    select_list.join(", ")
+                )
+            }
+            None => {
+                let mut select_list = tags.clone();
+                select_list.push(timestamp.clone());
+                select_list.push(value.clone());
+                format!(
+                    "SELECT {} FROM {table} WHERE {where_clause}",
+                    select_list.join(", ")
+                )
+            }
+        };
+
+        Ok(Self {
+            metric: query.metric.clone(),
+            sql,
+            aggregator,
+            rate: query.rate,
+            start,
+            timestamp: columns.timestamp.clone(),
+            downsample_all: matches!(downsample, Some(Downsample { interval: None, .. })),
+            group_by_tags,
+            aggregate_tags,
+            literal_tags,
+        })
+    }
+
+    /// Group the series by tags and aggregate them at every timestamp.
+    pub fn convert_output(&self, output: Output) -> Result<Vec<QueryResult>> {
+        let records = match output {
+            Output::Records(records) => records,
+            Output::AffectedRows(_) => {
+                return InternalNoCause {
+                    msg: "output in opentsdb query should not be affected rows",
+                }
+                .fail()
+            }
+        };
+
+        // group tag values -> timestamp -> values of series.
+        let mut groups: BTreeMap<Vec<String>, BTreeMap<i64, Vec<f64>>> = BTreeMap::new();
+        for record in records {
+            let schema = record.schema();
+            let column_index = |name: &str| {
+                schema.index_of(name).with_context(|| InternalNoCause {
+                    msg: format!("column {name} not found in output of opentsdb query"),
+                })
+            };
+            let tag_indexes = self
+                .group_by_tags
+                .iter()
+                .map(|tag| column_index(tag))
+                .collect::<Result<Vec<_>>>()?;
+            let timestamp_idx = column_index(&self.timestamp)?;
+            let value_idx = column_index(OPENTSDB_DEFAULT_FIELD)?;
+
+            for row_idx in 0..record.num_rows() {
+                let value = match record.column(value_idx).datum(row_idx).as_f64() {
+                    Some(v) => v,
+                    None => continue,
+                };
+                let timestamp = if self.downsample_all {
+                    self.start
+                } else {
+                    match record.column(timestamp_idx).datum(row_idx).as_timestamp() {
+                        Some(v) => v.as_i64(),
+                        None => continue,
+                    }
+                };
+                let group_key = tag_indexes
+                    .iter()
+                    .map(|idx| {
+                        record
+                            .column(*idx)
+                            .datum(row_idx)
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string()
+                    })
+                    .collect();
+
+                groups
+                    .entry(group_key)
+                    .or_default()
+                    .entry(timestamp)
+                    .or_default()
+                    .push(value);
+            }
+        }
+
+        let results = groups
+            .into_iter()
+            .map(|(group_key, points)| {
+                let mut tags = self.literal_tags.clone();
+                for (tag, value) in self.group_by_tags.iter().zip(group_key) {
+                    if !value.is_empty() {
+                        tags.insert(tag.clone(), value);
+                    }
+                }
+                let dps = points
+                    .into_iter()
+                    .map(|(ts, values)| (ts, self.aggregator.aggregate(&values)))
+                    .collect();
+                let dps = if self.rate { to_rate(dps) } else { dps };
+
+                QueryResult {
+                    metric: self.metric.clone(),
+                    tags,
+                    aggregate_tags: self.aggregate_tags.clone(),
+                    dps,
+                }
+            })
+            .collect();
+
+        Ok(results)
+    }
+}
+
+/// Convert the points to the rate of change per second.
+fn to_rate(dps: BTreeMap<i64, f64>) -> BTreeMap<i64, f64> {
+    dps.iter()
+        .zip(dps.iter().skip(1))
+        .map(|((prev_ts, prev_value), (ts, value))| {
+            let interval_secs = (ts - prev_ts) as f64 / 1000.0;
+            (*ts, (value - prev_value) / interval_secs)
+        })
+        .collect()
+}
+
+/// Convert the timestamps of the query response to seconds, which is the
+/// default resolution of OpenTSDB.
+pub(crate) fn convert_query_results_to_seconds(results: &mut [QueryResult]) {
+    for result in results {
+        let dps = std::mem::take(&mut result.dps);
+        result.dps = dps.into_iter().map(|(ts, v)| (ts / 1000, v)).collect();
+    }
+}
+
+pub(crate) fn convert_query_time_range(req: &QueryRequest) -> Result<(i64, i64)> {
+    let now = Timestamp::now().as_i64();
+    let start = req.start.to_millis(now)?;
+    let end = match &req.end {
+        Some(end) => end.to_millis(now)?,
+        None => now,
+    };
+    ensure!(
+        start <= end,
+        ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: format!("Start time must not be after end time, start:{start}, end:{end}"),
+        }
+    );
+
+    Ok((start, end))
+}
+
+/// Build the sql to find the distinct values of the tag starting with
+/// `prefix`.
+pub(crate) fn build_tag_values_sql(table: &str, tagk: &str, prefix: &str, limit: usize) -> String {
+    let column = quote_ident(tagk);
+    let pattern = prefix.replace('%', "\\%").replace('_', "\\_") + "%";
+    format!(
+        "SELECT DISTINCT {column} FROM {} WHERE {column} LIKE {} LIMIT {limit}",
+        quote_ident(table),
+        quote_literal(&pattern)
+    )
+}
+
+pub(crate) fn convert_tag_values_output(tagk: &str, output: Output) -> Result<Vec<String>> {
+    let records = match output {
+        Output::Records(records) => records,
+        Output::AffectedRows(_) => {
+            return InternalNoCause {
+                msg: "output in opentsdb suggest should not be affected rows",
+            }
+            .fail()
+        }
+    };
+
+    let mut values = Vec::new();
+    for record in records {
+        let idx = record
+            .schema()
+            .index_of(tagk)
+            .with_context(|| InternalNoCause {
+                msg: format!("column {tagk} not found in output of opentsdb suggest"),
+            })?;
+        for row_idx in 0..record.num_rows() {
+            if let Some(v) = record.column(idx).datum(row_idx).as_str() {
+                values.push(v.to_string());
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_columns() -> TableColumns {
+        TableColumns {
+            tags: vec!["host".to_string(), "dc".to_string()],
+            timestamp: "timestamp".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_time_value() {
+        let now = 1_700_000_000_000;
+        assert_eq!(
+            1_356_998_400_000,
+            TimeValue::Int(1_356_998_400).to_millis(now).unwrap()
+        );
+        assert_eq!(
+            1_356_998_400_123,
+            TimeValue::Str("1356998400123".to_string())
+                .to_millis(now)
+                .unwrap()
+        );
+        assert_eq!(
+            now - 3600 * 1000,
+            TimeValue::Str("1h-ago".to_string()).to_millis(now).unwrap()
+        );
+        assert!(TimeValue::Str("1x-ago".to_string()).to_millis(now).is_err());
+        assert!(TimeValue::Str("abc".to_string()).to_millis(now).is_err());
+        assert!(TimeValue::Int(i64::MIN).to_millis(now).is_err());
+        assert!(TimeValue::Str("9223372036854775807y-ago".to_string())
+            .to_millis(now)
+            .is_err());
+        assert!(TimeValue::Str("9223372036854775ms-ago".to_string())
+            .to_millis(i64::MIN)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_metric_query() {
+        let query =
+            SubQuery::parse_metric_query("sum:1m-avg:rate:sys.cpu{host=web01|web02,dc=*}").unwrap();
+        assert_eq!("sum", query.aggregator);
+        assert_eq!("sys.cpu", query.metric);
+        assert!(query.rate);
+        assert_eq!(Some("1m-avg".to_string()), query.downsample);
+        assert_eq!("web01|web02", query.tags["host"]);
+        assert_eq!("*", query.tags["dc"]);
+
+        let query = SubQuery::parse_metric_query("avg:rate{counter,,1000}:sys.cpu").unwrap();
+        assert!(query.rate);
+        assert_eq!("sys.cpu", query.metric);
+
+        let query = SubQuery::parse_metric_query("avg:sys.cpu").unwrap();
+        assert!(!query.rate);
+        assert!(query.downsample.is_none());
+        assert!(query.tags.is_empty());
+
+        assert!(SubQuery::parse_metric_query("sys.cpu").is_err());
+        assert!(SubQuery::parse_metric_query("sum:sys.cpu{host}").is_err());
+    }
+
+    #[test]
+    fn test_parse_downsample() {
+        let downsample = Downsample::try_from_str("1m-avg").unwrap();
+        assert_eq!(
+            Downsample {
+                interval: Some(60 * 1000),
+                aggregator: Aggregator::Avg,
+            },
+            downsample
+        );
+        let downsample = Downsample::try_from_str("0all-sum-nan").unwrap();
+        assert_eq!(
+            Downsample {
+                interval: None,
+                aggregator: Aggregator::Sum,
+            },
+            downsample
+        );
+        assert!(Downsample::try_from_str("1m").is_err());
+        assert!(Downsample::try_from_str("1m-none").is_err());
+
+        assert_eq!(
+            "PT2H",
+            Downsample::time_bucket_period(2 * 3600 * 1000).unwrap()
+        );
+        assert_eq!(
+            "PT90M",
+            Downsample::time_bucket_period(90 * 60 * 1000).unwrap()
+        );
+        assert_eq!("PT30S", Downsample::time_bucket_period(30 * 1000).unwrap());
+        assert!(Downsample::time_bucket_period(500).is_err());
+    }
+
+    #[test]
+    fn test_build_sub_query_sql() {
+        let query: SubQuery = serde_json::from_str(
+            r#"{"aggregator":"sum","metric":"sys.cpu","downsample":"1m-avg",
+                "tags":{"host":"web01"},
+                "filters":[{"type":"wildcard","tagk":"dc","filter":"lg*","groupBy":false}]}"#,
+        )
+        .unwrap();
+        let plan = SubQueryPlan::try_new(&query, &build_columns(), 1000, 2000).unwrap();
+        assert_eq!(
+            "SELECT `host`, `dc`, time_bucket(`timestamp`, 'PT1M') AS `timestamp`, \
+             AVG(`value`) AS `value` FROM `sys.cpu` WHERE `timestamp` >= 1000 AND \
+             `timestamp` <= 2000 AND `host` IN ('web01') AND `dc` LIKE 'lg%' \
+             GROUP BY `host`, `dc`, time_bucket(`timestamp`, 'PT1M')",
+            plan.sql
+        );
+        assert_eq!(vec!["host".to_string()], plan.group_by_tags);
+        assert_eq!(vec!["dc".to_string()], plan.aggregate_tags);
+
+        let query: SubQuery = serde_json::from_str(
+            r#"{"aggregator":"none","metric":"sys.cpu",
+                "filters":[{"type":"iliteral_or","tagk":"host","filter":"Web'01|web02"}]}"#,
+        )
+        .unwrap();
+        let plan = SubQueryPlan::try_new(&query, &build_columns(), 1000, 2000).unwrap();
+        assert_eq!(
+            "SELECT `host`, `dc`, `timestamp`, `value` FROM `sys.cpu` WHERE \
+             `timestamp` >= 1000 AND `timestamp` <= 2000 AND \
+             lower(`host`) IN ('web''01', 'web02')",
+            plan.sql
+        );
+        assert_eq!(build_columns().tags, plan.group_by_tags);
+
+        let query: SubQuery = serde_json::from_str(
+            r#"{"aggregator":"sum","metric":"sys.cpu","tags":{"unknown":"a"}}"#,
+        )
+        .unwrap();
+        assert!(SubQueryPlan::try_new(&query, &build_columns(), 1000, 2000).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_and_rate() {
+        let values = [1.0, 2.0, 3.0, 6.0];
+        assert_eq!(12.0, Aggregator::Sum.aggregate(&values));
+        assert_eq!(3.0, Aggregator::Avg.aggregate(&values));
+        assert_eq!(1.0, Aggregator::Min.aggregate(&values));
+        assert_eq!(6.0, Aggregator::Max.aggregate(&values));
+        assert_eq!(4.0, Aggregator::Count.aggregate(&values));
+
+        let dps = BTreeMap::from([(1000, 1.0), (3000, 5.0), (4000, 4.0)]);
+        assert_eq!(BTreeMap::from([(3000, 2.0), (4000, -1.0)]), to_rate(dps));
+    }
+
+    #[test]
+    fn test_quote_literal() {
+        assert_eq!("'web01'", quote_literal("web01"));
+        assert_eq!(r"'it''s\\d+'", quote_literal(r"it's\d+"));
+    }
+
+    #[test]
+    fn test_build_tag_values_sql() {
+        assert_eq!(
+            "SELECT DISTINCT `host` FROM `sys.cpu` WHERE `host` LIKE 'web\\\\_%' LIMIT 10",
+            build_tag_values_sql("sys.cpu", "host", "web_", 10)
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This module implements [metrics export][1] for OpenTelemetry protocol.
+//! [1]: https://opentelemetry.io/docs/specs/otlp/#otlphttp-request
+
+use horaedbproto::storage::{
+    RequestContext as GrpcRequestContext, WriteRequest as GrpcWriteRequest,
+};
+use http::StatusCode;
+use logger::debug;
+
+use crate::{
+    context::RequestContext,
+    error::{ErrNoCause, Result},
+    metrics::HTTP_HANDLER_COUNTER_VEC,
+    otlp::types::{convert_export_request, ExportRequest, ExportResponse},
+    Context, Proxy,
+};
+
+pub mod types;
+
+impl Proxy {
+    pub async fn handle_otlp_metrics_export(
+        &self,
+        ctx: RequestContext,
+        req: ExportRequest,
+    ) -> Result<ExportResponse> {
+        let write_table_requests = convert_export_request(req)?;
+
+        let num_rows: usize = write_table_requests
+            .iter()
+            .map(|req| {
+                req.entries
+                    .iter()
+                    .map(|e| e.field_groups.len())
+                    .sum::<usize>()
+            })
+            .sum();
+
+        let table_request = GrpcWriteRequest {
+            context: Some(GrpcRequestContext {
+                database: ctx.schema.clone(),
+            }),
+            table_requests: write_table_requests,
+        };
+        let proxy_context = Context::new(ctx.timeout, None);
+
+        match self
+            .handle_write_internal(proxy_context, table_request)
+            .await
+        {
+            Ok(result) => {
+                if result.failed != 0 {
+                    HTTP_HANDLER_COUNTER_VEC.write_failed.inc();
+                    HTTP_HANDLER_COUNTER_VEC
+                        .write_failed_row
+                        .inc_by(result.failed as u64);
+                    ErrNoCause {
+                        code: StatusCode::INTERNAL_SERVER_ERROR,
+                        msg: format!("fail to write storage, failed rows:{:?}", result.failed),
+                    }
+                    .fail()?;
+                }
+
+                debug!(
+                    "OTLP metrics write finished, catalog:{}, schema:{}, result:{result:?}",
+                    ctx.catalog, ctx.schema
+                );
+
+                // All the data points are accepted, so partial success is left empty
+                // according to the protocol.
+                Ok(ExportResponse {
+                    partial_success: None,
+                })
+            }
+            Err(e) => {
+                HTTP_HANDLER_COUNTER_VEC.write_failed.inc();
+                HTTP_HANDLER_COUNTER_VEC
+                    .write_failed_row
+                    .inc_by(num_rows as u64);
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,638 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This module contains the types for OpenTelemetry metrics.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use horaedbproto::storage::{
+    value, Field, FieldGroup, Tag, Value as ProtoValue, WriteSeriesEntry, WriteTableRequest,
+};
+use http::StatusCode;
+use opentelemetry_proto::tonic::{
+    collector::metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+    common::v1::{any_value, AnyValue, KeyValue},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        ExponentialHistogramDataPoint, HistogramDataPoint, NumberDataPoint, SummaryDataPoint,
+    },
+};
+use snafu::ensure;
+
+use crate::error::{ErrNoCause, Result};
+
+const OTLP_DEFAULT_FIELD: &str = "value";
+const OTLP_BUCKET_BOUND_TAG: &str = "le";
+const OTLP_QUANTILE_TAG: &str = "quantile";
+const OTLP_SCOPE_NAME_TAG: &str = "otel_scope_name";
+const OTLP_SCOPE_VERSION_TAG: &str = "otel_scope_version";
+const OTLP_INF_BOUND: &str = "+Inf";
+const NANOS_PER_MILLI: u64 = 1_000_000;
+
+/// Metrics export request of [OTLP][1], shared by the http and grpc
+/// transports.
+///
+/// [1]: https://opentelemetry.io/docs/specs/otlp/#otlpgrpc
+pub type ExportRequest = ExportMetricsServiceRequest;
+pub type ExportResponse = ExportMetricsServiceResponse;
+
+type TagSet = BTreeMap<String, String>;
+
+/// Samples grouped by table and then by series.
+///
+/// Every converted sample is stored under the `value` field, which keeps the
+/// table layout the same as the one created by the prometheus remote write
+/// and OpenTSDB put api.
+#[derive(Debug, Default)]
+struct SamplesBuilder {
+    tables: HashMap<String, BTreeMap<TagSet, Vec<(i64, f64)>>>,
+}
+
+impl SamplesBuilder {
+    fn push(&mut self, table: String, tags: TagSet, timestamp: i64, value: f64) {
+        self.tables
+            .entry(table)
+            .or_default()
+            .entry(tags)
+            .or_default()
+            .push((timestamp, value));
+    }
+
+    fn build(self) -> Vec<WriteTableRequest> {
+        let mut requests = Vec::with_capacity(self.tables.len());
+        for (table, series) in self.tables {
+            let tag_names: BTreeSet<_> = series.keys().flat_map(|tags| tags.keys()).collect();
+            let tag_name_to_tag_index: HashMap<_, _> = tag_names
+                .iter()
+                .enumerate()
+                .map(|(idx, name)| (*name, idx as u32))
+                .collect();
+
+            let entries = series
+                .iter()
+                .map(|(tags, samples)| {
+                    let tags = tags
+                        .iter()
+                        .map(|(name, value)| Tag {
+                            name_index: tag_name_to_tag_index[name],
+                            value: Some(ProtoValue {
+                                value: Some(value::Value::StringValue(value.clone())),
+                            }),
+                        })
+                        .collect();
+                    let field_groups = samples
+                        .iter()
+                        .map(|(timestamp, value)| FieldGroup {
+                            timestamp: *timestamp,
+                            fields: vec![Field {
+                                name_index: 0,
+                                value: Some(ProtoValue {
+                                    value: Some(value::Value::Float64Value(*value)),
+                                }),
+                            }],
+                        })
+                        .collect();
+
+                    WriteSeriesEntry { tags, field_groups }
+                })
+                .collect();
+
+            requests.push(WriteTableRequest {
+                table,
+                tag_names: tag_names.into_iter().cloned().collect(),
+                field_names: vec![OTLP_DEFAULT_FIELD.to_string()],
+                entries,
+            });
+        }
+
+        requests
+    }
+}
+
+/// Convert the otlp metrics into write requests.
+///
+/// The conversion follows the way prometheus handles otlp metrics:
+///  - Gauge and sum are written into the table named by the metric.
+///  - Histogram and exponential histogram are split into `{name}_count`,
+///    `{name}_sum` and cumulative `{name}_bucket` tables, and the upper bound
+///    of every bucket is stored in the `le` tag.
+///  - Summary is split into `{name}_count`, `{name}_sum` and `{name}` tables,
+///    and the quantile is stored in the `quantile` tag.
+///
+/// Attributes of resource, scope and data point are all converted into tags,
+/// and the latter one takes precedence when the same key is found.
+pub(crate) fn convert_export_request(req: ExportRequest) -> Result<Vec<WriteTableRequest>> {
+    let mut builder = SamplesBuilder::default();
+    for resource_metrics in req.resource_metrics {
+        let mut resource_tags = TagSet::new();
+        if let Some(resource) = resource_metrics.resource {
+            extend_tags(&mut resource_tags, resource.attributes);
+        }
+
+        for scope_metrics in resource_metrics.scope_metrics {
+            let mut scope_tags = resource_tags.clone();
+            if let Some(scope) = scope_metrics.scope {
+                if !scope.name.is_empty() {
+                    scope_tags.insert(OTLP_SCOPE_NAME_TAG.to_string(), scope.name);
+                }
+                if !scope.version.is_empty() {
+                    scope_tags.insert(OTLP_SCOPE_VERSION_TAG.to_string(), scope.version);
+                }
+                extend_tags(&mut scope_tags, scope.attributes);
+            }
+
+            for metric in scope_metrics.metrics {
+                ensure!(
+                    !metric.name.is_empty(),
+                    ErrNoCause {
+                        code: StatusCode::BAD_REQUEST,
+                        msg: "Metric name must not be empty",
+                    }
+                );
+
+                let name = metric.name;
+                match metric.data {
+                    Some(Data::Gauge(gauge)) => {
+                        for point in gauge.data_points {
+                            convert_number_point(&mut builder, &name, &scope_tags, point);
+                        }
+                    }
+                    Some(Data::Sum(sum)) => {
+                        for point in sum.data_points {
+                            convert_number_point(&mut builder, &name, &scope_tags, point);
+                        }
+                    }
+                    Some(Data::Histogram(histogram)) => {
+                        for point in histogram.data_points {
+                            convert_histogram_point(&mut builder, &name, &scope_tags, point)?;
+                        }
+                    }
+                    Some(Data::ExponentialHistogram(histogram)) => {
+                        for point in histogram.data_points {
+                            convert_exponential_histogram_point(
+                                &mut builder,
+                                &name,
+                                &scope_tags,
+                                point,
+                            );
+                        }
+                    }
+                    Some(Data::Summary(summary)) => {
+                        for point in summary.data_points {
+                            convert_summary_point(&mut builder, &name, &scope_tags, point);
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    Ok(builder.build())
+}
+
+fn convert_number_point(
+    builder: &mut SamplesBuilder,
+    name: &str,
+    base_tags: &TagSet,
+    point: NumberDataPoint,
+) {
+    let value = match point.value {
+        Some(number_data_point::Value::AsDouble(v)) => v,
+        Some(number_data_point::Value::AsInt(v)) => v as f64,
+        // A point without value is invalid, just ignore it.
+        None => return,
+    };
+    let tags = build_point_tags(base_tags, point.attributes);
+
+    builder.push(
+        name.to_string(),
+        tags,
+        nanos_to_millis(point.time_unix_nano),
+        value,
+    );
+}
+
+fn convert_histogram_point(
+    builder: &mut SamplesBuilder,
+    name: &str,
+    base_tags: &TagSet,
+    point: HistogramDataPoint,
+) -> Result<()> {
+    let timestamp = nanos_to_millis(point.time_unix_nano);
+    let tags = build_point_tags(base_tags, point.attributes);
+
+    // The bucket_counts should be empty or contain one more element than the
+    // explicit_bounds, the last one is for the `+Inf` bucket.
+    ensure!(
+        point.bucket_counts.is_empty()
+            || point.bucket_counts.len() == point.explicit_bounds.len() + 1,
+        ErrNoCause {
+            code: StatusCode::BAD_REQUEST,
+            msg: format!(
+                "Invalid histogram point, metric:{name}, bucket_counts:{}, explicit_bounds:{}",
+                point.bucket_counts.len(),
+                point.explicit_bounds.len()
+            ),
+        }
+    );
+
+    let mut cumulative_count = 0;
+    for (idx, count) in point.bucket_counts.iter().enumerate() {
+        cumulative_count += count;
+        let bound = match point.explicit_bounds.get(idx) {
+            Some(bound) => bound.to_string(),
+            None => OTLP_INF_BOUND.to_string(),
+        };
+        push_bucket(builder, name, &tags, bound, timestamp, cumulative_count);
+    }
+
+    push_count_and_sum(builder, name, tags, timestamp, point.count, point.sum);
+
+    Ok(())
+}
+
+fn convert_exponential_histogram_point(
+    builder: &mut SamplesBuilder,
+    name: &str,
+    base_tags: &TagSet,
+    point: ExponentialHistogramDataPoint,
+) {
+    let timestamp = nanos_to_millis(point.time_unix_nano);
+    let tags = build_point_tags(base_tags, point.attributes);
+
+    // Bucket with index `i` covers the range `(base^i, base^(i+1)]` where
+    // `base = 2^(2^-scale)`, and the negative buckets mirror the positive ones.
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let mut cumulative_count = 0;
+
+    // Negative buckets are emitted from the smallest upper bound, that is, from
+    // the largest index.
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = &point.negative
+    {
+        for (idx, count) in bucket_counts.iter().enumerate().rev() {
+            cumulative_count += count;
+            let bound = -base.powi(offset + idx as i32);
+            push_bucket(
+                builder,
+                name,
+                &tags,
+                bound.to_string(),
+                timestamp,
+                cumulative_count,
+            );
+        }
+    }
+
+    cumulative_count += point.zero_count;
+    push_bucket(
+        builder,
+        name,
+        &tags,
+        point.zero_threshold.to_string(),
+        timestamp,
+        cumulative_count,
+    );
+
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = &point.positive
+    {
+        for (idx, count) in bucket_counts.iter().enumerate() {
+            cumulative_count += count;
+            let bound = base.powi(offset + idx as i32 + 1);
+            push_bucket(
+                builder,
+                name,
+                &tags,
+                bound.to_string(),
+                timestamp,
+                cumulative_count,
+            );
+        }
+    }
+
+    push_bucket(
+        builder,
+        name,
+        &tags,
+        OTLP_INF_BOUND.to_string(),
+        timestamp,
+        point.count,
+    );
+
+    push_count_and_sum(builder, name, tags, timestamp, point.count, point.sum);
+}
+
+fn convert_summary_point(
+    builder: &mut SamplesBuilder,
+    name: &str,
+    base_tags: &TagSet,
+    point: SummaryDataPoint,
+) {
+    let timestamp = nanos_to_millis(point.time_unix_nano);
+    let tags = build_point_tags(base_tags, point.attributes);
+
+    for quantile_value in point.quantile_values {
+        let mut quantile_tags = tags.clone();
+        quantile_tags.insert(
+            OTLP_QUANTILE_TAG.to_string(),
+            quantile_value.quantile.to_string(),
+        );
+        builder.push(
+            name.to_string(),
+            quantile_tags,
+            timestamp,
+            quantile_value.value,
+        );
+    }
+
+    push_count_and_sum(builder, name, tags, timestamp, point.count, Some(point.sum));
+}
+
+fn push_bucket(
+    builder: &mut SamplesBuilder,
+    name: &str,
+    tags: &TagSet,
+    bound: String,
+    timestamp: i64,
+    cumulative_count: u64,
+) {
+    let mut bucket_tags = tags.clone();
+    bucket_tags.insert(OTLP_BUCKET_BOUND_TAG.to_string(), bound);
+    builder.push(
+        format!("{name}_bucket"),
+        bucket_tags,
+        timestamp,
+        cumulative_count as f64,
+    );
+}
+
+fn push_count_and_sum(
+    builder: &mut SamplesBuilder,
+    name: &str,
+    tags: TagSet,
+    timestamp: i64,
+    count: u64,
+    sum: Option<f64>,
+) {
+    if let Some(sum) = sum {
+        builder.push(format!("{name}_sum"), tags.clone(), timestamp, sum);
+    }
+    builder.push(format!("{name}_count"), tags, timestamp, count as f64);
+}
+
+fn build_point_tags(base_tags: &TagSet, attributes: Vec<KeyValue>) -> TagSet {
+    let mut tags = base_tags.clone();
+    extend_tags(&mut tags, attributes);
+    tags
+}
+
+fn extend_tags(tags: &mut TagSet, attributes: Vec<KeyValue>) {
+    for KeyValue { key, value } in attributes {
+        if key.is_empty() {
+            continue;
+        }
+        if let Some(value) = value.as_ref().and_then(any_value_to_string) {
+            tags.insert(key, value);
+        }
+    }
+}
+
+/// Convert the attribute value to the string stored in tag, returns `None` if
+/// value is not set.
+fn any_value_to_string(value: &AnyValue) -> Option<String> {
+    let value = match value.value.as_ref()? {
+        any_value::Value::StringValue(v) => v.clone(),
+        any_value::Value::BoolValue(v) => v.to_string(),
+        any_value::Value::IntValue(v) => v.to_string(),
+        any_value::Value::DoubleValue(v) => v.to_string(),
+        any_value::Value::BytesValue(v) => String::from_utf8_lossy(v).to_string(),
+        any_value::Value::ArrayValue(v) => {
+            let values: Vec<_> = v.values.iter().filter_map(any_value_to_string).collect();
+            format!("[{}]", values.join(","))
+        }
+        any_value::Value::KvlistValue(v) => {
+            let values: Vec<_> = v
+                .values
+                .iter()
+                .filter_map(|kv| {
+                    kv.value
+                        .as_ref()
+                        .and_then(any_value_to_string)
+                        .map(|value| format!("{}:{value}", kv.key))
+                })
+                .collect();
+            format!("{{{}}}", values.join(","))
+        }
+    };
+
+    Some(value)
+}
+
+#[inline]
+fn nanos_to_millis(nanos: u64) -> i64 {
+    (nanos / NANOS_PER_MILLI) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::tonic::{
+        common::v1::InstrumentationScope,
+        metrics::v1::{Gauge, Histogram, Metric, ResourceMetrics, ScopeMetrics},
+        resource::v1::Resource,
+    };
+
+    use super::*;
+
+    fn string_kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn build_request(metrics: Vec<Metric>) -> ExportRequest {
+        ExportRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![string_kv("service.name", "demo")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "meter".to_string(),
+                        ..Default::default()
+                    }),
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    fn find_table<'a>(reqs: &'a [WriteTableRequest], table: &str) -> &'a WriteTableRequest {
+        reqs.iter().find(|req| req.table == table).unwrap()
+    }
+
+    fn tag_value(req: &WriteTableRequest, entry: &WriteSeriesEntry, name: &str) -> String {
+        let tag = entry
+            .tags
+            .iter()
+            .find(|tag| req.tag_names[tag.name_index as usize] == name)
+            .unwrap();
+        match tag.value.as_ref().unwrap().value.as_ref().unwrap() {
+            value::Value::StringValue(v) => v.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn field_value(group: &FieldGroup) -> f64 {
+        match group.fields[0]
+            .value
+            .as_ref()
+            .unwrap()
+            .value
+            .as_ref()
+            .unwrap()
+        {
+            value::Value::Float64Value(v) => *v,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_convert_gauge() {
+        let gauge = Metric {
+            name: "cpu".to_string(),
+            data: Some(Data::Gauge(Gauge {
+                data_points: vec![
+                    NumberDataPoint {
+                        attributes: vec![string_kv("host", "h1")],
+                        time_unix_nano: 1_678_675_992_000_000_000,
+                        value: Some(number_data_point::Value::AsDouble(0.5)),
+                        ..Default::default()
+                    },
+                    NumberDataPoint {
+                        attributes: vec![string_kv("host", "h1")],
+                        time_unix_nano: 1_678_675_993_000_000_000,
+                        value: Some(number_data_point::Value::AsInt(1)),
+                        ..Default::default()
+                    },
+                ],
+            })),
+            ..Default::default()
+        };
+
+        let reqs = convert_export_request(build_request(vec![gauge])).unwrap();
+        assert_eq!(1, reqs.len());
+
+        let req = &reqs[0];
+        assert_eq!("cpu", req.table);
+        assert_eq!(
+            vec!["host", OTLP_SCOPE_NAME_TAG, "service.name"],
+            req.tag_names
+        );
+        assert_eq!(vec![OTLP_DEFAULT_FIELD], req.field_names);
+        assert_eq!(1, req.entries.len());
+
+        let entry = &req.entries[0];
+        assert_eq!("h1", tag_value(req, entry, "host"));
+        assert_eq!("meter", tag_value(req, entry, OTLP_SCOPE_NAME_TAG));
+        assert_eq!("demo", tag_value(req, entry, "service.name"));
+        assert_eq!(2, entry.field_groups.len());
+        assert_eq!(1678675992000, entry.field_groups[0].timestamp);
+        assert_eq!(0.5, field_value(&entry.field_groups[0]));
+        assert_eq!(1678675993000, entry.field_groups[1].timestamp);
+        assert_eq!(1.0, field_value(&entry.field_groups[1]));
+    }
+
+    #[test]
+    fn test_convert_histogram() {
+        let histogram = Metric {
+            name: "latency".to_string(),
+            data: Some(Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    time_unix_nano: 1_678_675_992_000_000_000,
+                    count: 6,
+                    sum: Some(42.0),
+                    bucket_counts: vec![1, 2, 3],
+                    explicit_bounds: vec![1.0, 5.0],
+                    ..Default::default()
+                }],
+                aggregation_temporality: 2,
+            })),
+            ..Default::default()
+        };
+
+        let reqs = convert_export_request(build_request(vec![histogram])).unwrap();
+        assert_eq!(3, reqs.len());
+
+        let count = find_table(&reqs, "latency_count");
+        assert_eq!(6.0, field_value(&count.entries[0].field_groups[0]));
+        let sum = find_table(&reqs, "latency_sum");
+        assert_eq!(42.0, field_value(&sum.entries[0].field_groups[0]));
+
+        let bucket = find_table(&reqs, "latency_bucket");
+        assert_eq!(3, bucket.entries.len());
+        let mut buckets: Vec<_> = bucket
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    tag_value(bucket, entry, OTLP_BUCKET_BOUND_TAG),
+                    field_value(&entry.field_groups[0]),
+                )
+            })
+            .collect();
+        buckets.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        assert_eq!(
+            vec![
+                ("1".to_string(), 1.0),
+                ("5".to_string(), 3.0),
+                (OTLP_INF_BOUND.to_string(), 6.0)
+            ],
+            buckets
+        );
+    }
+
+    #[test]
+    fn test_convert_invalid_histogram() {
+        let histogram = Metric {
+            name: "latency".to_string(),
+            data: Some(Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    count: 3,
+                    bucket_counts: vec![1, 2],
+                    explicit_bounds: vec![1.0, 5.0],
+                    ..Default::default()
+                }],
+                aggregation_temporality: 2,
+            })),
+            ..Default::default()
+        };
+
+        assert!(convert_export_request(build_request(vec![histogram])).is_err());
+    }
+}
@@ -0,0 +1,340 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Result cache of the repeated queries, e.g. the dashboard queries refreshed
+//! every few seconds.
+//!
+//! The results are keyed by the logical plan, the fully resolved names, ids and
+//! versions of the queried tables, so a cached result is never hit by the same
+//! query on the tables of other schemas or once the memtables or ssts of the
+//! tables change. For the tables whose versions are unknown (e.g. partitioned
+//! tables), only the queries whose time ranges end in the past are cached, and
+//! the results expire at the end of the time bucket they are cached in.
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Write,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use clru::{CLruCache, CLruCacheConfig, WeightScale};
+use datafusion::{
+    common::tree_node::{TreeNode, VisitRecursion},
+    logical_expr::{
+        expr::{ScalarFunction, ScalarUDF},
+        Expr, Volatility,
+    },
+};
+use interpreters::RecordBatchVec;
+use logger::debug;
+use query_frontend::plan::QueryPlan;
+use serde::{Deserialize, Serialize};
+use size_ext::ReadableSize;
+use table_engine::table::TableRef;
+use time_ext::ReadableDuration;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub enable: bool,
+    /// Memory budget of the cached results
+    pub capacity: ReadableSize,
+    /// The results of the tables without versions expire at the end of the
+    /// time bucket they are cached in.
+    pub time_bucket: ReadableDuration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            capacity: ReadableSize::mb(256),
+            time_bucket: ReadableDuration::minutes(1),
+        }
+    }
+}
+
+struct OutputScale;
+
+impl WeightScale<String, RecordBatchVec> for OutputScale {
+    fn weight(&self, key: &String, value: &RecordBatchVec) -> usize {
+        let value_size: usize = value
+            .iter()
+            .flat_map(|batch| batch.as_arrow_record_batch().columns())
+            .map(|column| column.get_array_memory_size())
+            .sum();
+        key.len() + value_size
+    }
+}
+
+pub struct QueryCache {
+    time_bucket_ms: u64,
+    inner: Mutex<CLruCache<String, RecordBatchVec, RandomState, OutputScale>>,
+}
+
+pub type QueryCacheRef = Arc<QueryCache>;
+
+impl QueryCache {
+    /// Returns `None` if the cache is disabled or the capacity is zero.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.enable {
+            return None;
+        }
+        let capacity = NonZeroUsize::new(config.capacity.as_byte() as usize)?;
+        let inner = CLruCache::with_config(CLruCacheConfig::new(capacity).with_scale(OutputScale));
+
+        Some(Self {
+            time_bucket_ms: config.time_bucket.as_millis().max(1),
+            inner: Mutex::new(inner),
+        })
+    }
+
+    /// Build the cache key of the plan at `now_ms`, returns `None` if the
+    /// result of the plan can't be cached.
+    pub fn cache_key(&self, plan: &QueryPlan, now_ms: i64) -> Option<String> {
+        if has_volatile_function(plan) {
+            return None;
+        }
+
+        let mut key = format!("{:?}", plan.df_plan);
+        let mut unversioned = false;
+        let _ = plan.tables.visit::<_, ()>(|table_ref, table| {
+            // The unqualified tables in the plan may be resolved to the tables of different
+            // schemas, so the resolved name and id of every table are always in the key.
+            let _ = write!(key, "\n{table_ref}#{}", table.id());
+            match table_version(table) {
+                Some(version) => {
+                    let _ = write!(key, "@{version}");
+                }
+                None => unversioned = true,
+            }
+            Ok(())
+        });
+
+        if unversioned {
+            let time_range = match plan.extract_time_range() {
+                Ok(Some(v)) => v,
+                _ => return None,
+            };
+            // The data of the recent time range may still change.
+            if time_range.exclusive_end().as_i64() > now_ms {
+                return None;
+            }
+            let bucket = now_ms / self.time_bucket_ms as i64;
+            let _ = write!(key, "\nbucket@{bucket}");
+        }
+
+        Some(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<RecordBatchVec> {
+        let output = self.inner.lock().unwrap().get(key).cloned();
+        debug!("Query cache get, hit:{}", output.is_some());
+        output
+    }
+
+    pub fn put(&self, key: String, output: RecordBatchVec) {
+        // The output larger than the capacity is not cached.
+        let _ = self.inner.lock().unwrap().put_with_weight(key, output);
+    }
+}
+
+/// The version changes once the memtables or ssts of the table change, `None`
+/// if the storage of the table is unknown.
+fn table_version(table: &TableRef) -> Option<String> {
+    let storage_info = table.storage_info()?;
+    let mut version = storage_info.last_sequence.to_string();
+    for sst in &storage_info.ssts {
+        let _ = write!(version, ":{}", sst.file_id);
+    }
+    Some(version)
+}
+
+/// The results of the plans with volatile functions, e.g. `now()`, can't be
+/// cached.
+fn has_volatile_function(plan: &QueryPlan) -> bool {
+    let mut found = false;
+    let _ = plan.df_plan.apply(&mut |plan| {
+        for expr in plan.expressions() {
+            let _ = expr.apply(&mut |expr| {
+                let volatility = match expr {
+                    Expr::ScalarFunction(ScalarFunction { fun, .. }) => fun.volatility(),
+                    Expr::ScalarUDF(ScalarUDF { fun, .. }) => fun.signature.volatility,
+                    _ => Volatility::Immutable,
+                };
+                found |= volatility != Volatility::Immutable;
+                Ok(if found {
+                    VisitRecursion::Stop
+                } else {
+                    VisitRecursion::Continue
+                })
+            });
+        }
+        Ok(if found {
+            VisitRecursion::Stop
+        } else {
+            VisitRecursion::Continue
+        })
+    });
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use common_types::request_id::RequestId;
+    use df_operator::{scalar::ScalarUdf, udaf::AggregateUdf};
+    use query_frontend::{
+        config::DynamicConfig,
+        container::TableReference,
+        parser::Parser,
+        plan::Plan,
+        planner::Planner,
+        provider::{MetaProvider, ResolvedTable, Result as ProviderResult},
+        tests::MockMetaProvider,
+    };
+
+    use super::*;
+
+    /// Resolve the unqualified tables of the [MockMetaProvider] to the
+    /// `schema`.
+    struct SchemaMetaProvider<'a> {
+        inner: &'a MockMetaProvider,
+        schema: &'a str,
+    }
+
+    impl MetaProvider for SchemaMetaProvider<'_> {
+        fn default_catalog_name(&self) -> &str {
+            self.inner.default_catalog_name()
+        }
+
+        fn default_schema_name(&self) -> &str {
+            self.schema
+        }
+
+        fn table(&self, name: TableReference) -> ProviderResult<Option<ResolvedTable>> {
+            let resolved = name.resolve(self.default_catalog_name(), self.schema);
+            self.inner.table(TableReference::full(
+                resolved.catalog,
+                resolved.schema,
+                resolved.table,
+            ))
+        }
+
+        fn scalar_udf(&self, name: &str) -> ProviderResult<Option<ScalarUdf>> {
+            self.inner.scalar_udf(name)
+        }
+
+        fn aggregate_udf(&self, name: &str) -> ProviderResult<Option<AggregateUdf>> {
+            self.inner.aggregate_udf(name)
+        }
+
+        fn all_tables(&self) -> ProviderResult<Vec<TableRef>> {
+            self.inner.all_tables()
+        }
+    }
+
+    fn sql_to_query_plan<P: MetaProvider>(meta_provider: &P, sql: &str) -> QueryPlan {
+        let dyn_config = DynamicConfig::default();
+        let planner = Planner::new(meta_provider, RequestId::next_id(), 1, &dyn_config);
+        let mut statements = Parser::parse_sql(sql).unwrap();
+        assert_eq!(statements.len(), 1);
+        match planner.statement_to_plan(statements.remove(0)).unwrap() {
+            Plan::Query(plan) => plan,
+            plan => panic!("unexpected plan:{plan:?}"),
+        }
+    }
+
+    fn new_query_cache(capacity: ReadableSize) -> QueryCache {
+        let config = Config {
+            enable: true,
+            capacity,
+            time_bucket: ReadableDuration::secs(10),
+        };
+        QueryCache::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let mock = MockMetaProvider::default();
+        let query_cache = new_query_cache(ReadableSize::mb(1));
+        let now_ms = 100_000;
+
+        // The tables of the mock provider have no versions, so only the queries on the
+        // past time range are cacheable.
+        let plan = sql_to_query_plan(
+            &mock,
+            "select * from test_table where key2 >= 1 and key2 < 10",
+        );
+        let key = query_cache.cache_key(&plan, now_ms).unwrap();
+        assert_eq!(Some(key.clone()), query_cache.cache_key(&plan, now_ms + 1));
+        assert_ne!(Some(key), query_cache.cache_key(&plan, now_ms + 10_000));
+
+        let uncacheable_sqls = [
+            "select * from test_table",
+            "select * from test_table where key2 > 1",
+            "select now(), key2 from test_table where key2 >= 1 and key2 < 10",
+        ];
+        for sql in uncacheable_sqls {
+            let plan = sql_to_query_plan(&mock, sql);
+            assert!(query_cache.cache_key(&plan, now_ms).is_none(), "sql:{sql}");
+        }
+    }
+
+    #[test]
+    fn test_cache_key_of_schemas() {
+        let mock = MockMetaProvider::default();
+        let query_cache = new_query_cache(ReadableSize::mb(1));
+        let now_ms = 100_000;
+        let sql = "select * from test_table where key2 >= 1 and key2 < 10";
+
+        let keys: Vec<_> = ["schema1", "schema2"]
+            .into_iter()
+            .map(|schema| {
+                let provider = SchemaMetaProvider {
+                    inner: &mock,
+                    schema,
+                };
+                let plan = sql_to_query_plan(&provider, sql);
+                let key = query_cache.cache_key(&plan, now_ms).unwrap();
+                assert!(
+                    key.contains(&format!("{schema}.test_table#100")),
+                    "key:{key}"
+                );
+                key
+            })
+            .collect();
+        assert_ne!(keys[0], keys[1]);
+    }
+
+    #[test]
+    fn test_get_and_put() {
+        let config = Config::default();
+        assert!(QueryCache::from_config(&config).is_none());
+
+        let query_cache = new_query_cache(ReadableSize(16));
+        query_cache.put("key".to_string(), Vec::new());
+        assert_eq!(Some(0), query_cache.get("key").map(|v| v.len()));
+        assert!(query_cache.get("other").is_none());
+
+        // Larger than the capacity.
+        let key = "a".repeat(17);
+        query_cache.put(key.clone(), Vec::new());
+        assert!(query_cache.get(&key).is_none());
+    }
+}
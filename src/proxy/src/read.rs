@@ -19,13 +19,16 @@
 
 use std::{sync::Arc, time::Duration};
 
-use futures::FutureExt;
+use futures::{
+    future::{Abortable, Aborted},
+    FutureExt,
+};
 use generic_error::BoxError;
 use horaedbproto::storage::{
     storage_service_client::StorageServiceClient, RequestContext, SqlQueryRequest, SqlQueryResponse,
 };
 use http::StatusCode;
-use interpreters::interpreter::Output;
+use interpreters::{interpreter::Output, process_list::ProcessDesc};
 use logger::{error, info, warn, SlowTimer};
 use notifier::notifier::{ExecutionGuard, RequestNotifiers, RequestResult};
 use query_frontend::{
@@ -40,10 +43,11 @@ use tokio::sync::mpsc::{self, Sender};
 use tonic::{transport::Channel, IntoRequest};
 
 use crate::{
-    error::{ErrNoCause, ErrWithCause, Error, Internal, InternalNoCause, Result},
+    error::{self, ErrNoCause, ErrWithCause, Error, Internal, InternalNoCause, Result},
     forward::{ForwardRequest, ForwardResult},
+    limiter,
     metrics::GRPC_HANDLER_COUNTER_VEC,
-    Context, Proxy,
+    Context, Proxy, FORWARDED_AS_ADMIN,
 };
 
 const DEDUP_READ_CHANNEL_LEN: usize = 1;
@@ -197,6 +201,17 @@ impl Proxy {
         };
         let frontend = Frontend::new(provider, instance.dyn_config.fronted.clone());
 
+        // The limits in the hint of the sql or the request can only tighten the
+        // configured ones.
+        let query_limits = limiter::parse_query_limit_hints(sql)
+            .box_err()
+            .context(ErrWithCause {
+                code: StatusCode::BAD_REQUEST,
+                msg: "Failed to parse query limit hints",
+            })?
+            .min(ctx.query_limits)
+            .min(self.instance.limiter.query_limits(schema));
+
         let mut sql_ctx = SqlContext::new(request_id.clone(), deadline);
         // Parse sql, frontend error of invalid sql already contains sql
         // TODO(yingwen): Maybe move sql from frontend error to outer error
@@ -236,6 +251,17 @@ impl Proxy {
                 code: StatusCode::INTERNAL_SERVER_ERROR,
                 msg: "Failed to create plan",
             })?;
+        ensure!(
+            ctx.is_admin
+                || !matches!(
+                    plan,
+                    Plan::BackupTable(_) | Plan::RestoreTable(_) | Plan::KillQuery(_)
+                ),
+            ErrNoCause {
+                code: StatusCode::FORBIDDEN,
+                msg: "Backup, restore and kill query are only allowed through the admin api",
+            }
+        );
 
         if enable_block_query {
             self.instance
@@ -246,6 +272,7 @@ impl Proxy {
                     msg: format!("Request is blocked, table_name:{table_name:?}"),
                 })?;
         }
+        self.try_acquire_rate_limit(schema, &plan)?;
 
         if let Plan::Query(plan) = &plan {
             if let Some(priority) = plan
@@ -261,23 +288,72 @@ impl Proxy {
             }
         }
 
-        let output = if enable_partition_table_access {
-            self.execute_plan_involving_partition_table(
-                request_id.clone(),
-                catalog,
-                schema,
-                plan,
-                deadline,
-            )
-            .await
-        } else {
-            self.execute_plan(request_id.clone(), catalog, schema, plan, deadline)
-                .await
+        let cache_key = match (&self.query_cache, &plan) {
+            (Some(query_cache), Plan::Query(plan)) if !ctx.bypass_query_cache => {
+                query_cache.cache_key(plan, time_ext::current_time_millis() as i64)
+            }
+            _ => None,
+        };
+        if let (Some(query_cache), Some(cache_key)) = (&self.query_cache, &cache_key) {
+            if let Some(records) = query_cache.get(cache_key) {
+                info!("Handle sql query hit cache, request_id:{request_id}, catalog:{catalog}, schema:{schema}, sql:{sql}");
+                return Ok(Output::Records(records));
+            }
+        }
+
+        // Register the query so that it can be listed and killed.
+        let (process_guard, abort_registration) =
+            self.instance.process_list.register(ProcessDesc {
+                request_id: request_id.clone(),
+                catalog: catalog.to_string(),
+                schema: schema.to_string(),
+                client: ctx.client.clone(),
+                sql: sql.to_string(),
+            });
+        let interpreter = self.build_interpreter(
+            request_id.clone(),
+            catalog,
+            schema,
+            plan,
+            deadline,
+            enable_partition_table_access,
+            Some(process_guard.handle()),
+            query_limits,
+        )?;
+        // Aborting the execution drops the record batch streams, including the ones
+        // of the remote sub plans.
+        let output = match Abortable::new(
+            Self::interpreter_execute_plan(interpreter, deadline),
+            abort_registration,
+        )
+        .await
+        {
+            Ok(output) => output,
+            Err(Aborted) => {
+                return ErrNoCause {
+                    code: error::query_killed_code(),
+                    msg: format!("Query is killed, request_id:{request_id}"),
+                }
+                .fail();
+            }
         };
-        let output = output.box_err().with_context(|| ErrWithCause {
-            code: StatusCode::INTERNAL_SERVER_ERROR,
-            msg: "Failed to execute plan",
-        })?;
+        drop(process_guard);
+        let output = match output {
+            Ok(v) => v,
+            Err(e) => {
+                let code = e.code();
+                return Err(e).box_err().context(ErrWithCause {
+                    code,
+                    msg: "Failed to execute plan",
+                });
+            }
+        };
+
+        if let (Some(query_cache), Some(cache_key), Output::Records(records)) =
+            (&self.query_cache, cache_key, &output)
+        {
+            query_cache.put(cache_key, records.clone());
+        }
 
         let cost = slow_timer.elapsed();
         info!(
@@ -311,10 +387,17 @@ impl Proxy {
             sql: sql.to_string(),
         };
 
+        let mut req = sql_request.into_request();
+        // Backup and restore must be executed by the node owning the table, so the
+        // admin flag is carried to it.
+        if ctx.is_admin {
+            req.metadata_mut()
+                .insert(FORWARDED_AS_ADMIN, "true".parse().unwrap());
+        }
         let forward_req = ForwardRequest {
             schema: schema.to_string(),
             table: table_name.unwrap(),
-            req: sql_request.into_request(),
+            req,
             forwarded_from: ctx.forwarded_from,
         };
         let do_query = |mut client: StorageServiceClient<Channel>,
@@ -28,6 +28,8 @@ use cluster::config::SchemaConfig;
 use common_types::{
     column_schema::ColumnSchema,
     datum::{Datum, DatumKind},
+    decimal::Decimal,
+    json,
     request_id::RequestId,
     row::{Row, RowGroup},
     schema::Schema,
@@ -177,6 +179,12 @@ impl Proxy {
         if !self.auto_create_table {
             return Ok(());
         }
+        // Don't create tables for the throttled requests.
+        Self::convert_throttled_error(
+            self.instance
+                .limiter
+                .check_write_requests(schema, &req.table_requests),
+        )?;
 
         let schema_config = self
             .schema_config_provider
@@ -237,6 +245,12 @@ impl Proxy {
             .unwrap_or_default();
 
         if self.auto_create_table {
+            // Don't create tables for the throttled requests.
+            Self::convert_throttled_error(
+                self.instance
+                    .limiter
+                    .check_write_requests(schema, &write_request.table_requests),
+            )?;
             for (idx, table_name) in table_names.iter().enumerate() {
                 let table = self.try_get_table(catalog, schema, table_name)?;
                 if table.is_none() {
@@ -463,10 +477,26 @@ impl Proxy {
             })?;
 
         match forward_res {
-            ForwardResult::Forwarded(resp) => resp.map(|v: WriteResponsePB| WriteResponse {
-                success: v.success,
-                failed: v.failed,
-            }),
+            ForwardResult::Forwarded(resp) => {
+                let resp: WriteResponsePB = resp?;
+                // Propagate the error of the remote, e.g. the write is throttled there.
+                if let Some(header) = resp
+                    .header
+                    .filter(|h| h.code != StatusCode::OK.as_u16() as u32)
+                {
+                    return ErrNoCause {
+                        code: StatusCode::from_u16(header.code as u16)
+                            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                        msg: header.error,
+                    }
+                    .fail();
+                }
+
+                Ok(WriteResponse {
+                    success: resp.success,
+                    failed: resp.failed,
+                })
+            }
             ForwardResult::Local => InternalNoCause {
                 msg: "Local response is not expected".to_string(),
             }
@@ -504,6 +534,13 @@ impl Proxy {
         let plan_vec = self
             .write_request_to_insert_plan(req.table_requests, write_context)
             .await?;
+        // Acquire the quota of all the tables before writing, to avoid partial writes
+        // of the throttled request.
+        Self::convert_throttled_error(
+            self.instance
+                .limiter
+                .try_acquire_inserts(&schema_name, &plan_vec),
+        )?;
 
         let mut success = 0;
         for insert_plan in plan_vec {
@@ -913,7 +950,7 @@ fn write_entry_to_rows(
                 table_name,
                 tag_name,
                 tag_value.clone(),
-                column_schema.data_type,
+                column_schema,
             )?;
         }
     }
@@ -970,7 +1007,7 @@ fn write_entry_to_rows(
                     table_name,
                     field_name,
                     field_value,
-                    column_schema.data_type,
+                    column_schema,
                 )?;
             }
         }
@@ -984,8 +1021,9 @@ fn convert_proto_value_to_datum(
     table_name: &str,
     name: &str,
     value: value::Value,
-    data_type: DatumKind,
+    column_schema: &ColumnSchema,
 ) -> Result<Datum> {
+    let data_type = column_schema.data_type;
     match (value, data_type) {
         (value::Value::Float64Value(v), DatumKind::Double) => Ok(Datum::Double(v)),
         (value::Value::StringValue(v), DatumKind::String) => Ok(Datum::String(v.into())),
@@ -1001,6 +1039,31 @@ fn convert_proto_value_to_datum(
         (value::Value::Uint8Value(v), DatumKind::UInt8) => Ok(Datum::UInt8(v as u8)),
         (value::Value::TimestampValue(v), DatumKind::Timestamp) => Ok(Datum::Timestamp(Timestamp::new(v))),
         (value::Value::VarbinaryValue(v), DatumKind::Varbinary) => Ok(Datum::Varbinary(Bytes::from(v))),
+        // The decimal is written in string to avoid losing precision.
+        (value::Value::StringValue(v), DatumKind::Decimal) => {
+            let decimal_type = column_schema.decimal_type.unwrap_or_default();
+            Decimal::parse(&v, decimal_type)
+                .map(Datum::Decimal)
+                .box_err()
+                .with_context(|| ErrWithCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!(
+                        "Invalid decimal value, table:{table_name}, value_name:{name}, value:{v}"
+                    ),
+                })
+        }
+        // The json is written in string and validated before stored.
+        (value::Value::StringValue(v), DatumKind::Json) => {
+            json::validate(&v)
+                .box_err()
+                .with_context(|| ErrWithCause {
+                    code: StatusCode::BAD_REQUEST,
+                    msg: format!(
+                        "Invalid json value, table:{table_name}, value_name:{name}, value:{v}"
+                    ),
+                })?;
+            Ok(Datum::Json(v.into()))
+        }
         (v, _) => ErrNoCause {
             code: StatusCode::BAD_REQUEST,
             msg: format!(
@@ -16,6 +16,7 @@
 // under the License.
 
 use serde::{Deserialize, Serialize};
+use size_ext::ReadableSize;
 use time_ext::ReadableDuration;
 
 // FIXME: Use cpu number as the default parallelism
@@ -26,6 +27,19 @@ const DEFAULT_READ_PARALLELISM: usize = 8;
 pub struct Config {
     pub read_parallelism: usize,
     pub expensive_query_threshold: ReadableDuration,
+    /// Memory limit of a single query, unlimited if not set.
+    pub query_memory_limit: Option<ReadableSize>,
+    /// Memory limit of all the queries on the node, unlimited if not set.
+    pub total_memory_limit: Option<ReadableSize>,
+    /// Whether to spill the state of sorts to disk when the memory limit is
+    /// reached.
+    ///
+    /// Only sorts support spilling, the aggregates and other operators can't
+    /// spill their state, so the query is aborted with a memory limit exceeded
+    /// error once the limit is reached.
+    pub enable_spill: bool,
+    /// Dir for the spilled files, the temp dir of os is used if not set.
+    pub spill_dir: Option<String>,
 }
 
 impl Default for Config {
@@ -33,6 +47,10 @@ impl Default for Config {
         Self {
             read_parallelism: DEFAULT_READ_PARALLELISM,
             expensive_query_threshold: ReadableDuration::hours(24),
+            query_memory_limit: None,
+            total_memory_limit: None,
+            enable_spill: true,
+            spill_dir: None,
         }
     }
 }
@@ -22,6 +22,8 @@ use std::{sync::Arc, time::Instant};
 use common_types::request_id::RequestId;
 use runtime::Priority;
 
+use crate::limits::QueryLimits;
+
 pub type ContextRef = Arc<Context>;
 
 /// Query context
@@ -32,4 +34,5 @@ pub struct Context {
     pub default_catalog: String,
     pub default_schema: String,
     pub priority: Priority,
+    pub limits: QueryLimits,
 }
@@ -0,0 +1,337 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Enforcement of the [`QueryLimits`] on the datafusion plans
+
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use arrow::{datatypes::SchemaRef, record_batch::RecordBatch};
+use datafusion::{
+    error::{DataFusionError, Result as DfResult},
+    execution::context::TaskContext,
+    physical_expr::PhysicalSortExpr,
+    physical_plan::{
+        stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan,
+        Partitioning, RecordBatchStream, SendableRecordBatchStream, Statistics,
+    },
+};
+use df_engine_extensions::dist_sql_query::physical_plan::is_resolved_partitioned_scan;
+use futures::{Stream, StreamExt};
+use table_engine::provider::ScanTable;
+use tokio::time::Sleep;
+
+use crate::limits::{Error, QueryLimits};
+
+/// Wrap the scans of the plan by [`ScanLimitExec`] sharing the same counter,
+/// so the query is aborted once the total scanned rows or bytes exceed the
+/// limits.
+pub fn limit_scan(
+    plan: Arc<dyn ExecutionPlan>,
+    limits: &QueryLimits,
+) -> DfResult<Arc<dyn ExecutionPlan>> {
+    if !limits.limit_scan() {
+        return Ok(plan);
+    }
+
+    let counter = Arc::new(ScanCounter {
+        max_rows: limits.max_scanned_rows,
+        max_bytes: limits.max_scanned_bytes.map(|v| v.as_byte()),
+        rows: AtomicU64::new(0),
+        bytes: AtomicU64::new(0),
+    });
+    wrap_scans(plan, &counter)
+}
+
+fn wrap_scans(
+    plan: Arc<dyn ExecutionPlan>,
+    counter: &Arc<ScanCounter>,
+) -> DfResult<Arc<dyn ExecutionPlan>> {
+    if plan.as_any().is::<ScanTable>() || is_resolved_partitioned_scan(plan.as_ref()) {
+        return Ok(Arc::new(ScanLimitExec {
+            input: plan,
+            counter: counter.clone(),
+        }));
+    }
+
+    let children = plan.children();
+    if children.is_empty() {
+        return Ok(plan);
+    }
+    let children = children
+        .into_iter()
+        .map(|child| wrap_scans(child, counter))
+        .collect::<DfResult<Vec<_>>>()?;
+    plan.with_new_children(children)
+}
+
+#[derive(Debug)]
+struct ScanCounter {
+    max_rows: Option<u64>,
+    max_bytes: Option<u64>,
+    rows: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl ScanCounter {
+    fn add(&self, batch: &RecordBatch) -> DfResult<()> {
+        let rows = self
+            .rows
+            .fetch_add(batch.num_rows() as u64, Ordering::Relaxed)
+            + batch.num_rows() as u64;
+        if let Some(limit) = self.max_rows {
+            if rows > limit {
+                return Err(DataFusionError::External(Box::new(
+                    Error::ScannedRowsExceeded { limit },
+                )));
+            }
+        }
+
+        let batch_bytes = batch.get_array_memory_size() as u64;
+        let bytes = self.bytes.fetch_add(batch_bytes, Ordering::Relaxed) + batch_bytes;
+        if let Some(limit) = self.max_bytes {
+            if bytes > limit {
+                return Err(DataFusionError::External(Box::new(
+                    Error::ScannedBytesExceeded { limit },
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Counts the output of the scan against the limits of the query.
+#[derive(Debug)]
+pub struct ScanLimitExec {
+    input: Arc<dyn ExecutionPlan>,
+    counter: Arc<ScanCounter>,
+}
+
+impl ExecutionPlan for ScanLimitExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        match children.len() {
+            1 => Ok(Arc::new(ScanLimitExec {
+                input: children[0].clone(),
+                counter: self.counter.clone(),
+            })),
+            _ => Err(DataFusionError::Internal(
+                "ScanLimitExec wrong number of children".to_string(),
+            )),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DfResult<SendableRecordBatchStream> {
+        let input = self.input.execute(partition, context)?;
+        let counter = self.counter.clone();
+        let stream = input.map(move |batch| {
+            let batch = batch?;
+            counter.add(&batch)?;
+            Ok(batch)
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.input.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+}
+
+impl DisplayAs for ScanLimitExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ScanLimitExec: max_scanned_rows={:?}, max_scanned_bytes={:?}",
+            self.counter.max_rows, self.counter.max_bytes,
+        )
+    }
+}
+
+/// Stream aborted once the max execution time or the max result rows of the
+/// query is exceeded.
+pub struct LimitedStream {
+    input: SendableRecordBatchStream,
+    max_result_rows: Option<u64>,
+    result_rows: u64,
+    /// The max execution time and the timer started with the stream
+    timeout: Option<(Duration, Pin<Box<Sleep>>)>,
+    exceeded: bool,
+}
+
+impl LimitedStream {
+    /// Must be called in the context of the tokio runtime if the execution
+    /// time is limited.
+    pub fn new(input: SendableRecordBatchStream, limits: &QueryLimits) -> Self {
+        let timeout = limits
+            .max_execution_time
+            .map(|limit| (limit.0, Box::pin(tokio::time::sleep(limit.0))));
+
+        Self {
+            input,
+            max_result_rows: limits.max_result_rows,
+            result_rows: 0,
+            timeout,
+            exceeded: false,
+        }
+    }
+}
+
+impl RecordBatchStream for LimitedStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+impl Stream for LimitedStream {
+    type Item = DfResult<RecordBatch>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.exceeded {
+            return Poll::Ready(None);
+        }
+
+        if let Some((limit, timer)) = &mut this.timeout {
+            if timer.as_mut().poll(cx).is_ready() {
+                this.exceeded = true;
+                return Poll::Ready(Some(Err(DataFusionError::External(Box::new(
+                    Error::ExecutionTimeExceeded { limit: *limit },
+                )))));
+            }
+        }
+
+        match this.input.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(batch))) => {
+                this.result_rows += batch.num_rows() as u64;
+                match this.max_result_rows {
+                    Some(limit) if this.result_rows > limit => {
+                        this.exceeded = true;
+                        Poll::Ready(Some(Err(DataFusionError::External(Box::new(
+                            Error::ResultRowsExceeded { limit },
+                        )))))
+                    }
+                    _ => Poll::Ready(Some(Ok(batch))),
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::Int32Array,
+        datatypes::{DataType, Field, Schema},
+    };
+    use datafusion::physical_plan::{common::collect, memory::MemoryExec};
+    use time_ext::ReadableDuration;
+
+    use super::*;
+
+    fn build_stream(num_batches: usize) -> SendableRecordBatchStream {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+        )
+        .unwrap();
+        let exec = MemoryExec::try_new(&[vec![batch; num_batches]], schema, None).unwrap();
+        exec.execute(0, Arc::new(TaskContext::default())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_limit_result_rows() {
+        let limits = QueryLimits {
+            max_result_rows: Some(6),
+            ..Default::default()
+        };
+        let batches = collect(Box::pin(LimitedStream::new(build_stream(2), &limits)))
+            .await
+            .unwrap();
+        assert_eq!(2, batches.len());
+
+        let err = collect(Box::pin(LimitedStream::new(build_stream(3), &limits)))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("max result rows"), "err:{err}");
+    }
+
+    #[tokio::test]
+    async fn test_limit_execution_time() {
+        let limits = QueryLimits {
+            max_execution_time: Some(ReadableDuration::millis(10)),
+            ..Default::default()
+        };
+        let input = build_stream(1);
+        let schema = input.schema();
+        // The input never ends.
+        let input = Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            input.chain(futures::stream::pending()),
+        ));
+        let err = collect(Box::pin(LimitedStream::new(input, &limits)))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("max execution time"), "err:{err}");
+    }
+}
@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Memory pools for limiting the memory usage of queries
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use datafusion::{
+    error::{DataFusionError, Result as DfResult},
+    execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation},
+};
+
+/// Memory pool of a single query.
+///
+/// The memory is accounted both in this pool and the node-wide pool shared by
+/// all the queries, and the allocation fails once any of them is exhausted.
+/// Only sorts spill to disk on the failure, others including aggregates will
+/// abort the query with the [DataFusionError::ResourcesExhausted] error.
+#[derive(Debug)]
+pub struct QueryMemoryPool {
+    request_id: String,
+    limit: usize,
+    used: AtomicUsize,
+    node_pool: Arc<dyn MemoryPool>,
+}
+
+impl QueryMemoryPool {
+    pub fn new(request_id: String, limit: usize, node_pool: Arc<dyn MemoryPool>) -> Self {
+        Self {
+            request_id,
+            limit,
+            used: AtomicUsize::new(0),
+            node_pool,
+        }
+    }
+}
+
+impl MemoryPool for QueryMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.node_pool.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.node_pool.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.used.fetch_add(additional, Ordering::Relaxed);
+        self.node_pool.grow(reservation, additional)
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.used.fetch_sub(shrink, Ordering::Relaxed);
+        self.node_pool.shrink(reservation, shrink)
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> DfResult<()> {
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(additional)
+                    .filter(|new_used| *new_used <= self.limit)
+            })
+            .map_err(|used| {
+                DataFusionError::ResourcesExhausted(format!(
+                    "Query memory limit exceeded, request_id:{}, consumer:{}, limit:{}, used:{used}, required:{additional}",
+                    self.request_id,
+                    reservation.consumer().name(),
+                    self.limit,
+                ))
+            })?;
+
+        if let Err(e) = self.node_pool.try_grow(reservation, additional) {
+            self.used.fetch_sub(additional, Ordering::Relaxed);
+            return Err(DataFusionError::ResourcesExhausted(format!(
+                "Node memory limit exceeded, request_id:{}, consumer:{}, required:{additional}, err:{e}",
+                self.request_id,
+                reservation.consumer().name(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::execution::memory_pool::GreedyMemoryPool;
+
+    use super::*;
+
+    #[test]
+    fn test_query_memory_pool() {
+        let node_pool: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(150));
+        let pool1: Arc<dyn MemoryPool> = Arc::new(QueryMemoryPool::new(
+            "1".to_string(),
+            100,
+            node_pool.clone(),
+        ));
+        let pool2: Arc<dyn MemoryPool> = Arc::new(QueryMemoryPool::new(
+            "2".to_string(),
+            100,
+            node_pool.clone(),
+        ));
+
+        let mut r1 = MemoryConsumer::new("r1").register(&pool1);
+        r1.try_grow(80).unwrap();
+        let err = r1.try_grow(30).unwrap_err();
+        assert!(err.to_string().contains("Query memory limit exceeded"));
+        assert_eq!(80, pool1.reserved());
+
+        let mut r2 = MemoryConsumer::new("r2").register(&pool2);
+        let err = r2.try_grow(80).unwrap_err();
+        assert!(err.to_string().contains("Node memory limit exceeded"));
+        assert_eq!(0, pool2.reserved());
+        r2.try_grow(70).unwrap();
+        assert_eq!(150, node_pool.reserved());
+
+        drop(r1);
+        assert_eq!(0, pool1.reserved());
+        assert_eq!(70, node_pool.reserved());
+    }
+}
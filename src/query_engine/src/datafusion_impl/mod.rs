@@ -21,18 +21,23 @@ use catalog::manager::ManagerRef as CatalogManager;
 use datafusion::{
     execution::{
         context::SessionState,
+        disk_manager::DiskManagerConfig,
+        memory_pool::FairSpillPool,
         runtime_env::{RuntimeConfig, RuntimeEnv},
         FunctionRegistry,
     },
     prelude::{SessionConfig, SessionContext},
 };
 use df_engine_extensions::codec::PhysicalExtensionCodecImpl;
+use generic_error::BoxError;
+use snafu::ResultExt;
 use table_engine::{provider::HoraeDBOptions, remote::RemoteEngineRef};
 
 use crate::{
     context::Context,
     datafusion_impl::{
-        executor::DatafusionExecutorImpl, physical_planner::DatafusionPhysicalPlannerImpl,
+        executor::DatafusionExecutorImpl, memory_pool::QueryMemoryPool,
+        physical_planner::DatafusionPhysicalPlannerImpl,
         physical_planner_extension::QueryPlannerAdapter, task_context::Preprocessor,
     },
     executor::ExecutorRef,
@@ -41,6 +46,8 @@ use crate::{
 };
 
 pub mod executor;
+pub mod limits;
+pub mod memory_pool;
 pub mod physical_optimizer;
 pub mod physical_plan;
 pub mod physical_plan_extension;
@@ -64,7 +71,12 @@ impl DatafusionQueryEngineImpl {
         remote_engine: RemoteEngineRef,
         catalog_manager: CatalogManager,
     ) -> Result<Self> {
-        let runtime_env = Arc::new(RuntimeEnv::new(runtime_config).unwrap());
+        let runtime_config = apply_memory_config(&config, runtime_config);
+        let runtime_env = Arc::new(RuntimeEnv::new(runtime_config).box_err().context(
+            InitWithCause {
+                msg: "build datafusion runtime env",
+            },
+        )?);
         let df_physical_planner = Arc::new(QueryPlannerAdapter);
         let df_ctx_builder = Arc::new(DfContextBuilder::new(config, runtime_env.clone()));
         let physical_planner = Arc::new(DatafusionPhysicalPlannerImpl::new(
@@ -90,6 +102,24 @@ impl DatafusionQueryEngineImpl {
     }
 }
 
+/// Apply the node-wide memory limit and spill configs to the
+/// `runtime_config`.
+fn apply_memory_config(config: &Config, runtime_config: RuntimeConfig) -> RuntimeConfig {
+    let runtime_config = match config.total_memory_limit {
+        Some(limit) => {
+            runtime_config.with_memory_pool(Arc::new(FairSpillPool::new(limit.as_byte() as usize)))
+        }
+        None => runtime_config,
+    };
+
+    let disk_manager_config = match (config.enable_spill, &config.spill_dir) {
+        (false, _) => DiskManagerConfig::Disabled,
+        (true, Some(dir)) => DiskManagerConfig::NewSpecified(vec![dir.into()]),
+        (true, None) => DiskManagerConfig::NewOs,
+    };
+    runtime_config.with_disk_manager(disk_manager_config)
+}
+
 impl QueryEngine for DatafusionQueryEngineImpl {
     fn physical_planner(&self) -> PhysicalPlannerRef {
         self.physical_planner.clone()
@@ -137,7 +167,31 @@ impl DfContextBuilder {
 
         // Using default logcial optimizer, if want to add more custom rule, using
         // `add_optimizer_rule` to add.
-        let state = SessionState::with_config_rt(df_session_config, self.runtime_env.clone());
+        let state = SessionState::with_config_rt(df_session_config, self.query_runtime_env(ctx));
         SessionContext::with_state(state)
     }
+
+    /// Build the runtime env with a dedicated memory pool if the memory of
+    /// single query is limited.
+    fn query_runtime_env(&self, ctx: &Context) -> Arc<RuntimeEnv> {
+        let limit = match self.config.query_memory_limit {
+            Some(limit) => limit.as_byte() as usize,
+            None => return self.runtime_env.clone(),
+        };
+
+        let memory_pool = QueryMemoryPool::new(
+            ctx.request_id.to_string(),
+            limit,
+            self.runtime_env.memory_pool.clone(),
+        );
+        let runtime_config = RuntimeConfig::new()
+            .with_memory_pool(Arc::new(memory_pool))
+            .with_disk_manager(DiskManagerConfig::Existing(
+                self.runtime_env.disk_manager.clone(),
+            ))
+            .with_object_store_registry(self.runtime_env.object_store_registry.clone());
+
+        // Building with the existing disk manager never fails.
+        Arc::new(RuntimeEnv::new(runtime_config).expect("build query runtime env"))
+    }
 }
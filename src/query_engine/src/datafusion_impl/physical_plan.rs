@@ -34,6 +34,7 @@ use snafu::{OptionExt, ResultExt};
 use table_engine::stream::{FromDfStream, SendableRecordBatchStream};
 
 use crate::{
+    datafusion_impl::limits::{limit_scan, LimitedStream},
     error::*,
     physical_planner::{PhysicalPlan, TaskExecContext},
 };
@@ -111,6 +112,13 @@ impl PhysicalPlan for DataFusionPhysicalPlanAdapter {
         } else {
             Arc::new(CoalescePartitionsExec::new(executable))
         };
+        let limits = &df_task_ctx.ctx.limits;
+        let executable =
+            limit_scan(executable, limits)
+                .box_err()
+                .context(PhysicalPlanWithCause {
+                    msg: Some(format!("failed to limit scan, limits:{limits:?}")),
+                })?;
 
         debug!(
             "DatafusionExecutorImpl get the executable plan, request_id:{}, physical_plan:{}",
@@ -132,6 +140,7 @@ impl PhysicalPlan for DataFusionPhysicalPlanAdapter {
             .context(PhysicalPlanWithCause {
                 msg: Some(format!("partition_count:{partition_count}")),
             })?;
+        let df_stream = Box::pin(LimitedStream::new(df_stream, limits));
 
         let stream = FromDfStream::new(df_stream)
             .box_err()
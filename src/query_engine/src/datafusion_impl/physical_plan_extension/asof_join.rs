@@ -0,0 +1,495 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Physical plan of `ASOF JOIN`
+
+use std::{any::Any, cmp::Ordering, fmt, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, Int64Array, UInt64Array},
+    compute::{cast, concat_batches, take},
+    datatypes::{DataType, SchemaRef},
+    record_batch::RecordBatch,
+    row::{RowConverter, Rows, SortField},
+};
+use datafusion::{
+    error::{DataFusionError, Result as DfResult},
+    execution::{context::TaskContext, memory_pool::MemoryConsumer},
+    logical_expr::{JoinType, Operator},
+    physical_expr::PhysicalSortExpr,
+    physical_plan::{
+        coalesce_partitions::CoalescePartitionsExec, stream::RecordBatchStreamAdapter, DisplayAs,
+        DisplayFormatType, Distribution, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+        Statistics,
+    },
+};
+use futures::{future, stream};
+
+use super::collect_with_reservation;
+
+/// Parameters of asof join, the columns are represented by their indexes in
+/// the inputs.
+#[derive(Debug, Clone)]
+pub struct AsofJoinParams {
+    /// Only inner and left joins are supported.
+    pub join_type: JoinType,
+    /// Equal columns of the left and right inputs.
+    pub on: Vec<(usize, usize)>,
+    pub left_time: usize,
+    pub right_time: usize,
+    /// Comparison of the left time to the right time, one of `>=`, `>`, `<=`
+    /// and `<`.
+    pub op: Operator,
+}
+
+/// Joins every left row with the right row of the same equal columns and the
+/// nearest time, the inputs are collected in memory before joining, and the
+/// memory is reserved from the memory pool of the task.
+///
+/// Both inputs are sorted by the equal columns and time, then the matched
+/// rows are found by a single merge pass over them instead of a hash table.
+/// The rows scanned from the analytic engine are already ordered by the
+/// primary key (tags and timestamp) in every partition, and the stable sort
+/// merges such sorted runs in nearly linear time.
+#[derive(Debug)]
+pub struct AsofJoinExec {
+    left: Arc<dyn ExecutionPlan>,
+    right: Arc<dyn ExecutionPlan>,
+    schema: SchemaRef,
+    params: AsofJoinParams,
+}
+
+impl AsofJoinExec {
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        params: AsofJoinParams,
+    ) -> DfResult<Self> {
+        if !matches!(params.join_type, JoinType::Inner | JoinType::Left) {
+            return Err(DataFusionError::Plan(format!(
+                "Unsupported asof join type:{}",
+                params.join_type
+            )));
+        }
+        if !matches!(
+            params.op,
+            Operator::GtEq | Operator::Gt | Operator::LtEq | Operator::Lt
+        ) {
+            return Err(DataFusionError::Plan(format!(
+                "Unsupported asof join time condition, op:{}",
+                params.op
+            )));
+        }
+
+        let (left_schema, right_schema) = (left.schema(), right.schema());
+        let left_time_type = left_schema.field(params.left_time).data_type();
+        let right_time_type = right_schema.field(params.right_time).data_type();
+        if !matches!(left_time_type, DataType::Timestamp(..)) || left_time_type != right_time_type {
+            return Err(DataFusionError::Plan(format!(
+                "Asof join requires the times of the same timestamp type, left:{left_time_type}, right:{right_time_type}"
+            )));
+        }
+        for (left_idx, right_idx) in &params.on {
+            let left_type = left_schema.field(*left_idx).data_type();
+            let right_type = right_schema.field(*right_idx).data_type();
+            if left_type != right_type {
+                return Err(DataFusionError::Plan(format!(
+                    "Asof join requires the equal columns of the same type, left:{left_type}, right:{right_type}"
+                )));
+            }
+        }
+
+        Ok(Self {
+            left,
+            right,
+            schema,
+            params,
+        })
+    }
+}
+
+impl ExecutionPlan for AsofJoinExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition, Distribution::SinglePartition]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        match children.len() {
+            2 => Ok(Arc::new(AsofJoinExec {
+                left: children[0].clone(),
+                right: children[1].clone(),
+                schema: self.schema.clone(),
+                params: self.params.clone(),
+            })),
+            _ => Err(DataFusionError::Internal(
+                "AsofJoinExec wrong number of children".to_string(),
+            )),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DfResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "AsofJoinExec invalid partition:{partition}"
+            )));
+        }
+
+        let execute_input = |input: &Arc<dyn ExecutionPlan>| {
+            let input: Arc<dyn ExecutionPlan> = if input.output_partitioning().partition_count() > 1
+            {
+                Arc::new(CoalescePartitionsExec::new(input.clone()))
+            } else {
+                input.clone()
+            };
+            let schema = input.schema();
+            input
+                .execute(0, context.clone())
+                .map(|stream| (schema, stream))
+        };
+        let (left_schema, left) = execute_input(&self.left)?;
+        let (right_schema, right) = execute_input(&self.right)?;
+        let mut left_reservation =
+            MemoryConsumer::new("AsofJoinExec[left]").register(context.memory_pool());
+        let mut right_reservation =
+            MemoryConsumer::new("AsofJoinExec[right]").register(context.memory_pool());
+        let schema = self.schema.clone();
+        let params = self.params.clone();
+        let join = async move {
+            let (left_batches, right_batches) = future::try_join(
+                collect_with_reservation(left, &mut left_reservation),
+                collect_with_reservation(right, &mut right_reservation),
+            )
+            .await?;
+            let left = concat_batches(&left_schema, &left_batches)?;
+            let right = concat_batches(&right_schema, &right_batches)?;
+            asof_join(&left, &right, schema, &params)
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            stream::once(join),
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for AsofJoinExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AsofJoinExec: type={}, on={:?}, time_condition={} {} {}",
+            self.params.join_type,
+            self.params.on,
+            self.params.left_time,
+            self.params.op,
+            self.params.right_time,
+        )
+    }
+}
+
+/// Rows of an input sorted by the equal columns and time, the rows with null
+/// equal columns or time are excluded.
+struct SortedInput {
+    rows: Vec<usize>,
+    /// Encoded equal columns, `None` if there is no equal column.
+    keys: Option<Rows>,
+    times: Int64Array,
+}
+
+impl SortedInput {
+    fn try_new(
+        batch: &RecordBatch,
+        key_columns: &[usize],
+        time_column: usize,
+        converter: Option<&RowConverter>,
+    ) -> DfResult<Self> {
+        let times = cast(batch.column(time_column), &DataType::Int64)?;
+        let times = times
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .expect("cast to int64 array")
+            .clone();
+        let key_columns: Vec<_> = key_columns
+            .iter()
+            .map(|idx| batch.column(*idx).clone())
+            .collect();
+        let keys = match converter {
+            Some(converter) => Some(converter.convert_columns(&key_columns)?),
+            None => None,
+        };
+
+        let mut input = Self {
+            rows: Vec::new(),
+            keys,
+            times,
+        };
+        let mut rows: Vec<_> = (0..batch.num_rows())
+            .filter(|idx| {
+                input.times.is_valid(*idx) && key_columns.iter().all(|col| col.is_valid(*idx))
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            input
+                .cmp_keys(*a, &input, *b)
+                .then_with(|| input.times.value(*a).cmp(&input.times.value(*b)))
+        });
+        input.rows = rows;
+
+        Ok(input)
+    }
+
+    fn cmp_keys(&self, row: usize, other: &SortedInput, other_row: usize) -> Ordering {
+        match (&self.keys, &other.keys) {
+            (Some(keys), Some(other_keys)) => keys.row(row).cmp(&other_keys.row(other_row)),
+            _ => Ordering::Equal,
+        }
+    }
+
+    /// Find the row nearest to the `time` among `rows`, which are sorted by
+    /// time.
+    fn find_nearest(&self, rows: &[usize], time: i64, op: Operator) -> Option<usize> {
+        let partition_point = |pred: fn(i64, i64) -> bool| {
+            rows.partition_point(|row| pred(self.times.value(*row), time))
+        };
+        let pos = match op {
+            // The latest row not later than the time.
+            Operator::GtEq => partition_point(|t, time| t <= time).checked_sub(1),
+            // The latest row earlier than the time.
+            Operator::Gt => partition_point(|t, time| t < time).checked_sub(1),
+            // The earliest row not earlier than the time.
+            Operator::LtEq => Some(partition_point(|t, time| t < time)),
+            // The earliest row later than the time.
+            Operator::Lt => Some(partition_point(|t, time| t <= time)),
+            _ => None,
+        };
+
+        pos.and_then(|pos| rows.get(pos).copied())
+    }
+}
+
+fn asof_join(
+    left: &RecordBatch,
+    right: &RecordBatch,
+    schema: SchemaRef,
+    params: &AsofJoinParams,
+) -> DfResult<RecordBatch> {
+    let (left_keys, right_keys): (Vec<_>, Vec<_>) = params.on.iter().copied().unzip();
+    let converter = if left_keys.is_empty() {
+        None
+    } else {
+        let sort_fields = left_keys
+            .iter()
+            .map(|idx| SortField::new(left.column(*idx).data_type().clone()))
+            .collect();
+        Some(RowConverter::new(sort_fields)?)
+    };
+    let left_input = SortedInput::try_new(left, &left_keys, params.left_time, converter.as_ref())?;
+    let right_input =
+        SortedInput::try_new(right, &right_keys, params.right_time, converter.as_ref())?;
+
+    let mut left_indices = Vec::with_capacity(left_input.rows.len());
+    let mut right_indices = Vec::with_capacity(left_input.rows.len());
+    let mut left_start = 0;
+    let mut right_start = 0;
+    while left_start < left_input.rows.len() {
+        let left_row = left_input.rows[left_start];
+        let left_end = left_input.rows[left_start..]
+            .iter()
+            .position(|row| left_input.cmp_keys(*row, &left_input, left_row) != Ordering::Equal)
+            .map_or(left_input.rows.len(), |n| left_start + n);
+
+        // Skip the right rows of the smaller keys, and find the ones of the same keys.
+        while right_start < right_input.rows.len()
+            && right_input.cmp_keys(right_input.rows[right_start], &left_input, left_row)
+                == Ordering::Less
+        {
+            right_start += 1;
+        }
+        let right_end = right_input.rows[right_start..]
+            .iter()
+            .position(|row| right_input.cmp_keys(*row, &left_input, left_row) != Ordering::Equal)
+            .map_or(right_input.rows.len(), |n| right_start + n);
+        let right_rows = &right_input.rows[right_start..right_end];
+
+        for row in &left_input.rows[left_start..left_end] {
+            let time = left_input.times.value(*row);
+            let matched = right_input.find_nearest(right_rows, time, params.op);
+            if matched.is_some() || params.join_type == JoinType::Left {
+                left_indices.push(Some(*row as u64));
+                right_indices.push(matched.map(|idx| idx as u64));
+            }
+        }
+
+        left_start = left_end;
+        right_start = right_end;
+    }
+
+    // The left rows with null equal columns or time never match.
+    if params.join_type == JoinType::Left && left_indices.len() < left.num_rows() {
+        let mut is_matched = vec![false; left.num_rows()];
+        for row in &left_input.rows {
+            is_matched[*row] = true;
+        }
+        for (row, _) in is_matched.iter().enumerate().filter(|(_, v)| !**v) {
+            left_indices.push(Some(row as u64));
+            right_indices.push(None);
+        }
+    }
+
+    let left_indices = UInt64Array::from(left_indices);
+    let right_indices = UInt64Array::from(right_indices);
+    let take_columns = |batch: &RecordBatch, indices: &UInt64Array| {
+        batch
+            .columns()
+            .iter()
+            .map(|column| take(column.as_ref(), indices, None))
+            .collect::<Result<Vec<ArrayRef>, _>>()
+    };
+    let mut columns = take_columns(left, &left_indices)?;
+    columns.extend(take_columns(right, &right_indices)?);
+
+    RecordBatch::try_new(schema, columns).map_err(DataFusionError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{Float64Array, StringArray, TimestampMillisecondArray},
+        datatypes::{Field, Schema, TimeUnit},
+    };
+
+    use super::*;
+
+    fn build_batch(prefix: &str, rows: Vec<(&str, i64, f64)>) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(format!("{prefix}_host"), DataType::Utf8, true),
+            Field::new(
+                format!("{prefix}_ts"),
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                true,
+            ),
+            Field::new(format!("{prefix}_value"), DataType::Float64, true),
+        ]));
+        let hosts: StringArray = rows.iter().map(|row| Some(row.0)).collect();
+        let times: TimestampMillisecondArray = rows.iter().map(|row| Some(row.1)).collect();
+        let values: Float64Array = rows.iter().map(|row| Some(row.2)).collect();
+
+        RecordBatch::try_new(
+            schema,
+            vec![Arc::new(hosts), Arc::new(times), Arc::new(values)],
+        )
+        .unwrap()
+    }
+
+    fn run_asof_join(join_type: JoinType, op: Operator) -> String {
+        let left = build_batch(
+            "cpu",
+            vec![
+                ("b", 1500, 1.0),
+                ("a", 2500, 2.0),
+                ("a", 500, 3.0),
+                ("c", 1000, 4.0),
+            ],
+        );
+        let right = build_batch(
+            "power",
+            vec![
+                ("a", 2000, 20.0),
+                ("b", 1000, 10.0),
+                ("a", 1000, 30.0),
+                ("b", 2000, 40.0),
+            ],
+        );
+        let mut fields = left.schema().fields().to_vec();
+        fields.extend(right.schema().fields().iter().cloned());
+        let schema = Arc::new(Schema::new(fields));
+        let params = AsofJoinParams {
+            join_type,
+            on: vec![(0, 0)],
+            left_time: 1,
+            right_time: 1,
+            op,
+        };
+
+        let output = asof_join(&left, &right, schema, &params).unwrap();
+        arrow::util::pretty::pretty_format_batches(&[output])
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_asof_join() {
+        let expected = vec![
+            "+----------+-------------------------+-----------+------------+---------------------+-------------+",
+            "| cpu_host | cpu_ts                  | cpu_value | power_host | power_ts            | power_value |",
+            "+----------+-------------------------+-----------+------------+---------------------+-------------+",
+            "| a        | 1970-01-01T00:00:02.500 | 2.0       | a          | 1970-01-01T00:00:02 | 20.0        |",
+            "| b        | 1970-01-01T00:00:01.500 | 1.0       | b          | 1970-01-01T00:00:01 | 10.0        |",
+            "+----------+-------------------------+-----------+------------+---------------------+-------------+",
+        ];
+        assert_eq!(
+            expected.join("\n"),
+            run_asof_join(JoinType::Inner, Operator::GtEq)
+        );
+
+        let expected = vec![
+            "+----------+-------------------------+-----------+------------+---------------------+-------------+",
+            "| cpu_host | cpu_ts                  | cpu_value | power_host | power_ts            | power_value |",
+            "+----------+-------------------------+-----------+------------+---------------------+-------------+",
+            "| a        | 1970-01-01T00:00:00.500 | 3.0       | a          | 1970-01-01T00:00:01 | 30.0        |",
+            "| a        | 1970-01-01T00:00:02.500 | 2.0       |            |                     |             |",
+            "| b        | 1970-01-01T00:00:01.500 | 1.0       | b          | 1970-01-01T00:00:02 | 40.0        |",
+            "| c        | 1970-01-01T00:00:01     | 4.0       |            |                     |             |",
+            "+----------+-------------------------+-----------+------------+---------------------+-------------+",
+        ];
+        assert_eq!(
+            expected.join("\n"),
+            run_asof_join(JoinType::Left, Operator::Lt)
+        );
+    }
+}
@@ -0,0 +1,507 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Physical plan of gap filling
+
+use std::{any::Any, cmp::Ordering, fmt, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, Float64Array, TimestampMillisecondArray, UInt64Array},
+    compute::{cast, concat_batches, take},
+    datatypes::{DataType, SchemaRef, TimeUnit},
+    record_batch::RecordBatch,
+    row::{RowConverter, SortField},
+};
+use datafusion::{
+    error::{DataFusionError, Result as DfResult},
+    execution::{context::TaskContext, memory_pool::MemoryConsumer},
+    physical_expr::PhysicalSortExpr,
+    physical_plan::{
+        coalesce_partitions::CoalescePartitionsExec, stream::RecordBatchStreamAdapter, DisplayAs,
+        DisplayFormatType, Distribution, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+        Statistics,
+    },
+};
+use df_operator::udfs::gapfill::GapFillPeriod;
+use futures::stream;
+use query_frontend::gap_fill::FillStrategy;
+
+use super::collect_with_reservation;
+
+/// Max number of the output rows, the buckets of all the series included.
+const MAX_GAP_FILL_ROWS: usize = 10_000_000;
+
+/// Parameters of gap filling, the columns are represented by their indexes in
+/// the input.
+#[derive(Debug, Clone)]
+pub struct GapFillParams {
+    pub time_column: usize,
+    pub period: GapFillPeriod,
+    /// Inclusive start of the time range in millis.
+    pub start: i64,
+    /// Exclusive end of the time range in millis.
+    pub end: i64,
+    pub series_columns: Vec<usize>,
+    pub fill_columns: Vec<(usize, FillStrategy)>,
+}
+
+/// Emits a row per bucket in the time range for every series of the input,
+/// the input is collected in memory before filling, and the memory is reserved
+/// from the memory pool of the task.
+#[derive(Debug)]
+pub struct GapFillExec {
+    input: Arc<dyn ExecutionPlan>,
+    schema: SchemaRef,
+    params: GapFillParams,
+}
+
+impl GapFillExec {
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        params: GapFillParams,
+    ) -> DfResult<Self> {
+        let input_schema = input.schema();
+        let time_type = input_schema.field(params.time_column).data_type();
+        if !matches!(time_type, DataType::Timestamp(TimeUnit::Millisecond, _)) {
+            return Err(DataFusionError::Plan(format!(
+                "Gap fill requires a millisecond timestamp column, type:{time_type}"
+            )));
+        }
+        for (idx, strategy) in &params.fill_columns {
+            let data_type = input_schema.field(*idx).data_type();
+            if *strategy == FillStrategy::Interpolate && !data_type.is_numeric() {
+                return Err(DataFusionError::Plan(format!(
+                    "Interpolate only supports numeric column, column:{}, type:{data_type}",
+                    input_schema.field(*idx).name()
+                )));
+            }
+        }
+
+        Ok(Self {
+            input,
+            schema,
+            params,
+        })
+    }
+}
+
+impl ExecutionPlan for GapFillExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        match children.len() {
+            1 => Ok(Arc::new(GapFillExec {
+                input: children[0].clone(),
+                schema: self.schema.clone(),
+                params: self.params.clone(),
+            })),
+            _ => Err(DataFusionError::Internal(
+                "GapFillExec wrong number of children".to_string(),
+            )),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DfResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "GapFillExec invalid partition:{partition}"
+            )));
+        }
+
+        let input: Arc<dyn ExecutionPlan> =
+            if self.input.output_partitioning().partition_count() > 1 {
+                Arc::new(CoalescePartitionsExec::new(self.input.clone()))
+            } else {
+                self.input.clone()
+            };
+        let input_schema = input.schema();
+        let input = input.execute(0, context.clone())?;
+        let mut reservation = MemoryConsumer::new("GapFillExec").register(context.memory_pool());
+        let schema = self.schema.clone();
+        let params = self.params.clone();
+        let fill = async move {
+            let batches = collect_with_reservation(input, &mut reservation).await?;
+            let batch = concat_batches(&input_schema, &batches)?;
+            fill_gaps(&batch, schema, &params)
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            stream::once(fill),
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for GapFillExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "GapFillExec: time_column={}, range=[{}, {}), series_columns={:?}, fill_columns={:?}",
+            self.params.time_column,
+            self.params.start,
+            self.params.end,
+            self.params.series_columns,
+            self.params.fill_columns,
+        )
+    }
+}
+
+/// A row of the output.
+struct OutputRow {
+    /// Index of the first input row of the series.
+    series_row: usize,
+    /// Index of the input row, `None` if it is a gap.
+    input_row: Option<usize>,
+    time: i64,
+}
+
+fn fill_gaps(
+    batch: &RecordBatch,
+    schema: SchemaRef,
+    params: &GapFillParams,
+) -> DfResult<RecordBatch> {
+    let times = cast(
+        batch.column(params.time_column),
+        &DataType::Timestamp(TimeUnit::Millisecond, None),
+    )?;
+    let times = times
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .expect("cast to timestamp array");
+
+    // Sort the rows by series and time.
+    let series_keys = if params.series_columns.is_empty() {
+        None
+    } else {
+        let series_columns: Vec<_> = params
+            .series_columns
+            .iter()
+            .map(|idx| batch.column(*idx).clone())
+            .collect();
+        let sort_fields = series_columns
+            .iter()
+            .map(|col| SortField::new(col.data_type().clone()))
+            .collect();
+        let converter = RowConverter::new(sort_fields)?;
+        Some(converter.convert_columns(&series_columns)?)
+    };
+    let cmp_series = |a: usize, b: usize| match &series_keys {
+        Some(keys) => keys.row(a).cmp(&keys.row(b)),
+        None => Ordering::Equal,
+    };
+    let mut rows: Vec<_> = (0..batch.num_rows())
+        .filter(|idx| times.is_valid(*idx))
+        .collect();
+    rows.sort_unstable_by(|a, b| {
+        cmp_series(*a, *b).then_with(|| times.value(*a).cmp(&times.value(*b)))
+    });
+
+    let mut output_rows = Vec::with_capacity(rows.len());
+    let first_bucket = params.period.truncate(params.start);
+    let next_bucket = |bucket: i64| {
+        bucket
+            .checked_add(params.period.stride_ms())
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!("Gap fill bucket overflow, bucket:{bucket}"))
+            })
+    };
+    let mut series_start = 0;
+    while series_start < rows.len() {
+        let series_row = rows[series_start];
+        let series_end = rows[series_start..]
+            .iter()
+            .position(|idx| cmp_series(*idx, series_row) != Ordering::Equal)
+            .map_or(rows.len(), |n| series_start + n);
+        let series = &rows[series_start..series_end];
+
+        let mut next = 0;
+        let mut bucket = first_bucket;
+        while bucket < params.end || next < series.len() {
+            let time = series.get(next).map(|idx| times.value(*idx));
+            let ord = match time {
+                Some(time) if bucket < params.end => time.cmp(&bucket),
+                Some(_) => Ordering::Less,
+                None => Ordering::Greater,
+            };
+            match ord {
+                // Rows not aligned to the buckets are kept as is.
+                Ordering::Less => {
+                    output_rows.push(OutputRow {
+                        series_row,
+                        input_row: Some(series[next]),
+                        time: time.unwrap(),
+                    });
+                    next += 1;
+                }
+                Ordering::Equal => {
+                    output_rows.push(OutputRow {
+                        series_row,
+                        input_row: Some(series[next]),
+                        time: bucket,
+                    });
+                    next += 1;
+                    bucket = next_bucket(bucket)?;
+                }
+                Ordering::Greater => {
+                    if output_rows.len() >= MAX_GAP_FILL_ROWS {
+                        return Err(DataFusionError::ResourcesExhausted(format!(
+                            "Too many rows to fill, max:{MAX_GAP_FILL_ROWS}"
+                        )));
+                    }
+                    output_rows.push(OutputRow {
+                        series_row,
+                        input_row: None,
+                        time: bucket,
+                    });
+                    bucket = next_bucket(bucket)?;
+                }
+            }
+        }
+
+        series_start = series_end;
+    }
+
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    for (idx, column) in batch.columns().iter().enumerate() {
+        let output = if idx == params.time_column {
+            let times: TimestampMillisecondArray =
+                output_rows.iter().map(|row| Some(row.time)).collect();
+            cast(&times, column.data_type())?
+        } else if params.series_columns.contains(&idx) {
+            let indices: UInt64Array = output_rows
+                .iter()
+                .map(|row| Some(row.series_row as u64))
+                .collect();
+            take(column.as_ref(), &indices, None)?
+        } else {
+            let strategy = params
+                .fill_columns
+                .iter()
+                .find(|(col, _)| *col == idx)
+                .map_or(FillStrategy::Null, |(_, strategy)| *strategy);
+            fill_column(column, &output_rows, strategy)?
+        };
+        columns.push(output);
+    }
+
+    RecordBatch::try_new(schema, columns).map_err(DataFusionError::from)
+}
+
+fn fill_column(
+    column: &ArrayRef,
+    output_rows: &[OutputRow],
+    strategy: FillStrategy,
+) -> DfResult<ArrayRef> {
+    let is_valid = |row: &OutputRow| row.input_row.map_or(false, |idx| column.is_valid(idx));
+
+    match strategy {
+        FillStrategy::Null => {
+            let indices: UInt64Array = output_rows
+                .iter()
+                .map(|row| row.input_row.map(|idx| idx as u64))
+                .collect();
+            Ok(take(column.as_ref(), &indices, None)?)
+        }
+        FillStrategy::Locf => {
+            let mut last = None;
+            let mut last_series = None;
+            let indices: UInt64Array = output_rows
+                .iter()
+                .map(|row| {
+                    if last_series != Some(row.series_row) {
+                        last_series = Some(row.series_row);
+                        last = None;
+                    }
+                    if is_valid(row) {
+                        last = row.input_row.map(|idx| idx as u64);
+                    }
+                    last
+                })
+                .collect();
+            Ok(take(column.as_ref(), &indices, None)?)
+        }
+        FillStrategy::Interpolate => {
+            let values = cast(column, &DataType::Float64)?;
+            let values = values
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .expect("cast to float64 array");
+            let value_of = |row: &OutputRow| {
+                row.input_row
+                    .filter(|idx| values.is_valid(*idx))
+                    .map(|idx| (row.time, values.value(idx)))
+            };
+
+            let mut filled = Vec::with_capacity(output_rows.len());
+            for (pos, row) in output_rows.iter().enumerate() {
+                if let Some((_, value)) = value_of(row) {
+                    filled.push(Some(value));
+                    continue;
+                }
+
+                let same_series = |r: &&OutputRow| r.series_row == row.series_row;
+                let prev = output_rows[..pos]
+                    .iter()
+                    .rev()
+                    .take_while(same_series)
+                    .find_map(value_of);
+                let next = output_rows[pos + 1..]
+                    .iter()
+                    .take_while(same_series)
+                    .find_map(value_of);
+                let value = match (prev, next) {
+                    (Some((t0, v0)), Some((t1, v1))) if t1 > t0 => {
+                        Some(v0 + (v1 - v0) * (row.time - t0) as f64 / (t1 - t0) as f64)
+                    }
+                    _ => None,
+                };
+                filled.push(value);
+            }
+
+            let filled: Float64Array = filled.into_iter().collect();
+            Ok(cast(&filled, column.data_type())?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::StringArray,
+        datatypes::{Field, Schema},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_fill_gaps() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new("host", DataType::Utf8, false),
+            Field::new("null", DataType::Float64, true),
+            Field::new("locf", DataType::Float64, true),
+            Field::new("interpolate", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(TimestampMillisecondArray::from(vec![3000, 0, 3000])),
+                Arc::new(StringArray::from(vec!["a", "a", "b"])),
+                Arc::new(Float64Array::from(vec![3.0, 0.0, 3.0])),
+                Arc::new(Float64Array::from(vec![3.0, 0.0, 3.0])),
+                Arc::new(arrow::array::Int64Array::from(vec![30, 0, 30])),
+            ],
+        )
+        .unwrap();
+        let params = GapFillParams {
+            time_column: 0,
+            period: GapFillPeriod::parse("PT1S").unwrap(),
+            start: 0,
+            end: 4000,
+            series_columns: vec![1],
+            fill_columns: vec![
+                (2, FillStrategy::Null),
+                (3, FillStrategy::Locf),
+                (4, FillStrategy::Interpolate),
+            ],
+        };
+
+        let output = fill_gaps(&batch, schema, &params).unwrap();
+        let expected = vec![
+            "+---------------------+------+------+------+-------------+",
+            "| time                | host | null | locf | interpolate |",
+            "+---------------------+------+------+------+-------------+",
+            "| 1970-01-01T00:00:00 | a    | 0.0  | 0.0  | 0           |",
+            "| 1970-01-01T00:00:01 | a    |      | 0.0  | 10          |",
+            "| 1970-01-01T00:00:02 | a    |      | 0.0  | 20          |",
+            "| 1970-01-01T00:00:03 | a    | 3.0  | 3.0  | 30          |",
+            "| 1970-01-01T00:00:00 | b    |      |      |             |",
+            "| 1970-01-01T00:00:01 | b    |      |      |             |",
+            "| 1970-01-01T00:00:02 | b    |      |      |             |",
+            "| 1970-01-01T00:00:03 | b    | 3.0  | 3.0  | 30          |",
+            "+---------------------+------+------+------+-------------+",
+        ];
+        let actual = arrow::util::pretty::pretty_format_batches(&[output])
+            .unwrap()
+            .to_string();
+        assert_eq!(expected.join("\n"), actual);
+    }
+
+    #[test]
+    fn test_fill_gaps_overflow() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "time",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            false,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(TimestampMillisecondArray::from(vec![
+                i64::MAX - 1,
+            ]))],
+        )
+        .unwrap();
+        let params = GapFillParams {
+            time_column: 0,
+            period: GapFillPeriod::parse("PT1S").unwrap(),
+            start: i64::MAX - 1,
+            end: i64::MAX,
+            series_columns: vec![],
+            fill_columns: vec![],
+        };
+
+        assert!(fill_gaps(&batch, schema, &params).is_err());
+    }
+}
@@ -15,5 +15,84 @@
 // specific language governing permissions and limitations
 // under the License.
 
+pub mod asof_join;
+pub mod gap_fill;
 pub mod prom_align;
+use arrow::record_batch::RecordBatch;
+pub use asof_join::AsofJoinExec;
+use datafusion::{
+    error::Result as DfResult, execution::memory_pool::MemoryReservation,
+    physical_plan::SendableRecordBatchStream,
+};
+use futures::StreamExt;
+pub use gap_fill::GapFillExec;
 pub use prom_align::PromAlignExec;
+
+/// Collect the batches of the stream into memory, the memory of the batches is
+/// reserved by the `reservation` and the collection fails once the memory pool
+/// is exhausted.
+async fn collect_with_reservation(
+    mut stream: SendableRecordBatchStream,
+    reservation: &mut MemoryReservation,
+) -> DfResult<Vec<RecordBatch>> {
+    let mut batches = Vec::new();
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        reservation.try_grow(batch.get_array_memory_size())?;
+        batches.push(batch);
+    }
+
+    Ok(batches)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::Int32Array,
+        datatypes::{DataType, Field, Schema},
+    };
+    use datafusion::{
+        error::DataFusionError,
+        execution::{
+            context::TaskContext,
+            memory_pool::{GreedyMemoryPool, MemoryConsumer, MemoryPool},
+        },
+        physical_plan::{memory::MemoryExec, ExecutionPlan},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_collect_with_reservation() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+        )
+        .unwrap();
+        let batch_size = batch.get_array_memory_size();
+        let exec = MemoryExec::try_new(&[vec![batch; 2]], schema, None).unwrap();
+
+        let pool: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(batch_size * 2));
+        let mut reservation = MemoryConsumer::new("test").register(&pool);
+        let stream = exec.execute(0, Arc::new(TaskContext::default())).unwrap();
+        let batches = collect_with_reservation(stream, &mut reservation)
+            .await
+            .unwrap();
+        assert_eq!(2, batches.len());
+        assert_eq!(batch_size * 2, reservation.size());
+
+        // The pool is exhausted by the batches collected above.
+        let mut reservation = MemoryConsumer::new("test").register(&pool);
+        let stream = exec.execute(0, Arc::new(TaskContext::default())).unwrap();
+        let err = collect_with_reservation(stream, &mut reservation)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DataFusionError::ResourcesExhausted(_)),
+            "err:{err}"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::{
+    error::Result as DfResult,
+    execution::context::SessionState,
+    logical_expr::logical_plan::{LogicalPlan, UserDefinedLogicalNode},
+    physical_plan::ExecutionPlan,
+    physical_planner::{ExtensionPlanner, PhysicalPlanner},
+};
+use query_frontend::asof_join::AsofJoinNode;
+
+use crate::datafusion_impl::physical_plan_extension::asof_join::{AsofJoinExec, AsofJoinParams};
+
+pub struct AsofJoinPlanner;
+
+#[async_trait]
+impl ExtensionPlanner for AsofJoinPlanner {
+    async fn plan_extension(
+        &self,
+        _planner: &dyn PhysicalPlanner,
+        node: &dyn UserDefinedLogicalNode,
+        logical_inputs: &[&LogicalPlan],
+        physical_inputs: &[Arc<dyn ExecutionPlan>],
+        _session_state: &SessionState,
+    ) -> DfResult<Option<Arc<dyn ExecutionPlan>>> {
+        let node = match node.as_any().downcast_ref::<AsofJoinNode>() {
+            Some(node) => node,
+            None => return Ok(None),
+        };
+        assert_eq!(logical_inputs.len(), 2, "Inconsistent number of inputs");
+        assert_eq!(physical_inputs.len(), 2, "Inconsistent number of inputs");
+
+        let left_schema = logical_inputs[0].schema();
+        let right_schema = logical_inputs[1].schema();
+        let on = node
+            .on
+            .iter()
+            .map(|(left, right)| {
+                Ok((
+                    left_schema.index_of_column(left)?,
+                    right_schema.index_of_column(right)?,
+                ))
+            })
+            .collect::<DfResult<_>>()?;
+        let params = AsofJoinParams {
+            join_type: node.join_type,
+            on,
+            left_time: left_schema.index_of_column(&node.time_condition.left)?,
+            right_time: right_schema.index_of_column(&node.time_condition.right)?,
+            op: node.time_condition.op,
+        };
+
+        let exec = AsofJoinExec::try_new(
+            physical_inputs[0].clone(),
+            physical_inputs[1].clone(),
+            Arc::new(node.schema.as_ref().into()),
+            params,
+        )?;
+        Ok(Some(Arc::new(exec)))
+    }
+}
@@ -0,0 +1,80 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::{
+    error::{DataFusionError, Result as DfResult},
+    execution::context::SessionState,
+    logical_expr::logical_plan::{LogicalPlan, UserDefinedLogicalNode},
+    physical_plan::ExecutionPlan,
+    physical_planner::{ExtensionPlanner, PhysicalPlanner},
+};
+use df_operator::udfs::gapfill::GapFillPeriod;
+use query_frontend::gap_fill::GapFillNode;
+
+use crate::datafusion_impl::physical_plan_extension::gap_fill::{GapFillExec, GapFillParams};
+
+pub struct GapFillPlanner;
+
+#[async_trait]
+impl ExtensionPlanner for GapFillPlanner {
+    async fn plan_extension(
+        &self,
+        _planner: &dyn PhysicalPlanner,
+        node: &dyn UserDefinedLogicalNode,
+        logical_inputs: &[&LogicalPlan],
+        physical_inputs: &[Arc<dyn ExecutionPlan>],
+        _session_state: &SessionState,
+    ) -> DfResult<Option<Arc<dyn ExecutionPlan>>> {
+        let node = match node.as_any().downcast_ref::<GapFillNode>() {
+            Some(node) => node,
+            None => return Ok(None),
+        };
+        assert_eq!(logical_inputs.len(), 1, "Inconsistent number of inputs");
+        assert_eq!(physical_inputs.len(), 1, "Inconsistent number of inputs");
+
+        let input_schema = logical_inputs[0].schema();
+        let series_columns = node
+            .series_columns
+            .iter()
+            .map(|col| input_schema.index_of_column(col))
+            .collect::<DfResult<_>>()?;
+        let fill_columns = node
+            .fill_columns
+            .iter()
+            .map(|(col, strategy)| Ok((input_schema.index_of_column(col)?, *strategy)))
+            .collect::<DfResult<_>>()?;
+        let params = GapFillParams {
+            time_column: input_schema.index_of_column(&node.time_column)?,
+            period: GapFillPeriod::parse(&node.period)
+                .map_err(|e| DataFusionError::Plan(e.to_string()))?,
+            start: node.start,
+            end: node.end,
+            series_columns,
+            fill_columns,
+        };
+
+        let exec = GapFillExec::try_new(
+            physical_inputs[0].clone(),
+            Arc::new(node.schema.as_ref().into()),
+            params,
+        )?;
+        Ok(Some(Arc::new(exec)))
+    }
+}
@@ -26,6 +26,8 @@ use datafusion::{
     physical_planner::{DefaultPhysicalPlanner, ExtensionPlanner, PhysicalPlanner},
 };
 
+pub mod asof_join;
+pub mod gap_fill;
 pub mod prom_align;
 use async_trait::async_trait;
 
@@ -41,6 +43,8 @@ impl QueryPlanner for QueryPlannerAdapter {
     ) -> datafusion::error::Result<Arc<dyn ExecutionPlan>> {
         let extension_planners: Vec<Arc<dyn ExtensionPlanner + Send + Sync>> = vec![
             Arc::new(prom_align::PromAlignPlanner),
+            Arc::new(gap_fill::GapFillPlanner),
+            Arc::new(asof_join::AsofJoinPlanner),
             Arc::new(influxql_query::exec::context::IOxExtensionPlanner {}),
         ];
 
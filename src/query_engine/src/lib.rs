@@ -24,6 +24,7 @@ pub mod context;
 pub mod datafusion_impl;
 pub mod error;
 pub mod executor;
+pub mod limits;
 pub mod physical_planner;
 use std::{fmt, sync::Arc};
 
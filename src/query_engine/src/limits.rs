@@ -0,0 +1,148 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Limits of a single query
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use size_ext::ReadableSize;
+use snafu::Snafu;
+use time_ext::ReadableDuration;
+
+/// Errors raised when the query exceeds its limits, the query is aborted.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum Error {
+    #[snafu(display("Query exceeds the max execution time, limit:{limit:?}"))]
+    ExecutionTimeExceeded { limit: Duration },
+
+    #[snafu(display("Query exceeds the max scanned rows, limit:{limit}"))]
+    ScannedRowsExceeded { limit: u64 },
+
+    #[snafu(display("Query exceeds the max scanned bytes, limit:{limit}"))]
+    ScannedBytesExceeded { limit: u64 },
+
+    #[snafu(display("Query exceeds the max result rows, limit:{limit}"))]
+    ResultRowsExceeded { limit: u64 },
+}
+
+/// Limits of a single query, unlimited if not set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct QueryLimits {
+    pub max_execution_time: Option<ReadableDuration>,
+    pub max_scanned_rows: Option<u64>,
+    /// Max bytes of the scanned record batches in memory
+    pub max_scanned_bytes: Option<ReadableSize>,
+    pub max_result_rows: Option<u64>,
+}
+
+impl QueryLimits {
+    /// Fill the limits not set by `self` with the ones of `fallback`.
+    pub fn or(self, fallback: QueryLimits) -> QueryLimits {
+        QueryLimits {
+            max_execution_time: self.max_execution_time.or(fallback.max_execution_time),
+            max_scanned_rows: self.max_scanned_rows.or(fallback.max_scanned_rows),
+            max_scanned_bytes: self.max_scanned_bytes.or(fallback.max_scanned_bytes),
+            max_result_rows: self.max_result_rows.or(fallback.max_result_rows),
+        }
+    }
+
+    /// Combine the limits with `other`, the stricter one is taken if both are
+    /// set, so the result is never looser than any of them.
+    pub fn min(self, other: QueryLimits) -> QueryLimits {
+        QueryLimits {
+            max_execution_time: min_limit(self.max_execution_time, other.max_execution_time),
+            max_scanned_rows: min_limit(self.max_scanned_rows, other.max_scanned_rows),
+            max_scanned_bytes: min_limit(self.max_scanned_bytes, other.max_scanned_bytes),
+            max_result_rows: min_limit(self.max_result_rows, other.max_result_rows),
+        }
+    }
+
+    #[inline]
+    pub fn limit_scan(&self) -> bool {
+        self.max_scanned_rows.is_some() || self.max_scanned_bytes.is_some()
+    }
+}
+
+fn min_limit<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b < a { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_limits_or() {
+        let request_limits = QueryLimits {
+            max_result_rows: Some(10),
+            ..Default::default()
+        };
+        let schema_limits = QueryLimits {
+            max_execution_time: Some(ReadableDuration::secs(30)),
+            max_result_rows: Some(1000),
+            ..Default::default()
+        };
+
+        let limits = request_limits.or(schema_limits);
+        assert_eq!(
+            QueryLimits {
+                max_execution_time: Some(ReadableDuration::secs(30)),
+                max_scanned_rows: None,
+                max_scanned_bytes: None,
+                max_result_rows: Some(10),
+            },
+            limits
+        );
+        assert!(!limits.limit_scan());
+        assert_eq!(
+            QueryLimits::default(),
+            QueryLimits::default().or(QueryLimits::default())
+        );
+    }
+
+    #[test]
+    fn test_query_limits_min() {
+        let request_limits = QueryLimits {
+            max_execution_time: Some(ReadableDuration::secs(60)),
+            max_scanned_bytes: Some(ReadableSize::mb(1)),
+            max_result_rows: Some(10),
+            ..Default::default()
+        };
+        let schema_limits = QueryLimits {
+            max_execution_time: Some(ReadableDuration::secs(30)),
+            max_scanned_rows: Some(100),
+            max_result_rows: Some(1000),
+            ..Default::default()
+        };
+
+        let expected = QueryLimits {
+            max_execution_time: Some(ReadableDuration::secs(30)),
+            max_scanned_rows: Some(100),
+            max_scanned_bytes: Some(ReadableSize::mb(1)),
+            max_result_rows: Some(10),
+        };
+        assert_eq!(expected, request_limits.min(schema_limits));
+        assert_eq!(expected, schema_limits.min(request_limits));
+        assert_eq!(schema_limits, QueryLimits::default().min(schema_limits));
+    }
+}
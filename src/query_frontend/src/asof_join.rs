@@ -0,0 +1,311 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Logical plan of `ASOF JOIN`.
+//!
+//! Every row of the left input is joined with the row of the right input
+//! having the same values of the equal columns and the nearest time satisfying
+//! the time condition, e.g. the latest right row not later than the left row
+//! for `a.ts >= b.ts`.
+
+use std::{any::Any, fmt, hash::Hash, sync::Arc};
+
+use datafusion::{
+    common::{
+        tree_node::{Transformed, TreeNode},
+        Column, DFSchema, DFSchemaRef,
+    },
+    config::ConfigOptions,
+    error::{DataFusionError, Result},
+    logical_expr::{
+        logical_plan::Extension, utils::split_conjunction, BinaryExpr, Expr, Join, JoinType,
+        LogicalPlan, Operator, UserDefinedLogicalNode,
+    },
+    optimizer::analyzer::AnalyzerRule,
+};
+use df_operator::udfs::asof_join::ASOF_JOIN_CONDITION;
+
+use crate::gap_fill::as_udf_call;
+
+#[derive(Clone, PartialEq, Hash)]
+pub struct AsofJoinNode {
+    pub left: LogicalPlan,
+    pub right: LogicalPlan,
+    pub schema: DFSchemaRef,
+    /// Only inner and left joins are supported.
+    pub join_type: JoinType,
+    /// Equal columns of the left and right inputs, usually the tags.
+    pub on: Vec<(Column, Column)>,
+    pub time_condition: TimeCondition,
+}
+
+/// The condition comparing the time of the left input to the right input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimeCondition {
+    pub left: Column,
+    /// One of `>=`, `>`, `<=` and `<`.
+    pub op: Operator,
+    pub right: Column,
+}
+
+impl fmt::Debug for AsofJoinNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_for_explain(f)
+    }
+}
+
+impl UserDefinedLogicalNode for AsofJoinNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "AsofJoin"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.left, &self.right]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        let mut exprs = Vec::with_capacity(self.on.len() * 2 + 2);
+        for (left, right) in &self.on {
+            exprs.push(Expr::Column(left.clone()));
+            exprs.push(Expr::Column(right.clone()));
+        }
+        exprs.push(Expr::Column(self.time_condition.left.clone()));
+        exprs.push(Expr::Column(self.time_condition.right.clone()));
+        exprs
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AsofJoin: type={}, on={:?}, time_condition={} {} {}",
+            self.join_type,
+            self.on,
+            self.time_condition.left,
+            self.time_condition.op,
+            self.time_condition.right,
+        )
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[Expr],
+        inputs: &[LogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode> {
+        let mut node = self.clone();
+        node.left = inputs[0].clone();
+        node.right = inputs[1].clone();
+        Arc::new(node)
+    }
+
+    fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+        let mut s = state;
+        self.hash(&mut s);
+    }
+
+    fn dyn_eq(&self, other: &dyn UserDefinedLogicalNode) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(o) => self == o,
+            None => false,
+        }
+    }
+}
+
+/// Analyzer rule planning the joins whose conditions are marked by
+/// `asof_join_condition` into [AsofJoinNode].
+pub(crate) struct AsofJoinRule;
+
+impl AnalyzerRule for AsofJoinRule {
+    fn analyze(&self, plan: LogicalPlan, _config: &ConfigOptions) -> Result<LogicalPlan> {
+        plan.transform_up(&|plan| match &plan {
+            LogicalPlan::Join(join) => match plan_asof_join(join)? {
+                Some(node) => Ok(Transformed::Yes(LogicalPlan::Extension(Extension {
+                    node: Arc::new(node),
+                }))),
+                None => Ok(Transformed::No(plan)),
+            },
+            _ => Ok(Transformed::No(plan)),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "asof_join"
+    }
+}
+
+fn plan_asof_join(join: &Join) -> Result<Option<AsofJoinNode>> {
+    let condition = match join
+        .filter
+        .as_ref()
+        .and_then(|filter| as_udf_call(filter, ASOF_JOIN_CONDITION))
+    {
+        Some(args) => &args[0],
+        None => return Ok(None),
+    };
+    if !matches!(join.join_type, JoinType::Inner | JoinType::Left) || !join.on.is_empty() {
+        return Err(DataFusionError::Plan(format!(
+            "Unsupported ASOF JOIN, join_type:{}",
+            join.join_type
+        )));
+    }
+
+    let (on, time_condition) =
+        split_asof_condition(condition, join.left.schema(), join.right.schema())?;
+
+    Ok(Some(AsofJoinNode {
+        left: join.left.as_ref().clone(),
+        right: join.right.as_ref().clone(),
+        schema: join.schema.clone(),
+        join_type: join.join_type,
+        on,
+        time_condition,
+    }))
+}
+
+/// Split the condition into the equal columns and the time condition, the
+/// condition must be a conjunction of comparisons between the columns of the
+/// two inputs, and exactly one of them is an inequality.
+fn split_asof_condition(
+    condition: &Expr,
+    left_schema: &DFSchema,
+    right_schema: &DFSchema,
+) -> Result<(Vec<(Column, Column)>, TimeCondition)> {
+    let unsupported = |expr: &Expr| {
+        Err(DataFusionError::Plan(format!(
+            "Unsupported ASOF JOIN condition, expr:{expr}"
+        )))
+    };
+
+    let mut on = Vec::new();
+    let mut time_condition = None;
+    for expr in split_conjunction(condition) {
+        let (left, op, right) = match expr {
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                match (left.as_ref(), right.as_ref()) {
+                    (Expr::Column(left), Expr::Column(right)) => (left, *op, right),
+                    _ => return unsupported(expr),
+                }
+            }
+            _ => return unsupported(expr),
+        };
+
+        // Make the left column belong to the left input.
+        let (left, op, right) = if left_schema.has_column(left) && right_schema.has_column(right) {
+            (left, op, right)
+        } else if left_schema.has_column(right) && right_schema.has_column(left) {
+            match op.swap() {
+                Some(op) => (right, op, left),
+                None => return unsupported(expr),
+            }
+        } else {
+            return unsupported(expr);
+        };
+
+        match op {
+            Operator::Eq => on.push((left.clone(), right.clone())),
+            Operator::GtEq | Operator::Gt | Operator::LtEq | Operator::Lt => {
+                if time_condition.is_some() {
+                    return Err(DataFusionError::Plan(format!(
+                        "ASOF JOIN requires exactly one inequality condition, condition:{condition}"
+                    )));
+                }
+                time_condition = Some(TimeCondition {
+                    left: left.clone(),
+                    op,
+                    right: right.clone(),
+                });
+            }
+            _ => return unsupported(expr),
+        }
+    }
+
+    match time_condition {
+        Some(v) => Ok((on, v)),
+        None => Err(DataFusionError::Plan(format!(
+            "ASOF JOIN requires exactly one inequality condition, condition:{condition}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::logical_expr::col;
+
+    use super::*;
+
+    fn build_schema(qualifier: &str) -> DFSchema {
+        let schema = Schema::new(vec![
+            Field::new("host", DataType::Utf8, false),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new("value", DataType::Float64, true),
+        ]);
+        DFSchema::try_from_qualified_schema(qualifier, &schema).unwrap()
+    }
+
+    #[test]
+    fn test_split_asof_condition() {
+        let left_schema = build_schema("cpu");
+        let right_schema = build_schema("power");
+
+        let condition = col("cpu.host")
+            .eq(col("power.host"))
+            .and(col("power.ts").lt_eq(col("cpu.ts")));
+        let (on, time_condition) =
+            split_asof_condition(&condition, &left_schema, &right_schema).unwrap();
+        assert_eq!(
+            vec![(Column::from("cpu.host"), Column::from("power.host"))],
+            on
+        );
+        assert_eq!(
+            TimeCondition {
+                left: Column::from("cpu.ts"),
+                op: Operator::GtEq,
+                right: Column::from("power.ts"),
+            },
+            time_condition
+        );
+
+        let invalid_conditions = vec![
+            col("cpu.host").eq(col("power.host")),
+            col("cpu.ts")
+                .gt(col("power.ts"))
+                .and(col("cpu.ts").lt(col("power.ts"))),
+            col("cpu.ts")
+                .gt_eq(col("power.ts"))
+                .and(col("cpu.value").not_eq(col("power.value"))),
+            col("cpu.ts")
+                .gt_eq(col("power.ts"))
+                .or(col("cpu.host").eq(col("power.host"))),
+            col("cpu.ts").gt_eq(col("cpu.ts")),
+        ];
+        for condition in invalid_conditions {
+            assert!(split_asof_condition(&condition, &left_schema, &right_schema).is_err());
+        }
+    }
+}
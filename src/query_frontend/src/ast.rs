@@ -38,7 +38,19 @@ pub enum Statement {
     ShowCreate(ShowCreate),
     ShowDatabases,
     ShowTables(ShowTables),
+    /// SHOW PROCESSLIST
+    ShowProcessList,
     Exists(ExistsTable),
+    /// KILL QUERY
+    KillQuery(KillQuery),
+    /// BACKUP TABLE
+    BackupTable(BackupTable),
+    /// RESTORE TABLE
+    RestoreTable(RestoreTable),
+    /// VERIFY TABLE
+    VerifyTable(VerifyTable),
+    /// Query reading some tables at a historical point
+    AsOfQuery(AsOfQuery),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -48,6 +60,15 @@ impl TableName {
     pub fn is_empty(&self) -> bool {
         self.0 .0.is_empty()
     }
+
+    /// The name without catalog and schema.
+    pub fn table_part(&self) -> &str {
+        self.0
+             .0
+            .last()
+            .map(|ident| ident.value.as_str())
+            .unwrap_or_default()
+    }
 }
 
 impl ToString for TableName {
@@ -159,6 +180,59 @@ pub struct ExistsTable {
     pub table_name: TableName,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct KillQuery {
+    /// Id of the query to kill, which is the request id of the query
+    pub query_id: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BackupTable {
+    pub table_name: TableName,
+    /// Location to store the backup
+    pub path: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RestoreTable {
+    /// Name of the table to create from the backup
+    pub table_name: TableName,
+    /// Location of the backup
+    pub path: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifyTable {
+    pub table_name: TableName,
+    /// Whether to repair the issues found
+    pub repair: bool,
+}
+
+/// The historical point to read a table at.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AsOf {
+    /// `AS OF SEQUENCE <sequence>`
+    Sequence(u64),
+    /// `AS OF TIMESTAMP <timestamp>`, the timestamp is either milliseconds or
+    /// a datetime string.
+    Timestamp(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TableAsOf {
+    pub table_name: TableName,
+    pub as_of: AsOf,
+}
+
+/// A query with `AS OF` clauses, such as `SELECT * FROM t AS OF SEQUENCE 10`.
+///
+/// The `AS OF` clauses are removed from the standard statement.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AsOfQuery {
+    pub statement: Box<SqlStatement>,
+    pub tables: Vec<TableAsOf>,
+}
+
 #[cfg(test)]
 mod tests {
     use sqlparser::ast::Ident;
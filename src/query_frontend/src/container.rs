@@ -24,7 +24,7 @@ use datafusion::datasource::TableProvider;
 use partition_table_engine::scan_builder::PartitionedTableScanBuilder;
 use table_engine::{
     provider::{NormalTableScanBuilder, TableProviderAdapter},
-    table::TableRef,
+    table::{ReadAsOf, TableRef},
 };
 
 #[derive(Debug, Clone)]
@@ -33,6 +33,8 @@ pub struct PlannedTable {
     pub schema: String,
     pub table: TableRef,
     pub enable_dist_query_push_down: bool,
+    /// Read the table at a historical point.
+    pub as_of: Option<ReadAsOf>,
 }
 
 impl PlannedTable {
@@ -46,11 +48,11 @@ impl PlannedTable {
                 partition_info,
             );
 
-            Arc::new(TableProviderAdapter::new(self.table.clone(), builder))
+            Arc::new(TableProviderAdapter::new(self.table.clone(), builder).with_as_of(self.as_of))
         } else {
             let builder = NormalTableScanBuilder::new(self.table.clone());
 
-            Arc::new(TableProviderAdapter::new(self.table.clone(), builder))
+            Arc::new(TableProviderAdapter::new(self.table.clone(), builder).with_as_of(self.as_of))
         }
     }
 }
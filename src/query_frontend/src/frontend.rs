@@ -23,7 +23,6 @@ use cluster::config::SchemaConfig;
 use common_types::request_id::RequestId;
 use generic_error::GenericError;
 use horaedbproto::{prometheus::Expr as PromExpr, storage::WriteTableRequest};
-use influxql_parser::statement::Statement as InfluxqlStatement;
 use macros::define_result;
 use prom_remote_api::types::Query as PromRemoteQuery;
 use snafu::{Backtrace, OptionExt, ResultExt, Snafu};
@@ -33,6 +32,7 @@ use table_engine::table;
 use crate::{
     ast::{Statement, TableName},
     config::DynamicConfig,
+    influxql::{self, parser::Statement as InfluxqlStatement},
     parser::Parser,
     plan::Plan,
     planner::Planner,
@@ -132,7 +132,7 @@ impl<P> Frontend<P> {
 
     /// Parse the sql and returns the statements
     pub fn parse_influxql(&self, _ctx: &Context, influxql: &str) -> Result<Vec<InfluxqlStatement>> {
-        match influxql_parser::parse_statements(influxql) {
+        match influxql::parser::parse_statements(influxql) {
             Ok(stmts) => Ok(stmts),
             Err(e) => Err(Error::InvalidInfluxql {
                 influxql: influxql.to_string(),
@@ -270,7 +270,13 @@ pub fn parse_table_name(statements: &StatementVec) -> Option<String> {
         Statement::ShowCreate(s) => Some(s.table_name.to_string()),
         Statement::ShowTables(_s) => None,
         Statement::ShowDatabases => None,
+        Statement::ShowProcessList => None,
         Statement::Exists(s) => Some(s.table_name.to_string()),
+        Statement::KillQuery(_) => None,
+        Statement::BackupTable(s) => Some(s.table_name.to_string()),
+        Statement::RestoreTable(s) => Some(s.table_name.to_string()),
+        Statement::VerifyTable(s) => Some(s.table_name.to_string()),
+        Statement::AsOfQuery(s) => parse_table_name_with_standard(&s.statement),
     }
 }
 
@@ -0,0 +1,495 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Gap filling of the time series aggregations.
+//!
+//! A query like:
+//! ```sql
+//! SELECT time_bucket_gapfill(ts, 'PT1M') AS bucket, host, locf(avg(value))
+//! FROM t
+//! WHERE ts >= '2023-07-01 00:00:00' AND ts < '2023-07-01 01:00:00'
+//! GROUP BY bucket, host
+//! ```
+//! will be planned as:
+//! ```plaintext
+//! Projection: bucket, host, AVG(t.value) AS locf(AVG(t.value))
+//!   GapFill: time_column=bucket, series_columns=[host], ...
+//!     Aggregate: groupBy=[[time_bucket_gapfill(t.ts, "PT1M"), t.host]], aggr=[[AVG(t.value)]]
+//!       Filter: ...
+//! ```
+
+use std::{any::Any, fmt, hash::Hash, sync::Arc};
+
+use datafusion::{
+    common::{
+        tree_node::{Transformed, TreeNode},
+        Column, DFField, DFSchema, DFSchemaRef,
+    },
+    config::ConfigOptions,
+    error::{DataFusionError, Result},
+    logical_expr::{
+        expr::{Alias, ScalarUDF},
+        logical_plan::Extension,
+        utils::split_conjunction,
+        Aggregate, Between, BinaryExpr, Expr, Filter, LogicalPlan, Operator, Projection,
+        UserDefinedLogicalNode,
+    },
+    optimizer::analyzer::AnalyzerRule,
+    scalar::ScalarValue,
+};
+use df_operator::udfs::gapfill::{GapFillPeriod, INTERPOLATE, LOCF, TIME_BUCKET_GAPFILL};
+
+/// Max number of the buckets in the time range of a series.
+pub const MAX_GAP_FILL_BUCKETS: i64 = 100_000;
+
+/// Strategy to fill the aggregated value of the gaps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FillStrategy {
+    Null,
+    /// Last observation carried forward.
+    Locf,
+    /// Linear interpolation between the neighbouring values.
+    Interpolate,
+}
+
+/// Logical plan node emitting a row per bucket for every series in the time
+/// range, the missing rows are filled according to the [FillStrategy].
+#[derive(Clone, PartialEq, Hash)]
+pub struct GapFillNode {
+    pub input: LogicalPlan,
+    /// Schema of the input with the filled columns made nullable.
+    pub schema: DFSchemaRef,
+    /// Column of the time buckets.
+    pub time_column: Column,
+    /// Period of the time buckets, e.g. `PT1M`.
+    pub period: String,
+    /// Inclusive start of the time range in millis.
+    pub start: i64,
+    /// Exclusive end of the time range in millis.
+    pub end: i64,
+    /// Columns identifying a series.
+    pub series_columns: Vec<Column>,
+    /// Columns of the aggregated values.
+    pub fill_columns: Vec<(Column, FillStrategy)>,
+}
+
+impl GapFillNode {
+    fn try_new(
+        input: LogicalPlan,
+        time_column: Column,
+        period: String,
+        (start, end): (i64, i64),
+        series_columns: Vec<Column>,
+        fill_columns: Vec<(Column, FillStrategy)>,
+    ) -> Result<Self> {
+        let gap_fill_period =
+            GapFillPeriod::parse(&period).map_err(|e| DataFusionError::Plan(e.to_string()))?;
+        num_buckets(&gap_fill_period, start, end).ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "Too many buckets to fill, period:{period}, range:[{start}, {end}), max:{MAX_GAP_FILL_BUCKETS}"
+            ))
+        })?;
+
+        let input_schema = input.schema();
+        let fields = input_schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let is_fill_column = fill_columns
+                    .iter()
+                    .any(|(col, _)| *col == field.qualified_column());
+                if is_fill_column {
+                    DFField::new(
+                        field.qualifier().cloned(),
+                        field.name(),
+                        field.data_type().clone(),
+                        true,
+                    )
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        let schema = DFSchema::new_with_metadata(fields, input_schema.metadata().clone())?;
+
+        Ok(Self {
+            input,
+            schema: Arc::new(schema),
+            time_column,
+            period,
+            start,
+            end,
+            series_columns,
+            fill_columns,
+        })
+    }
+
+    fn with_fill_strategy(&self, column: &Column, strategy: FillStrategy) -> Self {
+        let mut node = self.clone();
+        for (col, s) in &mut node.fill_columns {
+            if col == column {
+                *s = strategy;
+            }
+        }
+        node
+    }
+}
+
+impl fmt::Debug for GapFillNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_for_explain(f)
+    }
+}
+
+impl UserDefinedLogicalNode for GapFillNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "GapFill"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        let mut exprs = vec![Expr::Column(self.time_column.clone())];
+        exprs.extend(self.series_columns.iter().cloned().map(Expr::Column));
+        exprs.extend(
+            self.fill_columns
+                .iter()
+                .map(|(col, _)| Expr::Column(col.clone())),
+        );
+        exprs
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "GapFill: time_column={}, period={}, range=[{}, {}), series_columns={:?}, fill_columns={:?}",
+            self.time_column, self.period, self.start, self.end, self.series_columns, self.fill_columns,
+        )
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[Expr],
+        inputs: &[LogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode> {
+        let mut node = self.clone();
+        node.input = inputs[0].clone();
+        Arc::new(node)
+    }
+
+    fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+        let mut s = state;
+        self.hash(&mut s);
+    }
+
+    fn dyn_eq(&self, other: &dyn UserDefinedLogicalNode) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(o) => self == o,
+            None => false,
+        }
+    }
+}
+
+/// Number of the buckets in `[start, end)`, `None` if it exceeds
+/// [MAX_GAP_FILL_BUCKETS] or the end of the last bucket overflows.
+fn num_buckets(period: &GapFillPeriod, start: i64, end: i64) -> Option<i64> {
+    let first_bucket = i128::from(period.truncate(start));
+    let (end, stride) = (i128::from(end), i128::from(period.stride_ms()));
+    let num_buckets = if end > first_bucket {
+        (end - first_bucket + stride - 1) / stride
+    } else {
+        0
+    };
+    let buckets_end = first_bucket + num_buckets * stride;
+    if num_buckets > i128::from(MAX_GAP_FILL_BUCKETS) || buckets_end > i128::from(i64::MAX) {
+        return None;
+    }
+
+    Some(num_buckets as i64)
+}
+
+/// Analyzer rule planning the aggregations grouped by `time_bucket_gapfill`
+/// into [GapFillNode].
+pub(crate) struct GapFillRule;
+
+impl AnalyzerRule for GapFillRule {
+    fn analyze(&self, plan: LogicalPlan, _config: &ConfigOptions) -> Result<LogicalPlan> {
+        plan.transform_up(&|plan| match &plan {
+            LogicalPlan::Aggregate(aggregate) => match plan_gap_fill(aggregate)? {
+                Some(node) => Ok(Transformed::Yes(LogicalPlan::Extension(Extension {
+                    node: Arc::new(node),
+                }))),
+                None => Ok(Transformed::No(plan)),
+            },
+            LogicalPlan::Projection(projection) => match rewrite_fill_functions(projection)? {
+                Some(projection) => Ok(Transformed::Yes(LogicalPlan::Projection(projection))),
+                None => Ok(Transformed::No(plan)),
+            },
+            _ => Ok(Transformed::No(plan)),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "gap_fill"
+    }
+}
+
+pub(crate) fn as_udf_call<'a>(expr: &'a Expr, name: &str) -> Option<&'a [Expr]> {
+    match expr {
+        Expr::ScalarUDF(ScalarUDF { fun, args }) if fun.name == name => Some(args),
+        _ => None,
+    }
+}
+
+fn plan_gap_fill(aggregate: &Aggregate) -> Result<Option<GapFillNode>> {
+    let gapfill_exprs: Vec<_> = aggregate
+        .group_expr
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, expr)| {
+            let expr = match expr {
+                Expr::Alias(Alias { expr, .. }) => expr.as_ref(),
+                _ => expr,
+            };
+            as_udf_call(expr, TIME_BUCKET_GAPFILL).map(|args| (idx, args))
+        })
+        .collect();
+    let (time_idx, args) = match gapfill_exprs.as_slice() {
+        [] => return Ok(None),
+        [(idx, args)] => (*idx, *args),
+        _ => {
+            return Err(DataFusionError::Plan(format!(
+                "{TIME_BUCKET_GAPFILL} can only be used once in group by"
+            )))
+        }
+    };
+
+    let (ts_column, period) = match args {
+        [Expr::Column(ts), Expr::Literal(ScalarValue::Utf8(Some(period)))] => (ts, period),
+        _ => {
+            return Err(DataFusionError::Plan(format!(
+            "{TIME_BUCKET_GAPFILL} requires a timestamp column and a period literal, args:{args:?}"
+        )))
+        }
+    };
+    let time_range = match aggregate.input.as_ref() {
+        LogicalPlan::Filter(Filter { predicate, .. }) => extract_time_range(predicate, ts_column),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        DataFusionError::Plan(format!(
+            "{TIME_BUCKET_GAPFILL} requires both lower and upper bounds of {ts_column} in where clause"
+        ))
+    })?;
+
+    let schema = &aggregate.schema;
+    let num_groups = aggregate.group_expr.len();
+    let series_columns = (0..num_groups)
+        .filter(|idx| *idx != time_idx)
+        .map(|idx| schema.field(idx).qualified_column())
+        .collect();
+    let fill_columns = (num_groups..schema.fields().len())
+        .map(|idx| (schema.field(idx).qualified_column(), FillStrategy::Null))
+        .collect();
+
+    GapFillNode::try_new(
+        LogicalPlan::Aggregate(aggregate.clone()),
+        schema.field(time_idx).qualified_column(),
+        period.clone(),
+        time_range,
+        series_columns,
+        fill_columns,
+    )
+    .map(Some)
+}
+
+/// Extract `[start, end)` of the `ts_column` from the predicate.
+fn extract_time_range(predicate: &Expr, ts_column: &Column) -> Option<(i64, i64)> {
+    let as_millis = |expr: &Expr| match expr {
+        Expr::Literal(ScalarValue::TimestampMillisecond(Some(v), _))
+        | Expr::Literal(ScalarValue::Int64(Some(v))) => Some(*v),
+        _ => None,
+    };
+    let is_ts_column =
+        |expr: &Expr| matches!(expr, Expr::Column(col) if col.name == ts_column.name);
+
+    let mut start = None::<i64>;
+    let mut end = None::<i64>;
+    let mut update_start = |v: i64| start = Some(start.map_or(v, |s| s.max(v)));
+    let mut update_end = |v: i64| end = Some(end.map_or(v, |e| e.min(v)));
+    for expr in split_conjunction(predicate) {
+        match expr {
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                let (op, value) = if is_ts_column(left) {
+                    (*op, as_millis(right))
+                } else if is_ts_column(right) {
+                    match op.swap() {
+                        Some(op) => (op, as_millis(left)),
+                        None => continue,
+                    }
+                } else {
+                    continue;
+                };
+                let value = match value {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                match op {
+                    Operator::GtEq => update_start(value),
+                    Operator::Gt => update_start(value.saturating_add(1)),
+                    Operator::Lt => update_end(value),
+                    Operator::LtEq => update_end(value.saturating_add(1)),
+                    Operator::Eq => {
+                        update_start(value);
+                        update_end(value.saturating_add(1));
+                    }
+                    _ => {}
+                }
+            }
+            Expr::Between(Between {
+                expr,
+                negated: false,
+                low,
+                high,
+            }) if is_ts_column(expr) => {
+                if let (Some(low), Some(high)) = (as_millis(low), as_millis(high)) {
+                    update_start(low);
+                    update_end(high.saturating_add(1));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    start.zip(end)
+}
+
+/// Remove the `locf` and `interpolate` above the [GapFillNode], and set them
+/// as the fill strategies.
+fn rewrite_fill_functions(projection: &Projection) -> Result<Option<Projection>> {
+    let mut node = match projection.input.as_ref() {
+        LogicalPlan::Extension(Extension { node }) => {
+            match node.as_any().downcast_ref::<GapFillNode>() {
+                Some(node) => node.clone(),
+                None => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+
+    let mut rewritten = false;
+    let mut exprs = Vec::with_capacity(projection.expr.len());
+    for expr in &projection.expr {
+        let (inner, alias) = match expr {
+            Expr::Alias(Alias { expr: inner, name }) => (inner.as_ref(), Some(name.clone())),
+            _ => (expr, None),
+        };
+        let fill_fn = [
+            (LOCF, FillStrategy::Locf),
+            (INTERPOLATE, FillStrategy::Interpolate),
+        ]
+        .into_iter()
+        .find_map(|(name, strategy)| as_udf_call(inner, name).map(|args| (args, strategy)));
+        let (column, strategy) = match fill_fn {
+            Some(([Expr::Column(column)], strategy))
+                if node.fill_columns.iter().any(|(c, _)| c == column) =>
+            {
+                (column, strategy)
+            }
+            _ => {
+                exprs.push(expr.clone());
+                continue;
+            }
+        };
+
+        node = node.with_fill_strategy(column, strategy);
+        let name = match alias {
+            Some(alias) => alias,
+            None => expr.display_name()?,
+        };
+        exprs.push(Expr::Column(column.clone()).alias(name));
+        rewritten = true;
+    }
+
+    if !rewritten {
+        return Ok(None);
+    }
+    let input = LogicalPlan::Extension(Extension {
+        node: Arc::new(node),
+    });
+    Projection::try_new(exprs, Arc::new(input)).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::logical_expr::{col, lit};
+
+    use super::*;
+
+    #[test]
+    fn test_extract_time_range() {
+        let ts = Column::from_name("ts");
+        let ts_lit = |v| lit(ScalarValue::TimestampMillisecond(Some(v), None));
+
+        let predicate = col("ts")
+            .gt_eq(ts_lit(100))
+            .and(col("ts").lt(ts_lit(200)))
+            .and(col("host").eq(lit("a")));
+        assert_eq!(Some((100, 200)), extract_time_range(&predicate, &ts));
+
+        let predicate = ts_lit(100)
+            .lt(col("ts"))
+            .and(col("ts").lt_eq(ts_lit(200)))
+            .and(col("ts").gt(ts_lit(150)));
+        assert_eq!(Some((151, 201)), extract_time_range(&predicate, &ts));
+
+        let predicate = col("ts").between(ts_lit(100), ts_lit(200));
+        assert_eq!(Some((100, 201)), extract_time_range(&predicate, &ts));
+
+        let predicate = col("ts").gt_eq(ts_lit(100));
+        assert_eq!(None, extract_time_range(&predicate, &ts));
+    }
+
+    #[test]
+    fn test_num_buckets() {
+        let period = GapFillPeriod::parse("PT1M").unwrap();
+        assert_eq!(Some(60), num_buckets(&period, 0, 3_600_000));
+        assert_eq!(Some(61), num_buckets(&period, 30_000, 3_630_000));
+        assert_eq!(Some(0), num_buckets(&period, 100, 100));
+
+        let period = GapFillPeriod::parse("PT1S").unwrap();
+        assert_eq!(None, num_buckets(&period, 0, i64::MAX));
+        assert_eq!(
+            None,
+            num_buckets(&period, 0, (MAX_GAP_FILL_BUCKETS + 1) * 1000)
+        );
+
+        let period = GapFillPeriod::parse("P1W").unwrap();
+        assert_eq!(None, num_buckets(&period, i64::MAX - 1000, i64::MAX));
+    }
+}
@@ -17,6 +17,7 @@
 
 //! Influxql processing
 
+pub mod parser;
 pub mod planner;
 
 pub mod error {
@@ -0,0 +1,97 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! InfluxQL parser
+//!
+//! Thin wrapper of the upstream parser, which adds statements not supported
+//! by it.
+
+use influxql_parser::{
+    common::ParseError, show_tag_keys::ShowTagKeysStatement,
+    statement::Statement as InfluxqlStatement,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref SHOW_SERIES_PREFIX: Regex = Regex::new(r"(?i)^\s*SHOW\s+SERIES\b").unwrap();
+}
+
+/// InfluxQL statement
+#[derive(Clone, Debug)]
+pub enum Statement {
+    /// Statement supported by the upstream parser.
+    Standard(Box<InfluxqlStatement>),
+    /// `SHOW SERIES [ON db] [FROM ..] [WHERE ..] [LIMIT ..] [OFFSET ..]`.
+    ///
+    /// It shares the same grammar as `SHOW TAG KEYS`, so the parsed
+    /// [ShowTagKeysStatement] is reused.
+    ShowSeries(Box<ShowTagKeysStatement>),
+}
+
+/// Parse the influxql and returns the statements.
+///
+/// `SHOW SERIES` is only recognized when it is the first statement.
+pub fn parse_statements(influxql: &str) -> Result<Vec<Statement>, ParseError> {
+    let (influxql, show_series) = match SHOW_SERIES_PREFIX.find(influxql) {
+        Some(m) => (format!("SHOW TAG KEYS{}", &influxql[m.end()..]), true),
+        None => (influxql.to_string(), false),
+    };
+
+    let stmts = influxql_parser::parse_statements(&influxql)?;
+    let stmts = stmts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, stmt)| match stmt {
+            InfluxqlStatement::ShowTagKeys(stmt) if show_series && idx == 0 => {
+                Statement::ShowSeries(stmt)
+            }
+            stmt => Statement::Standard(Box::new(stmt)),
+        })
+        .collect();
+
+    Ok(stmts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_show_series() {
+        let stmts = parse_statements("show series from cpu where host = 'a' limit 10").unwrap();
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::ShowSeries(stmt) => {
+                assert!(stmt.from.is_some());
+                assert!(stmt.condition.is_some());
+                assert_eq!(Some(&10), stmt.limit.as_deref());
+            }
+            stmt => panic!("unexpected statement:{stmt:?}"),
+        }
+
+        // Only the leading statement is recognized.
+        assert!(parse_statements("SHOW TAG KEYS; SHOW SERIES").is_err());
+
+        let stmts = parse_statements("SHOW TAG KEYS FROM cpu").unwrap();
+        assert!(matches!(&stmts[0], Statement::Standard(stmt)
+            if matches!(**stmt, InfluxqlStatement::ShowTagKeys(_))));
+
+        let stmts = parse_statements("select * from series_cpu").unwrap();
+        assert!(matches!(&stmts[0], Statement::Standard(_)));
+    }
+}
@@ -19,27 +19,38 @@
 
 use std::{cell::OnceCell, sync::Arc};
 
-use arrow::datatypes::SchemaRef as ArrowSchemaRef;
+use arrow::datatypes::{DataType, Field, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef};
+use common_types::{datum::DatumKind, schema::TSID_COLUMN};
 use datafusion::{
-    error::DataFusionError, logical_expr::TableSource, sql::planner::ContextProvider,
+    common::{DFSchema, ScalarValue},
+    error::{DataFusionError, Result as DfResult},
+    logical_expr::{
+        cast, col, concat, concat_ws, lit, when, EmptyRelation, Expr, LogicalPlan,
+        LogicalPlanBuilder, TableSource,
+    },
+    sql::planner::ContextProvider,
 };
 use generic_error::BoxError;
 use influxql_logical_planner::plan::{
     ceresdb_schema_to_influxdb, InfluxQLToLogicalPlan, SchemaProvider,
 };
 use influxql_parser::{
-    common::{MeasurementName, QualifiedMeasurementName},
+    common::{MeasurementName, QualifiedMeasurementName, WhereClause},
     select::{MeasurementSelection, SelectStatement},
+    show_field_keys::ShowFieldKeysStatement,
     show_measurements::ShowMeasurementsStatement,
+    show_tag_keys::ShowTagKeysStatement,
+    show_tag_values::{ShowTagValuesStatement, WithKeyClause},
     statement::Statement as InfluxqlStatement,
 };
 use influxql_schema::Schema;
 use logger::error;
+use regex::Regex;
 use snafu::{ensure, ResultExt};
 use table_engine::table::TableRef;
 
 use crate::{
-    influxql::error::*,
+    influxql::{error::*, parser::Statement},
     logical_optimizer::optimize_plan,
     plan::{Plan, QueryPlan, QueryType, ShowPlan, ShowTablesPlan},
     provider::{ContextProviderAdapter, MetaProvider},
@@ -48,6 +59,19 @@ use crate::{
 // Same with iox
 pub const HORAEDB_MEASUREMENT_COLUMN_NAME: &str = "iox::measurement";
 
+// Column names of the metadata statements, same with influxdb.
+const TAG_KEY_COLUMN_NAME: &str = "tagKey";
+const FIELD_KEY_COLUMN_NAME: &str = "fieldKey";
+const FIELD_TYPE_COLUMN_NAME: &str = "fieldType";
+const KEY_COLUMN_NAME: &str = "key";
+const VALUE_COLUMN_NAME: &str = "value";
+
+// Results of `SHOW SERIES` and `SHOW RETENTION POLICIES` are not grouped by
+// measurement in influxdb, so an empty measurement name is used.
+const SHOW_SERIES_MEASUREMENT: &str = "";
+const SHOW_RETENTION_POLICIES_MEASUREMENT: &str = "";
+const DEFAULT_RETENTION_POLICY: &str = "autogen";
+
 // Port from https://github.com/CeresDB/influxql/blob/36fc4d873e/iox_query_influxql/src/frontend/planner.rs#L28
 struct InfluxQLSchemaProvider<'a, P: MetaProvider> {
     context_provider: ContextProviderAdapter<'a, P>,
@@ -156,16 +180,25 @@ impl<'a, P: MetaProvider> Planner<'a, P> {
         }
     }
 
-    /// Build sql logical plan from [InfluxqlStatement].
+    /// Build sql logical plan from [Statement].
     ///
     /// NOTICE: when building plan from influxql select statement,
     /// the [InfluxqlStatement] will be converted to [SqlStatement] first,
     /// and build plan then.
-    pub fn statement_to_plan(self, stmt: InfluxqlStatement) -> Result<Plan> {
+    pub fn statement_to_plan(self, stmt: Statement) -> Result<Plan> {
+        let stmt = match stmt {
+            Statement::ShowSeries(stmt) => return self.show_series_to_plan(*stmt),
+            Statement::Standard(stmt) => *stmt,
+        };
+
         match stmt {
             // TODO: show measurement is a temp workaround, it should be implemented in influxql
             // crates.
             InfluxqlStatement::ShowMeasurements(stmt) => self.show_measurements_to_plan(*stmt),
+            InfluxqlStatement::ShowTagKeys(stmt) => self.show_tag_keys_to_plan(*stmt),
+            InfluxqlStatement::ShowTagValues(stmt) => self.show_tag_values_to_plan(*stmt),
+            InfluxqlStatement::ShowFieldKeys(stmt) => self.show_field_keys_to_plan(*stmt),
+            InfluxqlStatement::ShowRetentionPolicies(_) => self.show_retention_policies_to_plan(),
             _ => {
                 let planner = InfluxQLToLogicalPlan::new(&self.schema_provider);
                 let df_plan =
@@ -175,30 +208,35 @@ impl<'a, P: MetaProvider> Planner<'a, P> {
                         .context(BuildPlanWithCause {
                             msg: "planner stmt to plan",
                         })?;
-                let df_plan = optimize_plan(&df_plan)
-                    .box_err()
-                    .context(BuildPlanWithCause {
-                        msg: "optimize plan",
-                    })?;
-
-                let tables = Arc::new(
-                    self.schema_provider
-                        .context_provider
-                        .try_into_container()
-                        .box_err()
-                        .context(BuildPlanWithCause {
-                            msg: "get tables from context_provider",
-                        })?,
-                );
-                Ok(Plan::Query(QueryPlan {
-                    df_plan,
-                    tables,
-                    table_name: None,
-                }))
+
+                self.df_plan_to_plan(df_plan)
             }
         }
     }
 
+    fn df_plan_to_plan(self, df_plan: LogicalPlan) -> Result<Plan> {
+        let df_plan = optimize_plan(&df_plan)
+            .box_err()
+            .context(BuildPlanWithCause {
+                msg: "optimize plan",
+            })?;
+
+        let tables = Arc::new(
+            self.schema_provider
+                .context_provider
+                .try_into_container()
+                .box_err()
+                .context(BuildPlanWithCause {
+                    msg: "get tables from context_provider",
+                })?,
+        );
+        Ok(Plan::Query(QueryPlan {
+            df_plan,
+            tables,
+            table_name: None,
+        }))
+    }
+
     // TODO: support offset/limit/match in stmt
     fn show_measurements_to_plan(self, _stmt: ShowMeasurementsStatement) -> Result<Plan> {
         let plan = ShowTablesPlan {
@@ -207,6 +245,495 @@ impl<'a, P: MetaProvider> Planner<'a, P> {
         };
         Ok(Plan::Show(ShowPlan::ShowTablesPlan(plan)))
     }
+
+    /// Tag keys are read from the table schema directly, unless there is a
+    /// `WHERE` clause, then only the tag keys with values in the matched rows
+    /// are returned.
+    ///
+    /// `LIMIT` and `OFFSET` are applied to the tag keys of each measurement.
+    fn show_tag_keys_to_plan(self, stmt: ShowTagKeysStatement) -> Result<Plan> {
+        let tables = self.measurements(stmt.from.as_deref())?;
+        let (offset, limit) = offset_and_limit(stmt.offset.as_deref(), stmt.limit.as_deref());
+
+        let df_plan = match &stmt.condition {
+            Some(condition) => self.build_show_tag_keys_plan(&tables, condition, offset, limit),
+            None => {
+                let rows = tables
+                    .iter()
+                    .flat_map(|table| {
+                        tag_keys(table)
+                            .into_iter()
+                            .skip(offset)
+                            .take(limit.unwrap_or(usize::MAX))
+                            .map(|key| {
+                                vec![ScalarValue::from(table.name()), ScalarValue::from(key)]
+                            })
+                    })
+                    .collect();
+                values_to_plan(
+                    vec![
+                        utf8_field(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                        utf8_field(TAG_KEY_COLUMN_NAME),
+                    ],
+                    rows,
+                )
+            }
+        }
+        .box_err()
+        .context(BuildPlanWithCause {
+            msg: "build show tag keys plan",
+        })?;
+
+        self.df_plan_to_plan(df_plan)
+    }
+
+    fn build_show_tag_keys_plan(
+        &self,
+        tables: &[TableRef],
+        condition: &WhereClause,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> DfResult<LogicalPlan> {
+        let mut plans = Vec::with_capacity(tables.len());
+        for table in tables {
+            let mut key_plans = Vec::new();
+            for key in tag_keys(table) {
+                let plan = self
+                    .select_all_to_plan(table.name(), Some(condition))?
+                    .filter(col(&key).is_not_null())?
+                    .limit(0, Some(1))?
+                    .project(vec![
+                        lit(table.name()).alias(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                        lit(key).alias(TAG_KEY_COLUMN_NAME),
+                    ])?
+                    .build()?;
+                key_plans.push(plan);
+            }
+
+            if key_plans.is_empty() {
+                continue;
+            }
+            let plan = union_plans(key_plans, vec![])?
+                .sort(vec![col(TAG_KEY_COLUMN_NAME).sort(true, false)])?
+                .limit(offset, limit)?
+                .build()?;
+            plans.push(plan);
+        }
+
+        union_plans(
+            plans,
+            vec![
+                utf8_field(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                utf8_field(TAG_KEY_COLUMN_NAME),
+            ],
+        )?
+        .sort(vec![
+            col(HORAEDB_MEASUREMENT_COLUMN_NAME).sort(true, false),
+            col(TAG_KEY_COLUMN_NAME).sort(true, false),
+        ])?
+        .build()
+    }
+
+    /// Tag values are collected by distinct scans over the tag columns
+    /// matched by the `WITH KEY` clause.
+    ///
+    /// `LIMIT` and `OFFSET` are applied to the `(key, value)` pairs of each
+    /// measurement.
+    fn show_tag_values_to_plan(self, stmt: ShowTagValuesStatement) -> Result<Plan> {
+        let tables = self.measurements(stmt.from.as_deref())?;
+        let key_matcher = KeyMatcher::try_new(&stmt.with_key)?;
+        let (offset, limit) = offset_and_limit(stmt.offset.as_deref(), stmt.limit.as_deref());
+
+        let df_plan = self
+            .build_show_tag_values_plan(
+                &tables,
+                &key_matcher,
+                stmt.condition.as_ref(),
+                offset,
+                limit,
+            )
+            .box_err()
+            .context(BuildPlanWithCause {
+                msg: "build show tag values plan",
+            })?;
+
+        self.df_plan_to_plan(df_plan)
+    }
+
+    fn build_show_tag_values_plan(
+        &self,
+        tables: &[TableRef],
+        key_matcher: &KeyMatcher,
+        condition: Option<&WhereClause>,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> DfResult<LogicalPlan> {
+        let mut plans = Vec::with_capacity(tables.len());
+        for table in tables {
+            let mut key_plans = Vec::new();
+            for key in tag_keys(table) {
+                if !key_matcher.is_match(&key) {
+                    continue;
+                }
+
+                let plan = self
+                    .select_all_to_plan(table.name(), condition)?
+                    .filter(col(&key).is_not_null())?
+                    .aggregate(vec![col(&key)], Vec::<Expr>::new())?
+                    .project(vec![
+                        lit(table.name()).alias(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                        lit(key.as_str()).alias(KEY_COLUMN_NAME),
+                        cast(col(&key), DataType::Utf8).alias(VALUE_COLUMN_NAME),
+                    ])?
+                    .build()?;
+                key_plans.push(plan);
+            }
+
+            if key_plans.is_empty() {
+                continue;
+            }
+            let plan = union_plans(key_plans, vec![])?
+                .sort(vec![
+                    col(KEY_COLUMN_NAME).sort(true, false),
+                    col(VALUE_COLUMN_NAME).sort(true, false),
+                ])?
+                .limit(offset, limit)?
+                .build()?;
+            plans.push(plan);
+        }
+
+        union_plans(
+            plans,
+            vec![
+                utf8_field(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                utf8_field(KEY_COLUMN_NAME),
+                utf8_field(VALUE_COLUMN_NAME),
+            ],
+        )?
+        .sort(vec![
+            col(HORAEDB_MEASUREMENT_COLUMN_NAME).sort(true, false),
+            col(KEY_COLUMN_NAME).sort(true, false),
+            col(VALUE_COLUMN_NAME).sort(true, false),
+        ])?
+        .build()
+    }
+
+    /// Field keys are read from the table schema, all the columns except the
+    /// tags, timestamp and tsid are considered as fields.
+    ///
+    /// `LIMIT` and `OFFSET` are applied to the field keys of each measurement.
+    fn show_field_keys_to_plan(self, stmt: ShowFieldKeysStatement) -> Result<Plan> {
+        let tables = self.measurements(stmt.from.as_deref())?;
+        let (offset, limit) = offset_and_limit(stmt.offset.as_deref(), stmt.limit.as_deref());
+
+        let rows = tables
+            .iter()
+            .flat_map(|table| {
+                field_keys(table)
+                    .into_iter()
+                    .skip(offset)
+                    .take(limit.unwrap_or(usize::MAX))
+                    .map(|(key, field_type)| {
+                        vec![
+                            ScalarValue::from(table.name()),
+                            ScalarValue::from(key),
+                            ScalarValue::from(field_type),
+                        ]
+                    })
+            })
+            .collect();
+        let df_plan = values_to_plan(
+            vec![
+                utf8_field(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                utf8_field(FIELD_KEY_COLUMN_NAME),
+                utf8_field(FIELD_TYPE_COLUMN_NAME),
+            ],
+            rows,
+        )
+        .box_err()
+        .context(BuildPlanWithCause {
+            msg: "build show field keys plan",
+        })?;
+
+        self.df_plan_to_plan(df_plan)
+    }
+
+    /// Series keys are built from the distinct tag values of each
+    /// measurement, formatted like `cpu,host=a,region=b`.
+    ///
+    /// `LIMIT` and `OFFSET` are applied to all the series.
+    fn show_series_to_plan(self, stmt: ShowTagKeysStatement) -> Result<Plan> {
+        let tables = self.measurements(stmt.from.as_deref())?;
+        let (offset, limit) = offset_and_limit(stmt.offset.as_deref(), stmt.limit.as_deref());
+
+        let df_plan = self
+            .build_show_series_plan(&tables, stmt.condition.as_ref(), offset, limit)
+            .box_err()
+            .context(BuildPlanWithCause {
+                msg: "build show series plan",
+            })?;
+
+        self.df_plan_to_plan(df_plan)
+    }
+
+    fn build_show_series_plan(
+        &self,
+        tables: &[TableRef],
+        condition: Option<&WhereClause>,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> DfResult<LogicalPlan> {
+        let mut plans = Vec::with_capacity(tables.len());
+        for table in tables {
+            let tags = tag_keys(table);
+            let builder = self.select_all_to_plan(table.name(), condition)?;
+            let builder = if tags.is_empty() {
+                // Measurement without tags has only one series.
+                builder.limit(0, Some(1))?
+            } else {
+                builder.aggregate(tags.iter().map(col).collect::<Vec<_>>(), Vec::<Expr>::new())?
+            };
+
+            let mut series_key = Vec::with_capacity(tags.len() + 1);
+            series_key.push(lit(table.name()));
+            for tag in &tags {
+                let tag_pair = concat(&[lit(format!("{tag}=")), cast(col(tag), DataType::Utf8)]);
+                series_key.push(when(col(tag).is_not_null(), tag_pair).end()?);
+            }
+            let plan = builder
+                .project(vec![
+                    lit(SHOW_SERIES_MEASUREMENT).alias(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                    concat_ws(lit(","), series_key).alias(KEY_COLUMN_NAME),
+                ])?
+                .build()?;
+            plans.push(plan);
+        }
+
+        union_plans(
+            plans,
+            vec![
+                utf8_field(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                utf8_field(KEY_COLUMN_NAME),
+            ],
+        )?
+        .sort(vec![col(KEY_COLUMN_NAME).sort(true, false)])?
+        .limit(offset, limit)?
+        .build()
+    }
+
+    /// Retention policy is not a concept of HoraeDB (ttl is defined per
+    /// table), so the default one of influxdb is returned.
+    fn show_retention_policies_to_plan(self) -> Result<Plan> {
+        let rows = vec![vec![
+            ScalarValue::from(SHOW_RETENTION_POLICIES_MEASUREMENT),
+            ScalarValue::from(DEFAULT_RETENTION_POLICY),
+            ScalarValue::from("0s"),
+            ScalarValue::from("168h0m0s"),
+            ScalarValue::Int64(Some(1)),
+            ScalarValue::Boolean(Some(true)),
+        ]];
+        let df_plan = values_to_plan(
+            vec![
+                utf8_field(HORAEDB_MEASUREMENT_COLUMN_NAME),
+                utf8_field("name"),
+                utf8_field("duration"),
+                utf8_field("shardGroupDuration"),
+                Field::new("replicaN", DataType::Int64, false),
+                Field::new("default", DataType::Boolean, false),
+            ],
+            rows,
+        )
+        .box_err()
+        .context(BuildPlanWithCause {
+            msg: "build show retention policies plan",
+        })?;
+
+        self.df_plan_to_plan(df_plan)
+    }
+
+    /// Resolve the measurements in the `FROM` clause, all the measurements are
+    /// returned if it is absent.
+    fn measurements(&self, from: Option<&[QualifiedMeasurementName]>) -> Result<Vec<TableRef>> {
+        let mut tables = self
+            .schema_provider
+            .context_provider
+            .all_tables()
+            .box_err()
+            .context(BuildPlanWithCause {
+                msg: "get all tables",
+            })?;
+        tables.sort_unstable_by(|a, b| a.name().cmp(b.name()));
+
+        let from = match from {
+            Some(from) => from,
+            None => return Ok(tables),
+        };
+        let mut patterns = Vec::with_capacity(from.len());
+        for measurement in from {
+            let pattern = match &measurement.name {
+                MeasurementName::Name(name) => format!("^{}$", regex::escape(name)),
+                MeasurementName::Regex(re) => re.as_str().to_string(),
+            };
+            let pattern = Regex::new(&pattern).box_err().context(BuildPlanWithCause {
+                msg: format!("invalid measurement in from clause, measurement:{measurement}"),
+            })?;
+            patterns.push(pattern);
+        }
+
+        tables.retain(|table| patterns.iter().any(|p| p.is_match(table.name())));
+        Ok(tables)
+    }
+
+    /// Build plan for `SELECT * FROM <measurement> [WHERE <condition>]`.
+    fn select_all_to_plan(
+        &self,
+        measurement: &str,
+        condition: Option<&WhereClause>,
+    ) -> DfResult<LogicalPlanBuilder> {
+        let mut influxql = format!(
+            "SELECT * FROM \"{}\"",
+            measurement.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        if let Some(condition) = condition {
+            influxql.push_str(&format!(" WHERE {}", **condition));
+        }
+
+        let mut stmts = influxql_parser::parse_statements(&influxql).map_err(|e| {
+            DataFusionError::Plan(format!("failed to parse influxql:{influxql}, err:{e}"))
+        })?;
+        let planner = InfluxQLToLogicalPlan::new(&self.schema_provider);
+        let df_plan = planner
+            .statement_to_plan(stmts.remove(0))
+            .box_err()
+            .map_err(DataFusionError::External)?;
+
+        Ok(LogicalPlanBuilder::from(df_plan))
+    }
+}
+
+/// Matcher of the `WITH KEY` clause.
+struct KeyMatcher {
+    regex: Regex,
+    negated: bool,
+}
+
+impl KeyMatcher {
+    fn try_new(with_key: &WithKeyClause) -> Result<Self> {
+        let (pattern, negated) = match with_key {
+            WithKeyClause::Eq(key) => (exact_pattern(&[key.as_str()]), false),
+            WithKeyClause::NotEq(key) => (exact_pattern(&[key.as_str()]), true),
+            WithKeyClause::EqRegex(re) => (re.as_str().to_string(), false),
+            WithKeyClause::NotEqRegex(re) => (re.as_str().to_string(), true),
+            WithKeyClause::In(keys) => {
+                let keys: Vec<_> = keys.iter().map(|k| k.as_str()).collect();
+                (exact_pattern(&keys), false)
+            }
+        };
+        let regex = Regex::new(&pattern).box_err().context(BuildPlanWithCause {
+            msg: format!("invalid with key clause, clause:{with_key}"),
+        })?;
+
+        Ok(Self { regex, negated })
+    }
+
+    fn is_match(&self, key: &str) -> bool {
+        self.regex.is_match(key) != self.negated
+    }
+}
+
+/// Pattern matching any of the `keys` exactly.
+fn exact_pattern(keys: &[&str]) -> String {
+    let keys: Vec<_> = keys.iter().map(|k| regex::escape(k)).collect();
+    format!("^(?:{})$", keys.join("|"))
+}
+
+fn tag_keys(table: &TableRef) -> Vec<String> {
+    let mut keys: Vec<_> = table
+        .schema()
+        .columns()
+        .iter()
+        .filter(|c| c.is_tag)
+        .map(|c| c.name.clone())
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+fn field_keys(table: &TableRef) -> Vec<(String, &'static str)> {
+    let schema = table.schema();
+    let timestamp_index = schema.timestamp_index();
+    let mut keys: Vec<_> = schema
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(idx, c)| !c.is_tag && *idx != timestamp_index && c.name != TSID_COLUMN)
+        .map(|(_, c)| (c.name.clone(), influxql_field_type(c.data_type)))
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+/// Map to the field types of influxdb.
+fn influxql_field_type(kind: DatumKind) -> &'static str {
+    match kind {
+        DatumKind::Double | DatumKind::Float | DatumKind::Decimal => "float",
+        DatumKind::Int64 | DatumKind::Int32 | DatumKind::Int16 | DatumKind::Int8 => "integer",
+        DatumKind::UInt64 | DatumKind::UInt32 | DatumKind::UInt16 | DatumKind::UInt8 => "unsigned",
+        DatumKind::Boolean => "boolean",
+        _ => "string",
+    }
+}
+
+fn offset_and_limit(offset: Option<&u64>, limit: Option<&u64>) -> (usize, Option<usize>) {
+    (
+        offset.map_or(0, |v| *v as usize),
+        limit.map(|v| *v as usize),
+    )
+}
+
+fn utf8_field(name: &str) -> Field {
+    Field::new(name, DataType::Utf8, false)
+}
+
+fn empty_plan(fields: Vec<Field>) -> DfResult<LogicalPlanBuilder> {
+    let schema = DFSchema::try_from(ArrowSchema::new(fields))?;
+    Ok(LogicalPlanBuilder::from(LogicalPlan::EmptyRelation(
+        EmptyRelation {
+            produce_one_row: false,
+            schema: Arc::new(schema),
+        },
+    )))
+}
+
+/// Union all the plans, an empty plan with `fields` is returned if `plans` is
+/// empty.
+fn union_plans(plans: Vec<LogicalPlan>, fields: Vec<Field>) -> DfResult<LogicalPlanBuilder> {
+    let mut plans = plans.into_iter();
+    match plans.next() {
+        Some(first) => plans.try_fold(LogicalPlanBuilder::from(first), |builder, plan| {
+            builder.union(plan)
+        }),
+        None => empty_plan(fields),
+    }
+}
+
+fn values_to_plan(fields: Vec<Field>, rows: Vec<Vec<ScalarValue>>) -> DfResult<LogicalPlan> {
+    if rows.is_empty() {
+        return empty_plan(fields)?.build();
+    }
+
+    let values = rows
+        .into_iter()
+        .map(|row| row.into_iter().map(lit).collect())
+        .collect();
+    // Columns of values plan are named as `column1`, `column2`...
+    let exprs: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| col(format!("column{}", idx + 1)).alias(field.name()))
+        .collect();
+
+    LogicalPlanBuilder::values(values)?.project(exprs)?.build()
 }
 
 pub fn check_select_statement(select_stmt: &SelectStatement) -> Result<()> {
@@ -248,7 +775,7 @@ pub fn check_select_statement(select_stmt: &SelectStatement) -> Result<()> {
 mod test {
     use influxql_parser::{select::SelectStatement, statement::Statement};
 
-    use super::check_select_statement;
+    use super::{check_select_statement, KeyMatcher};
 
     #[test]
     fn test_check_select_from() {
@@ -275,6 +802,42 @@ mod test {
         assert!(err.to_string().contains("select from subquery"));
     }
 
+    #[test]
+    fn test_key_matcher() {
+        let cases = [
+            ("show tag values with key = host", vec!["host"]),
+            (
+                "show tag values with key != host",
+                vec!["region", "hostname"],
+            ),
+            (
+                "show tag values with key =~ /^host/",
+                vec!["host", "hostname"],
+            ),
+            ("show tag values with key !~ /^host/", vec!["region"]),
+            (
+                "show tag values with key in (host, region)",
+                vec!["host", "region"],
+            ),
+        ];
+
+        for (influxql, mut expected) in cases {
+            let stmt = influxql_parser::parse_statements(influxql).unwrap()[0].clone();
+            let stmt = match stmt {
+                Statement::ShowTagValues(stmt) => stmt,
+                _ => unreachable!(),
+            };
+            let matcher = KeyMatcher::try_new(&stmt.with_key).unwrap();
+            let mut matched: Vec<_> = ["host", "region", "hostname"]
+                .into_iter()
+                .filter(|key| matcher.is_match(key))
+                .collect();
+            expected.sort_unstable();
+            matched.sort_unstable();
+            assert_eq!(expected, matched, "influxql:{influxql}");
+        }
+    }
+
     fn parse_select(influxql: &str) -> SelectStatement {
         let stmt = influxql_parser::parse_statements(influxql).unwrap()[0].clone();
         if let Statement::Select(select_stmt) = stmt {
@@ -21,10 +21,12 @@
 //!
 //! Parse sql into logical plan that can be handled by interpreters
 
+pub mod asof_join;
 pub mod ast;
 pub mod config;
 pub mod container;
 pub mod frontend;
+pub mod gap_fill;
 pub mod influxql;
 mod logical_optimizer;
 pub mod parser;
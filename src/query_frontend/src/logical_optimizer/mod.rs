@@ -27,8 +27,11 @@ use datafusion::{
     optimizer::analyzer::Analyzer,
     prelude::SessionConfig,
 };
+pub(crate) use type_conversion::string_to_timestamp_millis;
 use type_conversion::TypeConversion;
 
+use crate::{asof_join::AsofJoinRule, gap_fill::GapFillRule};
+
 pub fn optimize_plan(plan: &LogicalPlan) -> Result<LogicalPlan> {
     let state = SessionState::with_config_rt(SessionConfig::new(), Arc::new(RuntimeEnv::default()));
     let state = register_analyzer_rules(state);
@@ -47,6 +50,9 @@ fn register_analyzer_rules(mut state: SessionState) -> SessionState {
     for rule in Analyzer::new().rules {
         state = state.add_analyzer_rule(rule);
     }
+    // Gap fill relies on the literals converted by the rules above.
+    state = state.add_analyzer_rule(Arc::new(GapFillRule));
+    state = state.add_analyzer_rule(Arc::new(AsofJoinRule));
 
     state
 }
@@ -291,6 +291,19 @@ impl<'a> TreeNodeRewriter for TypeRewriter<'a> {
     }
 }
 
+/// Parse the string to milliseconds timestamp in the same way as the timestamp
+/// literal in the filters.
+pub(crate) fn string_to_timestamp_millis(string: &str) -> Result<i64> {
+    let value =
+        string_to_timestamp_ms_workaround(string).or_else(|_| string_to_timestamp_ms(string))?;
+    match value {
+        ScalarValue::TimestampMillisecond(Some(v), _) => Ok(v),
+        v => Err(DataFusionError::Internal(format!(
+            "Unexpected timestamp value, value:{v:?}"
+        ))),
+    }
+}
+
 fn string_to_timestamp_ms(string: &str) -> Result<ScalarValue> {
     let ts = string_to_timestamp_nanos(string)
         .map(|t| t / 1_000_000)
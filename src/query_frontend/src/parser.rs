@@ -19,13 +19,18 @@
 //!
 //! Some codes are copied from datafusion: <https://github.com/apache/arrow/blob/9d86440946b8b07e03abb94fad2da278affae08f/rust/datafusion/src/sql/parser.rs#L74>
 
+use std::str::FromStr;
+
+use common_types::column_schema::{ColumnAggregation, CompressionHint, EncodingHint};
+use df_operator::udfs::asof_join::ASOF_JOIN_CONDITION;
 use logger::debug;
 use macros::define_result;
 use paste::paste;
 use sqlparser::{
     ast::{
-        ColumnDef, ColumnOption, ColumnOptionDef, DataType, Expr, Ident, ObjectName, SetExpr,
-        Statement as SqlStatement, TableConstraint, TableFactor, TableWithJoins,
+        ColumnDef, ColumnOption, ColumnOptionDef, DataType, Expr, Function, FunctionArg,
+        FunctionArgExpr, Ident, JoinConstraint, JoinOperator, ObjectName, SetExpr,
+        Statement as SqlStatement, TableConstraint, TableFactor, TableWithJoins, Value,
     },
     dialect::{keywords::Keyword, Dialect, MySqlDialect},
     parser::{IsOptional::Mandatory, Parser as SqlParser, ParserError},
@@ -35,9 +40,10 @@ use table_engine::ANALYTIC_ENGINE_TYPE;
 
 use crate::{
     ast::{
-        AlterAddColumn, AlterModifySetting, CreateTable, DescribeTable, DropTable, ExistsTable,
-        HashPartition, KeyPartition, Partition, RandomPartition, ShowCreate, ShowCreateObject,
-        ShowTables, Statement,
+        AlterAddColumn, AlterModifySetting, AsOf, AsOfQuery, BackupTable, CreateTable,
+        DescribeTable, DropTable, ExistsTable, HashPartition, KeyPartition, KillQuery, Partition,
+        RandomPartition, RestoreTable, ShowCreate, ShowCreateObject, ShowTables, Statement,
+        TableAsOf, TableName, VerifyTable,
     },
     partition,
 };
@@ -57,6 +63,14 @@ const DICTIONARY: &str = "DICTIONARY";
 const UNSIGN: &str = "UNSIGN";
 const MODIFY: &str = "MODIFY";
 const SETTING: &str = "SETTING";
+const AGGREGATION: &str = "__aggregation";
+const ENCODING: &str = "ENCODING";
+const COMPRESSION: &str = "COMPRESSION";
+const OF: &str = "OF";
+const SEQUENCE: &str = "SEQUENCE";
+const TIMESTAMP: &str = "TIMESTAMP";
+const REPAIR: &str = "REPAIR";
+const ASOF: &str = "ASOF";
 
 macro_rules! is_custom_column {
     ($name: ident) => {
@@ -82,6 +96,37 @@ is_custom_column!(TAG);
 is_custom_column!(DICTIONARY);
 is_custom_column!(UNSIGN);
 
+/// Parse the value of the [`ColumnOption`] in form of `<name> <value>`.
+fn get_column_option_value<T: FromStr>(opt: &ColumnOption, name: &str) -> Option<T> {
+    if let ColumnOption::DialectSpecific(tokens) = opt {
+        if let [Token::Word(word), Token::Word(value)] = &tokens[..] {
+            if word.value == name {
+                return T::from_str(&value.value).ok();
+            }
+        }
+    }
+
+    None
+}
+
+/// Get the aggregation from the [`ColumnOption`] if it is an aggregation
+/// option.
+pub fn get_column_aggregation(opt: &ColumnOption) -> Option<ColumnAggregation> {
+    get_column_option_value(opt, AGGREGATION)
+}
+
+/// Get the encoding hint from the [`ColumnOption`] if it is an encoding
+/// option.
+pub fn get_column_encoding(opt: &ColumnOption) -> Option<EncodingHint> {
+    get_column_option_value(opt, ENCODING)
+}
+
+/// Get the compression hint from the [`ColumnOption`] if it is a compression
+/// option.
+pub fn get_column_compression(opt: &ColumnOption) -> Option<CompressionHint> {
+    get_column_option_value(opt, COMPRESSION)
+}
+
 /// Get the comment from the [`ColumnOption`] if it is a comment option.
 #[inline]
 pub fn get_column_comment(opt: &ColumnOption) -> Option<String> {
@@ -121,6 +166,12 @@ pub fn is_timestamp_key_constraint(constraint: &TableConstraint) -> bool {
 /// SQL Parser with horaedb dialect support
 pub struct Parser<'a> {
     parser: SqlParser<'a>,
+    /// `AS OF` clauses of each statement, which are not supported by the
+    /// native parser, so they are extracted from the tokens before parsing.
+    as_of_clauses: Vec<Vec<TableAsOf>>,
+    /// Right tables of the `ASOF JOIN`s of each statement, the `ASOF` keywords
+    /// are removed from the tokens before parsing, too.
+    asof_joins: Vec<Vec<TableName>>,
 }
 
 impl<'a> Parser<'a> {
@@ -128,11 +179,15 @@ impl<'a> Parser<'a> {
     fn new_with_dialect(sql: &str, dialect: &'a dyn Dialect) -> Result<Self> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
         let tokens = tokenizer.tokenize()?;
+        let (tokens, as_of_clauses) = extract_as_of_clauses(tokens)?;
+        let (tokens, asof_joins) = extract_asof_joins(tokens)?;
 
         let parser = SqlParser::new(dialect);
 
         Ok(Parser {
             parser: parser.with_tokens(tokens),
+            as_of_clauses,
+            asof_joins,
         })
     }
 
@@ -157,6 +212,8 @@ impl<'a> Parser<'a> {
             }
 
             let statement = parser.parse_statement()?;
+            let statement = parser.mark_asof_joins(stmts.len(), statement)?;
+            let statement = parser.attach_as_of_clauses(stmts.len(), statement)?;
             stmts.push(statement);
             expecting_statement_delimiter = true;
         }
@@ -166,6 +223,81 @@ impl<'a> Parser<'a> {
         Ok(stmts)
     }
 
+    /// Wrap the `idx`-th statement into [Statement::AsOfQuery] if it has `AS
+    /// OF` clauses.
+    fn attach_as_of_clauses(&mut self, idx: usize, statement: Statement) -> Result<Statement> {
+        let tables = match self.as_of_clauses.get_mut(idx) {
+            Some(clauses) if !clauses.is_empty() => std::mem::take(clauses),
+            _ => return Ok(statement),
+        };
+
+        match statement {
+            Statement::Standard(statement) if matches!(*statement, SqlStatement::Query(_)) => {
+                Ok(Statement::AsOfQuery(AsOfQuery { statement, tables }))
+            }
+            _ => parser_err!("AS OF is only supported in query".to_string()),
+        }
+    }
+
+    /// Wrap the conditions of the `ASOF JOIN`s in the `idx`-th statement into
+    /// the `asof_join_condition` marker function.
+    fn mark_asof_joins(&mut self, idx: usize, mut statement: Statement) -> Result<Statement> {
+        let tables = match self.asof_joins.get_mut(idx) {
+            Some(tables) if !tables.is_empty() => std::mem::take(tables),
+            _ => return Ok(statement),
+        };
+
+        let select = match &mut statement {
+            Statement::Standard(statement) => match statement.as_mut() {
+                SqlStatement::Query(query) => match query.body.as_mut() {
+                    SetExpr::Select(select) => Some(select),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        let select = match select {
+            Some(v) => v,
+            None => return parser_err!("ASOF JOIN is only supported in select".to_string()),
+        };
+
+        for table in tables {
+            let join = select
+                .from
+                .iter_mut()
+                .flat_map(|from| from.joins.iter_mut())
+                .find(|join| match &join.relation {
+                    TableFactor::Table { name, .. } => {
+                        TableName::from(name.clone()).to_string() == table.to_string()
+                    }
+                    _ => false,
+                });
+            let constraint = match join.map(|join| &mut join.join_operator) {
+                Some(JoinOperator::Inner(JoinConstraint::On(expr)))
+                | Some(JoinOperator::LeftOuter(JoinConstraint::On(expr))) => expr,
+                _ => {
+                    return parser_err!(format!(
+                        "ASOF JOIN requires an ON condition, table:{}",
+                        table.to_string()
+                    ))
+                }
+            };
+
+            let condition = std::mem::replace(constraint, Expr::Value(Value::Boolean(true)));
+            *constraint = Expr::Function(Function {
+                name: ObjectName(vec![Ident::new(ASOF_JOIN_CONDITION)]),
+                args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(condition))],
+                over: None,
+                distinct: false,
+                special: false,
+                order_by: vec![],
+            });
+        }
+
+        Ok(statement)
+    }
+
     // Report unexpected token
     fn expected<T>(&self, expected: &str, found: Token) -> Result<T> {
         parser_err!(format!("Expected {expected}, found: {found}"))
@@ -204,6 +336,23 @@ impl<'a> Parser<'a> {
                         self.parser.next_token();
                         self.parse_exists()
                     }
+                    Keyword::KILL => {
+                        self.parser.next_token();
+                        self.parse_kill()
+                    }
+                    // BACKUP and RESTORE are not keywords of sqlparser.
+                    Keyword::NoKeyword if w.value.eq_ignore_ascii_case("BACKUP") => {
+                        self.parser.next_token();
+                        self.parse_backup()
+                    }
+                    Keyword::NoKeyword if w.value.eq_ignore_ascii_case("RESTORE") => {
+                        self.parser.next_token();
+                        self.parse_restore()
+                    }
+                    Keyword::NoKeyword if w.value.eq_ignore_ascii_case("VERIFY") => {
+                        self.parser.next_token();
+                        self.parse_verify()
+                    }
                     _ => {
                         // use the native parser
                         let mut statement = self.parser.parse_statement()?;
@@ -255,9 +404,59 @@ impl<'a> Parser<'a> {
             Ok(Statement::ShowDatabases)
         } else if self.consume_token("CREATE") {
             Ok(self.parse_show_create()?)
+        } else if self.consume_token("PROCESSLIST") {
+            Ok(Statement::ShowProcessList)
         } else {
-            self.expected("create/tables/databases", self.parser.peek_token().token)
+            self.expected(
+                "create/tables/databases/processlist",
+                self.parser.peek_token().token,
+            )
+        }
+    }
+
+    // Parse `KILL QUERY <id>`, the id is the request id of the query, and can be
+    // given as a string literal or a bare word.
+    fn parse_kill(&mut self) -> Result<Statement> {
+        if !self.consume_token("QUERY") {
+            return self.expected("query", self.parser.peek_token().token);
         }
+
+        let query_id = match self.parser.next_token().token {
+            Token::SingleQuotedString(s) | Token::DoubleQuotedString(s) => s,
+            Token::Number(s, _) => s,
+            Token::Word(w) => w.value,
+            other => return self.expected("query id", other),
+        };
+        Ok(Statement::KillQuery(KillQuery { query_id }))
+    }
+
+    // Parse `BACKUP TABLE <table> TO '<path>'`.
+    fn parse_backup(&mut self) -> Result<Statement> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let table_name = self.parser.parse_object_name()?.into();
+        self.parser.expect_keyword(Keyword::TO)?;
+        let path = self.parser.parse_literal_string()?;
+
+        Ok(Statement::BackupTable(BackupTable { table_name, path }))
+    }
+
+    // Parse `RESTORE TABLE <table> FROM '<path>'`.
+    fn parse_restore(&mut self) -> Result<Statement> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let table_name = self.parser.parse_object_name()?.into();
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let path = self.parser.parse_literal_string()?;
+
+        Ok(Statement::RestoreTable(RestoreTable { table_name, path }))
+    }
+
+    // Parse `VERIFY TABLE <table> [REPAIR]`.
+    fn parse_verify(&mut self) -> Result<Statement> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let table_name = self.parser.parse_object_name()?.into();
+        let repair = self.consume_token(REPAIR);
+
+        Ok(Statement::VerifyTable(VerifyTable { table_name, repair }))
     }
 
     fn parse_show_tables(&mut self) -> Result<Statement> {
@@ -556,6 +755,27 @@ impl<'a> Parser<'a> {
             Ok(Some(ColumnOption::DialectSpecific(vec![
                 Token::make_keyword(UNSIGN),
             ])))
+        } else if let Some(aggregation) = self.consume_column_aggregation() {
+            // Support aggregation of field column for horaedb, e.g. `value double SUM`
+            Ok(Some(ColumnOption::DialectSpecific(vec![
+                Token::make_keyword(AGGREGATION),
+                Token::make_keyword(aggregation.as_str()),
+            ])))
+        } else if self.consume_token(ENCODING) {
+            // Support encoding hint of column for horaedb, e.g. `ENCODING
+            // BYTE_STREAM_SPLIT`
+            let encoding = self.parse_column_option_value::<EncodingHint>(ENCODING)?;
+            Ok(Some(ColumnOption::DialectSpecific(vec![
+                Token::make_keyword(ENCODING),
+                Token::make_keyword(encoding.as_str()),
+            ])))
+        } else if self.consume_token(COMPRESSION) {
+            // Support compression hint of column for horaedb, e.g. `COMPRESSION ZSTD`
+            let compression = self.parse_column_option_value::<CompressionHint>(COMPRESSION)?;
+            Ok(Some(ColumnOption::DialectSpecific(vec![
+                Token::make_keyword(COMPRESSION),
+                Token::make_keyword(compression.as_str()),
+            ])))
         } else if self.parser.parse_keyword(Keyword::COMMENT) {
             Ok(Some(ColumnOption::Comment(
                 self.parser.parse_literal_string()?,
@@ -752,6 +972,25 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_column_option_value<T>(&mut self, name: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let value = self.parser.parse_identifier()?.value;
+        T::from_str(&value).or_else(|e| parser_err!(format!("invalid {name}:{value}, err:{e}")))
+    }
+
+    fn consume_column_aggregation(&mut self) -> Option<ColumnAggregation> {
+        let aggregation = match self.parser.peek_token().token {
+            Token::Word(word) => ColumnAggregation::from_str(&word.value).ok()?,
+            _ => return None,
+        };
+        self.parser.next_token();
+
+        Some(aggregation)
+    }
+
     fn consume_tokens(&mut self, expected_tokens: &[&str]) -> bool {
         for expected in expected_tokens {
             if !self.consume_token(expected) {
@@ -858,6 +1097,219 @@ fn build_timestamp_key_constraint(col_defs: &[ColumnDef], constraints: &mut Vec<
     }
 }
 
+/// Remove the `AS OF SEQUENCE <n>` and `AS OF TIMESTAMP <t>` clauses from the
+/// tokens, and collect them with the table names before them.
+///
+/// Returns the remaining tokens and the clauses of each statement.
+fn extract_as_of_clauses(tokens: Vec<Token>) -> Result<(Vec<Token>, Vec<Vec<TableAsOf>>)> {
+    let mut remaining = Vec::with_capacity(tokens.len());
+    let mut clauses = vec![Vec::new()];
+    // Empty statements are ignored by the parser, so only count the non-empty
+    // ones.
+    let mut statement_started = false;
+    let mut idx = 0;
+    while idx < tokens.len() {
+        match &tokens[idx] {
+            Token::SemiColon => {
+                if statement_started {
+                    clauses.push(Vec::new());
+                    statement_started = false;
+                }
+            }
+            Token::Whitespace(_) => (),
+            _ => statement_started = true,
+        }
+
+        if let Some((as_of, next_idx)) = parse_as_of_clause(&tokens, idx)? {
+            let table_name = match object_name_at_end(&remaining) {
+                Some(v) => v,
+                None => return parser_err!("Expected table name before AS OF".to_string()),
+            };
+            // A table can only be read at one point in a statement.
+            let statement_clauses = clauses.last_mut().unwrap();
+            let existing = statement_clauses
+                .iter()
+                .find(|clause| clause.table_name.table_part() == table_name.table_part());
+            match existing {
+                Some(clause) if clause.as_of != as_of => {
+                    return parser_err!(format!(
+                        "Different AS OF clauses for table {}",
+                        table_name.to_string()
+                    ));
+                }
+                Some(_) => (),
+                None => statement_clauses.push(TableAsOf { table_name, as_of }),
+            }
+            idx = next_idx;
+            continue;
+        }
+
+        remaining.push(tokens[idx].clone());
+        idx += 1;
+    }
+
+    Ok((remaining, clauses))
+}
+
+/// Remove the `ASOF` keywords of `ASOF [LEFT [OUTER]] JOIN` from the tokens,
+/// and collect the table names after the joins.
+///
+/// Returns the remaining tokens and the right tables of the joins of each
+/// statement.
+fn extract_asof_joins(tokens: Vec<Token>) -> Result<(Vec<Token>, Vec<Vec<TableName>>)> {
+    let is_keyword = |token: Option<&Token>, keyword: Keyword| matches!(token, Some(Token::Word(w)) if w.keyword == keyword);
+
+    let mut remaining = Vec::with_capacity(tokens.len());
+    let mut joins = vec![Vec::new()];
+    let mut statement_started = false;
+    let mut idx = 0;
+    while idx < tokens.len() {
+        match &tokens[idx] {
+            Token::SemiColon => {
+                if statement_started {
+                    joins.push(Vec::new());
+                    statement_started = false;
+                }
+            }
+            Token::Whitespace(_) => (),
+            _ => statement_started = true,
+        }
+
+        let is_asof = matches!(&tokens[idx], Token::Word(w) if w.quote_style.is_none() && w.value.eq_ignore_ascii_case(ASOF));
+        if !is_asof {
+            remaining.push(tokens[idx].clone());
+            idx += 1;
+            continue;
+        }
+
+        // Skip the join keywords to find the table name.
+        let mut name_idx = next_non_whitespace(&tokens, idx + 1);
+        let mut is_join = false;
+        for keyword in [Keyword::LEFT, Keyword::OUTER, Keyword::JOIN] {
+            if is_keyword(tokens.get(name_idx), keyword) {
+                name_idx = next_non_whitespace(&tokens, name_idx + 1);
+                is_join = keyword == Keyword::JOIN;
+            }
+        }
+        if !is_join {
+            // Maybe an identifier named `asof`.
+            remaining.push(tokens[idx].clone());
+            idx += 1;
+            continue;
+        }
+        let table_name = match object_name_at_start(&tokens[name_idx..]) {
+            Some(v) => v,
+            None => return parser_err!("Expected table name after ASOF JOIN".to_string()),
+        };
+        joins.last_mut().unwrap().push(table_name);
+        idx += 1;
+    }
+
+    Ok((remaining, joins))
+}
+
+/// Index of the first non-whitespace token starting from `idx`.
+fn next_non_whitespace(tokens: &[Token], mut idx: usize) -> usize {
+    while matches!(tokens.get(idx), Some(Token::Whitespace(_))) {
+        idx += 1;
+    }
+    idx
+}
+
+/// Try to parse an `AS OF` clause starting at `idx`, returns the clause and
+/// the index of the token after it.
+fn parse_as_of_clause(tokens: &[Token], idx: usize) -> Result<Option<(AsOf, usize)>> {
+    let is_word = |idx: usize, value: &str| match tokens.get(idx) {
+        Some(Token::Word(w)) => w.quote_style.is_none() && w.value.eq_ignore_ascii_case(value),
+        _ => false,
+    };
+
+    if !matches!(&tokens[idx], Token::Word(w) if w.keyword == Keyword::AS) {
+        return Ok(None);
+    }
+    let of_idx = next_non_whitespace(tokens, idx + 1);
+    if !is_word(of_idx, OF) {
+        return Ok(None);
+    }
+    let kind_idx = next_non_whitespace(tokens, of_idx + 1);
+    let value_idx = next_non_whitespace(tokens, kind_idx + 1);
+    let value = tokens.get(value_idx);
+
+    let as_of = if is_word(kind_idx, SEQUENCE) {
+        match value {
+            Some(Token::Number(v, _)) => match v.parse::<u64>() {
+                Ok(sequence) => AsOf::Sequence(sequence),
+                Err(_) => return parser_err!(format!("Invalid sequence in AS OF, sequence:{v}")),
+            },
+            _ => return parser_err!("Expected sequence number after AS OF SEQUENCE".to_string()),
+        }
+    } else if is_word(kind_idx, TIMESTAMP) {
+        match value {
+            Some(Token::Number(v, _)) | Some(Token::SingleQuotedString(v)) => {
+                AsOf::Timestamp(v.clone())
+            }
+            _ => return parser_err!("Expected timestamp after AS OF TIMESTAMP".to_string()),
+        }
+    } else {
+        // Maybe an alias named `of`.
+        return Ok(None);
+    };
+
+    Ok(Some((as_of, value_idx + 1)))
+}
+
+/// Collect the object name (`a.b.c`) at the end of the tokens.
+fn object_name_at_end(tokens: &[Token]) -> Option<TableName> {
+    let mut idents = Vec::new();
+    let mut iter = tokens
+        .iter()
+        .rev()
+        .skip_while(|token| matches!(token, Token::Whitespace(_)));
+    loop {
+        match iter.next() {
+            Some(Token::Word(w)) => idents.push(Ident {
+                value: w.value.clone(),
+                quote_style: w.quote_style,
+            }),
+            _ => return None,
+        }
+
+        let mut peek = iter.clone();
+        if let Some(Token::Period) = peek.next() {
+            iter = peek;
+        } else {
+            break;
+        }
+    }
+    idents.reverse();
+
+    Some(ObjectName(idents).into())
+}
+
+/// Collect the object name (`a.b.c`) at the start of the tokens.
+fn object_name_at_start(tokens: &[Token]) -> Option<TableName> {
+    let mut idents = Vec::new();
+    let mut iter = tokens.iter();
+    loop {
+        match iter.next() {
+            Some(Token::Word(w)) => idents.push(Ident {
+                value: w.value.clone(),
+                quote_style: w.quote_style,
+            }),
+            _ => return None,
+        }
+
+        let mut peek = iter.clone();
+        if let Some(Token::Period) = peek.next() {
+            iter = peek;
+        } else {
+            break;
+        }
+    }
+
+    Some(ObjectName(idents).into())
+}
+
 /// Add quotes in table name (for example: convert table to `table`).
 ///
 /// It is used to process table name in `SELECT`, for preventing `datafusion`
@@ -1160,6 +1612,79 @@ mod tests {
         assert!(Parser::parse_sql(sql).is_err());
     }
 
+    #[test]
+    fn test_aggregation_column() {
+        let sql = "CREATE TABLE IF NOT EXISTS t(c1 string tag, c2 double sum, c3 varbinary hll_union comment 'users', c4 bigint)";
+        let statements = Parser::parse_sql(sql).unwrap();
+        assert_eq!(statements.len(), 1);
+        match &statements[0] {
+            Statement::Create(v) => {
+                let columns = &v.columns;
+                assert_eq!(4, columns.len());
+                let aggregations: Vec<_> = columns
+                    .iter()
+                    .map(|c| {
+                        c.options
+                            .iter()
+                            .find_map(|opt| get_column_aggregation(&opt.option))
+                    })
+                    .collect();
+                assert_eq!(
+                    vec![
+                        None,
+                        Some(ColumnAggregation::Sum),
+                        Some(ColumnAggregation::HllUnion),
+                        None
+                    ],
+                    aggregations
+                );
+            }
+            _ => panic!("failed"),
+        }
+    }
+
+    #[test]
+    fn test_encoding_and_compression_column() {
+        let sql = "CREATE TABLE IF NOT EXISTS t(c1 timestamp encoding delta_binary_packed compression zstd, c2 double encoding byte_stream_split, c3 string compression lz4)";
+        let statements = Parser::parse_sql(sql).unwrap();
+        assert_eq!(statements.len(), 1);
+        match &statements[0] {
+            Statement::Create(v) => {
+                let columns = &v.columns;
+                assert_eq!(3, columns.len());
+                let hints: Vec<_> = columns
+                    .iter()
+                    .map(|c| {
+                        let encoding = c
+                            .options
+                            .iter()
+                            .find_map(|opt| get_column_encoding(&opt.option));
+                        let compression = c
+                            .options
+                            .iter()
+                            .find_map(|opt| get_column_compression(&opt.option));
+                        (encoding, compression)
+                    })
+                    .collect();
+                assert_eq!(
+                    vec![
+                        (
+                            Some(EncodingHint::DeltaBinaryPacked),
+                            Some(CompressionHint::Zstd)
+                        ),
+                        (Some(EncodingHint::ByteStreamSplit), None),
+                        (None, Some(CompressionHint::Lz4)),
+                    ],
+                    hints
+                );
+            }
+            _ => panic!("failed"),
+        }
+
+        let sql = "CREATE TABLE IF NOT EXISTS t(c1 double encoding gorilla)";
+        assert!(Parser::parse_sql(sql).is_err());
+    }
+
     #[test]
     fn test_comment_column() {
         let sql = "CREATE TABLE IF NOT EXISTS t(c1 string, c2 float, c3 bigint comment 'id')";
@@ -1386,6 +1911,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_show_process_list() {
+        let sql = "SHOW PROCESSLIST;";
+        let statements = Parser::parse_sql(sql).unwrap();
+        assert_eq!(statements.len(), 1);
+        assert!(matches!(statements[0], Statement::ShowProcessList));
+    }
+
+    #[test]
+    fn test_kill_query() {
+        let testcases = [
+            ("KILL QUERY 'a1b2c3'", "a1b2c3"),
+            ("kill query \"a1b2c3\";", "a1b2c3"),
+            ("KILL QUERY 42", "42"),
+            ("KILL QUERY abc", "abc"),
+        ];
+        for (sql, expected) in testcases {
+            let statements = Parser::parse_sql(sql).unwrap();
+            assert_eq!(statements.len(), 1);
+            assert_eq!(
+                statements[0],
+                Statement::KillQuery(KillQuery {
+                    query_id: expected.to_string()
+                })
+            );
+        }
+
+        assert!(Parser::parse_sql("KILL 42").is_err());
+        assert!(Parser::parse_sql("KILL QUERY").is_err());
+    }
+
+    #[test]
+    fn test_backup_and_restore_table() {
+        let statements = Parser::parse_sql("BACKUP TABLE t1 TO '/data/backup/t1';").unwrap();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(
+            statements[0],
+            Statement::BackupTable(BackupTable {
+                table_name: make_table_name("t1"),
+                path: "/data/backup/t1".to_string(),
+            })
+        );
+
+        let statements = Parser::parse_sql("restore table t2 from 'backup/t1'").unwrap();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(
+            statements[0],
+            Statement::RestoreTable(RestoreTable {
+                table_name: make_table_name("t2"),
+                path: "backup/t1".to_string(),
+            })
+        );
+
+        assert!(Parser::parse_sql("BACKUP t1 TO '/data/backup/t1'").is_err());
+        assert!(Parser::parse_sql("BACKUP TABLE t1 '/data/backup/t1'").is_err());
+        assert!(Parser::parse_sql("RESTORE TABLE t1 FROM path").is_err());
+    }
+
+    #[test]
+    fn test_as_of_query() {
+        let sql = "SELECT * FROM t1 AS OF SEQUENCE 100 JOIN db.t2 as of timestamp '2023-01-01 00:00:00' ON t1.a = t2.a";
+        let statements = Parser::parse_sql(sql).unwrap();
+        assert_eq!(statements.len(), 1);
+        let as_of_query = match &statements[0] {
+            Statement::AsOfQuery(v) => v,
+            other => panic!("Expected as of query, given:{other:?}"),
+        };
+        assert_eq!(
+            "SELECT * FROM `t1` JOIN `db`.`t2` ON t1.a = t2.a",
+            as_of_query.statement.to_string()
+        );
+        assert_eq!(
+            vec![
+                TableAsOf {
+                    table_name: make_table_name("t1"),
+                    as_of: AsOf::Sequence(100),
+                },
+                TableAsOf {
+                    table_name: ObjectName(vec![Ident::new("db"), Ident::new("t2")]).into(),
+                    as_of: AsOf::Timestamp("2023-01-01 00:00:00".to_string()),
+                },
+            ],
+            as_of_query.tables
+        );
+
+        // The clauses belong to the statement they are in.
+        let statements =
+            Parser::parse_sql("SELECT * FROM t1;; SELECT * FROM t2 AS OF TIMESTAMP 1000").unwrap();
+        assert!(matches!(statements[0], Statement::Standard(_)));
+        assert!(matches!(statements[1], Statement::AsOfQuery(_)));
+
+        // An alias named `of` is not an `AS OF` clause.
+        let statements = Parser::parse_sql("SELECT a AS of FROM t1").unwrap();
+        assert!(matches!(statements[0], Statement::Standard(_)));
+
+        assert!(Parser::parse_sql("SELECT * FROM t1 AS OF SEQUENCE '1'").is_err());
+        assert!(Parser::parse_sql("SELECT * FROM t1 AS OF TIMESTAMP").is_err());
+        assert!(Parser::parse_sql(
+            "SELECT * FROM t1 AS OF SEQUENCE 1 JOIN t1 AS OF SEQUENCE 2 ON t1.a = t1.a"
+        )
+        .is_err());
+        assert!(Parser::parse_sql("DELETE FROM t1 AS OF SEQUENCE 1").is_err());
+    }
+
+    #[test]
+    fn test_asof_join() {
+        let standard_sql = |statement: &Statement| match statement {
+            Statement::Standard(v) => v.to_string(),
+            other => panic!("Expected standard statement, given:{other:?}"),
+        };
+
+        let sql =
+            "SELECT * FROM cpu ASOF JOIN db.power ON cpu.host = power.host AND cpu.ts >= power.ts";
+        let statements = Parser::parse_sql(sql).unwrap();
+        assert_eq!(
+            "SELECT * FROM `cpu` JOIN `db`.`power` ON asof_join_condition(cpu.host = power.host AND cpu.ts >= power.ts)",
+            standard_sql(&statements[0])
+        );
+
+        let sql = "SELECT asof FROM cpu asof LEFT OUTER JOIN power ON cpu.ts < power.ts JOIN mem ON cpu.ts = mem.ts";
+        let statements = Parser::parse_sql(sql).unwrap();
+        assert_eq!(
+            "SELECT asof FROM `cpu` LEFT JOIN `power` ON asof_join_condition(cpu.ts < power.ts) JOIN `mem` ON cpu.ts = mem.ts",
+            standard_sql(&statements[0])
+        );
+
+        assert!(Parser::parse_sql("SELECT * FROM cpu ASOF JOIN power USING (host)").is_err());
+        assert!(Parser::parse_sql(
+            "SELECT * FROM cpu ASOF JOIN (SELECT * FROM power) p ON cpu.ts >= p.ts"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_table() {
+        let statements = Parser::parse_sql("VERIFY TABLE t1").unwrap();
+        assert_eq!(
+            statements[0],
+            Statement::VerifyTable(VerifyTable {
+                table_name: make_table_name("t1"),
+                repair: false,
+            })
+        );
+
+        let statements = Parser::parse_sql("verify table t1 repair;").unwrap();
+        assert_eq!(
+            statements[0],
+            Statement::VerifyTable(VerifyTable {
+                table_name: make_table_name("t1"),
+                repair: true,
+            })
+        );
+
+        assert!(Parser::parse_sql("VERIFY t1").is_err());
+    }
+
     #[test]
     fn test_normalizing_table_name_in_select() {
         {
@@ -39,7 +39,11 @@ use runtime::Priority;
 use snafu::{OptionExt, Snafu};
 use table_engine::{partition::PartitionInfo, table::TableRef};
 
-use crate::{ast::ShowCreateObject, container::TableContainer, planner::get_table_ref};
+use crate::{
+    ast::ShowCreateObject,
+    container::{TableContainer, TableReference},
+    planner::get_table_ref,
+};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -81,6 +85,14 @@ pub enum Plan {
     Show(ShowPlan),
     /// Exists table
     Exists(ExistsTablePlan),
+    /// Kill a running query
+    KillQuery(KillQueryPlan),
+    /// Backup a table
+    BackupTable(BackupTablePlan),
+    /// Create a table from a backup
+    RestoreTable(RestoreTablePlan),
+    /// Verify the ssts of a table
+    VerifyTable(VerifyTablePlan),
 }
 
 impl Plan {
@@ -93,7 +105,11 @@ impl Plan {
             | Self::Describe(_)
             | Self::AlterTable(_)
             | Self::Show(_)
-            | Self::Exists(_) => "other",
+            | Self::Exists(_)
+            | Self::KillQuery(_)
+            | Self::BackupTable(_)
+            | Self::RestoreTable(_)
+            | Self::VerifyTable(_) => "other",
         }
     }
 }
@@ -119,9 +135,12 @@ impl QueryPlan {
                 return Ok(None);
             }
         };
+        // Tables outside the default catalog/schema (e.g. `system.public.tables`)
+        // are referenced by their qualified name.
         let table_ref = self
             .tables
             .get(get_table_ref(table_name))
+            .or_else(|| self.tables.get(TableReference::from(table_name.as_str())))
             .with_context(|| InvalidQueryPlan {
                 msg: format!("Couldn't find table in table container, name:{table_name}"),
             })?;
@@ -138,7 +157,7 @@ impl QueryPlan {
     /// Note: When it timestamp filter evals to false(such as ts < 10 and ts >
     /// 100), it will return None, which means no valid time range for this
     /// query.
-    fn extract_time_range(&self) -> Result<Option<TimeRange>> {
+    pub fn extract_time_range(&self) -> Result<Option<TimeRange>> {
         let ts_column = if let Some(v) = self.find_timestamp_column()? {
             v
         } else {
@@ -367,6 +386,8 @@ pub enum ShowPlan {
     ShowTablesPlan(ShowTablesPlan),
     /// show database
     ShowDatabase,
+    /// show processlist
+    ShowProcessList,
 }
 
 #[derive(Debug)]
@@ -374,6 +395,35 @@ pub struct ExistsTablePlan {
     pub exists: bool,
 }
 
+#[derive(Debug)]
+pub struct KillQueryPlan {
+    pub query_id: String,
+}
+
+#[derive(Debug)]
+pub struct BackupTablePlan {
+    /// The table to backup
+    pub table: TableRef,
+    /// Location to store the backup
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub struct RestoreTablePlan {
+    /// Name of the table to create
+    pub table: String,
+    /// Location of the backup
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub struct VerifyTablePlan {
+    /// The table to verify
+    pub table: TableRef,
+    /// Whether to repair the issues found
+    pub repair: bool,
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -34,15 +34,19 @@ use arrow::{
 use catalog::consts::{DEFAULT_CATALOG, DEFAULT_SCHEMA};
 use cluster::config::SchemaConfig;
 use common_types::{
+    array::{self, ArrayValue},
     column_schema::{self, ColumnSchema},
     datum::{Datum, DatumKind},
+    decimal::{self, DecimalType},
     request_id::RequestId,
     row::{RowBuilder, RowGroup},
     schema::{self, Builder as SchemaBuilder, Schema, TSID_COLUMN},
+    time::Timestamp,
 };
 use datafusion::{
     common::{DFField, DFSchema},
     error::DataFusionError,
+    logical_expr::{LogicalPlan, LogicalPlanBuilder},
     optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext},
     physical_expr::{create_physical_expr, execution_props::ExecutionProps},
     sql::{
@@ -50,34 +54,38 @@ use datafusion::{
         ResolvedTableReference,
     },
 };
+use df_operator::udfs::json::{JSON_EXTRACT, JSON_EXTRACT_STRING};
 use generic_error::GenericError;
 use horaedbproto::storage::{value::Value as PbValue, WriteTableRequest};
-use influxql_parser::statement::Statement as InfluxqlStatement;
 use logger::{debug, trace};
 use macros::define_result;
 use prom_remote_api::types::Query as PromRemoteQuery;
 use snafu::{ensure, Backtrace, OptionExt, ResultExt, Snafu};
 use sqlparser::ast::{
-    visit_statements_mut, ColumnDef, ColumnOption, Expr, Expr as SqlExpr, Ident, Query, SelectItem,
-    SetExpr, SqlOption, Statement as SqlStatement, TableConstraint, UnaryOperator, Value, Values,
+    visit_statements_mut, ColumnDef, ColumnOption, DataType as SqlDataType, ExactNumberInfo, Expr,
+    Expr as SqlExpr, Function, FunctionArg, FunctionArgExpr, Ident, JsonOperator, ObjectName,
+    Query, SelectItem, SetExpr, SqlOption, Statement as SqlStatement, TableConstraint,
+    UnaryOperator, Value, Values, VisitMut, VisitorMut,
 };
-use table_engine::table::TableRef;
+use table_engine::table::{ReadAsOf, TableRef};
 
 use crate::{
     ast::{
-        AlterAddColumn, AlterModifySetting, CreateTable, DescribeTable, DropTable, ExistsTable,
-        ShowCreate, ShowTables, Statement, TableName,
+        AlterAddColumn, AlterModifySetting, AsOf, BackupTable, CreateTable, DescribeTable,
+        DropTable, ExistsTable, KillQuery, RestoreTable, ShowCreate, ShowTables, Statement,
+        TableAsOf, TableName, VerifyTable,
     },
     config::DynamicConfig,
     container::TableReference,
     frontend::parse_table_name_with_standard,
-    logical_optimizer::optimize_plan,
+    influxql::parser::Statement as InfluxqlStatement,
+    logical_optimizer::{optimize_plan, string_to_timestamp_millis},
     parser,
     partition::PartitionParser,
     plan::{
-        AlterTableOperation, AlterTablePlan, CreateTablePlan, DescribeTablePlan, DropTablePlan,
-        ExistsTablePlan, InsertPlan, Plan, QueryPlan, QueryType, ShowCreatePlan, ShowPlan,
-        ShowTablesPlan,
+        AlterTableOperation, AlterTablePlan, BackupTablePlan, CreateTablePlan, DescribeTablePlan,
+        DropTablePlan, ExistsTablePlan, InsertPlan, KillQueryPlan, Plan, QueryPlan, QueryType,
+        RestoreTablePlan, ShowCreatePlan, ShowPlan, ShowTablesPlan, VerifyTablePlan,
     },
     promql::{remote_query_to_plan, ColumnNames, Expr as PromExpr, RemoteQueryPlan},
     provider::{ContextProviderAdapter, MetaProvider},
@@ -185,6 +193,18 @@ pub enum Error {
         source: column_schema::Error,
     },
 
+    #[snafu(display("Invalid decimal type, column_name:{}, err:{}", column_name, source))]
+    InvalidDecimalType {
+        column_name: String,
+        source: decimal::Error,
+    },
+
+    #[snafu(display("Invalid element type, column_name:{}, err:{}", column_name, source))]
+    InvalidElementType {
+        column_name: String,
+        source: common_types::datum::Error,
+    },
+
     #[snafu(display("Invalid table name, err:{}", source))]
     InvalidTableName { source: DataFusionError },
 
@@ -232,6 +252,12 @@ pub enum Error {
     #[snafu(display("Insert Failed to convert value, err:{}", source))]
     InsertConvertValue { source: common_types::datum::Error },
 
+    #[snafu(display("Insert Failed to convert array, err:{}", source))]
+    InsertConvertArray { source: array::Error },
+
+    #[snafu(display("Unsupported unnest, msg:{}.\nBacktrace:\n{}", msg, backtrace))]
+    UnsupportedUnnest { msg: String, backtrace: Backtrace },
+
     #[snafu(display("Failed to build row, err:{}", source))]
     BuildRow { source: common_types::row::Error },
 
@@ -279,6 +305,12 @@ pub enum Error {
     BuildInfluxqlPlan {
         source: crate::influxql::error::Error,
     },
+
+    #[snafu(display("Invalid timestamp in AS OF, timestamp:{}, err:{}", timestamp, source))]
+    InvalidAsOfTimestamp {
+        timestamp: String,
+        source: DataFusionError,
+    },
 }
 
 define_result!(Error);
@@ -329,8 +361,11 @@ impl<'a, P: MetaProvider> Planner<'a, P> {
             statement
         );
 
-        let adapter =
+        let mut adapter =
             ContextProviderAdapter::new(self.provider, self.read_parallelism, self.dyn_config);
+        if let Statement::AsOfQuery(s) = &statement {
+            adapter = adapter.with_table_as_of(tables_as_of(&s.tables)?);
+        }
         // SqlToRel needs to hold the reference to adapter, thus we can't both holds the
         // adapter and the SqlToRel in Planner, which is a self-referential
         // case. We wrap a PlannerDelegate to workaround this and avoid the usage of
@@ -339,6 +374,7 @@ impl<'a, P: MetaProvider> Planner<'a, P> {
 
         match statement {
             Statement::Standard(s) => planner.sql_statement_to_plan(*s),
+            Statement::AsOfQuery(s) => planner.sql_statement_to_plan(*s.statement),
             Statement::Create(s) => planner.create_table_to_plan(*s),
             Statement::Drop(s) => planner.drop_table_to_plan(s),
             Statement::Describe(s) => planner.describe_table_to_plan(s),
@@ -347,7 +383,12 @@ impl<'a, P: MetaProvider> Planner<'a, P> {
             Statement::ShowCreate(s) => planner.show_create_to_plan(s),
             Statement::ShowTables(s) => planner.show_tables_to_plan(s),
             Statement::ShowDatabases => planner.show_databases_to_plan(),
+            Statement::ShowProcessList => planner.show_process_list_to_plan(),
             Statement::Exists(s) => planner.exists_table_to_plan(s),
+            Statement::KillQuery(s) => planner.kill_query_to_plan(s),
+            Statement::BackupTable(s) => planner.backup_table_to_plan(s),
+            Statement::RestoreTable(s) => planner.restore_table_to_plan(s),
+            Statement::VerifyTable(s) => planner.verify_table_to_plan(s),
         }
     }
 
@@ -397,6 +438,28 @@ impl<'a, P: MetaProvider> Planner<'a, P> {
     }
 }
 
+/// Convert the `AS OF` clauses to the time-travel options of each table.
+fn tables_as_of(tables: &[TableAsOf]) -> Result<HashMap<String, ReadAsOf>> {
+    tables
+        .iter()
+        .map(|table| {
+            let as_of = match &table.as_of {
+                AsOf::Sequence(v) => ReadAsOf::Sequence(*v),
+                AsOf::Timestamp(v) => {
+                    let millis = match v.parse::<i64>() {
+                        Ok(millis) => millis,
+                        Err(_) => string_to_timestamp_millis(v)
+                            .context(InvalidAsOfTimestamp { timestamp: v })?,
+                    };
+                    ReadAsOf::Timestamp(Timestamp::new(millis))
+                }
+            };
+
+            Ok((table.table_name.table_part().to_string(), as_of))
+        })
+        .collect()
+}
+
 pub fn build_column_schema(
     column_name: &str,
     data_type: DatumKind,
@@ -561,8 +624,14 @@ fn ensure_data_type_compatible(
         }
     );
 
+    // The decimal is written in string to avoid losing precision, and the json is
+    // also written in string.
+    let is_str_compatible = matches!(
+        column_schema.data_type,
+        DatumKind::Decimal | DatumKind::Json
+    ) && data_type == DatumKind::String;
     ensure!(
-        column_schema.data_type == data_type,
+        column_schema.data_type == data_type || is_str_compatible,
         InvalidWriteEntry {
             msg: format!(
                 "Column: {} in table: {} data type is not same, expected: {}, actual: {}",
@@ -609,20 +678,27 @@ impl<'a, P: MetaProvider> PlannerDelegate<'a, P> {
             // Query statement use datafusion planner
             SqlStatement::Explain { .. } | SqlStatement::Query(_) => {
                 normalize_func_name(&mut sql_stmt);
-                self.sql_statement_to_datafusion_plan(sql_stmt)
+                rewrite_json_access(&mut sql_stmt);
+                let unnest_indexes = rewrite_unnest(&mut sql_stmt)?;
+                self.sql_statement_to_datafusion_plan(sql_stmt, &unnest_indexes)
             }
             SqlStatement::Insert { .. } => self.insert_to_plan(sql_stmt),
             _ => UnsupportedStatement.fail(),
         }
     }
 
-    fn sql_statement_to_datafusion_plan(self, sql_stmt: SqlStatement) -> Result<Plan> {
+    fn sql_statement_to_datafusion_plan(
+        self,
+        sql_stmt: SqlStatement,
+        unnest_indexes: &[usize],
+    ) -> Result<Plan> {
         let df_planner = SqlToRel::new_with_options(&self.meta_provider, DEFAULT_PARSER_OPTS);
         let table_name = parse_table_name_with_standard(&sql_stmt);
 
         let df_plan = df_planner
             .sql_statement_to_plan(sql_stmt)
             .context(DatafusionPlan)?;
+        let df_plan = unnest_output_columns(df_plan, unnest_indexes).context(DatafusionPlan)?;
         let df_plan = optimize_plan(&df_plan).context(DatafusionPlan)?;
 
         debug!("Sql statement to datafusion plan, df_plan:\n{:#?}", df_plan);
@@ -927,7 +1003,7 @@ impl<'a, P: MetaProvider> PlannerDelegate<'a, P> {
                     .map(|column_schema| {
                         DFField::new_unqualified(
                             &column_schema.name,
-                            column_schema.data_type.to_arrow_data_type(),
+                            column_schema.to_arrow_data_type(),
                             column_schema.is_nullable,
                         )
                     })
@@ -1054,6 +1130,47 @@ impl<'a, P: MetaProvider> PlannerDelegate<'a, P> {
         Ok(Plan::Show(ShowPlan::ShowDatabase))
     }
 
+    fn show_process_list_to_plan(&self) -> Result<Plan> {
+        Ok(Plan::Show(ShowPlan::ShowProcessList))
+    }
+
+    fn kill_query_to_plan(&self, kill_query: KillQuery) -> Result<Plan> {
+        Ok(Plan::KillQuery(KillQueryPlan {
+            query_id: kill_query.query_id,
+        }))
+    }
+
+    fn backup_table_to_plan(&self, stmt: BackupTable) -> Result<Plan> {
+        let table_name = stmt.table_name.to_string();
+        let table = self
+            .find_table(&table_name)?
+            .context(TableNotFound { name: table_name })?;
+
+        Ok(Plan::BackupTable(BackupTablePlan {
+            table,
+            path: stmt.path,
+        }))
+    }
+
+    fn restore_table_to_plan(&self, stmt: RestoreTable) -> Result<Plan> {
+        Ok(Plan::RestoreTable(RestoreTablePlan {
+            table: stmt.table_name.to_string(),
+            path: stmt.path,
+        }))
+    }
+
+    fn verify_table_to_plan(&self, stmt: VerifyTable) -> Result<Plan> {
+        let table_name = stmt.table_name.to_string();
+        let table = self
+            .find_table(&table_name)?
+            .context(TableNotFound { name: table_name })?;
+
+        Ok(Plan::VerifyTable(VerifyTablePlan {
+            table,
+            repair: stmt.repair,
+        }))
+    }
+
     pub(crate) fn find_table(&self, table_name: &str) -> Result<Option<TableRef>> {
         let table_ref = get_table_ref(table_name);
         let resolved_table = self
@@ -1092,6 +1209,168 @@ fn normalize_func_name(sql_stmt: &mut SqlStatement) {
     });
 }
 
+/// Datafusion doesn't support the json access operators, so rewrite them into
+/// the json functions, e.g. `col->'$.a'` into `json_extract(col, '$.a')` and
+/// `col->>'$.a'` into `json_extract_string(col, '$.a')`.
+fn rewrite_json_access(sql_stmt: &mut SqlStatement) {
+    struct JsonAccessRewriter;
+
+    impl VisitorMut for JsonAccessRewriter {
+        type Break = ();
+
+        fn pre_visit_expr(&mut self, expr: &mut SqlExpr) -> ControlFlow<()> {
+            loop {
+                let func_name = match expr {
+                    SqlExpr::JsonAccess {
+                        operator: JsonOperator::Arrow,
+                        ..
+                    } => JSON_EXTRACT,
+                    SqlExpr::JsonAccess {
+                        operator: JsonOperator::LongArrow,
+                        ..
+                    } => JSON_EXTRACT_STRING,
+                    _ => break,
+                };
+
+                if let SqlExpr::JsonAccess { left, right, .. } =
+                    mem::replace(expr, SqlExpr::Value(Value::Null))
+                {
+                    // The right side is parsed greedily, e.g. `a->>'$.b' = 'c'` is parsed as
+                    // `a->>('$.b' = 'c')`, so the path is actually the leftmost operand of it.
+                    let mut rewritten = *right;
+                    replace_leftmost_operand(&mut rewritten, |path| {
+                        make_json_function(func_name, *left, path)
+                    });
+                    *expr = rewritten;
+                }
+            }
+
+            ControlFlow::Continue(())
+        }
+    }
+
+    let _ = sql_stmt.visit(&mut JsonAccessRewriter);
+}
+
+fn replace_leftmost_operand<F>(expr: &mut SqlExpr, f: F)
+where
+    F: FnOnce(SqlExpr) -> SqlExpr,
+{
+    match expr {
+        SqlExpr::BinaryOp { left, .. } | SqlExpr::JsonAccess { left, .. } => {
+            replace_leftmost_operand(left, f)
+        }
+        SqlExpr::IsNull(inner)
+        | SqlExpr::IsNotNull(inner)
+        | SqlExpr::InList { expr: inner, .. }
+        | SqlExpr::Between { expr: inner, .. }
+        | SqlExpr::Like { expr: inner, .. }
+        | SqlExpr::ILike { expr: inner, .. } => replace_leftmost_operand(inner, f),
+        _ => {
+            let operand = mem::replace(expr, SqlExpr::Value(Value::Null));
+            *expr = f(operand);
+        }
+    }
+}
+
+fn make_json_function(name: &str, json: SqlExpr, path: SqlExpr) -> SqlExpr {
+    let args = [json, path]
+        .into_iter()
+        .map(|arg| FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)))
+        .collect();
+
+    SqlExpr::Function(Function {
+        name: ObjectName(vec![Ident::new(name)]),
+        args,
+        over: None,
+        distinct: false,
+        special: false,
+        order_by: vec![],
+    })
+}
+
+/// The function to expand the array into rows.
+const UNNEST: &str = "unnest";
+
+/// Datafusion doesn't support the `unnest` function in sql, so the
+/// `unnest(col)` in the projection of the query is rewritten into `col`, and
+/// the indexes of the unnested columns in the projection are returned.
+///
+/// The columns are unnested on the output of the query by
+/// [unnest_output_columns], so the `unnest` is applied after the filter, order
+/// and limit of the query.
+fn rewrite_unnest(sql_stmt: &mut SqlStatement) -> Result<Vec<usize>> {
+    let select = match sql_stmt {
+        SqlStatement::Query(q) => match q.body.as_mut() {
+            SetExpr::Select(select) => select,
+            _ => return Ok(Vec::new()),
+        },
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut unnest_indexes = Vec::new();
+    let mut has_wildcard = false;
+    for (index, projection) in select.projection.iter_mut().enumerate() {
+        let expr = match projection {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => expr,
+            SelectItem::QualifiedWildcard(_, _) | SelectItem::Wildcard(_) => {
+                has_wildcard = true;
+                continue;
+            }
+        };
+
+        let arg = match expr {
+            SqlExpr::Function(func) if func.name.0.len() == 1 && func.name.0[0].value == UNNEST => {
+                match func.args.as_mut_slice() {
+                    [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] => {
+                        mem::replace(arg, SqlExpr::Value(Value::Null))
+                    }
+                    _ => {
+                        return UnsupportedUnnest {
+                            msg: "unnest requires exactly one argument",
+                        }
+                        .fail()
+                    }
+                }
+            }
+            _ => continue,
+        };
+        *expr = arg;
+        unnest_indexes.push(index);
+    }
+
+    // The index of the column in the output is unknown if there is a wildcard.
+    ensure!(
+        unnest_indexes.is_empty() || !has_wildcard,
+        UnsupportedUnnest {
+            msg: "unnest with wildcard in the projection",
+        }
+    );
+
+    Ok(unnest_indexes)
+}
+
+/// Unnest the array columns at the `indexes` of the output of the plan.
+fn unnest_output_columns(
+    plan: LogicalPlan,
+    indexes: &[usize],
+) -> datafusion::error::Result<LogicalPlan> {
+    if indexes.is_empty() {
+        return Ok(plan);
+    }
+
+    let columns = indexes
+        .iter()
+        .map(|i| plan.schema().field(*i).qualified_column())
+        .collect::<Vec<_>>();
+    columns
+        .into_iter()
+        .try_fold(LogicalPlanBuilder::from(plan), |builder, column| {
+            builder.unnest_column(column)
+        })?
+        .build()
+}
+
 #[derive(Debug)]
 enum InsertMode {
     // Insert the value in expr with given index directly.
@@ -1142,6 +1421,27 @@ fn parse_data_value_from_expr(data_type: DatumKind, expr: &mut Expr) -> Result<D
     }
 }
 
+/// Parse [Datum] of the column from the [Expr], the elements of the array
+/// literal are parsed by the element type of the column.
+fn parse_column_value_from_expr(column_schema: &ColumnSchema, expr: &mut Expr) -> Result<Datum> {
+    match (column_schema.element_type, expr) {
+        (Some(element_type), Expr::Array(array)) => {
+            let elements = array
+                .elem
+                .iter_mut()
+                .map(|elem| match elem {
+                    Expr::Value(Value::Null) => Ok(Datum::Null),
+                    elem => parse_data_value_from_expr(element_type, elem),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            ArrayValue::try_new(element_type, elements)
+                .map(Datum::Array)
+                .context(InsertConvertArray)
+        }
+        (_, expr) => parse_data_value_from_expr(column_schema.data_type, expr),
+    }
+}
+
 /// Build RowGroup
 fn build_row_group(
     schema: Schema,
@@ -1171,7 +1471,7 @@ fn build_row_group(
                                 index: *index,
                             })?;
 
-                            let datum = parse_data_value_from_expr(column_schema.data_type, expr)?;
+                            let datum = parse_column_value_from_expr(column_schema, expr)?;
                             row_builder = row_builder.append_datum(datum).context(BuildRow)?;
                         }
                         InsertMode::Null => {
@@ -1284,6 +1584,9 @@ fn parse_column(col: &ColumnDef) -> Result<ColumnSchema> {
     let mut is_unsign = false;
     let mut comment = String::new();
     let mut default_value = None;
+    let mut aggregation = None;
+    let mut encoding = None;
+    let mut compression = None;
     for option_def in &col.options {
         if matches!(option_def.option, ColumnOption::NotNull) {
             is_nullable = false;
@@ -1297,6 +1600,12 @@ fn parse_column(col: &ColumnDef) -> Result<ColumnSchema> {
             default_value = Some(default_value_expr);
         } else if let Some(v) = parser::get_column_comment(&option_def.option) {
             comment = v;
+        } else if let Some(v) = parser::get_column_aggregation(&option_def.option) {
+            aggregation = Some(v);
+        } else if let Some(v) = parser::get_column_encoding(&option_def.option) {
+            encoding = Some(v);
+        } else if let Some(v) = parser::get_column_compression(&option_def.option) {
+            compression = Some(v);
         }
     }
 
@@ -1311,13 +1620,51 @@ fn parse_column(col: &ColumnDef) -> Result<ColumnSchema> {
         .is_tag(is_tag)
         .is_dictionary(is_dictionary)
         .comment(comment)
-        .default_value(default_value);
+        .default_value(default_value)
+        .aggregation(aggregation)
+        .encoding(encoding)
+        .compression(compression)
+        .decimal_type(parse_decimal_type(col)?)
+        .element_type(parse_element_type(col)?);
 
     builder.build().context(InvalidColumnSchema {
         column_name: &col.name.value,
     })
 }
 
+/// The scale is zero if only the precision is given, and the default decimal
+/// type is used if neither is given.
+fn parse_decimal_type(col: &ColumnDef) -> Result<Option<DecimalType>> {
+    let info = match &col.data_type {
+        SqlDataType::Decimal(info) | SqlDataType::Numeric(info) => info,
+        _ => return Ok(None),
+    };
+
+    let decimal_type = match info {
+        ExactNumberInfo::None => Ok(DecimalType::default()),
+        ExactNumberInfo::Precision(p) => DecimalType::try_new(*p, 0),
+        ExactNumberInfo::PrecisionAndScale(p, s) => DecimalType::try_new(*p, *s),
+    }
+    .context(InvalidDecimalType {
+        column_name: &col.name.value,
+    })?;
+
+    Ok(Some(decimal_type))
+}
+
+/// The element type of the array column, and the default element type is used
+/// if it's not given.
+fn parse_element_type(col: &ColumnDef) -> Result<Option<DatumKind>> {
+    match &col.data_type {
+        SqlDataType::Array(Some(element_type)) => DatumKind::try_from(element_type.as_ref())
+            .map(Some)
+            .context(InvalidElementType {
+                column_name: &col.name.value,
+            }),
+        _ => Ok(None),
+    }
+}
+
 // Ensure default value option of columns are valid.
 fn ensure_column_default_value_valid<P: MetaProvider>(
     columns: &[ColumnSchema],
@@ -1359,11 +1706,11 @@ fn ensure_column_default_value_valid<P: MetaProvider>(
                 .data_type(&arrow_schema)
                 .context(DatafusionDataType)?;
             ensure! {
-                can_cast_types(&from_type, &column_def.data_type.into()),
+                can_cast_types(&from_type, &column_def.to_arrow_data_type()),
                 InvalidDefaultValueCoercion::<Expr, ArrowDataType, ArrowDataType>{
                     expr: expr.clone(),
                     from: from_type,
-                    to: column_def.data_type.into(),
+                    to: column_def.to_arrow_data_type(),
                 },
             }
         }
@@ -1514,6 +1861,11 @@ pub mod tests {
                         comment: "",
                         escaped_name: "c1",
                         default_value: None,
+                        aggregation: None,
+                        encoding: None,
+                        compression: None,
+                        decimal_type: None,
+                        element_type: None,
                     },
                     ColumnSchema {
                         id: 2,
@@ -1525,6 +1877,11 @@ pub mod tests {
                         comment: "",
                         escaped_name: "ts",
                         default_value: None,
+                        aggregation: None,
+                        encoding: None,
+                        compression: None,
+                        decimal_type: None,
+                        element_type: None,
                     },
                     ColumnSchema {
                         id: 3,
@@ -1536,6 +1893,11 @@ pub mod tests {
                         comment: "",
                         escaped_name: "c3",
                         default_value: None,
+                        aggregation: None,
+                        encoding: None,
+                        compression: None,
+                        decimal_type: None,
+                        element_type: None,
                     },
                     ColumnSchema {
                         id: 4,
@@ -1554,6 +1916,11 @@ pub mod tests {
                                 ),
                             ),
                         ),
+                        aggregation: None,
+                        encoding: None,
+                        compression: None,
+                        decimal_type: None,
+                        element_type: None,
                     },
                     ColumnSchema {
                         id: 5,
@@ -1581,6 +1948,11 @@ pub mod tests {
                                 ),
                             },
                         ),
+                        aggregation: None,
+                        encoding: None,
+                        compression: None,
+                        decimal_type: None,
+                        element_type: None,
                     },
                     ColumnSchema {
                         id: 6,
@@ -1599,6 +1971,11 @@ pub mod tests {
                                 },
                             ),
                         ),
+                        aggregation: None,
+                        encoding: None,
+                        compression: None,
+                        decimal_type: None,
+                        element_type: None,
                     },
                 ],
             },
@@ -1732,6 +2109,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 2,
@@ -1743,6 +2125,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 3,
@@ -1754,6 +2141,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 4,
@@ -1765,6 +2157,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 5,
@@ -1776,6 +2173,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field3",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 6,
@@ -1787,6 +2189,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field4",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                     ],
                 },
@@ -1813,6 +2220,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 2,
@@ -1824,6 +2236,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 3,
@@ -1835,6 +2252,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 4,
@@ -1846,6 +2268,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 5,
@@ -1857,6 +2284,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field3",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 6,
@@ -1868,6 +2300,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field4",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                     ],
                 },
@@ -1947,6 +2384,56 @@ pub mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_backup_and_restore_statement_to_plan() {
+        let sql = "backup table test_tablex to '/data/backup';";
+        assert!(quick_test(sql, "").is_err());
+
+        let sql = "restore table test_tablex from '/data/backup';";
+        quick_test(
+            sql,
+            r#"RestoreTable(
+    RestoreTablePlan {
+        table: "test_tablex",
+        path: "/data/backup",
+    },
+)"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_as_of_query_to_plan() {
+        let sql = "select * from test_table as of sequence 10 where key1 = 'a'";
+        let plan = match sql_to_logical_plan(sql).unwrap() {
+            Plan::Query(v) => v,
+            other => panic!("Expected query plan, given:{other:?}"),
+        };
+        let planned = plan.tables.get(TableReference::bare("test_table")).unwrap();
+        assert_eq!(Some(ReadAsOf::Sequence(10)), planned.as_of);
+
+        let tables = vec![
+            TableAsOf {
+                table_name: ObjectName(vec![Ident::new("db"), Ident::new("t1")]).into(),
+                as_of: AsOf::Timestamp("1672531200000".to_string()),
+            },
+            TableAsOf {
+                table_name: ObjectName(vec![Ident::new("t2")]).into(),
+                as_of: AsOf::Timestamp("2023-01-01T00:00:00Z".to_string()),
+            },
+        ];
+        let tables = tables_as_of(&tables).unwrap();
+        let expect = ReadAsOf::Timestamp(Timestamp::new(1672531200000));
+        assert_eq!(Some(&expect), tables.get("t1"));
+        assert_eq!(Some(&expect), tables.get("t2"));
+
+        let tables = vec![TableAsOf {
+            table_name: ObjectName(vec![Ident::new("t1")]).into(),
+            as_of: AsOf::Timestamp("yesterday".to_string()),
+        }];
+        assert!(tables_as_of(&tables).is_err());
+    }
+
     #[test]
     fn test_desc_statement_to_plan() {
         let sql = "desc test_tablex;";
@@ -1977,6 +2464,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 2,
@@ -1988,6 +2480,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 3,
@@ -1999,6 +2496,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 4,
@@ -2010,6 +2512,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 5,
@@ -2021,6 +2528,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field3",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 6,
@@ -2032,6 +2544,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field4",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                     ],
                 },
@@ -2075,6 +2592,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 2,
@@ -2086,6 +2608,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 3,
@@ -2097,6 +2624,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 4,
@@ -2108,6 +2640,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 5,
@@ -2119,6 +2656,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field3",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 6,
@@ -2130,6 +2672,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field4",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                     ],
                 },
@@ -2152,6 +2699,11 @@ pub mod tests {
                     comment: "",
                     escaped_name: "dic",
                     default_value: None,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    decimal_type: None,
+                    element_type: None,
                 },
             ],
         ),
@@ -2191,6 +2743,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 2,
@@ -2202,6 +2759,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 3,
@@ -2213,6 +2775,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 4,
@@ -2224,6 +2791,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 5,
@@ -2235,6 +2807,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field3",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 6,
@@ -2246,6 +2823,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field4",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                     ],
                 },
@@ -2268,6 +2850,11 @@ pub mod tests {
                     comment: "",
                     escaped_name: "add_col",
                     default_value: None,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    decimal_type: None,
+                    element_type: None,
                 },
             ],
         ),
@@ -2307,6 +2894,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 2,
@@ -2318,6 +2910,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "key2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 3,
@@ -2329,6 +2926,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field1",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 4,
@@ -2340,6 +2942,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field2",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 5,
@@ -2351,6 +2958,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field3",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                         ColumnSchema {
                             id: 6,
@@ -2362,6 +2974,11 @@ pub mod tests {
                             comment: "",
                             escaped_name: "field4",
                             default_value: None,
+                            aggregation: None,
+                            encoding: None,
+                            compression: None,
+                            decimal_type: None,
+                            element_type: None,
                         },
                     ],
                 },
@@ -2414,6 +3031,11 @@ pub mod tests {
                                 comment: "",
                                 escaped_name: "key1",
                                 default_value: None,
+                                aggregation: None,
+                                encoding: None,
+                                compression: None,
+                                decimal_type: None,
+                                element_type: None,
                             },
                             ColumnSchema {
                                 id: 2,
@@ -2425,6 +3047,11 @@ pub mod tests {
                                 comment: "",
                                 escaped_name: "key2",
                                 default_value: None,
+                                aggregation: None,
+                                encoding: None,
+                                compression: None,
+                                decimal_type: None,
+                                element_type: None,
                             },
                             ColumnSchema {
                                 id: 3,
@@ -2436,6 +3063,11 @@ pub mod tests {
                                 comment: "",
                                 escaped_name: "field1",
                                 default_value: None,
+                                aggregation: None,
+                                encoding: None,
+                                compression: None,
+                                decimal_type: None,
+                                element_type: None,
                             },
                             ColumnSchema {
                                 id: 4,
@@ -2447,6 +3079,11 @@ pub mod tests {
                                 comment: "",
                                 escaped_name: "field2",
                                 default_value: None,
+                                aggregation: None,
+                                encoding: None,
+                                compression: None,
+                                decimal_type: None,
+                                element_type: None,
                             },
                             ColumnSchema {
                                 id: 5,
@@ -2458,6 +3095,11 @@ pub mod tests {
                                 comment: "",
                                 escaped_name: "field3",
                                 default_value: None,
+                                aggregation: None,
+                                encoding: None,
+                                compression: None,
+                                decimal_type: None,
+                                element_type: None,
                             },
                             ColumnSchema {
                                 id: 6,
@@ -2469,6 +3111,11 @@ pub mod tests {
                                 comment: "",
                                 escaped_name: "field4",
                                 default_value: None,
+                                aggregation: None,
+                                encoding: None,
+                                compression: None,
+                                decimal_type: None,
+                                element_type: None,
                             },
                         ],
                     },
@@ -2530,6 +3177,66 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_rewrite_json_access() {
+        let cases = [
+            (
+                "select attrs->'$.a', attrs->>'$.b' from t",
+                "SELECT json_extract(attrs, '$.a'), json_extract_string(attrs, '$.b') FROM t",
+            ),
+            (
+                "select * from t where attrs->>'$.a' = 'x' and id > 1",
+                "SELECT * FROM t WHERE json_extract_string(attrs, '$.a') = 'x' AND id > 1",
+            ),
+            (
+                "select attrs->'$.a'->>'$.b' from t where attrs->>'$.c' is null",
+                "SELECT json_extract_string(json_extract(attrs, '$.a'), '$.b') FROM t WHERE json_extract_string(attrs, '$.c') IS NULL",
+            ),
+        ];
+
+        for (sql, expect) in cases {
+            let mut statements = Parser::parse_sql(sql).unwrap();
+            let mut sql_stmt = match statements.remove(0) {
+                Statement::Standard(v) => *v,
+                _ => unreachable!(),
+            };
+            rewrite_json_access(&mut sql_stmt);
+            assert_eq!(expect, sql_stmt.to_string());
+        }
+    }
+
+    #[test]
+    fn test_rewrite_unnest() {
+        let cases = [
+            (
+                "select unnest(roles), name from t",
+                "SELECT roles, name FROM t",
+                Ok(vec![0]),
+            ),
+            (
+                "select name, unnest(roles) as role from t where id > 1",
+                "SELECT name, roles AS role FROM t WHERE id > 1",
+                Ok(vec![1]),
+            ),
+            ("select * from t", "SELECT * FROM t", Ok(vec![])),
+            ("select *, unnest(roles) from t", "", Err(())),
+            ("select unnest(roles, 1) from t", "", Err(())),
+        ];
+
+        for (sql, expect_sql, expect_indexes) in cases {
+            let mut statements = Parser::parse_sql(sql).unwrap();
+            let mut sql_stmt = match statements.remove(0) {
+                Statement::Standard(v) => *v,
+                _ => unreachable!(),
+            };
+            let indexes = rewrite_unnest(&mut sql_stmt).map_err(|_| ());
+            assert_eq!(expect_indexes, indexes, "sql:{sql}");
+            if indexes.is_ok() {
+                assert_eq!(expect_sql, sql_stmt.to_string());
+            }
+        }
+    }
+
     #[test]
     fn test_parse_data_value() {
         // normal cases
@@ -33,7 +33,7 @@ use datafusion::{
 use df_operator::{registry::FunctionRegistry, scalar::ScalarUdf, udaf::AggregateUdf};
 use macros::define_result;
 use snafu::{OptionExt, ResultExt, Snafu};
-use table_engine::table::TableRef;
+use table_engine::table::{ReadAsOf, TableRef};
 
 use crate::{
     config::DynamicConfig,
@@ -238,6 +238,8 @@ pub struct ContextProviderAdapter<'a, P> {
     config: ConfigOptions,
     /// Hint for logical plan creation.
     dyn_config: &'a DynamicConfig,
+    /// Table name => the historical point to read the table at.
+    table_as_of: HashMap<String, ReadAsOf>,
 }
 
 impl<'a, P: MetaProvider> ContextProviderAdapter<'a, P> {
@@ -261,9 +263,16 @@ impl<'a, P: MetaProvider> ContextProviderAdapter<'a, P> {
             meta_provider,
             config,
             dyn_config,
+            table_as_of: HashMap::new(),
         }
     }
 
+    /// Read the tables at the historical points, see [ReadAsOf].
+    pub fn with_table_as_of(mut self, table_as_of: HashMap<String, ReadAsOf>) -> Self {
+        self.table_as_of = table_as_of;
+        self
+    }
+
     /// Consumes the adapter, returning the tables used during planning if no
     /// error occurs, otherwise returning the error
     pub fn try_into_container(self) -> Result<TableContainer> {
@@ -344,11 +353,13 @@ impl<'a, P: MetaProvider> ContextProvider for ContextProviderAdapter<'a, P> {
                     .enable_dist_query_push_down
                     .load(std::sync::atomic::Ordering::Relaxed);
 
+                let as_of = self.table_as_of.get(table.name()).copied();
                 let planned_table = PlannedTable {
                     catalog,
                     schema,
                     table,
                     enable_dist_query_push_down,
+                    as_of,
                 };
 
                 self.table_cache
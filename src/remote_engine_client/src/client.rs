@@ -20,7 +20,7 @@
 use std::{
     collections::HashMap,
     pin::Pin,
-    sync::{Arc, Mutex},
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -46,7 +46,8 @@ use snafu::{ensure, OptionExt, ResultExt};
 use table_engine::{
     remote::model::{
         AlterTableOptionsRequest, AlterTableSchemaRequest, ExecutePlanRequest, GetTableInfoRequest,
-        ReadRequest, TableIdentifier, TableInfo, WriteBatchRequest, WriteBatchResult, WriteRequest,
+        ReadRequest, RemoteMetrics, RemoteMetricsRef, TableIdentifier, TableInfo,
+        WriteBatchRequest, WriteBatchResult, WriteRequest,
     },
     table::{SchemaId, TableId},
 };
@@ -530,7 +531,7 @@ pub struct ClientReadRecordBatchStream {
     pub table_ident: TableIdentifier,
     pub response_stream: Streaming<remote_engine::ReadResponse>,
     pub record_schema: RecordSchema,
-    pub remote_metrics: Arc<Mutex<Option<String>>>,
+    pub remote_metrics: RemoteMetricsRef,
 }
 
 impl ClientReadRecordBatchStream {
@@ -539,7 +540,7 @@ impl ClientReadRecordBatchStream {
         table_ident: TableIdentifier,
         response_stream: Streaming<remote_engine::ReadResponse>,
         record_schema: RecordSchema,
-        remote_metrics: Arc<Mutex<Option<String>>>,
+        remote_metrics: RemoteMetricsRef,
     ) -> Self {
         Self {
             endpoint,
@@ -574,7 +575,10 @@ impl Stream for ClientReadRecordBatchStream {
                         Arrow(v) => Poll::Ready(Some(convert_arrow_payload(v))),
                         Metric(v) => {
                             let mut remote_metrics = this.remote_metrics.lock().unwrap();
-                            *remote_metrics = Some(v.metric);
+                            *remote_metrics = Some(RemoteMetrics {
+                                endpoint: this.endpoint.to_string(),
+                                metrics: v.metric,
+                            });
                             Poll::Ready(None)
                         }
                     },
@@ -25,7 +25,7 @@ use std::{
 use cluster::config::SchemaConfig;
 use common_types::schema::TIMESTAMP_COLUMN;
 use meta_client::types::ShardId;
-use proxy::{forward, hotspot, SubTableAccessPerm};
+use proxy::{forward, hotspot, query_cache, SubTableAccessPerm};
 use router::{
     endpoint::Endpoint,
     rule_based::{ClusterView, RuleList},
@@ -162,8 +162,14 @@ pub struct ServerConfig {
     /// Config of dedup query
     pub query_dedup: QueryDedupConfig,
 
+    /// Config of query result cache
+    pub query_cache: query_cache::Config,
+
     /// Whether enable to access partition table
     pub sub_table_access_perm: SubTableAccessPerm,
+
+    /// Tokens accepted by the influxdb 2.x api, and no auth if empty
+    pub influxdb_tokens: Vec<String>,
 }
 
 impl Default for ServerConfig {
@@ -185,7 +191,9 @@ impl Default for ServerConfig {
             hotspot: hotspot::Config::default(),
             remote_client: remote_engine_client::Config::default(),
             query_dedup: QueryDedupConfig::default(),
+            query_cache: query_cache::Config::default(),
             sub_table_access_perm: SubTableAccessPerm::default(),
+            influxdb_tokens: Vec::new(),
         }
     }
 }
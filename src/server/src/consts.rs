@@ -23,7 +23,18 @@ pub const CATALOG_HEADER: &str = "x-horaedb-catalog";
 pub const SCHEMA_HEADER: &str = "x-horaedb-schema";
 /// Header of tenant name
 pub const TENANT_HEADER: &str = "x-horaedb-access-tenant";
+/// Header of bypassing the query cache
+pub const BYPASS_QUERY_CACHE_HEADER: &str = "x-horaedb-bypass-query-cache";
+/// Header of the query limits in the form of the hints, e.g.
+/// `MAX_EXECUTION_TIME(10s) MAX_RESULT_ROWS(1000)`
+pub const QUERY_LIMITS_HEADER: &str = "x-horaedb-query-limits";
+/// Header of authorization
+pub const AUTHORIZATION_HEADER: &str = "authorization";
 /// Header of content encoding type
 pub const CONTENT_ENCODING_HEADER: &str = "content-encoding";
 
 pub const GZIP_ENCODING: &str = "gzip";
+/// Header of content type
+pub const CONTENT_TYPE_HEADER: &str = "content-type";
+
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
@@ -36,6 +36,7 @@ use horaedbproto::{
 use logger::{info, warn};
 use macros::define_result;
 use notifier::notifier::RequestNotifiers;
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::MetricsServiceServer;
 use proxy::{
     forward,
     hotspot::HotspotRecorder,
@@ -54,13 +55,14 @@ use self::remote_engine_service::QueryDedup;
 use crate::{
     config::QueryDedupConfig,
     grpc::{
-        meta_event_service::MetaServiceImpl, remote_engine_service::RemoteEngineServiceImpl,
-        storage_service::StorageServiceImpl,
+        meta_event_service::MetaServiceImpl, otlp_service::OtlpMetricsServiceImpl,
+        remote_engine_service::RemoteEngineServiceImpl, storage_service::StorageServiceImpl,
     },
 };
 
 mod meta_event_service;
 mod metrics;
+mod otlp_service;
 mod remote_engine_service;
 mod storage_service;
 
@@ -161,6 +163,7 @@ pub struct RpcServices {
     rpc_server: StorageServiceServer<StorageServiceImpl>,
     meta_rpc_server: Option<MetaEventServiceServer<MetaServiceImpl>>,
     remote_engine_server: RemoteEngineServiceServer<RemoteEngineServiceImpl>,
+    otlp_metrics_server: MetricsServiceServer<OtlpMetricsServiceImpl>,
     runtime: Arc<Runtime>,
     stop_tx: Option<Sender<()>>,
     join_handle: Option<JoinHandle<()>>,
@@ -171,6 +174,7 @@ impl RpcServices {
         let rpc_server = self.rpc_server.clone();
         let meta_rpc_server = self.meta_rpc_server.clone();
         let remote_engine_server = self.remote_engine_server.clone();
+        let otlp_metrics_server = self.otlp_metrics_server.clone();
         let serve_addr = self.serve_addr;
         let (stop_tx, stop_rx) = oneshot::channel();
         let join_handle = self.runtime.spawn(async move {
@@ -186,6 +190,9 @@ impl RpcServices {
             info!("Grpc server serves remote engine rpc service");
             router = router.add_service(remote_engine_server);
 
+            info!("Grpc server serves otlp metrics service");
+            router = router.add_service(otlp_metrics_server);
+
             router
                 .serve_with_shutdown(serve_addr, stop_rx.map(drop))
                 .await
@@ -325,6 +332,12 @@ impl Builder {
 
         let runtime = runtimes.default_runtime.clone();
 
+        let otlp_metrics_server = MetricsServiceServer::new(OtlpMetricsServiceImpl {
+            proxy: proxy.clone(),
+            runtimes: runtimes.clone(),
+            timeout: self.timeout,
+        });
+
         let storage_service = StorageServiceImpl {
             proxy,
             runtimes,
@@ -339,6 +352,7 @@ impl Builder {
             rpc_server,
             meta_rpc_server,
             remote_engine_server,
+            otlp_metrics_server,
             runtime,
             stop_tx: None,
             join_handle: None,
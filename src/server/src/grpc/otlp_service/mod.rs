@@ -0,0 +1,124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! OTLP/gRPC metrics service.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use http::StatusCode;
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::MetricsService;
+use proxy::{
+    context::RequestContext,
+    otlp::types::{ExportRequest, ExportResponse},
+    Proxy,
+};
+use table_engine::engine::EngineRuntimes;
+use time_ext::InstantExt;
+
+use crate::{
+    consts::{CATALOG_HEADER, SCHEMA_HEADER},
+    grpc::metrics::GRPC_HANDLER_DURATION_HISTOGRAM_VEC,
+};
+
+#[derive(Clone)]
+pub struct OtlpMetricsServiceImpl {
+    pub proxy: Arc<Proxy>,
+    pub runtimes: Arc<EngineRuntimes>,
+    pub timeout: Option<Duration>,
+}
+
+#[async_trait]
+impl MetricsService for OtlpMetricsServiceImpl {
+    async fn export(
+        &self,
+        req: tonic::Request<ExportRequest>,
+    ) -> Result<tonic::Response<ExportResponse>, tonic::Status> {
+        let begin_instant = Instant::now();
+
+        let resp = self.export_internal(req).await;
+
+        GRPC_HANDLER_DURATION_HISTOGRAM_VEC
+            .handle_otlp_export
+            .observe(begin_instant.saturating_elapsed().as_secs_f64());
+
+        resp
+    }
+}
+
+impl OtlpMetricsServiceImpl {
+    async fn export_internal(
+        &self,
+        req: tonic::Request<ExportRequest>,
+    ) -> Result<tonic::Response<ExportResponse>, tonic::Status> {
+        let ctx = self.build_context(&req)?;
+        let req = req.into_inner();
+        let proxy = self.proxy.clone();
+
+        let join_handle = self
+            .runtimes
+            .write_runtime
+            .spawn(async move { proxy.handle_otlp_metrics_export(ctx, req).await });
+
+        match join_handle.await {
+            Ok(Ok(resp)) => Ok(tonic::Response::new(resp)),
+            Ok(Err(e)) => Err(convert_status(e.code(), e.error_message())),
+            Err(e) => Err(tonic::Status::internal(format!(
+                "fail to join the spawn task, err:{e:?}"
+            ))),
+        }
+    }
+
+    /// Catalog and schema are taken from the same headers as the http api,
+    /// and fallback to the default ones if not set.
+    fn build_context<T>(&self, req: &tonic::Request<T>) -> Result<RequestContext, tonic::Status> {
+        let get_header = |key: &str| {
+            req.metadata()
+                .get(key)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let catalog_manager = &self.proxy.instance().catalog_manager;
+        let catalog = get_header(CATALOG_HEADER)
+            .unwrap_or_else(|| catalog_manager.default_catalog_name().to_string());
+        let schema = get_header(SCHEMA_HEADER)
+            .unwrap_or_else(|| catalog_manager.default_schema_name().to_string());
+
+        RequestContext::builder()
+            .catalog(catalog)
+            .schema(schema)
+            .timeout(self.timeout)
+            .build()
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))
+    }
+}
+
+/// Convert the http status code to the grpc one, the retryable errors defined
+/// by the otlp protocol are mapped to `Unavailable`.
+fn convert_status(code: StatusCode, msg: String) -> tonic::Status {
+    match code {
+        StatusCode::BAD_REQUEST => tonic::Status::invalid_argument(msg),
+        StatusCode::NOT_FOUND => tonic::Status::not_found(msg),
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => {
+            tonic::Status::unavailable(msg)
+        }
+        _ => tonic::Status::internal(msg),
+    }
+}
@@ -55,6 +55,7 @@ use proxy::{
 use query_engine::{
     context::Context as QueryContext,
     datafusion_impl::physical_plan::{DataFusionPhysicalPlanAdapter, TypedPlan},
+    limits::QueryLimits,
     physical_planner::PhysicalPlanRef,
     QueryEngineRef, QueryEngineType,
 };
@@ -248,7 +249,7 @@ impl Stream for RemoteExecStream {
             match this.inner.poll_next_unpin(cx) {
                 Poll::Ready(Some(res)) => {
                     // If the request is explain, we try drain the stream to get the metrics.
-                    if !is_explain {
+                    if !is_explain || res.is_err() {
                         return Poll::Ready(Some(res.map(RecordBatchWithMetric::RecordBatch)));
                     }
                 }
@@ -793,8 +794,6 @@ impl RemoteEngineServiceImpl {
         let physical_plan: PhysicalPlanRef = Arc::new(DataFusionPhysicalPlanAdapter::new(
             TypedPlan::Remote(encoded_plan),
         ));
-        // TODO: Use in handle_execute_plan fn to build stream with metrics
-        let physical_plan_for_explain = ctx.explain.map(|_| physical_plan.clone());
 
         let QueryDedup {
             config,
@@ -832,10 +831,8 @@ impl RemoteEngineServiceImpl {
         }
 
         let stream = StreamWithMetric::new(Box::pin(ReceiverStream::new(rx)), metric);
-        Ok(RemoteExecStream::new(
-            Box::pin(stream),
-            physical_plan_for_explain,
-        ))
+        // The explain requests are never dedupped, see `execute_physical_plan`.
+        Ok(RemoteExecStream::new(Box::pin(stream), None))
     }
 
     async fn alter_table_schema_internal(
@@ -981,7 +978,20 @@ impl RemoteEngineService for RemoteEngineServiceImpl {
                 .await
         }
 
-        let record_stream_result = match self.query_dedup.clone() {
+        // The explain request needs the metrics of its own execution, so it can't be
+        // dedupped.
+        let is_explain = request
+            .get_ref()
+            .context
+            .as_ref()
+            .map(|ctx| ctx.explain.is_some())
+            .unwrap_or(false);
+        let query_dedup = if is_explain {
+            None
+        } else {
+            self.query_dedup.clone()
+        };
+        let record_stream_result = match query_dedup {
             Some(query_dedup) => self
                 .dedup_execute_physical_plan_internal(query_dedup, request)
                 .await
@@ -1252,12 +1262,15 @@ fn create_query_ctx(
         QueryPriority::High => Priority::High,
     };
 
+    // The limits of the query are enforced by the node receiving it, the sub
+    // plans are not limited again.
     QueryContext {
         request_id,
         deadline,
         default_catalog,
         default_schema,
         priority,
+        limits: QueryLimits::default(),
     }
 }
 
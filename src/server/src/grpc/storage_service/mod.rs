@@ -35,11 +35,13 @@ use horaedbproto::{
     },
 };
 use http::StatusCode;
-use proxy::{Context, Proxy, FORWARDED_FROM};
+use proxy::{Context, Proxy, FORWARDED_AS_ADMIN, FORWARDED_FROM};
 use table_engine::engine::EngineRuntimes;
 use time_ext::InstantExt;
 
-use crate::grpc::metrics::GRPC_HANDLER_DURATION_HISTOGRAM_VEC;
+use crate::{
+    consts::BYPASS_QUERY_CACHE_HEADER, grpc::metrics::GRPC_HANDLER_DURATION_HISTOGRAM_VEC,
+};
 
 #[derive(Clone)]
 pub struct StorageServiceImpl {
@@ -148,7 +150,8 @@ impl StorageService for StorageServiceImpl {
     ) -> Result<tonic::Response<Self::StreamSqlQueryStream>, tonic::Status> {
         let begin_instant = Instant::now();
         let proxy = self.proxy.clone();
-        let ctx = Context::new(self.timeout, get_forwarded_from(&req));
+        let ctx = Context::new(self.timeout, get_forwarded_from(&req))
+            .with_bypass_query_cache(get_bypass_query_cache(&req));
 
         let stream = self.stream_sql_query_internal(ctx, proxy, req).await;
 
@@ -166,6 +169,25 @@ fn get_forwarded_from<T>(req: &tonic::Request<T>) -> Option<String> {
         .map(|value| value.to_str().unwrap().to_string())
 }
 
+/// The admin flag is only accepted on the requests forwarded by other nodes.
+fn get_forwarded_as_admin<T>(req: &tonic::Request<T>) -> bool {
+    let metadata = req.metadata();
+    metadata.get(FORWARDED_FROM).is_some()
+        && metadata
+            .get(FORWARDED_AS_ADMIN)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+}
+
+fn get_bypass_query_cache<T>(req: &tonic::Request<T>) -> bool {
+    req.metadata()
+        .get(BYPASS_QUERY_CACHE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
 // TODO: Use macros to simplify duplicate code
 impl StorageServiceImpl {
     async fn route_internal(
@@ -236,7 +258,9 @@ impl StorageServiceImpl {
         &self,
         req: tonic::Request<SqlQueryRequest>,
     ) -> Result<tonic::Response<SqlQueryResponse>, tonic::Status> {
-        let ctx = Context::new(self.timeout, get_forwarded_from(&req));
+        let ctx = Context::new(self.timeout, get_forwarded_from(&req))
+            .with_bypass_query_cache(get_bypass_query_cache(&req))
+            .with_is_admin(get_forwarded_as_admin(&req));
         let proxy = self.proxy.clone();
 
         let join_handle = self
@@ -36,13 +36,21 @@ use logger::{error, info, RuntimeLevel};
 use macros::define_result;
 use profile::Profiler;
 use prom_remote_api::web;
+use prost::Message;
 use proxy::{
     context::RequestContext,
-    handlers::{self},
+    handlers::{
+        self,
+        admin::{KillQueryRequest, TableBackupRequest, TableVerifyRequest},
+    },
     http::sql::{convert_output, Request},
-    influxdb::types::{InfluxqlParams, InfluxqlRequest, WriteParams, WriteRequest},
+    influxdb::types::{
+        InfluxqlParams, InfluxqlRequest, QueryV2Params, WriteParams, WriteRequest, WriteV2Params,
+    },
     instance::InstanceRef,
-    opentsdb::types::{PutParams, PutRequest},
+    limiter,
+    opentsdb::types::{PutParams, PutRequest, QueryParams, QueryRequest, SuggestParams},
+    otlp::types::ExportRequest,
     Proxy,
 };
 use router::endpoint::Endpoint;
@@ -61,7 +69,10 @@ use warp::{
 };
 
 use crate::{
-    consts::{self, CONTENT_ENCODING_HEADER, GZIP_ENCODING},
+    consts::{
+        self, AUTHORIZATION_HEADER, CONTENT_ENCODING_HEADER, CONTENT_TYPE_HEADER, GZIP_ENCODING,
+        PROTOBUF_CONTENT_TYPE,
+    },
     error_util,
     metrics::{self, HTTP_HANDLER_DURATION_HISTOGRAM_VEC},
 };
@@ -71,6 +82,9 @@ pub enum Error {
     #[snafu(display("Failed to create request context, err:{}", source))]
     CreateContext { source: proxy::context::Error },
 
+    #[snafu(display("Failed to parse query limits, err:{}", source))]
+    ParseQueryLimits { source: proxy::limiter::Error },
+
     #[snafu(display("Failed to handle request, err:{}", source))]
     HandleRequest { source: GenericError },
 
@@ -134,6 +148,12 @@ pub enum Error {
     #[snafu(display("Unsupported content encoding type, value:{}.", encoding_type))]
     UnspportedContentEncodingType { encoding_type: String },
 
+    #[snafu(display("Fail to decode otlp request, err:{}.", source))]
+    DecodeOtlpRequest { source: prost::DecodeError },
+
+    #[snafu(display("Unauthorized request, msg:{}.", msg))]
+    Unauthorized { msg: String },
+
     #[snafu(display("Server already started.\nBacktrace:\n{}", backtrace))]
     AlreadyStarted { backtrace: Backtrace },
 
@@ -171,6 +191,26 @@ impl TryFrom<&str> for ContentEncodingType {
     }
 }
 
+/// Decompress the body according to the `content-encoding` header.
+fn decode_content(body: Bytes, encoding: Option<String>) -> Result<Bytes> {
+    let encoding = match encoding {
+        Some(v) => v,
+        None => return Ok(body),
+    };
+
+    match ContentEncodingType::try_from(encoding.as_str())? {
+        ContentEncodingType::Gzip => {
+            let bytes = body.as_bytes();
+            let mut decoder = GzDecoder::new(bytes);
+            let mut decompressed_data = Vec::with_capacity(bytes.len() * 2);
+            decoder
+                .read_to_end(&mut decompressed_data)
+                .context(UnGzip)?;
+            Ok(decompressed_data.into())
+        }
+    }
+}
+
 /// Http service
 ///
 /// Endpoints beginning with /debug are for internal use, and may subject to
@@ -236,11 +276,18 @@ impl Service {
             .or(self.metrics())
             .or(self.sql())
             .or(self.influxdb_api())
+            .or(self.influxdb_v2_api())
             .or(self.opentsdb_api())
+            .or(self.otlp_api())
             .or(self.prom_api())
             .or(self.route())
             // admin APIs
             .or(self.admin_block())
+            .or(self.admin_rate_limit())
+            .or(self.admin_backup())
+            .or(self.admin_restore())
+            .or(self.admin_verify())
+            .or(self.admin_kill_query())
             // debug APIs
             .or(self.flush_memtable())
             .or(self.update_log_level())
@@ -417,7 +464,74 @@ impl Service {
         warp::path!("influxdb" / "v1" / ..).and(write_api.or(query_api))
     }
 
+    /// for write api:
+    ///     POST `/api/v2/write`
+    ///
+    /// for query api:
+    ///     POST `/api/v2/query`
+    ///
+    /// It's derived from the influxdb 2.x api described in doc of 2.7:
+    ///     https://docs.influxdata.com/influxdb/v2.7/api/
+    ///
+    /// NOTE: only influxql is supported in the query api.
+    fn influxdb_v2_api(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let body_limit = warp::body::content_length_limit(self.config.max_body_size);
+
+        let write_api = warp::path!("write")
+            .and(warp::post())
+            .and(body_limit.clone())
+            .and(self.with_influxdb_auth())
+            .and(self.with_context())
+            .and(warp::query::<WriteV2Params>())
+            .and(warp::body::bytes())
+            .and(self.with_proxy())
+            .and(header::optional::<String>(CONTENT_ENCODING_HEADER))
+            .and_then(
+                |ctx, params, lines: Bytes, proxy: Arc<Proxy>, encoding: Option<String>| async move {
+                    let lines = decode_content(lines, encoding)?;
+                    let request =
+                        WriteRequest::try_new_v2(lines, params).map_err(reject::custom)?;
+                    let result = proxy.handle_influxdb_v2_write(ctx, request).await;
+                    match result {
+                        Ok(_res) => Ok(reply::with_status(warp::reply(), StatusCode::NO_CONTENT)),
+                        Err(e) => Err(reject::custom(e)),
+                    }
+                },
+            );
+
+        let query_api = warp::path!("query")
+            .and(warp::post())
+            .and(body_limit)
+            .and(self.with_influxdb_auth())
+            .and(self.with_context())
+            .and(warp::query::<QueryV2Params>())
+            .and(header::optional::<String>(CONTENT_TYPE_HEADER))
+            .and(warp::body::bytes())
+            .and(self.with_proxy())
+            .and_then(
+                |ctx, params, content_type, body, proxy: Arc<Proxy>| async move {
+                    let request = InfluxqlRequest::try_new_v2(content_type, body, params)
+                        .map_err(reject::custom)?;
+                    let result = proxy
+                        .handle_influxdb_v2_query(ctx, request)
+                        .await
+                        .box_err()
+                        .context(HandleRequest);
+                    match result {
+                        Ok(res) => Ok(reply::json(&res)),
+                        Err(e) => Err(reject::custom(e)),
+                    }
+                },
+            );
+
+        warp::path!("api" / "v2" / ..).and(write_api.or(query_api))
+    }
+
     // POST /opentsdb/api/put
+    // GET/POST /opentsdb/api/query
+    // GET /opentsdb/api/suggest
     fn opentsdb_api(
         &self,
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
@@ -425,28 +539,14 @@ impl Service {
 
         let put_api = warp::path!("put")
             .and(warp::post())
-            .and(body_limit)
+            .and(body_limit.clone())
             .and(self.with_context())
             .and(warp::query::<PutParams>())
             .and(warp::body::bytes())
             .and(self.with_proxy())
             .and(header::optional::<String>(CONTENT_ENCODING_HEADER))
             .and_then(|ctx, params, points: Bytes, proxy: Arc<Proxy>, encoding: Option<String>| async move {
-                let points = match encoding {
-                    Some(encoding) => {
-                        let encode_type = ContentEncodingType::try_from(encoding.as_str())?;
-                        match encode_type {
-                            ContentEncodingType::Gzip => {
-                                let bytes = points.as_bytes();
-                                let mut decoder = GzDecoder::new(bytes);
-                                let mut decompressed_data = Vec::with_capacity(bytes.len()* 2);
-                                decoder.read_to_end(&mut decompressed_data).context(UnGzip)?;
-                                decompressed_data.into()
-                            },
-                        }
-                    },
-                    None => points,
-                };
+                let points = decode_content(points, encoding)?;
                 let request = PutRequest::new(points, params);
                 let result = proxy.handle_opentsdb_put(ctx, request).await;
                 match result {
@@ -455,7 +555,74 @@ impl Service {
                 }
             });
 
-        warp::path!("opentsdb" / "api" / ..).and(put_api)
+        // Query support both get and post method, and only the body of post is used,
+        // so `body_limit` is added to the post method only.
+        let query_body = warp::get()
+            .map(Bytes::new)
+            .or(warp::post().and(body_limit).and(warp::body::bytes()))
+            .unify();
+        let query_api = warp::path!("query")
+            .and(query_body)
+            .and(self.with_context())
+            .and(warp::query::<QueryParams>())
+            .and(self.with_proxy())
+            .and_then(|body: Bytes, ctx, params, proxy: Arc<Proxy>| async move {
+                let request = QueryRequest::try_new(body, params).map_err(reject::custom)?;
+                let result = proxy.handle_opentsdb_query(ctx, request).await;
+                match result {
+                    Ok(res) => Ok(reply::json(&res)),
+                    Err(e) => Err(reject::custom(e)),
+                }
+            });
+
+        let suggest_api = warp::path!("suggest")
+            .and(warp::get())
+            .and(self.with_context())
+            .and(warp::query::<SuggestParams>())
+            .and(self.with_proxy())
+            .and_then(|ctx, params, proxy: Arc<Proxy>| async move {
+                let result = proxy.handle_opentsdb_suggest(ctx, params).await;
+                match result {
+                    Ok(res) => Ok(reply::json(&res)),
+                    Err(e) => Err(reject::custom(e)),
+                }
+            });
+
+        warp::path!("opentsdb" / "api" / ..).and(put_api.or(query_api).or(suggest_api))
+    }
+
+    /// POST `/v1/metrics`
+    ///
+    /// It's derived from the OTLP/HTTP protocol, and only the binary protobuf
+    /// encoding is supported now:
+    ///     https://opentelemetry.io/docs/specs/otlp/#otlphttp
+    fn otlp_api(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let body_limit = warp::body::content_length_limit(self.config.max_body_size);
+
+        warp::path!("v1" / "metrics")
+            .and(warp::post())
+            .and(body_limit)
+            .and(self.with_context())
+            .and(warp::body::bytes())
+            .and(self.with_proxy())
+            .and(header::optional::<String>(CONTENT_ENCODING_HEADER))
+            .and_then(
+                |ctx, body: Bytes, proxy: Arc<Proxy>, encoding: Option<String>| async move {
+                    let body = decode_content(body, encoding)?;
+                    let request = ExportRequest::decode(body).context(DecodeOtlpRequest)?;
+                    let result = proxy.handle_otlp_metrics_export(ctx, request).await;
+                    match result {
+                        Ok(res) => Ok(reply::with_header(
+                            res.encode_to_vec(),
+                            CONTENT_TYPE_HEADER,
+                            PROTOBUF_CONTENT_TYPE,
+                        )),
+                        Err(e) => Err(reject::custom(e)),
+                    }
+                },
+            )
     }
 
     // POST /debug/flush_memtable
@@ -659,6 +826,113 @@ impl Service {
             })
     }
 
+    // POST /admin/rate_limit
+    fn admin_rate_limit(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("admin" / "rate_limit")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(self.with_context())
+            .and(self.with_instance())
+            .and_then(|req, ctx, instance| async {
+                let result = handlers::admin::handle_rate_limit(ctx, instance, req)
+                    .await
+                    .box_err()
+                    .context(HandleRequest);
+
+                match result {
+                    Ok(res) => Ok(reply::json(&res)),
+                    Err(e) => Err(reject::custom(e)),
+                }
+            })
+    }
+
+    // POST /admin/backup
+    fn admin_backup(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("admin" / "backup")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(self.with_context())
+            .and(self.with_proxy())
+            .and_then(
+                |req: TableBackupRequest, ctx: RequestContext, proxy: Arc<Proxy>| async move {
+                    Self::handle_admin_sql(ctx, proxy, req.to_backup_sql()).await
+                },
+            )
+    }
+
+    // POST /admin/restore
+    fn admin_restore(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("admin" / "restore")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(self.with_context())
+            .and(self.with_proxy())
+            .and_then(
+                |req: TableBackupRequest, ctx: RequestContext, proxy: Arc<Proxy>| async move {
+                    Self::handle_admin_sql(ctx, proxy, req.to_restore_sql()).await
+                },
+            )
+    }
+
+    // POST /admin/verify
+    fn admin_verify(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("admin" / "verify")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(self.with_context())
+            .and(self.with_proxy())
+            .and_then(
+                |req: TableVerifyRequest, ctx: RequestContext, proxy: Arc<Proxy>| async move {
+                    Self::handle_admin_sql(ctx, proxy, req.to_sql()).await
+                },
+            )
+    }
+
+    // POST /admin/kill_query
+    fn admin_kill_query(
+        &self,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("admin" / "kill_query")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(self.with_context())
+            .and(self.with_proxy())
+            .and_then(
+                |req: KillQueryRequest, ctx: RequestContext, proxy: Arc<Proxy>| async move {
+                    Self::handle_admin_sql(ctx, proxy, req.to_sql()).await
+                },
+            )
+    }
+
+    async fn handle_admin_sql(
+        mut ctx: RequestContext,
+        proxy: Arc<Proxy>,
+        req: Request,
+    ) -> std::result::Result<impl warp::Reply, Rejection> {
+        // Copying or verifying ssts may take a long time, so don't timeout it.
+        ctx.timeout = None;
+        ctx.is_admin = true;
+
+        let result = proxy
+            .handle_http_sql_query(&ctx, req)
+            .await
+            .map(convert_output)
+            .box_err()
+            .context(HandleRequest);
+        match result {
+            Ok(res) => Ok(reply::json(&res)),
+            Err(e) => Err(reject::custom(e)),
+        }
+    }
+
     // POST /debug/query_push_down/{true/false}
     fn query_push_down(
         &self,
@@ -718,16 +992,30 @@ impl Service {
         header::optional::<String>(consts::CATALOG_HEADER)
             .and(header::optional::<String>(consts::SCHEMA_HEADER))
             .and(header::optional::<String>(consts::TENANT_HEADER))
+            .and(header::optional::<bool>(consts::BYPASS_QUERY_CACHE_HEADER))
+            .and(header::optional::<String>(consts::QUERY_LIMITS_HEADER))
             .and_then(
-                move |catalog: Option<_>, schema: Option<_>, _tenant: Option<_>| {
+                move |catalog: Option<_>,
+                      schema: Option<_>,
+                      _tenant: Option<_>,
+                      bypass_query_cache: Option<bool>,
+                      query_limits: Option<String>| {
                     // Clone the captured variables
                     let default_catalog = default_catalog.clone();
                     let schema = schema.unwrap_or_else(|| default_schema.clone());
                     async move {
+                        let query_limits = query_limits
+                            .map(|v| limiter::parse_query_limits(&v))
+                            .transpose()
+                            .context(ParseQueryLimits)
+                            .map_err(reject::custom)?
+                            .unwrap_or_default();
                         RequestContext::builder()
                             .catalog(catalog.unwrap_or(default_catalog))
                             .schema(schema)
                             .timeout(timeout)
+                            .bypass_query_cache(bypass_query_cache.unwrap_or(false))
+                            .query_limits(query_limits)
                             .build()
                             .context(CreateContext)
                             .map_err(reject::custom)
@@ -736,6 +1024,37 @@ impl Service {
             )
     }
 
+    /// Check the token in the `Authorization` header, which is in the form of
+    /// `Token <token>` or `Bearer <token>` in influxdb 2.x.
+    fn with_influxdb_auth(&self) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+        let tokens = Arc::new(self.config.influxdb_tokens.clone());
+
+        header::optional::<String>(AUTHORIZATION_HEADER)
+            .and_then(move |auth: Option<String>| {
+                let tokens = tokens.clone();
+                async move {
+                    if tokens.is_empty() {
+                        return Ok(());
+                    }
+
+                    let token = auth.as_deref().and_then(|v| {
+                        v.strip_prefix("Token ")
+                            .or_else(|| v.strip_prefix("Bearer "))
+                    });
+                    match token {
+                        Some(token) if tokens.iter().any(|t| t == token) => Ok(()),
+                        Some(_) => Err(reject::custom(Error::Unauthorized {
+                            msg: "invalid token".to_string(),
+                        })),
+                        None => Err(reject::custom(Error::Unauthorized {
+                            msg: "missing token".to_string(),
+                        })),
+                    }
+                }
+            })
+            .untuple_one()
+    }
+
     fn with_profiler(&self) -> impl Filter<Extract = (Arc<Profiler>,), Error = Infallible> + Clone {
         let profiler = self.profiler.clone();
         warp::any().map(move || profiler.clone())
@@ -873,6 +1192,8 @@ pub struct HttpConfig {
     pub endpoint: Endpoint,
     pub max_body_size: u64,
     pub timeout: Option<Duration>,
+    /// Tokens accepted by the influxdb 2.x api, and no auth if empty
+    pub influxdb_tokens: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -881,11 +1202,35 @@ struct ErrorResponse {
     message: String,
 }
 
+/// Status code of the proxy error which is passed through to the client, e.g.
+/// the request is throttled by the rate limits and expected to be retried
+/// later, or the query is killed.
+fn proxy_status_code(source: &(dyn StdError + Send + Sync + 'static)) -> Option<StatusCode> {
+    source
+        .downcast_ref::<proxy::error::Error>()
+        .map(|e| e.code())
+        .filter(|code| {
+            *code == StatusCode::TOO_MANY_REQUESTS
+                || *code == StatusCode::FORBIDDEN
+                || *code == StatusCode::PAYLOAD_TOO_LARGE
+                || *code == StatusCode::UNPROCESSABLE_ENTITY
+                || *code == proxy::error::query_killed_code()
+        })
+}
+
 fn error_to_status_code(err: &Error) -> StatusCode {
+    if let Error::HandleRequest { source } | Error::Internal { source } = err {
+        if let Some(code) = proxy_status_code(source.as_ref()) {
+            return code;
+        }
+    }
+
     match err {
         Error::UnGzip { .. }
         | Error::UnspportedContentEncodingType { .. }
-        | Error::CreateContext { .. } => StatusCode::BAD_REQUEST,
+        | Error::DecodeOtlpRequest { .. }
+        | Error::CreateContext { .. }
+        | Error::ParseQueryLimits { .. } => StatusCode::BAD_REQUEST,
         // TODO(yingwen): Map handle request error to more accurate status code
         Error::HandleRequest { .. }
         | Error::MissingEngineRuntimes { .. }
@@ -903,6 +1248,7 @@ fn error_to_status_code(err: &Error) -> StatusCode {
         | Error::MissingWal { .. }
         | Error::QueryShards { .. } => StatusCode::BAD_REQUEST,
         Error::HandleUpdateLogLevel { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        Error::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
         Error::QueryMaybeExceedTTL { .. } => StatusCode::OK,
     }
 }
@@ -920,6 +1266,9 @@ async fn handle_rejection(
         code = error_to_status_code(err);
         let err_string = err.to_string();
         message = error_util::remove_backtrace_from_err(&err_string).to_string();
+    } else if let Some(err) = rejection.find::<proxy::error::Error>() {
+        code = err.code();
+        message = err.error_message();
     } else {
         code = StatusCode::INTERNAL_SERVER_ERROR;
         message = error_util::remove_backtrace_from_err(&format!("UNKNOWN_ERROR: {rejection:?}"))
@@ -152,6 +152,7 @@ where
             .catalog(session.catalog().to_string())
             .schema(session.schema().to_string())
             .timeout(self.timeout)
+            .client(session.conn_info().to_string())
             .build()
             .context(CreateContext)
     }
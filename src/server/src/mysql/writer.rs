@@ -78,6 +78,7 @@ impl<'a, W: std::io::Write> MysqlQueryResultWriter<'a, W> {
                     match (data_type, val) {
                         (_, Datum::Varbinary(v)) => row_writer.write_col(v.as_ref()),
                         (_, Datum::Null) => row_writer.write_col(None::<u8>),
+                        (_, Datum::Array(v)) => row_writer.write_col(v.to_string()),
                         (ColumnType::MYSQL_TYPE_LONG, Datum::Timestamp(t)) => {
                             row_writer.write_col(t.as_i64())
                         }
@@ -96,6 +97,12 @@ impl<'a, W: std::io::Write> MysqlQueryResultWriter<'a, W> {
                         (ColumnType::MYSQL_TYPE_FLOAT, v) => {
                             row_writer.write_col(v.as_f64().map_or(0.0, |v| v))
                         }
+                        (ColumnType::MYSQL_TYPE_NEWDECIMAL, Datum::Decimal(v)) => {
+                            row_writer.write_col(v.to_string())
+                        }
+                        (ColumnType::MYSQL_TYPE_JSON, Datum::Json(v)) => {
+                            row_writer.write_col(v.as_str())
+                        }
                         (_, v) => Err(std::io::Error::new(
                             std::io::ErrorKind::Other,
                             format!("Unsupported column type, val: {v:?}"),
@@ -140,6 +147,10 @@ fn convert_datum_kind_type(data_type: &DatumKind) -> ColumnType {
         DatumKind::Null => ColumnType::MYSQL_TYPE_NULL,
         DatumKind::Date => ColumnType::MYSQL_TYPE_DATE,
         DatumKind::Time => ColumnType::MYSQL_TYPE_TIME,
+        DatumKind::Decimal => ColumnType::MYSQL_TYPE_NEWDECIMAL,
+        DatumKind::Json => ColumnType::MYSQL_TYPE_JSON,
+        // The array is written in its display string.
+        DatumKind::Array => ColumnType::MYSQL_TYPE_VARCHAR,
     }
 }
 
@@ -169,6 +180,11 @@ mod tests {
                     comment: "".to_string(),
                     escaped_name: "id".to_string(),
                     default_value: None,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    decimal_type: None,
+                    element_type: None,
                 },
                 target_type: ColumnType::MYSQL_TYPE_LONG,
             },
@@ -183,6 +199,11 @@ mod tests {
                     comment: "".to_string(),
                     escaped_name: "name".to_string(),
                     default_value: None,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    decimal_type: None,
+                    element_type: None,
                 },
                 target_type: ColumnType::MYSQL_TYPE_VARCHAR,
             },
@@ -197,6 +218,11 @@ mod tests {
                     comment: "".to_string(),
                     escaped_name: "birthday".to_string(),
                     default_value: None,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    decimal_type: None,
+                    element_type: None,
                 },
                 target_type: ColumnType::MYSQL_TYPE_LONG,
             },
@@ -211,6 +237,11 @@ mod tests {
                     comment: "".to_string(),
                     escaped_name: "is_show".to_string(),
                     default_value: None,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    decimal_type: None,
+                    element_type: None,
                 },
                 target_type: ColumnType::MYSQL_TYPE_SHORT,
             },
@@ -225,6 +256,11 @@ mod tests {
                     comment: "".to_string(),
                     escaped_name: "money".to_string(),
                     default_value: None,
+                    aggregation: None,
+                    encoding: None,
+                    compression: None,
+                    decimal_type: None,
+                    element_type: None,
                 },
                 target_type: ColumnType::MYSQL_TYPE_DOUBLE,
             },
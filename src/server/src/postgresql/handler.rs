@@ -156,6 +156,9 @@ fn convert_data_type(data_type: &DatumKind) -> Type {
         DatumKind::Boolean => Type::BOOL,
         DatumKind::Date => Type::DATE,
         DatumKind::Time => Type::TIME,
+        DatumKind::Decimal => Type::NUMERIC,
+        DatumKind::Json => Type::JSON,
+        DatumKind::Array => Type::TEXT,
     }
 }
 
@@ -175,6 +178,9 @@ fn encode_data(encoder: &mut DataRowEncoder, val: Datum) -> PgWireResult<()> {
         Datum::Boolean(b) => encoder.encode_field(&b),
         Datum::Date(v) => encoder.encode_field(&v),
         Datum::Time(v) => encoder.encode_field(&v),
+        Datum::Decimal(v) => encoder.encode_field(&v.to_string()),
+        Datum::Json(v) => encoder.encode_field(&v.as_str()),
+        Datum::Array(v) => encoder.encode_field(&v.to_string()),
         // FIXME: PostgreSQL does not support unsigned integers in the wire protocol.
         // Maybe we should return decimal or numeric instead?
         Datum::UInt64(v) => encoder.encode_field(&format!("{v}")),
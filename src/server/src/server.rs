@@ -23,7 +23,7 @@ use catalog::manager::ManagerRef;
 use cluster::ClusterRef;
 use datafusion::execution::{runtime_env::RuntimeConfig, FunctionRegistry};
 use df_operator::registry::FunctionRegistryRef;
-use interpreters::table_manipulator::TableManipulatorRef;
+use interpreters::{process_list::ProcessList, table_manipulator::TableManipulatorRef};
 use logger::{info, warn, RuntimeLevel};
 use macros::define_result;
 use notifier::notifier::RequestNotifiers;
@@ -32,6 +32,7 @@ use proxy::{
     hotspot::HotspotRecorder,
     instance::{DynamicConfig, Instance, InstanceRef},
     limiter::Limiter,
+    query_cache::QueryCache,
     schema_config_provider::SchemaConfigProviderRef,
     Proxy,
 };
@@ -424,6 +425,7 @@ impl Builder {
                 table_manipulator,
                 remote_engine_ref,
                 dyn_config: proxy_dyn_config,
+                process_list: Arc::new(ProcessList::default()),
             };
             InstanceRef::new(instance)
         };
@@ -443,6 +445,7 @@ impl Builder {
             endpoint: http_endpoint,
             max_body_size: self.server_config.http_max_body_size.as_byte(),
             timeout: self.server_config.timeout.map(|v| v.0),
+            influxdb_tokens: self.server_config.influxdb_tokens.clone(),
         };
 
         let request_notifiers = self
@@ -450,6 +453,7 @@ impl Builder {
             .query_dedup
             .enable
             .then(|| Arc::new(RequestNotifiers::default()));
+        let query_cache = QueryCache::from_config(&self.server_config.query_cache).map(Arc::new);
 
         let proxy = Arc::new(Proxy::new(
             router.clone(),
@@ -465,6 +469,7 @@ impl Builder {
             self.server_config.sub_table_access_perm,
             request_notifiers,
             expensive_query_threshold,
+            query_cache,
         ));
 
         let http_service = http::Builder::new(http_config)
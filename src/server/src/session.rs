@@ -61,7 +61,6 @@ impl Session {
     }
 
     #[inline]
-    #[allow(dead_code)]
     pub fn conn_info(&self) -> &ConnInfo {
         &self.conn_info
     }
@@ -0,0 +1,258 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! implementation of system table: Compactions
+//! For example `SELECT * FROM system.public.compactions`
+
+use std::fmt::{Debug, Formatter};
+
+use async_trait::async_trait;
+use catalog::{manager::ManagerRef, schema::SchemaRef, CatalogRef};
+use common_types::{
+    datum::{Datum, DatumKind},
+    row::Row,
+    schema,
+    schema::Schema,
+};
+use table_engine::{
+    stream::SendableRecordBatchStream,
+    table::{ReadRequest, TableId, TableRef, TableStorageInfo},
+};
+
+use crate::{
+    build_column, build_one_record_batch_stream, tables::ENTRY_TIMESTAMP, visit_all_tables,
+    SystemTable, COMPACTIONS_TABLE_ID, COMPACTIONS_TABLE_NAME,
+};
+
+/// Status of the compaction waiting to be scheduled.
+const PENDING_STATUS: &str = "pending";
+/// Status of the compaction in progress.
+const RUNNING_STATUS: &str = "running";
+
+/// Build a new table schema for compactions
+fn compactions_schema() -> Schema {
+    schema::Builder::with_capacity(8)
+        .auto_increment_column_id(true)
+        .add_key_column(build_column("timestamp", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_key_column(build_column("catalog", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("schema", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("table_name", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("status", DatumKind::String, false))
+        .unwrap()
+        .add_normal_column(build_column("table_id", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("input_files", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("input_size", DatumKind::UInt64, false))
+        .unwrap()
+        .primary_key_indexes(vec![0, 1, 2, 3, 4])
+        .build()
+        .unwrap()
+}
+
+/// Pending and running compactions of all the tables opened on this node.
+///
+/// The inputs of pending compaction are unknown until it's scheduled, so the
+/// `input_files` and `input_size` of them are always zero.
+pub struct Compactions {
+    schema: Schema,
+    catalog_manager: ManagerRef,
+}
+
+impl Debug for Compactions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SysCompactions")
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+impl Compactions {
+    pub fn new(catalog_manager: ManagerRef) -> Self {
+        Self {
+            schema: compactions_schema(),
+            catalog_manager,
+        }
+    }
+
+    fn build_row(
+        &self,
+        catalog: &CatalogRef,
+        schema: &SchemaRef,
+        table: &TableRef,
+        status: &str,
+        input_files: u64,
+        input_size: u64,
+    ) -> Row {
+        let mut datums = Vec::with_capacity(self.schema.num_columns());
+        datums.push(Datum::Timestamp(ENTRY_TIMESTAMP));
+        datums.push(Datum::from(catalog.name()));
+        datums.push(Datum::from(schema.name()));
+        datums.push(Datum::from(table.name()));
+        datums.push(Datum::from(status));
+        datums.push(Datum::from(table.id().as_u64()));
+        datums.push(Datum::from(input_files));
+        datums.push(Datum::from(input_size));
+        Row::from_datums(datums)
+    }
+
+    /// Rows of the pending and running compactions of the table.
+    fn build_rows(
+        &self,
+        catalog: &CatalogRef,
+        schema: &SchemaRef,
+        table: &TableRef,
+        storage_info: &TableStorageInfo,
+    ) -> Vec<Row> {
+        let mut rows = Vec::new();
+        if storage_info.compaction_pending {
+            rows.push(self.build_row(catalog, schema, table, PENDING_STATUS, 0, 0));
+        }
+
+        let (input_files, input_size) = storage_info
+            .ssts
+            .iter()
+            .filter(|sst| sst.being_compacted)
+            .fold((0, 0), |(files, size), sst| (files + 1, size + sst.size));
+        if input_files > 0 {
+            rows.push(self.build_row(
+                catalog,
+                schema,
+                table,
+                RUNNING_STATUS,
+                input_files,
+                input_size,
+            ));
+        }
+
+        rows
+    }
+}
+
+#[async_trait]
+impl SystemTable for Compactions {
+    fn name(&self) -> &str {
+        COMPACTIONS_TABLE_NAME
+    }
+
+    fn id(&self) -> TableId {
+        COMPACTIONS_TABLE_ID
+    }
+
+    fn schema(&self) -> Schema {
+        self.schema.clone()
+    }
+
+    async fn read(
+        &self,
+        request: ReadRequest,
+    ) -> table_engine::table::Result<SendableRecordBatchStream> {
+        let mut rows = Vec::new();
+        visit_all_tables(
+            &self.catalog_manager,
+            self.name(),
+            |catalog, schema, table| {
+                if let Some(storage_info) = table.storage_info() {
+                    rows.extend(self.build_rows(catalog, schema, table, &storage_info));
+                }
+            },
+        )?;
+
+        build_one_record_batch_stream(self.name(), &self.schema, request, rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_types::time::TimeRange;
+    use table_engine::table::SstInfo;
+
+    use super::*;
+    use crate::tests::{build_test_table, TEST_CATALOG, TEST_SCHEMA, TEST_TABLE, TEST_TABLE_ID};
+
+    fn build_sst(file_id: u64, size: u64, being_compacted: bool) -> SstInfo {
+        SstInfo {
+            level: 0,
+            file_id,
+            size,
+            row_num: 1,
+            time_range: TimeRange::new_unchecked_for_test(0, 1000),
+            max_sequence: file_id,
+            being_compacted,
+        }
+    }
+
+    fn expected_row(status: &str, input_files: u64, input_size: u64) -> Row {
+        Row::from_datums(vec![
+            Datum::Timestamp(ENTRY_TIMESTAMP),
+            Datum::from(TEST_CATALOG),
+            Datum::from(TEST_SCHEMA),
+            Datum::from(TEST_TABLE),
+            Datum::from(status),
+            Datum::UInt64(TEST_TABLE_ID),
+            Datum::UInt64(input_files),
+            Datum::UInt64(input_size),
+        ])
+    }
+
+    #[test]
+    fn test_build_rows() {
+        let (manager, catalog, schema, table) = build_test_table();
+        let compactions = Compactions::new(manager);
+
+        // No compaction.
+        let storage_info = TableStorageInfo {
+            ssts: vec![build_sst(1, 100, false)],
+            ..Default::default()
+        };
+        let rows = compactions.build_rows(&catalog, &schema, &table, &storage_info);
+        assert!(rows.is_empty());
+
+        // Pending compaction only.
+        let storage_info = TableStorageInfo {
+            ssts: vec![build_sst(1, 100, false)],
+            compaction_pending: true,
+            ..Default::default()
+        };
+        let rows = compactions.build_rows(&catalog, &schema, &table, &storage_info);
+        assert_eq!(vec![expected_row(PENDING_STATUS, 0, 0)], rows);
+
+        // Both pending and running compactions.
+        let storage_info = TableStorageInfo {
+            ssts: vec![
+                build_sst(1, 100, true),
+                build_sst(2, 200, false),
+                build_sst(3, 300, true),
+            ],
+            compaction_pending: true,
+            ..Default::default()
+        };
+        let rows = compactions.build_rows(&catalog, &schema, &table, &storage_info);
+        assert_eq!(compactions.schema.num_columns(), rows[0].num_columns());
+        assert_eq!(
+            vec![
+                expected_row(PENDING_STATUS, 0, 0),
+                expected_row(RUNNING_STATUS, 2, 400),
+            ],
+            rows
+        );
+    }
+}
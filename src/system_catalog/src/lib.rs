@@ -28,21 +28,31 @@ use std::{
 };
 
 use async_trait::async_trait;
+use catalog::{manager::ManagerRef, schema::SchemaRef, CatalogRef};
 use common_types::{
-    record_batch::RecordBatch,
+    column_schema::{self, ColumnSchema},
+    datum::DatumKind,
+    projected_schema::RowProjector,
+    record_batch::{FetchedRecordBatchBuilder, RecordBatch},
     row::Row,
     schema::{RecordSchema, Schema},
 };
 use futures::Stream;
+use generic_error::BoxError;
+use snafu::ResultExt;
 use table_engine::{
     stream,
     stream::{PartitionedStreams, RecordBatchStream, SendableRecordBatchStream},
     table::{
         AlterSchemaRequest, FlushRequest, GetRequest, ReadRequest, SchemaId, Table, TableId,
-        TableSeq, TableStats, WriteRequest,
+        TableRef, TableSeq, TableStats, WriteRequest,
     },
 };
 
+pub mod compactions;
+pub mod memtables;
+pub mod shards;
+pub mod sst_files;
 pub mod sys_catalog_table;
 pub mod tables;
 
@@ -64,11 +74,42 @@ pub const TABLES_TABLE_SEQ: TableSeq = TableSeq::from_u32(2);
 /// Table id of the `tables` table.
 pub const TABLES_TABLE_ID: TableId = TableId::with_seq(SYSTEM_SCHEMA_ID, TABLES_TABLE_SEQ).unwrap();
 
+/// Table name of the `sst_files` table.
+pub const SST_FILES_TABLE_NAME: &str = "sst_files";
+/// Table sequence of the `sst_files` table.
+pub const SST_FILES_TABLE_SEQ: TableSeq = TableSeq::from_u32(3);
+/// Table id of the `sst_files` table.
+pub const SST_FILES_TABLE_ID: TableId =
+    TableId::with_seq(SYSTEM_SCHEMA_ID, SST_FILES_TABLE_SEQ).unwrap();
+
+/// Table name of the `memtables` table.
+pub const MEMTABLES_TABLE_NAME: &str = "memtables";
+/// Table sequence of the `memtables` table.
+pub const MEMTABLES_TABLE_SEQ: TableSeq = TableSeq::from_u32(4);
+/// Table id of the `memtables` table.
+pub const MEMTABLES_TABLE_ID: TableId =
+    TableId::with_seq(SYSTEM_SCHEMA_ID, MEMTABLES_TABLE_SEQ).unwrap();
+
+/// Table name of the `compactions` table.
+pub const COMPACTIONS_TABLE_NAME: &str = "compactions";
+/// Table sequence of the `compactions` table.
+pub const COMPACTIONS_TABLE_SEQ: TableSeq = TableSeq::from_u32(5);
+/// Table id of the `compactions` table.
+pub const COMPACTIONS_TABLE_ID: TableId =
+    TableId::with_seq(SYSTEM_SCHEMA_ID, COMPACTIONS_TABLE_SEQ).unwrap();
+
+/// Table name of the `shards` table.
+pub const SHARDS_TABLE_NAME: &str = "shards";
+/// Table sequence of the `shards` table.
+pub const SHARDS_TABLE_SEQ: TableSeq = TableSeq::from_u32(6);
+/// Table id of the `shards` table.
+pub const SHARDS_TABLE_ID: TableId = TableId::with_seq(SYSTEM_SCHEMA_ID, SHARDS_TABLE_SEQ).unwrap();
+
 // NOTE: The MAX_SYSTEM_TABLE_ID should be updated if any new system table is
 // added.
 
 /// Max table id of all the system tables.
-pub const MAX_SYSTEM_TABLE_SEQ: TableSeq = TABLES_TABLE_SEQ;
+pub const MAX_SYSTEM_TABLE_SEQ: TableSeq = SHARDS_TABLE_SEQ;
 
 /// The minimal thing that a system table needs to implement
 #[async_trait]
@@ -207,3 +248,130 @@ impl RecordBatchStream for OneRecordBatchStream {
         &self.schema
     }
 }
+
+/// Build a column of the system table.
+pub(crate) fn build_column(name: &str, kind: DatumKind, is_nullable: bool) -> ColumnSchema {
+    column_schema::Builder::new(name.to_string(), kind)
+        .is_nullable(is_nullable)
+        .is_tag(false)
+        .build()
+        .unwrap()
+}
+
+/// Visit all the tables of all the catalogs.
+pub(crate) fn visit_all_tables<F>(
+    catalog_manager: &ManagerRef,
+    system_table_name: &str,
+    mut visitor: F,
+) -> table_engine::table::Result<()>
+where
+    F: FnMut(&CatalogRef, &SchemaRef, &TableRef),
+{
+    let catalogs = catalog_manager
+        .all_catalogs()
+        .box_err()
+        .context(table_engine::table::Scan {
+            table: system_table_name,
+        })?;
+    for catalog in &catalogs {
+        let schemas = catalog
+            .all_schemas()
+            .box_err()
+            .context(table_engine::table::Scan {
+                table: system_table_name,
+            })?;
+        for schema in &schemas {
+            let tables = schema
+                .all_tables()
+                .box_err()
+                .context(table_engine::table::Scan {
+                    table: system_table_name,
+                })?;
+            for table in &tables {
+                visitor(catalog, schema, table);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Build a stream of one record batch from the rows of the system table, and
+/// the rows should be built from the `table_schema`.
+pub(crate) fn build_one_record_batch_stream(
+    system_table_name: &str,
+    table_schema: &Schema,
+    request: ReadRequest,
+    rows: Vec<Row>,
+) -> table_engine::table::Result<SendableRecordBatchStream> {
+    let fetched_schema = request.projected_schema.to_record_schema_with_key();
+    let primary_key_indexes = fetched_schema.primary_key_idx().to_vec();
+    let fetched_schema = fetched_schema.to_record_schema();
+    let mut builder =
+        FetchedRecordBatchBuilder::new(fetched_schema.clone(), Some(primary_key_indexes.clone()));
+
+    let row_projector = RowProjector::new(
+        &fetched_schema,
+        Some(primary_key_indexes),
+        request.projected_schema.table_schema(),
+        table_schema,
+    )
+    .box_err()
+    .context(table_engine::table::Scan {
+        table: system_table_name,
+    })?;
+    for row in rows {
+        let projected_row = row_projector.project_row(&row, Vec::new());
+        builder
+            .append_row(projected_row)
+            .box_err()
+            .context(table_engine::table::Scan {
+                table: system_table_name,
+            })?;
+    }
+    let record_batch = builder
+        .build()
+        .box_err()
+        .context(table_engine::table::Scan {
+            table: system_table_name,
+        })?
+        .into_record_batch();
+
+    Ok(Box::pin(OneRecordBatchStream {
+        schema: table_schema.clone().to_record_schema(),
+        record_batch: Some(record_batch),
+    }))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use catalog::{manager::Manager, test_util::MockCatalogManagerBuilder, Catalog};
+    use table_engine::memory::MemoryTable;
+
+    use super::*;
+
+    pub const TEST_CATALOG: &str = "test_catalog";
+    pub const TEST_SCHEMA: &str = "test_schema";
+    pub const TEST_TABLE: &str = "test_table";
+    pub const TEST_TABLE_ID: u64 = 100;
+
+    /// Build a catalog manager with one schema holding one memory table.
+    pub fn build_test_table() -> (ManagerRef, CatalogRef, SchemaRef, TableRef) {
+        let table: TableRef = Arc::new(MemoryTable::new(
+            TEST_TABLE.to_string(),
+            TableId::new(TEST_TABLE_ID),
+            common_types::tests::build_schema(),
+            "memory".to_string(),
+        ));
+        let manager = MockCatalogManagerBuilder::new(
+            TEST_CATALOG.to_string(),
+            TEST_SCHEMA.to_string(),
+            vec![table.clone()],
+        )
+        .build();
+        let catalog = manager.catalog_by_name(TEST_CATALOG).unwrap().unwrap();
+        let schema = catalog.schema_by_name(TEST_SCHEMA).unwrap().unwrap();
+
+        (manager, catalog, schema, table)
+    }
+}
@@ -0,0 +1,208 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! implementation of system table: MemTables
+//! For example `SELECT * FROM system.public.memtables`
+
+use std::fmt::{Debug, Formatter};
+
+use async_trait::async_trait;
+use catalog::{manager::ManagerRef, schema::SchemaRef, CatalogRef};
+use common_types::{
+    datum::{Datum, DatumKind},
+    row::Row,
+    schema,
+    schema::Schema,
+    time::Timestamp,
+};
+use table_engine::{
+    stream::SendableRecordBatchStream,
+    table::{MemTableInfo, ReadRequest, TableId, TableRef, TableStorageInfo},
+};
+
+use crate::{
+    build_column, build_one_record_batch_stream, tables::ENTRY_TIMESTAMP, visit_all_tables,
+    SystemTable, MEMTABLES_TABLE_ID, MEMTABLES_TABLE_NAME,
+};
+
+/// Build a new table schema for memtables
+fn memtables_schema() -> Schema {
+    schema::Builder::with_capacity(13)
+        .auto_increment_column_id(true)
+        .add_key_column(build_column("timestamp", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_key_column(build_column("catalog", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("schema", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("table_name", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("memtable_id", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("table_id", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("status", DatumKind::String, false))
+        .unwrap()
+        .add_normal_column(build_column("memory_usage", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("start_time", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_normal_column(build_column("end_time", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_normal_column(build_column("last_sequence", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("flushed_sequence", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("last_flush_time", DatumKind::Timestamp, false))
+        .unwrap()
+        .primary_key_indexes(vec![0, 1, 2, 3, 4])
+        .build()
+        .unwrap()
+}
+
+/// Memtables not flushed yet of all the tables opened on this node, and the
+/// flush state of the table is also included.
+pub struct MemTables {
+    schema: Schema,
+    catalog_manager: ManagerRef,
+}
+
+impl Debug for MemTables {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SysMemTables")
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+impl MemTables {
+    pub fn new(catalog_manager: ManagerRef) -> Self {
+        Self {
+            schema: memtables_schema(),
+            catalog_manager,
+        }
+    }
+
+    fn build_row(
+        &self,
+        catalog: &CatalogRef,
+        schema: &SchemaRef,
+        table: &TableRef,
+        storage_info: &TableStorageInfo,
+        memtable: &MemTableInfo,
+    ) -> Row {
+        let mut datums = Vec::with_capacity(self.schema.num_columns());
+        datums.push(Datum::Timestamp(ENTRY_TIMESTAMP));
+        datums.push(Datum::from(catalog.name()));
+        datums.push(Datum::from(schema.name()));
+        datums.push(Datum::from(table.name()));
+        datums.push(Datum::from(memtable.id));
+        datums.push(Datum::from(table.id().as_u64()));
+        datums.push(Datum::from(memtable.status.as_str()));
+        datums.push(Datum::from(memtable.memory_usage));
+        datums.push(Datum::Timestamp(memtable.time_range.inclusive_start()));
+        datums.push(Datum::Timestamp(memtable.time_range.exclusive_end()));
+        datums.push(Datum::from(memtable.last_sequence));
+        datums.push(Datum::from(storage_info.flushed_sequence));
+        datums.push(Datum::Timestamp(Timestamp::new(
+            storage_info.last_flush_time_ms as i64,
+        )));
+        Row::from_datums(datums)
+    }
+}
+
+#[async_trait]
+impl SystemTable for MemTables {
+    fn name(&self) -> &str {
+        MEMTABLES_TABLE_NAME
+    }
+
+    fn id(&self) -> TableId {
+        MEMTABLES_TABLE_ID
+    }
+
+    fn schema(&self) -> Schema {
+        self.schema.clone()
+    }
+
+    async fn read(
+        &self,
+        request: ReadRequest,
+    ) -> table_engine::table::Result<SendableRecordBatchStream> {
+        let mut rows = Vec::new();
+        visit_all_tables(
+            &self.catalog_manager,
+            self.name(),
+            |catalog, schema, table| {
+                if let Some(storage_info) = table.storage_info() {
+                    for memtable in &storage_info.memtables {
+                        rows.push(self.build_row(catalog, schema, table, &storage_info, memtable));
+                    }
+                }
+            },
+        )?;
+
+        build_one_record_batch_stream(self.name(), &self.schema, request, rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_types::time::TimeRange;
+    use table_engine::table::MemTableStatus;
+
+    use super::*;
+    use crate::tests::{build_test_table, TEST_CATALOG, TEST_SCHEMA, TEST_TABLE, TEST_TABLE_ID};
+
+    #[test]
+    fn test_build_row() {
+        let (manager, catalog, schema, table) = build_test_table();
+        let memtables = MemTables::new(manager);
+        let storage_info = TableStorageInfo {
+            last_sequence: 20,
+            flushed_sequence: 10,
+            last_flush_time_ms: 3000,
+            ..Default::default()
+        };
+        let memtable = MemTableInfo {
+            id: 5,
+            status: MemTableStatus::Immutable,
+            memory_usage: 4096,
+            time_range: TimeRange::new_unchecked_for_test(1000, 2000),
+            last_sequence: 15,
+        };
+
+        let row = memtables.build_row(&catalog, &schema, &table, &storage_info, &memtable);
+        assert_eq!(memtables.schema.num_columns(), row.num_columns());
+        let expected = vec![
+            Datum::Timestamp(ENTRY_TIMESTAMP),
+            Datum::from(TEST_CATALOG),
+            Datum::from(TEST_SCHEMA),
+            Datum::from(TEST_TABLE),
+            Datum::UInt64(5),
+            Datum::UInt64(TEST_TABLE_ID),
+            Datum::from("immutable"),
+            Datum::UInt64(4096),
+            Datum::Timestamp(Timestamp::new(1000)),
+            Datum::Timestamp(Timestamp::new(2000)),
+            Datum::UInt64(15),
+            Datum::UInt64(10),
+            Datum::Timestamp(Timestamp::new(3000)),
+        ];
+        assert_eq!(Row::from_datums(expected), row);
+    }
+}
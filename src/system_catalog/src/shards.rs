@@ -0,0 +1,212 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! implementation of system table: Shards
+//! For example `SELECT * FROM system.public.shards`
+
+use std::fmt::{Debug, Formatter};
+
+use async_trait::async_trait;
+use cluster::ClusterRef;
+use common_types::{
+    datum::{Datum, DatumKind},
+    row::Row,
+    schema,
+    schema::Schema,
+};
+use meta_client::types::{ShardInfo, TableInfo};
+use table_engine::{
+    stream::SendableRecordBatchStream,
+    table::{ReadRequest, TableId},
+};
+
+use crate::{
+    build_column, build_one_record_batch_stream, tables::ENTRY_TIMESTAMP, SystemTable,
+    SHARDS_TABLE_ID, SHARDS_TABLE_NAME,
+};
+
+/// Build a new table schema for shards
+fn shards_schema() -> Schema {
+    schema::Builder::with_capacity(8)
+        .auto_increment_column_id(true)
+        .add_key_column(build_column("timestamp", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_key_column(build_column("shard_id", DatumKind::UInt32, false))
+        .unwrap()
+        .add_normal_column(build_column("role", DatumKind::String, false))
+        .unwrap()
+        .add_normal_column(build_column("version", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("status", DatumKind::String, false))
+        .unwrap()
+        .add_normal_column(build_column("table_id", DatumKind::UInt64, true))
+        .unwrap()
+        .add_normal_column(build_column("schema", DatumKind::String, true))
+        .unwrap()
+        .add_normal_column(build_column("table_name", DatumKind::String, true))
+        .unwrap()
+        .primary_key_indexes(vec![0, 1])
+        .build()
+        .unwrap()
+}
+
+/// Shards opened on this node and the tables of them.
+///
+/// Every table of the shard takes one row, and the shard without any table
+/// takes one row whose table columns are null. It's always empty in
+/// stand-alone mode.
+pub struct Shards {
+    schema: Schema,
+    cluster: Option<ClusterRef>,
+}
+
+impl Debug for Shards {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SysShards")
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+impl Shards {
+    pub fn new(cluster: Option<ClusterRef>) -> Self {
+        Self {
+            schema: shards_schema(),
+            cluster,
+        }
+    }
+
+    fn build_row(&self, shard_info: &ShardInfo, table: Option<&TableInfo>) -> Row {
+        let mut datums = Vec::with_capacity(self.schema.num_columns());
+        datums.push(Datum::Timestamp(ENTRY_TIMESTAMP));
+        datums.push(Datum::from(shard_info.id));
+        datums.push(Datum::from(format!("{:?}", shard_info.role).as_str()));
+        datums.push(Datum::from(shard_info.version));
+        datums.push(Datum::from(format!("{:?}", shard_info.status).as_str()));
+        match table {
+            Some(table) => {
+                datums.push(Datum::from(table.id));
+                datums.push(Datum::from(table.schema_name.as_str()));
+                datums.push(Datum::from(table.name.as_str()));
+            }
+            None => {
+                datums.push(Datum::Null);
+                datums.push(Datum::Null);
+                datums.push(Datum::Null);
+            }
+        }
+        Row::from_datums(datums)
+    }
+}
+
+#[async_trait]
+impl SystemTable for Shards {
+    fn name(&self) -> &str {
+        SHARDS_TABLE_NAME
+    }
+
+    fn id(&self) -> TableId {
+        SHARDS_TABLE_ID
+    }
+
+    fn schema(&self) -> Schema {
+        self.schema.clone()
+    }
+
+    async fn read(
+        &self,
+        request: ReadRequest,
+    ) -> table_engine::table::Result<SendableRecordBatchStream> {
+        let mut rows = Vec::new();
+        if let Some(cluster) = &self.cluster {
+            for shard_info in cluster.list_shards() {
+                let tables = cluster
+                    .shard(shard_info.id)
+                    .map(|shard| shard.tables())
+                    .unwrap_or_default();
+                if tables.is_empty() {
+                    rows.push(self.build_row(&shard_info, None));
+                }
+                for table in &tables {
+                    rows.push(self.build_row(&shard_info, Some(table)));
+                }
+            }
+        }
+
+        build_one_record_batch_stream(self.name(), &self.schema, request, rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use meta_client::types::{ShardRole, ShardStatus};
+
+    use super::*;
+
+    fn build_shard_info() -> ShardInfo {
+        ShardInfo {
+            id: 3,
+            role: ShardRole::Leader,
+            version: 9,
+            status: ShardStatus::Ready,
+        }
+    }
+
+    #[test]
+    fn test_build_row_with_table() {
+        let shards = Shards::new(None);
+        let table = TableInfo {
+            id: 100,
+            name: "test_table".to_string(),
+            schema_id: 1,
+            schema_name: "test_schema".to_string(),
+            partition_info: None,
+        };
+
+        let row = shards.build_row(&build_shard_info(), Some(&table));
+        assert_eq!(shards.schema.num_columns(), row.num_columns());
+        let expected = vec![
+            Datum::Timestamp(ENTRY_TIMESTAMP),
+            Datum::UInt32(3),
+            Datum::from("Leader"),
+            Datum::UInt64(9),
+            Datum::from("Ready"),
+            Datum::UInt64(100),
+            Datum::from("test_schema"),
+            Datum::from("test_table"),
+        ];
+        assert_eq!(Row::from_datums(expected), row);
+    }
+
+    #[test]
+    fn test_build_row_without_table() {
+        let shards = Shards::new(None);
+
+        let row = shards.build_row(&build_shard_info(), None);
+        let expected = vec![
+            Datum::Timestamp(ENTRY_TIMESTAMP),
+            Datum::UInt32(3),
+            Datum::from("Leader"),
+            Datum::UInt64(9),
+            Datum::from("Ready"),
+            Datum::Null,
+            Datum::Null,
+            Datum::Null,
+        ];
+        assert_eq!(Row::from_datums(expected), row);
+    }
+}
@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! implementation of system table: SstFiles
+//! For example `SELECT * FROM system.public.sst_files`
+use std::fmt::{Debug, Formatter};
+
+use async_trait::async_trait;
+use catalog::{manager::ManagerRef, schema::SchemaRef, CatalogRef};
+use common_types::{
+    datum::{Datum, DatumKind},
+    row::Row,
+    schema,
+    schema::Schema,
+};
+use table_engine::{
+    stream::SendableRecordBatchStream,
+    table::{ReadRequest, SstInfo, TableId, TableRef},
+};
+
+use crate::{
+    build_column, build_one_record_batch_stream, tables::ENTRY_TIMESTAMP, visit_all_tables,
+    SystemTable, SST_FILES_TABLE_ID, SST_FILES_TABLE_NAME,
+};
+
+/// Build a new table schema for sst files
+fn sst_files_schema() -> Schema {
+    schema::Builder::with_capacity(13)
+        .auto_increment_column_id(true)
+        .add_key_column(build_column("timestamp", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_key_column(build_column("catalog", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("schema", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("table_name", DatumKind::String, false))
+        .unwrap()
+        .add_key_column(build_column("file_id", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("table_id", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("level", DatumKind::UInt16, false))
+        .unwrap()
+        .add_normal_column(build_column("size", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("row_num", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("start_time", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_normal_column(build_column("end_time", DatumKind::Timestamp, false))
+        .unwrap()
+        .add_normal_column(build_column("max_sequence", DatumKind::UInt64, false))
+        .unwrap()
+        .add_normal_column(build_column("being_compacted", DatumKind::Boolean, false))
+        .unwrap()
+        .primary_key_indexes(vec![0, 1, 2, 3, 4])
+        .build()
+        .unwrap()
+}
+
+/// Sst files of all the tables opened on this node.
+pub struct SstFiles {
+    schema: Schema,
+    catalog_manager: ManagerRef,
+}
+
+impl Debug for SstFiles {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SysSstFiles")
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+impl SstFiles {
+    pub fn new(catalog_manager: ManagerRef) -> Self {
+        Self {
+            schema: sst_files_schema(),
+            catalog_manager,
+        }
+    }
+
+    fn build_row(
+        &self,
+        catalog: &CatalogRef,
+        schema: &SchemaRef,
+        table: &TableRef,
+        sst: &SstInfo,
+    ) -> Row {
+        let mut datums = Vec::with_capacity(self.schema.num_columns());
+        datums.push(Datum::Timestamp(ENTRY_TIMESTAMP));
+        datums.push(Datum::from(catalog.name()));
+        datums.push(Datum::from(schema.name()));
+        datums.push(Datum::from(table.name()));
+        datums.push(Datum::from(sst.file_id));
+        datums.push(Datum::from(table.id().as_u64()));
+        datums.push(Datum::UInt16(sst.level));
+        datums.push(Datum::from(sst.size));
+        datums.push(Datum::from(sst.row_num));
+        datums.push(Datum::Timestamp(sst.time_range.inclusive_start()));
+        datums.push(Datum::Timestamp(sst.time_range.exclusive_end()));
+        datums.push(Datum::from(sst.max_sequence));
+        datums.push(Datum::Boolean(sst.being_compacted));
+        Row::from_datums(datums)
+    }
+}
+
+#[async_trait]
+impl SystemTable for SstFiles {
+    fn name(&self) -> &str {
+        SST_FILES_TABLE_NAME
+    }
+
+    fn id(&self) -> TableId {
+        SST_FILES_TABLE_ID
+    }
+
+    fn schema(&self) -> Schema {
+        self.schema.clone()
+    }
+
+    async fn read(
+        &self,
+        request: ReadRequest,
+    ) -> table_engine::table::Result<SendableRecordBatchStream> {
+        let mut rows = Vec::new();
+        visit_all_tables(
+            &self.catalog_manager,
+            self.name(),
+            |catalog, schema, table| {
+                if let Some(storage_info) = table.storage_info() {
+                    for sst in &storage_info.ssts {
+                        rows.push(self.build_row(catalog, schema, table, sst));
+                    }
+                }
+            },
+        )?;
+
+        build_one_record_batch_stream(self.name(), &self.schema, request, rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_types::time::{TimeRange, Timestamp};
+
+    use super::*;
+    use crate::tests::{build_test_table, TEST_CATALOG, TEST_SCHEMA, TEST_TABLE, TEST_TABLE_ID};
+
+    #[test]
+    fn test_build_row() {
+        let (manager, catalog, schema, table) = build_test_table();
+        let sst_files = SstFiles::new(manager);
+        let sst = SstInfo {
+            level: 1,
+            file_id: 7,
+            size: 1024,
+            row_num: 10,
+            time_range: TimeRange::new_unchecked_for_test(1000, 2000),
+            max_sequence: 42,
+            being_compacted: true,
+        };
+
+        let row = sst_files.build_row(&catalog, &schema, &table, &sst);
+        assert_eq!(sst_files.schema.num_columns(), row.num_columns());
+        let expected = vec![
+            Datum::Timestamp(ENTRY_TIMESTAMP),
+            Datum::from(TEST_CATALOG),
+            Datum::from(TEST_SCHEMA),
+            Datum::from(TEST_TABLE),
+            Datum::UInt64(7),
+            Datum::UInt64(TEST_TABLE_ID),
+            Datum::UInt16(1),
+            Datum::UInt64(1024),
+            Datum::UInt64(10),
+            Datum::Timestamp(Timestamp::new(1000)),
+            Datum::Timestamp(Timestamp::new(2000)),
+            Datum::UInt64(42),
+            Datum::Boolean(true),
+        ];
+        assert_eq!(Row::from_datums(expected), row);
+    }
+}
@@ -24,21 +24,20 @@ use catalog::{manager::ManagerRef, schema::SchemaRef, CatalogRef};
 use common_types::{
     column_schema,
     datum::{Datum, DatumKind},
-    projected_schema::RowProjector,
-    record_batch::FetchedRecordBatchBuilder,
     row::Row,
     schema,
     schema::Schema,
     time::Timestamp,
 };
-use generic_error::BoxError;
-use snafu::ResultExt;
 use table_engine::{
     stream::SendableRecordBatchStream,
     table::{ReadRequest, TableId, TableRef},
 };
 
-use crate::{OneRecordBatchStream, SystemTable, TABLES_TABLE_ID, TABLES_TABLE_NAME};
+use crate::{
+    build_one_record_batch_stream, visit_all_tables, SystemTable, TABLES_TABLE_ID,
+    TABLES_TABLE_NAME,
+};
 
 /// Timestamp of entry
 pub const ENTRY_TIMESTAMP: Timestamp = Timestamp::new(0);
@@ -152,51 +151,15 @@ impl SystemTable for Tables {
         &self,
         request: ReadRequest,
     ) -> table_engine::table::Result<SendableRecordBatchStream> {
-        let catalogs = self
-            .catalog_manager
-            .all_catalogs()
-            .box_err()
-            .context(table_engine::table::Scan { table: self.name() })?;
-        let fetched_schema = request.projected_schema.to_record_schema_with_key();
-        let primary_key_indexes = fetched_schema.primary_key_idx().to_vec();
-        let fetched_schema = fetched_schema.to_record_schema();
-        let mut builder = FetchedRecordBatchBuilder::new(
-            fetched_schema.clone(),
-            Some(primary_key_indexes.clone()),
-        );
+        let mut rows = Vec::new();
+        visit_all_tables(
+            &self.catalog_manager,
+            self.name(),
+            |catalog, schema, table| {
+                rows.push(self.from_table(catalog.clone(), schema.clone(), table.clone()));
+            },
+        )?;
 
-        let table_schema = request.projected_schema.table_schema();
-        let row_projector = RowProjector::new(
-            &fetched_schema,
-            Some(primary_key_indexes),
-            table_schema,
-            &self.schema,
-        )
-        .expect("Should succeed to try_project_key of sys_tables");
-        for catalog in &catalogs {
-            for schema in &catalog
-                .all_schemas()
-                .box_err()
-                .context(table_engine::table::Scan { table: self.name() })?
-            {
-                for table in &schema
-                    .all_tables()
-                    .box_err()
-                    .context(table_engine::table::Scan { table: self.name() })?
-                {
-                    let row = self.from_table(catalog.clone(), schema.clone(), table.clone());
-                    let projected_row = row_projector.project_row(&row, Vec::new());
-                    builder
-                        .append_row(projected_row)
-                        .box_err()
-                        .context(table_engine::table::Scan { table: self.name() })?;
-                }
-            }
-        }
-        let record_batch = builder.build().unwrap().into_record_batch();
-        Ok(Box::pin(OneRecordBatchStream {
-            schema: self.schema.clone().to_record_schema(),
-            record_batch: Some(record_batch),
-        }))
+        build_one_record_batch_stream(self.name(), &self.schema, request, rows)
     }
 }
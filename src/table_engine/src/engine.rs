@@ -95,6 +95,21 @@ pub enum Error {
         msg: Option<String>,
         source: GenericError,
     },
+
+    #[snafu(display(
+        "Unsupported engine method, engine:{}, method:{}.\nBacktrace:\n{}",
+        engine,
+        method,
+        backtrace
+    ))]
+    UnsupportedMethod {
+        engine: String,
+        method: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to read table backup, path:{}, err:{}", path, source))]
+    ReadTableBackup { path: String, source: GenericError },
 }
 
 define_result!(Error);
@@ -283,6 +298,15 @@ pub struct CloseTableRequest {
     pub engine: String,
 }
 
+/// Meta data of a table backup, which is enough to recreate the table.
+#[derive(Debug, Clone)]
+pub struct TableBackupMeta {
+    /// Name of the table when it was backed up
+    pub table_name: String,
+    pub table_schema: Schema,
+    pub table_options: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct OpenShardRequest {
     /// Shard id
@@ -355,6 +379,15 @@ pub trait TableEngine: Send + Sync {
     async fn report_statistics(&self) -> Result<Option<TableEngineStats>> {
         Ok(None)
     }
+
+    /// Read the meta data of the table backup at the `path`.
+    async fn read_table_backup(&self, _path: &str) -> Result<TableBackupMeta> {
+        UnsupportedMethod {
+            engine: self.engine_type(),
+            method: "read_table_backup",
+        }
+        .fail()
+    }
 }
 
 pub type OpenShardResult = HashMap<TableId, GenericResult<Option<TableRef>>>;
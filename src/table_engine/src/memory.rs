@@ -28,7 +28,8 @@ use std::{
 use async_trait::async_trait;
 use common_types::{
     column_block::{ColumnBlock, ColumnBlockBuilder},
-    datum::{Datum, DatumKind},
+    column_schema::ColumnSchema,
+    datum::Datum,
     record_batch::RecordBatch,
     row::{Row, RowGroup},
     schema::{RecordSchema, Schema},
@@ -256,7 +257,7 @@ fn row_group_to_record_batch(
                 ),
             })?;
         let cols = rows.iter_column(col_index);
-        let column_block = build_column_block(&column.data_type, cols, column.is_dictionary)?;
+        let column_block = build_column_block(column, cols)?;
         column_blocks.push(column_block);
     }
 
@@ -268,12 +269,10 @@ fn row_group_to_record_batch(
 }
 
 fn build_column_block<'a, I: Iterator<Item = &'a Datum>>(
-    data_type: &DatumKind,
+    column_schema: &ColumnSchema,
     iter: I,
-    is_dictionary: bool,
 ) -> stream::Result<ColumnBlock> {
-    let mut builder =
-        ColumnBlockBuilder::with_capacity(data_type, iter.size_hint().0, is_dictionary);
+    let mut builder = ColumnBlockBuilder::with_column_schema(column_schema, iter.size_hint().0);
     for datum in iter {
         builder
             .append(datum.clone())
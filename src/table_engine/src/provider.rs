@@ -48,7 +48,7 @@ use trace_metric::{collector::FormatCollectorVisitor, MetricsCollector};
 use crate::{
     predicate::{PredicateBuilder, PredicateRef},
     stream::{ScanStreamState, ToDfStream},
-    table::{ReadOptions, ReadRequest, TableRef},
+    table::{ReadAsOf, ReadOptions, ReadRequest, TableRef},
 };
 
 pub const SCAN_TABLE_METRICS_COLLECTOR_NAME: &str = "scan_table";
@@ -184,6 +184,9 @@ pub struct TableProviderAdapter<B> {
 
     /// Table scan builder
     builder: B,
+
+    /// Read the table at a historical point, see [ReadAsOf].
+    as_of: Option<ReadAsOf>,
 }
 
 impl<B: TableScanBuilder> TableProviderAdapter<B> {
@@ -195,9 +198,16 @@ impl<B: TableScanBuilder> TableProviderAdapter<B> {
             table,
             current_table_schema,
             builder,
+            as_of: None,
         }
     }
 
+    /// Make the scans of this table read at the historical point.
+    pub fn with_as_of(mut self, as_of: Option<ReadAsOf>) -> Self {
+        self.as_of = as_of;
+        self
+    }
+
     pub fn as_table_ref(&self) -> &TableRef {
         &self.table
     }
@@ -244,6 +254,7 @@ impl<B: TableScanBuilder> TableProviderAdapter<B> {
             deadline,
             read_parallelism,
             batch_size: state.config_options().execution.batch_size,
+            as_of: self.as_of,
         };
 
         // TODO: metrics collector name should relate to detail scan impl?
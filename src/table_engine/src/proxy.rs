@@ -22,8 +22,8 @@ use async_trait::async_trait;
 use crate::{
     engine::{
         CloseShardRequest, CloseTableRequest, CreateTableParams, CreateTableRequest,
-        DropTableRequest, OpenShardRequest, OpenShardResult, OpenTableRequest, TableEngine,
-        TableEngineRef, UnknownEngineType,
+        DropTableRequest, OpenShardRequest, OpenShardResult, OpenTableRequest, TableBackupMeta,
+        TableEngine, TableEngineRef, UnknownEngineType,
     },
     memory::MemoryTableEngine,
     table::TableRef,
@@ -115,4 +115,9 @@ impl TableEngine for TableEngineProxy {
             engine_type => vec![UnknownEngineType { engine_type }.fail()],
         }
     }
+
+    /// Only the analytic engine supports backup.
+    async fn read_table_backup(&self, path: &str) -> crate::engine::Result<TableBackupMeta> {
+        self.analytic.read_table_backup(path).await
+    }
 }
@@ -407,6 +407,18 @@ pub struct TableInfo {
     pub partition_info: Option<PartitionInfo>,
 }
 
+/// Metrics of the physical plan executed by the remote engine, collected for
+/// `explain analyze`
+#[derive(Clone, Debug)]
+pub struct RemoteMetrics {
+    /// Endpoint of the remote engine
+    pub endpoint: String,
+    /// Formatted metrics of the executed plan
+    pub metrics: String,
+}
+
+pub type RemoteMetricsRef = Arc<Mutex<Option<RemoteMetrics>>>;
+
 /// Request for remote executing physical plan
 pub struct ExecutePlanRequest {
     /// Schema of the encoded physical plan
@@ -416,7 +428,7 @@ pub struct ExecutePlanRequest {
     pub remote_request: RemoteExecuteRequest,
 
     /// Collect metrics of remote plan
-    pub remote_metrics: Arc<Mutex<Option<String>>>,
+    pub remote_metrics: RemoteMetricsRef,
 }
 
 impl ExecutePlanRequest {
@@ -425,7 +437,7 @@ impl ExecutePlanRequest {
         plan_schema: RecordSchema,
         context: ExecContext,
         physical_plan: PhysicalPlan,
-        remote_metrics: Arc<Mutex<Option<String>>>,
+        remote_metrics: RemoteMetricsRef,
     ) -> Self {
         let remote_request = RemoteExecuteRequest {
             table,
@@ -35,6 +35,8 @@ use common_types::{
     request_id::RequestId,
     row::{Row, RowGroup},
     schema::{RecordSchemaWithKey, Schema, Version},
+    time::{TimeRange, Timestamp},
+    SequenceNumber,
 };
 use generic_error::{BoxError, GenericError};
 use horaedbproto::sys_catalog as sys_catalog_pb;
@@ -122,12 +124,31 @@ pub enum Error {
     #[snafu(display("Failed to compact table, table:{}, err:{}", table, source))]
     Compact { table: String, source: GenericError },
 
+    #[snafu(display("Failed to backup table, table:{}, err:{}", table, source))]
+    Backup { table: String, source: GenericError },
+
+    #[snafu(display("Failed to restore table, table:{}, err:{}", table, source))]
+    Restore { table: String, source: GenericError },
+
+    #[snafu(display("Failed to verify table, table:{}, err:{}", table, source))]
+    Verify { table: String, source: GenericError },
+
     #[snafu(display("Failed to convert read request to pb, msg:{}, err:{}", msg, source))]
     ReadRequestToPb { msg: String, source: GenericError },
 
     #[snafu(display("Empty read options.\nBacktrace:\n{}", backtrace))]
     EmptyReadOptions { backtrace: Backtrace },
 
+    #[snafu(display(
+        "Time-travel read can't be sent to remote, as_of:{:?}.\nBacktrace:\n{}",
+        as_of,
+        backtrace
+    ))]
+    RemoteTimeTravel {
+        as_of: ReadAsOf,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Empty projected schema.\nBacktrace:\n{}", backtrace))]
     EmptyProjectedSchema { backtrace: Backtrace },
 
@@ -327,6 +348,8 @@ pub struct ReadOptions {
     pub read_parallelism: usize,
     /// Request deadline
     pub deadline: Option<Instant>,
+    /// Read the table at a historical point instead of the latest data.
+    pub as_of: Option<ReadAsOf>,
 }
 
 impl Default for ReadOptions {
@@ -335,10 +358,20 @@ impl Default for ReadOptions {
             batch_size: 10000,
             read_parallelism: DEFAULT_READ_PARALLELISM,
             deadline: None,
+            as_of: None,
         }
     }
 }
 
+/// The historical point a time-travel read sees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadAsOf {
+    /// Only the data written at or before the sequence is visible.
+    Sequence(SequenceNumber),
+    /// Only the data written at or before the timestamp is visible.
+    Timestamp(Timestamp),
+}
+
 impl From<horaedbproto::remote_engine::ReadOptions> for ReadOptions {
     fn from(pb: horaedbproto::remote_engine::ReadOptions) -> Self {
         Self {
@@ -349,6 +382,8 @@ impl From<horaedbproto::remote_engine::ReadOptions> for ReadOptions {
             } else {
                 Some(Instant::now() + Duration::from_millis(pb.timeout_ms as u64))
             },
+            // Time-travel read is not supported by remote engine.
+            as_of: None,
         }
     }
 }
@@ -429,6 +464,10 @@ impl TryFrom<ReadRequest> for horaedbproto::remote_engine::TableReadRequest {
     type Error = Error;
 
     fn try_from(request: ReadRequest) -> std::result::Result<Self, Error> {
+        if let Some(as_of) = request.opts.as_of {
+            return RemoteTimeTravel { as_of }.fail();
+        }
+
         let predicate_pb =
             request
                 .predicate
@@ -502,6 +541,74 @@ impl Default for FlushRequest {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct BackupRequest {
+    /// Location to store the backup, an absolute path is treated as a local
+    /// directory, otherwise it is a path in the object store of the engine.
+    pub path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreRequest {
+    /// Location of the backup, see [BackupRequest::path].
+    pub path: String,
+}
+
+/// Summary of the ssts copied by backup or restore.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackupStats {
+    pub num_ssts: usize,
+    /// Total size of the ssts in bytes
+    pub total_size: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifyRequest {
+    /// Remove the orphan objects and rebuild the broken meta data of ssts.
+    pub repair: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SstIssueKind {
+    /// The sst referenced by the table is not found in the store.
+    Missing,
+    /// The sst or its meta data can't be decoded.
+    Corrupted,
+    /// The sst doesn't match the meta data recorded by the table.
+    Mismatched,
+    /// The object in the table directory is not referenced by the table.
+    Orphan,
+}
+
+impl SstIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SstIssueKind::Missing => "missing",
+            SstIssueKind::Corrupted => "corrupted",
+            SstIssueKind::Mismatched => "mismatched",
+            SstIssueKind::Orphan => "orphan",
+        }
+    }
+}
+
+/// An issue found by verifying the ssts of a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SstIssue {
+    pub kind: SstIssueKind,
+    /// Path of the object in the store
+    pub path: String,
+    pub msg: String,
+    /// Whether the issue is repaired
+    pub repaired: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    /// Number of the ssts verified
+    pub num_ssts: usize,
+    pub issues: Vec<SstIssue>,
+}
+
 /// Table abstraction
 ///
 /// We do not let Table trait extends datafusion's TableProvider, since
@@ -531,6 +638,13 @@ pub trait Table: std::fmt::Debug {
     /// Get table's statistics.
     fn stats(&self) -> TableStats;
 
+    /// Get details about the storage of this table, e.g. ssts and memtables.
+    ///
+    /// Returns `None` if the table has no such details.
+    fn storage_info(&self) -> Option<TableStorageInfo> {
+        None
+    }
+
     /// Whether the columns used in filter expr can be pushdown.
     ///
     /// `read_schema` is used here to avoid upper layer see different schema
@@ -565,6 +679,36 @@ pub trait Table: std::fmt::Debug {
 
     /// Compact this table and wait until compaction completes.
     async fn compact(&self) -> Result<()>;
+
+    /// Flush this table and copy its ssts and meta data to the location
+    /// specified in [BackupRequest].
+    async fn backup(&self, _request: BackupRequest) -> Result<BackupStats> {
+        UnsupportedMethod {
+            table: self.name(),
+            method: "backup",
+        }
+        .fail()
+    }
+
+    /// Load the ssts of the backup specified in [RestoreRequest] into this
+    /// table, which is expected to be newly created from the same backup.
+    async fn restore(&self, _request: RestoreRequest) -> Result<BackupStats> {
+        UnsupportedMethod {
+            table: self.name(),
+            method: "restore",
+        }
+        .fail()
+    }
+
+    /// Verify the ssts referenced by this table and the objects in its
+    /// directory, and repair the issues found if required.
+    async fn verify(&self, _request: VerifyRequest) -> Result<VerifyReport> {
+        UnsupportedMethod {
+            table: self.name(),
+            method: "verify",
+        }
+        .fail()
+    }
 }
 
 /// Basic statistics of table.
@@ -578,6 +722,69 @@ pub struct TableStats {
     pub num_flush: u64,
 }
 
+/// Details about the storage of table.
+#[derive(Debug, Clone, Default)]
+pub struct TableStorageInfo {
+    /// Ssts of all levels
+    pub ssts: Vec<SstInfo>,
+    /// Memtables not flushed yet
+    pub memtables: Vec<MemTableInfo>,
+    /// Last sequence visible to the reads
+    pub last_sequence: SequenceNumber,
+    /// All the data with sequence <= `flushed_sequence` is flushed
+    pub flushed_sequence: SequenceNumber,
+    /// Last flush time in milliseconds
+    pub last_flush_time_ms: u64,
+    /// Whether compaction of the table is waiting to be scheduled
+    pub compaction_pending: bool,
+}
+
+/// Meta data of a sst file.
+#[derive(Debug, Clone)]
+pub struct SstInfo {
+    pub level: u16,
+    pub file_id: u64,
+    /// File size in bytes
+    pub size: u64,
+    pub row_num: u64,
+    pub time_range: TimeRange,
+    pub max_sequence: SequenceNumber,
+    /// Whether the file is an input of the running compaction
+    pub being_compacted: bool,
+}
+
+/// Status of memtable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemTableStatus {
+    /// Memtable for sampling the segment duration
+    Sampling,
+    /// Memtable accepting writes
+    Mutable,
+    /// Memtable waiting to be flushed
+    Immutable,
+}
+
+impl MemTableStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemTableStatus::Sampling => "sampling",
+            MemTableStatus::Mutable => "mutable",
+            MemTableStatus::Immutable => "immutable",
+        }
+    }
+}
+
+/// Meta data of a memtable.
+#[derive(Debug, Clone)]
+pub struct MemTableInfo {
+    pub id: u64,
+    pub status: MemTableStatus,
+    /// Approximate memory usage in bytes
+    pub memory_usage: u64,
+    pub time_range: TimeRange,
+    pub last_sequence: SequenceNumber,
+}
+
 /// A reference-counted pointer to Table
 pub type TableRef = Arc<dyn Table + Send + Sync>;
 
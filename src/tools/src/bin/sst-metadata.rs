@@ -292,9 +292,9 @@ async fn parse_metadata(
             ObjectStoreReader::new(storage.clone(), path.clone(), Arc::new(parquet_metadata));
         let parquet_metadata =
             parquet_ext::meta_data::meta_with_page_indexes(object_store_reader).await?;
-        MetaData::try_new(&parquet_metadata, false, storage).await?
+        MetaData::try_new(&parquet_metadata, false, &path, storage).await?
     } else {
-        MetaData::try_new(&parquet_metadata, false, storage).await?
+        MetaData::try_new(&parquet_metadata, false, &path, storage).await?
     };
 
     Ok((md, metadata_size, kv_size))
@@ -47,6 +47,14 @@ impl WalManager for DoNothing {
         Ok(())
     }
 
+    async fn advance_sequence_num(
+        &self,
+        _location: WalLocation,
+        _sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     async fn close_region(&self, _region: RegionId) -> Result<()> {
         Ok(())
     }
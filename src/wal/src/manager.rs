@@ -334,6 +334,14 @@ pub trait WalManager: Send + Sync + fmt::Debug + 'static {
         sequence_num: SequenceNumber,
     ) -> Result<()>;
 
+    /// Make sure the sequence numbers allocated afterwards are all greater than
+    /// `sequence_num`.
+    async fn advance_sequence_num(
+        &self,
+        location: WalLocation,
+        sequence_num: SequenceNumber,
+    ) -> Result<()>;
+
     /// Close a region.
     async fn close_region(&self, region: RegionId) -> Result<()>;
 
@@ -77,6 +77,21 @@ impl<M: MessageQueue> WalManager for MessageQueueImpl<M> {
             .context(Delete)
     }
 
+    async fn advance_sequence_num(
+        &self,
+        location: WalLocation,
+        sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        // The sequence numbers are mapped to the offsets of the message queue,
+        // which can't be moved forward arbitrarily.
+        Unknown {
+            msg: format!(
+                "advancing sequence is not supported by message queue wal, location:{location:?}, sequence_num:{sequence_num}"
+            ),
+        }
+        .fail()
+    }
+
     async fn close_region(&self, region_id: RegionId) -> Result<()> {
         self.0
             .close_region(region_id)
@@ -152,16 +152,7 @@ impl TableUnit {
                 .context(Delete)?;
 
             // Update the max sequence number.
-            let meta_key = MetaKey { table_id: self.id };
-            let meta_value = MaxSeqMetaValue { max_seq };
-            let (mut meta_key_buf, mut meta_value_buf) = (BytesMut::new(), BytesMut::new());
-            self.max_seq_meta_encoding
-                .encode_key(&mut meta_key_buf, &meta_key)?;
-            self.max_seq_meta_encoding
-                .encode_value(&mut meta_value_buf, &meta_value)?;
-            wb.put(&meta_key_buf, &meta_value_buf)
-                .map_err(|e| e.into())
-                .context(Delete)?;
+            self.put_max_seq_meta(&wb, max_seq)?;
 
             wb
         };
@@ -174,6 +165,41 @@ impl TableUnit {
             .context(Delete)?
     }
 
+    /// Make the sequence numbers allocated later greater than `sequence_num`,
+    /// which is persisted as the max sequence number of the table unit.
+    async fn advance_sequence_num(&self, sequence_num: SequenceNumber) -> Result<()> {
+        let _delete_guard = self.delete_lock.lock().await;
+        if self.sequence_num()? >= sequence_num {
+            return Ok(());
+        }
+
+        let wb = WriteBatch::default();
+        self.put_max_seq_meta(&wb, sequence_num)?;
+        let db = self.db.clone();
+        self.runtime
+            .spawn_blocking(move || db.write(&wb).map_err(|e| e.into()).context(Write))
+            .await
+            .box_err()
+            .context(Write)??;
+        self.next_sequence_num
+            .fetch_max(sequence_num + 1, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    fn put_max_seq_meta(&self, wb: &WriteBatch, max_seq: SequenceNumber) -> Result<()> {
+        let meta_key = MetaKey { table_id: self.id };
+        let meta_value = MaxSeqMetaValue { max_seq };
+        let (mut meta_key_buf, mut meta_value_buf) = (BytesMut::new(), BytesMut::new());
+        self.max_seq_meta_encoding
+            .encode_key(&mut meta_key_buf, &meta_key)?;
+        self.max_seq_meta_encoding
+            .encode_value(&mut meta_value_buf, &meta_value)?;
+        wb.put(&meta_key_buf, &meta_value_buf)
+            .map_err(|e| e.into())
+            .context(Write)
+    }
+
     fn read(&self, ctx: &ReadContext, req: &ReadRequest) -> Result<RocksLogIterator> {
         debug!("Wal table unit begin reading, ctx:{:?}, req:{:?}", ctx, req);
 
@@ -877,6 +903,16 @@ impl WalManager for RocksImpl {
         Ok(())
     }
 
+    async fn advance_sequence_num(
+        &self,
+        location: WalLocation,
+        sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        self.get_or_create_table_unit(location)
+            .advance_sequence_num(sequence_num)
+            .await
+    }
+
     async fn close_region(&self, region_id: RegionId) -> Result<()> {
         debug!(
             "Close region for RocksDB based WAL is noop operation, region_id:{}",
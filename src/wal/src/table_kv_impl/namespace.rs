@@ -209,6 +209,18 @@ pub enum Error {
         source: crate::table_kv_impl::table_unit::Error,
     },
 
+    #[snafu(display(
+        "Failed to advance sequence, namespace:{}, wal location:{:?}, err:{}",
+        namespace,
+        location,
+        source
+    ))]
+    AdvanceSequence {
+        namespace: String,
+        location: WalLocation,
+        source: crate::table_kv_impl::table_unit::Error,
+    },
+
     #[snafu(display("Failed to stop task, namespace:{}, err:{}", namespace, source))]
     StopTask {
         namespace: String,
@@ -745,6 +757,23 @@ impl<T: TableKv> NamespaceInner<T> {
         Ok(())
     }
 
+    async fn advance_sequence_num(
+        &self,
+        location: WalLocation,
+        sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        let table_unit = self.get_or_create_table_unit(location).await?;
+        let table_unit_meta_table = self.table_unit_meta_table(location.table_id);
+
+        table_unit
+            .advance_sequence_num(&self.table_kv, table_unit_meta_table, sequence_num)
+            .await
+            .context(AdvanceSequence {
+                namespace: self.name(),
+                location,
+            })
+    }
+
     pub async fn scan_log(
         &self,
         ctx: &ScanContext,
@@ -1211,6 +1240,18 @@ impl<T: TableKv> Namespace<T> {
         self.inner.delete_entries(location, sequence_num).await
     }
 
+    /// Make the sequences allocated later by the table unit identified by
+    /// `location` greater than `sequence_num`.
+    pub async fn advance_sequence_num(
+        &self,
+        location: WalLocation,
+        sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        self.inner
+            .advance_sequence_num(location, sequence_num)
+            .await
+    }
+
     /// Scan logs of a whole region from this namespace.
     // TODO: maybe we should filter the log marked deleted,
     // but there isn't any actual benefit such as reducing network IO,
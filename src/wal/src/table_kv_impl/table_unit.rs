@@ -416,6 +416,26 @@ impl TableUnit {
             .await
     }
 
+    /// Advance the last sequence to `sequence_num`, so the sequences allocated
+    /// later are all greater than it.
+    pub async fn advance_sequence_num<T: TableKv>(
+        &self,
+        table_kv: &T,
+        table_unit_meta_table: &str,
+        sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        let mut writer = self.writer.lock().await;
+        writer
+            .advance_sequence_num(
+                &self.runtimes.write_runtime,
+                table_kv,
+                &self.state,
+                table_unit_meta_table,
+                sequence_num,
+            )
+            .await
+    }
+
     #[inline]
     pub fn table_id(&self) -> TableId {
         self.state.table_id
@@ -1030,4 +1050,44 @@ impl TableUnitWriter {
 
         Ok(())
     }
+
+    async fn advance_sequence_num<T: TableKv>(
+        &mut self,
+        runtime: &Runtime,
+        table_kv: &T,
+        table_unit_state: &TableUnitState,
+        table_unit_meta_table: &str,
+        sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        if table_unit_state.last_sequence() >= sequence_num {
+            return Ok(());
+        }
+
+        // The last sequence is recovered from the start sequence if no newer logs
+        // are found, so persisting the start sequence is enough. The logs before
+        // `sequence_num` are all deleted by the way.
+        let mut table_unit_entry = table_unit_state.table_unit_entry();
+        table_unit_entry.start_sequence = sequence_num + 1;
+
+        debug!(
+            "Update table unit entry due to advancing sequence, table_unit_entry:{:?}, meta table:{}",
+            table_unit_entry, table_unit_meta_table
+        );
+
+        let table_kv = table_kv.clone();
+        let table_unit_meta_table = table_unit_meta_table.to_string();
+        runtime
+            .spawn_blocking(move || {
+                Self::update_table_unit_entry(&table_kv, &table_unit_meta_table, &table_unit_entry)
+            })
+            .await
+            .context(RuntimeExec)??;
+
+        table_unit_state.set_start_sequence(table_unit_entry.start_sequence);
+        table_unit_state
+            .last_sequence
+            .fetch_max(sequence_num, Ordering::Relaxed);
+
+        Ok(())
+    }
 }
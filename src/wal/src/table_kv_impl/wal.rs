@@ -135,6 +135,18 @@ impl<T: TableKv> WalManager for WalNamespaceImpl<T> {
             .context(Delete)
     }
 
+    async fn advance_sequence_num(
+        &self,
+        location: WalLocation,
+        sequence_num: SequenceNumber,
+    ) -> Result<()> {
+        self.namespace
+            .advance_sequence_num(location, sequence_num)
+            .await
+            .box_err()
+            .context(Write)
+    }
+
     async fn close_region(&self, region_id: RegionId) -> Result<()> {
         self.namespace
             .close_region(region_id)